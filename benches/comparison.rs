@@ -169,7 +169,7 @@ fn performance_analysis() {
 
     // Show sample results
     println!("\nSample translations:");
-    for (_i, &text) in TELUGU_SAMPLES.iter().take(5).enumerate() {
+    for &text in TELUGU_SAMPLES.iter().take(5) {
         let result = transliterator
             .transliterate(text, "telugu", "slp1")
             .unwrap();
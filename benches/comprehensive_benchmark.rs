@@ -1,6 +1,7 @@
-use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use shlesha::Shlesha;
 use std::fs;
+use std::hint::black_box;
 use std::time::Duration;
 
 // Test data sets
@@ -146,6 +147,7 @@ fn write_benchmark_results(category: &str, results: &[BenchmarkResult]) {
     let _ = fs::write(&filename, csv_content);
 }
 
+#[allow(dead_code)]
 fn generate_markdown_report() {
     let categories = [
         "hub",
@@ -0,0 +1,49 @@
+//! Benchmark for `transliterate_cow` against `transliterate` on mixed lines
+//!
+//! Converts a corpus line by line, where half the lines are ASCII (numbers,
+//! references, punctuation) that Devanagari reads nothing from. `transliterate_cow`
+//! borrows those lines instead of converting and copying them.
+//!
+//! Run with `cargo bench --bench cow_benchmark`.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use shlesha::Shlesha;
+use std::hint::black_box;
+
+const LINES: &[&str] = &[
+    "धर्मक्षेत्रे कुरुक्षेत्रे समवेता युयुत्सवः ।",
+    "1.1 (p. 42)",
+    "मामकाः पाण्डवाश्चैव किमकुर्वत सञ्जय ॥",
+    "-- 2, 3, 5, 7, 11 --",
+];
+
+fn benchmark_cow(c: &mut Criterion) {
+    let shlesha = Shlesha::new();
+    let corpus: Vec<&str> = LINES.iter().copied().cycle().take(1000).collect();
+    let bytes: usize = corpus.iter().map(|line| line.len()).sum();
+
+    let mut group = c.benchmark_group("devanagari_to_iast_lines");
+    group.throughput(Throughput::Bytes(bytes as u64));
+    group.bench_function("transliterate", |b| {
+        b.iter(|| {
+            for line in &corpus {
+                black_box(shlesha.transliterate(line, "devanagari", "iast").unwrap());
+            }
+        })
+    });
+    group.bench_function("transliterate_cow", |b| {
+        b.iter(|| {
+            for line in &corpus {
+                black_box(
+                    shlesha
+                        .transliterate_cow(line, "devanagari", "iast")
+                        .unwrap(),
+                );
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_cow);
+criterion_main!(benches);
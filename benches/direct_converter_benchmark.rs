@@ -0,0 +1,51 @@
+//! Benchmark for the direct converters against the hub path
+//!
+//! Converts the same text with the generated direct converter and with
+//! `set_force_hub(true)`, for a same-token-type pair (iast → slp1) and a pair
+//! that needs implicit 'a' handling (devanagari → iast). Both paths produce
+//! identical output, so the rows differ only in speed.
+//!
+//! Run with `cargo bench --bench direct_converter_benchmark`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use shlesha::Shlesha;
+use std::hint::black_box;
+
+const DEVANAGARI_VERSE: &str = "धर्मक्षेत्रे कुरुक्षेत्रे समवेता युयुत्सवः । मामकाः पाण्डवाश्चैव किमकुर्वत सञ्जय ॥\n";
+
+const IAST_VERSE: &str =
+    "dharmakṣetre kurukṣetre samavetā yuyutsavaḥ | māmakāḥ pāṇḍavāścaiva kimakurvata sañjaya ||\n";
+
+fn benchmark_direct_vs_hub(c: &mut Criterion) {
+    let direct = Shlesha::new();
+    let mut hub = Shlesha::new();
+    hub.set_force_hub(true);
+
+    for (verse, from, to) in [
+        (IAST_VERSE, "iast", "slp1"),
+        (DEVANAGARI_VERSE, "devanagari", "iast"),
+    ] {
+        let mut group = c.benchmark_group(format!("{from}_to_{to}"));
+
+        for repeats in [1, 100] {
+            let text = verse.repeat(repeats);
+            assert_eq!(
+                direct.transliterate(&text, from, to).unwrap(),
+                hub.transliterate(&text, from, to).unwrap()
+            );
+            group.throughput(Throughput::Bytes(text.len() as u64));
+
+            group.bench_with_input(BenchmarkId::new("direct", repeats), &text, |b, text| {
+                b.iter(|| black_box(direct.transliterate(text, from, to).unwrap()))
+            });
+            group.bench_with_input(BenchmarkId::new("hub", repeats), &text, |b, text| {
+                b.iter(|| black_box(hub.transliterate(text, from, to).unwrap()))
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, benchmark_direct_vs_hub);
+criterion_main!(benches);
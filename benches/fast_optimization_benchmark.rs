@@ -1,5 +1,6 @@
-use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use shlesha::Shlesha;
+use std::hint::black_box;
 
 // Fast benchmark suite for iterative optimization
 // Focuses on Telugu ↔ SLP1 roundtrip (Indic ↔ Roman non-hub script)
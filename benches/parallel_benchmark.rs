@@ -0,0 +1,54 @@
+//! Benchmark for parallel conversion of large inputs
+//!
+//! Converts a ~10 MB Devanagari corpus serially and on rayon pools of increasing
+//! size. With enough cores the per-thread rows should scale close to linearly.
+//!
+//! Run with `cargo bench --bench parallel_benchmark --features parallel`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use shlesha::Shlesha;
+use std::hint::black_box;
+
+const VERSE: &str = "धर्मक्षेत्रे कुरुक्षेत्रे समवेता युयुत्सवः । मामकाः पाण्डवाश्चैव किमकुर्वत सञ्जय ॥\n";
+
+const CORPUS_BYTES: usize = 10 * 1024 * 1024;
+
+fn corpus() -> String {
+    VERSE.repeat(CORPUS_BYTES / VERSE.len() + 1)
+}
+
+fn benchmark_parallel_scaling(c: &mut Criterion) {
+    let text = corpus();
+    let mut group = c.benchmark_group("parallel_10mb");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(text.len() as u64));
+
+    let mut serial = Shlesha::new();
+    serial.set_parallel_threshold(usize::MAX);
+    group.bench_function("serial", |b| {
+        b.iter(|| black_box(serial.transliterate(&text, "devanagari", "iast").unwrap()))
+    });
+
+    let parallel = Shlesha::new();
+    let max_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut threads = 1;
+    while threads <= max_threads {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        group.bench_with_input(BenchmarkId::new("threads", threads), &text, |b, text| {
+            b.iter(|| {
+                pool.install(|| {
+                    black_box(parallel.transliterate(text, "devanagari", "iast").unwrap())
+                })
+            })
+        });
+        threads *= 2;
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_parallel_scaling);
+criterion_main!(benches);
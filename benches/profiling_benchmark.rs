@@ -3,10 +3,10 @@
 //! This benchmark measures the effectiveness of the profiling system
 //! by comparing baseline performance with optimized performance.
 
-use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use shlesha::{modules::profiler::ProfilerConfig, Shlesha};
+use std::hint::black_box;
 use std::path::PathBuf;
-use std::time::Duration;
 
 // Test data with repeated patterns (realistic for Sanskrit/Hindi)
 const REPEATED_SANSKRIT_TEXT: &str = "धर्म कर्म योग वेद मन्त्र धर्म योग कर्म वेद धर्म मन्त्र योग कर्म वेद धर्म योग वेद कर्म मन्त्र धर्म योग कर्म वेद मन्त्र धर्म योग";
@@ -36,10 +36,12 @@ fn benchmark_baseline_vs_optimized(c: &mut Criterion) {
     let baseline_transliterator = Shlesha::new();
 
     // Create transliterator with profiling
-    let mut config = ProfilerConfig::default();
-    config.profile_dir = PathBuf::from("bench_profiles");
-    config.optimization_dir = PathBuf::from("bench_optimizations");
-    config.min_sequence_frequency = 2; // Low threshold for benchmark
+    let config = ProfilerConfig {
+        profile_dir: PathBuf::from("bench_profiles"),
+        optimization_dir: PathBuf::from("bench_optimizations"),
+        min_sequence_frequency: 2, // Low threshold for benchmark
+        ..Default::default()
+    };
 
     let mut profiled_transliterator = Shlesha::new();
     profiled_transliterator.enable_profiling_with_config(config);
@@ -118,7 +120,7 @@ fn benchmark_profiling_overhead(c: &mut Criterion) {
     let mut group = c.benchmark_group("profiling_overhead");
 
     let baseline_transliterator = Shlesha::new();
-    let mut profiled_transliterator = Shlesha::with_profiling();
+    let profiled_transliterator = Shlesha::with_profiling();
 
     // Benchmark the overhead of profiling itself
     group.bench_function("baseline_no_profiling", |b| {
@@ -148,7 +150,7 @@ fn benchmark_optimization_generation(c: &mut Criterion) {
     let mut group = c.benchmark_group("optimization_generation");
 
     // Create transliterator with substantial profile data
-    let mut transliterator = Shlesha::with_profiling();
+    let transliterator = Shlesha::with_profiling();
 
     // Build up a realistic profile
     for i in 0..100 {
@@ -0,0 +1,102 @@
+//! Benchmark for the result cache on words drawn from a Zipfian distribution
+//!
+//! Converts 10,000 draws from 500 Devanagari words, the kth most common drawn
+//! in proportion to 1/k as word frequencies in running text are, with and
+//! without `enable_result_cache`. Most draws repeat a common word, which the
+//! cache answers without converting.
+//!
+//! Run with `cargo bench --bench result_cache_benchmark`.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use shlesha::Shlesha;
+use std::hint::black_box;
+
+const STEMS: &[&str] = &[
+    "धर्म",
+    "क्षेत्र",
+    "कुरु",
+    "पाण्डव",
+    "सञ्जय",
+    "युयुत्सु",
+    "मामक",
+    "राम",
+    "सीता",
+    "कृष्ण",
+];
+const ENDINGS: &[&str] = &["", "ः", "म्", "स्य", "ेन", "े", "ाय", "ात्", "ानाम्", "ेषु"];
+const WORDS: usize = 500;
+const DRAWS: usize = 10_000;
+
+/// Distinct words, built from stems, endings and a numbered prefix
+fn vocabulary() -> Vec<String> {
+    (0..WORDS)
+        .map(|index| {
+            let stem = STEMS[index % STEMS.len()];
+            let ending = ENDINGS[(index / STEMS.len()) % ENDINGS.len()];
+            let prefix = ["", "सु", "अ", "प्र", "वि"][index / (STEMS.len() * ENDINGS.len())];
+            format!("{prefix}{stem}{ending}")
+        })
+        .collect()
+}
+
+/// Indices of `DRAWS` words drawn with probability proportional to 1/rank,
+/// from a fixed-seed linear congruential generator
+fn zipfian_draws() -> Vec<usize> {
+    let weights: Vec<f64> = (1..=WORDS).map(|rank| 1.0 / rank as f64).collect();
+    let total: f64 = weights.iter().sum();
+    let cumulative: Vec<f64> = weights
+        .iter()
+        .scan(0.0, |sum, weight| {
+            *sum += weight / total;
+            Some(*sum)
+        })
+        .collect();
+
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..DRAWS)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let uniform = (state >> 11) as f64 / (1u64 << 53) as f64;
+            cumulative
+                .partition_point(|&bound| bound < uniform)
+                .min(WORDS - 1)
+        })
+        .collect()
+}
+
+fn benchmark_result_cache(c: &mut Criterion) {
+    let words = vocabulary();
+    let draws: Vec<&str> = zipfian_draws()
+        .into_iter()
+        .map(|index| words[index].as_str())
+        .collect();
+    let bytes: usize = draws.iter().map(|word| word.len()).sum();
+
+    let uncached = Shlesha::new();
+    let mut cached = Shlesha::new();
+    cached.enable_result_cache(256);
+
+    let mut group = c.benchmark_group("zipfian_words_devanagari_to_iast");
+    group.throughput(Throughput::Bytes(bytes as u64));
+    for (name, shlesha) in [("uncached", &uncached), ("cached_256", &cached)] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for word in &draws {
+                    black_box(shlesha.transliterate(word, "devanagari", "iast").unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+
+    let stats = cached.stats();
+    println!(
+        "cached_256: {} hits, {} misses",
+        stats.cache_hits, stats.cache_misses
+    );
+}
+
+criterion_group!(benches, benchmark_result_cache);
+criterion_main!(benches);
@@ -0,0 +1,96 @@
+//! Benchmark for runtime-loaded schemas against the generated converters
+//!
+//! Loads the slp1 and Telugu schema files again at runtime, as `runtime_slp1`
+//! and `runtime_telugu`, and converts the same text to and from each copy and
+//! its built-in script. Both produce identical output, so the rows differ only
+//! in speed. The `register` group times loading a copy, which builds its
+//! matcher.
+//!
+//! Run with `cargo bench --bench runtime_schema_benchmark`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use shlesha::Shlesha;
+use std::hint::black_box;
+
+const DEVANAGARI_VERSE: &str = "धर्मक्षेत्रे कुरुक्षेत्रे समवेता युयुत्सवः । मामकाः पाण्डवाश्चैव किमकुर्वत सञ्जय ॥\n";
+
+/// A schema file with its aliases left out, so the copy does not take them over
+fn schema_copy(script: &str) -> String {
+    let path = format!("{}/schemas/{script}.yaml", env!("CARGO_MANIFEST_DIR"));
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("aliases:"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn benchmark_runtime_schemas(c: &mut Criterion) {
+    let mut shlesha = Shlesha::new();
+    let copies: Vec<(&str, String)> = ["slp1", "telugu"]
+        .into_iter()
+        .map(|script| (script, schema_copy(script)))
+        .collect();
+    for (script, schema) in &copies {
+        shlesha
+            .load_schema_from_string(schema, &format!("runtime_{script}"))
+            .unwrap();
+    }
+
+    let mut group = c.benchmark_group("register");
+    for (script, schema) in &copies {
+        let name = format!("runtime_{script}");
+        group.bench_with_input(BenchmarkId::from_parameter(script), schema, |b, schema| {
+            b.iter(|| {
+                shlesha
+                    .load_schema_from_string(black_box(schema), &name)
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+
+    let devanagari = DEVANAGARI_VERSE.repeat(100);
+    for (script, _) in &copies {
+        let runtime = format!("runtime_{script}");
+        let written = shlesha
+            .transliterate(&devanagari, "devanagari", script)
+            .unwrap();
+        assert_eq!(
+            written,
+            shlesha
+                .transliterate(&devanagari, "devanagari", &runtime)
+                .unwrap()
+        );
+        assert_eq!(
+            shlesha
+                .transliterate(&written, script, "devanagari")
+                .unwrap(),
+            shlesha
+                .transliterate(&written, &runtime, "devanagari")
+                .unwrap()
+        );
+
+        for (direction, text, from, to) in [
+            ("from_devanagari", &devanagari, "devanagari", *script),
+            ("to_devanagari", &written, *script, "devanagari"),
+        ] {
+            let mut group = c.benchmark_group(format!("{script}_{direction}"));
+            group.throughput(Throughput::Bytes(text.len() as u64));
+            let runtime_pair = if from == "devanagari" {
+                ("devanagari", runtime.as_str())
+            } else {
+                (runtime.as_str(), "devanagari")
+            };
+            for (label, (from, to)) in [("builtin", (from, to)), ("runtime", runtime_pair)] {
+                group.bench_with_input(BenchmarkId::new(label, 100), text, |b, text| {
+                    b.iter(|| black_box(shlesha.transliterate(text, from, to).unwrap()))
+                });
+            }
+            group.finish();
+        }
+    }
+}
+
+criterion_group!(benches, benchmark_runtime_schemas);
+criterion_main!(benches);
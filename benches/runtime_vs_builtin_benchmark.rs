@@ -1,5 +1,6 @@
-use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use shlesha::Shlesha;
+use std::hint::black_box;
 use std::time::Duration;
 
 // Test text for benchmarking
@@ -1,65 +1,17 @@
 use handlebars::Handlebars;
 use rustc_hash::FxHashMap;
 use serde_json::json;
-use std::collections::BTreeSet;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-#[derive(serde::Deserialize, Debug, Clone)]
-struct ScriptMetadata {
-    name: String,
-    #[allow(dead_code)]
-    script_type: String,
-    #[allow(dead_code)]
-    has_implicit_a: bool,
-    aliases: Option<Vec<String>>,
-}
-
-#[derive(serde::Deserialize, Debug, Clone)]
-struct TokenMappings {
-    vowels: Option<FxHashMap<String, TokenMapping>>, // "VowelA" -> ["a", "A"] or "VowelA" -> "a"
-    consonants: Option<FxHashMap<String, TokenMapping>>, // "ConsonantK" -> ["k", "K"]
-    vowel_signs: Option<FxHashMap<String, TokenMapping>>, // For abugida scripts
-    marks: Option<FxHashMap<String, TokenMapping>>,  // "MarkAnusvara" -> ["M", "ṁ"]
-    digits: Option<FxHashMap<String, TokenMapping>>, // "Digit0" -> "0"
-    special: Option<FxHashMap<String, TokenMapping>>, // "SpecialKs" -> ["kS", "kṣ"]
-    extended: Option<FxHashMap<String, TokenMapping>>, // "ExtendedQ" -> "q"
-    vedic: Option<FxHashMap<String, TokenMapping>>,  // "MarkUdatta" -> "॑"
-}
-
-// Support both single string and array of strings for flexibility
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
-#[serde(untagged)]
-enum TokenMapping {
-    Single(String),        // "a"
-    Multiple(Vec<String>), // ["a", "A"]
-}
-
-#[derive(serde::Deserialize, Debug, Clone)]
-struct CodegenConfig {
-    #[allow(dead_code)]
-    processor_type: String,
-}
-
-impl TokenMapping {
-    #[allow(dead_code)]
-    fn get_preferred(&self) -> String {
-        match self {
-            TokenMapping::Single(s) => s.clone(),
-            TokenMapping::Multiple(vec) => vec.first().unwrap_or(&"".to_string()).clone(),
-        }
-    }
-}
+#[path = "build/schema.rs"]
+mod schema;
 
-#[derive(serde::Deserialize, Debug, Clone)]
-struct ScriptSchema {
-    metadata: ScriptMetadata,
-    target: Option<String>, // "alphabet_tokens" or "abugida_tokens" (optional for legacy schemas)
-    mappings: TokenMappings,
-    #[allow(dead_code)]
-    codegen: Option<CodegenConfig>,
-}
+use schema::{
+    assign_token_ids, check_token_names, parse_token_ids, read_schemas, HubTokens, RuleConfig,
+    RuleContext, ScriptMetadata, ScriptSchema, TokenId, TokenMapping,
+};
 
 // Convert TokenMapping mappings to legacy String mappings for compatibility
 #[allow(dead_code)]
@@ -70,165 +22,255 @@ fn flatten_token_mappings(mappings: &FxHashMap<String, TokenMapping>) -> FxHashM
         .collect()
 }
 
+/// Registry of the stable IDs of hub tokens, committed with the crate
+const TOKEN_IDS: &str = "build/token_ids.tsv";
+
+const TOKEN_IDS_HEADER: &str = "\
+# Stable IDs of hub tokens: ID, format (abugida or alphabet) and token name
+#
+# Maintained by build.rs, which appends an ID for each new token. An ID is never
+# reused or changed, even once its token is gone, so that external tools can
+# store them. ID 0 stands for unknown text.
+";
+
+/// Hub scripts, whose converters are always built
+const HUB_SCRIPTS: &[&str] = &["devanagari", "iso15919"];
+
+/// Cargo feature gating each group of generated converters
+///
+/// A schema in no group is only built with `scripts-all`.
+const SCRIPT_GROUPS: &[(&str, &[&str])] = &[
+    (
+        "scripts-core",
+        &[
+            "iast",
+            "itrans",
+            "slp1",
+            "harvard_kyoto",
+            "velthuis",
+            "wx",
+            "baraha",
+            "kolkata",
+        ],
+    ),
+    (
+        "scripts-north",
+        &[
+            "bengali", "chakma", "gujarati", "gurmukhi", "odia", "tibetan",
+        ],
+    ),
+    // Sinhala, Thai and Khmer are grouped here as the Theravada Pali scripts
+    (
+        "scripts-south",
+        &[
+            "kannada",
+            "malayalam",
+            "tamil",
+            "telugu",
+            "sinhala",
+            "thai",
+            "khmer",
+            "tai_tham",
+            "balinese",
+        ],
+    ),
+    (
+        "scripts-historic",
+        &[
+            "bhaiksuki",
+            "brahmi",
+            "dogra",
+            "grantha",
+            "kaithi",
+            "kharoshthi",
+            "modi",
+            "nandinagari",
+            "newa",
+            "sharada",
+            "siddham",
+            "takri",
+        ],
+    ),
+    ("scripts-braille", &["bharati_braille"]),
+];
+
+/// Bumped by hand when outputs change for a reason the output version does not hash
+///
+/// See "Output Compatibility" in the README.
+const OUTPUT_EPOCH: u32 = 1;
+
+/// Hub rule sources hashed into the output version, besides the schemas and templates
+const HUB_RULE_SOURCES: &[&str] = &[
+    "build.rs",
+    "build/schema.rs",
+    "src/modules/hub/mod.rs",
+    "src/modules/hub/tokens.rs",
+    "src/modules/hub/trait_based_converter.rs",
+    "src/modules/rules/mod.rs",
+];
+
+fn feature_enabled(feature: &str) -> bool {
+    let var = format!("CARGO_FEATURE_{}", feature.to_uppercase().replace('-', "_"));
+    env::var_os(var).is_some()
+}
+
+/// Whether the enabled `scripts-*` features include a converter for `script`
+fn script_compiled_in(script: &str) -> bool {
+    HUB_SCRIPTS.contains(&script)
+        || feature_enabled("scripts-all")
+        || SCRIPT_GROUPS
+            .iter()
+            .any(|(feature, scripts)| scripts.contains(&script) && feature_enabled(feature))
+}
+
 fn main() {
     println!("cargo:rerun-if-changed=schemas/");
     println!("cargo:rerun-if-changed=templates/");
 
-    if let Err(e) = generate_tokens_from_schemas() {
-        println!("cargo:warning=Failed to generate tokens: {e}");
+    // A schema that cannot be generated fails the build: the generated code is
+    // included by the crate, so carrying on would only end in compile errors there
+    let schemas = match read_schemas(Path::new("schemas")) {
+        Ok(schemas) => schemas,
+        Err(e) => panic!("Failed to read the schemas: {e}"),
+    };
+    if let Err(e) = check_token_names(&schemas) {
+        panic!("{e}");
     }
-
-    if let Err(e) = generate_schema_based_converters() {
-        println!("cargo:warning=Failed to generate schema-based converters: {e}");
+    for schema in &schemas {
+        println!("cargo:rerun-if-changed={}", schema.path.display());
+        // Schemas in the old character-to-character format are not generated
+        if schema.target.is_none() {
+            println!(
+                "cargo:warning={} has no target, so no converter is generated for {}",
+                schema.path.display(),
+                schema.metadata.name
+            );
+        }
     }
-}
-
-/// Collect all unique tokens from schemas and generate tokens.rs
-fn generate_tokens_from_schemas() -> Result<(), Box<dyn std::error::Error>> {
-    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
-    let schemas_dir = Path::new("schemas");
-
-    // Collections for unique tokens
-    let mut abugida_vowels = BTreeSet::new();
-    let mut abugida_vowel_signs = BTreeSet::new();
-    let mut abugida_consonants = BTreeSet::new();
-    let mut abugida_marks = BTreeSet::new();
-    let mut abugida_special = BTreeSet::new();
-    let mut abugida_digits = BTreeSet::new();
-    let mut abugida_vedic = BTreeSet::new();
-
-    let mut alphabet_vowels = BTreeSet::new();
-    let mut alphabet_consonants = BTreeSet::new();
-    let mut alphabet_marks = BTreeSet::new();
-    let mut alphabet_special = BTreeSet::new();
-    let mut alphabet_digits = BTreeSet::new();
-    let mut alphabet_vedic = BTreeSet::new();
-
-    // Process all YAML schemas
-    if schemas_dir.exists() {
-        for entry in fs::read_dir(schemas_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.extension().and_then(|s| s.to_str()) == Some("yaml") {
-                let content = fs::read_to_string(&path)?;
-                let schema: ScriptSchema = serde_yaml::from_str(&content)
-                    .map_err(|e| format!("Failed to parse YAML schema {}: {e}", path.display()))?;
-
-                // Skip debug schemas
-                if schema.metadata.name == "abugida_tokens"
-                    || schema.metadata.name == "alphabet_tokens"
-                {
-                    continue;
-                }
-
-                // Skip non-token schemas
-                let target = match &schema.target {
-                    Some(t) => t,
-                    None => continue,
-                };
-
-                let is_abugida = target == "abugida_tokens";
-                let is_alphabet = target == "alphabet_tokens";
 
-                if !is_abugida && !is_alphabet {
-                    continue;
-                }
-
-                // Collect tokens from each category
-                if let Some(vowels) = &schema.mappings.vowels {
-                    for token in vowels.keys() {
-                        if is_abugida {
-                            abugida_vowels.insert(token.clone());
-                        } else {
-                            alphabet_vowels.insert(token.clone());
-                        }
-                    }
-                }
-
-                if let Some(vowel_signs) = &schema.mappings.vowel_signs {
-                    for token in vowel_signs.keys() {
-                        if is_abugida {
-                            abugida_vowel_signs.insert(token.clone());
-                        }
-                    }
-                }
-
-                if let Some(consonants) = &schema.mappings.consonants {
-                    for token in consonants.keys() {
-                        if is_abugida {
-                            abugida_consonants.insert(token.clone());
-                        } else {
-                            alphabet_consonants.insert(token.clone());
-                        }
-                    }
-                }
+    if let Err(e) = generate_tokens_from_schemas(&schemas) {
+        panic!("Failed to generate tokens: {e}");
+    }
 
-                if let Some(marks) = &schema.mappings.marks {
-                    for token in marks.keys() {
-                        if is_abugida {
-                            abugida_marks.insert(token.clone());
-                        } else {
-                            alphabet_marks.insert(token.clone());
-                        }
-                    }
-                }
+    if let Err(e) = generate_schema_based_converters(&schemas) {
+        panic!("Failed to generate schema-based converters: {e}");
+    }
 
-                if let Some(special) = &schema.mappings.special {
-                    for token in special.keys() {
-                        if is_abugida {
-                            abugida_special.insert(token.clone());
-                        } else {
-                            alphabet_special.insert(token.clone());
-                        }
-                    }
-                }
+    match output_version() {
+        Ok(version) => println!("cargo:rustc-env=SHLESHA_OUTPUT_VERSION={version}"),
+        Err(e) => panic!("Failed to compute the output version: {e}"),
+    }
+}
 
-                if let Some(digits) = &schema.mappings.digits {
-                    for token in digits.keys() {
-                        if is_abugida {
-                            abugida_digits.insert(token.clone());
-                        } else {
-                            alphabet_digits.insert(token.clone());
-                        }
-                    }
-                }
+/// Hash of everything that decides conversion outputs, as 16 hex digits
+///
+/// Schemas are hashed by content, so comments, formatting and test cases do
+/// not count; templates and hub sources by their lines, without blank lines,
+/// comments and unit tests. Every schema is hashed whatever the `scripts-*`
+/// features, so the version is the same for any feature set.
+fn output_version() -> Result<String, Box<dyn std::error::Error>> {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&OUTPUT_EPOCH.to_le_bytes());
+
+    let mut schema_paths: Vec<PathBuf> = fs::read_dir("schemas")?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    schema_paths.retain(|path| path.extension().and_then(|s| s.to_str()) == Some("yaml"));
+    schema_paths.sort();
+    for path in &schema_paths {
+        let mut content: serde_json::Value = serde_yaml::from_str(&fs::read_to_string(path)?)?;
+        // A schema's test cases check its output without deciding it
+        if let Some(sections) = content.as_object_mut() {
+            sections.remove("tests");
+        }
+        hasher.update(path.to_string_lossy().as_bytes());
+        // serde_json maps sort their keys, so the hash follows content, not layout
+        hasher.update(content.to_string().as_bytes());
+    }
 
-                if let Some(vedic) = &schema.mappings.vedic {
-                    for token in vedic.keys() {
-                        if is_abugida {
-                            abugida_vedic.insert(token.clone());
-                        } else {
-                            alphabet_vedic.insert(token.clone());
-                        }
-                    }
-                }
+    let mut template_paths: Vec<PathBuf> = fs::read_dir("templates")?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    template_paths.sort();
+    let sources = template_paths
+        .iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .chain(HUB_RULE_SOURCES.iter().map(|path| path.to_string()));
+    for path in sources {
+        println!("cargo:rerun-if-changed={path}");
+        hasher.update(path.as_bytes());
+        for line in fs::read_to_string(&path)?.lines() {
+            let line = line.trim();
+            if line == "#[cfg(test)]" {
+                break;
+            }
+            if line.is_empty() || line.starts_with("//") {
+                continue;
             }
+            hasher.update(line.as_bytes());
+            hasher.update(b"\n");
         }
     }
 
+    Ok(hasher.finalize().to_hex()[..16].to_string())
+}
+
+/// Collect all unique tokens from schemas and generate tokens.rs
+fn generate_tokens_from_schemas(
+    schemas: &[ScriptSchema],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    let tokens = HubTokens::collect(schemas);
+    let token_ids = stable_token_ids(&tokens)?;
+    // The ID of each variant, for the enum of `format`
+    let ids_of = |format: &str| -> Vec<serde_json::Value> {
+        let names = tokens.names(&format!("{format}_tokens"));
+        token_ids
+            .iter()
+            .filter(|entry| entry.format == format && names.contains(entry.name.as_str()))
+            .map(|entry| json!({ "name": entry.name, "id": entry.id }))
+            .collect()
+    };
+    let abugida_ids = ids_of("abugida");
+    let alphabet_ids = ids_of("alphabet");
+    let HubTokens {
+        abugida_vowels,
+        abugida_vowel_signs,
+        abugida_consonants,
+        abugida_marks,
+        abugida_special,
+        abugida_digits,
+        abugida_vedic,
+        alphabet_vowels,
+        alphabet_consonants,
+        alphabet_marks,
+        alphabet_special,
+        alphabet_digits,
+        alphabet_vedic,
+    } = tokens;
+
     // Generate tokens.rs using template
     let mut handlebars = Handlebars::new();
     handlebars.set_strict_mode(false);
     handlebars.register_escape_fn(handlebars::no_escape);
     handlebars.register_template_file("tokens", "templates/tokens.hbs")?;
 
-    // Generate vowel to sign mappings
+    // Pair each vowel sign with its independent vowel by name (VowelSignAa with
+    // VowelAa), so a vowel after a consonant becomes its sign whenever any abugida
+    // schema has one
     let mut vowel_to_sign_mappings = Vec::new();
-    for vowel in &abugida_vowels {
-        if vowel == "VowelA" {
-            continue; // No sign for short 'a'
-        }
-        // Extract the vowel suffix (e.g., "Aa" from "VowelAa")
-        if let Some(suffix) = vowel.strip_prefix("Vowel") {
-            let sign_name = format!("VowelSign{}", suffix);
-            if abugida_vowel_signs.contains(&sign_name) {
-                vowel_to_sign_mappings.push(json!({
-                    "vowel": vowel,
-                    "sign": sign_name,
-                }));
-            }
-        }
+    for sign in &abugida_vowel_signs {
+        let vowel = sign
+            .strip_prefix("VowelSign")
+            .map(|suffix| format!("Vowel{suffix}"))
+            .filter(|vowel| abugida_vowels.contains(vowel))
+            .ok_or_else(|| {
+                format!("Vowel sign {sign} has no independent vowel; name it VowelSign<X> after a Vowel<X> token")
+            })?;
+        vowel_to_sign_mappings.push(json!({
+            "vowel": vowel,
+            "sign": sign,
+        }));
     }
 
     // Generate same sound mappings (tokens with same name exist in both systems)
@@ -294,30 +336,6 @@ fn generate_tokens_from_schemas() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    // Handle special cases where tokens don't exist in one system
-    // These could be read from schema files in the future
-    let special_mappings = vec![
-        // If alphabet doesn't have long e/o, they still map to themselves for preservation
-        ("VowelEe", "VowelEe"),
-        ("VowelOo", "VowelOo"),
-        // Vocalic L often doesn't exist in many scripts
-        ("VowelL", "VowelL"),
-        ("VowelLl", "VowelLl"),
-    ];
-
-    for (abugida, alphabet) in special_mappings {
-        if (abugida_vowels.contains(abugida) || abugida_marks.contains(abugida))
-            && !alphabet_vowels.contains(alphabet)
-            && !alphabet_marks.contains(alphabet)
-        {
-            // This token exists in abugida but not alphabet - it will be preserved as-is
-            abugida_to_alphabet_mappings.push(json!({
-                "from": abugida,
-                "to": abugida,  // Map to itself for preservation
-            }));
-        }
-    }
-
     let template_data = json!({
         "abugida_vowels": abugida_vowels.into_iter().collect::<Vec<_>>(),
         "abugida_vowel_signs": abugida_vowel_signs.into_iter().collect::<Vec<_>>(),
@@ -336,6 +354,8 @@ fn generate_tokens_from_schemas() -> Result<(), Box<dyn std::error::Error>> {
         "same_sound_mappings": same_sound_mappings,
         "abugida_to_alphabet_mappings": abugida_to_alphabet_mappings,
         "alphabet_to_abugida_mappings": alphabet_to_abugida_mappings,
+        "abugida_ids": abugida_ids,
+        "alphabet_ids": alphabet_ids,
     });
 
     let tokens_code = handlebars.render("tokens", &template_data)?;
@@ -344,9 +364,36 @@ fn generate_tokens_from_schemas() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn generate_schema_based_converters() -> Result<(), Box<dyn std::error::Error>> {
+/// The entries of `TOKEN_IDS`, after appending an ID for each token it lacks
+fn stable_token_ids(tokens: &HubTokens) -> Result<Vec<TokenId>, Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed={TOKEN_IDS}");
+    let content = match fs::read_to_string(TOKEN_IDS) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => TOKEN_IDS_HEADER.to_string(),
+        Err(e) => return Err(format!("Cannot read {TOKEN_IDS}: {e}").into()),
+    };
+    let mut ids = parse_token_ids(&content).map_err(|e| format!("{TOKEN_IDS}: {e}"))?;
+    let added = assign_token_ids(&mut ids, tokens);
+    if !added.is_empty() {
+        let mut updated = content;
+        for entry in &added {
+            updated.push_str(&entry.line());
+            updated.push('\n');
+        }
+        fs::write(TOKEN_IDS, updated).map_err(|e| {
+            format!(
+                "Cannot add IDs for {} new tokens to {TOKEN_IDS}: {e}",
+                added.len()
+            )
+        })?;
+    }
+    Ok(ids)
+}
+
+fn generate_schema_based_converters(
+    schemas: &[ScriptSchema],
+) -> Result<(), Box<dyn std::error::Error>> {
     let out_dir = PathBuf::from(env::var("OUT_DIR")?);
-    let schemas_dir = Path::new("schemas");
 
     // Initialize Handlebars template engine - token-based only!
     let mut handlebars = Handlebars::new();
@@ -367,7 +414,6 @@ fn generate_schema_based_converters() -> Result<(), Box<dyn std::error::Error>>
 
     let mut generated_code = String::new();
     let mut converter_registrations = Vec::new();
-    let mut schemas = Vec::new();
 
     // Add header with all necessary imports once
     generated_code.push_str(
@@ -382,70 +428,61 @@ fn generate_schema_based_converters() -> Result<(), Box<dyn std::error::Error>>
 #[allow(clippy::clone_on_copy)]
 #[allow(clippy::match_like_matches_macro)]
 #[allow(clippy::duplicated_attributes)]
-
 use once_cell::sync::Lazy;
 use crate::modules::hub::HubFormat;
 use crate::modules::hub::tokens::{AbugidaToken, AlphabetToken, HubToken, HubTokenSequence};
+use crate::modules::hub::trait_based_converter::Streamed;
 use aho_corasick::AhoCorasick;
 
 "#,
     );
 
-    // Process YAML schemas
-    if schemas_dir.exists() {
-        for entry in fs::read_dir(schemas_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.extension().and_then(|s| s.to_str()) == Some("yaml") {
-                println!("cargo:rerun-if-changed={}", path.display());
-
-                let content = fs::read_to_string(&path)?;
-                let schema: ScriptSchema = serde_yaml::from_str(&content)
-                    .map_err(|e| format!("Failed to parse YAML schema {}: {e}", path.display()))?;
-
-                // Add schema to collection for Hub generation
-                schemas.push(schema.clone());
-
-                // Only process token-based schemas
-                if let Some(ref target) = schema.target {
-                    if target != "alphabet_tokens" && target != "abugida_tokens" {
-                        continue; // Skip non-token schemas
-                    }
-                } else {
-                    continue; // Skip schemas without target
-                }
-
-                let converter_code =
-                    generate_converter_from_schema(&handlebars, &schema).map_err(|e| {
-                        format!(
-                            "Failed to generate converter for {}: {e}",
-                            schema.metadata.name
-                        )
-                    })?;
-                generated_code.push_str(&converter_code);
-
-                // Only register token-based converters!
-                if let Some(ref target) = schema.target {
-                    if target == "alphabet_tokens" || target == "abugida_tokens" {
-                        converter_registrations.push(format!(
-                            "{}Converter",
-                            capitalize_first(&schema.metadata.name)
-                        ));
-                    }
-                }
+    // Every schema is read before any converter is generated, since the token
+    // names a converter may use are those of all the schemas
+    for schema in schemas {
+        // Leave out scripts not selected by the scripts-* features
+        if !script_compiled_in(&schema.metadata.name) {
+            continue;
+        }
 
-                // No more Roman → Devanagari converters - everything goes through tokens!
+        // Only process token-based schemas
+        if let Some(ref target) = schema.target {
+            if target != "alphabet_tokens" && target != "abugida_tokens" {
+                continue; // Skip non-token schemas
             }
+        } else {
+            continue; // Skip schemas without target
         }
+
+        let converter_code =
+            generate_converter_from_schema(&handlebars, schema, schemas).map_err(|e| {
+                format!(
+                    "Failed to generate converter for {}: {e}",
+                    schema.path.display()
+                )
+            })?;
+        generated_code.push_str(&converter_code);
+
+        // Only register token-based converters!
+        converter_registrations.push(format!(
+            "{}Converter",
+            capitalize_first(&schema.metadata.name)
+        ));
+
+        // No more Roman → Devanagari converters - everything goes through tokens!
     }
 
     // Hub converter is no longer needed - using trait_based_converter instead
 
     // Generate direct converters for common script pairs to bypass hub overhead
-    if let Ok(direct_code) = generate_direct_converters(&handlebars, &schemas) {
-        fs::write(out_dir.join("direct_converters_generated.rs"), direct_code)?;
-    }
+    let compiled_schemas: Vec<ScriptSchema> = schemas
+        .iter()
+        .filter(|schema| script_compiled_in(&schema.metadata.name))
+        .cloned()
+        .collect();
+    let direct_code = generate_direct_converters(&handlebars, &compiled_schemas)
+        .map_err(|e| format!("Failed to generate direct converters: {e}"))?;
+    fs::write(out_dir.join("direct_converters_generated.rs"), direct_code)?;
 
     // Generate token-based converter registry with aliases
     let token_registrations = converter_registrations
@@ -454,47 +491,60 @@ use aho_corasick::AhoCorasick;
         .collect::<Vec<_>>()
         .join("\n");
 
-    // Generate registration with aliases
-    let token_registrations_with_aliases = schemas
+    // One alias table for every shipped schema, sorted for binary search
+    let mut script_aliases: Vec<(&str, &str)> = Vec::new();
+    for schema in schemas {
+        for alias in schema.metadata.aliases.iter().flatten() {
+            script_aliases.push((alias.as_str(), schema.metadata.name.as_str()));
+        }
+    }
+    script_aliases.sort_unstable();
+    for pair in script_aliases.windows(2) {
+        if pair[0].0 == pair[1].0 {
+            return Err(format!(
+                "Alias '{}' is declared by both {} and {}",
+                pair[0].0, pair[0].1, pair[1].1
+            )
+            .into());
+        }
+    }
+    for (alias, name) in &script_aliases {
+        if schemas.iter().any(|schema| schema.metadata.name == *alias) {
+            return Err(format!("Alias '{alias}' of {name} is the name of another schema").into());
+        }
+    }
+    let script_aliases = script_aliases
         .iter()
-        .filter_map(|schema| {
-            let converter_name = format!(
-                "{}Converter",
-                schema
-                    .metadata
-                    .name
-                    .chars()
-                    .next()
-                    .unwrap()
-                    .to_uppercase()
-                    .to_string()
-                    + &schema.metadata.name[1..]
-            );
-
-            if converter_registrations.contains(&converter_name) {
-                let aliases = schema
-                    .metadata
-                    .aliases
-                    .as_ref()
-                    .map(|aliases| {
-                        aliases
-                            .iter()
-                            .map(|alias| format!("\"{alias}\".to_string()"))
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    })
-                    .unwrap_or_default();
-
-                Some(format!(
-                    "        (Box::new({converter_name}::new()), vec![{aliases}]),"
-                ))
-            } else {
-                None
-            }
-        })
+        .map(|(alias, name)| format!("    (\"{alias}\", \"{name}\"),"))
         .collect::<Vec<_>>()
         .join("\n");
 
+    // Direction and rendering notes of the schemas that declare them, sorted by name
+    let mut script_layouts = Vec::new();
+    for schema in schemas {
+        let metadata = &schema.metadata;
+        let direction = match metadata.direction.as_deref() {
+            None | Some("ltr") => "Ltr",
+            Some("rtl") => "Rtl",
+            Some(other) => {
+                return Err(format!(
+                    "Direction '{other}' of {} is neither \"ltr\" nor \"rtl\"",
+                    metadata.name
+                )
+                .into())
+            }
+        };
+        if direction == "Ltr" && metadata.rendering_notes.is_none() {
+            continue;
+        }
+        script_layouts.push(format!(
+            "    (\"{}\", crate::modules::registry::TextDirection::{direction}, {:?}),",
+            metadata.name, metadata.rendering_notes
+        ));
+    }
+    script_layouts.sort_unstable();
+    let script_layouts = script_layouts.join("\n");
+
     generated_code.push_str(&format!(r#"
 /// Register token-based converters
 pub fn register_schema_generated_converters(_registry: &mut crate::modules::script_converter::ScriptConverterRegistry) {{
@@ -502,26 +552,29 @@ pub fn register_schema_generated_converters(_registry: &mut crate::modules::scri
     // Token-based converters are managed separately
 }}
 
-/// Get all token-based converters (legacy)
+/// Get all token-based converters
 pub fn register_token_converters() -> Vec<Box<dyn crate::modules::script_converter::TokenConverter>> {{
     vec![
 {token_registrations}
     ]
 }}
 
-/// Get all token-based converters with their aliases
-pub fn register_token_converters_with_aliases() -> Vec<(Box<dyn crate::modules::script_converter::TokenConverter>, Vec<String>)> {{
-    vec![
-{token_registrations_with_aliases}
-    ]
-}}
+/// The aliases declared in the shipped schemas and the scripts they name, by alias
+pub static SCRIPT_ALIASES: &[(&str, &str)] = &[
+{script_aliases}
+];
+
+/// The shipped schemas not written left to right or with rendering notes, by name
+pub static SCRIPT_LAYOUTS: &[(&str, crate::modules::registry::TextDirection, Option<&str>)] = &[
+{script_layouts}
+];
 "#));
 
     // Generate script type helper functions
     let mut brahmic_scripts = Vec::new();
     let mut roman_scripts = Vec::new();
 
-    for schema in &schemas {
+    for schema in schemas {
         match schema.metadata.script_type.as_str() {
             "brahmic" => {
                 brahmic_scripts.push(format!("\"{}\"", schema.metadata.name.to_lowercase()));
@@ -547,18 +600,32 @@ pub fn register_token_converters_with_aliases() -> Vec<(Box<dyn crate::modules::
         r#"
 /// Check if a script is an Indic/Brahmic script
 pub fn is_indic_script(script: &str) -> bool {{
-    matches!(
-        script.to_lowercase().as_str(),
+    with_lowercase(script, |script| matches!(
+        script,
         {}
-    )
+    ))
 }}
 
 /// Check if a script is a Roman script
 pub fn is_roman_script(script: &str) -> bool {{
-    matches!(
-        script.to_lowercase().as_str(),
+    with_lowercase(script, |script| matches!(
+        script,
         {}
-    )
+    ))
+}}
+
+/// Call `f` with `script` lowercased, without allocating for short ASCII names
+fn with_lowercase(script: &str, f: impl FnOnce(&str) -> bool) -> bool {{
+    let mut buffer = [0u8; 32];
+    if script.is_ascii() && script.len() <= buffer.len() {{
+        let lowercase = &mut buffer[..script.len()];
+        lowercase.copy_from_slice(script.as_bytes());
+        lowercase.make_ascii_lowercase();
+        // Lowercased ASCII is still valid UTF-8
+        f(std::str::from_utf8(lowercase).unwrap_or_default())
+    }} else {{
+        f(&script.to_lowercase())
+    }}
 }}
 "#,
         brahmic_scripts.join("\n            | "),
@@ -575,9 +642,19 @@ pub fn is_roman_script(script: &str) -> bool {{
 fn generate_converter_from_schema(
     handlebars: &Handlebars,
     schema: &ScriptSchema,
+    schemas: &[ScriptSchema],
 ) -> Result<String, Box<dyn std::error::Error>> {
     // Token-based only! All converters must use tokens
-    generate_token_based_converter(handlebars, schema)
+    generate_token_based_converter(handlebars, schema, schemas)
+}
+
+/// Whether `token` names a hub token of the kind `target` reads: one a schema
+/// with that target maps, or one the kind's token list names
+fn is_hub_token(schemas: &[ScriptSchema], target: &str, token: &str) -> bool {
+    schemas
+        .iter()
+        .filter(|schema| schema.target.as_deref() == Some(target) || schema.metadata.name == target)
+        .any(|schema| schema.mappings.token_names().any(|name| name == token))
 }
 
 fn capitalize_first(s: &str) -> String {
@@ -1014,9 +1091,43 @@ fn add_vowel_sign_mappings(
     }
 }
 
+/// Template entries of one mapping category: each token, its written spelling
+/// and every spelling it is read with
+///
+/// The written spelling may be empty, for a token written as nothing; it is
+/// then not read. Any other spelling is only read, so an empty one, which
+/// would match without consuming input, is an error.
+fn spelling_entries(
+    category: &FxHashMap<String, TokenMapping>,
+    script_name: &str,
+) -> Result<Vec<serde_json::Value>, Box<dyn std::error::Error>> {
+    category
+        .iter()
+        .map(|(token, mapping)| {
+            let all_inputs = match mapping {
+                TokenMapping::Single(s) => vec![s.clone()],
+                TokenMapping::Multiple(v) => v.clone(),
+            };
+            match all_inputs.split_first() {
+                None => Err(format!("{token} in {script_name} has no spellings").into()),
+                Some((_, others)) if others.iter().any(String::is_empty) => Err(format!(
+                    "{token} in {script_name} is read with an empty spelling; only the written (first) spelling may be empty"
+                )
+                .into()),
+                Some((preferred, _)) => Ok(json!({
+                    "token": token,
+                    "preferred": preferred,
+                    "all_inputs": all_inputs
+                })),
+            }
+        })
+        .collect()
+}
+
 fn generate_token_based_converter(
     handlebars: &Handlebars,
     schema: &ScriptSchema,
+    schemas: &[ScriptSchema],
 ) -> Result<String, Box<dyn std::error::Error>> {
     let script_name = &schema.metadata.name;
     let struct_name = format!("{}Converter", capitalize_first(script_name));
@@ -1026,21 +1137,7 @@ fn generate_token_based_converter(
     let mut mappings = Vec::new();
 
     if let Some(ref vowels) = schema.mappings.vowels {
-        let entries: Vec<_> = vowels
-            .iter()
-            .map(|(token, mapping)| {
-                let (preferred, all_inputs) = match mapping {
-                    TokenMapping::Single(s) => (s.clone(), vec![s.clone()]),
-                    TokenMapping::Multiple(v) => (v[0].clone(), v.clone()),
-                };
-                // Debug output to understand the issue
-                json!({
-                    "token": token,
-                    "preferred": preferred,
-                    "all_inputs": all_inputs
-                })
-            })
-            .collect();
+        let entries = spelling_entries(vowels, script_name)?;
         mappings.push(json!({
             "category": "Vowels",
             "entries": entries
@@ -1048,20 +1145,7 @@ fn generate_token_based_converter(
     }
 
     if let Some(ref consonants) = schema.mappings.consonants {
-        let entries: Vec<_> = consonants
-            .iter()
-            .map(|(token, mapping)| {
-                let (preferred, all_inputs) = match mapping {
-                    TokenMapping::Single(s) => (s.clone(), vec![s.clone()]),
-                    TokenMapping::Multiple(v) => (v[0].clone(), v.clone()),
-                };
-                json!({
-                    "token": token,
-                    "preferred": preferred,
-                    "all_inputs": all_inputs
-                })
-            })
-            .collect();
+        let entries = spelling_entries(consonants, script_name)?;
         mappings.push(json!({
             "category": "Consonants",
             "entries": entries
@@ -1069,20 +1153,7 @@ fn generate_token_based_converter(
     }
 
     if let Some(ref vowel_signs) = schema.mappings.vowel_signs {
-        let entries: Vec<_> = vowel_signs
-            .iter()
-            .map(|(token, mapping)| {
-                let (preferred, all_inputs) = match mapping {
-                    TokenMapping::Single(s) => (s.clone(), vec![s.clone()]),
-                    TokenMapping::Multiple(v) => (v[0].clone(), v.clone()),
-                };
-                json!({
-                    "token": token,
-                    "preferred": preferred,
-                    "all_inputs": all_inputs
-                })
-            })
-            .collect();
+        let entries = spelling_entries(vowel_signs, script_name)?;
         mappings.push(json!({
             "category": "Vowel Signs",
             "entries": entries
@@ -1090,20 +1161,7 @@ fn generate_token_based_converter(
     }
 
     if let Some(ref marks) = schema.mappings.marks {
-        let entries: Vec<_> = marks
-            .iter()
-            .map(|(token, mapping)| {
-                let (preferred, all_inputs) = match mapping {
-                    TokenMapping::Single(s) => (s.clone(), vec![s.clone()]),
-                    TokenMapping::Multiple(v) => (v[0].clone(), v.clone()),
-                };
-                json!({
-                    "token": token,
-                    "preferred": preferred,
-                    "all_inputs": all_inputs
-                })
-            })
-            .collect();
+        let entries = spelling_entries(marks, script_name)?;
         mappings.push(json!({
             "category": "Marks",
             "entries": entries
@@ -1111,20 +1169,7 @@ fn generate_token_based_converter(
     }
 
     if let Some(ref special) = schema.mappings.special {
-        let entries: Vec<_> = special
-            .iter()
-            .map(|(token, mapping)| {
-                let (preferred, all_inputs) = match mapping {
-                    TokenMapping::Single(s) => (s.clone(), vec![s.clone()]),
-                    TokenMapping::Multiple(v) => (v[0].clone(), v.clone()),
-                };
-                json!({
-                    "token": token,
-                    "preferred": preferred,
-                    "all_inputs": all_inputs
-                })
-            })
-            .collect();
+        let entries = spelling_entries(special, script_name)?;
         mappings.push(json!({
             "category": "Special",
             "entries": entries
@@ -1132,20 +1177,7 @@ fn generate_token_based_converter(
     }
 
     if let Some(ref extended) = schema.mappings.extended {
-        let entries: Vec<_> = extended
-            .iter()
-            .map(|(token, mapping)| {
-                let (preferred, all_inputs) = match mapping {
-                    TokenMapping::Single(s) => (s.clone(), vec![s.clone()]),
-                    TokenMapping::Multiple(v) => (v[0].clone(), v.clone()),
-                };
-                json!({
-                    "token": token,
-                    "preferred": preferred,
-                    "all_inputs": all_inputs
-                })
-            })
-            .collect();
+        let entries = spelling_entries(extended, script_name)?;
         mappings.push(json!({
             "category": "Extended",
             "entries": entries
@@ -1153,20 +1185,7 @@ fn generate_token_based_converter(
     }
 
     if let Some(ref vedic) = schema.mappings.vedic {
-        let entries: Vec<_> = vedic
-            .iter()
-            .map(|(token, mapping)| {
-                let (preferred, all_inputs) = match mapping {
-                    TokenMapping::Single(s) => (s.clone(), vec![s.clone()]),
-                    TokenMapping::Multiple(v) => (v[0].clone(), v.clone()),
-                };
-                json!({
-                    "token": token,
-                    "preferred": preferred,
-                    "all_inputs": all_inputs
-                })
-            })
-            .collect();
+        let entries = spelling_entries(vedic, script_name)?;
         mappings.push(json!({
             "category": "Vedic",
             "entries": entries
@@ -1174,23 +1193,12 @@ fn generate_token_based_converter(
     }
 
     if let Some(ref digits) = schema.mappings.digits {
-        let entries: Vec<_> = digits
-            .iter()
-            .map(|(token, mapping)| {
-                let (preferred, all_inputs) = match mapping {
-                    TokenMapping::Single(s) => (s.clone(), vec![s.clone()]),
-                    TokenMapping::Multiple(v) => (v[0].clone(), v.clone()),
-                };
-                json!({
-                    "token": token,
-                    "preferred": preferred,
-                    "all_inputs": all_inputs
-                })
-            })
-            .collect();
+        let entries = spelling_entries(digits, script_name)?;
         mappings.push(json!({
             "category": "Digits",
-            "entries": entries
+            "entries": entries,
+            // Read only after the numeric indicator, not by the matcher
+            "numeric": schema.numeric_indicator.is_some()
         }));
     }
 
@@ -1214,10 +1222,272 @@ fn generate_token_based_converter(
             .map(|m| m.keys().any(|k| k.len() > 1))
             .unwrap_or(false);
 
+    // Lossy mappings render a token with the output of its substitute
+    let mut lossy_mappings = Vec::new();
+    if let Some(ref lossy) = schema.lossy_mappings {
+        let mut pairs: Vec<_> = lossy.iter().collect();
+        pairs.sort();
+        for (token, substitute) in pairs {
+            let preferred = mappings
+                .iter()
+                .filter_map(|category| category["entries"].as_array())
+                .flatten()
+                .find(|entry| entry["token"] == substitute.as_str())
+                .and_then(|entry| entry["preferred"].as_str())
+                .ok_or_else(|| {
+                    format!(
+                        "Lossy mapping {token} -> {substitute} in {script_name}: {substitute} is not mapped"
+                    )
+                })?
+                .to_string();
+            lossy_mappings.push(json!({
+                "token": token,
+                "substitute": substitute,
+                "preferred": preferred
+            }));
+        }
+    }
+
+    // Fallbacks render a token with the outputs of its substitutes in turn
+    let mut fallbacks = Vec::new();
+    if let Some(ref schema_fallbacks) = schema.fallbacks {
+        let mut pairs: Vec<_> = schema_fallbacks.iter().collect();
+        pairs.sort();
+        for (token, substitutes) in pairs {
+            if substitutes.is_empty() {
+                return Err(format!("Fallback for {token} in {script_name} is empty").into());
+            }
+            let mut preferred = String::new();
+            for substitute in substitutes {
+                let output = mappings
+                    .iter()
+                    .filter_map(|category| category["entries"].as_array())
+                    .flatten()
+                    .find(|entry| entry["token"] == substitute.as_str())
+                    .and_then(|entry| entry["preferred"].as_str())
+                    .ok_or_else(|| {
+                        format!(
+                            "Fallback {token} -> {substitute} in {script_name}: {substitute} is not mapped"
+                        )
+                    })?;
+                preferred.push_str(output);
+            }
+            fallbacks.push(json!({
+                "token": token,
+                "substitutes": substitutes,
+                "preferred": preferred
+            }));
+        }
+    }
+
+    // Hiatus forms must read back as the same vowel, or round trips would break
+    let mut hiatus_forms = Vec::new();
+    let mut hiatus_separators = Vec::new();
+    let mut hiatus_alternates = Vec::new();
+    if let Some(ref hiatus) = schema.hiatus {
+        let mut forms: Vec<_> = hiatus.forms.iter().collect();
+        forms.sort_by_key(|(token, _)| *token);
+        for (token, mapping) in forms {
+            let (form, alternates) = match mapping {
+                TokenMapping::Single(s) => (s.clone(), Vec::new()),
+                TokenMapping::Multiple(v) => (v[0].clone(), v[1..].to_vec()),
+            };
+            let accepted = mappings
+                .iter()
+                .filter_map(|category| category["entries"].as_array())
+                .flatten()
+                .filter(|entry| entry["token"] == token.as_str())
+                .filter_map(|entry| entry["all_inputs"].as_array())
+                .flatten()
+                .any(|input| input == form.as_str());
+            if !accepted {
+                return Err(format!(
+                    "Hiatus form {form:?} for {token} in {script_name} is not an input of {token}"
+                )
+                .into());
+            }
+            for alternate in alternates {
+                hiatus_alternates.push(json!({
+                    "alternate": alternate,
+                    "form": form
+                }));
+            }
+            hiatus_forms.push(json!({
+                "token": token,
+                "form": form
+            }));
+        }
+        if hiatus_forms.is_empty() && !hiatus.separators.is_empty() {
+            return Err(
+                format!("Hiatus separators in {script_name} need at least one form").into(),
+            );
+        }
+        hiatus_separators = hiatus.separators.clone();
+    }
+
+    // Scripts without positional digits write digits as ASCII rather than as [Digit1]
+    let passthrough_digits: Vec<String> = if schema.positional_digits == Some(false) {
+        if schema.mappings.digits.is_some() {
+            return Err(
+                format!("{script_name} maps digits but declares positional_digits: false").into(),
+            );
+        }
+        (0..10).map(|digit| digit.to_string()).collect()
+    } else {
+        Vec::new()
+    };
+
+    // Digits that share letters' text are read after the numeric indicator
+    let mut numeric_digits = Vec::new();
+    if schema.numeric_indicator.is_some() {
+        let Some(ref digits) = schema.mappings.digits else {
+            return Err(
+                format!("{script_name} declares a numeric indicator but maps no digits").into(),
+            );
+        };
+        let mut digits: Vec<_> = digits.iter().collect();
+        digits.sort_by_key(|(token, _)| *token);
+        for (token, mapping) in digits {
+            let inputs = match mapping {
+                TokenMapping::Single(s) => vec![s.clone()],
+                TokenMapping::Multiple(v) => v.clone(),
+            };
+            for input in inputs {
+                numeric_digits.push(json!({ "token": token, "input": input }));
+            }
+        }
+    }
+
+    // Conjuncts written as one unit, longest first so they are matched greedily;
+    // read-only conjuncts are matched on input alone
+    let mut conjuncts = Vec::new();
+    let written = schema.conjuncts.iter().flatten().map(|pair| (pair, true));
+    let read_only = schema
+        .read_conjuncts
+        .iter()
+        .flatten()
+        .map(|pair| (pair, false));
+    let mut pairs: Vec<_> = written.chain(read_only).collect();
+    pairs.sort_by(|((a, a_tokens), _), ((b, b_tokens), _)| {
+        b_tokens.len().cmp(&a_tokens.len()).then(a.cmp(b))
+    });
+    for ((text, tokens), written) in pairs {
+        if tokens.len() < 2 {
+            return Err(format!(
+                "Conjunct {text:?} in {script_name} must stand for two or more tokens"
+            )
+            .into());
+        }
+        let target = schema.target.as_deref().unwrap_or_default();
+        if let Some(unknown) = tokens
+            .iter()
+            .find(|token| !is_hub_token(schemas, target, token))
+        {
+            return Err(format!(
+                "Conjunct {text:?} in {script_name}: {unknown:?} is not a token name"
+            )
+            .into());
+        }
+        conjuncts.push(json!({ "text": text, "tokens": tokens, "written": written }));
+    }
+
+    // The subjoiner is read as the virama it spells, so it must be one of its spellings
+    if let Some(subjoiner) = &schema.subjoiner {
+        let virama = schema
+            .mappings
+            .marks
+            .as_ref()
+            .and_then(|marks| marks.get("MarkVirama"));
+        let spells_virama = match virama {
+            Some(TokenMapping::Single(virama)) => virama == subjoiner,
+            Some(TokenMapping::Multiple(viramas)) => viramas.contains(subjoiner),
+            None => false,
+        };
+        if !spells_virama {
+            return Err(format!(
+                "Subjoiner {subjoiner:?} in {script_name} is not a spelling of MarkVirama"
+            )
+            .into());
+        }
+    }
+
+    // Rules become values of crate::modules::rules, with token classes as its variants
+    let token_type = if is_alphabet { "Alphabet" } else { "Abugida" };
+    let token_expression =
+        |name: &str| format!("HubToken::{token_type}({token_type}Token::{name})");
+    let rule_expressions = |rules: &[RuleConfig]| -> Result<Vec<serde_json::Value>, String> {
+        let mut expressions = Vec::new();
+        for rule in rules {
+            if rule.tokens.is_empty() || rule.text.is_empty() {
+                return Err(format!(
+                    "Rule {:?} in {script_name} must spell one or more tokens with non-empty text",
+                    rule.text
+                ));
+            }
+            let mut contexts = Vec::new();
+            for context in [&rule.before, &rule.after] {
+                let mut classes = Vec::new();
+                for name in context.iter().flat_map(RuleContext::names) {
+                    classes.push(match name.as_str() {
+                        "boundary" => "TokenClass::Boundary".to_string(),
+                        "consonant" => "TokenClass::Consonant".to_string(),
+                        "vowel" => "TokenClass::Vowel".to_string(),
+                        "vowel_sign" => "TokenClass::VowelSign".to_string(),
+                        "virama" => "TokenClass::Virama".to_string(),
+                        "mark" => "TokenClass::Mark".to_string(),
+                        name if name.starts_with(char::is_uppercase) => {
+                            format!("TokenClass::Token({})", token_expression(name))
+                        }
+                        name => {
+                            return Err(format!(
+                                "Rule {:?} in {script_name}: {name:?} is neither a token class nor a token name",
+                                rule.text
+                            ))
+                        }
+                    });
+                }
+                contexts.push(classes);
+            }
+            expressions.push(json!({
+                "tokens": rule.tokens.iter().map(|name| token_expression(name)).collect::<Vec<_>>(),
+                "text": rule.text,
+                "before": contexts[0],
+                "after": contexts[1],
+            }));
+        }
+        Ok(expressions)
+    };
+    let rules = match &schema.rules {
+        Some(rules) if !rules.write.is_empty() || !rules.read.is_empty() => json!({
+            "write": rule_expressions(&rules.write)?,
+            "read": rule_expressions(&rules.read)?,
+        }),
+        _ => serde_json::Value::Null,
+    };
+
+    // Vowel signs written as their vowel are told apart by whether a consonant precedes
+    let vowel_sign_pairs: Vec<_> = vowel_sign_pairs(schema)
+        .into_iter()
+        .map(|(vowel, sign)| json!({ "vowel": vowel, "sign": sign }))
+        .collect();
+
     let template_data = json!({
         "struct_name": struct_name,
         "script_name": script_name,
         "is_alphabet": is_alphabet,
+        "lossy_mappings": lossy_mappings,
+        "fallbacks": fallbacks,
+        "passthrough_digits": passthrough_digits,
+        "numeric_indicator": schema.numeric_indicator,
+        "numeric_digits": numeric_digits,
+        "conjuncts": conjuncts,
+        "subjoiner": schema.subjoiner,
+        "rules": rules,
+        "vowel_sign_pairs": vowel_sign_pairs,
+        "hiatus_forms": hiatus_forms,
+        "hiatus_separators": hiatus_separators,
+        "hiatus_alternates": hiatus_alternates,
+        "contextual_avagraha": schema.contextual_avagraha && is_alphabet,
         "target_type": schema.target.as_ref().unwrap_or(&"unknown".to_string()),
         "mappings": mappings,
         "has_multi_char_mappings": has_multi_char_mappings,
@@ -1228,6 +1498,27 @@ fn generate_token_based_converter(
         .map_err(|e| format!("Template rendering failed: {e}").into())
 }
 
+/// The vowels and vowel signs of `schema` written alike, as (vowel, sign) token names
+fn vowel_sign_pairs(schema: &ScriptSchema) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    if let (Some(vowels), Some(vowel_signs)) =
+        (&schema.mappings.vowels, &schema.mappings.vowel_signs)
+    {
+        let mut signs: Vec<_> = vowel_signs.iter().collect();
+        signs.sort_by_key(|(token, _)| *token);
+        for (sign, mapping) in signs {
+            let vowel = sign.replacen("VowelSign", "Vowel", 1);
+            if vowels
+                .get(&vowel)
+                .is_some_and(|v| v.get_preferred() == mapping.get_preferred())
+            {
+                pairs.push((vowel, sign.clone()));
+            }
+        }
+    }
+    pairs
+}
+
 /// Generate direct converters for common script pairs to bypass hub overhead
 fn generate_direct_converters(
     handlebars: &Handlebars,
@@ -1235,19 +1526,11 @@ fn generate_direct_converters(
 ) -> Result<String, Box<dyn std::error::Error>> {
     let mut generated_code = String::new();
 
-    // Add imports and header
     generated_code.push_str(
         r#"
 // Auto-generated direct converters - bypass hub for maximum performance
 // DO NOT EDIT - Generated by build.rs at compile time
 
-#[allow(unreachable_patterns)]
-#[allow(dead_code)]
-
-use crate::modules::script_converter::ConverterError;
-use aho_corasick::AhoCorasick;
-use once_cell::sync::Lazy;
-
 "#,
     );
 
@@ -1296,112 +1579,110 @@ use once_cell::sync::Lazy;
         .map(|s| (s.metadata.name.as_str(), s))
         .collect();
 
+    let mut struct_names = Vec::new();
     for (from_script, to_script) in &conversion_pairs {
         if let (Some(from_schema), Some(to_schema)) =
             (schema_map.get(from_script), schema_map.get(to_script))
         {
-            if let Ok(converter_code) =
-                generate_single_direct_converter(handlebars, from_schema, to_schema)
-            {
-                generated_code.push_str(&converter_code);
-                generated_code.push('\n');
-            }
+            let (struct_name, converter_code) =
+                generate_single_direct_converter(handlebars, from_schema, to_schema)?;
+            generated_code.push_str(&converter_code);
+            generated_code.push('\n');
+            struct_names.push(struct_name);
         }
     }
 
     // Add registry function to access all direct converters
-    generated_code.push_str(r#"
-/// Registry of all direct converters for fast lookup
-pub struct DirectConverterRegistry {
-    converters: std::collections::HashMap<(String, String), Box<dyn DirectConverter>>,
-}
-
-impl DirectConverterRegistry {
-    pub fn new() -> Self {
-        let mut converters: std::collections::HashMap<(String, String), Box<dyn DirectConverter>> = std::collections::HashMap::new();
-        
-        // Register all generated direct converters
-"#);
-
-    // Register each converter
-    for (from_script, to_script) in &conversion_pairs {
-        if schema_map.contains_key(from_script) && schema_map.contains_key(to_script) {
-            let struct_name = format!(
-                "{}To{}Converter",
-                capitalize_first(from_script),
-                capitalize_first(to_script)
-            );
-            generated_code.push_str(&format!(
-                "        converters.insert((\"{}\".to_string(), \"{}\".to_string()), Box::new({}::new()));\n",
-                from_script, to_script, struct_name
-            ));
-        }
-    }
-
     generated_code.push_str(
-        r#"        
-        Self { converters }
-    }
-    
-    pub fn get_converter(&self, from: &str, to: &str) -> Option<&dyn DirectConverter> {
-        self.converters.get(&(from.to_string(), to.to_string()))
-            .map(|c| c.as_ref())
-    }
-}
-
-pub trait DirectConverter: Send + Sync {
-    fn convert(&self, input: &str) -> Result<String, ConverterError>;
-    fn from_script(&self) -> &'static str;
-    fn to_script(&self) -> &'static str;
-}
+        r#"
+/// All generated direct converters
+fn generated_direct_converters() -> Vec<Box<dyn DirectConverter>> {
+    vec![
 "#,
     );
+    for struct_name in &struct_names {
+        generated_code.push_str(&format!("        Box::new({struct_name}),\n"));
+    }
+    generated_code.push_str("    ]\n}\n");
 
     Ok(generated_code)
 }
 
 /// Generate a single direct converter between two schemas
+///
+/// Returns the converter's struct name with its code. The converter reuses the
+/// generated token converters of both scripts, so its output follows the hub path.
 fn generate_single_direct_converter(
     handlebars: &Handlebars,
     from_schema: &ScriptSchema,
     to_schema: &ScriptSchema,
-) -> Result<String, Box<dyn std::error::Error>> {
+) -> Result<(String, String), Box<dyn std::error::Error>> {
     let from_script = &from_schema.metadata.name;
     let to_script = &to_schema.metadata.name;
+    let from_is_alphabet = from_schema.target.as_deref() == Some("alphabet_tokens");
+    let to_is_alphabet = to_schema.target.as_deref() == Some("alphabet_tokens");
+
+    // Pairs of different token types still need the hub's implicit 'a' handling
+    let hub_step = match (from_is_alphabet, to_is_alphabet) {
+        (true, false) => Some("alphabet_to_abugida"),
+        (false, true) => Some("abugida_to_alphabet"),
+        _ => None,
+    };
 
-    // Build direct mapping by computing token-to-token conversion at build time
-    let mut direct_mappings = Vec::new();
-
-    // Get all mappings from both schemas
-    let from_mappings = collect_all_mappings(from_schema);
-    let to_mappings = collect_all_mappings(to_schema);
-
-    // Create reverse mapping for target schema (string -> token)
-    let mut to_reverse_map = FxHashMap::default();
-    for (token, strings) in &to_mappings {
-        for string in strings {
-            to_reverse_map.insert(token.clone(), string.clone());
-        }
+    // Input the single replacement pass would read differently from the tokenizer:
+    // bracketed token names, the source's hiatus spellings and conjuncts, and apostrophes
+    // read by context
+    let mut token_path_markers = vec!["[".to_string()];
+    for conjuncts in [&from_schema.conjuncts, &from_schema.read_conjuncts] {
+        token_path_markers.extend(
+            conjuncts
+                .iter()
+                .flat_map(|conjuncts| conjuncts.keys().cloned()),
+        );
     }
-
-    // For each source pattern, find the corresponding target pattern
-    for (token, from_strings) in &from_mappings {
-        if let Some(to_string) = to_reverse_map.get(token) {
-            for from_string in from_strings {
-                direct_mappings.push(json!({
-                    "from_pattern": from_string,
-                    "to_pattern": to_string
-                }));
+    token_path_markers.extend(
+        from_schema
+            .rules
+            .iter()
+            .flat_map(|rules| rules.read.iter().map(|rule| rule.text.clone())),
+    );
+    if from_schema.contextual_avagraha {
+        // As `hub::AVAGRAHA_APOSTROPHES`
+        token_path_markers.extend(["'", "\u{2019}", "\u{02BC}"].map(String::from));
+    }
+    if let Some(ref hiatus) = from_schema.hiatus {
+        for mapping in hiatus.forms.values() {
+            if let TokenMapping::Multiple(forms) = mapping {
+                token_path_markers.extend(forms[1..].iter().cloned());
             }
         }
+        token_path_markers.extend(hiatus.separators.iter().cloned());
     }
+    token_path_markers.sort();
+    token_path_markers.dedup();
 
-    // Sort by length (longest first) for proper matching
-    direct_mappings.sort_by(|a, b| {
-        let a_len = a["from_pattern"].as_str().unwrap().len();
-        let b_len = b["from_pattern"].as_str().unwrap().len();
-        b_len.cmp(&a_len)
-    });
+    // Replacing pattern by pattern writes each token alone, which rules of the target would not
+    let replaces = hub_step.is_none()
+        && to_schema
+            .rules
+            .as_ref()
+            .is_none_or(|rules| rules.write.is_empty());
+
+    // Tokens stream from abugida to alphabet when reading needs no pass over the whole
+    // sequence and writing no look ahead; other pairs collect them
+    let streams = hub_step == Some("abugida_to_alphabet")
+        && vowel_sign_pairs(from_schema).is_empty()
+        && from_schema.hiatus.is_none()
+        && to_schema
+            .rules
+            .as_ref()
+            .is_none_or(|rules| rules.write.is_empty())
+        && to_schema.conjuncts.as_ref().is_none_or(|c| c.is_empty());
+
+    let to_has_hiatus_forms = to_schema
+        .hiatus
+        .as_ref()
+        .is_some_and(|hiatus| !hiatus.forms.is_empty());
 
     let struct_name = format!(
         "{}To{}Converter",
@@ -1413,99 +1694,17 @@ fn generate_single_direct_converter(
         "struct_name": struct_name,
         "from_script": from_script,
         "to_script": to_script,
-        "direct_mappings": direct_mappings,
+        "from_struct": format!("{}Converter", capitalize_first(from_script)),
+        "to_struct": format!("{}Converter", capitalize_first(to_script)),
+        "is_alphabet": from_is_alphabet,
+        "hub_step": hub_step,
+        "composes_nukta": hub_step == Some("abugida_to_alphabet"),
+        "replaces": replaces,
+        "streams": streams,
+        "to_has_hiatus_forms": to_has_hiatus_forms,
+        "token_path_markers": token_path_markers,
     });
 
-    let mut converter_code = handlebars.render("direct_converter", &template_data)?;
-
-    // Add trait implementation
-    converter_code.push_str(&format!(
-        r#"
-
-impl DirectConverter for {} {{
-    fn convert(&self, input: &str) -> Result<String, ConverterError> {{
-        self.convert(input)
-    }}
-    
-    fn from_script(&self) -> &'static str {{
-        "{}"
-    }}
-    
-    fn to_script(&self) -> &'static str {{
-        "{}"
-    }}
-}}
-"#,
-        struct_name, from_script, to_script
-    ));
-
-    Ok(converter_code)
-}
-
-/// Collect all mappings from a schema (token -> [strings])
-fn collect_all_mappings(schema: &ScriptSchema) -> FxHashMap<String, Vec<String>> {
-    let mut mappings = FxHashMap::default();
-
-    // Process each mapping category
-    if let Some(ref vowels) = schema.mappings.vowels {
-        for (token, mapping) in vowels {
-            let strings = match mapping {
-                TokenMapping::Single(s) => vec![s.clone()],
-                TokenMapping::Multiple(v) => v.clone(),
-            };
-            mappings.insert(token.clone(), strings);
-        }
-    }
-
-    if let Some(ref consonants) = schema.mappings.consonants {
-        for (token, mapping) in consonants {
-            let strings = match mapping {
-                TokenMapping::Single(s) => vec![s.clone()],
-                TokenMapping::Multiple(v) => v.clone(),
-            };
-            mappings.insert(token.clone(), strings);
-        }
-    }
-
-    if let Some(ref marks) = schema.mappings.marks {
-        for (token, mapping) in marks {
-            let strings = match mapping {
-                TokenMapping::Single(s) => vec![s.clone()],
-                TokenMapping::Multiple(v) => v.clone(),
-            };
-            mappings.insert(token.clone(), strings);
-        }
-    }
-
-    if let Some(ref digits) = schema.mappings.digits {
-        for (token, mapping) in digits {
-            let strings = match mapping {
-                TokenMapping::Single(s) => vec![s.clone()],
-                TokenMapping::Multiple(v) => v.clone(),
-            };
-            mappings.insert(token.clone(), strings);
-        }
-    }
-
-    if let Some(ref special) = schema.mappings.special {
-        for (token, mapping) in special {
-            let strings = match mapping {
-                TokenMapping::Single(s) => vec![s.clone()],
-                TokenMapping::Multiple(v) => v.clone(),
-            };
-            mappings.insert(token.clone(), strings);
-        }
-    }
-
-    if let Some(ref extended) = schema.mappings.extended {
-        for (token, mapping) in extended {
-            let strings = match mapping {
-                TokenMapping::Single(s) => vec![s.clone()],
-                TokenMapping::Multiple(v) => v.clone(),
-            };
-            mappings.insert(token.clone(), strings);
-        }
-    }
-
-    mappings
+    let converter_code = handlebars.render("direct_converter", &template_data)?;
+    Ok((struct_name, converter_code))
 }
@@ -0,0 +1,536 @@
+//! Schema files as build.rs reads them, and the checks made on them before
+//! any code is generated
+//!
+//! Shared with tests/build_schema_tests.rs, which includes this file by path.
+
+use rustc_hash::FxHashMap;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct ScriptMetadata {
+    pub name: String,
+    #[allow(dead_code)]
+    pub script_type: String,
+    #[allow(dead_code)]
+    pub has_implicit_a: bool,
+    pub aliases: Option<Vec<String>>,
+    // "ltr" unless given, or "rtl"
+    pub direction: Option<String>,
+    // How the script is laid out beyond its direction, for rendering engines
+    pub rendering_notes: Option<String>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct TokenMappings {
+    pub vowels: Option<FxHashMap<String, TokenMapping>>, // "VowelA" -> ["a", "A"] or "VowelA" -> "a"
+    pub consonants: Option<FxHashMap<String, TokenMapping>>, // "ConsonantK" -> ["k", "K"]
+    pub vowel_signs: Option<FxHashMap<String, TokenMapping>>, // For abugida scripts
+    pub marks: Option<FxHashMap<String, TokenMapping>>,  // "MarkAnusvara" -> ["M", "ṁ"]
+    pub digits: Option<FxHashMap<String, TokenMapping>>, // "Digit0" -> "0"
+    pub special: Option<FxHashMap<String, TokenMapping>>, // "SpecialKs" -> ["kS", "kṣ"]
+    pub extended: Option<FxHashMap<String, TokenMapping>>, // "ExtendedQ" -> "q"
+    pub vedic: Option<FxHashMap<String, TokenMapping>>,  // "MarkUdatta" -> "॑"
+}
+
+impl TokenMappings {
+    /// The names of the tokens mapped, in every category
+    pub fn token_names(&self) -> impl Iterator<Item = &String> {
+        [
+            &self.vowels,
+            &self.consonants,
+            &self.vowel_signs,
+            &self.marks,
+            &self.digits,
+            &self.special,
+            &self.extended,
+            &self.vedic,
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(|category| category.keys())
+    }
+}
+
+// Support both single string and array of strings for flexibility
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum TokenMapping {
+    Single(String),        // "a"
+    Multiple(Vec<String>), // ["a", "A"]
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct CodegenConfig {
+    #[allow(dead_code)]
+    pub processor_type: String,
+}
+
+impl TokenMapping {
+    #[allow(dead_code)]
+    pub fn get_preferred(&self) -> String {
+        match self {
+            TokenMapping::Single(s) => s.clone(),
+            TokenMapping::Multiple(vec) => vec.first().unwrap_or(&"".to_string()).clone(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct ScriptSchema {
+    // The file the schema was read from, for error messages
+    #[serde(skip)]
+    pub path: PathBuf,
+    pub metadata: ScriptMetadata,
+    pub target: Option<String>, // "alphabet_tokens" or "abugida_tokens" (optional for legacy schemas)
+    pub mappings: TokenMappings,
+    // Tokens the script cannot represent, rendered as another token: "VowelE" -> "VowelEe"
+    pub lossy_mappings: Option<FxHashMap<String, String>>,
+    // Tokens the script has no letter for, spelled out: "VowelR" -> [ConsonantR, VowelSignI]
+    pub fallbacks: Option<FxHashMap<String, Vec<String>>>,
+    // False for scripts whose numerals are not positional; digits then pass through as ASCII
+    pub positional_digits: Option<bool>,
+    // Written before each run of digits, for scripts whose digits share letters' text
+    pub numeric_indicator: Option<String>,
+    // Text written for a whole token sequence: "⠟" -> [ConsonantK, MarkVirama, ConsonantSs]
+    pub conjuncts: Option<FxHashMap<String, Vec<String>>>,
+    // Text read as a token sequence but never written: "ౝ" -> [ConsonantNn, MarkVirama]
+    pub read_conjuncts: Option<FxHashMap<String, Vec<String>>>,
+    // Spelling of MarkVirama written between two consonants, stacking the second: Tai Tham "᩠"
+    pub subjoiner: Option<String>,
+    // Vowels written apart from a preceding "a" where the pair would read as a diphthong
+    pub hiatus: Option<HiatusConfig>,
+    // Avagraha written as an apostrophe, read as avagraha only between a vowel and a syllable
+    #[serde(default)]
+    pub contextual_avagraha: bool,
+    // Spellings that depend on the neighbouring tokens; see src/modules/rules
+    pub rules: Option<RulesConfig>,
+    #[allow(dead_code)]
+    pub codegen: Option<CodegenConfig>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct HiatusConfig {
+    // Written form of each vowel after "a": "VowelU" -> "ü" (praüga, not prauga).
+    // Further forms are read as the first, e.g. "u" + combining diaeresis
+    pub forms: FxHashMap<String, TokenMapping>,
+    // Characters written between "a" and one of these vowels only to keep them apart
+    #[serde(default)]
+    pub separators: Vec<String>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone, Default)]
+pub struct RulesConfig {
+    #[serde(default)]
+    pub write: Vec<RuleConfig>,
+    #[serde(default)]
+    pub read: Vec<RuleConfig>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct RuleConfig {
+    pub tokens: Vec<String>,
+    pub text: String,
+    pub before: Option<RuleContext>,
+    pub after: Option<RuleContext>,
+}
+
+// One token class, or a list of them any of which will do
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum RuleContext {
+    One(String),
+    Any(Vec<String>),
+}
+
+impl RuleContext {
+    pub fn names(&self) -> &[String] {
+        match self {
+            RuleContext::One(name) => std::slice::from_ref(name),
+            RuleContext::Any(names) => names,
+        }
+    }
+}
+
+/// Read every YAML schema in `dir`, sorted by file name
+///
+/// A schema that does not parse is an error naming the file and the YAML path
+/// of the problem, such as `mappings.vowels.VowelA`.
+pub fn read_schemas(dir: &Path) -> Result<Vec<ScriptSchema>, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Cannot read schemas from {}: {e}", dir.display()))?;
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Cannot read schemas from {}: {e}", dir.display()))?
+            .path();
+        if path.extension().and_then(|s| s.to_str()) == Some("yaml") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut schemas = Vec::new();
+    for path in paths {
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+        let deserializer = serde_yaml::Deserializer::from_str(&content);
+        let mut schema: ScriptSchema =
+            serde_path_to_error::deserialize(deserializer).map_err(|e| {
+                match e.path().to_string().as_str() {
+                    "." => format!("{}: {}", path.display(), e.inner()),
+                    at => format!("{}: at {at}: {}", path.display(), e.inner()),
+                }
+            })?;
+        schema.path = path;
+        schemas.push(schema);
+    }
+    Ok(schemas)
+}
+
+/// The variants of the generated token enums, by category
+///
+/// Taken from the categories the schemas of each kind map, and for tokens no
+/// script maps, such as MarkPluta (written with a digit 3), from the token
+/// list schemas `abugida_tokens` and `alphabet_tokens`.
+#[derive(Default)]
+pub struct HubTokens {
+    pub abugida_vowels: BTreeSet<String>,
+    pub abugida_vowel_signs: BTreeSet<String>,
+    pub abugida_consonants: BTreeSet<String>,
+    pub abugida_marks: BTreeSet<String>,
+    pub abugida_special: BTreeSet<String>,
+    pub abugida_digits: BTreeSet<String>,
+    pub abugida_vedic: BTreeSet<String>,
+    pub alphabet_vowels: BTreeSet<String>,
+    pub alphabet_consonants: BTreeSet<String>,
+    pub alphabet_marks: BTreeSet<String>,
+    pub alphabet_special: BTreeSet<String>,
+    pub alphabet_digits: BTreeSet<String>,
+    pub alphabet_vedic: BTreeSet<String>,
+}
+
+impl HubTokens {
+    pub fn collect(schemas: &[ScriptSchema]) -> Self {
+        let mut tokens = HubTokens::default();
+        let mut token_lists = Vec::new();
+
+        for schema in schemas {
+            if is_token_list(schema) {
+                token_lists.push(schema);
+                continue;
+            }
+            let is_abugida = match schema.target.as_deref() {
+                Some("abugida_tokens") => true,
+                Some("alphabet_tokens") => false,
+                _ => continue,
+            };
+            let mappings = &schema.mappings;
+            let categories = if is_abugida {
+                let signs = &mut tokens.abugida_vowel_signs;
+                signs.extend(mappings.vowel_signs.iter().flat_map(|e| e.keys()).cloned());
+                [
+                    (&mappings.vowels, &mut tokens.abugida_vowels),
+                    (&mappings.consonants, &mut tokens.abugida_consonants),
+                    (&mappings.marks, &mut tokens.abugida_marks),
+                    (&mappings.special, &mut tokens.abugida_special),
+                    (&mappings.digits, &mut tokens.abugida_digits),
+                    (&mappings.vedic, &mut tokens.abugida_vedic),
+                ]
+            } else {
+                // Alphabets have no vowel signs
+                [
+                    (&mappings.vowels, &mut tokens.alphabet_vowels),
+                    (&mappings.consonants, &mut tokens.alphabet_consonants),
+                    (&mappings.marks, &mut tokens.alphabet_marks),
+                    (&mappings.special, &mut tokens.alphabet_special),
+                    (&mappings.digits, &mut tokens.alphabet_digits),
+                    (&mappings.vedic, &mut tokens.alphabet_vedic),
+                ]
+            };
+            for (entries, names) in categories {
+                names.extend(entries.iter().flat_map(|entries| entries.keys()).cloned());
+            }
+        }
+
+        // Tokens that no script maps
+        for schema in token_lists {
+            let categories = if schema.metadata.name == "abugida_tokens" {
+                [
+                    (&schema.mappings.vowels, &mut tokens.abugida_vowels),
+                    (&schema.mappings.consonants, &mut tokens.abugida_consonants),
+                    (&schema.mappings.marks, &mut tokens.abugida_marks),
+                    (&schema.mappings.special, &mut tokens.abugida_special),
+                    (&schema.mappings.digits, &mut tokens.abugida_digits),
+                    (&schema.mappings.vedic, &mut tokens.abugida_vedic),
+                ]
+            } else {
+                [
+                    (&schema.mappings.vowels, &mut tokens.alphabet_vowels),
+                    (&schema.mappings.consonants, &mut tokens.alphabet_consonants),
+                    (&schema.mappings.marks, &mut tokens.alphabet_marks),
+                    (&schema.mappings.special, &mut tokens.alphabet_special),
+                    (&schema.mappings.digits, &mut tokens.alphabet_digits),
+                    (&schema.mappings.vedic, &mut tokens.alphabet_vedic),
+                ]
+            };
+            let known: BTreeSet<String> = categories
+                .iter()
+                .flat_map(|(_, names)| names.iter().cloned())
+                .collect();
+            for (entries, names) in categories {
+                for token in entries.iter().flat_map(|entries| entries.keys()) {
+                    if !known.contains(token) {
+                        names.insert(token.clone());
+                    }
+                }
+            }
+        }
+        tokens
+    }
+
+    /// Every variant of the enum the schemas with `target` read into
+    pub fn names(&self, target: &str) -> BTreeSet<&str> {
+        let categories = if target == "abugida_tokens" {
+            vec![
+                &self.abugida_vowels,
+                &self.abugida_vowel_signs,
+                &self.abugida_consonants,
+                &self.abugida_marks,
+                &self.abugida_special,
+                &self.abugida_digits,
+                &self.abugida_vedic,
+            ]
+        } else {
+            vec![
+                &self.alphabet_vowels,
+                &self.alphabet_consonants,
+                &self.alphabet_marks,
+                &self.alphabet_special,
+                &self.alphabet_digits,
+                &self.alphabet_vedic,
+            ]
+        };
+        categories
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Whether the schema is one of the debug schemas that list every token
+pub fn is_token_list(schema: &ScriptSchema) -> bool {
+    matches!(
+        schema.metadata.name.as_str(),
+        "abugida_tokens" | "alphabet_tokens"
+    )
+}
+
+/// Check that every token name a schema uses is a variant of the generated
+/// enum it reads into, listing each offender with its file and YAML path
+///
+/// Token names the enums lack would otherwise surface as compile errors in the
+/// generated code, far from the schema that caused them.
+pub fn check_token_names(schemas: &[ScriptSchema]) -> Result<(), String> {
+    let tokens = HubTokens::collect(schemas);
+    let mut offenders = Vec::new();
+
+    for schema in schemas {
+        let target = match schema.target.as_deref() {
+            Some(target @ ("abugida_tokens" | "alphabet_tokens")) if !is_token_list(schema) => {
+                target
+            }
+            _ => continue,
+        };
+        let known = tokens.names(target);
+
+        // Each token name used, with the YAML path it is used at
+        let mut used: Vec<(String, &str)> = Vec::new();
+        let mappings = &schema.mappings;
+        for (category, entries) in [
+            ("vowels", &mappings.vowels),
+            ("consonants", &mappings.consonants),
+            ("vowel_signs", &mappings.vowel_signs),
+            ("marks", &mappings.marks),
+            ("digits", &mappings.digits),
+            ("special", &mappings.special),
+            ("extended", &mappings.extended),
+            ("vedic", &mappings.vedic),
+        ] {
+            for token in entries.iter().flat_map(|entries| entries.keys()) {
+                used.push((format!("mappings.{category}.{token}"), token));
+            }
+        }
+        for (token, substitute) in schema.lossy_mappings.iter().flatten() {
+            used.push((format!("lossy_mappings.{token}"), token));
+            used.push((format!("lossy_mappings.{token}"), substitute));
+        }
+        for (token, substitutes) in schema.fallbacks.iter().flatten() {
+            used.push((format!("fallbacks.{token}"), token));
+            for substitute in substitutes {
+                used.push((format!("fallbacks.{token}"), substitute));
+            }
+        }
+        for (section, conjuncts) in [
+            ("conjuncts", &schema.conjuncts),
+            ("read_conjuncts", &schema.read_conjuncts),
+        ] {
+            for (text, sequence) in conjuncts.iter().flatten() {
+                for token in sequence {
+                    used.push((format!("{section}.{text}"), token));
+                }
+            }
+        }
+        for token in schema.hiatus.iter().flat_map(|hiatus| hiatus.forms.keys()) {
+            used.push((format!("hiatus.forms.{token}"), token));
+        }
+        if let Some(rules) = &schema.rules {
+            for (section, rules) in [("write", &rules.write), ("read", &rules.read)] {
+                for (i, rule) in rules.iter().enumerate() {
+                    let at = format!("rules.{section}[{i}]");
+                    for token in &rule.tokens {
+                        used.push((format!("{at}.tokens"), token));
+                    }
+                    // Lowercase names are token classes, checked when the rule is generated
+                    for (context, names) in [("before", &rule.before), ("after", &rule.after)] {
+                        for name in names.iter().flat_map(RuleContext::names) {
+                            if name.starts_with(char::is_uppercase) {
+                                used.push((format!("{at}.{context}"), name));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let kind = target.trim_end_matches("_tokens");
+        for (at, token) in used {
+            if !known.contains(token) {
+                offenders.push(format!(
+                    "  {}: at {at}: {token} is not an {kind} token",
+                    schema.path.display()
+                ));
+            }
+        }
+    }
+
+    if offenders.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Schemas use token names the hub token enums lack:\n{}\n\
+             Map the token in a category the enums are generated from (vowels, \
+             consonants, marks, special, digits, vedic, and vowel_signs in abugidas), \
+             or add it to schemas/abugida_tokens.yaml or schemas/alphabet_tokens.yaml",
+            offenders.join("\n")
+        ))
+    }
+}
+
+/// A hub token's stable ID, as a line of `build/token_ids.tsv`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenId {
+    pub id: u32,
+    /// `abugida` or `alphabet`
+    pub format: String,
+    pub name: String,
+}
+
+impl TokenId {
+    fn same_token(&self, other: &TokenId) -> bool {
+        self.format == other.format && self.name == other.name
+    }
+
+    /// The entry as a line of the registry, without the newline
+    pub fn line(&self) -> String {
+        format!("{}\t{}\t{}", self.id, self.format, self.name)
+    }
+}
+
+/// The ID 0, which no token is given, stands for unknown text
+pub const UNKNOWN_TOKEN_ID: u32 = 0;
+
+/// Read the entries of a token ID registry, skipping blank and `#` lines
+pub fn parse_token_ids(content: &str) -> Result<Vec<TokenId>, String> {
+    let mut ids: Vec<TokenId> = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        let entry = match fields.as_slice() {
+            [id, format @ ("abugida" | "alphabet"), name] => TokenId {
+                id: id
+                    .parse()
+                    .map_err(|e| format!("line {}: ID {id}: {e}", number + 1))?,
+                format: format.to_string(),
+                name: name.to_string(),
+            },
+            _ => {
+                return Err(format!(
+                    "line {}: expected `ID<tab>abugida|alphabet<tab>Name`, found `{line}`",
+                    number + 1
+                ))
+            }
+        };
+        if entry.id == UNKNOWN_TOKEN_ID {
+            return Err(format!(
+                "line {}: ID {UNKNOWN_TOKEN_ID} is kept for unknown text",
+                number + 1
+            ));
+        }
+        if let Some(other) = ids
+            .iter()
+            .find(|other| other.id == entry.id || other.same_token(&entry))
+        {
+            return Err(format!(
+                "line {}: {} repeats the ID or token of `{}`",
+                number + 1,
+                entry.line(),
+                other.line()
+            ));
+        }
+        ids.push(entry);
+    }
+    Ok(ids)
+}
+
+/// Give every token of `tokens` missing from `ids` the next free ID, returning
+/// the new entries
+///
+/// Existing entries are never changed or removed, so a token keeps its ID even
+/// after a schema stops using it. New tokens are numbered abugida first, each
+/// format in name order.
+pub fn assign_token_ids(ids: &mut Vec<TokenId>, tokens: &HubTokens) -> Vec<TokenId> {
+    let mut next = ids
+        .iter()
+        .map(|entry| entry.id)
+        .max()
+        .unwrap_or(UNKNOWN_TOKEN_ID)
+        + 1;
+    let mut added = Vec::new();
+    for (format, target) in [
+        ("abugida", "abugida_tokens"),
+        ("alphabet", "alphabet_tokens"),
+    ] {
+        for name in tokens.names(target) {
+            let known = ids
+                .iter()
+                .any(|entry| entry.format == format && entry.name == name);
+            if !known {
+                let entry = TokenId {
+                    id: next,
+                    format: format.to_string(),
+                    name: name.to_string(),
+                };
+                next += 1;
+                ids.push(entry.clone());
+                added.push(entry);
+            }
+        }
+    }
+    added
+}
@@ -0,0 +1,88 @@
+//! Time every supported script pair on a sample text
+//!
+//! The sample is written in each source script by converting it from
+//! Devanagari first, so every pair converts the same text. Pairs are listed
+//! slowest first, with their throughput.
+//!
+//! Run with `cargo run --release --example benchmark_pairs -- [ITERATIONS]`
+
+use shlesha::Shlesha;
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+const SAMPLE: &str = "धर्मक्षेत्रे कुरुक्षेत्रे समवेता युयुत्सवः। मामकाः पाण्डवाश्चैव किमकुर्वत सञ्जय॥";
+
+/// Time taken by one script pair
+pub struct PairTiming {
+    pub from: String,
+    pub to: String,
+    /// Bytes of source text converted
+    pub bytes: usize,
+    /// Total time over all iterations
+    pub elapsed: Duration,
+}
+
+impl PairTiming {
+    /// Source bytes converted per second, in MB/s
+    pub fn throughput(&self, iterations: u32) -> f64 {
+        (self.bytes as f64 * iterations as f64) / self.elapsed.as_secs_f64() / 1_000_000.0
+    }
+}
+
+/// Convert `sample` (Devanagari) between every supported pair `iterations` times
+pub fn benchmark(
+    shlesha: &Shlesha,
+    sample: &str,
+    iterations: u32,
+) -> Result<Vec<PairTiming>, Box<dyn Error>> {
+    let mut timings = Vec::new();
+    for pair in shlesha.capability_matrix() {
+        if !pair.supported {
+            continue;
+        }
+        let text = shlesha.transliterate(sample, "devanagari", &pair.from)?;
+        // Warm up lazily built tables
+        shlesha.transliterate(&text, &pair.from, &pair.to)?;
+
+        let start = Instant::now();
+        for _ in 0..iterations {
+            std::hint::black_box(shlesha.transliterate(
+                std::hint::black_box(&text),
+                &pair.from,
+                &pair.to,
+            )?);
+        }
+        timings.push(PairTiming {
+            bytes: text.len(),
+            elapsed: start.elapsed(),
+            from: pair.from,
+            to: pair.to,
+        });
+    }
+    timings.sort_by_key(|timing| std::cmp::Reverse(timing.elapsed));
+    Ok(timings)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let iterations = match std::env::args().nth(1) {
+        Some(arg) => arg.parse()?,
+        None => 100,
+    };
+    let timings = benchmark(&Shlesha::new(), SAMPLE, iterations)?;
+
+    println!("{} pairs, {iterations} iterations each\n", timings.len());
+    println!(
+        "{:<20} {:<20} {:>12} {:>10}",
+        "from", "to", "µs/call", "MB/s"
+    );
+    for timing in &timings {
+        println!(
+            "{:<20} {:<20} {:>12.2} {:>10.2}",
+            timing.from,
+            timing.to,
+            timing.elapsed.as_secs_f64() * 1e6 / iterations as f64,
+            timing.throughput(iterations)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! Plugging a hand-written converter into a Shlesha instance
+//!
+//! Schemas map strings to tokens one-to-one, which cannot express rules that depend
+//! on context. This example registers a token converter for a Hindi-style
+//! romanization that drops the word-final inherent vowel (schwa deletion):
+//! राम → "rām" instead of the Sanskrit "rāma".
+
+use shlesha::modules::hub::tokens::{AlphabetToken, HubToken, HubTokenSequence};
+use shlesha::modules::script_converter::{IastConverter, TokenConverter};
+use shlesha::Shlesha;
+
+/// IAST-based romanization with word-final schwa deletion
+struct HindiRomanConverter {
+    iast: IastConverter,
+}
+
+impl HindiRomanConverter {
+    fn new() -> Self {
+        Self {
+            iast: IastConverter::new(),
+        }
+    }
+
+    /// A final 'a' is dropped when it follows a consonant and ends the word
+    fn is_final_schwa(tokens: &HubTokenSequence, i: usize) -> bool {
+        let follows_consonant = i > 0 && tokens[i - 1].is_consonant();
+        let ends_word = match tokens.get(i + 1) {
+            None => true,
+            Some(HubToken::Alphabet(AlphabetToken::Unknown(s))) => {
+                s.chars().all(|c| !c.is_alphanumeric())
+            }
+            Some(_) => false,
+        };
+        // Keep the vowel in monosyllables like "na" so they stay readable
+        let is_monosyllable = i < 2 || !tokens[..i - 1].iter().any(|t| t.is_vowel());
+        follows_consonant && ends_word && !is_monosyllable
+    }
+}
+
+impl TokenConverter for HindiRomanConverter {
+    fn string_to_tokens(&self, input: &str) -> HubTokenSequence {
+        self.iast.string_to_tokens(input)
+    }
+
+    fn tokens_to_string(&self, tokens: &HubTokenSequence) -> String {
+        let kept: HubTokenSequence = tokens
+            .iter()
+            .enumerate()
+            .filter(|(i, token)| {
+                !(matches!(token, HubToken::Alphabet(AlphabetToken::VowelA))
+                    && Self::is_final_schwa(tokens, *i))
+            })
+            .map(|(_, token)| token.clone())
+            .collect();
+        self.iast.tokens_to_string(&kept)
+    }
+
+    fn script_name(&self) -> &'static str {
+        "hindi_roman"
+    }
+
+    fn is_alphabet(&self) -> bool {
+        true
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut transliterator = Shlesha::new();
+    transliterator.register_token_converter(Box::new(HindiRomanConverter::new()), &["hi_roman"]);
+
+    println!(
+        "Supports 'hindi_roman': {}",
+        transliterator.supports_script("hindi_roman")
+    );
+
+    for text in ["राम", "कमल नयन", "न", "भारत देश"] {
+        let sanskrit = transliterator.transliterate(text, "devanagari", "iast")?;
+        let hindi = transliterator.transliterate(text, "devanagari", "hi_roman")?;
+        println!("{text}: iast = {sanskrit}, hindi_roman = {hindi}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! A transliteration scheme defined in code
+//!
+//! Builds a plain-ASCII Roman scheme with [`SchemaBuilder`], adds it to a
+//! transliterator and converts between it and the built-in scripts in both
+//! directions. Mappings are keyed by hub token names; see the schemas/
+//! directory for the full set.
+//!
+//! Run with `cargo run --example custom_schema`
+
+use shlesha::modules::schema::{Schema, SchemaBuilder};
+use shlesha::Shlesha;
+
+/// A scheme that writes long vowels doubled and aspiration as `h`
+pub fn doubled_vowel_schema() -> Schema {
+    SchemaBuilder::new("doubled_vowels")
+        .description("ASCII Sanskrit with doubled long vowels")
+        .script_type("roman")
+        .add_vowel_mapping("VowelA", &["a"])
+        .add_vowel_mapping("VowelAa", &["aa"])
+        .add_vowel_mapping("VowelI", &["i"])
+        .add_vowel_mapping("VowelIi", &["ii"])
+        .add_vowel_mapping("VowelU", &["u"])
+        .add_vowel_mapping("VowelUu", &["uu"])
+        .add_vowel_mapping("VowelEe", &["e"])
+        .add_vowel_mapping("VowelOo", &["o"])
+        .add_consonant_mapping("ConsonantK", &["k"])
+        .add_consonant_mapping("ConsonantG", &["g"])
+        .add_consonant_mapping("ConsonantTt", &["t"])
+        .add_consonant_mapping("ConsonantDd", &["d"])
+        .add_consonant_mapping("ConsonantDdh", &["dh"])
+        .add_consonant_mapping("ConsonantNn", &["n"])
+        .add_consonant_mapping("ConsonantP", &["p"])
+        .add_consonant_mapping("ConsonantBh", &["bh"])
+        .add_consonant_mapping("ConsonantM", &["m"])
+        .add_consonant_mapping("ConsonantY", &["y"])
+        .add_consonant_mapping("ConsonantR", &["r"])
+        .add_consonant_mapping("ConsonantL", &["l"])
+        .add_consonant_mapping("ConsonantV", &["v"])
+        .add_consonant_mapping("ConsonantS", &["s"])
+        .add_consonant_mapping("ConsonantH", &["h"])
+        .build()
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut transliterator = Shlesha::new();
+    // Convert through the schema registry rather than compiling the schema
+    transliterator.disable_runtime_compiler();
+    transliterator.add_runtime_schema(doubled_vowel_schema())?;
+
+    for word in ["raama", "bhaarata", "deva", "viira", "puurna"] {
+        let devanagari = transliterator.transliterate(word, "doubled_vowels", "devanagari")?;
+        let back = transliterator.transliterate(&devanagari, "devanagari", "doubled_vowels")?;
+        let iast = transliterator.transliterate(word, "doubled_vowels", "iast")?;
+        println!("{word:10} → {devanagari} ({iast}) → {back}");
+    }
+    Ok(())
+}
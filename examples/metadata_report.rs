@@ -0,0 +1,79 @@
+//! Report what a conversion could not carry over
+//!
+//! Converts a file of mixed content (script text, Latin punctuation, symbols)
+//! with metadata collection on, and prints the output followed by the unknown
+//! tokens of each conversion stage and the distinctions the target merged.
+//!
+//! Run with `cargo run --example metadata_report -- [FILE] [FROM] [TO]`; the
+//! defaults convert examples/mixed_content.txt from Devanagari to IAST.
+
+use shlesha::{Shlesha, UnknownStage};
+use std::error::Error;
+use std::fmt::Write;
+
+/// The converted text followed by a report of its metadata
+pub fn report(text: &str, from: &str, to: &str) -> Result<String, Box<dyn Error>> {
+    let result = Shlesha::new().transliterate_with_metadata(text, from, to)?;
+    let mut report = format!("{}\n", result.output);
+    let Some(metadata) = result.metadata else {
+        return Ok(report);
+    };
+
+    if metadata.unknown_tokens.is_empty() {
+        writeln!(report, "\n{}", metadata.report())?;
+    }
+    for (stage, label) in [
+        (UnknownStage::SourceParsing, "not read from the source"),
+        (
+            UnknownStage::HubConversion,
+            "not converted between script families",
+        ),
+        (UnknownStage::TargetRendering, "not written in the target"),
+    ] {
+        // One line per character (or hub token, once past the source), in order
+        // of first appearance
+        let mut seen: Vec<(String, usize, usize)> = Vec::new();
+        for token in metadata.unknowns_in_stage(stage) {
+            let name = match &token.hub_token {
+                Some(hub) => hub.clone(),
+                None => format!("'{}' ({})", token.token, token.unicode),
+            };
+            match seen.iter_mut().find(|(seen, ..)| *seen == name) {
+                Some((_, _, count)) => *count += 1,
+                None => seen.push((name, token.position, 1)),
+            }
+        }
+        if seen.is_empty() {
+            continue;
+        }
+        writeln!(report, "\nToken(s) {label}:")?;
+        for (name, first, count) in seen {
+            writeln!(report, "  {name}: {count} time(s), first at {first}")?;
+        }
+    }
+
+    if metadata.is_lossy() {
+        writeln!(report, "\nDistinctions merged by {to}:")?;
+        for mapping in &metadata.lossy_mappings {
+            writeln!(
+                report,
+                "  token {:>4}  {} written as {}",
+                mapping.position, mapping.token, mapping.substitute
+            )?;
+        }
+    }
+    Ok(report)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().unwrap_or_else(|| {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/examples/mixed_content.txt").into()
+    });
+    let from = args.next().unwrap_or_else(|| "devanagari".into());
+    let to = args.next().unwrap_or_else(|| "iast".into());
+
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+    print!("{}", report(&text, &from, &to)?);
+    Ok(())
+}
@@ -47,8 +47,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Test loading all schemas from directory
     println!("\nLoading all schemas from schemas/ directory...");
     match registry.load_schemas_from_directory("schemas") {
-        Ok(count) => {
-            println!("✅ Successfully loaded {} schemas", count);
+        Ok(report) => {
+            println!("✅ Successfully loaded {} schemas", report.loaded);
+            for failure in &report.failures {
+                println!("❌ {}", failure);
+            }
 
             println!("\nAll available schemas:");
             for schema_name in registry.list_schemas() {
@@ -0,0 +1,19 @@
+//! Arbitrary YAML through the schema loader, then through the loaded schema
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use shlesha::Shlesha;
+
+fuzz_target!(|yaml: &str| {
+    let mut shlesha = Shlesha::new();
+    if shlesha.load_schema_from_string(yaml, "fuzzed").is_err() {
+        return;
+    }
+
+    // A schema that loads must also convert without panicking or hanging
+    for other in ["devanagari", "iast"] {
+        let _ = shlesha.transliterate("dharma धर्म ā3 [VowelA]", "fuzzed", other);
+        let _ = shlesha.transliterate("dharma धर्म ā3 [VowelA]", other, "fuzzed");
+    }
+});
@@ -0,0 +1,25 @@
+//! Arbitrary UTF-8 through a converter between two built-in scripts
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use once_cell::sync::Lazy;
+use shlesha::Shlesha;
+
+static SHLESHA: Lazy<Shlesha> = Lazy::new(Shlesha::new);
+static SCRIPTS: Lazy<Vec<String>> = Lazy::new(|| SHLESHA.list_supported_scripts());
+
+fuzz_target!(|data: &[u8]| {
+    // The first two bytes pick the script pair, the rest is the text
+    let [from, to, text @ ..] = data else {
+        return;
+    };
+    let Ok(text) = std::str::from_utf8(text) else {
+        return;
+    };
+
+    let from = &SCRIPTS[*from as usize % SCRIPTS.len()];
+    let to = &SCRIPTS[*to as usize % SCRIPTS.len()];
+    let _ = SHLESHA.transliterate(text, from, to);
+    let _ = SHLESHA.transliterate_with_metadata(text, from, to);
+});
@@ -0,0 +1,53 @@
+#!/usr/bin/env python3
+"""
+Binding-level benchmarks of the Python transliteration paths
+
+Compares transliterate, transliterate_fast and transliterate_with_metadata on
+the same inputs, so the cost of the metadata path and of the profiler and
+statistics wrapper shows per call. Needs pytest-benchmark and a built wheel:
+
+    maturin develop --release --features python
+    pytest python_benchmarks/test_binding_benchmarks.py -m benchmark
+
+Without pytest-benchmark installed the module is skipped.
+"""
+
+import pytest
+
+pytest.importorskip("pytest_benchmark")
+shlesha = pytest.importorskip("shlesha")
+
+pytestmark = pytest.mark.benchmark
+
+CASES = {
+    "short": ("धर्म", "devanagari", "iast"),
+    "sentence": ("धर्मक्षेत्रे कुरुक्षेत्रे समवेता युयुत्सवः", "devanagari", "iast"),
+    "paragraph": ("dharmakṣetre kurukṣetre samavetā yuyutsavaḥ " * 50, "iast", "telugu"),
+}
+
+
+@pytest.fixture(scope="module")
+def transliterator():
+    return shlesha.Shlesha()
+
+
+@pytest.mark.parametrize("case", CASES)
+def test_transliterate(benchmark, transliterator, case):
+    text, source, target = CASES[case]
+    benchmark.group = case
+    benchmark(transliterator.transliterate, text, source, target)
+
+
+@pytest.mark.parametrize("case", CASES)
+def test_transliterate_fast(benchmark, transliterator, case):
+    text, source, target = CASES[case]
+    benchmark.group = case
+    output = benchmark(transliterator.transliterate_fast, text, source, target)
+    assert output == transliterator.transliterate(text, source, target)
+
+
+@pytest.mark.parametrize("case", CASES)
+def test_transliterate_with_metadata(benchmark, transliterator, case):
+    text, source, target = CASES[case]
+    benchmark.group = case
+    benchmark(transliterator.transliterate_with_metadata, text, source, target)
@@ -0,0 +1,130 @@
+"""Type stubs for the shlesha extension module
+
+Maintained by hand alongside src/python_bindings.rs; tests/python_stub_tests.rs
+and tests/test_type_stubs.py fail when the two disagree. maturin ships this
+file in the wheel as shlesha/__init__.pyi, with a py.typed marker.
+
+Lone surrogates in text to convert are read as U+FFFD and reported as
+unknown tokens of kind "invalid_encoding". Failed conversions and schema
+loads raise RuntimeError; an unknown processor type passed to
+Shlesha.benchmark_processor raises ValueError.
+"""
+
+from typing import Literal, TypedDict, final
+
+__version__: str
+__output_version__: str
+__author__: str
+__description__: str
+
+class SchemaInfo(TypedDict):
+    """What get_schema_info and reload_schema return"""
+
+    name: str
+    description: str
+    script_type: str
+    is_runtime_loaded: bool
+    mapping_count: int
+    revision: int
+    direction: Literal["ltr", "rtl"]
+    rendering_notes: str | None
+
+class Stats(TypedDict):
+    """What Shlesha.stats returns"""
+
+    conversions: int
+    input_bytes: int
+    output_bytes: int
+    errors: int
+    pairs: dict[tuple[str, str], int]
+
+@final
+class UnknownToken:
+    """A character a conversion could not map"""
+
+    @property
+    def script(self) -> str: ...
+    @property
+    def token(self) -> str: ...
+    @property
+    def position(self) -> int: ...
+    @property
+    def unicode(self) -> str: ...
+    @property
+    def is_extension(self) -> bool: ...
+    @property
+    def stage(self) -> Literal["source_parsing", "hub_conversion", "target_rendering"]: ...
+    @property
+    def kind(self) -> Literal["unmapped", "invalid_encoding"]: ...
+
+@final
+class TransliterationMetadata:
+    """The unknown tokens of a conversion"""
+
+    @property
+    def source_script(self) -> str: ...
+    @property
+    def target_script(self) -> str: ...
+    @property
+    def used_extensions(self) -> str: ...
+    @property
+    def unknown_tokens(self) -> list[UnknownToken]: ...
+    @property
+    def total_unknown_count(self) -> int: ...
+    @property
+    def truncated(self) -> bool: ...
+
+@final
+class TransliterationResult:
+    """The output of transliterate_with_metadata"""
+
+    @property
+    def output(self) -> str: ...
+    @property
+    def metadata(self) -> TransliterationMetadata | None: ...
+
+@final
+class Shlesha:
+    """A transliterator with every built-in script"""
+
+    def __init__(self) -> None: ...
+    def transliterate(self, text: str, from_script: str, to_script: str) -> str:
+        """Raises RuntimeError if the conversion fails"""
+    def transliterate_fast(self, text: str, from_script: str, to_script: str) -> str:
+        """Raises RuntimeError if the conversion fails"""
+    def transliterate_with_metadata(
+        self, text: str, from_script: str, to_script: str
+    ) -> TransliterationResult:
+        """Raises RuntimeError if the conversion fails"""
+    def list_supported_scripts(self) -> list[str]: ...
+    def supports_script(self, script: str) -> bool: ...
+    def tokenize_ids(self, text: str, script: str) -> list[int]:
+        """Raises RuntimeError if the script is not supported"""
+    def load_schema_from_file(self, file_path: str) -> None:
+        """Raises RuntimeError if the schema cannot be loaded"""
+    def load_schema_from_string(self, yaml_content: str, schema_name: str) -> None:
+        """Raises RuntimeError if the schema cannot be loaded"""
+    def get_schema_info(self, script_name: str) -> SchemaInfo | None: ...
+    def reload_schema(self, name: str, yaml_content: str) -> SchemaInfo:
+        """Raises RuntimeError if the schema is not loaded or the YAML is invalid"""
+    def stats(self) -> Stats: ...
+    def reset_stats(self) -> None: ...
+    def remove_schema(self, script_name: str) -> bool: ...
+    def clear_runtime_schemas(self) -> None: ...
+    def get_script_info(self) -> dict[str, str]: ...
+    def benchmark_processor(
+        self,
+        text: str,
+        processor_type: Literal["fx_hashmap", "aho_corasick", "fast_lookup"],
+        mappings: dict[str, str],
+    ) -> str:
+        """Raises ValueError for an unknown processor type, RuntimeError if processing fails"""
+
+PyShlesha = Shlesha
+
+def create_transliterator() -> Shlesha: ...
+def transliterate(text: str, from_script: str, to_script: str) -> str:
+    """Raises RuntimeError if the conversion fails"""
+
+def get_supported_scripts() -> list[str]: ...
+def get_token_ids() -> dict[int, tuple[Literal["abugida", "alphabet"], str]]: ...
@@ -0,0 +1,230 @@
+//! `shlesha bench`: time the conversion of a file
+//!
+//! The file is read once, converted `warmup` times untimed, then converted
+//! `iterations` times, each timed with the monotonic clock. A pair with a
+//! direct converter is timed through it and again through the hub. With the
+//! `count-allocations` feature a counting global allocator also reports the
+//! allocations of a conversion.
+
+use serde_json::json;
+use shlesha::{Shlesha, Transliterator};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "count-allocations")]
+mod allocations {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    /// Allocations made so far
+    pub fn count() -> Option<usize> {
+        Some(ALLOCATIONS.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(not(feature = "count-allocations"))]
+mod allocations {
+    /// Allocations are not counted without the `count-allocations` feature
+    pub fn count() -> Option<usize> {
+        None
+    }
+}
+
+/// How many conversions to run
+#[derive(Clone, Copy)]
+pub struct Runs {
+    pub warmup: u32,
+    pub iterations: u32,
+}
+
+/// The timings of one conversion path
+pub struct PathTiming {
+    /// `direct` or `hub`
+    pub path: &'static str,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    /// Allocations per conversion, when counted
+    pub allocations: Option<usize>,
+}
+
+/// The timings of a file's conversion by each path the pair has
+pub struct Report {
+    pub from: String,
+    pub to: String,
+    pub bytes: usize,
+    pub chars: usize,
+    pub runs: Runs,
+    pub paths: Vec<PathTiming>,
+}
+
+/// Time converting `input` from `from` to `to`
+///
+/// The path `transliterate` takes is timed first; when that is a direct
+/// converter, the hub path is timed after it.
+pub fn run(
+    mut shlesha: Shlesha,
+    input: &str,
+    from: &str,
+    to: &str,
+    runs: Runs,
+) -> Result<Report, Box<dyn std::error::Error>> {
+    let mut paths = Vec::new();
+    if shlesha.has_direct_converter(from, to) && !shlesha.force_hub() {
+        paths.push(time_path("direct", &shlesha, input, from, to, runs)?);
+        shlesha.set_force_hub(true);
+    }
+    paths.push(time_path("hub", &shlesha, input, from, to, runs)?);
+
+    Ok(Report {
+        from: from.to_string(),
+        to: to.to_string(),
+        bytes: input.len(),
+        chars: input.chars().count(),
+        runs,
+        paths,
+    })
+}
+
+/// Time a conversion through the `Transliterator` trait, as an application
+/// holding a `dyn Transliterator` would run it
+fn time_path(
+    path: &'static str,
+    transliterator: &dyn Transliterator,
+    input: &str,
+    from: &str,
+    to: &str,
+    runs: Runs,
+) -> Result<PathTiming, Box<dyn std::error::Error>> {
+    for _ in 0..runs.warmup {
+        black_box(transliterator.transliterate(black_box(input), from, to)?);
+    }
+
+    let mut times = Vec::with_capacity(runs.iterations as usize);
+    let allocations_before = allocations::count();
+    for _ in 0..runs.iterations {
+        let start = Instant::now();
+        let output = transliterator.transliterate(black_box(input), from, to)?;
+        times.push(start.elapsed());
+        // Dropped outside the timing, as the caller would
+        black_box(output);
+    }
+    let allocations = allocations::count()
+        .zip(allocations_before)
+        .map(|(after, before)| after.saturating_sub(before) / runs.iterations as usize);
+
+    times.sort();
+    Ok(PathTiming {
+        path,
+        min: times[0],
+        median: median(&times),
+        // Nearest rank
+        p95: times[(times.len() * 95).div_ceil(100) - 1],
+        allocations,
+    })
+}
+
+/// The median of sorted `times`
+fn median(times: &[Duration]) -> Duration {
+    let middle = times.len() / 2;
+    if times.len().is_multiple_of(2) {
+        (times[middle - 1] + times[middle]) / 2
+    } else {
+        times[middle]
+    }
+}
+
+/// `amount` per second, taking `time` at least a nanosecond
+fn per_second(amount: usize, time: Duration) -> f64 {
+    amount as f64 / time.as_secs_f64().max(1e-9)
+}
+
+impl PathTiming {
+    /// The reported statistics, by name
+    fn statistics(&self) -> [(&'static str, Duration); 3] {
+        [
+            ("min", self.min),
+            ("median", self.median),
+            ("p95", self.p95),
+        ]
+    }
+}
+
+impl Report {
+    pub fn json(&self) -> serde_json::Value {
+        let paths: Vec<_> = self
+            .paths
+            .iter()
+            .map(|timing| {
+                let mut path = json!({
+                    "path": timing.path,
+                    "allocations": timing.allocations,
+                });
+                for (name, time) in timing.statistics() {
+                    path[name] = json!({
+                        "seconds": time.as_secs_f64(),
+                        "mb_per_s": per_second(self.bytes, time) / 1e6,
+                        "chars_per_s": per_second(self.chars, time),
+                    });
+                }
+                path
+            })
+            .collect();
+        json!({
+            "from": self.from,
+            "to": self.to,
+            "input_bytes": self.bytes,
+            "input_chars": self.chars,
+            "warmup": self.runs.warmup,
+            "iterations": self.runs.iterations,
+            "paths": paths,
+        })
+    }
+
+    pub fn print(&self) {
+        println!(
+            "{} → {}: {} bytes ({} chars), {} iterations after {} warmup",
+            self.from, self.to, self.bytes, self.chars, self.runs.iterations, self.runs.warmup
+        );
+        for timing in &self.paths {
+            println!("{}:", timing.path);
+            for (name, time) in timing.statistics() {
+                println!(
+                    "  {name:<6} {:>10.3} ms {:>9.2} MB/s {:>12.0} chars/s",
+                    time.as_secs_f64() * 1e3,
+                    per_second(self.bytes, time) / 1e6,
+                    per_second(self.chars, time)
+                );
+            }
+            if let Some(allocations) = timing.allocations {
+                println!("  {allocations} allocations per conversion");
+            }
+        }
+        if let [direct, hub] = &self.paths[..] {
+            println!(
+                "{} is {:.2}× as fast as {} (median)",
+                direct.path,
+                hub.median.as_secs_f64() / direct.median.as_secs_f64().max(1e-9),
+                hub.path
+            );
+        }
+    }
+}
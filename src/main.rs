@@ -1,14 +1,31 @@
 //! Simple CLI for Shlesha transliterator
 
-use clap::{Parser, Subcommand};
-use shlesha::Shlesha;
+use clap::builder::PossibleValuesParser;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use shlesha::modules::schema::scaffold::{diff_schemas, scaffold_schema};
+#[cfg(feature = "bench")]
+mod bench;
+#[cfg(feature = "repl")]
+mod repl;
+
+use shlesha::modules::quality::require_fidelity;
+use shlesha::{
+    Capitalization, ConversionWarning, Hyphenation, LossyMapping, MarkerOutput, NormalizationKind,
+    QualityError, SelectivePolicy, Shlesha, SinhalaConjuncts, TransliterationMetadata,
+    TransliterationOptions, Transliterator, UnknownStage, UnknownToken,
+};
 
 #[derive(Parser)]
 #[command(name = "shlesha")]
 #[command(about = "High-performance extensible transliteration", long_about = None)]
+#[command(arg_required_else_help = true)]
 struct Cli {
+    /// Print the documentation of every command as Markdown, for packaging
+    #[arg(long, hide = true)]
+    markdown_help: bool,
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
@@ -30,38 +47,682 @@ enum Commands {
         /// Show detailed metadata breakdown
         #[arg(short, long)]
         verbose: bool,
+        /// Read a 3 after a vowel as the Vedic pluta mark
+        #[arg(long)]
+        vedic: bool,
+        /// Convert only Sanskrit words of mixed-language Roman text
+        #[arg(long)]
+        selective: bool,
+        /// File of words to also convert in selective mode, one per line
+        #[arg(long, value_name = "FILE", requires = "selective")]
+        selective_words: Option<std::path::PathBuf>,
+        /// Read ITRANS words written entirely in capitals as properly-cased ITRANS
+        #[arg(long)]
+        itrans_legacy_caps: bool,
+        /// Capitalize the first letter of each sentence or word of IAST or ISO-15919 output
+        #[arg(long, value_enum, value_name = "WHICH")]
+        capitalize: Option<CapitalizeArg>,
+        /// Read hyphens between the members of compounds as part of the word,
+        /// keeping them in the output or leaving them out
+        #[arg(long, value_enum, value_name = "OUTPUT", conflicts_with = "selective")]
+        hyphens: Option<HyphensArg>,
+        /// Write Sinhala clusters as touching letters, as Pali printing does
+        #[arg(long)]
+        sinhala_touching: bool,
+        /// Write Devanagari e, ai, o and au with the prishthamatra (ॎ), as old books do
+        #[arg(long)]
+        prishthamatra: bool,
+        /// Write word-final న్ in Telugu as the nakara pollu (ౝ)
+        #[arg(long)]
+        nakara_pollu: bool,
+        /// Write the short vowels and jazm (harakat) in Urdu
+        #[arg(long)]
+        harakat: bool,
+        /// Read malformed vowel sequences in Indic input as written, without repairing them
+        #[arg(long)]
+        no_repair: bool,
+        /// Keep the whitespace around stdin input, and add no newline after the output
+        #[arg(long)]
+        raw: bool,
+        /// Convert each line of the input on its own, writing the lines converted
+        /// with less fidelity, the share of tokens converted without loss, to the
+        /// errors file instead of the output
+        #[arg(
+            long,
+            value_name = "FIDELITY",
+            value_parser = parse_fidelity,
+            conflicts_with_all = ["selective", "verbose", "raw"]
+        )]
+        min_fidelity: Option<f64>,
+        /// File the lines below --min-fidelity are written to; standard error if not given
+        #[arg(long, value_name = "FILE", requires = "min_fidelity")]
+        errors: Option<std::path::PathBuf>,
+        /// Output format; json writes one object with the output and its metadata
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Convert lines as they are typed, with commands to change scripts
+    #[cfg(feature = "repl")]
+    Repl {
+        /// Source script (e.g., devanagari, iso)
+        #[arg(short, long)]
+        from: String,
+        /// Target script (e.g., devanagari, iso)
+        #[arg(short, long)]
+        to: String,
+    },
+    /// Time the conversion of a file, reporting throughput
+    ///
+    /// Pairs with a direct converter are timed through it and through the hub.
+    #[cfg(feature = "bench")]
+    Bench {
+        /// Source script (e.g., devanagari, iso)
+        #[arg(short, long)]
+        from: String,
+        /// Target script (e.g., devanagari, iso)
+        #[arg(short, long)]
+        to: String,
+        /// File to convert, read once
+        #[arg(long, value_name = "FILE")]
+        input: std::path::PathBuf,
+        /// Timed conversions
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+        /// Untimed conversions before them
+        #[arg(long, default_value_t = 2)]
+        warmup: u32,
+        /// Output format; json writes one object with the timings of each path
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
     },
     /// List supported scripts
-    Scripts,
+    Scripts {
+        /// Output format; json writes an array of scripts
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// List script pairs and whether conversion between them loses distinctions
+    Pairs {
+        /// Only list pairs whose conversion is lossy
+        #[arg(long)]
+        lossy_only: bool,
+    },
+    /// Compare two texts in a script token by token, reporting where they differ
+    ///
+    /// Exits with status 1 when the texts differ.
+    Compare {
+        /// Script both texts are written in
+        #[arg(short, long)]
+        script: String,
+        /// The text expected
+        expected: String,
+        /// The text to check against it
+        actual: String,
+    },
+    /// Run conformance case files (YAML/TOML) from a directory
+    TestData {
+        /// Directory of case files, searched recursively
+        dir: std::path::PathBuf,
+    },
+    /// Help with writing script schemas
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommands,
+    },
+    /// Print a completion script for a shell
+    #[command(hide = true)]
+    Completions { shell: Shell },
+    /// Print the supported script names, one per line, for shell completion
+    #[command(name = "__complete-scripts", hide = true)]
+    CompleteScripts,
+}
+
+#[derive(Subcommand)]
+enum SchemaCommands {
+    /// Print a skeleton schema listing every hub token
+    New {
+        /// Name of the new script
+        name: String,
+        /// Kind of script the schema describes
+        #[arg(long = "type", value_parser = ["brahmic", "roman"])]
+        script_type: String,
+        /// Fill in the tokens an existing script maps with its text
+        #[arg(long, value_name = "SCRIPT")]
+        like: Option<String>,
+    },
+    /// List the tokens one schema file maps and the other does not
+    Diff {
+        first: std::path::PathBuf,
+        second: std::path::PathBuf,
+    },
+    /// Load schema files, or directories of them, and report any errors
+    Validate {
+        #[arg(required = true)]
+        paths: Vec<std::path::PathBuf>,
+    },
+    /// Run the cases of a schema file's `tests` section
+    Test { file: std::path::PathBuf },
+}
+
+/// Which letters `transliterate --capitalize` writes as capitals
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CapitalizeArg {
+    /// The first letter of each sentence
+    Sentences,
+    /// The first letter of each word
+    Words,
+}
+
+/// What `transliterate --hyphens` does with the hyphens of compounds
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum HyphensArg {
+    /// Write them back between the members, as in tat-tvam-asi
+    Keep,
+    /// Leave them out, as in tattvamasi
+    Strip,
+}
+
+/// How a subcommand writes its results, and its errors
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Text for people to read
+    Text,
+    /// JSON for other programs; errors are written to stderr as `{"error": "..."}`
+    Json,
+}
+
+/// Stands for the script names in generated completions until replaced
+const SCRIPTS_PLACEHOLDER: &str = "__SHLESHA_SCRIPTS__";
+
+/// The completion script for `shell`
+///
+/// Bash, zsh and fish complete script names for --from, --to and --like by
+/// asking `shlesha __complete-scripts` as they complete, so schemas loaded at
+/// runtime are offered too. The PowerShell and Elvish scripts complete
+/// commands and options but no option values.
+fn completion_script(shell: Shell) -> String {
+    let (placeholder, hook) = match shell {
+        Shell::Bash => (
+            SCRIPTS_PLACEHOLDER.to_string(),
+            "$(shlesha __complete-scripts 2>/dev/null)",
+        ),
+        Shell::Zsh => (
+            format!("({SCRIPTS_PLACEHOLDER})"),
+            "{compadd -- $(shlesha __complete-scripts 2>/dev/null)}",
+        ),
+        Shell::Fish => (
+            SCRIPTS_PLACEHOLDER.to_string(),
+            "(shlesha __complete-scripts 2>/dev/null)",
+        ),
+        _ => (SCRIPTS_PLACEHOLDER.to_string(), ""),
+    };
+    let script_names =
+        |arg: clap::Arg| arg.value_parser(PossibleValuesParser::new([SCRIPTS_PLACEHOLDER]));
+
+    let command = Cli::command()
+        .mut_subcommand("transliterate", |transliterate| {
+            transliterate
+                .mut_arg("from", script_names)
+                .mut_arg("to", script_names)
+        })
+        .mut_subcommand("compare", |compare| compare.mut_arg("script", script_names))
+        .mut_subcommand("schema", |schema| {
+            schema.mut_subcommand("new", |new| new.mut_arg("like", script_names))
+        });
+    #[cfg(feature = "bench")]
+    let command = command.mut_subcommand("bench", |bench| {
+        bench
+            .mut_arg("from", script_names)
+            .mut_arg("to", script_names)
+    });
+    #[cfg(feature = "repl")]
+    let command = command.mut_subcommand("repl", |repl| {
+        repl.mut_arg("from", script_names)
+            .mut_arg("to", script_names)
+    });
+    let mut command = command;
+    let mut output = Vec::new();
+    clap_complete::generate(shell, &mut command, "shlesha", &mut output);
+    String::from_utf8(output)
+        .expect("completion scripts are UTF-8")
+        .replace(&placeholder, hook)
+}
+
+/// `text` with the `spans` of it that differ set off, in `color` on a terminal and in brackets otherwise
+fn mark_spans(text: &str, spans: &[std::ops::Range<usize>], color: Option<&str>) -> String {
+    let mut marked = String::with_capacity(text.len() + spans.len() * 10);
+    let mut last = 0;
+    for span in spans {
+        marked.push_str(&text[last..span.start]);
+        match color {
+            Some(color) => marked.push_str(&format!("\x1b[{color}m{}\x1b[0m", &text[span.clone()])),
+            None => marked.push_str(&format!("[{}]", &text[span.clone()])),
+        }
+        last = span.end;
+    }
+    marked.push_str(&text[last..]);
+    marked
+}
+
+/// Print where `actual` differs from `expected`, colored on a terminal unless NO_COLOR is set
+fn print_comparison(diff: &shlesha::TransliterationDiff, expected: &str, actual: &str) {
+    use std::io::IsTerminal;
+
+    if diff.is_equal() {
+        println!("The texts read as the same {} tokens", diff.script);
+        return;
+    }
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let (red, green) = if color {
+        (Some("31"), Some("32"))
+    } else {
+        (None, None)
+    };
+
+    let differences: Vec<_> = diff.differences().collect();
+    for edit in &differences {
+        println!("{edit}");
+    }
+    let expected_spans: Vec<_> = differences
+        .iter()
+        .map(|edit| edit.expected_span.clone())
+        .collect();
+    let actual_spans: Vec<_> = differences
+        .iter()
+        .map(|edit| edit.actual_span.clone())
+        .collect();
+    println!();
+    println!("expected: {}", mark_spans(expected, &expected_spans, red));
+    println!("actual:   {}", mark_spans(actual, &actual_spans, green));
+    println!();
+    println!(
+        "{} difference{}",
+        differences.len(),
+        if differences.len() == 1 { "" } else { "s" }
+    );
+}
+
+/// Report an error in `format` and exit
+fn fail(format: OutputFormat, message: impl std::fmt::Display) -> ! {
+    match format {
+        OutputFormat::Text => eprintln!("Error: {message}"),
+        OutputFormat::Json => {
+            eprintln!("{}", serde_json::json!({ "error": message.to_string() }))
+        }
+    }
+    std::process::exit(1);
+}
+
+/// The name of an unknown token's stage in JSON output
+fn stage_name(stage: UnknownStage) -> &'static str {
+    match stage {
+        UnknownStage::SourceParsing => "source_parsing",
+        UnknownStage::HubConversion => "hub_conversion",
+        UnknownStage::TargetRendering => "target_rendering",
+    }
+}
+
+/// A conversion's output and metadata as one JSON object
+fn transliteration_json(
+    input: &str,
+    output: &str,
+    from: &str,
+    to: &str,
+    metadata: Option<&TransliterationMetadata>,
+) -> serde_json::Value {
+    use serde_json::json;
+
+    let unknown_tokens: Vec<_> = metadata
+        .map(|metadata| {
+            metadata
+                .unknown_tokens
+                .iter()
+                .map(unknown_token_json)
+                .collect()
+        })
+        .unwrap_or_default();
+    let lossy_mappings: Vec<_> = metadata
+        .map(|metadata| {
+            metadata
+                .lossy_mappings
+                .iter()
+                .map(lossy_mapping_json)
+                .collect()
+        })
+        .unwrap_or_default();
+    let warnings: Vec<_> = metadata
+        .map(|metadata| metadata.warnings.iter().map(warning_json).collect())
+        .unwrap_or_default();
+    let provenance = metadata
+        .and_then(|metadata| metadata.provenance.as_ref())
+        .map(|provenance| {
+            json!({
+                "crate_version": provenance.crate_version,
+                "output_version": provenance.output_version,
+                "runtime_schemas": provenance
+                    .runtime_schemas
+                    .iter()
+                    .map(|schema| json!({ "name": schema.name, "version": schema.version }))
+                    .collect::<Vec<_>>(),
+            })
+        });
+
+    json!({
+        "output": output,
+        "from": from,
+        "to": to,
+        "unknown_tokens": unknown_tokens,
+        "lossy_mappings": lossy_mappings,
+        "warnings": warnings,
+        "provenance": provenance,
+        "stats": {
+            "input_bytes": input.len(),
+            "output_bytes": output.len(),
+            "unknown_count": metadata.map_or(0, |metadata| metadata.total_unknown_count),
+            "unknown_truncated": metadata.is_some_and(|metadata| metadata.truncated),
+            "lossy_count": lossy_mappings.len(),
+        },
+    })
+}
+
+fn warning_json(warning: &ConversionWarning) -> serde_json::Value {
+    match warning {
+        ConversionWarning::MixedScriptInput { detected, fraction } => serde_json::json!({
+            "kind": "mixed_script_input",
+            "detected": detected,
+            "fraction": fraction,
+        }),
+    }
+}
+
+fn unknown_token_json(token: &UnknownToken) -> serde_json::Value {
+    serde_json::json!({
+        "token": token.token.to_string(),
+        "position": token.position,
+        "unicode": token.unicode,
+        "stage": stage_name(token.stage),
+        "script": token.script,
+        "hub_token": token.hub_token,
+    })
+}
+
+fn lossy_mapping_json(mapping: &LossyMapping) -> serde_json::Value {
+    serde_json::json!({
+        "token": mapping.token,
+        "substitute": mapping.substitute,
+        "position": mapping.position,
+    })
+}
+
+/// A fidelity between 0 and 1, for --min-fidelity
+fn parse_fidelity(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(fidelity) if (0.0..=1.0).contains(&fidelity) => Ok(fidelity),
+        Ok(_) => Err("must be between 0 and 1".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Convert `input` a line at a time, setting aside the lines below `min_fidelity`
+///
+/// Lines that pass are written to stdout, as text or as one JSON object each.
+/// The others go to `errors`, or stderr: in text, a line of their line number,
+/// fidelity and input separated by tabs; in JSON, an object with the output
+/// and the unknown tokens and lossy mappings that lowered the fidelity.
+fn convert_lines_with_min_fidelity(
+    transliterator: &Shlesha,
+    input: &str,
+    (from, to): (&str, &str),
+    options: &TransliterationOptions,
+    min_fidelity: f64,
+    errors: Option<&std::path::Path>,
+    format: OutputFormat,
+) {
+    use std::io::Write;
+
+    let mut errors: Box<dyn Write> = match errors {
+        Some(path) => match std::fs::File::create(path) {
+            Ok(file) => Box::new(std::io::BufWriter::new(file)),
+            Err(e) => fail(
+                format,
+                format_args!("cannot create {}: {e}", path.display()),
+            ),
+        },
+        None => Box::new(std::io::stderr()),
+    };
+    for (index, line) in input.lines().enumerate() {
+        let converted = transliterator
+            .transliterate_with_options_and_metadata(line, from, to, options)
+            .map_err(QualityError::Conversion)
+            .and_then(|result| require_fidelity(result, min_fidelity));
+        let written = match converted {
+            Ok(output) => {
+                match format {
+                    OutputFormat::Text => println!("{output}"),
+                    OutputFormat::Json => {
+                        println!("{}", transliteration_json(line, &output, from, to, None))
+                    }
+                }
+                Ok(())
+            }
+            Err(QualityError::BelowThreshold {
+                fidelity,
+                unknown_tokens,
+                unknown_count,
+                lossy_mappings,
+                lossy_count,
+                output,
+                ..
+            }) => match format {
+                OutputFormat::Text => writeln!(errors, "{}\t{fidelity:.4}\t{line}", index + 1),
+                OutputFormat::Json => {
+                    let entry = serde_json::json!({
+                        "line": index + 1,
+                        "input": line,
+                        "output": output,
+                        "fidelity": fidelity,
+                        "unknown_tokens": unknown_tokens.iter().map(unknown_token_json).collect::<Vec<_>>(),
+                        "unknown_count": unknown_count,
+                        "lossy_mappings": lossy_mappings.iter().map(lossy_mapping_json).collect::<Vec<_>>(),
+                        "lossy_count": lossy_count,
+                    });
+                    writeln!(errors, "{entry}")
+                }
+            },
+            Err(e) => fail(format, e),
+        };
+        if let Err(e) = written {
+            fail(format, format_args!("cannot write errors: {e}"));
+        }
+    }
+    if let Err(e) = errors.flush() {
+        fail(format, format_args!("cannot write errors: {e}"));
+    }
+}
+
+/// A description of the built-in scripts, for the scripts listing
+fn script_description(name: &str) -> Option<&'static str> {
+    match name {
+        "iast" => Some("IAST (International Alphabet of Sanskrit Transliteration)"),
+        "itrans" => Some("ITRANS (ASCII transliteration)"),
+        "slp1" => Some("SLP1 (Sanskrit Library Phonetic scheme)"),
+        "harvard_kyoto" => Some("Harvard-Kyoto (ASCII-based academic standard)"),
+        "velthuis" => Some("Velthuis (TeX-based notation)"),
+        "wx" => Some("WX (Computational notation)"),
+        "devanagari" => Some("Devanagari script (देवनागरी)"),
+        "bengali" => Some("Bengali script (বাংলা)"),
+        "tamil" => Some("Tamil script (தமிழ்)"),
+        "telugu" => Some("Telugu script (తెలుగు)"),
+        "gujarati" => Some("Gujarati script (ગુજરાતી)"),
+        "gurmukhi" => Some("Gurmukhi script (ਗੁਰਮੁਖੀ)"),
+        "kannada" => Some("Kannada script (ಕನ್ನಡ)"),
+        "malayalam" => Some("Malayalam script (മലയാളം)"),
+        "odia" => Some("Odia script (ଓଡ଼ିଆ)"),
+        "sinhala" => Some("Sinhala script (සිංහල)"),
+        "iso15919" => Some("ISO-15919 (International standard)"),
+        _ => None,
+    }
+}
+
+/// Read and parse a schema file, exiting on failure
+fn read_schema(path: &std::path::Path) -> shlesha::modules::schema::Schema {
+    let parsed = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|yaml| {
+            shlesha::modules::schema::Schema::from_yaml_str(&yaml).map_err(|e| e.to_string())
+        });
+    match parsed {
+        Ok(schema) => schema,
+        Err(e) => {
+            eprintln!("Error: cannot read {}: {e}", path.display());
+            std::process::exit(1);
+        }
+    }
 }
 
 fn main() {
     let cli = Cli::parse();
-    let transliterator = Shlesha::new();
+    if cli.markdown_help {
+        clap_markdown::print_help_markdown::<Cli>();
+        return;
+    }
+    let Some(command) = cli.command else {
+        // arg_required_else_help leaves no other way to get here
+        Cli::command().print_help().ok();
+        std::process::exit(2);
+    };
+    let mut transliterator = Shlesha::new();
 
-    match cli.command {
+    match command {
         Commands::Transliterate {
             from,
             to,
             text,
             verbose,
+            vedic,
+            selective,
+            selective_words,
+            itrans_legacy_caps,
+            capitalize,
+            hyphens,
+            sinhala_touching,
+            prishthamatra,
+            nakara_pollu,
+            harakat,
+            no_repair,
+            raw,
+            min_fidelity,
+            errors,
+            format,
         } => {
+            transliterator.set_vedic_mode(vedic);
+            if sinhala_touching {
+                transliterator.set_sinhala_conjuncts(SinhalaConjuncts::Touching);
+            }
+            transliterator.set_prishthamatra_output(prishthamatra);
+            transliterator.set_nakara_pollu_output(nakara_pollu);
+            transliterator.set_urdu_harakat_output(harakat);
+            transliterator.set_repair_input(!no_repair);
             // Get input text
             let input = match text {
                 Some(t) => t,
                 None => {
                     use std::io::Read;
                     let mut buffer = String::new();
-                    std::io::stdin()
-                        .read_to_string(&mut buffer)
-                        .expect("Failed to read from stdin");
-                    buffer.trim().to_string()
+                    if let Err(e) = std::io::stdin().read_to_string(&mut buffer) {
+                        fail(format, format_args!("cannot read stdin: {e}"));
+                    }
+                    // Lines keep their numbers for the errors file
+                    if raw || min_fidelity.is_some() {
+                        buffer
+                    } else {
+                        buffer.trim().to_string()
+                    }
+                }
+            };
+            let print_output = |output: &str| {
+                if raw {
+                    print!("{output}");
+                } else {
+                    println!("{output}");
                 }
             };
 
+            // Conversions without options go through the trait an application would hold
+            let plain = !itrans_legacy_caps && capitalize.is_none() && hyphens.is_none();
+
             // Perform transliteration with or without metadata
-            if verbose {
-                match transliterator.transliterate_with_metadata(&input, &from, &to) {
+            let mut options = TransliterationOptions {
+                itrans_legacy_caps,
+                capitalize: match capitalize {
+                    None => Capitalization::None,
+                    Some(CapitalizeArg::Sentences) => Capitalization::Sentences,
+                    Some(CapitalizeArg::Words) => Capitalization::Words,
+                },
+                hyphenation: hyphens.map(|hyphens| Hyphenation {
+                    output: match hyphens {
+                        HyphensArg::Keep => MarkerOutput::Keep,
+                        HyphensArg::Strip => MarkerOutput::Strip,
+                    },
+                    ..Default::default()
+                }),
+                // Verbose and JSON output say what produced them
+                record_provenance: verbose || format == OutputFormat::Json,
+                ..Default::default()
+            };
+            if selective {
+                let policy = match selective_words {
+                    Some(path) => match std::fs::read_to_string(&path) {
+                        Ok(list) => SelectivePolicy::from_word_list(&list),
+                        Err(e) => fail(format, format_args!("cannot read {}: {e}", path.display())),
+                    },
+                    None => SelectivePolicy::new(),
+                };
+                options.selective = Some(policy);
+            }
+
+            if let Some(min_fidelity) = min_fidelity {
+                convert_lines_with_min_fidelity(
+                    &transliterator,
+                    &input,
+                    (&from, &to),
+                    &options,
+                    min_fidelity,
+                    errors.as_deref(),
+                    format,
+                );
+            } else if format == OutputFormat::Json {
+                // Selective conversion does not collect metadata
+                let json = if options.selective.is_some() {
+                    transliterator
+                        .transliterate_with_options(&input, &from, &to, &options)
+                        .map(|output| transliteration_json(&input, &output, &from, &to, None))
+                } else {
+                    transliterator
+                        .transliterate_with_options_and_metadata(&input, &from, &to, &options)
+                        .map(|result| {
+                            transliteration_json(
+                                &input,
+                                &result.output,
+                                &from,
+                                &to,
+                                result.metadata.as_ref(),
+                            )
+                        })
+                };
+                match json {
+                    Ok(json) => println!("{json}"),
+                    Err(e) => fail(format, e),
+                }
+            } else if options.selective.is_some() {
+                match transliterator.transliterate_with_options(&input, &from, &to, &options) {
+                    Ok(result) => print_output(&result),
+                    Err(e) => fail(format, e),
+                }
+            } else if verbose {
+                match transliterator
+                    .transliterate_with_options_and_metadata(&input, &from, &to, &options)
+                {
                     Ok(result) => {
                         // Detailed metadata output
                         println!("{}", result.output);
@@ -72,36 +733,324 @@ fn main() {
                                 metadata.source_script, metadata.target_script
                             );
                             println!("  Extensions used: {}", metadata.used_extensions);
-                            if !metadata.unknown_tokens.is_empty() {
+                            if let Some(provenance) = &metadata.provenance {
+                                println!(
+                                    "  Produced by: shlesha {} (output version {})",
+                                    provenance.crate_version, provenance.output_version
+                                );
+                                for schema in &provenance.runtime_schemas {
+                                    println!(
+                                        "    Runtime schema {}, version {}",
+                                        schema.name,
+                                        schema.version.as_deref().unwrap_or("not declared")
+                                    );
+                                }
+                            }
+                            if metadata.truncated {
+                                println!(
+                                    "  Unknown tokens: {} (first {} listed)",
+                                    metadata.total_unknown_count,
+                                    metadata.unknown_tokens.len()
+                                );
+                            } else {
                                 println!("  Unknown tokens: {}", metadata.unknown_tokens.len());
-                                for (i, token) in metadata.unknown_tokens.iter().enumerate() {
+                            }
+                            for stage in UnknownStage::ALL {
+                                let tokens: Vec<_> = metadata.unknowns_in_stage(stage).collect();
+                                if tokens.is_empty() {
+                                    continue;
+                                }
+                                println!("    During {stage}: {}", tokens.len());
+                                for (i, token) in tokens.iter().enumerate() {
+                                    match (&token.hub_token, stage) {
+                                        (Some(hub_token), UnknownStage::HubConversion) => {
+                                            println!(
+                                                "      {}. {} has no counterpart for {} (token {})",
+                                                i + 1,
+                                                hub_token,
+                                                metadata.target_script,
+                                                token.position
+                                            )
+                                        }
+                                        (Some(hub_token), _) => println!(
+                                            "      {}. {} has no {} mapping (token {})",
+                                            i + 1,
+                                            hub_token,
+                                            token.script,
+                                            token.position
+                                        ),
+                                        (None, _) => println!(
+                                            "      {}. '{}' at position {} ({})",
+                                            i + 1,
+                                            token.token,
+                                            token.position,
+                                            token.unicode
+                                        ),
+                                    }
+                                }
+                            }
+                            if !metadata.recased_words.is_empty() {
+                                println!("  Recased words: {}", metadata.recased_words.len());
+                                for (i, word) in metadata.recased_words.iter().enumerate() {
                                     println!(
-                                        "    {}. '{}' at position {} ({})",
+                                        "    {}. {} read as {} at position {}",
                                         i + 1,
-                                        token.token,
-                                        token.position,
-                                        token.unicode
+                                        word.word,
+                                        word.read_as,
+                                        word.position
                                     );
                                 }
-                            } else {
-                                println!("  Unknown tokens: 0");
+                            }
+                            if !metadata.normalized_spans.is_empty() {
+                                println!(
+                                    "  Spellings normalized: {}",
+                                    metadata.normalized_spans.len()
+                                );
+                                for (i, span) in metadata.normalized_spans.iter().enumerate() {
+                                    let kind = match span.kind {
+                                        NormalizationKind::OldSpelling => "old spelling",
+                                        NormalizationKind::RepairedSequence => "repaired",
+                                    };
+                                    println!(
+                                        "    {}. {} read as {} at position {} ({kind})",
+                                        i + 1,
+                                        span.input,
+                                        span.read_as,
+                                        span.position
+                                    );
+                                }
+                            }
+                            if !metadata.lossy_mappings.is_empty() {
+                                println!("  Lossy mappings: {}", metadata.lossy_mappings.len());
+                                for (i, mapping) in metadata.lossy_mappings.iter().enumerate() {
+                                    println!(
+                                        "    {}. {} written as {} at token {}",
+                                        i + 1,
+                                        mapping.token,
+                                        mapping.substitute,
+                                        mapping.position
+                                    );
+                                }
+                            }
+                            for warning in &metadata.warnings {
+                                match warning {
+                                    ConversionWarning::MixedScriptInput { detected, fraction } => {
+                                        println!(
+                                            "  Warning: {:.0}% of the input is in {}",
+                                            fraction * 100.0,
+                                            detected
+                                        )
+                                    }
+                                }
                             }
                         }
                     }
-                    Err(e) => {
-                        eprintln!("Error: {e}");
-                        std::process::exit(1);
-                    }
+                    Err(e) => fail(format, e),
+                }
+            } else if plain {
+                let transliterator: &dyn Transliterator = &transliterator;
+                match transliterator.transliterate(&input, &from, &to) {
+                    Ok(result) => print_output(&result),
+                    Err(e) => fail(format, e),
                 }
             } else {
                 // Regular transliteration without metadata
-                match transliterator.transliterate(&input, &from, &to) {
-                    Ok(result) => println!("{result}"),
-                    Err(e) => {
-                        eprintln!("Error: {e}");
+                match transliterator.transliterate_with_options(&input, &from, &to, &options) {
+                    Ok(result) => print_output(&result),
+                    Err(e) => fail(format, e),
+                }
+            }
+        }
+
+        #[cfg(feature = "repl")]
+        Commands::Repl { from, to } => {
+            for script in [&from, &to] {
+                if !transliterator.supports_script(script) {
+                    eprintln!("Error: unsupported script: {script}");
+                    std::process::exit(1);
+                }
+            }
+            if let Err(e) = repl::Repl::new(transliterator, from, to).run() {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+
+        #[cfg(feature = "bench")]
+        Commands::Bench {
+            from,
+            to,
+            input,
+            iterations,
+            warmup,
+            format,
+        } => {
+            for script in [&from, &to] {
+                if !transliterator.supports_script(script) {
+                    fail(format, format_args!("unsupported script: {script}"));
+                }
+            }
+            let text = match std::fs::read_to_string(&input) {
+                Ok(text) => text,
+                Err(e) => fail(format, format_args!("cannot read {}: {e}", input.display())),
+            };
+            let runs = bench::Runs { warmup, iterations };
+            match bench::run(transliterator, &text, &from, &to, runs) {
+                Ok(report) if format == OutputFormat::Json => println!("{}", report.json()),
+                Ok(report) => report.print(),
+                Err(e) => fail(format, e),
+            }
+        }
+
+        Commands::Compare {
+            script,
+            expected,
+            actual,
+        } => match shlesha::compare(&expected, &actual, &script) {
+            Ok(diff) => {
+                print_comparison(&diff, &expected, &actual);
+                if !diff.is_equal() {
+                    std::process::exit(1);
+                }
+            }
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(2);
+            }
+        },
+
+        Commands::TestData { dir } => {
+            use shlesha::modules::conformance::run_directory;
+
+            match run_directory(&transliterator, &dir) {
+                Ok(report) => {
+                    for failure in &report.failures {
+                        println!("{failure}\n");
+                    }
+                    println!("{}", report.summary());
+                    if !report.is_success() {
                         std::process::exit(1);
                     }
                 }
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::Schema {
+            command:
+                SchemaCommands::New {
+                    name,
+                    script_type,
+                    like,
+                },
+        } => match scaffold_schema(&name, &script_type, like.as_deref()) {
+            Ok(yaml) => print!("{yaml}"),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Schema {
+            command: SchemaCommands::Validate { paths },
+        } => {
+            use shlesha::modules::registry::{
+                SchemaLoadFailure, SchemaRegistry, SchemaRegistryTrait,
+            };
+
+            let mut registry = SchemaRegistry::new();
+            let mut loaded = 0;
+            let mut failures = Vec::new();
+            for path in paths {
+                let name = path.to_string_lossy().into_owned();
+                if path.is_dir() {
+                    match registry.load_schemas_from_directory(&name) {
+                        Ok(report) => {
+                            loaded += report.loaded;
+                            failures.extend(report.failures);
+                        }
+                        Err(error) => failures.push(SchemaLoadFailure { path, error }),
+                    }
+                } else {
+                    match registry.load_schema(&name) {
+                        Ok(()) => loaded += 1,
+                        Err(error) => failures.push(SchemaLoadFailure { path, error }),
+                    }
+                }
+            }
+
+            for failure in &failures {
+                println!("{failure}\n");
+            }
+            println!("{loaded} valid, {} invalid", failures.len());
+            if !failures.is_empty() {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::Schema {
+            command: SchemaCommands::Test { file },
+        } => {
+            use shlesha::modules::registry::SchemaRegistry;
+
+            let mut registry = SchemaRegistry::new();
+            let report = registry
+                .load_schema_file(&file)
+                .and_then(|name| registry.run_schema_tests(&name));
+            match report {
+                Ok(report) if report.checks == 0 => {
+                    eprintln!("Error: {} has no test cases", file.display());
+                    std::process::exit(1);
+                }
+                Ok(report) => {
+                    for failure in &report.failures {
+                        println!("{failure}\n");
+                    }
+                    println!("{}", report.summary());
+                    if !report.is_success() {
+                        std::process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::Schema {
+            command: SchemaCommands::Diff { first, second },
+        } => {
+            let diff = diff_schemas(&read_schema(&first), &read_schema(&second));
+            for (path, missing) in [
+                (&first, &diff.only_in_first),
+                (&second, &diff.only_in_second),
+            ] {
+                println!("Only in {}: {}", path.display(), missing.len());
+                let mut current_category = None;
+                for (category, token) in missing {
+                    if current_category != Some(category) {
+                        println!("  {category}:");
+                        current_category = Some(category);
+                    }
+                    println!("    {token}");
+                }
+            }
+        }
+
+        Commands::Pairs { lossy_only } => {
+            for pair in transliterator.capability_matrix() {
+                if lossy_only && !pair.lossy {
+                    continue;
+                }
+                let fidelity = if pair.lossy { "lossy" } else { "lossless" };
+                println!("{} → {}: {}", pair.from, pair.to, fidelity);
+                for note in &pair.notes {
+                    println!("  {note}");
+                }
             }
         }
 
@@ -143,33 +1092,45 @@ fn main() {
             println!("  iast: {}", registry.supports_script("iast"));
         }
 
-        Commands::Scripts => {
-            println!("Currently supported scripts:");
+        Commands::Completions { shell } => {
+            print!("{}", completion_script(shell));
+        }
 
-            let scripts = transliterator.list_supported_scripts();
-            for script in scripts {
-                // Provide descriptions for known scripts
-                let description = match script.as_str() {
-                    "iast" => "IAST (International Alphabet of Sanskrit Transliteration)",
-                    "itrans" => "ITRANS (ASCII transliteration)",
-                    "slp1" => "SLP1 (Sanskrit Library Phonetic scheme)",
-                    "harvard_kyoto" | "hk" => "Harvard-Kyoto (ASCII-based academic standard)",
-                    "velthuis" => "Velthuis (TeX-based notation)",
-                    "wx" => "WX (Computational notation)",
-                    "devanagari" | "deva" => "Devanagari script (देवनागरी)",
-                    "bengali" | "bn" => "Bengali script (বাংলা)",
-                    "tamil" | "ta" => "Tamil script (தமிழ்)",
-                    "telugu" | "te" => "Telugu script (తెలుగు)",
-                    "gujarati" | "gu" => "Gujarati script (ગુજરાતી)",
-                    "kannada" | "kn" => "Kannada script (ಕನ್ನಡ)",
-                    "malayalam" | "ml" => "Malayalam script (മലയാളം)",
-                    "odia" | "od" | "oriya" => "Odia script (ଓଡ଼ିଆ)",
-                    "iso15919" | "iso" | "iso_15919" => "ISO-15919 (International standard)",
-                    "bangla" => "Bengali script (বাংলা)",
-                    "wx_notation" => "WX (Computational notation)",
-                    _ => "Unknown script type",
-                };
-                println!("  {script} - {description}");
+        Commands::CompleteScripts => {
+            for script in transliterator.list_supported_scripts() {
+                println!("{script}");
+            }
+        }
+
+        Commands::Scripts { format } => {
+            let listings = transliterator.list_scripts_detailed();
+            if format == OutputFormat::Json {
+                let scripts: Vec<_> = listings
+                    .iter()
+                    .map(|listing| {
+                        serde_json::json!({
+                            "name": listing.name,
+                            "aliases": listing.aliases,
+                            "direction": listing.direction.as_str(),
+                            "rendering_notes": listing.rendering_notes,
+                            "description": script_description(&listing.name),
+                        })
+                    })
+                    .collect();
+                println!("{}", serde_json::Value::Array(scripts));
+                return;
+            }
+
+            println!("Currently supported scripts:");
+            for listing in listings {
+                let mut line = format!("  {}", listing.name);
+                if !listing.aliases.is_empty() {
+                    line.push_str(&format!(" (also {})", listing.aliases.join(", ")));
+                }
+                if let Some(description) = script_description(&listing.name) {
+                    line.push_str(&format!(" - {description}"));
+                }
+                println!("{line}");
             }
         }
     }
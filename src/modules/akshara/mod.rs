@@ -0,0 +1,135 @@
+//! Akshara (orthographic syllable) segmentation of abugida hub tokens
+//!
+//! An akshara is a vowel, or a cluster of consonants joined by viramas with the
+//! vowel sign or inherent a that follows, together with the anusvara, visarga,
+//! candrabindu and accents written after it. A cluster with no vowel after it,
+//! such as the म् of संस्कृतम्, is an akshara of its own ending in a virama.
+//! Marks of the Vedic blocks the script does not map, such as the Samavedic
+//! svara numerals, belong to the akshara they are written on.
+
+use crate::modules::hub::{is_attached_unknown, is_joiner, AbugidaToken, HubToken};
+
+/// One akshara of the input
+#[derive(Debug, Clone, PartialEq)]
+pub struct Akshara {
+    /// The akshara as written in the input
+    pub text: String,
+    /// Byte offset of `text` in the input
+    pub position: usize,
+    /// The abugida hub tokens the akshara was read as
+    pub tokens: Vec<AbugidaToken>,
+    /// Whether the akshara starts with a cluster of two or more consonants
+    pub has_conjunct: bool,
+    /// The independent vowel or vowel sign, `VowelA` for the inherent a, and
+    /// `None` for an akshara ending in a virama
+    pub vowel: Option<AbugidaToken>,
+    /// Anusvara, visarga, candrabindu, accents and attached marks after the vowel
+    pub final_marks: Vec<AbugidaToken>,
+}
+
+impl Akshara {
+    /// Whether the akshara ends in a virama, with no vowel
+    pub fn is_halanta(&self) -> bool {
+        self.vowel.is_none()
+    }
+}
+
+/// Whether `token` is written after an akshara's vowel as part of the akshara
+fn is_final_mark(token: &HubToken) -> bool {
+    match token {
+        HubToken::Abugida(
+            AbugidaToken::MarkVirama | AbugidaToken::MarkNukta | AbugidaToken::MarkAvagraha,
+        ) => false,
+        HubToken::Abugida(AbugidaToken::MarkPluta) => true,
+        _ => token.is_yogavaha() || token.is_vedic_accent() || is_attached_unknown(token),
+    }
+}
+
+/// Split abugida hub tokens into aksharas
+///
+/// `starts` holds the byte offset in `text` each token was read from. Tokens
+/// that belong to no akshara, such as digits, punctuation, avagraha and
+/// unknown characters, are skipped.
+pub fn segment(tokens: &[HubToken], starts: &[usize], text: &str) -> Vec<Akshara> {
+    let abugida = |i: usize| match tokens.get(i) {
+        Some(HubToken::Abugida(token)) => Some(token),
+        _ => None,
+    };
+
+    let mut aksharas = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let begin = i;
+        let mut consonants = 0;
+        let vowel = if tokens[i].is_vowel() {
+            i += 1;
+            abugida(begin).cloned()
+        } else if tokens[i].is_consonant() {
+            loop {
+                consonants += 1;
+                i += 1;
+                if abugida(i) == Some(&AbugidaToken::MarkNukta) {
+                    i += 1;
+                }
+                if abugida(i) != Some(&AbugidaToken::MarkVirama) {
+                    break;
+                }
+                // A virama joins the next consonant to the cluster, through any
+                // joiner or attached mark
+                let mut next = i + 1;
+                while next < tokens.len()
+                    && (is_joiner(&tokens[next]) || is_attached_unknown(&tokens[next]))
+                {
+                    next += 1;
+                }
+                i = next;
+                if next == tokens.len() || !tokens[next].is_consonant() {
+                    break;
+                }
+            }
+
+            let last = tokens[begin..i]
+                .iter()
+                .rposition(|token| !is_attached_unknown(token))
+                .map_or(i - 1, |offset| begin + offset);
+            if abugida(last) == Some(&AbugidaToken::MarkVirama) || is_joiner(&tokens[last]) {
+                None
+            } else if tokens.get(i).is_some_and(HubToken::is_vowel_sign) {
+                i += 1;
+                abugida(i - 1).cloned()
+            } else {
+                Some(AbugidaToken::VowelA)
+            }
+        } else {
+            i += 1;
+            continue;
+        };
+
+        let marks_start = i;
+        while i < tokens.len() && is_final_mark(&tokens[i]) {
+            i += 1;
+        }
+
+        // Attached marks keep their own positions, which may come before the last token's
+        let position = starts[begin];
+        let last = starts[begin..i].iter().copied().max().unwrap_or(position);
+        let end = starts[i..]
+            .iter()
+            .copied()
+            .find(|&start| start > last)
+            .unwrap_or(text.len());
+        aksharas.push(Akshara {
+            text: text[position..end].trim_end().to_string(),
+            position,
+            tokens: (begin..i)
+                .filter_map(|index| abugida(index).cloned())
+                .collect(),
+            has_conjunct: consonants > 1,
+            vowel,
+            final_marks: (marks_start..i)
+                .filter_map(|index| abugida(index).cloned())
+                .collect(),
+        });
+    }
+    aksharas
+}
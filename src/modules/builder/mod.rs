@@ -0,0 +1,343 @@
+//! Construction of a `Shlesha` with its configuration given upfront
+//!
+//! Schemas, word overrides and options added to the builder are loaded by
+//! `build`, in the order they were added. Unlike the loading methods of a
+//! constructed `Shlesha`, nothing is skipped: the first schema or override
+//! file that cannot be loaded fails the build, naming the file.
+//!
+//! `build` first loads `schemas/devanagari.yaml` from the working directory,
+//! when there is such a file, as `Shlesha::new` always has. A missing file is
+//! fine, since the compiled-in converters cover Devanagari; a file that is
+//! there but invalid fails the build, where `Shlesha::new` keeps the error in
+//! `Shlesha::initialization_warnings` instead.
+
+use crate::modules::hub::Hub;
+use crate::modules::options::TransliterationOptions;
+use crate::modules::overrides::{OverrideError, WordOverrides};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::modules::profiler::{OptimizationCache, ProfilerConfig};
+use crate::modules::registry::{
+    RegistryError, SchemaLoadFailure, SchemaRegistry, SchemaRegistryTrait,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::modules::runtime::RuntimeCompiler;
+use crate::modules::script_converter::ScriptConverterRegistry;
+use crate::{ApostropheAvagraha, RuntimeSchemas, Shlesha, SinhalaConjuncts};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ShleshaError {
+    #[error("Invalid schema {0}")]
+    Schema(Box<SchemaLoadFailure>),
+    #[error("Invalid schema '{name}': {source}")]
+    SchemaString {
+        name: String,
+        source: Box<RegistryError>,
+    },
+    #[error("Cannot load schemas from {}: {source}", .dir.display())]
+    SchemaDirectory {
+        dir: PathBuf,
+        source: Box<RegistryError>,
+    },
+    #[error("{} schema(s) in {} failed to load:{}", .failures.len(), .dir.display(), list_failures(.failures))]
+    SchemaDirectoryFailures {
+        dir: PathBuf,
+        failures: Vec<SchemaLoadFailure>,
+    },
+    #[error("Invalid word overrides {}: {source}", .path.display())]
+    WordOverrides {
+        path: PathBuf,
+        source: OverrideError,
+    },
+    #[error("Unsupported script '{0}'")]
+    UnsupportedScript(String),
+    /// A conversion that failed, with the message of the error that stopped it
+    #[error("{0}")]
+    Conversion(String),
+}
+
+/// The Devanagari schema loaded from the working directory, when it exists
+const WORKING_DEVANAGARI_SCHEMA: &str = "schemas/devanagari.yaml";
+
+/// What `build` does with the Devanagari schema in the working directory
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum WorkingSchema {
+    /// Leave it out, for a registry given upfront
+    Skip,
+    /// Load it if the file exists, failing the build when it is invalid
+    Load,
+    /// Load it if the file exists, keeping the error as an initialization warning
+    LoadOrWarn,
+}
+
+fn list_failures(failures: &[SchemaLoadFailure]) -> String {
+    failures
+        .iter()
+        .map(|failure| format!("\n  {failure}"))
+        .collect()
+}
+
+/// Where a schema added to the builder comes from
+enum SchemaSource {
+    File(PathBuf),
+    Str { yaml: String, name: String },
+    Dir(PathBuf),
+}
+
+/// Word overrides for a script pair, read from a TSV file
+struct OverrideFile {
+    from: String,
+    to: String,
+    path: PathBuf,
+}
+
+/// Builds a `Shlesha`, failing on the first schema or override file that cannot be loaded
+pub struct ShleshaBuilder {
+    registry: SchemaRegistry,
+    #[cfg(not(target_arch = "wasm32"))]
+    runtime_compiler: bool,
+    schemas: Vec<SchemaSource>,
+    #[cfg(not(target_arch = "wasm32"))]
+    profiling: Option<ProfilerConfig>,
+    options: Option<TransliterationOptions>,
+    word_overrides: Vec<OverrideFile>,
+    working_schema: WorkingSchema,
+}
+
+impl ShleshaBuilder {
+    pub fn new() -> Self {
+        Self {
+            registry: SchemaRegistry::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            runtime_compiler: true,
+            schemas: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            profiling: None,
+            options: None,
+            word_overrides: Vec::new(),
+            working_schema: WorkingSchema::Load,
+        }
+    }
+
+    /// Start from `registry` instead of an empty one, without a runtime compiler
+    /// or the working directory's Devanagari schema
+    pub(crate) fn registry(mut self, registry: SchemaRegistry) -> Self {
+        self.registry = registry;
+        self.working_schema = WorkingSchema::Skip;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.runtime_compiler = false;
+        }
+        self
+    }
+
+    /// Load the schema in the YAML (or, ending in .toml, TOML) file at `path`
+    pub fn with_schema_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.schemas.push(SchemaSource::File(path.into()));
+        self
+    }
+
+    /// Load a schema from YAML text, `name` standing for it in errors
+    pub fn with_schema_str(mut self, yaml: impl Into<String>, name: impl Into<String>) -> Self {
+        self.schemas.push(SchemaSource::Str {
+            yaml: yaml.into(),
+            name: name.into(),
+        });
+        self
+    }
+
+    /// Load every `.yaml`, `.yml` and `.toml` schema in the directory at `path` and its subdirectories
+    pub fn with_schema_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.schemas.push(SchemaSource::Dir(path.into()));
+        self
+    }
+
+    /// Profile conversions with `config`
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_profiling(mut self, config: ProfilerConfig) -> Self {
+        self.profiling = Some(config);
+        self
+    }
+
+    /// Convert with `options` wherever a conversion is not given options of its own
+    ///
+    /// They apply to `transliterate`, `transliterate_cow` and
+    /// `transliterate_with_metadata`.
+    pub fn with_options(mut self, options: TransliterationOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Add word overrides from `from` to `to` from the TSV file at `path`
+    ///
+    /// See `Shlesha::load_word_overrides` for the file format. Script names are
+    /// resolved after the builder's schemas are loaded, so a pair may name them.
+    pub fn with_word_overrides(
+        mut self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        self.word_overrides.push(OverrideFile {
+            from: from.into(),
+            to: to.into(),
+            path: path.into(),
+        });
+        self
+    }
+
+    /// Keep an invalid Devanagari schema in the working directory as an
+    /// initialization warning rather than failing the build, for `Shlesha::new`
+    pub(crate) fn warn_on_working_schema(mut self) -> Self {
+        if self.working_schema == WorkingSchema::Load {
+            self.working_schema = WorkingSchema::LoadOrWarn;
+        }
+        self
+    }
+
+    /// The configured `Shlesha`, or the error of the first file that could not be loaded
+    pub fn build(self) -> Result<Shlesha, ShleshaError> {
+        let Self {
+            registry,
+            #[cfg(not(target_arch = "wasm32"))]
+            runtime_compiler,
+            schemas,
+            #[cfg(not(target_arch = "wasm32"))]
+            profiling,
+            options,
+            word_overrides,
+            working_schema,
+        } = self;
+
+        let mut registry = registry;
+        let mut warnings = Vec::new();
+        if working_schema != WorkingSchema::Skip {
+            if let Err(error) = load_working_schema(&mut registry) {
+                if working_schema == WorkingSchema::Load {
+                    return Err(error);
+                }
+                #[cfg(feature = "tracing")]
+                crate::modules::trace::initialization_failed(&error);
+                warnings.push(error);
+            }
+        }
+        for source in schemas {
+            load_schema(&mut registry, source)?;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut shlesha = assemble(registry, runtime_compiler);
+        #[cfg(target_arch = "wasm32")]
+        let mut shlesha = assemble(registry);
+
+        for OverrideFile { from, to, path } in word_overrides {
+            shlesha
+                .read_word_overrides(&from, &to, &path)
+                .map_err(|source| ShleshaError::WordOverrides { path, source })?;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(config) = profiling {
+            shlesha.enable_profiling_with_config(config);
+        }
+        shlesha.default_options = options;
+        shlesha.initialization_warnings = warnings;
+        Ok(shlesha)
+    }
+}
+
+impl Default for ShleshaBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Load the Devanagari schema in the working directory, if there is one
+fn load_working_schema(registry: &mut SchemaRegistry) -> Result<(), ShleshaError> {
+    let path = Path::new(WORKING_DEVANAGARI_SCHEMA);
+    if !path.exists() {
+        return Ok(());
+    }
+    load_schema(registry, SchemaSource::File(path.to_path_buf()))
+}
+
+fn load_schema(registry: &mut SchemaRegistry, source: SchemaSource) -> Result<(), ShleshaError> {
+    match source {
+        SchemaSource::File(path) => registry
+            .load_schema(&path.to_string_lossy())
+            .map_err(|error| ShleshaError::Schema(Box::new(SchemaLoadFailure { path, error }))),
+        SchemaSource::Str { yaml, name } => {
+            registry
+                .load_schema_from_string(&yaml, &name)
+                .map_err(|source| ShleshaError::SchemaString {
+                    name,
+                    source: Box::new(source),
+                })
+        }
+        SchemaSource::Dir(dir) => {
+            let report = match registry.load_schemas_from_directory(&dir.to_string_lossy()) {
+                Ok(report) => report,
+                Err(source) => {
+                    return Err(ShleshaError::SchemaDirectory {
+                        dir,
+                        source: Box::new(source),
+                    })
+                }
+            };
+            if report.failures.is_empty() {
+                Ok(())
+            } else {
+                Err(ShleshaError::SchemaDirectoryFailures {
+                    dir,
+                    failures: report.failures,
+                })
+            }
+        }
+    }
+}
+
+/// A `Shlesha` over `registry` with every setting at its default
+fn assemble(
+    registry: SchemaRegistry,
+    #[cfg(not(target_arch = "wasm32"))] runtime_compiler: bool,
+) -> Shlesha {
+    let script_converter_registry = ScriptConverterRegistry::default();
+    #[cfg(feature = "stats")]
+    let stats =
+        crate::modules::stats::StatsCounters::new(script_converter_registry.primary_scripts());
+
+    Shlesha {
+        hub: Hub::new(),
+        script_converter_registry,
+        runtime: RwLock::new(RuntimeSchemas::new(registry)),
+        #[cfg(not(target_arch = "wasm32"))]
+        runtime_compiler: runtime_compiler
+            .then(|| RuntimeCompiler::new().ok())
+            .flatten(),
+        #[cfg(not(target_arch = "wasm32"))]
+        profiler: None,
+        #[cfg(not(target_arch = "wasm32"))]
+        optimization_cache: OptimizationCache::new(),
+        #[cfg(feature = "parallel")]
+        parallel_threshold: crate::modules::parallel::DEFAULT_PARALLEL_THRESHOLD,
+        vedic_mode: false,
+        force_hub: false,
+        apostrophe_avagraha: ApostropheAvagraha::default(),
+        sinhala_conjuncts: SinhalaConjuncts::default(),
+        prishthamatra_output: false,
+        nakara_pollu_output: false,
+        urdu_harakat_output: false,
+        repair_input: true,
+        word_overrides: WordOverrides::new(),
+        preprocessors: Vec::new(),
+        postprocessors: Vec::new(),
+        default_options: None,
+        initialization_warnings: Vec::new(),
+        result_cache: None,
+        #[cfg(feature = "stats")]
+        stats,
+        #[cfg(feature = "tracing")]
+        redact_trace_input: false,
+    }
+}
@@ -0,0 +1,145 @@
+//! Which script pairs convert, and which lose distinctions on the way
+//!
+//! A pair is lossy when some token the source script reads cannot be written
+//! faithfully by the target: the target has no mapping for it, writes it as
+//! another token or spells it out with several (a declared lossy mapping or
+//! fallback), or the hub has no counterpart for it in the target's token type.
+//! Coverage is derived from the generated converters' mappings, so it follows
+//! the schemas without a hand-kept table.
+
+use crate::modules::hub::{AbugidaToken, Hub, HubToken, HubTrait};
+use crate::modules::script_converter::{fallback_name, token_name, TokenConverter};
+
+/// Conversion support and fidelity for one (from, to) script pair
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairCapability {
+    pub from: String,
+    pub to: String,
+    /// Both scripts are known, so the conversion can run
+    pub supported: bool,
+    /// Some source distinction is lost or approximated in the target
+    pub lossy: bool,
+    /// Why the pair is unsupported or lossy, one entry per cause
+    pub notes: Vec<String>,
+}
+
+impl PairCapability {
+    pub fn unsupported(from: &str, to: &str, note: String) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            supported: false,
+            lossy: false,
+            notes: vec![note],
+        }
+    }
+
+    pub fn lossless(from: &str, to: &str) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            supported: true,
+            lossy: false,
+            notes: Vec::new(),
+        }
+    }
+
+    fn add_loss(&mut self, note: String) {
+        self.lossy = true;
+        if !self.notes.contains(&note) {
+            self.notes.push(note);
+        }
+    }
+}
+
+/// Compare what `source` reads with what `target` can write, through the hub
+pub fn token_pair_capability(
+    hub: &Hub,
+    from: &str,
+    source: &dyn TokenConverter,
+    to: &str,
+    target: &dyn TokenConverter,
+) -> PairCapability {
+    let mut capability = PairCapability::lossless(from, to);
+    let Some(source_tokens) = source.mapped_tokens() else {
+        capability
+            .notes
+            .push(format!("tokens read by {from} are not known"));
+        return capability;
+    };
+
+    for token in source_tokens {
+        let name = token_name(&token);
+        let written = match written_as(hub, token, source.is_alphabet(), target.is_alphabet()) {
+            Ok(written) => written,
+            Err(note) => {
+                capability.add_loss(format!("{name} {note}"));
+                continue;
+            }
+        };
+
+        for written_token in written {
+            let written_name = token_name(&written_token);
+            if !target.maps_token(&written_token) {
+                capability.add_loss(format!("{written_name} is not written in {to}"));
+            } else if let Some(substitute) = target.lossy_substitute(&written_token) {
+                capability.add_loss(format!(
+                    "{written_name} is written as {}",
+                    token_name(&substitute)
+                ));
+            } else if let Some(fallback) = target.fallback_tokens(&written_token) {
+                capability.add_loss(format!(
+                    "{written_name} is written as {}",
+                    fallback_name(&fallback)
+                ));
+            }
+        }
+    }
+
+    capability.notes.sort();
+    capability
+}
+
+/// The tokens the target receives for a single source token
+fn written_as(
+    hub: &Hub,
+    token: HubToken,
+    source_is_alphabet: bool,
+    target_is_alphabet: bool,
+) -> Result<Vec<HubToken>, &'static str> {
+    match (source_is_alphabet, target_is_alphabet) {
+        (false, true) => {
+            // Virama and nukta only modify the consonant before them
+            let structural = matches!(
+                token,
+                HubToken::Abugida(AbugidaToken::MarkVirama | AbugidaToken::MarkNukta)
+            );
+            let written = hub
+                .abugida_to_alphabet_tokens(&vec![token])
+                .map_err(|_| "has no Roman counterpart")?;
+            if written.is_empty() && !structural {
+                return Err("is dropped in Roman output");
+            }
+            Ok(written)
+        }
+        (true, false) => {
+            let mut written = hub
+                .alphabet_to_abugida_tokens(&vec![token])
+                .map_err(|_| "has no Indic counterpart")?;
+            if written.is_empty() {
+                return Err("is dropped in Indic output");
+            }
+            // After a consonant the vowel is written as its sign
+            let signs: Vec<_> = written
+                .iter()
+                .filter_map(|written| match written {
+                    HubToken::Abugida(vowel) => vowel.vowel_to_sign().map(HubToken::Abugida),
+                    HubToken::Alphabet(_) => None,
+                })
+                .collect();
+            written.extend(signs);
+            Ok(written)
+        }
+        _ => Ok(vec![token]),
+    }
+}
@@ -0,0 +1,111 @@
+//! Capitals in Roman output, for sentence starts and proper nouns
+//!
+//! Roman schemes are written in lowercase. For bibliographies and running
+//! text, `TransliterationOptions::capitalize` uppercases the first letter of
+//! each sentence or word of the output, and `proper_nouns` that of the words
+//! the caller marks in the input, since names cannot be told from the text.
+//!
+//! A letter is uppercased as a whole character, so ā becomes Ā and ś becomes
+//! Ś; a combining mark after it, as in r̥, stays in place. Words are runs of
+//! non-whitespace, and conversions keep whitespace as it is, so the nth word of
+//! the input is the nth word of the output.
+
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+/// Which letters of Roman output are written as capitals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Capitalization {
+    /// None besides those of proper nouns
+    #[default]
+    None,
+    /// The first letter of the text and after each danda, full stop, ? or !
+    Sentences,
+    /// The first letter of every word
+    Words,
+}
+
+/// Characters that end a sentence: the dandas, with | and || for them in Roman text
+const SENTENCE_ENDS: &[char] = &['।', '॥', '|', '.', '?', '!'];
+
+/// Indices of the words of `text` that the byte ranges `spans` overlap
+///
+/// An empty span marks the word it starts in. A span past the end of the text
+/// is an error.
+pub fn words_in_spans(text: &str, spans: &[Range<usize>]) -> Result<BTreeSet<usize>, String> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (position, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (c.is_whitespace(), start) {
+            (true, Some(word_start)) => {
+                words.push(word_start..position);
+                start = None;
+            }
+            (false, None) => start = Some(position),
+            _ => {}
+        }
+    }
+
+    let mut indices = BTreeSet::new();
+    for span in spans {
+        if span.start > span.end || span.end > text.len() {
+            return Err(format!(
+                "Proper noun span {span:?} is outside the {}-byte input",
+                text.len()
+            ));
+        }
+        for (index, word) in words.iter().enumerate() {
+            let overlaps = word.start < span.end && span.start < word.end;
+            if overlaps || (span.is_empty() && word.contains(&span.start)) {
+                indices.insert(index);
+            }
+        }
+    }
+    Ok(indices)
+}
+
+/// `text` with the first letter of the words `capitalization` picks, and of
+/// the words at `proper_nouns`, uppercased
+///
+/// The first letter of a word is its first alphabetic character; punctuation
+/// before it, such as an opening parenthesis, is passed over, and a word or
+/// sentence starting with a digit is left as it is.
+pub fn capitalize(
+    text: &str,
+    capitalization: Capitalization,
+    proper_nouns: &BTreeSet<usize>,
+) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut words = 0;
+    let mut in_word = false;
+    // Whether the next letter starts a sentence, or the current word
+    let mut sentence_start = capitalization == Capitalization::Sentences;
+    let mut word_start = false;
+
+    for c in text.chars() {
+        if c.is_whitespace() {
+            in_word = false;
+            word_start = false;
+            output.push(c);
+            continue;
+        }
+        if !in_word {
+            in_word = true;
+            word_start = capitalization == Capitalization::Words || proper_nouns.contains(&words);
+            words += 1;
+        }
+
+        if c.is_alphabetic() && (word_start || sentence_start) {
+            output.extend(c.to_uppercase());
+        } else {
+            output.push(c);
+        }
+        if c.is_alphanumeric() {
+            word_start = false;
+            sentence_start = false;
+        } else if capitalization == Capitalization::Sentences && SENTENCE_ENDS.contains(&c) {
+            sentence_start = true;
+        }
+    }
+    output
+}
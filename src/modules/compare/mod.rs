@@ -0,0 +1,308 @@
+//! Token-level differences between two texts in the same script
+//!
+//! For checking output against another system's: both texts are read into hub
+//! tokens by the script's converter and the token streams are aligned with
+//! Myers' diff algorithm, so a difference is reported as the letters and signs
+//! that differ rather than as unequal strings. Texts are compared in Unicode
+//! NFC, and spellings the converter reads alike compare equal, so precomposed
+//! and decomposed vowel signs (ো and ে + া) and nukta letters (क़ and क + ़) do.
+
+use crate::modules::hub::trait_based_converter::nukta_form;
+use crate::modules::hub::{AbugidaToken, HubToken, HubTokenSequence};
+use crate::modules::normalize::compose;
+use crate::modules::script_converter::ScriptConverterRegistry;
+use once_cell::sync::Lazy;
+use std::fmt;
+use std::ops::Range;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CompareError {
+    #[error("Unknown script: {0}")]
+    UnknownScript(String),
+}
+
+/// How a run of tokens differs between the expected and actual text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp {
+    /// The same tokens on both sides
+    Equal,
+    /// Expected tokens written as other tokens
+    Replace,
+    /// Tokens only the actual text has
+    Insert,
+    /// Tokens only the expected text has
+    Delete,
+}
+
+/// One run of aligned tokens
+///
+/// Spans are byte ranges of the two texts. An insertion has an empty expected
+/// span where the tokens would go, and a deletion an empty actual span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffEdit {
+    pub op: DiffOp,
+    pub expected: Vec<HubToken>,
+    pub actual: Vec<HubToken>,
+    pub expected_span: Range<usize>,
+    pub actual_span: Range<usize>,
+}
+
+impl fmt::Display for DiffEdit {
+    /// `position 14: expected VowelSignAa, got VowelSignI`, at a byte offset of the expected text
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "position {}: expected {}, got {}",
+            self.expected_span.start,
+            token_names(&self.expected),
+            token_names(&self.actual)
+        )
+    }
+}
+
+/// The alignment of two texts, as runs of equal and differing tokens in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransliterationDiff {
+    pub script: String,
+    pub edits: Vec<DiffEdit>,
+}
+
+impl TransliterationDiff {
+    /// Whether the two texts read as the same tokens
+    pub fn is_equal(&self) -> bool {
+        self.edits.iter().all(|edit| edit.op == DiffOp::Equal)
+    }
+
+    /// The runs where the texts differ
+    pub fn differences(&self) -> impl Iterator<Item = &DiffEdit> {
+        self.edits.iter().filter(|edit| edit.op != DiffOp::Equal)
+    }
+}
+
+/// The built-in converters the texts are read with
+static CONVERTERS: Lazy<ScriptConverterRegistry> = Lazy::new(ScriptConverterRegistry::default);
+
+/// Compare `actual` with `expected`, both written in `script`
+pub fn compare(
+    expected: &str,
+    actual: &str,
+    script: &str,
+) -> Result<TransliterationDiff, CompareError> {
+    let converter = CONVERTERS
+        .token_converter(script)
+        .ok_or_else(|| CompareError::UnknownScript(script.to_string()))?;
+    let read = |text: &str| match compose(text) {
+        Some(composed) => {
+            let (tokens, positions) = converter.string_to_tokens_with_positions(&composed.text);
+            let positions = positions
+                .iter()
+                .map(|&position| composed.original_position(position))
+                .collect();
+            read_tokens(tokens, positions, text)
+        }
+        None => {
+            let (tokens, positions) = converter.string_to_tokens_with_positions(text);
+            read_tokens(tokens, positions, text)
+        }
+    };
+    let (expected_tokens, actual_tokens) = (read(expected), read(actual));
+
+    let mut edits: Vec<DiffEdit> = Vec::new();
+    let mut run: Option<DiffEdit> = None;
+    let start = |tokens: &[SpannedToken], index: usize, text: &str| {
+        tokens
+            .get(index)
+            .map_or(text.len(), |token| token.span.start)
+    };
+    let (mut expected_index, mut actual_index) = (0, 0);
+    for step in myers(&expected_tokens, &actual_tokens) {
+        let equal = matches!(step, Step::Equal);
+        // Deletions and insertions between two equal runs make one run
+        if run
+            .as_ref()
+            .is_some_and(|edit| (edit.op == DiffOp::Equal) != equal)
+        {
+            edits.extend(run.take());
+        }
+        let edit = run.get_or_insert_with(|| {
+            let expected_at = start(&expected_tokens, expected_index, expected);
+            let actual_at = start(&actual_tokens, actual_index, actual);
+            DiffEdit {
+                op: if equal {
+                    DiffOp::Equal
+                } else {
+                    DiffOp::Replace
+                },
+                expected: Vec::new(),
+                actual: Vec::new(),
+                expected_span: expected_at..expected_at,
+                actual_span: actual_at..actual_at,
+            }
+        });
+        if matches!(step, Step::Equal | Step::Delete) {
+            let token = &expected_tokens[expected_index];
+            edit.expected.push(token.token.clone());
+            edit.expected_span.end = token.span.end;
+            expected_index += 1;
+        }
+        if matches!(step, Step::Equal | Step::Insert) {
+            let token = &actual_tokens[actual_index];
+            edit.actual.push(token.token.clone());
+            edit.actual_span.end = token.span.end;
+            actual_index += 1;
+        }
+    }
+    edits.extend(run);
+    for edit in &mut edits {
+        if edit.op == DiffOp::Replace {
+            if edit.actual.is_empty() {
+                edit.op = DiffOp::Delete;
+            } else if edit.expected.is_empty() {
+                edit.op = DiffOp::Insert;
+            }
+        }
+    }
+
+    Ok(TransliterationDiff {
+        script: script.to_string(),
+        edits,
+    })
+}
+
+/// A token and the bytes of the text it was read from
+#[derive(Debug)]
+struct SpannedToken {
+    token: HubToken,
+    span: Range<usize>,
+}
+
+impl PartialEq for SpannedToken {
+    fn eq(&self, other: &Self) -> bool {
+        self.token == other.token
+    }
+}
+
+/// Tokens with their spans in `text`, each consonant + nukta read as the letter it stands for
+fn read_tokens(tokens: HubTokenSequence, positions: Vec<usize>, text: &str) -> Vec<SpannedToken> {
+    // Tokens read from one piece of text, such as a conjunct, share its span
+    let mut spanned: Vec<SpannedToken> = Vec::with_capacity(tokens.len());
+    for (index, (token, &start)) in tokens.into_iter().zip(&positions).enumerate() {
+        let end = positions[index + 1..]
+            .iter()
+            .copied()
+            .find(|&position| position > start)
+            .unwrap_or(text.len());
+        spanned.push(SpannedToken {
+            token,
+            span: start..end,
+        });
+    }
+
+    let mut composed: Vec<SpannedToken> = Vec::with_capacity(spanned.len());
+    for token in spanned {
+        if let (Some(previous), HubToken::Abugida(AbugidaToken::MarkNukta)) =
+            (composed.last_mut(), &token.token)
+        {
+            let letter = match &previous.token {
+                HubToken::Abugida(consonant) => nukta_form(consonant),
+                HubToken::Alphabet(_) => None,
+            };
+            if let Some(letter) = letter {
+                previous.token = HubToken::Abugida(letter);
+                previous.span.end = token.span.end;
+                continue;
+            }
+        }
+        composed.push(token);
+    }
+    composed
+}
+
+/// How one token is reported: its name, or the text of an unknown token in quotes
+fn token_name(token: &HubToken) -> String {
+    match token.as_unknown_string() {
+        Some(text) => format!("{text:?}"),
+        None => match token {
+            HubToken::Abugida(token) => token.to_string(),
+            HubToken::Alphabet(token) => token.to_string(),
+        },
+    }
+}
+
+fn token_names(tokens: &[HubToken]) -> String {
+    if tokens.is_empty() {
+        return "nothing".to_string();
+    }
+    tokens.iter().map(token_name).collect::<Vec<_>>().join(" ")
+}
+
+/// One step of an alignment, taking the next token of either side or both
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// A shortest edit script turning `a` into `b` (Myers, "An O(ND) Difference Algorithm")
+fn myers<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Step> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = n + m;
+    let offset = max + 1;
+    let mut v = vec![0isize; 2 * max as usize + 3];
+    // The furthest x on each diagonal k = x - y before each round of edits
+    let mut trace = Vec::new();
+
+    'search: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let index = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[index - 1] < v[index + 1]) {
+                v[index + 1]
+            } else {
+                v[index - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut steps = Vec::with_capacity(max as usize);
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let previous_k =
+            if k == -d || (k != d && v[(k - 1 + offset) as usize] < v[(k + 1 + offset) as usize]) {
+                k + 1
+            } else {
+                k - 1
+            };
+        let previous_x = v[(previous_k + offset) as usize];
+        let previous_y = previous_x - previous_k;
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            steps.push(Step::Equal);
+        }
+        if d > 0 {
+            if x == previous_x {
+                steps.push(Step::Insert);
+            } else {
+                steps.push(Step::Delete);
+            }
+        }
+        x = previous_x;
+        y = previous_y;
+    }
+    steps.reverse();
+    steps
+}
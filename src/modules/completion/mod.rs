@@ -0,0 +1,127 @@
+//! Prefixes of text in a script, for spell-checkers and autocompletion
+//!
+//! A script reads text as a run of its patterns: the spellings of its
+//! mappings, their alternates and the texts of its read rules, the strings its
+//! matcher is built from. A prefix is valid when it is some patterns followed
+//! by the start of one more, and its continuations are the characters that
+//! extend a pattern or conjunct it ends in: in IAST, `k` may become `kh` or
+//! `kṣ` and `a` may become `ai` or `au`. Any other character starting a new
+//! pattern is no continuation.
+//!
+//! The conjuncts are kṣa and jña, learned as letters of their own, spelled
+//! with the scheme's letters for their two consonants. Digits and the signs a
+//! script maps, such as the apostrophe of IAST avagraha, are patterns too;
+//! spaces and other punctuation, which every script passes through, end a
+//! valid prefix.
+
+use crate::modules::hub::tokens::AlphabetToken;
+use std::collections::BTreeSet;
+
+/// The consonants of the conjuncts kṣa and jña
+const CONJUNCTS: [[AlphabetToken; 2]; 2] = [
+    [AlphabetToken::ConsonantK, AlphabetToken::ConsonantSs],
+    [AlphabetToken::ConsonantJ, AlphabetToken::ConsonantNy],
+];
+
+/// The conjuncts a Roman scheme spells, given its spelling of each consonant
+pub fn conjuncts<'a>(spelling: impl Fn(&AlphabetToken) -> Option<&'a str>) -> Vec<String> {
+    CONJUNCTS
+        .iter()
+        .filter_map(|[first, second]| Some(format!("{}{}", spelling(first)?, spelling(second)?)))
+        .collect()
+}
+
+/// The patterns a script reads, sorted and without repeats
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatternSet {
+    patterns: Vec<String>,
+    /// The patterns and the script's conjuncts, which prefixes are read as
+    units: Vec<String>,
+}
+
+impl PatternSet {
+    pub fn new(patterns: impl IntoIterator<Item = String>) -> Self {
+        let patterns = sorted(patterns);
+        Self {
+            units: patterns.clone(),
+            patterns,
+        }
+    }
+
+    /// The set with `conjuncts` read as units of their own
+    pub fn with_conjuncts(mut self, conjuncts: impl IntoIterator<Item = String>) -> Self {
+        self.units = sorted(self.patterns.iter().cloned().chain(conjuncts));
+        self
+    }
+
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Whether `prefix` is complete patterns followed by the start of another
+    pub fn is_valid_prefix(&self, prefix: &str) -> bool {
+        self.unfinished(prefix)
+            .iter()
+            .any(|rest| self.starting_with(rest).next().is_some())
+    }
+
+    /// The next characters of the longer patterns or conjuncts `prefix` ends in
+    ///
+    /// In code point order; for an empty prefix, the first character of every
+    /// pattern. Empty when `prefix` is not valid or nothing extends its end.
+    pub fn next_valid_continuations(&self, prefix: &str) -> Vec<String> {
+        let mut next = BTreeSet::new();
+        let rests = self.unfinished(prefix);
+        // After the whole prefix any pattern may start, which extends nothing
+        let extended = rests
+            .into_iter()
+            .filter(|rest| !rest.is_empty() || prefix.is_empty());
+        for rest in extended {
+            next.extend(
+                self.starting_with(rest)
+                    .filter_map(|pattern| pattern[rest.len()..].chars().next()),
+            );
+        }
+        next.into_iter().map(String::from).collect()
+    }
+
+    /// The patterns and conjuncts that start with `rest`, `rest` itself included
+    fn starting_with<'a>(&'a self, rest: &'a str) -> impl Iterator<Item = &'a String> + 'a {
+        let start = self.units.partition_point(|unit| unit.as_str() < rest);
+        self.units[start..]
+            .iter()
+            .take_while(move |pattern| pattern.starts_with(rest))
+    }
+
+    /// What is left of `prefix` after each way of reading its start as whole units
+    fn unfinished<'a>(&self, prefix: &'a str) -> Vec<&'a str> {
+        // Whether the first `i` bytes of the prefix are whole units
+        let mut whole = vec![false; prefix.len() + 1];
+        whole[0] = true;
+        let mut rests = Vec::new();
+        for at in 0..=prefix.len() {
+            if !whole[at] {
+                continue;
+            }
+            let rest = &prefix[at..];
+            rests.push(rest);
+            for unit in &self.units {
+                if rest.starts_with(unit.as_str()) {
+                    whole[at + unit.len()] = true;
+                }
+            }
+        }
+        rests
+    }
+}
+
+/// `strings` without empty ones, sorted and without repeats
+fn sorted(strings: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut strings: Vec<String> = strings
+        .into_iter()
+        .filter(|string| !string.is_empty())
+        .collect();
+    strings.sort();
+    strings.dedup();
+    strings
+}
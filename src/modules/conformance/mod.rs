@@ -0,0 +1,309 @@
+//! Data-driven conformance cases
+//!
+//! A case file declares one conversion and the cases it must satisfy:
+//!
+//! ```yaml
+//! from: telugu
+//! to: iso15919
+//! cases:
+//!   - input: "ఏకమ్"
+//!     expected: "ēkam"
+//!   - input: "కెరళ"
+//!     expected: "keraḷa"
+//!     options:
+//!       roundtrip: true
+//! ```
+//!
+//! Files may be YAML (`.yaml`, `.yml`) or TOML (`.toml`, with `[[cases]]` tables).
+//! The `shlesha test-data <dir>` subcommand and the crate's own conformance test
+//! both run directories of these files through [`run_directory`].
+
+use crate::Shlesha;
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// One conversion and the cases it must satisfy
+#[derive(Debug, Clone, Deserialize)]
+pub struct CaseFile {
+    pub from: String,
+    pub to: String,
+    pub cases: Vec<Case>,
+}
+
+/// A single input and its expected output
+#[derive(Debug, Clone, Deserialize)]
+pub struct Case {
+    pub input: String,
+    pub expected: String,
+    #[serde(default)]
+    pub options: CaseOptions,
+}
+
+/// Extra checks applied to a case
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CaseOptions {
+    /// Also convert `expected` back and require the original input
+    #[serde(default)]
+    pub roundtrip: bool,
+    /// Require the metadata to report (or not report) a lossy mapping
+    pub lossy: Option<bool>,
+}
+
+#[derive(Error, Debug)]
+pub enum ConformanceError {
+    #[error("Failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse {path}: {reason}")]
+    Parse { path: PathBuf, reason: String },
+    #[error("Unsupported case file format: {0} (expected .yaml, .yml or .toml)")]
+    UnsupportedFormat(PathBuf),
+}
+
+/// A case (or whole file) that did not conform
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseFailure {
+    pub file: PathBuf,
+    /// Index of the case in the file, or `None` when the file itself is unusable
+    pub case_index: Option<usize>,
+    pub from: String,
+    pub to: String,
+    pub input: String,
+    pub expected: String,
+    pub actual: String,
+    pub reason: String,
+}
+
+impl fmt::Display for CaseFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.case_index {
+            Some(index) => writeln!(
+                f,
+                "{} case {} ({} → {}): {}",
+                self.file.display(),
+                index,
+                self.from,
+                self.to,
+                self.reason
+            )?,
+            None => {
+                return write!(
+                    f,
+                    "{} ({} → {}): {}",
+                    self.file.display(),
+                    self.from,
+                    self.to,
+                    self.reason
+                )
+            }
+        }
+        writeln!(f, "  input:    {}", self.input)?;
+        writeln!(f, "  expected: {}", self.expected)?;
+        write!(f, "  actual:   {}", self.actual)?;
+        if let Some(diff) = first_difference(&self.expected, &self.actual) {
+            write!(f, "\n  {diff}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of running one or more case files
+#[derive(Debug, Clone, Default)]
+pub struct ConformanceReport {
+    pub files: usize,
+    pub cases: usize,
+    pub failures: Vec<CaseFailure>,
+}
+
+impl ConformanceReport {
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// One-line summary, e.g. "12 files, 240 cases, 0 failures"
+    pub fn summary(&self) -> String {
+        format!(
+            "{} files, {} cases, {} failures",
+            self.files,
+            self.cases,
+            self.failures.len()
+        )
+    }
+}
+
+/// Load a case file, choosing the format from its extension
+pub fn load_case_file(path: &Path) -> Result<CaseFile, ConformanceError> {
+    let content = fs::read_to_string(path).map_err(|source| ConformanceError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let parse_error = |reason: String| ConformanceError::Parse {
+        path: path.to_path_buf(),
+        reason,
+    };
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml") | Some("yml") => {
+            serde_yaml::from_str(&content).map_err(|e| parse_error(e.to_string()))
+        }
+        Some("toml") => toml::from_str(&content).map_err(|e| parse_error(e.to_string())),
+        _ => Err(ConformanceError::UnsupportedFormat(path.to_path_buf())),
+    }
+}
+
+/// Run every case in a loaded file and collect the failures
+///
+/// A script that the transliterator does not support fails the whole file
+/// rather than skipping it, so a typo in a script name cannot hide cases.
+pub fn run_case_file(shlesha: &Shlesha, path: &Path, file: &CaseFile) -> Vec<CaseFailure> {
+    let file_failure = |reason: String| CaseFailure {
+        file: path.to_path_buf(),
+        case_index: None,
+        from: file.from.clone(),
+        to: file.to.clone(),
+        input: String::new(),
+        expected: String::new(),
+        actual: String::new(),
+        reason,
+    };
+
+    for script in [&file.from, &file.to] {
+        if !shlesha.supports_script(script) {
+            return vec![file_failure(format!("unknown script '{script}'"))];
+        }
+    }
+    if file.cases.is_empty() {
+        return vec![file_failure("file declares no cases".to_string())];
+    }
+
+    let mut failures = Vec::new();
+    for (index, case) in file.cases.iter().enumerate() {
+        let failure = |actual: String, reason: String| CaseFailure {
+            file: path.to_path_buf(),
+            case_index: Some(index),
+            from: file.from.clone(),
+            to: file.to.clone(),
+            input: case.input.clone(),
+            expected: case.expected.clone(),
+            actual,
+            reason,
+        };
+
+        let output = match shlesha.transliterate(&case.input, &file.from, &file.to) {
+            Ok(output) => output,
+            Err(e) => {
+                failures.push(failure(String::new(), format!("conversion failed: {e}")));
+                continue;
+            }
+        };
+
+        if output != case.expected {
+            failures.push(failure(output, "output mismatch".to_string()));
+            continue;
+        }
+
+        if let Some(expect_lossy) = case.options.lossy {
+            let is_lossy = shlesha
+                .transliterate_with_metadata(&case.input, &file.from, &file.to)
+                .ok()
+                .and_then(|result| result.metadata)
+                .is_some_and(|metadata| metadata.is_lossy());
+            if is_lossy != expect_lossy {
+                failures.push(failure(
+                    output,
+                    format!("expected lossy = {expect_lossy}, metadata reported {is_lossy}"),
+                ));
+                continue;
+            }
+        }
+
+        if case.options.roundtrip {
+            match shlesha.transliterate(&case.expected, &file.to, &file.from) {
+                Ok(back) if back == case.input => {}
+                Ok(back) => failures.push(CaseFailure {
+                    expected: case.input.clone(),
+                    actual: back,
+                    ..failure(
+                        String::new(),
+                        format!("roundtrip {} → {} mismatch", file.to, file.from),
+                    )
+                }),
+                Err(e) => failures.push(failure(
+                    String::new(),
+                    format!("roundtrip conversion failed: {e}"),
+                )),
+            }
+        }
+    }
+
+    failures
+}
+
+/// Run every case file found under `dir` (recursively, in path order)
+pub fn run_directory(shlesha: &Shlesha, dir: &Path) -> Result<ConformanceReport, ConformanceError> {
+    let mut paths = Vec::new();
+    collect_case_files(dir, &mut paths)?;
+    paths.sort();
+
+    let mut report = ConformanceReport::default();
+    for path in paths {
+        let file = load_case_file(&path)?;
+        report.files += 1;
+        report.cases += file.cases.len();
+        report.failures.extend(run_case_file(shlesha, &path, &file));
+    }
+
+    Ok(report)
+}
+
+fn collect_case_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), ConformanceError> {
+    let io_error = |source| ConformanceError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.is_dir() {
+            collect_case_files(&path, paths)?;
+        } else if matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("yaml") | Some("yml") | Some("toml")
+        ) {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Describe where two strings first diverge, by character index and code point
+fn first_difference(expected: &str, actual: &str) -> Option<String> {
+    let mut expected_chars = expected.chars();
+    let mut actual_chars = actual.chars();
+    let mut index = 0;
+
+    loop {
+        match (expected_chars.next(), actual_chars.next()) {
+            (None, None) => return None,
+            (Some(e), Some(a)) if e == a => index += 1,
+            (e, a) => {
+                let describe = |c: Option<char>| match c {
+                    Some(c) => format!("'{c}' (U+{:04X})", c as u32),
+                    None => "end of string".to_string(),
+                };
+                return Some(format!(
+                    "first difference at char {index}: expected {}, got {}",
+                    describe(e),
+                    describe(a)
+                ));
+            }
+        }
+    }
+}
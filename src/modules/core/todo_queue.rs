@@ -6,7 +6,7 @@
 use serde_json::Value;
 use std::collections::VecDeque;
 use std::sync::mpsc::{channel, Receiver, Sender};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, PoisonError};
 
 /// Priority levels for todo items
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -59,7 +59,7 @@ impl ModuleTodoQueue {
 
     /// Add a todo item to the queue
     pub fn add_todo(&self, todo: TodoItem) {
-        let mut queues = self.queues.lock().unwrap();
+        let mut queues = self.queues.lock().unwrap_or_else(PoisonError::into_inner);
         let module_queue = queues.entry(todo.module.clone()).or_default();
 
         // Insert based on priority
@@ -74,13 +74,13 @@ impl ModuleTodoQueue {
 
     /// Get the next todo for a specific module
     pub fn get_todo(&self, module: &str) -> Option<TodoItem> {
-        let mut queues = self.queues.lock().unwrap();
+        let mut queues = self.queues.lock().unwrap_or_else(PoisonError::into_inner);
         queues.get_mut(module).and_then(|queue| queue.pop_front())
     }
 
     /// Check if a module has pending todos
     pub fn has_todos(&self, module: &str) -> bool {
-        let queues = self.queues.lock().unwrap();
+        let queues = self.queues.lock().unwrap_or_else(PoisonError::into_inner);
         queues.get(module).is_some_and(|queue| !queue.is_empty())
     }
 
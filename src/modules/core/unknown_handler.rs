@@ -1,6 +1,72 @@
 use std::collections::HashSet;
+use std::fmt;
+use std::ops::Range;
+
+/// The conversion stage that could not handle a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum UnknownStage {
+    /// The source script has no reading for a character of the input
+    SourceParsing,
+    /// A hub token has no counterpart in the other hub token type
+    HubConversion,
+    /// The target script has no output for a hub token
+    TargetRendering,
+}
+
+impl UnknownStage {
+    /// All stages, in conversion order
+    pub const ALL: [UnknownStage; 3] = [
+        UnknownStage::SourceParsing,
+        UnknownStage::HubConversion,
+        UnknownStage::TargetRendering,
+    ];
+
+    /// Snake-case name of the stage, as used by the bindings
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnknownStage::SourceParsing => "source_parsing",
+            UnknownStage::HubConversion => "hub_conversion",
+            UnknownStage::TargetRendering => "target_rendering",
+        }
+    }
+}
+
+impl fmt::Display for UnknownStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            UnknownStage::SourceParsing => "source parsing",
+            UnknownStage::HubConversion => "hub conversion",
+            UnknownStage::TargetRendering => "target rendering",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Why a character of the input could not be read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum UnknownKind {
+    /// The script has no mapping for the character or hub token
+    Unmapped,
+    /// U+FFFD in the input: text that was not valid Unicode before it reached
+    /// Shlesha, such as a lone surrogate from JavaScript or Python
+    InvalidEncoding,
+}
+
+impl UnknownKind {
+    /// Snake-case name of the kind, as used by the bindings
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnknownKind::Unmapped => "unmapped",
+            UnknownKind::InvalidEncoding => "invalid_encoding",
+        }
+    }
+}
 
 /// Represents an unknown token found during transliteration
+///
+/// `position` depends on the stage: a byte offset into the input for
+/// `SourceParsing`, and an index into the hub token sequence the stage worked
+/// on for `HubConversion` (source tokens) and `TargetRendering` (rendered tokens).
 #[derive(Debug, Clone, PartialEq)]
 pub struct UnknownToken {
     /// The script where the unknown token was found
@@ -13,23 +79,60 @@ pub struct UnknownToken {
     pub position: usize,
     /// Whether this came from a runtime extension
     pub is_extension: bool,
+    /// Hub token that could not be converted or written (e.g. "VowelSignL")
+    pub hub_token: Option<String>,
+    /// The conversion stage that could not handle the token
+    pub stage: UnknownStage,
+    /// Why the token could not be handled
+    pub kind: UnknownKind,
 }
 
 impl UnknownToken {
-    /// Create a new unknown token
+    /// Create a new unknown token for a character the source script cannot read
+    ///
+    /// U+FFFD is recorded as [`UnknownKind::InvalidEncoding`], since the input
+    /// only holds it where something could not be decoded.
     pub fn new(script: &str, token: char, position: usize, is_extension: bool) -> Self {
+        let kind = if token == char::REPLACEMENT_CHARACTER {
+            UnknownKind::InvalidEncoding
+        } else {
+            UnknownKind::Unmapped
+        };
         Self {
             script: script.to_string(),
             token,
             unicode: format!("U+{:04X}", token as u32),
             position,
             is_extension,
+            hub_token: None,
+            stage: UnknownStage::SourceParsing,
+            kind,
+        }
+    }
+
+    /// A hub token the target script cannot write, recorded with U+FFFD as its character
+    pub fn unmapped(script: &str, hub_token: &str, position: usize) -> Self {
+        Self {
+            hub_token: Some(hub_token.to_string()),
+            stage: UnknownStage::TargetRendering,
+            kind: UnknownKind::Unmapped,
+            ..Self::new(script, char::REPLACEMENT_CHARACTER, position, false)
+        }
+    }
+
+    /// A hub token of `script` with no counterpart in the other hub token type
+    pub fn unconverted(script: &str, hub_token: &str, position: usize) -> Self {
+        Self {
+            stage: UnknownStage::HubConversion,
+            ..Self::unmapped(script, hub_token, position)
         }
     }
 
     /// Format as annotation string if needed
     pub fn format(&self) -> String {
-        if self.is_extension {
+        if let Some(hub_token) = &self.hub_token {
+            format!("[{}:{}]", self.script, hub_token)
+        } else if self.is_extension {
             format!("[ext:{}:{}:{}]", self.script, self.token, self.unicode)
         } else {
             format!("[{}:{}:{}]", self.script, self.token, self.unicode)
@@ -37,37 +140,327 @@ impl UnknownToken {
     }
 }
 
+/// A token the target script cannot represent, written as a substitute token
+#[derive(Debug, Clone, PartialEq)]
+pub struct LossyMapping {
+    /// The target script that merged the distinction
+    pub script: String,
+    /// The hub token that was lost (e.g. "VowelE")
+    pub token: String,
+    /// The hub token whose output was written instead (e.g. "VowelEe"), or the
+    /// tokens of a fallback spelling (e.g. "ConsonantR + VowelSignI")
+    pub substitute: String,
+    /// Position in the hub token sequence
+    pub position: usize,
+}
+
+impl LossyMapping {
+    /// Create a new lossy mapping record
+    pub fn new(script: &str, token: &str, substitute: &str, position: usize) -> Self {
+        Self {
+            script: script.to_string(),
+            token: token.to_string(),
+            substitute: substitute.to_string(),
+            position,
+        }
+    }
+}
+
+/// A word of ALL CAPS legacy ITRANS, read with the case it stands for
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecasedWord {
+    /// The word as written (e.g. "DHARMAH")
+    pub word: String,
+    /// The ITRANS it was read as (e.g. "dharmaH")
+    pub read_as: String,
+    /// Position in the original input (byte offset)
+    pub position: usize,
+}
+
+/// Why a span of the input was read as something other than what it spells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormalizationKind {
+    /// Old orthography, such as the prishthamatra, read as the modern spelling
+    OldSpelling,
+    /// A malformed sequence, such as two vowel signs in a row, read as repaired
+    RepairedSequence,
+}
+
+/// A span of the input read as the spelling it stands for
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedSpan {
+    pub kind: NormalizationKind,
+    /// The span as written (e.g. "ॎा", a prishthamatra o)
+    pub input: String,
+    /// The spelling it was read as (e.g. "ो")
+    pub read_as: String,
+    /// Position in the original input (byte offset)
+    pub position: usize,
+}
+
+/// Why a span of the input was written other than by the conversion rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanKind {
+    /// Written as registered with `Shlesha::add_word_override`
+    Override,
+    /// A format character (bidi control, soft hyphen, word joiner…) passed through
+    FormatChar,
+    /// Protected by a preprocessor added with `Shlesha::add_preprocessor`
+    Protected,
+    /// A combining mark of the Vedic blocks the source script does not map,
+    /// written after the akshara it is on
+    AttachedMark,
+}
+
+/// A span of the input written as given rather than converted
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedSpan {
+    pub kind: SpanKind,
+    /// The span as written in the input (e.g. "Bengaluru")
+    pub input: String,
+    /// What was written in its place (e.g. "ಬೆಂಗಳೂರು")
+    pub output: String,
+    /// Position in the original input (byte offset)
+    pub position: usize,
+}
+
+/// What produced a conversion's output, for reproducing it later
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionProvenance {
+    /// Version of the shlesha crate
+    pub crate_version: String,
+    /// `Shlesha::output_version()`, the version of the built-in conversions
+    pub output_version: String,
+    /// The runtime schemas the conversion read or wrote with, source first
+    pub runtime_schemas: Vec<SchemaProvenance>,
+}
+
+/// A runtime schema used by a conversion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaProvenance {
+    pub name: String,
+    /// The `version` its metadata declares, if any
+    pub version: Option<String>,
+}
+
+/// Something about the input that makes the output less than it seems
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConversionWarning {
+    /// `fraction` of the non-ASCII input is in `detected`, an Indic script
+    /// other than the source; see `modules::mixed_script`
+    MixedScriptInput { detected: String, fraction: f64 },
+}
+
 /// Metadata collected during transliteration
 #[derive(Debug, Clone, Default)]
 pub struct TransliterationMetadata {
-    /// Unknown tokens found during conversion
+    /// Unknown tokens found during conversion, the first `unknown_limit` of them
     pub unknown_tokens: Vec<UnknownToken>,
+    /// Unknown tokens found, including those past `unknown_limit` that were not kept
+    pub total_unknown_count: usize,
+    /// Whether unknown tokens were found past `unknown_limit` and not kept
+    pub truncated: bool,
+    /// Most unknown tokens kept; `None` keeps all of them
+    pub unknown_limit: Option<usize>,
     /// Source script
     pub source_script: String,
     /// Target script  
     pub target_script: String,
     /// Whether any runtime extensions were used
     pub used_extensions: bool,
+    /// Distinctions merged because the target script cannot represent them
+    pub lossy_mappings: Vec<LossyMapping>,
+    /// ALL CAPS words re-cased before reading, with `itrans_legacy_caps`
+    pub recased_words: Vec<RecasedWord>,
+    /// Spans of the input written as given, such as word overrides
+    pub fixed_spans: Vec<FixedSpan>,
+    /// Old spellings read as their modern form, and malformed sequences as repaired
+    pub normalized_spans: Vec<NormalizedSpan>,
+    /// Byte ranges of the input the source script read as tokens, in input
+    /// order, with touching ranges joined; the text between them was passed
+    /// through or reported unknown
+    pub converted_spans: Vec<Range<usize>>,
+    /// Hub tokens read from the input, which `HubConversion` positions index into
+    pub source_token_count: usize,
+    /// Hub tokens the output was written from, which `TargetRendering` and
+    /// lossy mapping positions index into
+    pub rendered_token_count: usize,
+    /// Versions of what produced the output, when
+    /// `TransliterationOptions::record_provenance` asks for them
+    pub provenance: Option<ConversionProvenance>,
+    /// Warnings about the input as a whole
+    pub warnings: Vec<ConversionWarning>,
 }
 
 impl TransliterationMetadata {
     pub fn new(source_script: &str, target_script: &str) -> Self {
         Self {
             unknown_tokens: Vec::new(),
+            total_unknown_count: 0,
+            truncated: false,
+            unknown_limit: None,
             source_script: source_script.to_string(),
             target_script: target_script.to_string(),
             used_extensions: false,
+            lossy_mappings: Vec::new(),
+            recased_words: Vec::new(),
+            fixed_spans: Vec::new(),
+            normalized_spans: Vec::new(),
+            converted_spans: Vec::new(),
+            source_token_count: 0,
+            rendered_token_count: 0,
+            provenance: None,
+            warnings: Vec::new(),
         }
     }
 
+    /// Metadata of a whole input from that of its pieces, in order
+    ///
+    /// Each part comes with the byte offset of its piece in the whole input.
+    /// Byte positions are shifted by that offset, hub-token positions by the
+    /// token counts of the parts before it, and unknown counts are summed. The
+    /// scripts, `unknown_limit` and provenance of the first part apply to the
+    /// whole.
+    pub fn merge(parts: Vec<(usize, TransliterationMetadata)>) -> TransliterationMetadata {
+        let mut parts = parts.into_iter();
+        let Some((offset, first)) = parts.next() else {
+            return TransliterationMetadata::default();
+        };
+        let mut merged = TransliterationMetadata::new(&first.source_script, &first.target_script)
+            .with_unknown_limit(first.unknown_limit);
+        merged.provenance = first.provenance.clone();
+        merged.append_shifted(first, offset);
+        for (offset, part) in parts {
+            merged.append_shifted(part, offset);
+        }
+        merged
+    }
+
+    /// The same metadata, keeping at most `limit` unknown tokens
+    ///
+    /// Tokens already kept past the limit are dropped and only counted.
+    pub fn with_unknown_limit(mut self, limit: Option<usize>) -> Self {
+        self.unknown_limit = limit;
+        self.total_unknown_count = self.total_unknown_count.max(self.unknown_tokens.len());
+        if let Some(limit) = limit.filter(|&limit| self.unknown_tokens.len() > limit) {
+            self.unknown_tokens.truncate(limit);
+            self.truncated = true;
+        }
+        self
+    }
+
     /// Add an unknown token to the metadata
+    ///
+    /// Past `unknown_limit`, the token is only counted.
     pub fn add_unknown(&mut self, token: UnknownToken) {
         if token.is_extension {
             self.used_extensions = true;
         }
+        if self.is_full() {
+            self.count_uncollected(1);
+            return;
+        }
+        self.total_unknown_count += 1;
         self.unknown_tokens.push(token);
     }
 
+    /// Whether `unknown_limit` unknown tokens have been kept
+    pub fn is_full(&self) -> bool {
+        self.unknown_limit
+            .is_some_and(|limit| self.unknown_tokens.len() >= limit)
+    }
+
+    /// Count unknown tokens found but not kept
+    pub fn count_uncollected(&mut self, count: usize) {
+        self.total_unknown_count += count;
+        self.truncated |= count > 0;
+    }
+
+    /// Unknown tokens this metadata counted but did not keep
+    pub fn uncollected_count(&self) -> usize {
+        self.total_unknown_count
+            .saturating_sub(self.unknown_tokens.len())
+    }
+
+    /// Record a lossy mapping applied by the target converter
+    pub fn add_lossy(&mut self, mapping: LossyMapping) {
+        self.lossy_mappings.push(mapping);
+    }
+
+    /// Append metadata collected for a piece of a larger input
+    ///
+    /// Byte positions are shifted by `byte_offset`, the piece's start within
+    /// the whole input, and hub-token positions by the tokens counted so far.
+    fn append_shifted(&mut self, other: TransliterationMetadata, byte_offset: usize) {
+        let (source_token_offset, token_offset) =
+            (self.source_token_count, self.rendered_token_count);
+        self.source_token_count += other.source_token_count;
+        self.rendered_token_count += other.rendered_token_count;
+        self.used_extensions |= other.used_extensions;
+        self.count_uncollected(other.uncollected_count());
+        for mut token in other.unknown_tokens {
+            token.position += match token.stage {
+                UnknownStage::SourceParsing => byte_offset,
+                UnknownStage::HubConversion => source_token_offset,
+                UnknownStage::TargetRendering => token_offset,
+            };
+            self.add_unknown(token);
+        }
+        for mut mapping in other.lossy_mappings {
+            mapping.position += token_offset;
+            self.add_lossy(mapping);
+        }
+        for mut word in other.recased_words {
+            word.position += byte_offset;
+            self.recased_words.push(word);
+        }
+        for mut span in other.fixed_spans {
+            span.position += byte_offset;
+            self.fixed_spans.push(span);
+        }
+        for mut span in other.normalized_spans {
+            span.position += byte_offset;
+            self.normalized_spans.push(span);
+        }
+        for span in other.converted_spans {
+            let span = span.start + byte_offset..span.end + byte_offset;
+            match self.converted_spans.last_mut() {
+                Some(last) if last.end == span.start => last.end = span.end,
+                _ => self.converted_spans.push(span),
+            }
+        }
+    }
+
+    /// Unknown tokens reported by one conversion stage
+    pub fn unknowns_in_stage(&self, stage: UnknownStage) -> impl Iterator<Item = &UnknownToken> {
+        self.unknown_tokens
+            .iter()
+            .filter(move |token| token.stage == stage)
+    }
+
+    /// Whether the output lost any distinction present in the source
+    pub fn is_lossy(&self) -> bool {
+        !self.lossy_mappings.is_empty()
+    }
+
+    /// Share of the tokens read from the input that reached the output unchanged
+    ///
+    /// Every unknown token, kept or only counted, and every lossy mapping counts
+    /// against one token of the input or the output, whichever has more. A
+    /// conversion that lost nothing, including that of empty text, has a
+    /// fidelity of 1.0.
+    pub fn fidelity(&self) -> f64 {
+        let lost = self.total_unknown_count + self.lossy_mappings.len();
+        let tokens = self
+            .source_token_count
+            .max(self.rendered_token_count)
+            .max(lost);
+        if tokens == 0 {
+            return 1.0;
+        }
+        1.0 - lost as f64 / tokens as f64
+    }
+
     /// Get unique unknown characters (for creating custom mappings)
     pub fn unique_unknowns(&self) -> Vec<char> {
         let mut unique: HashSet<char> = HashSet::new();
@@ -110,6 +503,12 @@ impl TransliterationMetadata {
             ));
         }
 
+        if self.truncated {
+            report.push_str(&format!(
+                "\nNote: {} more unknown token(s) found past the limit and not listed\n",
+                self.uncollected_count()
+            ));
+        }
         if self.used_extensions {
             report.push_str("\nNote: Some unknown tokens came from runtime extensions\n");
         }
@@ -183,6 +582,39 @@ impl TransliterationResult {
     }
 }
 
+/// Metadata of one script-tagged segment of a segmented conversion
+#[derive(Debug, Clone)]
+pub struct SegmentMetadata {
+    /// Index of the segment among the segments given
+    pub index: usize,
+    /// Byte offset of the segment's output in the whole output
+    pub output_offset: usize,
+    /// The segment's metadata; positions are relative to the segment
+    pub metadata: TransliterationMetadata,
+}
+
+/// Result of a segmented conversion with metadata for each converted segment
+#[derive(Debug, Clone)]
+pub struct SegmentedResult {
+    /// The segment outputs, concatenated
+    pub output: String,
+    /// Metadata of the tagged segments, in order; untagged segments have none
+    pub segments: Vec<SegmentMetadata>,
+}
+
+impl SegmentedResult {
+    /// Unknown tokens of every segment, with the index of their segment
+    pub fn unknown_tokens(&self) -> impl Iterator<Item = (usize, &UnknownToken)> {
+        self.segments.iter().flat_map(|segment| {
+            segment
+                .metadata
+                .unknown_tokens
+                .iter()
+                .map(move |token| (segment.index, token))
+        })
+    }
+}
+
 /// Trait for converters that support unknown token tracking
 pub trait UnknownHandler {
     /// Check if a character is known for a given script
@@ -241,6 +673,24 @@ mod tests {
         assert_eq!(unique, vec!['a', 'b']);
     }
 
+    #[test]
+    fn test_unknown_limit_counts_past_the_limit() {
+        let mut metadata =
+            TransliterationMetadata::new("source", "target").with_unknown_limit(Some(2));
+        for (position, ch) in "abc".char_indices() {
+            metadata.add_unknown(UnknownToken::new("source", ch, position, false));
+        }
+        assert_eq!(metadata.unknown_tokens.len(), 2);
+        assert_eq!(metadata.total_unknown_count, 3);
+        assert!(metadata.truncated);
+
+        // Merged pieces carry over what they counted but did not keep
+        let whole = TransliterationMetadata::merge(vec![(0, metadata)]);
+        assert_eq!(whole.unknown_tokens.len(), 2);
+        assert_eq!(whole.total_unknown_count, 3);
+        assert!(whole.truncated);
+    }
+
     #[test]
     fn test_transliteration_result() {
         let result = TransliterationResult::simple("dharma".to_string());
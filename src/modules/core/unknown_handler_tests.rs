@@ -31,6 +31,45 @@ mod comprehensive_tests {
         assert_eq!(chinese.format(), "[test:中:U+4E2D]");
     }
 
+    #[test]
+    fn test_unknown_token_stages() {
+        assert_eq!(
+            UnknownToken::new("devanagari", '☺', 5, false).stage,
+            UnknownStage::SourceParsing
+        );
+        assert_eq!(
+            UnknownToken::unconverted("grantha", "OmSymbol", 2).stage,
+            UnknownStage::HubConversion
+        );
+        assert_eq!(
+            UnknownToken::unmapped("slp1", "MarkCandrabindu", 2).stage,
+            UnknownStage::TargetRendering
+        );
+
+        let labels: Vec<_> = UnknownStage::ALL.iter().map(|s| s.as_str()).collect();
+        assert_eq!(
+            labels,
+            ["source_parsing", "hub_conversion", "target_rendering"]
+        );
+        assert_eq!(UnknownStage::HubConversion.to_string(), "hub conversion");
+    }
+
+    #[test]
+    fn test_unknown_token_kinds() {
+        assert_eq!(
+            UnknownToken::new("devanagari", '☺', 5, false).kind,
+            UnknownKind::Unmapped
+        );
+        let replacement = UnknownToken::new("devanagari", '\u{FFFD}', 3, false);
+        assert_eq!(replacement.kind, UnknownKind::InvalidEncoding);
+        assert_eq!(replacement.kind.as_str(), "invalid_encoding");
+        // Tokens the target cannot write carry U+FFFD too, but were decoded fine
+        assert_eq!(
+            UnknownToken::unmapped("slp1", "MarkCandrabindu", 2).kind,
+            UnknownKind::Unmapped
+        );
+    }
+
     #[test]
     fn test_transliteration_metadata() {
         let mut metadata = TransliterationMetadata::new("source", "target");
@@ -50,6 +89,19 @@ mod comprehensive_tests {
         assert!(metadata.used_extensions);
     }
 
+    #[test]
+    fn test_unmapped_hub_token() {
+        let token = UnknownToken::unmapped("siddham", "VowelSignL", 3);
+        assert_eq!(token.token, '\u{FFFD}');
+        assert_eq!(token.unicode, "U+FFFD");
+        assert_eq!(token.position, 3);
+        assert_eq!(token.hub_token.as_deref(), Some("VowelSignL"));
+        assert_eq!(token.format(), "[siddham:VowelSignL]");
+
+        // Tokens found in the input carry no hub token
+        assert_eq!(UnknownToken::new("test", 'x', 0, false).hub_token, None);
+    }
+
     #[test]
     fn test_unique_unknowns() {
         let mut metadata = TransliterationMetadata::new("test", "test");
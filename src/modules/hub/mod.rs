@@ -3,7 +3,9 @@ use thiserror::Error;
 
 pub mod tokens;
 pub mod trait_based_converter;
-pub use tokens::{AbugidaToken, AlphabetToken, HubToken, HubTokenSequence};
+pub use tokens::{
+    AbugidaToken, AlphabetToken, HubToken, HubTokenSequence, TokenCategory, UNKNOWN_STABLE_ID,
+};
 
 #[derive(Error, Debug, Clone)]
 pub enum HubError {
@@ -15,6 +17,48 @@ pub enum HubError {
     ConversionFailed(String),
 }
 
+/// How Roman input reads an apostrophe, which Sanskrit writes for avagraha
+///
+/// IAST, ISO 15919 and Harvard-Kyoto write avagraha as ' (or ’ and U+02BC ʼ),
+/// the same character English text uses for possessives and quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ApostropheAvagraha {
+    /// Avagraha after a vowel and before more of the word (so'ham), punctuation
+    /// anywhere else (Arjuna's, 'quoted')
+    #[default]
+    Contextual,
+    /// Every apostrophe is avagraha
+    Always,
+    /// Every apostrophe is punctuation
+    Never,
+}
+
+/// The apostrophes Roman schemes read as avagraha
+pub const AVAGRAHA_APOSTROPHES: [&str; 3] = ["'", "\u{2019}", "\u{02BC}"];
+
+/// Whether the apostrophe at `i` reads as avagraha in context
+///
+/// It must follow a vowel, allowing for accents, and be followed in the same
+/// word by a syllable: letters with a vowel among them. The s of a possessive
+/// has no vowel, so "Arjuna's" keeps its apostrophe.
+pub fn is_contextual_avagraha(tokens: &[HubToken], i: usize) -> bool {
+    let after_vowel = tokens[..i]
+        .iter()
+        .rev()
+        .find(|token| !token.is_vedic_accent())
+        .is_some_and(HubToken::is_vowel);
+    let before_syllable = tokens[i + 1..]
+        .iter()
+        .take_while(|token| {
+            token.is_vowel()
+                || token.is_consonant()
+                || token.is_yogavaha()
+                || token.is_vedic_accent()
+        })
+        .any(HubToken::is_vowel);
+    after_vowel && before_syllable
+}
+
 /// Hub format representation - token-based only
 #[derive(Debug, Clone, PartialEq)]
 pub enum HubFormat {
@@ -55,6 +99,283 @@ impl HubFormat {
     pub fn is_alphabet(&self) -> bool {
         matches!(self, HubFormat::AlphabetTokens(_))
     }
+
+    /// The tokens, of either type
+    pub fn tokens(&self) -> &HubTokenSequence {
+        match self {
+            HubFormat::AbugidaTokens(tokens) | HubFormat::AlphabetTokens(tokens) => tokens,
+        }
+    }
+
+    /// Read a lone digit 3 that ends a syllable as the pluta (prolonged vowel) mark
+    ///
+    /// Vedic texts write आ३ (ā3) for a pluta ā. A 3 after a vowel, vowel sign or
+    /// consonant with its inherent a, allowing for accents in between, becomes
+    /// `MarkPluta`; a 3 followed by another digit stays a digit.
+    pub fn mark_pluta(&mut self) {
+        let tokens = match self {
+            HubFormat::AbugidaTokens(tokens) | HubFormat::AlphabetTokens(tokens) => tokens,
+        };
+
+        for i in 1..tokens.len() {
+            let pluta = match &tokens[i] {
+                HubToken::Abugida(AbugidaToken::Digit3) => {
+                    HubToken::Abugida(AbugidaToken::MarkPluta)
+                }
+                HubToken::Alphabet(AlphabetToken::Digit3) => {
+                    HubToken::Alphabet(AlphabetToken::MarkPluta)
+                }
+                _ => continue,
+            };
+            let ends_syllable = tokens[..i]
+                .iter()
+                .rev()
+                .find(|token| !token.is_vedic_accent())
+                .is_some_and(|token| match token {
+                    HubToken::Abugida(token) => {
+                        token.is_vowel() || token.is_vowel_sign() || token.is_consonant()
+                    }
+                    HubToken::Alphabet(token) => token.is_vowel(),
+                });
+            let next_is_digit = tokens.get(i + 1).is_some_and(is_digit);
+            if ends_syllable && !next_is_digit {
+                tokens[i] = pluta;
+            }
+        }
+    }
+
+    /// Read every apostrophe of Roman input as avagraha, or none, whatever its context
+    ///
+    /// `positions` are where each token was read in `input`. Converters read
+    /// apostrophes by context; this overrides them for `Always` and `Never`.
+    pub fn read_apostrophes(
+        &mut self,
+        input: &str,
+        positions: &[usize],
+        reading: ApostropheAvagraha,
+    ) {
+        let HubFormat::AlphabetTokens(tokens) = self else {
+            return;
+        };
+
+        for (token, &position) in tokens.iter_mut().zip(positions) {
+            let apostrophe = AVAGRAHA_APOSTROPHES
+                .iter()
+                .find(|apostrophe| input[position..].starts_with(*apostrophe));
+            let Some(apostrophe) = apostrophe else {
+                continue;
+            };
+            match (reading, &*token) {
+                (ApostropheAvagraha::Always, HubToken::Alphabet(AlphabetToken::Unknown(_))) => {
+                    *token = HubToken::Alphabet(AlphabetToken::MarkAvagraha);
+                }
+                (ApostropheAvagraha::Never, HubToken::Alphabet(AlphabetToken::MarkAvagraha)) => {
+                    *token = HubToken::Alphabet(AlphabetToken::Unknown(apostrophe.to_string()));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Write each consonant followed by a nukta that has a letter of its own as that letter
+    ///
+    /// क + ़ becomes क़ (`ConsonantQa`), the form every schema renders.
+    pub fn compose_nukta(&mut self) {
+        let HubFormat::AbugidaTokens(tokens) = self else {
+            return;
+        };
+
+        // One pass, so that a long chain of conjuncts is not shifted once per nukta
+        let mut composed: HubTokenSequence = Vec::with_capacity(tokens.len());
+        for token in tokens.drain(..) {
+            if token == HubToken::Abugida(AbugidaToken::MarkNukta) {
+                if let Some(HubToken::Abugida(consonant)) = composed.last_mut() {
+                    if let Some(letter) = trait_based_converter::nukta_form(consonant) {
+                        *consonant = letter;
+                        continue;
+                    }
+                }
+            }
+            composed.push(token);
+        }
+        *tokens = composed;
+    }
+
+    /// Keep zero-width joiners only where they select a conjunct form
+    ///
+    /// A ZWJ or ZWNJ directly after a virama asks for a half form or a visible
+    /// virama; anywhere else it has no effect and is dropped. The older spelling
+    /// consonant + ZWJ + virama is written consonant + virama + ZWJ, and of several
+    /// joiners in a row only the first is kept.
+    pub fn normalize_joiners(&mut self) {
+        let HubFormat::AbugidaTokens(tokens) = self else {
+            return;
+        };
+
+        for i in 2..tokens.len() {
+            if tokens[i] == HubToken::Abugida(AbugidaToken::MarkVirama)
+                && is_joiner(&tokens[i - 1])
+                && matches!(&tokens[i - 2], HubToken::Abugida(token) if token.is_consonant())
+            {
+                tokens.swap(i - 1, i);
+            }
+        }
+
+        let mut kept: HubTokenSequence = Vec::with_capacity(tokens.len());
+        for token in tokens.drain(..) {
+            if is_joiner(&token)
+                && kept.last() != Some(&HubToken::Abugida(AbugidaToken::MarkVirama))
+            {
+                continue;
+            }
+            kept.push(token);
+        }
+        *tokens = kept;
+    }
+}
+
+/// Zero-width joiner or non-joiner, which script converters pass through as unknown
+pub(crate) fn is_joiner(token: &HubToken) -> bool {
+    matches!(token.as_unknown_string(), Some("\u{200C}" | "\u{200D}"))
+}
+
+/// Whether `ch` is a combining mark of the Devanagari Extended (U+A8E0–A8FF)
+/// or Vedic Extensions (U+1CD0–1CFF) blocks, such as the Samavedic svara
+/// numerals written above an akshara
+pub fn is_attached_mark(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{A8E0}'..='\u{A8F1}'
+            | '\u{A8FF}'
+            | '\u{1CD0}'..='\u{1CD2}'
+            | '\u{1CD4}'..='\u{1CE8}'
+            | '\u{1CED}'
+            | '\u{1CF4}'
+            | '\u{1CF7}'..='\u{1CF9}'
+    )
+}
+
+/// An unknown token that is an attached mark, written on the akshara before it
+pub(crate) fn is_attached_unknown(token: &HubToken) -> bool {
+    token
+        .as_unknown_string()
+        .and_then(|text| text.chars().next())
+        .is_some_and(is_attached_mark)
+}
+
+/// Move each attached unknown after the nukta, vowel sign, virama and marks
+/// that follow it, so that it ends the akshara it is written on
+///
+/// Scripts that do not map these marks read them as unknown tokens, which
+/// would otherwise part a consonant from its vowel sign: र꣣ा is read as रा꣣.
+/// Marks keep their order among themselves, and `positions` move with their
+/// tokens.
+pub(crate) fn attach_unknowns(tokens: &mut [HubToken], mut positions: Option<&mut Vec<usize>>) {
+    let is_dependent = |token: &HubToken| match token {
+        HubToken::Abugida(token) => {
+            token.is_vowel_sign()
+                || matches!(token, AbugidaToken::MarkVirama | AbugidaToken::MarkNukta)
+                || (token.is_yogavaha() && *token != AbugidaToken::MarkAvagraha)
+                || token.is_vedic_accent()
+        }
+        HubToken::Alphabet(_) => false,
+    };
+
+    let mut i = 0;
+    while i < tokens.len() {
+        if !is_attached_unknown(&tokens[i]) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < tokens.len() && (is_attached_unknown(&tokens[i]) || is_dependent(&tokens[i])) {
+            i += 1;
+        }
+        if tokens[start..i].iter().all(is_attached_unknown) {
+            continue;
+        }
+        let order: Vec<usize> = (start..i)
+            .filter(|&k| !is_attached_unknown(&tokens[k]))
+            .chain((start..i).filter(|&k| is_attached_unknown(&tokens[k])))
+            .collect();
+        let reordered: Vec<HubToken> = order.iter().map(|&k| tokens[k].clone()).collect();
+        tokens[start..i].clone_from_slice(&reordered);
+        if let Some(positions) = positions.as_deref_mut() {
+            let reordered: Vec<usize> = order.iter().map(|&k| positions[k]).collect();
+            positions[start..i].copy_from_slice(&reordered);
+        }
+    }
+}
+
+/// Anusvara, visarga or candrabindu, which Unicode writes after the vowel sign
+/// of their akshara
+pub(crate) fn follows_vowel_sign(token: &AbugidaToken) -> bool {
+    matches!(
+        token,
+        AbugidaToken::MarkAnusvara | AbugidaToken::MarkVisarga | AbugidaToken::MarkCandrabindu
+    )
+}
+
+/// Move each vowel sign read after the anusvara, visarga or candrabindu of its
+/// consonant back before them
+///
+/// Text typed by the look of the glyphs can have कंो for कों, or दःु for दुः.
+/// Scripts that draw the marks elsewhere would otherwise write them before the
+/// vowel, and reading the output back would not give the same tokens.
+/// `positions` move with their tokens.
+pub(crate) fn order_vowel_signs(tokens: &mut [HubToken], mut positions: Option<&mut Vec<usize>>) {
+    let is_mark =
+        |token: &HubToken| matches!(token, HubToken::Abugida(token) if follows_vowel_sign(token));
+    for i in 1..tokens.len() {
+        if !matches!(&tokens[i], HubToken::Abugida(token) if token.is_vowel_sign()) {
+            continue;
+        }
+        let mut start = i;
+        while start > 0 && is_mark(&tokens[start - 1]) {
+            start -= 1;
+        }
+        let after_consonant = start > 0
+            && matches!(&tokens[start - 1], HubToken::Abugida(token) if token.is_consonant());
+        if start == i || !after_consonant {
+            continue;
+        }
+        tokens[start..=i].rotate_right(1);
+        if let Some(positions) = positions.as_deref_mut() {
+            positions[start..=i].rotate_right(1);
+        }
+    }
+}
+
+/// Whether `token` is one of the digits 0 to 9, of either hub token type
+pub(crate) fn is_digit(token: &HubToken) -> bool {
+    use AbugidaToken as Ab;
+    use AlphabetToken as Al;
+    matches!(
+        token,
+        HubToken::Abugida(
+            Ab::Digit0
+                | Ab::Digit1
+                | Ab::Digit2
+                | Ab::Digit3
+                | Ab::Digit4
+                | Ab::Digit5
+                | Ab::Digit6
+                | Ab::Digit7
+                | Ab::Digit8
+                | Ab::Digit9
+        ) | HubToken::Alphabet(
+            Al::Digit0
+                | Al::Digit1
+                | Al::Digit2
+                | Al::Digit3
+                | Al::Digit4
+                | Al::Digit5
+                | Al::Digit6
+                | Al::Digit7
+                | Al::Digit8
+                | Al::Digit9
+        )
+    )
 }
 
 // Type aliases for backward compatibility
@@ -1,5 +1,5 @@
 use super::*;
-use crate::modules::hub::tokens::{AbugidaToken, AlphabetToken, HubToken};
+use crate::modules::hub::tokens::{AbugidaToken, AlphabetToken, HubToken, TokenCategory};
 
 #[test]
 fn test_hub_creation() {
@@ -166,3 +166,163 @@ fn test_mark_reordering_alphabet_to_abugida() {
         Err(e) => panic!("Conversion failed: {:?}", e),
     }
 }
+
+#[test]
+fn test_word_final_consonant_gets_virama() {
+    let hub = Hub::new();
+
+    // vāk tat: the k before a space and the t at the end have no vowel
+    let input_tokens = vec![
+        HubToken::Alphabet(AlphabetToken::ConsonantV),
+        HubToken::Alphabet(AlphabetToken::VowelAa),
+        HubToken::Alphabet(AlphabetToken::ConsonantK),
+        HubToken::Alphabet(AlphabetToken::Unknown(" ".to_string())),
+        HubToken::Alphabet(AlphabetToken::ConsonantTt),
+        HubToken::Alphabet(AlphabetToken::VowelA),
+        HubToken::Alphabet(AlphabetToken::ConsonantTt),
+    ];
+
+    let output_tokens = hub.alphabet_to_abugida_tokens(&input_tokens).unwrap();
+    assert_eq!(
+        output_tokens,
+        vec![
+            HubToken::Abugida(AbugidaToken::ConsonantV),
+            HubToken::Abugida(AbugidaToken::VowelSignAa),
+            HubToken::Abugida(AbugidaToken::ConsonantK),
+            HubToken::Abugida(AbugidaToken::MarkVirama),
+            HubToken::Abugida(AbugidaToken::Unknown(" ".to_string())),
+            HubToken::Abugida(AbugidaToken::ConsonantTt),
+            HubToken::Abugida(AbugidaToken::ConsonantTt),
+            HubToken::Abugida(AbugidaToken::MarkVirama),
+        ]
+    );
+
+    // And back: the virama suppresses the implicit a, which is otherwise written out
+    let roundtrip = hub.abugida_to_alphabet_tokens(&output_tokens).unwrap();
+    assert_eq!(roundtrip, input_tokens);
+}
+
+/// Each vowel sign with the vowel of the same sound, in both token types
+fn vowels_with_signs() -> Vec<(AlphabetToken, AbugidaToken, AbugidaToken)> {
+    let (_, signs) = AbugidaToken::INVENTORY
+        .iter()
+        .find(|(category, _)| *category == TokenCategory::VowelSigns)
+        .unwrap();
+    signs
+        .iter()
+        .map(|sign| {
+            let vowel = sign.replace("VowelSign", "Vowel");
+            (
+                vowel.parse().unwrap(),
+                vowel.parse().unwrap(),
+                sign.parse().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_every_vowel_but_a_has_a_sign() {
+    let (_, vowels) = AbugidaToken::INVENTORY
+        .iter()
+        .find(|(category, _)| *category == TokenCategory::Vowels)
+        .unwrap();
+    let with_signs: Vec<_> = vowels_with_signs()
+        .into_iter()
+        .map(|(_, vowel, _)| vowel)
+        .collect();
+    for vowel in vowels.iter().filter(|vowel| **vowel != "VowelA") {
+        assert!(with_signs.contains(&vowel.parse().unwrap()), "{vowel}");
+    }
+}
+
+#[test]
+fn test_vowel_after_consonant_becomes_sign() {
+    let hub = Hub::new();
+    for (vowel, _, sign) in vowels_with_signs() {
+        let input = vec![
+            HubToken::Alphabet(AlphabetToken::ConsonantK),
+            HubToken::Alphabet(vowel.clone()),
+        ];
+        let expected = vec![
+            HubToken::Abugida(AbugidaToken::ConsonantK),
+            HubToken::Abugida(sign),
+        ];
+        assert_eq!(
+            hub.alphabet_to_abugida_tokens(&input).unwrap(),
+            expected,
+            "{vowel}"
+        );
+        assert_eq!(hub.abugida_to_alphabet_tokens(&expected).unwrap(), input);
+    }
+}
+
+#[test]
+fn test_vowel_without_consonant_stays_independent() {
+    let hub = Hub::new();
+    for (vowel, abugida_vowel, _) in vowels_with_signs() {
+        // At word start
+        let input = vec![HubToken::Alphabet(vowel.clone())];
+        let expected = vec![HubToken::Abugida(abugida_vowel.clone())];
+        assert_eq!(hub.alphabet_to_abugida_tokens(&input).unwrap(), expected);
+        assert_eq!(hub.abugida_to_alphabet_tokens(&expected).unwrap(), input);
+
+        // After another vowel
+        let input = vec![
+            HubToken::Alphabet(AlphabetToken::VowelI),
+            HubToken::Alphabet(vowel.clone()),
+        ];
+        let expected = vec![
+            HubToken::Abugida(AbugidaToken::VowelI),
+            HubToken::Abugida(abugida_vowel.clone()),
+        ];
+        assert_eq!(hub.alphabet_to_abugida_tokens(&input).unwrap(), expected);
+        assert_eq!(hub.abugida_to_alphabet_tokens(&expected).unwrap(), input);
+
+        // After a consonant's written-out a, in hiatus
+        let input = vec![
+            HubToken::Alphabet(AlphabetToken::ConsonantK),
+            HubToken::Alphabet(AlphabetToken::VowelA),
+            HubToken::Alphabet(vowel.clone()),
+        ];
+        let expected = vec![
+            HubToken::Abugida(AbugidaToken::ConsonantK),
+            HubToken::Abugida(abugida_vowel),
+        ];
+        assert_eq!(
+            hub.alphabet_to_abugida_tokens(&input).unwrap(),
+            expected,
+            "{vowel}"
+        );
+        assert_eq!(hub.abugida_to_alphabet_tokens(&expected).unwrap(), input);
+    }
+}
+
+#[test]
+fn test_vowel_sign_moves_before_its_marks() {
+    use AbugidaToken::*;
+    let abugida = |tokens: &[AbugidaToken]| -> Vec<HubToken> {
+        tokens.iter().cloned().map(HubToken::Abugida).collect()
+    };
+
+    // कंःु: both marks of the akshara follow its vowel sign
+    let mut tokens = abugida(&[ConsonantK, MarkAnusvara, MarkVisarga, VowelSignU]);
+    let mut positions = vec![0, 3, 6, 9];
+    order_vowel_signs(&mut tokens, Some(&mut positions));
+    assert_eq!(
+        tokens,
+        abugida(&[ConsonantK, VowelSignU, MarkAnusvara, MarkVisarga])
+    );
+    assert_eq!(positions, [0, 9, 3, 6]);
+
+    // Already in order, or with no consonant to attach to
+    for sequence in [
+        &[ConsonantK, VowelSignU, MarkAnusvara][..],
+        &[VowelA, MarkCandrabindu, VowelSignAa],
+        &[MarkAnusvara, VowelSignI],
+    ] {
+        let mut tokens = abugida(sequence);
+        order_vowel_signs(&mut tokens, None);
+        assert_eq!(tokens, abugida(sequence));
+    }
+}
@@ -4,24 +4,276 @@ use super::{AbugidaToken, AlphabetToken, HubError, HubToken, HubTokenSequence};
 /// Uses an optimized state machine approach instead of stack-based processing
 pub struct TraitBasedConverter;
 
+/// A hub conversion with a record of where each token came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct TracedConversion {
+    /// The converted tokens
+    pub tokens: HubTokenSequence,
+    /// For each converted token, the index of the input token it was converted from
+    pub origins: Vec<usize>,
+    /// Indices of input tokens with no counterpart in the other token type, which were dropped
+    pub unconverted: Vec<usize>,
+}
+
+/// A token of a streamed conversion, or text read as no token
+///
+/// Streamed conversions borrow the text they could not read from the input,
+/// where token sequences copy it into `Unknown` tokens.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Streamed<'a> {
+    Token(HubToken),
+    Unknown(&'a str),
+}
+
+/// Converts abugida tokens to alphabet tokens as they arrive, without collecting them
+///
+/// Gives the output of [`TraitBasedConverter::abugida_to_alphabet`] one token
+/// at a time: a consonant is held until the token after it shows whether it
+/// keeps its inherent a, and a yogavaha until the vedic accents Roman text
+/// writes before it have passed. Call [`Self::finish`] after the last token.
+///
+/// Token sequences move a vowel sign read after an anusvara, visarga or
+/// candrabindu before it, which a stream cannot; [`Self::needs_reordering`]
+/// tells when the input had one, and its output is then not to be used.
+pub struct AbugidaToAlphabetStream<F> {
+    emit: F,
+    /// A consonant waiting for the token after it
+    consonant: Option<AbugidaToken>,
+    nukta_letters: NuktaLetters,
+    yogavaha: Option<HubToken>,
+    error: Option<HubError>,
+    /// Whether the last token was a mark a vowel sign is written before
+    after_mark: bool,
+    misordered: bool,
+}
+
+impl<'a, F: FnMut(Streamed<'a>)> AbugidaToAlphabetStream<F> {
+    /// A stream passing each converted token to `emit`
+    pub fn new(emit: F) -> Self {
+        Self {
+            emit,
+            consonant: None,
+            nukta_letters: NuktaLetters::NONE,
+            yogavaha: None,
+            error: None,
+            after_mark: false,
+            misordered: false,
+        }
+    }
+
+    /// Read a consonant + nukta as one of `letters`, as [`TraitBasedConverter::abugida_to_alphabet_composing`] does
+    pub fn composing(mut self, letters: NuktaLetters) -> Self {
+        self.nukta_letters = letters;
+        self
+    }
+
+    /// Whether a vowel sign followed an anusvara, visarga or candrabindu
+    pub fn needs_reordering(&self) -> bool {
+        self.misordered
+    }
+
+    /// Convert the next token; tokens after a failed one are ignored
+    pub fn push(&mut self, item: Streamed<'a>) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Streamed::Token(HubToken::Abugida(token)) = &item {
+            self.misordered |= self.after_mark && token.is_vowel_sign();
+            self.after_mark = super::follows_vowel_sign(token);
+        } else {
+            self.after_mark = false;
+        }
+        if let Some(consonant) = self.consonant.take() {
+            // A nukta with no letter of its own is dropped, so the vowel after it decides
+            if item == Streamed::Token(HubToken::Abugida(AbugidaToken::MarkNukta)) {
+                self.consonant = Some(self.nukta_letters.compose(&consonant).unwrap_or(consonant));
+                return;
+            }
+            let has_explicit_vowel = matches!(
+                &item,
+                Streamed::Token(HubToken::Abugida(next)) if next.is_virama() || next.is_vowel_sign()
+            );
+            self.write_consonant(consonant, has_explicit_vowel);
+        }
+        match item {
+            Streamed::Token(HubToken::Abugida(token)) => self.convert(token),
+            Streamed::Token(token) => self.write(token),
+            Streamed::Unknown(text) => {
+                self.flush_yogavaha();
+                (self.emit)(Streamed::Unknown(text));
+            }
+        }
+    }
+
+    /// Write the tokens still held, ending the stream
+    pub fn finish(mut self) -> Result<(), HubError> {
+        if let (Some(consonant), None) = (self.consonant.take(), &self.error) {
+            self.write_consonant(consonant, false);
+        }
+        self.flush_yogavaha();
+        self.error.map_or(Ok(()), Err)
+    }
+
+    fn convert(&mut self, token: AbugidaToken) {
+        if token.is_consonant() {
+            self.consonant = Some(token);
+        } else if token.is_virama() {
+            // The consonant before it was written without its inherent a
+        } else if token.is_vowel_sign() {
+            if let Some(vowel) = token.sign_to_vowel().and_then(|vowel| vowel.to_alphabet()) {
+                self.write(HubToken::Alphabet(vowel));
+            }
+        } else if token == AbugidaToken::SpecialOm {
+            self.write(HubToken::Alphabet(AlphabetToken::VowelOo));
+            self.write(HubToken::Alphabet(AlphabetToken::MarkAnusvara));
+        } else if let Some(alphabet_token) = token.to_alphabet() {
+            self.write(HubToken::Alphabet(alphabet_token));
+        }
+    }
+
+    fn write_consonant(&mut self, consonant: AbugidaToken, has_explicit_vowel: bool) {
+        match consonant.to_alphabet() {
+            Some(alphabet_token) => {
+                self.write(HubToken::Alphabet(alphabet_token));
+                if !has_explicit_vowel {
+                    self.write(HubToken::Alphabet(AlphabetToken::VowelA));
+                }
+            }
+            None => {
+                self.error = Some(HubError::MappingNotFound(format!(
+                    "No alphabet mapping for {:?}",
+                    consonant
+                )))
+            }
+        }
+    }
+
+    fn write(&mut self, token: HubToken) {
+        // In Roman scripts, vedic accents come before yogavaha marks
+        if self.yogavaha.is_some() && token.is_vedic_accent() {
+            (self.emit)(Streamed::Token(token));
+            return;
+        }
+        self.flush_yogavaha();
+        if token.is_yogavaha() {
+            self.yogavaha = Some(token);
+        } else {
+            (self.emit)(Streamed::Token(token));
+        }
+    }
+
+    fn flush_yogavaha(&mut self) {
+        if let Some(yogavaha) = self.yogavaha.take() {
+            (self.emit)(Streamed::Token(yogavaha));
+        }
+    }
+}
+
+/// Converted tokens, with their origins when tracing
+struct Output {
+    tokens: HubTokenSequence,
+    origins: Option<Vec<usize>>,
+    unconverted: Vec<usize>,
+}
+
+impl Output {
+    fn new(capacity: usize, trace: bool) -> Self {
+        Self {
+            tokens: Vec::with_capacity(capacity),
+            origins: trace.then(|| Vec::with_capacity(capacity)),
+            unconverted: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, token: HubToken, origin: usize) {
+        self.tokens.push(token);
+        if let Some(origins) = &mut self.origins {
+            origins.push(origin);
+        }
+    }
+
+    fn pop(&mut self) -> Option<(HubToken, usize)> {
+        let token = self.tokens.pop()?;
+        let origin = self.origins.as_mut().and_then(Vec::pop).unwrap_or_default();
+        Some((token, origin))
+    }
+
+    fn last(&self) -> Option<&HubToken> {
+        self.tokens.last()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    fn into_traced(self) -> TracedConversion {
+        TracedConversion {
+            tokens: self.tokens,
+            origins: self.origins.unwrap_or_default(),
+            unconverted: self.unconverted,
+        }
+    }
+}
+
 impl TraitBasedConverter {
     /// Convert abugida tokens to alphabet tokens using state machine approach
     pub fn abugida_to_alphabet(tokens: &HubTokenSequence) -> Result<HubTokenSequence, HubError> {
+        Self::abugida_to_alphabet_composing(tokens, NuktaLetters::NONE)
+    }
+
+    /// Convert abugida tokens to alphabet tokens, reading a consonant + nukta as one of `letters`
+    pub fn abugida_to_alphabet_composing(
+        tokens: &HubTokenSequence,
+        letters: NuktaLetters,
+    ) -> Result<HubTokenSequence, HubError> {
+        Ok(Self::abugida_to_alphabet_impl(tokens, false, letters)?.tokens)
+    }
+
+    /// Convert abugida tokens to alphabet tokens, tracing each token's origin
+    pub fn abugida_to_alphabet_traced(
+        tokens: &HubTokenSequence,
+        letters: NuktaLetters,
+    ) -> Result<TracedConversion, HubError> {
+        Ok(Self::abugida_to_alphabet_impl(tokens, true, letters)?.into_traced())
+    }
+
+    fn abugida_to_alphabet_impl(
+        tokens: &HubTokenSequence,
+        trace: bool,
+        letters: NuktaLetters,
+    ) -> Result<Output, HubError> {
         // Pre-allocate with estimated capacity
-        let mut result = Vec::with_capacity(tokens.len());
+        let mut result = Output::new(tokens.len(), trace);
 
         let mut i = 0;
         while i < tokens.len() {
+            let index = i;
             match &tokens[i] {
                 HubToken::Abugida(abugida_token) => {
                     if abugida_token.is_consonant() {
+                        let nukta_letter = match tokens.get(i + 1) {
+                            Some(HubToken::Abugida(AbugidaToken::MarkNukta)) => {
+                                letters.compose(abugida_token)
+                            }
+                            _ => None,
+                        };
+                        if nukta_letter.is_some() {
+                            i += 1;
+                        }
+                        let abugida_token = nukta_letter.as_ref().unwrap_or(abugida_token);
+
                         // Find corresponding alphabet consonant
                         if let Some(alphabet_token) = abugida_token.to_alphabet() {
-                            result.push(HubToken::Alphabet(alphabet_token));
+                            result.push(HubToken::Alphabet(alphabet_token), index);
 
-                            // Check if next token is virama or vowel sign
-                            let has_explicit_vowel = if i + 1 < tokens.len() {
-                                match &tokens[i + 1] {
+                            // Check if next token is virama or vowel sign, past a nukta
+                            let mut next = i + 1;
+                            if tokens.get(next) == Some(&HubToken::Abugida(AbugidaToken::MarkNukta))
+                            {
+                                next += 1;
+                            }
+                            let has_explicit_vowel = if next < tokens.len() {
+                                match &tokens[next] {
                                     HubToken::Abugida(next) => {
                                         next.is_virama() || next.is_vowel_sign()
                                     }
@@ -33,12 +285,15 @@ impl TraitBasedConverter {
 
                             // Add implicit 'a' if no virama or vowel sign follows
                             if !has_explicit_vowel {
-                                result.push(HubToken::Alphabet(AlphabetToken::VowelA));
+                                result.push(HubToken::Alphabet(AlphabetToken::VowelA), index);
                             }
                         } else {
                             // No mapping - preserve as unknown
                             if let AbugidaToken::Unknown(s) = abugida_token {
-                                result.push(HubToken::Alphabet(AlphabetToken::Unknown(s.clone())));
+                                result.push(
+                                    HubToken::Alphabet(AlphabetToken::Unknown(s.clone())),
+                                    index,
+                                );
                             } else {
                                 return Err(HubError::MappingNotFound(format!(
                                     "No alphabet mapping for {:?}",
@@ -50,17 +305,24 @@ impl TraitBasedConverter {
                         // Virama consumed - skip it (implicit 'a' already suppressed above)
                     } else if abugida_token.is_vowel_sign() {
                         // Convert vowel sign to corresponding vowel
-                        if let Some(vowel) = abugida_token.sign_to_vowel() {
-                            if let Some(alphabet_vowel) = vowel.to_alphabet() {
-                                result.push(HubToken::Alphabet(alphabet_vowel));
+                        match abugida_token
+                            .sign_to_vowel()
+                            .and_then(|vowel| vowel.to_alphabet())
+                        {
+                            Some(alphabet_vowel) => {
+                                result.push(HubToken::Alphabet(alphabet_vowel), index)
                             }
+                            None => result.unconverted.push(index),
                         }
                     } else if abugida_token.is_vowel() {
                         // Independent vowel
                         if let Some(alphabet_vowel) = abugida_token.to_alphabet() {
-                            result.push(HubToken::Alphabet(alphabet_vowel));
+                            result.push(HubToken::Alphabet(alphabet_vowel), index);
                         } else if let AbugidaToken::Unknown(s) = abugida_token {
-                            result.push(HubToken::Alphabet(AlphabetToken::Unknown(s.clone())));
+                            result
+                                .push(HubToken::Alphabet(AlphabetToken::Unknown(s.clone())), index);
+                        } else {
+                            result.unconverted.push(index);
                         }
                     } else if abugida_token.is_mark() {
                         if let Some(alphabet_mark) = abugida_token.to_alphabet() {
@@ -70,35 +332,38 @@ impl TraitBasedConverter {
                             // If we're converting from Indic (where it's yogavaha + accent)
                             // to Roman (where it's accent + yogavaha), check if this is a vedic accent
                             // and the previous token was yogavaha
-                            if current_token.is_vedic_accent() && !result.is_empty() {
-                                if let Some(last_token) = result.last() {
-                                    if last_token.is_yogavaha() {
-                                        // Pop the yogavaha, push vedic accent, then push yogavaha back
-                                        let yogavaha = result.pop().unwrap();
-                                        result.push(current_token);
-                                        result.push(yogavaha);
-                                    } else {
-                                        result.push(current_token);
-                                    }
-                                } else {
-                                    result.push(current_token);
-                                }
+                            let yogavaha = if current_token.is_vedic_accent()
+                                && result.last().is_some_and(|last| last.is_yogavaha())
+                            {
+                                // Pop the yogavaha, push vedic accent, then push yogavaha back
+                                result.pop()
                             } else {
-                                result.push(current_token);
+                                None
+                            };
+                            result.push(current_token, index);
+                            if let Some((yogavaha, origin)) = yogavaha {
+                                result.push(yogavaha, origin);
                             }
                         } else if let AbugidaToken::Unknown(s) = abugida_token {
-                            result.push(HubToken::Alphabet(AlphabetToken::Unknown(s.clone())));
+                            result
+                                .push(HubToken::Alphabet(AlphabetToken::Unknown(s.clone())), index);
+                        } else {
+                            result.unconverted.push(index);
                         }
+                    } else if *abugida_token == AbugidaToken::SpecialOm {
+                        // Roman schemes have no om ligature - spell it out as ōṁ
+                        result.push(HubToken::Alphabet(AlphabetToken::VowelOo), index);
+                        result.push(HubToken::Alphabet(AlphabetToken::MarkAnusvara), index);
+                    } else if let Some(alphabet_token) = abugida_token.to_alphabet() {
+                        // Digits and other tokens shared by both token types (Unknown included)
+                        result.push(HubToken::Alphabet(alphabet_token), index);
                     } else {
-                        // Unknown token type - preserve
-                        if let AbugidaToken::Unknown(s) = abugida_token {
-                            result.push(HubToken::Alphabet(AlphabetToken::Unknown(s.clone())));
-                        }
+                        result.unconverted.push(index);
                     }
                 }
                 HubToken::Alphabet(_) => {
                     // Already alphabet - pass through
-                    result.push(tokens[i].clone());
+                    result.push(tokens[i].clone(), index);
                 }
             }
             i += 1;
@@ -109,17 +374,32 @@ impl TraitBasedConverter {
 
     /// Convert alphabet tokens to abugida tokens using state machine approach
     pub fn alphabet_to_abugida(tokens: &HubTokenSequence) -> Result<HubTokenSequence, HubError> {
+        Ok(Self::alphabet_to_abugida_impl(tokens, false)?.tokens)
+    }
+
+    /// Convert alphabet tokens to abugida tokens, tracing each token's origin
+    pub fn alphabet_to_abugida_traced(
+        tokens: &HubTokenSequence,
+    ) -> Result<TracedConversion, HubError> {
+        Ok(Self::alphabet_to_abugida_impl(tokens, true)?.into_traced())
+    }
+
+    fn alphabet_to_abugida_impl(
+        tokens: &HubTokenSequence,
+        trace: bool,
+    ) -> Result<Output, HubError> {
         // Pre-allocate with estimated capacity (worst case: each consonant needs a virama)
-        let mut result = Vec::with_capacity(tokens.len() * 2);
+        let mut result = Output::new(tokens.len() * 2, trace);
 
         let mut i = 0;
         while i < tokens.len() {
+            let index = i;
             match &tokens[i] {
                 HubToken::Alphabet(alphabet_token) => {
                     if alphabet_token.is_consonant() {
                         // Convert consonant
                         if let Some(abugida_consonant) = alphabet_token.to_abugida() {
-                            result.push(HubToken::Abugida(abugida_consonant));
+                            result.push(HubToken::Abugida(abugida_consonant), index);
 
                             // Look ahead to determine if we need a virama
                             let needs_virama = if i + 1 < tokens.len() {
@@ -148,14 +428,19 @@ impl TraitBasedConverter {
                             };
 
                             if needs_virama {
-                                result.push(HubToken::Abugida(AbugidaToken::MarkVirama));
+                                result.push(HubToken::Abugida(AbugidaToken::MarkVirama), index);
                             }
                         } else if let AlphabetToken::Unknown(s) = alphabet_token {
-                            result.push(HubToken::Abugida(AbugidaToken::Unknown(s.clone())));
+                            result.push(HubToken::Abugida(AbugidaToken::Unknown(s.clone())), index);
+                        } else {
+                            result.unconverted.push(index);
                         }
                     } else if alphabet_token.is_vowel() {
                         // Check if this vowel follows a consonant (for vowel sign conversion)
-                        let prev_was_consonant = if !result.is_empty() {
+                        // A vowel after a consonant's explicit 'a' stands on its own (pra-uga)
+                        let follows_explicit_a =
+                            i > 0 && tokens[i - 1] == HubToken::Alphabet(AlphabetToken::VowelA);
+                        let prev_was_consonant = if !result.is_empty() && !follows_explicit_a {
                             match result.last() {
                                 Some(HubToken::Abugida(prev)) => prev.is_consonant(),
                                 _ => false,
@@ -166,21 +451,28 @@ impl TraitBasedConverter {
 
                         if prev_was_consonant && *alphabet_token != AlphabetToken::VowelA {
                             // Convert to vowel sign after consonant
-                            if let Some(abugida_vowel) = alphabet_token.to_abugida() {
-                                if let Some(sign) = abugida_vowel.vowel_to_sign() {
+                            match alphabet_token
+                                .to_abugida()
+                                .and_then(|vowel| vowel.vowel_to_sign())
+                            {
+                                Some(sign) => {
                                     // Remove virama if it was added
                                     if let Some(HubToken::Abugida(AbugidaToken::MarkVirama)) =
                                         result.last()
                                     {
                                         result.pop();
                                     }
-                                    result.push(HubToken::Abugida(sign));
+                                    result.push(HubToken::Abugida(sign), index);
                                 }
+                                None => result.unconverted.push(index),
                             }
                         } else if *alphabet_token != AlphabetToken::VowelA || !prev_was_consonant {
                             // Independent vowel (not implicit 'a')
-                            if let Some(abugida_vowel) = alphabet_token.to_abugida() {
-                                result.push(HubToken::Abugida(abugida_vowel));
+                            match alphabet_token.to_abugida() {
+                                Some(abugida_vowel) => {
+                                    result.push(HubToken::Abugida(abugida_vowel), index)
+                                }
+                                None => result.unconverted.push(index),
                             }
                         }
                         // If it's VowelA after consonant, it's implicit - already handled
@@ -195,11 +487,13 @@ impl TraitBasedConverter {
                                 if let HubToken::Alphabet(next_token) = &tokens[i + 1] {
                                     if next_token.is_yogavaha() {
                                         // Convert and push yogavaha first
-                                        if let Some(abugida_yogavaha) = next_token.to_abugida() {
-                                            result.push(HubToken::Abugida(abugida_yogavaha));
+                                        match next_token.to_abugida() {
+                                            Some(abugida_yogavaha) => result
+                                                .push(HubToken::Abugida(abugida_yogavaha), i + 1),
+                                            None => result.unconverted.push(i + 1),
                                         }
                                         // Then push the vedic accent
-                                        result.push(current_token);
+                                        result.push(current_token, index);
                                         // Skip the next token since we already processed it
                                         i += 2;
                                         continue;
@@ -207,22 +501,27 @@ impl TraitBasedConverter {
                                 }
                             }
 
-                            result.push(current_token);
+                            result.push(current_token, index);
                         } else if let AlphabetToken::Unknown(s) = alphabet_token {
-                            result.push(HubToken::Abugida(AbugidaToken::Unknown(s.clone())));
+                            result.push(HubToken::Abugida(AbugidaToken::Unknown(s.clone())), index);
+                        } else {
+                            result.unconverted.push(index);
                         }
                     } else if let AlphabetToken::Unknown(s) = alphabet_token {
-                        result.push(HubToken::Abugida(AbugidaToken::Unknown(s.clone())));
+                        result.push(HubToken::Abugida(AbugidaToken::Unknown(s.clone())), index);
                     } else {
                         // Other tokens - try direct mapping
-                        if let Some(abugida_token) = alphabet_token.to_abugida() {
-                            result.push(HubToken::Abugida(abugida_token));
+                        match alphabet_token.to_abugida() {
+                            Some(abugida_token) => {
+                                result.push(HubToken::Abugida(abugida_token), index)
+                            }
+                            None => result.unconverted.push(index),
                         }
                     }
                 }
                 HubToken::Abugida(_) => {
                     // Already abugida - pass through
-                    result.push(tokens[i].clone());
+                    result.push(tokens[i].clone(), index);
                 }
             }
             i += 1;
@@ -231,3 +530,64 @@ impl TraitBasedConverter {
         Ok(result)
     }
 }
+
+/// Nukta letters a consonant written with a separate nukta may be read as
+///
+/// Roman schemes write only some of them (IAST has q, z and f). A consonant +
+/// nukta is read as a letter of the set; otherwise the nukta, which has no
+/// Roman letter, is dropped and the base consonant written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NuktaLetters(u8);
+
+impl NuktaLetters {
+    /// No nukta letters, so every separate nukta is dropped
+    pub const NONE: Self = Self(0);
+
+    /// The nukta letters whose alphabet token `writes` accepts
+    pub fn written_by(writes: impl Fn(&HubToken) -> bool) -> Self {
+        let mut bits = 0;
+        for (bit, letter) in NUKTA_LETTERS.iter().enumerate() {
+            if letter
+                .to_alphabet()
+                .is_some_and(|token| writes(&HubToken::Alphabet(token)))
+            {
+                bits |= 1 << bit;
+            }
+        }
+        Self(bits)
+    }
+
+    /// The letter of the set `consonant` + nukta stands for
+    fn compose(self, consonant: &AbugidaToken) -> Option<AbugidaToken> {
+        let letter = nukta_form(consonant)?;
+        let bit = NUKTA_LETTERS.iter().position(|known| *known == letter)?;
+        (self.0 & 1 << bit != 0).then_some(letter)
+    }
+}
+
+/// Every letter [`nukta_form`] gives
+const NUKTA_LETTERS: [AbugidaToken; 8] = [
+    AbugidaToken::ConsonantQa,
+    AbugidaToken::ConsonantKha,
+    AbugidaToken::ConsonantGha,
+    AbugidaToken::ConsonantZa,
+    AbugidaToken::ConsonantFa,
+    AbugidaToken::ConsonantRra,
+    AbugidaToken::ConsonantRrha,
+    AbugidaToken::ConsonantYa,
+];
+
+/// The consonant a base consonant plus nukta stands for (क + ़ = क़)
+pub(crate) fn nukta_form(consonant: &AbugidaToken) -> Option<AbugidaToken> {
+    match consonant {
+        AbugidaToken::ConsonantK => Some(AbugidaToken::ConsonantQa),
+        AbugidaToken::ConsonantKh => Some(AbugidaToken::ConsonantKha),
+        AbugidaToken::ConsonantG => Some(AbugidaToken::ConsonantGha),
+        AbugidaToken::ConsonantJ => Some(AbugidaToken::ConsonantZa),
+        AbugidaToken::ConsonantPh => Some(AbugidaToken::ConsonantFa),
+        AbugidaToken::ConsonantD => Some(AbugidaToken::ConsonantRra),
+        AbugidaToken::ConsonantDh => Some(AbugidaToken::ConsonantRrha),
+        AbugidaToken::ConsonantY => Some(AbugidaToken::ConsonantYa),
+        _ => None,
+    }
+}
@@ -0,0 +1,191 @@
+//! Hyphens between the members of compounds, as editions mark them
+//!
+//! Indological editions mark the members of compounds with hyphens, as in
+//! tat-tvam-asi, and conversion reads a hyphen as the end of a word: IAST
+//! "tat-tvam-asi" became तत्-त्वम्-असि, with a virama before each hyphen and an
+//! independent vowel after one. With `TransliterationOptions::hyphenation`, a
+//! marker between two letters of a word is taken out before conversion, so the
+//! word is read whole, and is then written back or left out. A marker inside
+//! what the source then reads as one letter, as between the a and i of
+//! rāma-iti, is left in the text, since there it marks a hiatus.
+//!
+//! A marker is written back where the output of the members before it stops
+//! agreeing with that of the whole word. In an Indic script two members can
+//! share an akshara, and the marker then stands inside it (तत्-त्वम-सि).
+
+/// Characters read as markers by default: the hyphen-minus and U+2010 HYPHEN
+pub const DEFAULT_MARKERS: &[char] = &['-', '\u{2010}'];
+
+/// How markers between the members of compounds are read and written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hyphenation {
+    /// Characters that mark a boundary when they stand between two letters of a word
+    pub markers: Vec<char>,
+    /// Whether the output keeps the markers
+    pub output: MarkerOutput,
+}
+
+impl Default for Hyphenation {
+    fn default() -> Self {
+        Self {
+            markers: DEFAULT_MARKERS.to_vec(),
+            output: MarkerOutput::default(),
+        }
+    }
+}
+
+/// What happens to markers in the output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarkerOutput {
+    /// Written back between the members, as in tat-tvam-asi
+    #[default]
+    Keep,
+    /// Left out, as in tattvamasi
+    Strip,
+}
+
+/// Text with the markers between members taken out, and where they stood
+#[derive(Debug, Clone, PartialEq)]
+pub struct Joined {
+    pub text: String,
+    pub joints: Vec<Joint>,
+}
+
+/// A marker taken out of a word
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Joint {
+    /// Byte offset in the joined text of the member after the marker
+    pub position: usize,
+    pub marker: char,
+}
+
+impl Joined {
+    /// Byte offset in the original text of `position` in the joined text
+    pub fn original_position(&self, position: usize) -> usize {
+        position
+            + self
+                .joints
+                .iter()
+                .take_while(|joint| joint.position <= position)
+                .map(|joint| joint.marker.len_utf8())
+                .sum::<usize>()
+    }
+
+    /// `self` with the markers for which `keep` holds put back into the text
+    ///
+    /// `keep` is given the position of each joint in the joined text.
+    pub fn restore(self, mut keep: impl FnMut(usize) -> bool) -> Joined {
+        let mut restored = Joined {
+            text: String::with_capacity(self.text.len() + self.joints.len()),
+            joints: Vec::with_capacity(self.joints.len()),
+        };
+        let mut last = 0;
+        for joint in self.joints {
+            restored.text.push_str(&self.text[last..joint.position]);
+            last = joint.position;
+            if keep(joint.position) {
+                restored.text.push(joint.marker);
+            } else {
+                restored.joints.push(Joint {
+                    position: restored.text.len(),
+                    marker: joint.marker,
+                });
+            }
+        }
+        restored.text.push_str(&self.text[last..]);
+        restored
+    }
+}
+
+/// `text` with the `markers` that stand between two letters of a word taken out
+///
+/// A marker at the start or end of a word, or next to another marker, is left
+/// as it is, so a dash between words or a line-end hyphen is still converted.
+pub fn join_members(text: &str, markers: &[char]) -> Joined {
+    let is_letter =
+        |c: Option<char>| c.is_some_and(|c| !c.is_whitespace() && !markers.contains(&c));
+    let mut joined = Joined {
+        text: String::with_capacity(text.len()),
+        joints: Vec::new(),
+    };
+    let mut previous = None;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if markers.contains(&c) && is_letter(previous) && is_letter(chars.peek().copied()) {
+            joined.joints.push(Joint {
+                position: joined.text.len(),
+                marker: c,
+            });
+        } else {
+            joined.text.push(c);
+        }
+        previous = Some(c);
+    }
+    joined
+}
+
+/// `output`, the conversion of `joined.text`, with its markers written back
+///
+/// `convert` converts the members of a word before a marker as the whole text
+/// was converted. Conversions keep whitespace, so the nth word of the output
+/// is the conversion of the nth word of the input.
+pub fn rehyphenate<E>(
+    joined: &Joined,
+    output: &str,
+    mut convert: impl FnMut(&str) -> Result<String, E>,
+) -> Result<String, E> {
+    if joined.joints.is_empty() {
+        return Ok(output.to_string());
+    }
+    let input_words = word_spans(&joined.text);
+    let output_words = word_spans(output);
+
+    let mut insertions = Vec::with_capacity(joined.joints.len());
+    for joint in &joined.joints {
+        let Some(index) = input_words
+            .iter()
+            .position(|&(start, end)| start < joint.position && joint.position < end)
+        else {
+            continue;
+        };
+        let Some(&(start, end)) = output_words.get(index) else {
+            continue;
+        };
+        let members = convert(&joined.text[input_words[index].0..joint.position])?;
+        let agreed: usize = members
+            .chars()
+            .zip(output[start..end].chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(c, _)| c.len_utf8())
+            .sum();
+        insertions.push((start + agreed, joint.marker));
+    }
+    insertions.sort_by_key(|&(position, _)| position);
+
+    let mut hyphenated = String::with_capacity(output.len() + insertions.len());
+    let mut last = 0;
+    for (position, marker) in insertions {
+        hyphenated.push_str(&output[last..position]);
+        hyphenated.push(marker);
+        last = position;
+    }
+    hyphenated.push_str(&output[last..]);
+    Ok(hyphenated)
+}
+
+/// Byte ranges of the whitespace-delimited words of `text`
+fn word_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (position, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (c.is_whitespace(), start) {
+            (true, Some(word_start)) => {
+                words.push((word_start, position));
+                start = None;
+            }
+            (false, None) => start = Some(position),
+            _ => {}
+        }
+    }
+    words
+}
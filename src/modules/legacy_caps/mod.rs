@@ -0,0 +1,124 @@
+//! Reading ITRANS written in ALL CAPS
+//!
+//! ITRANS is case-significant ("A" is ā, "T" is ṭ), but archive files often
+//! write it entirely in capitals, so "DHARMAH" stands for dharmaH. A word with
+//! no lowercase letters is lowercased, apart from the capitals Sanskrit
+//! phonotactics recover: a word-final H after a vowel is the visarga, an R
+//! between consonants (or at a word edge) is vocalic, and an M before a
+//! sibilant or h is the anusvara. KSH is read as kṣ and JN as jñ, since kś and
+//! jn do not occur. The retroflexes, ś/ṣ and the long vowels written "A"/"I"/"U"
+//! stay ambiguous and are read as their lowercase letters.
+
+use crate::modules::core::unknown_handler::RecasedWord;
+
+/// Text with its ALL CAPS words re-cased, and the words that were
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recased {
+    pub text: String,
+    pub words: Vec<RecasedWord>,
+}
+
+impl Recased {
+    /// Byte offset in the original text of `position` in the re-cased text
+    ///
+    /// A position within a re-cased word whose length changed maps to the
+    /// corresponding offset in the original word, clamped to its last byte.
+    pub fn original_position(&self, position: usize) -> usize {
+        let mut shift = 0isize;
+        for word in &self.words {
+            let start = word.position.saturating_add_signed(shift);
+            if position < start {
+                break;
+            }
+            if position < start + word.read_as.len() {
+                return word.position + (position - start).min(word.word.len() - 1);
+            }
+            shift += word.read_as.len() as isize - word.word.len() as isize;
+        }
+        position.saturating_add_signed(-shift)
+    }
+}
+
+/// Re-case the ALL CAPS words of ITRANS `text`
+///
+/// Words are delimited by whitespace. When the whole text has no lowercase
+/// letters every word is re-cased; otherwise only words of two or more letters,
+/// so that a properly-cased "A" (ā) is left alone.
+pub fn recase_legacy_itrans(text: &str) -> Recased {
+    let all_caps = !text.bytes().any(|b| b.is_ascii_lowercase());
+    let mut recased = Recased {
+        text: String::with_capacity(text.len() + text.len() / 8),
+        words: Vec::new(),
+    };
+
+    let mut last = 0;
+    for (position, word) in words(text) {
+        let letters = word.bytes().filter(u8::is_ascii_alphabetic).count();
+        let caps = letters > 0 && !word.bytes().any(|b| b.is_ascii_lowercase());
+        if !caps || (letters < 2 && !all_caps) {
+            continue;
+        }
+        recased.text.push_str(&text[last..position]);
+        let read_as = recase_word(word);
+        recased.text.push_str(&read_as);
+        recased.words.push(RecasedWord {
+            word: word.to_string(),
+            read_as,
+            position,
+        });
+        last = position + word.len();
+    }
+    recased.text.push_str(&text[last..]);
+
+    recased
+}
+
+/// Whitespace-delimited words of `text` with their byte offsets
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split(char::is_whitespace)
+        .filter(|word| !word.is_empty())
+        .map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+}
+
+/// The properly-cased ITRANS an ALL CAPS word stands for
+fn recase_word(word: &str) -> String {
+    let bytes = word.as_bytes();
+    let letter = |i: usize| bytes.get(i).copied().filter(u8::is_ascii_alphabetic);
+    let is_vowel = |b: u8| matches!(b, b'A' | b'E' | b'I' | b'O' | b'U');
+
+    let mut read_as = String::with_capacity(word.len() + 1);
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &word[i..];
+        if rest.starts_with("KSH") {
+            read_as.push_str("kSh");
+            i += 3;
+            continue;
+        }
+        if rest.starts_with("JN") {
+            read_as.push_str("j~n");
+            i += 2;
+            continue;
+        }
+
+        let Some(ch) = rest.chars().next() else {
+            break;
+        };
+        let previous = i.checked_sub(1).and_then(letter);
+        let next = letter(i + 1);
+        let keep_capital = match ch {
+            'H' => next.is_none() && previous.is_some_and(is_vowel),
+            'R' => !previous.is_some_and(is_vowel) && !next.is_some_and(is_vowel),
+            'M' => matches!(next, Some(b'S' | b'H')),
+            _ => false,
+        };
+        if keep_capital {
+            read_as.push(ch);
+        } else {
+            read_as.push(ch.to_ascii_lowercase());
+        }
+        i += ch.len_utf8();
+    }
+
+    read_as
+}
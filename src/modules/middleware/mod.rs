@@ -0,0 +1,154 @@
+//! Hooks run before and after every conversion
+//!
+//! A preprocessor rewrites the input and may mark spans of what it returns as
+//! protected: protected spans bypass conversion and are written to the output
+//! as they are, between the converted text around them, however much longer or
+//! shorter that text becomes. Preprocessors run in the order they were added,
+//! each on the text the one before left unprotected, so a span once protected
+//! is never seen by a later hook. Postprocessors then run, in order, on the
+//! whole output.
+
+use std::ops::Range;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MiddlewareError {
+    #[error(
+        "Protected span {start}..{end} is not a span of the {len}-byte preprocessed text on character boundaries"
+    )]
+    SpanOutOfBounds {
+        start: usize,
+        end: usize,
+        len: usize,
+    },
+    #[error(
+        "Protected spans must be in order and not overlap; {start}..{end} overlaps an earlier one"
+    )]
+    SpansOverlap { start: usize, end: usize },
+}
+
+/// Rewrites the input, marking spans that bypass conversion
+pub type Preprocessor = Box<dyn Fn(&str) -> PreprocessOutput + Send + Sync>;
+
+/// Rewrites the output
+pub type Postprocessor = Box<dyn Fn(&str) -> String + Send + Sync>;
+
+/// The text a preprocessor returns, with the spans of it to write unconverted
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PreprocessOutput {
+    pub text: String,
+    /// Byte ranges of `text`, in order and not overlapping
+    pub protected: Vec<Range<usize>>,
+}
+
+impl PreprocessOutput {
+    /// `text` to be converted whole
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            protected: Vec::new(),
+        }
+    }
+
+    /// Also protect `range` of the text
+    pub fn protect(mut self, range: Range<usize>) -> Self {
+        self.protected.push(range);
+        self
+    }
+
+    /// The text cut into pieces to convert (`false`) and protected pieces (`true`)
+    pub(crate) fn pieces(&self) -> Vec<(Range<usize>, bool)> {
+        let mut pieces = Vec::with_capacity(self.protected.len() * 2 + 1);
+        let mut last = 0;
+        for range in &self.protected {
+            if last < range.start {
+                pieces.push((last..range.start, false));
+            }
+            pieces.push((range.clone(), true));
+            last = range.end;
+        }
+        if last < self.text.len() || self.text.is_empty() {
+            pieces.push((last..self.text.len(), false));
+        }
+        pieces
+    }
+
+    /// Check the protected spans, dropping empty ones
+    fn validated(mut self) -> Result<Self, MiddlewareError> {
+        self.protected.retain(|range| !range.is_empty());
+        let mut last = 0;
+        for range in &self.protected {
+            let Range { start, end } = range.clone();
+            if start > end
+                || end > self.text.len()
+                || !self.text.is_char_boundary(start)
+                || !self.text.is_char_boundary(end)
+            {
+                return Err(MiddlewareError::SpanOutOfBounds {
+                    start,
+                    end,
+                    len: self.text.len(),
+                });
+            }
+            if start < last {
+                return Err(MiddlewareError::SpansOverlap { start, end });
+            }
+            last = end;
+        }
+        Ok(self)
+    }
+}
+
+/// Run `preprocessors` in order over `text`, each on the pieces left unprotected
+pub(crate) fn preprocess(
+    preprocessors: &[Preprocessor],
+    text: &str,
+) -> Result<PreprocessOutput, MiddlewareError> {
+    let mut current = PreprocessOutput::new(text);
+    for preprocessor in preprocessors {
+        let mut next = PreprocessOutput::new(String::with_capacity(current.text.len()));
+        for (range, protected) in current.pieces() {
+            let start = next.text.len();
+            if protected {
+                next.text.push_str(&current.text[range]);
+                next.protected.push(start..next.text.len());
+                continue;
+            }
+            let piece = preprocessor(&current.text[range]).validated()?;
+            next.text.push_str(&piece.text);
+            next.protected.extend(
+                piece
+                    .protected
+                    .into_iter()
+                    .map(|range| range.start + start..range.end + start),
+            );
+        }
+        current = next;
+    }
+    Ok(current)
+}
+
+/// Run `postprocessors` in order over `output`
+pub(crate) fn postprocess(postprocessors: &[Postprocessor], output: String) -> String {
+    postprocessors
+        .iter()
+        .fold(output, |output, postprocessor| postprocessor(&output))
+}
+
+/// A preprocessor protecting `{braced}` spans, braces included
+///
+/// Editorial notes and words in other languages can be written in braces to
+/// keep them out of conversion: "{Note:} dharma" converts only "dharma". An
+/// unclosed brace protects nothing.
+pub fn protect_braced(text: &str) -> PreprocessOutput {
+    let mut output = PreprocessOutput::new(text);
+    let mut from = 0;
+    while let Some(open) = text[from..].find('{').map(|at| from + at) {
+        let Some(close) = text[open..].find('}').map(|at| open + at) else {
+            break;
+        };
+        output.protected.push(open..close + 1);
+        from = close + 1;
+    }
+    output
+}
@@ -0,0 +1,191 @@
+//! Indic input with text of another script in it
+//!
+//! Text pasted together from two sources can be half Devanagari and half
+//! Bengali. Converted as Devanagari, the Bengali is passed through, and the
+//! output looks converted but is not. Each character is classified by the
+//! Unicode block it is in; when enough of the non-ASCII input is in the block
+//! of another Indic script than the declared one, the conversion's metadata
+//! carries a `ConversionWarning::MixedScriptInput`. With
+//! `MixedScriptPolicy::convert`, runs of that script are first respelled in
+//! the declared script, so they convert with the rest.
+
+use std::ops::{Range, RangeInclusive};
+
+/// Share of the non-ASCII input in another script that is reported by default
+pub const DEFAULT_MIXED_SCRIPT_THRESHOLD: f64 = 0.2;
+
+/// The Unicode blocks of the Indic scripts, by the primary names of the scripts
+///
+/// The dandas, which every script of India uses, are left out of Devanagari.
+pub const INDIC_BLOCKS: &[(RangeInclusive<char>, &str)] = &[
+    ('\u{0600}'..='\u{06FF}', "urdu"),
+    ('\u{0900}'..='\u{0963}', "devanagari"),
+    ('\u{0966}'..='\u{097F}', "devanagari"),
+    ('\u{0980}'..='\u{09FF}', "bengali"),
+    ('\u{0A00}'..='\u{0A7F}', "gurmukhi"),
+    ('\u{0A80}'..='\u{0AFF}', "gujarati"),
+    ('\u{0B00}'..='\u{0B7F}', "odia"),
+    ('\u{0B80}'..='\u{0BFF}', "tamil"),
+    ('\u{0C00}'..='\u{0C7F}', "telugu"),
+    ('\u{0C80}'..='\u{0CFF}', "kannada"),
+    ('\u{0D00}'..='\u{0D7F}', "malayalam"),
+    ('\u{0D80}'..='\u{0DFF}', "sinhala"),
+    ('\u{0E00}'..='\u{0E7F}', "thai"),
+    ('\u{0F00}'..='\u{0FFF}', "tibetan"),
+    ('\u{1780}'..='\u{17FF}', "khmer"),
+    ('\u{1A20}'..='\u{1AAF}', "tai_tham"),
+    ('\u{1B00}'..='\u{1B7F}', "balinese"),
+    ('\u{A8E0}'..='\u{A8FF}', "devanagari"),
+    ('\u{10A00}'..='\u{10A5F}', "kharoshthi"),
+    ('\u{11000}'..='\u{1107F}', "brahmi"),
+    ('\u{11080}'..='\u{110CF}', "kaithi"),
+    ('\u{11100}'..='\u{1114F}', "chakma"),
+    ('\u{11180}'..='\u{111DF}', "sharada"),
+    ('\u{11300}'..='\u{1137F}', "grantha"),
+    ('\u{11400}'..='\u{1147F}', "newa"),
+    ('\u{11580}'..='\u{115FF}', "siddham"),
+    ('\u{11600}'..='\u{1165F}', "modi"),
+    ('\u{11680}'..='\u{116CF}', "takri"),
+    ('\u{11800}'..='\u{1184F}', "dogra"),
+    ('\u{119A0}'..='\u{119FF}', "nandinagari"),
+    ('\u{11C00}'..='\u{11C6F}', "bhaiksuki"),
+];
+
+/// The Indic script whose block `c` is in
+pub fn block_script(c: char) -> Option<&'static str> {
+    INDIC_BLOCKS
+        .iter()
+        .find(|(block, _)| block.contains(&c))
+        .map(|&(_, script)| script)
+}
+
+/// How conversions from an Indic script treat input in other Indic scripts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MixedScriptPolicy {
+    /// Share of the non-ASCII input, from 0.0 to 1.0, that must be in another
+    /// script for it to be reported
+    pub threshold: f64,
+    /// Convert the runs of each script reported as that script, not the declared one
+    pub convert: bool,
+}
+
+impl Default for MixedScriptPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_MIXED_SCRIPT_THRESHOLD,
+            convert: false,
+        }
+    }
+}
+
+/// The scripts other than `declared` that at least `threshold` of the
+/// non-ASCII characters of `text` are in, with their shares, the largest first
+pub fn other_scripts(text: &str, declared: &str, threshold: f64) -> Vec<(&'static str, f64)> {
+    let mut counts: Vec<(&'static str, usize)> = Vec::new();
+    let mut non_ascii = 0;
+    for c in text.chars().filter(|c| !c.is_ascii()) {
+        non_ascii += 1;
+        match block_script(c) {
+            Some(script) if script != declared => {
+                match counts.iter_mut().find(|(counted, _)| *counted == script) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((script, 1)),
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut scripts: Vec<(&'static str, f64)> = counts
+        .into_iter()
+        .map(|(script, count)| (script, count as f64 / non_ascii as f64))
+        .filter(|&(_, fraction)| fraction >= threshold)
+        .collect();
+    scripts.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scripts
+}
+
+/// Byte ranges of the runs of `text` in `script`'s blocks
+///
+/// A joiner (ZWJ or ZWNJ) between two characters of a run is part of it.
+pub fn runs(text: &str, script: &str) -> Vec<Range<usize>> {
+    let is_joiner = |c: char| matches!(c, '\u{200C}' | '\u{200D}');
+    let mut runs: Vec<Range<usize>> = Vec::new();
+    let mut open: Option<Range<usize>> = None;
+    for (at, c) in text.char_indices() {
+        let end = at + c.len_utf8();
+        if block_script(c) == Some(script) {
+            match &mut open {
+                Some(run) => run.end = end,
+                None => open = Some(at..end),
+            }
+        } else if !(is_joiner(c) && open.is_some()) {
+            runs.extend(open.take());
+        }
+    }
+    runs.extend(open);
+    runs
+}
+
+/// A run of the input written again in the declared script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RespelledRun {
+    /// Byte range of the run in the input
+    pub input: Range<usize>,
+    /// Bytes the run takes in the respelled text
+    pub respelled_len: usize,
+}
+
+/// Input with runs of other scripts respelled in the declared one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Respelled {
+    pub text: String,
+    /// The runs respelled, in input order
+    pub runs: Vec<RespelledRun>,
+}
+
+impl Respelled {
+    /// `text` with each of `runs`, which must be in order and not overlap,
+    /// replaced by what `respell` writes for it and the script it is in
+    pub fn new<E>(
+        text: &str,
+        runs: &[(Range<usize>, &str)],
+        mut respell: impl FnMut(&str, &str) -> Result<String, E>,
+    ) -> Result<Self, E> {
+        let mut respelled = Self {
+            text: String::with_capacity(text.len()),
+            runs: Vec::with_capacity(runs.len()),
+        };
+        let mut copied = 0;
+        for (run, script) in runs {
+            respelled.text.push_str(&text[copied..run.start]);
+            let written = respell(&text[run.clone()], script)?;
+            respelled.text.push_str(&written);
+            respelled.runs.push(RespelledRun {
+                input: run.clone(),
+                respelled_len: written.len(),
+            });
+            copied = run.end;
+        }
+        respelled.text.push_str(&text[copied..]);
+        Ok(respelled)
+    }
+
+    /// The input position of `position` in the respelled text
+    ///
+    /// A position within a run whose length changed maps to the corresponding
+    /// offset in the input run, clamped to its last byte.
+    pub fn original_position(&self, position: usize) -> usize {
+        let mut shift = 0isize;
+        for run in &self.runs {
+            let start = run.input.start.saturating_add_signed(shift);
+            if position < start {
+                break;
+            }
+            if position < start + run.respelled_len {
+                return run.input.start + (position - start).min(run.input.len() - 1);
+            }
+            shift += run.respelled_len as isize - run.input.len() as isize;
+        }
+        position.saturating_add_signed(-shift)
+    }
+}
@@ -1,12 +1,42 @@
+pub mod akshara;
+pub mod builder;
+pub mod capability;
+pub mod capitalize;
+pub mod compare;
+pub mod completion;
+// Conformance runner reads case files from disk
+#[cfg(not(target_arch = "wasm32"))]
+pub mod conformance;
 pub mod core;
 pub mod hub;
+pub mod hyphenation;
+pub mod legacy_caps;
+pub mod middleware;
+pub mod mixed_script;
+pub mod normalize;
+pub mod numerals;
+pub mod options;
+pub mod orthography;
+pub mod overrides;
+// Splitting large inputs for the rayon-backed conversion path
+#[cfg(feature = "parallel")]
+pub mod parallel;
 // Profiler uses std::time which is not available in WASM
 #[cfg(not(target_arch = "wasm32"))]
 pub mod profiler;
+pub mod quality;
 pub mod registry;
+pub mod repair;
+pub mod result_cache;
+pub mod rules;
 pub mod runtime;
 pub mod schema;
 pub mod script_converter;
+pub mod selective;
+pub mod stats;
+// Spans around the conversion stages, compiled in with the `tracing` feature
+pub(crate) mod trace;
+pub mod transliterator;
 
 // Re-export module todo queue types for cross-module communication
 pub use self::core::{ModuleTodoQueue, TodoItem, TodoPriority, TodoResponse};
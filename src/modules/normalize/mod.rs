@@ -0,0 +1,262 @@
+//! Canonical spellings for `Shlesha::normalize`
+//!
+//! Most of normalization happens on hub tokens: text is read into tokens and
+//! written back by the same script's converter, so every alternative input
+//! spelling comes out as the schema's preferred one. The passes here cover what
+//! tokens do not capture: character sequences Unicode asks not to be used, and
+//! the spacing around dandas. Roman input is also read in NFC, whatever form it
+//! was typed in, with [`compose`].
+
+use crate::modules::core::unknown_handler::{TransliterationMetadata, UnknownStage};
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// Devanagari sequences Unicode lists as "do not use", with the letter to write instead
+///
+/// From table 12-1 of the Unicode Standard, plus the nukta letters that have
+/// precomposed forms but no hub token.
+const DEVANAGARI_SEQUENCES: &[(&str, &str)] = &[
+    ("\u{0905}\u{0946}", "\u{0904}"), // अ + ॆ → ऄ
+    ("\u{0905}\u{093E}", "\u{0906}"), // अ + ा → आ
+    ("\u{0905}\u{0949}", "\u{0911}"), // अ + ॉ → ऑ
+    ("\u{0906}\u{0945}", "\u{0911}"), // आ + ॅ → ऑ
+    ("\u{0905}\u{094A}", "\u{0912}"), // अ + ॊ → ऒ
+    ("\u{0906}\u{0946}", "\u{0912}"), // आ + ॆ → ऒ
+    ("\u{0905}\u{094B}", "\u{0913}"), // अ + ो → ओ
+    ("\u{0906}\u{0947}", "\u{0913}"), // आ + े → ओ
+    ("\u{0905}\u{094C}", "\u{0914}"), // अ + ौ → औ
+    ("\u{0906}\u{0948}", "\u{0914}"), // आ + ै → औ
+    ("\u{090F}\u{0945}", "\u{090D}"), // ए + ॅ → ऍ
+    ("\u{090F}\u{0946}", "\u{090E}"), // ए + ॆ → ऎ
+    ("\u{090F}\u{0947}", "\u{0910}"), // ए + े → ऐ
+    ("\u{0905}\u{0945}", "\u{0972}"), // अ + ॅ → ॲ
+    ("\u{0905}\u{093A}", "\u{0973}"), // अ + ऺ → ॳ
+    ("\u{0905}\u{093B}", "\u{0974}"), // अ + ऻ → ॴ
+    ("\u{0905}\u{094F}", "\u{0975}"), // अ + ॏ → ॵ
+    ("\u{0905}\u{0956}", "\u{0976}"), // अ + ॖ → ॶ
+    ("\u{0905}\u{0957}", "\u{0977}"), // अ + ॗ → ॷ
+    ("\u{0928}\u{093C}", "\u{0929}"), // न + ़ → ऩ
+    ("\u{0930}\u{093C}", "\u{0931}"), // र + ़ → ऱ
+    ("\u{0933}\u{093C}", "\u{0934}"), // ळ + ़ → ऴ
+];
+
+/// Replace discouraged Devanagari sequences with the letters Unicode recommends
+pub fn replace_devanagari_sequences(text: &str) -> Cow<'_, str> {
+    let mut text = Cow::Borrowed(text);
+    for (sequence, letter) in DEVANAGARI_SEQUENCES {
+        if text.contains(sequence) {
+            text = Cow::Owned(text.replace(sequence, letter));
+        }
+    }
+    text
+}
+
+/// Put one space before each danda and after it when a word follows
+///
+/// A danda after a digit (॥१॥) or another danda stays attached, and two single
+/// dandas in a row are written as a double danda.
+pub fn normalize_danda_spacing(text: &str) -> String {
+    let mut output = String::with_capacity(text.len() + text.len() / 8);
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if !is_danda(c) {
+            output.push(c);
+            continue;
+        }
+
+        let danda = if c == '\u{0964}' && chars.peek() == Some(&'\u{0964}') {
+            chars.next();
+            '\u{0965}'
+        } else {
+            c
+        };
+
+        let trimmed = output.trim_end_matches([' ', '\t']).len();
+        output.truncate(trimmed);
+        if output
+            .chars()
+            .last()
+            .is_some_and(|last| last != '\n' && !last.is_numeric() && !is_danda(last))
+        {
+            output.push(' ');
+        }
+        output.push(danda);
+
+        if chars.peek().is_some_and(|next| next.is_alphabetic()) {
+            output.push(' ');
+        }
+    }
+    output
+}
+
+fn is_danda(c: char) -> bool {
+    matches!(c, '\u{0964}' | '\u{0965}')
+}
+
+/// How Indic output writes letters that have a canonical decomposition
+///
+/// Unicode excludes the nukta letters (क़, ড়, ਸ਼, ଡ଼ and others) from
+/// composition, so NFC writes them as consonant + nukta. Output precomposes
+/// them by default, so the same conversion gives the same bytes on every path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnicodeForm {
+    /// Nukta letters precomposed (ज़ is U+095B), everything else as written
+    #[default]
+    Precomposed,
+    /// Unicode NFC (ज़ is ज + ़)
+    Nfc,
+    /// Unicode NFD (ज़ is ज + ़)
+    Nfd,
+}
+
+/// The nukta letters Unicode excludes from composition, with their decompositions
+const NUKTA_LETTERS: &[(char, char, char)] = &[
+    ('\u{0958}', '\u{0915}', '\u{093C}'), // क़
+    ('\u{0959}', '\u{0916}', '\u{093C}'), // ख़
+    ('\u{095A}', '\u{0917}', '\u{093C}'), // ग़
+    ('\u{095B}', '\u{091C}', '\u{093C}'), // ज़
+    ('\u{095C}', '\u{0921}', '\u{093C}'), // ड़
+    ('\u{095D}', '\u{0922}', '\u{093C}'), // ढ़
+    ('\u{095E}', '\u{092B}', '\u{093C}'), // फ़
+    ('\u{095F}', '\u{092F}', '\u{093C}'), // य़
+    ('\u{09DC}', '\u{09A1}', '\u{09BC}'), // ড়
+    ('\u{09DD}', '\u{09A2}', '\u{09BC}'), // ঢ়
+    ('\u{09DF}', '\u{09AF}', '\u{09BC}'), // য়
+    ('\u{0A33}', '\u{0A32}', '\u{0A3C}'), // ਲ਼
+    ('\u{0A36}', '\u{0A38}', '\u{0A3C}'), // ਸ਼
+    ('\u{0A59}', '\u{0A16}', '\u{0A3C}'), // ਖ਼
+    ('\u{0A5A}', '\u{0A17}', '\u{0A3C}'), // ਗ਼
+    ('\u{0A5B}', '\u{0A1C}', '\u{0A3C}'), // ਜ਼
+    ('\u{0A5E}', '\u{0A2B}', '\u{0A3C}'), // ਫ਼
+    ('\u{0B5C}', '\u{0B21}', '\u{0B3C}'), // ଡ଼
+    ('\u{0B5D}', '\u{0B22}', '\u{0B3C}'), // ଢ଼
+];
+
+/// Write `text` in `form`
+pub fn to_unicode_form(text: &str, form: UnicodeForm) -> Cow<'_, str> {
+    use unicode_normalization::{is_nfc_quick, is_nfd_quick, IsNormalized, UnicodeNormalization};
+
+    match form {
+        UnicodeForm::Precomposed => compose_nukta_letters(text),
+        UnicodeForm::Nfc => match is_nfc_quick(text.chars()) {
+            IsNormalized::Yes => Cow::Borrowed(text),
+            _ => Cow::Owned(text.nfc().collect()),
+        },
+        UnicodeForm::Nfd => match is_nfd_quick(text.chars()) {
+            IsNormalized::Yes => Cow::Borrowed(text),
+            _ => Cow::Owned(text.nfd().collect()),
+        },
+    }
+}
+
+/// Write each consonant + nukta that has a precomposed letter as that letter
+fn compose_nukta_letters(text: &str) -> Cow<'_, str> {
+    let is_nukta = |c: char| NUKTA_LETTERS.iter().any(|&(_, _, nukta)| nukta == c);
+    if !text.contains(is_nukta) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let letter = chars.peek().and_then(|&next| {
+            NUKTA_LETTERS
+                .iter()
+                .find(|&&(_, base, nukta)| base == c && nukta == next)
+        });
+        match letter {
+            Some(&(letter, _, _)) => {
+                output.push(letter);
+                chars.next();
+            }
+            None => output.push(c),
+        }
+    }
+    Cow::Owned(output)
+}
+
+/// Text in Unicode NFC, with the offset in the text as written of each of its bytes
+///
+/// Characters that compose are one piece, and every byte of a piece NFC
+/// changes maps to the piece's start. A character NFC would replace on its
+/// own, such as a CJK compatibility ideograph, is kept as written: only
+/// sequences are composed, so text no schema reads passes through unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Composed {
+    pub text: String,
+    offsets: Vec<usize>,
+}
+
+impl Composed {
+    /// Byte offset in the text as written of `position` in the composed text
+    pub fn original_position(&self, position: usize) -> usize {
+        self.offsets[position.min(self.text.len())]
+    }
+
+    /// Move the input positions of `metadata`, collected on the composed text, to the text as written
+    pub fn restore_positions(&self, metadata: &mut TransliterationMetadata) {
+        for token in &mut metadata.unknown_tokens {
+            if token.stage == UnknownStage::SourceParsing {
+                token.position = self.original_position(token.position);
+            }
+        }
+        for span in &mut metadata.fixed_spans {
+            span.position = self.original_position(span.position);
+        }
+        for span in &mut metadata.normalized_spans {
+            span.position = self.original_position(span.position);
+        }
+        for span in &mut metadata.converted_spans {
+            *span = self.original_position(span.start)..self.original_position(span.end);
+        }
+    }
+}
+
+/// `text` in NFC with the offsets it was composed from, `None` if it already is NFC
+///
+/// macOS and some keyboards produce NFD, ā as a + combining macron, while
+/// schemas spell their letters in NFC; reading composed text matches both.
+pub fn compose(text: &str) -> Option<Composed> {
+    use unicode_normalization::char::{canonical_combining_class, compose};
+    use unicode_normalization::{is_nfc, is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+    if is_nfc_quick(text.chars()) == IsNormalized::Yes {
+        return None;
+    }
+    let mut composed = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    let mut push_piece = |range: Range<usize>| {
+        let piece = &text[range.clone()];
+        if piece.chars().nth(1).is_none() || is_nfc(piece) {
+            composed.push_str(piece);
+            offsets.extend(range);
+        } else {
+            for c in piece.nfc() {
+                offsets.extend(std::iter::repeat_n(range.start, c.len_utf8()));
+                composed.push(c);
+            }
+        }
+    };
+
+    let mut piece_start = 0;
+    let mut previous = None;
+    for (position, c) in text.char_indices() {
+        let starts_piece = canonical_combining_class(c) == 0
+            && previous.is_none_or(|previous| compose(previous, c).is_none());
+        if starts_piece && position > piece_start {
+            push_piece(piece_start..position);
+            piece_start = position;
+        }
+        previous = Some(c);
+    }
+    push_piece(piece_start..text.len());
+    offsets.push(text.len());
+    if composed == text {
+        return None;
+    }
+    Some(Composed {
+        text: composed,
+        offsets,
+    })
+}
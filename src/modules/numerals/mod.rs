@@ -0,0 +1,198 @@
+//! Numbers written in the digits of a script
+//!
+//! Each script's digits are the ones its schema maps the `Digit0`–`Digit9`
+//! tokens to, so only scripts with a single character for every digit have
+//! numerals here (Bharati Braille, which writes a numeric indicator before a
+//! run of digits, does not). Roman schemes use the ASCII digits. Numbers may be
+//! grouped with commas, either in the Indian style (1,23,45,678: three digits,
+//! then twos) or the Western one (12,345,678).
+
+use crate::modules::hub::{AbugidaToken, HubToken};
+use crate::modules::script_converter::ScriptConverterRegistry;
+use once_cell::sync::Lazy;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum NumeralError {
+    #[error("Unknown script: {0}")]
+    UnknownScript(String),
+    #[error("Script {0} has no single-character digits")]
+    NoDigits(String),
+}
+
+/// How the digits of a number are grouped with commas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Grouping {
+    /// Three digits, then groups of two: 1,23,45,678
+    #[default]
+    Indian,
+    /// Groups of three: 12,345,678
+    Western,
+    /// No separators: 12345678
+    Ungrouped,
+}
+
+const GROUP_SEPARATOR: char = ',';
+const DECIMAL_POINT: char = '.';
+
+const DIGIT_TOKENS: [AbugidaToken; 10] = [
+    AbugidaToken::Digit0,
+    AbugidaToken::Digit1,
+    AbugidaToken::Digit2,
+    AbugidaToken::Digit3,
+    AbugidaToken::Digit4,
+    AbugidaToken::Digit5,
+    AbugidaToken::Digit6,
+    AbugidaToken::Digit7,
+    AbugidaToken::Digit8,
+    AbugidaToken::Digit9,
+];
+
+/// The built-in converters, whose schemas give each script's digits
+static CONVERTERS: Lazy<ScriptConverterRegistry> = Lazy::new(ScriptConverterRegistry::default);
+
+/// The digits 0 to 9 of `script`
+fn digits(script: &str) -> Result<[char; 10], NumeralError> {
+    let converter = CONVERTERS
+        .token_converter(script)
+        .ok_or_else(|| NumeralError::UnknownScript(script.to_string()))?;
+    let no_digits = || NumeralError::NoDigits(script.to_string());
+
+    let mut digits = ['0'; 10];
+    for (digit, token) in digits.iter_mut().zip(DIGIT_TOKENS) {
+        let token = if converter.is_alphabet() {
+            HubToken::Alphabet(token.to_alphabet().ok_or_else(no_digits)?)
+        } else {
+            HubToken::Abugida(token)
+        };
+        if !converter.maps_token(&token) {
+            return Err(no_digits());
+        }
+        let written = converter.tokens_to_string(&vec![token]);
+        let mut chars = written.chars();
+        *digit = match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => return Err(no_digits()),
+        };
+    }
+    Ok(digits)
+}
+
+fn value(digits: &[char; 10], c: char) -> Option<usize> {
+    digits.iter().position(|&digit| digit == c)
+}
+
+/// Write digit `values` as the characters of `digits`, grouped by `grouping`
+fn write_grouped(output: &mut String, values: &[usize], digits: &[char; 10], grouping: Grouping) {
+    let len = values.len();
+    for (index, &value) in values.iter().enumerate() {
+        let remaining = len - index;
+        let separator_before = index > 0
+            && match grouping {
+                Grouping::Indian => remaining == 3 || (remaining > 3 && remaining % 2 == 1),
+                Grouping::Western => remaining.is_multiple_of(3),
+                Grouping::Ungrouped => false,
+            };
+        if separator_before {
+            output.push(GROUP_SEPARATOR);
+        }
+        output.push(digits[value]);
+    }
+}
+
+/// Write the digits of `from` in `text` as the digits of `to`, leaving separators as written
+///
+/// Everything but the digits of `from` is left as it is, including digits of
+/// other scripts.
+pub fn convert_digits(text: &str, from: &str, to: &str) -> Result<String, NumeralError> {
+    let (from_digits, to_digits) = (digits(from)?, digits(to)?);
+    Ok(text
+        .chars()
+        .map(|c| value(&from_digits, c).map_or(c, |value| to_digits[value]))
+        .collect())
+}
+
+/// [`convert_digits`], regrouping the whole part of each number by `grouping`
+///
+/// A number is a run of the digits of `from`, with commas between digits, and
+/// the digits after a decimal point are left ungrouped. Commas anywhere else
+/// are not part of a number and are left as written.
+pub fn convert_digits_grouped(
+    text: &str,
+    from: &str,
+    to: &str,
+    grouping: Grouping,
+) -> Result<String, NumeralError> {
+    let (from_digits, to_digits) = (digits(from)?, digits(to)?);
+    let chars: Vec<char> = text.chars().collect();
+    let is_digit = |index: usize| {
+        chars
+            .get(index)
+            .is_some_and(|&c| value(&from_digits, c).is_some())
+    };
+
+    let mut output = String::with_capacity(text.len());
+    let mut index = 0;
+    while index < chars.len() {
+        if !is_digit(index) {
+            output.push(chars[index]);
+            index += 1;
+            continue;
+        }
+
+        let mut values = Vec::new();
+        while index < chars.len() {
+            if let Some(value) = value(&from_digits, chars[index]) {
+                values.push(value);
+            } else if !(chars[index] == GROUP_SEPARATOR && is_digit(index + 1)) {
+                break;
+            }
+            index += 1;
+        }
+        write_grouped(&mut output, &values, &to_digits, grouping);
+
+        if chars.get(index) == Some(&DECIMAL_POINT) && is_digit(index + 1) {
+            output.push(DECIMAL_POINT);
+            index += 1;
+            while let Some(value) = chars.get(index).and_then(|&c| value(&from_digits, c)) {
+                output.push(to_digits[value]);
+                index += 1;
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// The number `text` writes in the digits of `script`
+///
+/// Commas between digits are allowed, in any grouping. `None` for text with
+/// anything else in it, for an unknown script, and for numbers past `u64::MAX`.
+pub fn parse_number(text: &str, script: &str) -> Option<u64> {
+    let digits = digits(script).ok()?;
+    let text = text.trim();
+    if text.starts_with(GROUP_SEPARATOR) || text.ends_with(GROUP_SEPARATOR) || text.is_empty() {
+        return None;
+    }
+
+    let mut number: u64 = 0;
+    let mut after_separator = false;
+    for c in text.chars() {
+        if c == GROUP_SEPARATOR && !after_separator {
+            after_separator = true;
+            continue;
+        }
+        let value = value(&digits, c)? as u64;
+        number = number.checked_mul(10)?.checked_add(value)?;
+        after_separator = false;
+    }
+    Some(number)
+}
+
+/// `n` written in the digits of `script`, grouped by `grouping`
+pub fn format_number(n: u64, script: &str, grouping: Grouping) -> Result<String, NumeralError> {
+    let digits = digits(script)?;
+    let values: Vec<usize> = n.to_string().bytes().map(|b| (b - b'0') as usize).collect();
+    let mut output = String::with_capacity(values.len() * 4);
+    write_grouped(&mut output, &values, &digits, grouping);
+    Ok(output)
+}
@@ -0,0 +1,113 @@
+//! Per-call options for `Shlesha::transliterate_with_options`
+
+use crate::modules::capitalize::Capitalization;
+use crate::modules::hyphenation::Hyphenation;
+use crate::modules::mixed_script::MixedScriptPolicy;
+use crate::modules::normalize::UnicodeForm;
+use crate::modules::selective::SelectivePolicy;
+use std::ops::Range;
+
+/// Unknown tokens the metadata of a conversion keeps by default
+pub const DEFAULT_MAX_UNKNOWN_TOKENS: usize = 1000;
+
+/// Options changing how a single conversion is carried out
+///
+/// The default converts the whole input, exactly as `Shlesha::transliterate`.
+#[derive(Debug, Clone)]
+pub struct TransliterationOptions {
+    /// Convert only the Sanskrit spans of mixed-language Roman text
+    pub selective: Option<SelectivePolicy>,
+    /// Read ITRANS words written entirely in capitals as the ITRANS they stand for
+    ///
+    /// Only applies when the source scheme is ITRANS; see `modules::legacy_caps`.
+    pub itrans_legacy_caps: bool,
+    /// Most unknown tokens the metadata keeps, `None` for all of them
+    ///
+    /// Unknown tokens past the limit are counted in the metadata's
+    /// `total_unknown_count` but not kept, so garbage input cannot grow the
+    /// metadata without bound. Defaults to `DEFAULT_MAX_UNKNOWN_TOKENS`.
+    pub max_unknown_tokens: Option<usize>,
+    /// How output in an Indic script writes letters with canonical decompositions
+    ///
+    /// Applied to the whole output as the last step. The default precomposes
+    /// the nukta letters, as every conversion does; see `UnicodeForm`.
+    pub unicode_form: UnicodeForm,
+    /// Write output in a right-to-left script between U+2068 FIRST STRONG
+    /// ISOLATE and U+2069 POP DIRECTIONAL ISOLATE
+    ///
+    /// The isolates keep right-to-left output from reordering the text around
+    /// it when it is put into left-to-right text. Output in other scripts is
+    /// not wrapped.
+    pub wrap_directional_isolates: bool,
+    /// Capitalize the first letter of each sentence or word of Roman output
+    ///
+    /// Only for Roman targets whose letters are all lowercase, such as IAST
+    /// and ISO-15919; in SLP1 or ITRANS a capital is another letter, and
+    /// converting to them is an error. Output in other scripts is unchanged.
+    pub capitalize: Capitalization,
+    /// Byte ranges of the input that are proper nouns, whose words begin with
+    /// a capital in Roman output
+    ///
+    /// Applied as `capitalize` is, and as well as it; see `modules::capitalize`.
+    pub proper_nouns: Vec<Range<usize>>,
+    /// Read hyphens between the members of compounds as part of the word
+    ///
+    /// The markers are kept in the output or left out as `Hyphenation::output`
+    /// says; see `modules::hyphenation`. `None` converts hyphens as any other
+    /// punctuation. Not available with `selective`.
+    pub hyphenation: Option<Hyphenation>,
+    /// Record in the metadata the versions of what produced the output
+    ///
+    /// The crate version, `Shlesha::output_version()` and the declared
+    /// `version` of each runtime schema used; see `ConversionProvenance`.
+    pub record_provenance: bool,
+    /// How input from an Indic script that is partly in another Indic script
+    /// is reported, and whether those parts are converted as that script
+    ///
+    /// See `modules::mixed_script`.
+    pub mixed_script: MixedScriptPolicy,
+}
+
+impl Default for TransliterationOptions {
+    fn default() -> Self {
+        Self {
+            selective: None,
+            itrans_legacy_caps: false,
+            max_unknown_tokens: Some(DEFAULT_MAX_UNKNOWN_TOKENS),
+            unicode_form: UnicodeForm::default(),
+            wrap_directional_isolates: false,
+            capitalize: Capitalization::None,
+            proper_nouns: Vec::new(),
+            hyphenation: None,
+            record_provenance: false,
+            mixed_script: MixedScriptPolicy::default(),
+        }
+    }
+}
+
+impl TransliterationOptions {
+    /// Options that convert only the spans `policy` picks out, leaving other words untouched
+    pub fn selective(policy: SelectivePolicy) -> Self {
+        Self {
+            selective: Some(policy),
+            ..Self::default()
+        }
+    }
+
+    /// Options for citations, reading the hyphens of compounds as part of the
+    /// word and writing them back, as in tat-tvam-asi
+    pub fn citation() -> Self {
+        Self {
+            hyphenation: Some(Hyphenation::default()),
+            ..Self::default()
+        }
+    }
+
+    /// Options reading ALL CAPS words of legacy ITRANS files with their intended case
+    pub fn itrans_legacy_caps() -> Self {
+        Self {
+            itrans_legacy_caps: true,
+            ..Self::default()
+        }
+    }
+}
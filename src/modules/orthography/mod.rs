@@ -0,0 +1,171 @@
+//! Spelling choices of scripts written more than one way
+//!
+//! Converters write each hub token one way; the passes here adjust the written
+//! text where a script's users differ on how clusters are formed, or where old
+//! books spell vowels differently.
+
+use crate::modules::core::unknown_handler::{NormalizationKind, NormalizedSpan};
+use std::borrow::Cow;
+
+const AL_LAKUNA: char = '\u{0DCA}';
+const ZWJ: char = '\u{200D}';
+const PRISHTHAMATRA: char = '\u{094E}';
+const NAKARA_POLLU: char = '\u{0C5D}';
+/// న with pollu (virama), which nakara pollu writes as one letter
+const TELUGU_NA_POLLU: &str = "\u{0C28}\u{0C4D}";
+/// The harakat the Urdu schema writes: zabar, pesh, zer and jazm
+const URDU_HARAKAT: [char; 4] = ['\u{064E}', '\u{064F}', '\u{0650}', '\u{0652}'];
+
+/// Modern Devanagari vowel signs and their prishthamatra spellings
+///
+/// From the Unicode Standard's description of U+094E: the prishthamatra alone
+/// is e, and before े, ा and ो it is ai, o and au.
+const PRISHTHAMATRA_SIGNS: [(char, &str); 4] = [
+    ('\u{0947}', "\u{094E}"),
+    ('\u{0948}', "\u{094E}\u{0947}"),
+    ('\u{094B}', "\u{094E}\u{093E}"),
+    ('\u{094C}', "\u{094E}\u{094B}"),
+];
+
+/// How Sinhala output joins the consonants of a cluster
+///
+/// Yansaya and rakaransaya (a cluster ending in ya or ra, as in ශ්‍රී) are
+/// written with al-lakuna + ZWJ in either style, as all Sinhala text does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SinhalaConjuncts {
+    /// A visible al-lakuna between the consonants (ක්ෂ), as modern Sinhala writes
+    #[default]
+    Explicit,
+    /// Touching letters, ZWJ + al-lakuna (ක‍්ෂ), as Pali and Sanskrit printing writes
+    Touching,
+}
+
+fn is_sinhala_consonant(c: char) -> bool {
+    ('\u{0D9A}'..='\u{0DC6}').contains(&c)
+}
+
+/// Write the clusters of Sinhala text in `style`
+///
+/// Only an al-lakuna between two consonants is changed; one already next to a
+/// joiner is left as written.
+pub fn join_sinhala_clusters(text: &str, style: SinhalaConjuncts) -> Cow<'_, str> {
+    if !text.contains(AL_LAKUNA) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut output = String::with_capacity(text.len() + text.len() / 8);
+    let mut changed = false;
+    let mut prev = None;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let next = chars.peek().copied();
+        let in_cluster = c == AL_LAKUNA
+            && prev.is_some_and(is_sinhala_consonant)
+            && next.is_some_and(is_sinhala_consonant);
+        match next {
+            // Yansaya and rakaransaya
+            Some('\u{0DBA}' | '\u{0DBB}') if in_cluster => {
+                output.extend([AL_LAKUNA, ZWJ]);
+                changed = true;
+            }
+            _ if in_cluster && style == SinhalaConjuncts::Touching => {
+                output.extend([ZWJ, AL_LAKUNA]);
+                changed = true;
+            }
+            _ => output.push(c),
+        }
+        prev = Some(c);
+    }
+
+    if changed {
+        Cow::Owned(output)
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Write the e, ai, o and au vowel signs of Devanagari text with the prishthamatra
+pub fn write_prishthamatra(text: &str) -> Cow<'_, str> {
+    let is_modern_sign = |c: char| PRISHTHAMATRA_SIGNS.iter().any(|&(sign, _)| sign == c);
+    if !text.contains(is_modern_sign) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut output = String::with_capacity(text.len() + text.len() / 4);
+    for c in text.chars() {
+        match PRISHTHAMATRA_SIGNS.iter().find(|&&(sign, _)| sign == c) {
+            Some((_, old)) => output.push_str(old),
+            None => output.push(c),
+        }
+    }
+    Cow::Owned(output)
+}
+
+/// The prishthamatra vowel signs of Devanagari text, with the modern signs they are read as
+pub fn prishthamatra_spans(text: &str) -> Vec<NormalizedSpan> {
+    let mut spans = Vec::new();
+    for (position, _) in text.match_indices(PRISHTHAMATRA) {
+        // The longest spelling at this position; the prishthamatra alone is e
+        let (modern, old) = PRISHTHAMATRA_SIGNS
+            .iter()
+            .rev()
+            .find(|(_, old)| text[position..].starts_with(old))
+            .copied()
+            .unwrap_or(PRISHTHAMATRA_SIGNS[0]);
+        spans.push(NormalizedSpan {
+            kind: NormalizationKind::OldSpelling,
+            input: old.to_string(),
+            read_as: modern.to_string(),
+            position,
+        });
+    }
+    spans
+}
+
+/// Write each word-final న్ of Telugu text as the nakara pollu ౝ
+///
+/// Only a న్ not followed by more of the word is written so: before another
+/// Telugu letter or sign, or a joiner, it is part of a cluster and left as written.
+pub fn write_nakara_pollu(text: &str) -> Cow<'_, str> {
+    if !text.contains(TELUGU_NA_POLLU) {
+        return Cow::Borrowed(text);
+    }
+
+    let continues_word = |c: char| matches!(c, '\u{0C00}'..='\u{0C7F}' | '\u{200C}' | '\u{200D}');
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find(TELUGU_NA_POLLU) {
+        let after = &rest[at + TELUGU_NA_POLLU.len()..];
+        output.push_str(&rest[..at]);
+        if after.chars().next().is_some_and(continues_word) {
+            output.push_str(TELUGU_NA_POLLU);
+        } else {
+            output.push(NAKARA_POLLU);
+        }
+        rest = after;
+    }
+    output.push_str(rest);
+    Cow::Owned(output)
+}
+
+/// The nakara pollu letters of Telugu text, with the న్ they are read as
+pub fn nakara_pollu_spans(text: &str) -> Vec<NormalizedSpan> {
+    text.match_indices(NAKARA_POLLU)
+        .map(|(position, old)| NormalizedSpan {
+            kind: NormalizationKind::OldSpelling,
+            input: old.to_string(),
+            read_as: TELUGU_NA_POLLU.to_string(),
+            position,
+        })
+        .collect()
+}
+
+/// Urdu text without its harakat (zabar, pesh, zer and jazm), as Urdu is usually written
+///
+/// The letters are left as they are: پانِی is written پانی, and اُرْدُو اُردو.
+pub fn omit_harakat(text: &str) -> Cow<'_, str> {
+    if !text.contains(URDU_HARAKAT) {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(text.chars().filter(|c| !URDU_HARAKAT.contains(c)).collect())
+}
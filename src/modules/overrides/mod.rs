@@ -0,0 +1,158 @@
+//! Words written a fixed way whatever the conversion rules produce
+//!
+//! Proper nouns ("Bengaluru"), organization names and irregular sandhi splits
+//! are registered per script pair. Before conversion, overrides are matched on
+//! word boundaries: a match must start and end next to whitespace, punctuation
+//! or the edge of the text, so "Bengaluru" is not overridden inside
+//! "Bengalurean". Where entries overlap, the longest match wins. Matching is
+//! case-sensitive, as Roman schemes like SLP1 and ITRANS are.
+
+use rustc_hash::FxHashMap;
+use std::ops::Range;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum OverrideError {
+    #[error("Override words cannot be empty")]
+    EmptyWord,
+    #[error("Line {line}: expected the word and its output separated by a tab")]
+    MalformedLine { line: usize },
+    #[error("Failed to read overrides: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Override outputs for one script pair, by input word
+#[derive(Debug, Clone, Default)]
+struct PairOverrides {
+    words: FxHashMap<String, String>,
+    /// Byte length of the longest word, bounding the search for matches
+    longest: usize,
+}
+
+/// Word overrides for any number of script pairs
+#[derive(Debug, Clone, Default)]
+pub struct WordOverrides {
+    pairs: FxHashMap<(String, String), PairOverrides>,
+}
+
+impl WordOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write `word` as `output` when converting from `from` to `to`
+    ///
+    /// A word may span several words of text ("Tata Consultancy Services").
+    /// Adding a word again replaces its output.
+    pub fn insert(
+        &mut self,
+        from: &str,
+        to: &str,
+        word: &str,
+        output: &str,
+    ) -> Result<(), OverrideError> {
+        if word.is_empty() {
+            return Err(OverrideError::EmptyWord);
+        }
+        let pair = self
+            .pairs
+            .entry((from.to_string(), to.to_string()))
+            .or_default();
+        pair.longest = pair.longest.max(word.len());
+        pair.words.insert(word.to_string(), output.to_string());
+        Ok(())
+    }
+
+    /// Add the overrides of a TSV list, returning how many were added
+    ///
+    /// Each line holds a word and its output separated by a tab; blank lines and
+    /// lines starting with `#` are ignored.
+    pub fn insert_tsv(&mut self, from: &str, to: &str, tsv: &str) -> Result<usize, OverrideError> {
+        let mut entries = Vec::new();
+        for (index, line) in tsv.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split('\t').collect::<Vec<_>>()[..] {
+                [word, output] if !word.is_empty() => entries.push((word, output)),
+                _ => return Err(OverrideError::MalformedLine { line: index + 1 }),
+            }
+        }
+
+        // Nothing is added from a list with a malformed line
+        for (word, output) in &entries {
+            self.insert(from, to, word, output)?;
+        }
+        Ok(entries.len())
+    }
+
+    /// Whether no overrides are registered for any pair
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Remove every override
+    pub fn clear(&mut self) {
+        self.pairs.clear();
+    }
+
+    /// Byte ranges of `text` to override, with their outputs
+    ///
+    /// The ranges are in order and do not overlap.
+    pub fn matches<'a>(&'a self, from: &str, to: &str, text: &str) -> Vec<(Range<usize>, &'a str)> {
+        let Some(pair) = self.pairs.get(&(from.to_string(), to.to_string())) else {
+            return Vec::new();
+        };
+
+        let mut matches = Vec::new();
+        let mut at_boundary = true;
+        let mut start = 0;
+        while let Some(c) = text[start..].chars().next() {
+            if at_boundary && !is_boundary(c) {
+                if let Some((end, output)) = longest_match(pair, text, start) {
+                    matches.push((start..end, output));
+                    // The match ends at a boundary, or at the end of the text
+                    start = end;
+                    continue;
+                }
+            }
+            at_boundary = is_boundary(c);
+            start += c.len_utf8();
+        }
+        matches
+    }
+}
+
+/// End and output of the longest override starting at `start` and ending on a boundary
+fn longest_match<'a>(
+    pair: &'a PairOverrides,
+    text: &str,
+    start: usize,
+) -> Option<(usize, &'a str)> {
+    let mut ends: Vec<usize> = text[start..]
+        .char_indices()
+        .skip(1)
+        .take_while(|&(i, _)| i <= pair.longest)
+        .filter(|&(_, c)| is_boundary(c))
+        .map(|(i, _)| start + i)
+        .collect();
+    if text.len() - start <= pair.longest {
+        ends.push(text.len());
+    }
+
+    ends.into_iter().rev().find_map(|end| {
+        pair.words
+            .get(&text[start..end])
+            .map(|output| (end, output.as_str()))
+    })
+}
+
+/// Characters words are separated by, in any script
+fn is_boundary(c: char) -> bool {
+    c.is_whitespace()
+        || c.is_ascii_punctuation()
+        // Danda and double danda
+        || matches!(c, '\u{0964}' | '\u{0965}')
+        // General punctuation: dashes, curly quotes, ellipsis
+        || matches!(c, '\u{2010}'..='\u{205E}')
+}
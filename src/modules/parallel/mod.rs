@@ -0,0 +1,62 @@
+//! Splitting large inputs for parallel conversion
+//!
+//! Conversion is context-sensitive only within a run of script text: a consonant
+//! looks ahead for a virama or vowel sign, and a Roman tokenizer matches the longest
+//! pattern starting at each position. Whitespace and dandas end every such run, so
+//! text split immediately after them converts to the same output piece by piece as
+//! it does whole.
+
+/// Smallest chunk worth handing to another thread
+pub const MIN_CHUNK_LEN: usize = 16 * 1024;
+
+/// Default input size (in bytes) above which `Shlesha` converts in parallel
+pub const DEFAULT_PARALLEL_THRESHOLD: usize = 256 * 1024;
+
+/// Characters after which a chunk may end
+fn is_boundary_char(c: char) -> bool {
+    // Devanagari danda and double danda are shared by most Brahmic scripts
+    c.is_whitespace() || matches!(c, '\u{0964}' | '\u{0965}')
+}
+
+/// Target chunk length for `text_len` bytes spread over `threads` workers
+///
+/// Aims for a few chunks per thread so uneven chunks still balance out.
+pub fn chunk_len(text_len: usize, threads: usize) -> usize {
+    text_len.div_ceil(threads.max(1) * 4).max(MIN_CHUNK_LEN)
+}
+
+/// Split `text` into chunks of roughly `target_len` bytes at safe boundaries
+///
+/// Each chunk ends after a complete run of whitespace and dandas, so no chunk
+/// starts with one. Returns each chunk with its byte offset in `text`; the chunks
+/// concatenate back to `text`. Text with no boundary past `target_len` stays whole.
+pub fn split_at_safe_boundaries(text: &str, target_len: usize) -> Vec<(usize, &str)> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while text.len() - start > target_len {
+        let mut search_from = start + target_len;
+        while !text.is_char_boundary(search_from) {
+            search_from += 1;
+        }
+        let Some(run_start) = text[search_from..]
+            .char_indices()
+            .find(|&(_, c)| is_boundary_char(c))
+            .map(|(i, _)| search_from + i)
+        else {
+            break;
+        };
+        let end = text[run_start..]
+            .char_indices()
+            .find(|&(_, c)| !is_boundary_char(c))
+            .map_or(text.len(), |(i, _)| run_start + i);
+
+        chunks.push((start, &text[start..end]));
+        start = end;
+    }
+
+    if start < text.len() || chunks.is_empty() {
+        chunks.push((start, &text[start..]));
+    }
+    chunks
+}
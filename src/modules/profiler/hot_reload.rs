@@ -7,9 +7,12 @@
 //! - Supports rollback on failed loads
 
 use super::{OptimizedLookupTable, Profiler};
+use crate::modules::registry::SchemaRegistry;
+use crate::modules::trace::stage;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
 use std::thread;
 use std::time::{Duration, SystemTime};
 
@@ -44,7 +47,11 @@ impl HotReloadManager {
             loop {
                 thread::sleep(Duration::from_secs(10)); // Check every 10 seconds
 
-                if !*manager.enabled.read().unwrap() {
+                if !*manager
+                    .enabled
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                {
                     continue;
                 }
 
@@ -55,7 +62,10 @@ impl HotReloadManager {
 
     /// Check for new or updated optimization files
     pub fn check_for_updates(&self) {
-        let last_check = *self.last_check.read().unwrap();
+        let last_check = *self
+            .last_check
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
 
         if let Ok(entries) = fs::read_dir(&self.watch_dir) {
             for entry in entries.flatten() {
@@ -79,7 +89,10 @@ impl HotReloadManager {
             }
         }
 
-        *self.last_check.write().unwrap() = SystemTime::now();
+        *self
+            .last_check
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = SystemTime::now();
     }
 
     /// Try to load an optimization file
@@ -137,7 +150,7 @@ impl HotReloadManager {
 
     /// Enable or disable hot reloading
     pub fn set_enabled(&self, enabled: bool) {
-        *self.enabled.write().unwrap() = enabled;
+        *self.enabled.write().unwrap_or_else(PoisonError::into_inner) = enabled;
     }
 
     /// Manually trigger a reload check
@@ -151,10 +164,75 @@ impl HotReloadManager {
     }
 }
 
+/// Most tables an [`OptimizationCache`] holds by default
+pub const DEFAULT_MAX_OPTIMIZATION_TABLES: usize = 64;
+
+/// Most sequence and word mappings an [`OptimizationCache`] holds by default
+pub const DEFAULT_MAX_OPTIMIZATION_ENTRIES: usize = 1 << 20;
+
+/// Bounds of an [`OptimizationCache`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OptimizationCacheConfig {
+    /// Most tables kept, one per script pair
+    pub max_tables: usize,
+    /// Most sequence and word mappings kept across all tables
+    pub max_entries: usize,
+}
+
+impl Default for OptimizationCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_tables: DEFAULT_MAX_OPTIMIZATION_TABLES,
+            max_entries: DEFAULT_MAX_OPTIMIZATION_ENTRIES,
+        }
+    }
+}
+
+/// Revisions of the schemas of a script pair, `None` for a script with no schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PairRevision {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+impl PairRevision {
+    /// The revisions `schemas` has for `from_script` and `to_script`
+    pub fn current(schemas: &SchemaRegistry, from_script: &str, to_script: &str) -> Self {
+        Self {
+            from: schemas.schema_revision(from_script),
+            to: schemas.schema_revision(to_script),
+        }
+    }
+}
+
+/// A loaded table, with the revisions it was loaded for and when it was last used
+struct CachedTable {
+    table: OptimizedLookupTable,
+    revision: PairRevision,
+    last_used: AtomicU64,
+}
+
+impl CachedTable {
+    fn entries(&self) -> usize {
+        self.table.sequence_mappings.len() + self.table.word_mappings.len()
+    }
+}
+
 /// Optimization cache that integrates with the transliterator
+///
+/// Holds at most [`OptimizationCacheConfig::max_tables`] tables and
+/// [`OptimizationCacheConfig::max_entries`] mappings, evicting the least
+/// recently used table when a load goes past either; a table with more
+/// mappings than the bound is not kept at all. Each table is tagged with the
+/// revisions of its pair's schemas when it is loaded, and dropped when it is
+/// next looked up after either schema has been reloaded.
 pub struct OptimizationCache {
-    /// Cached optimizations by conversion path
-    cache: Arc<RwLock<FxHashMap<(String, String), OptimizedLookupTable>>>,
+    /// Cached optimizations by source and then target script, so lookups by
+    /// `&str` need no owned key
+    cache: Arc<RwLock<FxHashMap<String, FxHashMap<String, CachedTable>>>>,
+    config: OptimizationCacheConfig,
+    /// Ticks on every load and lookup, ordering the tables by last use
+    clock: AtomicU64,
 }
 
 use rustc_hash::FxHashMap;
@@ -166,41 +244,145 @@ impl Default for OptimizationCache {
 }
 
 impl OptimizationCache {
-    /// Create a new optimization cache
+    /// Create a new optimization cache with the default bounds
     pub fn new() -> Self {
+        Self::with_config(OptimizationCacheConfig::default())
+    }
+
+    /// Create a new optimization cache with the given bounds
+    pub fn with_config(config: OptimizationCacheConfig) -> Self {
         Self {
             cache: Arc::new(RwLock::new(FxHashMap::default())),
+            config,
+            clock: AtomicU64::new(0),
         }
     }
 
-    /// Get an optimization for a specific conversion path
-    pub fn get(&self, from_script: &str, to_script: &str) -> Option<OptimizedLookupTable> {
-        let cache = self.cache.read().unwrap();
-        cache
-            .get(&(from_script.to_string(), to_script.to_string()))
-            .cloned()
+    /// The bounds of the cache
+    pub fn config(&self) -> OptimizationCacheConfig {
+        self.config
     }
 
-    /// Load an optimization into the cache
-    pub fn load(&self, optimization: OptimizedLookupTable) {
-        let mut cache = self.cache.write().unwrap();
-        let key = (
-            optimization.from_script.clone(),
-            optimization.to_script.clone(),
-        );
-        cache.insert(key, optimization);
+    /// Change the bounds of the cache, evicting tables until it is within them
+    pub fn set_config(&mut self, config: OptimizationCacheConfig) {
+        self.config = config;
+        let mut cache = self.cache.write().unwrap_or_else(PoisonError::into_inner);
+        Self::evict(&mut cache, config);
+    }
+
+    /// Get the optimization for a conversion path, if it was loaded for the
+    /// schemas `schemas` has now
+    ///
+    /// A table loaded before either schema was reloaded is dropped.
+    pub fn get(
+        &self,
+        from_script: &str,
+        to_script: &str,
+        schemas: &SchemaRegistry,
+    ) -> Option<OptimizedLookupTable> {
+        {
+            let cache = self.cache.read().unwrap_or_else(PoisonError::into_inner);
+            let cached = cache.get(from_script)?.get(to_script)?;
+            if cached.revision == PairRevision::current(schemas, from_script, to_script) {
+                cached.last_used.store(self.tick(), Ordering::Relaxed);
+                return Some(cached.table.clone());
+            }
+        }
+
+        let mut cache = self.cache.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(targets) = cache.get_mut(from_script) {
+            // Checked again, since another thread may have loaded a fresh table
+            if targets.get(to_script).is_some_and(|cached| {
+                cached.revision != PairRevision::current(schemas, from_script, to_script)
+            }) {
+                targets.remove(to_script);
+            }
+            if targets.is_empty() {
+                cache.remove(from_script);
+            }
+        }
+        None
+    }
+
+    /// Load an optimization into the cache, for the schemas `schemas` has now
+    ///
+    /// Replaces any table for the same pair, then evicts the least recently
+    /// used tables while the cache is over its bounds.
+    pub fn load(&self, optimization: OptimizedLookupTable, schemas: &SchemaRegistry) {
+        let revision =
+            PairRevision::current(schemas, &optimization.from_script, &optimization.to_script);
+        let cached = CachedTable {
+            table: optimization,
+            revision,
+            last_used: AtomicU64::new(self.tick()),
+        };
+        let mut cache = self.cache.write().unwrap_or_else(PoisonError::into_inner);
+        cache
+            .entry(cached.table.from_script.clone())
+            .or_default()
+            .insert(cached.table.to_script.clone(), cached);
+        Self::evict(&mut cache, self.config);
     }
 
     /// Clear all cached optimizations
     pub fn clear(&self) {
-        let mut cache = self.cache.write().unwrap();
+        let mut cache = self.cache.write().unwrap_or_else(PoisonError::into_inner);
         cache.clear();
     }
 
     /// Get the number of cached optimizations
     pub fn size(&self) -> usize {
-        let cache = self.cache.read().unwrap();
-        cache.len()
+        let cache = self.cache.read().unwrap_or_else(PoisonError::into_inner);
+        cache.values().map(FxHashMap::len).sum()
+    }
+
+    /// Get the number of sequence and word mappings across the cached optimizations
+    pub fn entry_count(&self) -> usize {
+        let cache = self.cache.read().unwrap_or_else(PoisonError::into_inner);
+        cache
+            .values()
+            .flat_map(FxHashMap::values)
+            .map(CachedTable::entries)
+            .sum()
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Drop the least recently used tables until `cache` is within `config`
+    fn evict(
+        cache: &mut FxHashMap<String, FxHashMap<String, CachedTable>>,
+        config: OptimizationCacheConfig,
+    ) {
+        let mut tables: usize = cache.values().map(FxHashMap::len).sum();
+        let mut entries: usize = cache
+            .values()
+            .flat_map(FxHashMap::values)
+            .map(CachedTable::entries)
+            .sum();
+        while tables > config.max_tables || entries > config.max_entries {
+            let Some((from, to)) = cache
+                .iter()
+                .flat_map(|(from, targets)| {
+                    targets.iter().map(move |(to, cached)| (from, to, cached))
+                })
+                .min_by_key(|(_, _, cached)| cached.last_used.load(Ordering::Relaxed))
+                .map(|(from, to, _)| (from.clone(), to.clone()))
+            else {
+                break;
+            };
+            let Some(targets) = cache.get_mut(&from) else {
+                break;
+            };
+            if let Some(evicted) = targets.remove(&to) {
+                tables -= 1;
+                entries -= evicted.entries();
+            }
+            if targets.is_empty() {
+                cache.remove(&from);
+            }
+        }
     }
 
     /// Apply optimization to convert text
@@ -209,12 +391,25 @@ impl OptimizationCache {
         text: &str,
         from_script: &str,
         to_script: &str,
+        schemas: &SchemaRegistry,
         fallback: F,
     ) -> Result<String, Box<dyn std::error::Error>>
     where
         F: Fn(&str) -> Result<String, Box<dyn std::error::Error>>,
     {
-        if let Some(optimization) = self.get(from_script, to_script) {
+        let optimization = {
+            let stage = stage!(
+                "optimization_cache",
+                from = from_script,
+                to = to_script,
+                hit = tracing::field::Empty
+            );
+            let optimization = self.get(from_script, to_script, schemas);
+            stage.record("hit", optimization.is_some());
+            optimization
+        };
+
+        if let Some(optimization) = optimization {
             // Try to use optimized conversion
             let mut result = String::new();
             let chars = text.chars();
@@ -328,18 +523,19 @@ mod tests {
         assert!(!manager.validate_optimization(&invalid_opt));
     }
 
-    #[test]
-    fn test_optimization_cache() {
-        let cache = OptimizationCache::new();
-
-        let mut optimization = OptimizedLookupTable {
-            from_script: "devanagari".to_string(),
-            to_script: "iast".to_string(),
-            sequence_mappings: FxHashMap::default(),
+    /// A table for `from` → `to` with the given sequence mappings
+    fn table(from: &str, to: &str, mappings: &[(&str, &str)]) -> OptimizedLookupTable {
+        OptimizedLookupTable {
+            from_script: from.to_string(),
+            to_script: to.to_string(),
+            sequence_mappings: mappings
+                .iter()
+                .map(|&(from, to)| (from.to_string(), to.to_string()))
+                .collect(),
             word_mappings: FxHashMap::default(),
             metadata: OptimizationMetadata {
                 generated_at: SystemTime::now(),
-                sequence_count: 1,
+                sequence_count: mappings.len(),
                 min_frequency: 10,
                 profile_stats: ProfileStats {
                     total_sequences_profiled: 100,
@@ -347,16 +543,97 @@ mod tests {
                     top_sequences: vec![],
                 },
             },
-        };
-        optimization
-            .sequence_mappings
-            .insert("धर्म".to_string(), "dharma".to_string());
+        }
+    }
+
+    #[test]
+    fn test_optimization_cache() {
+        let cache = OptimizationCache::new();
+        let schemas = SchemaRegistry::new();
 
-        cache.load(optimization.clone());
+        cache.load(table("devanagari", "iast", &[("धर्म", "dharma")]), &schemas);
         assert_eq!(cache.size(), 1);
+        assert_eq!(cache.entry_count(), 1);
 
-        let retrieved = cache.get("devanagari", "iast").unwrap();
+        let retrieved = cache.get("devanagari", "iast", &schemas).unwrap();
         assert_eq!(retrieved.from_script, "devanagari");
         assert_eq!(retrieved.sequence_mappings["धर्म"], "dharma");
     }
+
+    #[test]
+    fn test_least_recently_used_table_is_evicted() {
+        let cache = OptimizationCache::with_config(OptimizationCacheConfig {
+            max_tables: 2,
+            ..Default::default()
+        });
+        let schemas = SchemaRegistry::new();
+        let cached = |to: &str| cache.get("devanagari", to, &schemas).is_some();
+
+        cache.load(table("devanagari", "iast", &[("क", "ka")]), &schemas);
+        cache.load(table("devanagari", "slp1", &[("क", "ka")]), &schemas);
+        // Using iast makes slp1 the least recently used
+        assert!(cached("iast"));
+        cache.load(table("devanagari", "itrans", &[("क", "ka")]), &schemas);
+        assert_eq!(cache.size(), 2);
+        assert!(!cached("slp1"));
+        assert!(cached("iast"));
+        assert!(cached("itrans"));
+
+        // Then itrans, used before iast
+        assert!(cached("iast"));
+        cache.load(table("devanagari", "wx", &[("क", "ka")]), &schemas);
+        assert!(!cached("itrans"));
+        assert!(cached("iast"));
+        assert!(cached("wx"));
+
+        // Reloading a pair replaces its table rather than adding one
+        cache.load(table("devanagari", "wx", &[("ख", "Ka")]), &schemas);
+        assert_eq!(cache.size(), 2);
+        assert!(cached("iast"));
+    }
+
+    #[test]
+    fn test_entry_bound() {
+        let mut cache = OptimizationCache::with_config(OptimizationCacheConfig {
+            max_tables: 8,
+            max_entries: 3,
+        });
+        let schemas = SchemaRegistry::new();
+
+        cache.load(
+            table("devanagari", "iast", &[("क", "ka"), ("ख", "kha")]),
+            &schemas,
+        );
+        cache.load(table("devanagari", "slp1", &[("क", "ka")]), &schemas);
+        assert_eq!((cache.size(), cache.entry_count()), (2, 3));
+
+        // Two more mappings push out the oldest table
+        cache.load(
+            table("devanagari", "wx", &[("क", "ka"), ("ख", "Ka")]),
+            &schemas,
+        );
+        assert_eq!((cache.size(), cache.entry_count()), (2, 3));
+        assert!(cache.get("devanagari", "iast", &schemas).is_none());
+
+        // A table over the bound on its own is not kept
+        cache.load(
+            table(
+                "devanagari",
+                "itrans",
+                &[("क", "ka"), ("ख", "kha"), ("ग", "ga"), ("घ", "gha")],
+            ),
+            &schemas,
+        );
+        assert_eq!((cache.size(), cache.entry_count()), (0, 0));
+
+        // Tightening the bounds evicts at once
+        cache.load(table("devanagari", "iast", &[("क", "ka")]), &schemas);
+        cache.load(table("devanagari", "slp1", &[("क", "ka")]), &schemas);
+        cache.set_config(OptimizationCacheConfig {
+            max_tables: 1,
+            max_entries: 3,
+        });
+        assert_eq!(cache.size(), 1);
+        assert!(cache.get("devanagari", "slp1", &schemas).is_some());
+    }
 }
@@ -9,14 +9,17 @@
 pub mod hot_reload;
 pub mod optimizer;
 
-pub use hot_reload::{HotReloadManager, OptimizationCache};
+pub use hot_reload::{
+    HotReloadManager, OptimizationCache, OptimizationCacheConfig, PairRevision,
+    DEFAULT_MAX_OPTIMIZATION_ENTRIES, DEFAULT_MAX_OPTIMIZATION_TABLES,
+};
 pub use optimizer::{OptimizationBenchmark, OptimizationGenerator};
 
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex, RwLock};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
 use std::time::{Duration, Instant, SystemTime};
 
 /// Usage statistics for a character sequence
@@ -172,8 +175,12 @@ impl Profiler {
             return;
         }
 
+        // A panic while recording may have left a profile half-updated; skip
+        // profiling from then on rather than fail the conversion
+        let Ok(mut profiles) = self.profiles.write() else {
+            return;
+        };
         let key = (from_script.to_string(), to_script.to_string());
-        let mut profiles = self.profiles.write().unwrap();
 
         let profile = profiles
             .entry(key.clone())
@@ -232,7 +239,10 @@ impl Profiler {
 
         // Extract sequences from the text
         let sequences = self.extract_sequences(text);
-        let time_per_sequence = processing_time / sequences.len() as u32;
+        // Text of only whitespace and punctuation has no sequences
+        let time_per_sequence = processing_time
+            .checked_div(sequences.len() as u32)
+            .unwrap_or_default();
 
         for sequence in sequences {
             self.record_sequence(from_script, to_script, &sequence, time_per_sequence);
@@ -278,7 +288,7 @@ impl Profiler {
 
     /// Generate optimized lookup tables from current profiles
     pub fn generate_optimizations(&self) -> Vec<OptimizedLookupTable> {
-        let profiles = self.profiles.read().unwrap();
+        let profiles = self.profiles.read().unwrap_or_else(PoisonError::into_inner);
         let mut optimizations = Vec::new();
 
         for ((from_script, to_script), profile) in profiles.iter() {
@@ -327,7 +337,10 @@ impl Profiler {
         from_script: &str,
         to_script: &str,
     ) -> Option<OptimizedLookupTable> {
-        let optimizations = self.optimizations.read().unwrap();
+        let optimizations = self
+            .optimizations
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
         optimizations
             .get(&(from_script.to_string(), to_script.to_string()))
             .cloned()
@@ -339,14 +352,17 @@ impl Profiler {
             return;
         }
 
-        let mut optimizations = self.optimizations.write().unwrap();
+        let mut optimizations = self
+            .optimizations
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
         let key = (table.from_script.clone(), table.to_script.clone());
         optimizations.insert(key, table);
     }
 
     /// Save current profiles to disk
     pub fn save_profiles(&self) {
-        let profiles = self.profiles.read().unwrap();
+        let profiles = self.profiles.read().unwrap_or_else(PoisonError::into_inner);
 
         for ((from_script, to_script), profile) in profiles.iter() {
             let filename = format!("{from_script}_{to_script}_profile.json");
@@ -357,7 +373,10 @@ impl Profiler {
             }
         }
 
-        *self.last_save_time.lock().unwrap() = Instant::now();
+        *self
+            .last_save_time
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Instant::now();
     }
 
     /// Load profiles from disk
@@ -366,7 +385,10 @@ impl Profiler {
             return;
         }
 
-        let mut profiles = self.profiles.write().unwrap();
+        let mut profiles = self
+            .profiles
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
 
         if let Ok(entries) = fs::read_dir(&self.config.profile_dir) {
             for entry in entries.flatten() {
@@ -404,7 +426,10 @@ impl Profiler {
             return;
         }
 
-        let mut optimizations = self.optimizations.write().unwrap();
+        let mut optimizations = self
+            .optimizations
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
 
         if let Ok(entries) = fs::read_dir(&self.config.optimization_dir) {
             for entry in entries.flatten() {
@@ -423,7 +448,10 @@ impl Profiler {
 
     /// Check if we should auto-save profiles
     fn maybe_auto_save(&self) {
-        let last_save = *self.last_save_time.lock().unwrap();
+        let last_save = *self
+            .last_save_time
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
         if last_save.elapsed() >= self.config.auto_save_interval {
             self.save_profiles();
         }
@@ -431,7 +459,7 @@ impl Profiler {
 
     /// Get profile statistics for monitoring
     pub fn get_profile_stats(&self) -> FxHashMap<(String, String), ProfileStats> {
-        let profiles = self.profiles.read().unwrap();
+        let profiles = self.profiles.read().unwrap_or_else(PoisonError::into_inner);
         let mut stats = FxHashMap::default();
 
         for (key, profile) in profiles.iter() {
@@ -459,7 +487,10 @@ impl Profiler {
 
     /// Clear all profile data
     pub fn clear_profiles(&self) {
-        let mut profiles = self.profiles.write().unwrap();
+        let mut profiles = self
+            .profiles
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
         profiles.clear();
     }
 
@@ -499,6 +530,38 @@ mod tests {
         assert_eq!(profile.sequences["योग"].count, 1);
     }
 
+    #[test]
+    fn test_poisoned_profiler_does_not_fail_conversions() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut shlesha = crate::Shlesha::new();
+        shlesha.enable_profiling_with_config(ProfilerConfig {
+            profile_dir: dir.path().join("profiles"),
+            optimization_dir: dir.path().join("optimizations"),
+            ..Default::default()
+        });
+        assert_eq!(
+            shlesha.transliterate(" ", "devanagari", "iast").unwrap(),
+            " "
+        );
+
+        let profiler = shlesha.profiler.as_ref().unwrap();
+        std::thread::scope(|scope| {
+            let poisoner = scope.spawn(|| {
+                let _profiles = profiler.profiles.write().unwrap();
+                panic!("poison the profiles lock");
+            });
+            assert!(poisoner.join().is_err());
+        });
+        assert!(profiler.profiles.is_poisoned());
+
+        assert_eq!(
+            shlesha.transliterate("धर्म", "devanagari", "iast").unwrap(),
+            "dharma"
+        );
+        assert!(shlesha.get_profile_stats().is_some());
+        shlesha.save_profiles();
+    }
+
     #[test]
     fn test_sequence_extraction() {
         let profiler = Profiler::new();
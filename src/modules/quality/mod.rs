@@ -0,0 +1,67 @@
+//! Rejecting conversions that lost too much of their input
+//!
+//! A conversion's fidelity, [`TransliterationMetadata::fidelity`], is the
+//! share of the hub tokens read from its input that reached the output
+//! unchanged. [`require_fidelity`] turns a conversion below a threshold into a
+//! [`QualityError`] carrying what went wrong, so a pipeline can set such text
+//! aside for a person to look at instead of ingesting it.
+
+use crate::modules::core::unknown_handler::{LossyMapping, TransliterationResult, UnknownToken};
+use thiserror::Error;
+
+/// Unknown tokens, and lossy mappings, a [`QualityError`] keeps of each
+pub const MAX_REPORTED_ISSUES: usize = 100;
+
+#[derive(Error, Debug)]
+pub enum QualityError {
+    /// The text could not be converted, for example from an unknown script
+    #[error("{0}")]
+    Conversion(Box<dyn std::error::Error>),
+    /// The text converted with less fidelity than required
+    ///
+    /// `unknown_tokens` and `lossy_mappings` keep the first
+    /// `MAX_REPORTED_ISSUES` of each; the counts include the rest.
+    #[error(
+        "fidelity {fidelity:.4} is below {min_fidelity} \
+         ({unknown_count} unknown, {lossy_count} lossy)"
+    )]
+    BelowThreshold {
+        fidelity: f64,
+        min_fidelity: f64,
+        unknown_tokens: Vec<UnknownToken>,
+        unknown_count: usize,
+        lossy_mappings: Vec<LossyMapping>,
+        lossy_count: usize,
+        /// The output, as converted despite the losses
+        output: String,
+    },
+}
+
+/// The output of a conversion whose fidelity is at least `min_fidelity`
+///
+/// A result without metadata lost nothing that was recorded, and passes.
+pub fn require_fidelity(
+    result: TransliterationResult,
+    min_fidelity: f64,
+) -> Result<String, QualityError> {
+    let Some(mut metadata) = result.metadata else {
+        return Ok(result.output);
+    };
+    let fidelity = metadata.fidelity();
+    if fidelity >= min_fidelity {
+        return Ok(result.output);
+    }
+
+    let lossy_count = metadata.lossy_mappings.len();
+    metadata.unknown_tokens.truncate(MAX_REPORTED_ISSUES);
+    metadata.lossy_mappings.truncate(MAX_REPORTED_ISSUES);
+    Err(QualityError::BelowThreshold {
+        fidelity,
+        min_fidelity,
+        unknown_tokens: metadata.unknown_tokens,
+        unknown_count: metadata.total_unknown_count,
+        lossy_mappings: metadata.lossy_mappings,
+        lossy_count,
+        output: result.output,
+    })
+}
@@ -61,8 +61,9 @@ mappings:
         let result = registry.load_schema(file_path.to_str().unwrap());
 
         match result {
-            Err(RegistryError::ParseError(msg)) => {
-                assert!(msg.contains("Failed to parse YAML"));
+            Err(RegistryError::ParseError { origin, line, .. }) => {
+                assert_eq!(origin, file_path.display().to_string());
+                assert!(line.is_some());
             }
             _ => panic!("Expected ParseError for invalid YAML"),
         }
@@ -96,7 +97,7 @@ mappings:
         let result = registry.load_schema(file_path.to_str().unwrap());
 
         match result {
-            Err(RegistryError::ParseError(_)) => {
+            Err(RegistryError::ParseError { .. }) => {
                 // Expected - empty YAML should fail parsing
             }
             _ => panic!("Expected ParseError for empty file"),
@@ -132,7 +133,7 @@ mappings:
 
         // Test completely invalid YAML
         let result = registry.load_schema_from_string("this is not yaml at all!!!", "test");
-        assert!(matches!(result, Err(RegistryError::ParseError(_))));
+        assert!(matches!(result, Err(RegistryError::ParseError { .. })));
 
         // Test valid YAML but wrong structure
         let wrong_structure = r#"
@@ -199,16 +200,104 @@ mappings:
         let mut registry = SchemaRegistry::new();
         let result = registry.load_schemas_from_directory(temp_dir.path().to_str().unwrap());
 
-        // Should succeed and return count of successfully loaded schemas
+        // Should succeed, loading the valid schema and reporting the invalid one
         match result {
-            Ok(count) => {
-                assert_eq!(count, 1); // Only the valid schema should be loaded
+            Ok(report) => {
+                assert_eq!(report.loaded, 1); // Only the valid schema should be loaded
                 assert!(registry.get_schema("valid_test").is_some());
+                assert_eq!(report.failures.len(), 1);
+                assert!(report.failures[0].path.ends_with("invalid.yaml"));
             }
             Err(e) => panic!("Expected success with partial loading, got error: {}", e),
         }
     }
 
+    #[test]
+    fn test_parse_error_names_file_and_line() {
+        let temp_dir = create_temp_dir();
+        let broken_yaml = r#"metadata:
+  name: "broken"
+  script_type: "roman"
+  has_implicit_a: false
+mappings:
+  vowels:
+    a: "अ"
+  consonants: "should be a map"
+"#;
+        let file_path = create_temp_file(&temp_dir, "broken.yaml", broken_yaml);
+        let mut registry = SchemaRegistry::new();
+
+        let error = registry
+            .load_schema(file_path.to_str().unwrap())
+            .unwrap_err();
+        match &error {
+            RegistryError::ParseError {
+                origin,
+                line,
+                column,
+                message,
+                snippet,
+            } => {
+                assert_eq!(origin, &file_path.display().to_string());
+                assert_eq!(*line, Some(8));
+                assert_eq!(*column, Some(15));
+                assert!(message.starts_with("mappings.consonants: invalid type: string"));
+                assert_eq!(
+                    snippet.as_deref(),
+                    Some("  consonants: \"should be a map\"")
+                );
+            }
+            other => panic!("Expected ParseError, got {other:?}"),
+        }
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Parse error in {} at line 8, column 15: {}\n     8 |   consonants: \"should be a map\"",
+                file_path.display(),
+                "mappings.consonants: invalid type: string \"should be a map\", expected a map"
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_error_from_string_names_schema() {
+        let mut registry = SchemaRegistry::new();
+
+        let result = registry.load_schema_from_string("metadata: [unclosed", "my_script");
+        match result {
+            Err(RegistryError::ParseError { origin, line, .. }) => {
+                assert_eq!(origin, "my_script");
+                assert_eq!(line, Some(1));
+            }
+            other => panic!("Expected ParseError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_directory_failures_are_reported() {
+        let temp_dir = create_temp_dir();
+        let nested = temp_dir.path().join("nested");
+        fs::create_dir(&nested).unwrap();
+        fs::write(
+            nested.join("broken.yaml"),
+            "metadata:\n  name: \"broken\"\n  script_type: [roman\n",
+        )
+        .unwrap();
+
+        let mut registry = SchemaRegistry::new();
+        let report = registry
+            .load_schemas_from_directory(temp_dir.path().to_str().unwrap())
+            .unwrap();
+
+        assert_eq!(report.loaded, 0);
+        assert_eq!(report.failures.len(), 1);
+        let failure = &report.failures[0];
+        assert_eq!(failure.path, nested.join("broken.yaml"));
+        let described = failure.to_string();
+        assert!(described.contains(&nested.join("broken.yaml").display().to_string()));
+        assert!(described.contains(" at line "));
+    }
+
     #[test]
     fn test_register_duplicate_schema() {
         let mut registry = SchemaRegistry::new();
@@ -0,0 +1,114 @@
+//! Matchers for runtime-loaded schemas, built once when a schema is registered
+//!
+//! Reading a runtime schema needs the longest spelling at each position, and
+//! writing one needs the spelling of each token. Both are worked out from the
+//! schema's mappings at registration and shared by every conversion through
+//! the schema, as the generated converters share theirs.
+
+use super::{RegistryError, Schema};
+use crate::modules::hub::{AbugidaToken, AlphabetToken, HubToken};
+use aho_corasick::{AhoCorasick, Anchored, Input, MatchKind, StartKind};
+use rustc_hash::FxHashMap;
+use std::str::FromStr;
+
+/// The reading and writing tables of one schema
+#[derive(Debug, Clone)]
+pub struct SchemaMatcher {
+    /// The schema's spellings, matched longest first from a given position
+    reader: AhoCorasick,
+    /// The token each spelling of `reader` reads as, by pattern index
+    read_tokens: Vec<HubToken>,
+    /// The spelling each mapped token is written with
+    written: FxHashMap<HubToken, String>,
+}
+
+impl SchemaMatcher {
+    /// Build the matcher for `schema`
+    ///
+    /// A spelling mapped by two tokens reads as one of them, the one it is the
+    /// written spelling of if either. A token name that is not a hub token
+    /// reads as the spelling itself, as an unknown token.
+    pub fn new(schema: &Schema) -> Result<Self, RegistryError> {
+        let mut reverse: FxHashMap<&str, &str> = FxHashMap::default();
+        let alternates = schema
+            .alternates
+            .iter()
+            .flat_map(|(token_name, spellings)| {
+                spellings.iter().map(move |spelling| (token_name, spelling))
+            });
+        for (token_name, spelling) in alternates.chain(&schema.mappings) {
+            // A token written as nothing is not read: an empty pattern would
+            // match without advancing
+            if spelling.is_empty() {
+                continue;
+            }
+            reverse.insert(spelling, token_name);
+        }
+
+        let is_alphabet = schema.is_alphabet();
+        let (patterns, read_tokens): (Vec<&str>, Vec<HubToken>) = reverse
+            .into_iter()
+            .map(|(spelling, token_name)| {
+                let token = if is_alphabet {
+                    AlphabetToken::from_str(token_name)
+                        .map(HubToken::Alphabet)
+                        .unwrap_or_else(|_| {
+                            HubToken::Alphabet(AlphabetToken::Unknown(spelling.to_string()))
+                        })
+                } else {
+                    AbugidaToken::from_str(token_name)
+                        .map(HubToken::Abugida)
+                        .unwrap_or_else(|_| {
+                            HubToken::Abugida(AbugidaToken::Unknown(spelling.to_string()))
+                        })
+                };
+                (spelling, token)
+            })
+            .unzip();
+
+        let reader = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .start_kind(StartKind::Anchored)
+            .build(&patterns)
+            .map_err(|e| {
+                RegistryError::InvalidSchema(format!(
+                    "Cannot build the matcher for schema '{}': {e}",
+                    schema.name
+                ))
+            })?;
+
+        // Tokens are written by name, so a name serves tokens of either kind
+        let mut written = FxHashMap::default();
+        for (token_name, spelling) in &schema.mappings {
+            if let Ok(token) = AlphabetToken::from_str(token_name) {
+                if !matches!(token, AlphabetToken::Unknown(_)) {
+                    written.insert(HubToken::Alphabet(token), spelling.clone());
+                }
+            }
+            if let Ok(token) = AbugidaToken::from_str(token_name) {
+                if !matches!(token, AbugidaToken::Unknown(_)) {
+                    written.insert(HubToken::Abugida(token), spelling.clone());
+                }
+            }
+        }
+
+        Ok(Self {
+            reader,
+            read_tokens,
+            written,
+        })
+    }
+
+    /// The token read by the longest spelling starting at byte `pos`, and the
+    /// length of that spelling
+    pub fn read_at(&self, input: &str, pos: usize) -> Option<(&HubToken, usize)> {
+        self.reader
+            .find(Input::new(input).range(pos..).anchored(Anchored::Yes))
+            .map(|found| (&self.read_tokens[found.pattern().as_usize()], found.len()))
+    }
+
+    /// The spelling `token` is written with, if the schema maps it
+    pub fn written(&self, token: &HubToken) -> Option<&str> {
+        self.written.get(token).map(String::as_str)
+    }
+}
@@ -1,9 +1,19 @@
+use crate::modules::hub::{AbugidaToken, AlphabetToken, HubToken};
+use crate::modules::rules::{RuleDefinitions, SchemaRules};
+use crate::modules::script_converter::{DevanagariConverter, Iso15919Converter};
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use thiserror::Error;
 
+mod matcher;
+mod schema_tests;
+pub use matcher::SchemaMatcher;
+pub use schema_tests::{SchemaTestCase, SchemaTestFailure, SchemaTestReport};
+
 #[derive(Error, Debug, Clone)]
 pub enum RegistryError {
     #[error("Schema not found: {0}")]
@@ -16,8 +26,104 @@ pub enum RegistryError {
     RegistrationFailed(String),
     #[error("IO error: {0}")]
     IoError(String),
-    #[error("Parse error: {0}")]
-    ParseError(String),
+    #[error("Parse error in {origin}{}: {message}{}", at_line(.line, .column), quote_line(.line, .snippet))]
+    ParseError {
+        /// File path, or the schema name for schemas loaded from a string
+        origin: String,
+        /// Line of the error, counting from 1, where the parser reports one
+        line: Option<usize>,
+        /// Column of the error, counting from 1
+        column: Option<usize>,
+        message: String,
+        /// The offending line of the schema
+        snippet: Option<String>,
+    },
+}
+
+impl RegistryError {
+    /// A parse error of the schema `yaml` read from `origin`
+    fn parse(origin: &str, yaml: &str, error: &serde_yaml::Error) -> Self {
+        let location = error.location();
+        let mut message = error.to_string();
+        if let Some(location) = &location {
+            // serde_yaml ends most messages with the location, reported separately here
+            let suffix = format!(" at line {} column {}", location.line(), location.column());
+            if let Some(trimmed) = message.strip_suffix(&suffix) {
+                message = trimmed.to_string();
+            }
+        }
+        RegistryError::ParseError {
+            origin: origin.to_string(),
+            line: location.as_ref().map(|location| location.line()),
+            column: location.as_ref().map(|location| location.column()),
+            message,
+            snippet: location
+                .and_then(|location| yaml.lines().nth(location.line() - 1))
+                .map(|line| line.trim_end().to_string()),
+        }
+    }
+}
+
+impl RegistryError {
+    /// A parse error of the TOML schema `toml` read from `origin`
+    fn parse_toml(origin: &str, toml: &str, error: &toml::de::Error) -> Self {
+        // toml reports a byte span; lines and columns count from 1
+        let start = error.span().map(|span| span.start.min(toml.len()));
+        let line = start.map(|start| toml[..start].matches('\n').count() + 1);
+        let column = start.map(|start| {
+            let line_start = toml[..start].rfind('\n').map_or(0, |newline| newline + 1);
+            toml[line_start..start].chars().count() + 1
+        });
+        RegistryError::ParseError {
+            origin: origin.to_string(),
+            line,
+            column,
+            message: error.message().to_string(),
+            snippet: line
+                .and_then(|line| toml.lines().nth(line - 1))
+                .map(|line| line.trim_end().to_string()),
+        }
+    }
+}
+
+fn at_line(line: &Option<usize>, column: &Option<usize>) -> String {
+    match (line, column) {
+        (Some(line), Some(column)) => format!(" at line {line}, column {column}"),
+        _ => String::new(),
+    }
+}
+
+fn quote_line(line: &Option<usize>, snippet: &Option<String>) -> String {
+    match (line, snippet) {
+        (Some(line), Some(snippet)) => format!("\n  {line:>4} | {snippet}"),
+        _ => String::new(),
+    }
+}
+
+/// Outcome of loading a directory of schemas
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    /// Number of schemas loaded
+    pub loaded: usize,
+    /// Schema files that could not be loaded
+    pub failures: Vec<SchemaLoadFailure>,
+}
+
+/// A schema file that could not be loaded, and why
+#[derive(Debug, Clone)]
+pub struct SchemaLoadFailure {
+    pub path: PathBuf,
+    pub error: RegistryError,
+}
+
+impl fmt::Display for SchemaLoadFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.error {
+            // Parse errors name the file already
+            RegistryError::ParseError { .. } => write!(f, "{}", self.error),
+            error => write!(f, "{}: {error}", self.path.display()),
+        }
+    }
 }
 
 /// Statistics about the schema registry
@@ -37,6 +143,33 @@ pub struct RegistryStats {
     pub total_mappings: usize,
 }
 
+/// The direction a script's lines run in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextDirection {
+    /// Left to right, as most scripts are written
+    #[default]
+    Ltr,
+    /// Right to left, as Kharoshthi is written
+    Rtl,
+}
+
+impl TextDirection {
+    /// The name schemas use for the direction, "ltr" or "rtl"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TextDirection::Ltr => "ltr",
+            TextDirection::Rtl => "rtl",
+        }
+    }
+}
+
+impl fmt::Display for TextDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Represents metadata about a schema (unified format matching build system)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchemaMetadata {
@@ -45,6 +178,17 @@ pub struct SchemaMetadata {
     pub has_implicit_a: bool,
     pub description: Option<String>,
     pub aliases: Option<Vec<String>>,
+    /// The direction the script is written in, left to right unless given
+    #[serde(default)]
+    pub direction: TextDirection,
+    /// How the script is laid out beyond its direction, for rendering engines
+    #[serde(default)]
+    pub rendering_notes: Option<String>,
+    /// The schema's own version, as its author declares it
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
 }
 
 impl Default for SchemaMetadata {
@@ -55,6 +199,10 @@ impl Default for SchemaMetadata {
             has_implicit_a: false,
             description: None,
             aliases: None,
+            direction: TextDirection::Ltr,
+            rendering_notes: None,
+            version: None,
+            author: None,
         }
     }
 }
@@ -62,13 +210,43 @@ impl Default for SchemaMetadata {
 /// Script mappings structure (matches build system)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchemaMapping {
-    pub vowels: Option<FxHashMap<String, String>>,
-    pub consonants: Option<FxHashMap<String, String>>,
-    pub vowel_signs: Option<FxHashMap<String, String>>,
-    pub marks: Option<FxHashMap<String, String>>,
-    pub digits: Option<FxHashMap<String, String>>,
-    pub sanskrit_extensions: Option<FxHashMap<String, String>>,
-    pub special: Option<FxHashMap<String, String>>,
+    pub vowels: Option<FxHashMap<String, Spellings>>,
+    pub consonants: Option<FxHashMap<String, Spellings>>,
+    pub vowel_signs: Option<FxHashMap<String, Spellings>>,
+    pub marks: Option<FxHashMap<String, Spellings>>,
+    pub digits: Option<FxHashMap<String, Spellings>>,
+    pub sanskrit_extensions: Option<FxHashMap<String, Spellings>>,
+    pub special: Option<FxHashMap<String, Spellings>>,
+    pub vedic: Option<FxHashMap<String, Spellings>>,
+}
+
+/// The spelling of a token in a schema, or a list of them
+///
+/// Of a list, the first spelling is written and every one is read, as in the
+/// schemas the built-in converters are generated from: `VowelAa: ["ā", "aa"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Spellings {
+    One(String),
+    Several(Vec<String>),
+}
+
+impl Spellings {
+    /// The spelling the token is written with
+    pub fn written(&self) -> &str {
+        match self {
+            Spellings::One(spelling) => spelling,
+            Spellings::Several(spellings) => spellings.first().map_or("", String::as_str),
+        }
+    }
+
+    /// The spellings read as the token besides the written one
+    pub fn alternates(&self) -> &[String] {
+        match self {
+            Spellings::One(_) => &[],
+            Spellings::Several(spellings) => spellings.get(1..).unwrap_or_default(),
+        }
+    }
 }
 
 /// Code generation configuration (optional)
@@ -84,7 +262,107 @@ pub struct SchemaFile {
     pub metadata: SchemaMetadata,
     pub target: Option<String>, // "iso15919" for Roman, "devanagari" for Indic (default)
     pub mappings: SchemaMapping,
+    /// Spellings that depend on the neighbouring tokens; see [`crate::modules::rules`]
+    #[serde(default)]
+    pub rules: Option<RuleDefinitions>,
+    /// Text written and read for a whole token sequence: "x" -> [ConsonantK, MarkVirama, ConsonantSs]
+    #[serde(default)]
+    pub conjuncts: Option<FxHashMap<String, Vec<String>>>,
+    /// Text read as a token sequence but never written
+    #[serde(default)]
+    pub read_conjuncts: Option<FxHashMap<String, Vec<String>>>,
     pub codegen: Option<CodegenConfig>,
+    /// Cases checking the schema's own conversions; see [`SchemaRegistry::run_schema_tests`]
+    #[serde(default)]
+    pub tests: Option<Vec<SchemaTestCase>>,
+}
+
+/// A schema in the TOML layout of older tooling
+///
+/// The tables are those of the YAML format, under the names older tooling
+/// gives some of them: `vowel_marks` for `vowel_signs` and `special_marks` for
+/// `marks`. `punctuation` and `ligatures` tables, and mappings written
+/// directly under `[mappings]`, are read with `special`. Every table is
+/// flattened into one mapping when the schema is built, so only the mappings
+/// themselves matter.
+#[derive(Debug, Deserialize)]
+struct TomlSchemaFile {
+    metadata: SchemaMetadata,
+    target: Option<String>,
+    #[serde(default)]
+    mappings: TomlMappings,
+    rules: Option<RuleDefinitions>,
+    conjuncts: Option<FxHashMap<String, Vec<String>>>,
+    read_conjuncts: Option<FxHashMap<String, Vec<String>>>,
+    codegen: Option<CodegenConfig>,
+    tests: Option<Vec<SchemaTestCase>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TomlMappings {
+    vowels: Option<FxHashMap<String, Spellings>>,
+    consonants: Option<FxHashMap<String, Spellings>>,
+    #[serde(alias = "vowel_marks")]
+    vowel_signs: Option<FxHashMap<String, Spellings>>,
+    #[serde(alias = "special_marks")]
+    marks: Option<FxHashMap<String, Spellings>>,
+    digits: Option<FxHashMap<String, Spellings>>,
+    sanskrit_extensions: Option<FxHashMap<String, Spellings>>,
+    special: Option<FxHashMap<String, Spellings>>,
+    vedic: Option<FxHashMap<String, Spellings>>,
+    punctuation: Option<FxHashMap<String, Spellings>>,
+    ligatures: Option<FxHashMap<String, Spellings>>,
+    #[serde(flatten)]
+    flat: FxHashMap<String, Spellings>,
+}
+
+impl From<TomlSchemaFile> for SchemaFile {
+    fn from(toml: TomlSchemaFile) -> Self {
+        let TomlMappings {
+            vowels,
+            consonants,
+            vowel_signs,
+            marks,
+            digits,
+            sanskrit_extensions,
+            special,
+            vedic,
+            punctuation,
+            ligatures,
+            flat,
+        } = toml.mappings;
+
+        let special: FxHashMap<String, Spellings> = [special, punctuation, ligatures]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .chain(flat)
+            .collect();
+        SchemaFile {
+            metadata: toml.metadata,
+            target: toml.target,
+            mappings: SchemaMapping {
+                vowels,
+                consonants,
+                vowel_signs,
+                marks,
+                digits,
+                sanskrit_extensions,
+                special: (!special.is_empty()).then_some(special),
+                vedic,
+            },
+            rules: toml.rules,
+            conjuncts: toml.conjuncts,
+            read_conjuncts: toml.read_conjuncts,
+            codegen: toml.codegen,
+            tests: toml.tests,
+        }
+    }
+}
+
+/// Whether the schema file at `path` is TOML, by its extension; YAML otherwise
+fn is_toml_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "toml")
 }
 
 /// Represents a schema in the registry
@@ -93,8 +371,15 @@ pub struct Schema {
     pub name: String,
     pub script_type: String,
     pub target: String,
+    /// The spelling each token is written with
     pub mappings: FxHashMap<String, String>,
+    /// Further spellings read as a token, never written
+    pub alternates: FxHashMap<String, Vec<String>>,
     pub metadata: SchemaMetadata,
+    /// The schema's rules, applied before its mappings
+    pub rules: SchemaRules,
+    /// The cases of the schema's `tests` section, which conversion ignores
+    pub tests: Vec<SchemaTestCase>,
 }
 
 impl Schema {
@@ -108,74 +393,193 @@ impl Schema {
                 "devanagari".to_string()
             },
             mappings: FxHashMap::default(),
+            alternates: FxHashMap::default(),
             metadata: SchemaMetadata {
                 name,
+                has_implicit_a: script_type == "brahmic",
                 script_type,
-                has_implicit_a: false,
                 description: None,
                 aliases: None,
+                direction: TextDirection::Ltr,
+                rendering_notes: None,
+                version: None,
+                author: None,
             },
+            rules: SchemaRules::default(),
+            tests: Vec::new(),
         }
     }
 
+    /// Whether the schema reads and writes alphabet (Roman) tokens
+    ///
+    /// A token target says which; otherwise consonants without a vowel sign
+    /// are read as in the schema's `has_implicit_a`.
+    pub fn is_alphabet(&self) -> bool {
+        reads_alphabet_tokens(&self.target, self.metadata.has_implicit_a)
+    }
+
     /// Create a Schema from a loaded SchemaFile
     pub fn from_schema_file(schema_file: SchemaFile) -> Result<Self, RegistryError> {
         // Flatten the nested mappings structure
+        let mappings = &schema_file.mappings;
         let mut flattened_mappings = FxHashMap::default();
-
-        // Flatten vowels
-        if let Some(vowels) = &schema_file.mappings.vowels {
-            flattened_mappings.extend(vowels.clone());
-        }
-
-        // Flatten consonants
-        if let Some(consonants) = &schema_file.mappings.consonants {
-            flattened_mappings.extend(consonants.clone());
-        }
-
-        // Flatten vowel signs
-        if let Some(vowel_signs) = &schema_file.mappings.vowel_signs {
-            flattened_mappings.extend(vowel_signs.clone());
+        let mut alternates = FxHashMap::default();
+        for category in [
+            &mappings.vowels,
+            &mappings.consonants,
+            &mappings.vowel_signs,
+            &mappings.marks,
+            &mappings.digits,
+            &mappings.sanskrit_extensions,
+            &mappings.special,
+            &mappings.vedic,
+        ] {
+            for (token, spellings) in category.iter().flatten() {
+                flattened_mappings.insert(token.clone(), spellings.written().to_string());
+                if spellings.alternates().is_empty() {
+                    alternates.remove(token);
+                } else {
+                    alternates.insert(token.clone(), spellings.alternates().to_vec());
+                }
+            }
         }
 
-        // Flatten marks
-        if let Some(marks) = &schema_file.mappings.marks {
-            flattened_mappings.extend(marks.clone());
-        }
+        let has_implicit_a = schema_file.metadata.has_implicit_a;
+        let mut target = schema_file.target.unwrap_or_else(|| {
+            if has_implicit_a {
+                "devanagari".to_string()
+            } else {
+                "iso15919".to_string()
+            }
+        });
 
-        // Flatten digits
-        if let Some(digits) = &schema_file.mappings.digits {
-            flattened_mappings.extend(digits.clone());
+        // Older schemas map characters to Devanagari or ISO-15919 text rather than
+        // token names to characters
+        if matches!(target.as_str(), "devanagari" | "iso15919" | "iso")
+            && !flattened_mappings.keys().all(|key| is_token_name(key))
+        {
+            let is_alphabet = !has_implicit_a;
+            flattened_mappings =
+                legacy_to_token_mappings(&flattened_mappings, &target, is_alphabet)?;
+            alternates.clear();
+            target = if is_alphabet {
+                "alphabet_tokens".to_string()
+            } else {
+                "abugida_tokens".to_string()
+            };
         }
 
-        // Flatten sanskrit extensions
-        if let Some(sanskrit_extensions) = &schema_file.mappings.sanskrit_extensions {
-            flattened_mappings.extend(sanskrit_extensions.clone());
-        }
+        let is_alphabet = reads_alphabet_tokens(&target, has_implicit_a);
+        let rules = schema_file
+            .rules
+            .clone()
+            .unwrap_or_default()
+            .with_conjuncts(
+                schema_file.conjuncts.iter().flatten(),
+                schema_file.read_conjuncts.iter().flatten(),
+            )
+            .and_then(|rules| SchemaRules::parse(&rules, is_alphabet))
+            .map_err(|e| {
+                RegistryError::InvalidSchema(format!("{}: {e}", schema_file.metadata.name))
+            })?;
 
-        // Flatten special characters
-        if let Some(special) = &schema_file.mappings.special {
-            flattened_mappings.extend(special.clone());
+        let tests = schema_file.tests.unwrap_or_default();
+        if let Some(case) = tests
+            .iter()
+            .find(|case| case.expectations().next().is_none())
+        {
+            return Err(RegistryError::InvalidSchema(format!(
+                "{}: test case {:?} has neither expected_devanagari nor expected_iso",
+                schema_file.metadata.name, case.input
+            )));
         }
 
-        let target = schema_file.target.unwrap_or_else(|| {
-            if schema_file.metadata.script_type == "roman" {
-                "iso15919".to_string()
-            } else {
-                "devanagari".to_string()
-            }
-        });
-
         Ok(Self {
             name: schema_file.metadata.name.clone(),
             script_type: schema_file.metadata.script_type.clone(),
             target,
             mappings: flattened_mappings,
+            alternates,
             metadata: schema_file.metadata,
+            rules,
+            tests,
         })
     }
 }
 
+/// Whether a schema with `target` reads alphabet tokens: as the target says
+/// for token targets, and as `has_implicit_a` says otherwise
+fn reads_alphabet_tokens(target: &str, has_implicit_a: bool) -> bool {
+    match target {
+        "alphabet_tokens" => true,
+        "abugida_tokens" => false,
+        _ => !has_implicit_a,
+    }
+}
+
+fn is_token_name(name: &str) -> bool {
+    name.parse::<AbugidaToken>().is_ok() || name.parse::<AlphabetToken>().is_ok()
+}
+
+/// Translate legacy `source: reference` mappings into `token name: source` mappings
+///
+/// Each reference text must be exactly one Devanagari or ISO-15919 token. The
+/// declared reference script is tried first, then the other, since Roman schemas
+/// often map to Devanagari without saying so. When several sources stand for the
+/// same token, the first in sorted order is kept.
+fn legacy_to_token_mappings(
+    mappings: &FxHashMap<String, String>,
+    reference: &str,
+    is_alphabet: bool,
+) -> Result<FxHashMap<String, String>, RegistryError> {
+    let mut legacy: Vec<(&String, &String)> = mappings.iter().collect();
+    legacy.sort();
+
+    let mut translated = FxHashMap::default();
+    for (source, reference_text) in legacy {
+        if source.is_empty() {
+            return Err(RegistryError::InvalidSchema(format!(
+                "Legacy mapping \"\": \"{reference_text}\" has no source text to read"
+            )));
+        }
+        let devanagari = || {
+            DevanagariConverter::new()
+                .string_to_token(reference_text)
+                .map(HubToken::Abugida)
+        };
+        let iso = || {
+            Iso15919Converter::new()
+                .string_to_token(reference_text)
+                .map(HubToken::Alphabet)
+        };
+        let token = if reference == "devanagari" {
+            devanagari().or_else(iso)
+        } else {
+            iso().or_else(devanagari)
+        };
+        // Roman schemes read alphabet tokens and Brahmic scripts abugida tokens
+        let name = match (token, is_alphabet) {
+            (Some(HubToken::Abugida(token)), true) => token.to_alphabet().map(|t| t.to_string()),
+            (Some(HubToken::Alphabet(token)), false) => token.to_abugida().map(|t| t.to_string()),
+            (Some(token), _) => Some(match token {
+                HubToken::Abugida(token) => token.to_string(),
+                HubToken::Alphabet(token) => token.to_string(),
+            }),
+            (None, _) => None,
+        };
+        let Some(name) = name else {
+            return Err(RegistryError::InvalidSchema(format!(
+                "Legacy mapping \"{source}\": \"{reference_text}\" is not a single Devanagari or ISO-15919 character. \
+                 Use the token format instead: set `target: {}` and map token names to characters, \
+                 e.g. `ConsonantK: \"{source}\"`",
+                if is_alphabet { "alphabet_tokens" } else { "abugida_tokens" }
+            )));
+        };
+        translated.entry(name).or_insert_with(|| source.clone());
+    }
+    Ok(translated)
+}
+
 pub trait SchemaRegistryTrait {
     fn get_schema(&self, script_name: &str) -> Option<&Schema>;
     fn register_schema(&mut self, name: String, schema: Schema) -> Result<(), RegistryError>;
@@ -209,6 +613,10 @@ pub trait SchemaRegistryTrait {
 pub struct SchemaRegistry {
     schemas: FxHashMap<String, Schema>,
     schema_cache: FxHashMap<String, SchemaFile>,
+    /// Times each schema has been registered under its name, counting from 1
+    revisions: FxHashMap<String, u64>,
+    /// The matcher of each schema, built when it is registered
+    matchers: FxHashMap<String, Arc<SchemaMatcher>>,
 }
 
 impl SchemaRegistry {
@@ -216,6 +624,8 @@ impl SchemaRegistry {
         let mut registry = Self {
             schemas: FxHashMap::default(),
             schema_cache: FxHashMap::default(),
+            revisions: FxHashMap::default(),
+            matchers: FxHashMap::default(),
         };
 
         // Register built-in schemas
@@ -233,15 +643,19 @@ impl SchemaRegistry {
         let _ = self.register_schema("iso15919".to_string(), iso_schema);
     }
 
-    /// Load a schema from a YAML file
+    /// Load a schema from a YAML file, or a TOML file if its extension is .toml
     fn load_schema_from_file(&mut self, path: &Path) -> Result<Schema, RegistryError> {
         // Read the file
         let contents = fs::read_to_string(path)
             .map_err(|e| RegistryError::IoError(format!("Failed to read file: {e}")))?;
 
-        // Parse YAML
-        let schema_file: SchemaFile = serde_yaml::from_str(&contents)
-            .map_err(|e| RegistryError::ParseError(format!("Failed to parse YAML: {e}")))?;
+        let origin = path.display().to_string();
+        let schema_file = if is_toml_file(path) {
+            Self::parse_toml_schema_file(&contents, &origin)?
+        } else {
+            serde_yaml::from_str(&contents)
+                .map_err(|e| RegistryError::parse(&origin, &contents, &e))?
+        };
 
         // Cache the schema file
         self.schema_cache
@@ -251,8 +665,29 @@ impl SchemaRegistry {
         Schema::from_schema_file(schema_file)
     }
 
-    /// Load all schemas from a directory
-    pub fn load_schemas_from_directory(&mut self, dir_path: &str) -> Result<usize, RegistryError> {
+    /// Load a schema file, returning the name it is registered under
+    pub fn load_schema_file(&mut self, path: &Path) -> Result<String, RegistryError> {
+        if !path.exists() {
+            return Err(RegistryError::LoadFailed(format!(
+                "Schema file not found: {}",
+                path.display()
+            )));
+        }
+
+        let schema = self.load_schema_from_file(path)?;
+        let name = schema.name.clone();
+        self.register_schema(name.clone(), schema)?;
+        Ok(name)
+    }
+
+    /// Load all schemas from a directory and its subdirectories
+    ///
+    /// A file that fails to load does not stop the others from loading; it is
+    /// listed in the report's failures with its error.
+    pub fn load_schemas_from_directory(
+        &mut self,
+        dir_path: &str,
+    ) -> Result<LoadReport, RegistryError> {
         let dir = Path::new(dir_path);
 
         if !dir.is_dir() {
@@ -261,7 +696,7 @@ impl SchemaRegistry {
             )));
         }
 
-        let mut loaded_count = 0;
+        let mut report = LoadReport::default();
 
         // Walk through directory recursively
         for entry in fs::read_dir(dir)
@@ -273,28 +708,27 @@ impl SchemaRegistry {
             let path = entry.path();
 
             if path.is_file() {
-                // Check if it's a YAML file
+                // Check if it's a YAML or TOML file
                 if let Some(ext) = path.extension() {
-                    if ext == "yaml" || ext == "yml" {
-                        // Try to load the schema
+                    if ext == "yaml" || ext == "yml" || ext == "toml" {
                         match self.load_schema(path.to_str().unwrap_or("")) {
-                            Ok(_) => loaded_count += 1,
-                            Err(e) => {
-                                // Log error but continue loading other schemas
-                                eprintln!("Warning: Failed to load schema from {path:?}: {e}");
-                            }
+                            Ok(_) => report.loaded += 1,
+                            Err(error) => report.failures.push(SchemaLoadFailure { path, error }),
                         }
                     }
                 }
             } else if path.is_dir() {
-                // Recursively load from subdirectories
-                if let Ok(count) = self.load_schemas_from_directory(path.to_str().unwrap_or("")) {
-                    loaded_count += count;
+                match self.load_schemas_from_directory(path.to_str().unwrap_or("")) {
+                    Ok(subdirectory) => {
+                        report.loaded += subdirectory.loaded;
+                        report.failures.extend(subdirectory.failures);
+                    }
+                    Err(error) => report.failures.push(SchemaLoadFailure { path, error }),
                 }
             }
         }
 
-        Ok(loaded_count)
+        Ok(report)
     }
 
     /// Get schemas by script type
@@ -313,16 +747,125 @@ impl SchemaRegistry {
             .collect()
     }
 
+    /// The name the schema a name or alias refers to is registered under
+    ///
+    /// A schema's own name comes before any alias. An alias several schemas
+    /// declare refers to the first of them in name order, so the answer does
+    /// not depend on the order they were loaded in.
+    pub fn resolve_name(&self, script_name: &str) -> Option<&str> {
+        match self.schemas.get_key_value(script_name) {
+            Some((name, _)) => Some(name),
+            None => self.aliased_name(script_name),
+        }
+    }
+
+    /// The name of the first schema, in name order, that declares `alias`
+    fn aliased_name(&self, alias: &str) -> Option<&str> {
+        self.schemas
+            .iter()
+            .filter(|(_, schema)| {
+                schema
+                    .metadata
+                    .aliases
+                    .as_ref()
+                    .is_some_and(|aliases| aliases.iter().any(|a| a == alias))
+            })
+            .map(|(name, _)| name.as_str())
+            .min()
+    }
+
+    /// The schema a name or alias refers to, with the matcher built for it
+    pub fn get_schema_with_matcher(&self, script_name: &str) -> Option<(&Schema, &SchemaMatcher)> {
+        let name = self.resolve_name(script_name)?;
+        Some((self.schemas.get(name)?, self.matchers.get(name)?))
+    }
+
     /// Find schemas by alias
     pub fn find_schema_by_alias(&self, alias: &str) -> Option<&Schema> {
-        self.schemas.values().find(|schema| {
-            schema
-                .metadata
-                .aliases
-                .as_ref()
-                .map(|aliases| aliases.contains(&alias.to_string()))
-                .unwrap_or(false)
-        })
+        self.aliased_name(alias)
+            .and_then(|name| self.schemas.get(name))
+    }
+
+    /// Parse a schema from YAML content, named `schema_name` unless that is empty
+    pub fn parse_schema(yaml_content: &str, schema_name: &str) -> Result<Schema, RegistryError> {
+        let origin = if schema_name.is_empty() {
+            "<string>"
+        } else {
+            schema_name
+        };
+        let schema_file: SchemaFile = serde_yaml::from_str(yaml_content)
+            .map_err(|e| RegistryError::parse(origin, yaml_content, &e))?;
+
+        let mut schema = Schema::from_schema_file(schema_file)?;
+        if !schema_name.is_empty() {
+            schema.name = schema_name.to_string();
+        }
+        Ok(schema)
+    }
+
+    /// Parse a schema from TOML content, named `schema_name` unless that is empty
+    ///
+    /// See [`SchemaRegistry::load_schema_from_toml_string`] for the layout.
+    pub fn parse_toml_schema(
+        toml_content: &str,
+        schema_name: &str,
+    ) -> Result<Schema, RegistryError> {
+        let origin = if schema_name.is_empty() {
+            "<string>"
+        } else {
+            schema_name
+        };
+        let mut schema =
+            Schema::from_schema_file(Self::parse_toml_schema_file(toml_content, origin)?)?;
+        if !schema_name.is_empty() {
+            schema.name = schema_name.to_string();
+        }
+        Ok(schema)
+    }
+
+    fn parse_toml_schema_file(
+        toml_content: &str,
+        origin: &str,
+    ) -> Result<SchemaFile, RegistryError> {
+        toml::from_str::<TomlSchemaFile>(toml_content)
+            .map(SchemaFile::from)
+            .map_err(|e| RegistryError::parse_toml(origin, toml_content, &e))
+    }
+
+    /// Load a schema from TOML content
+    ///
+    /// TOML schemas have the tables of YAML ones (`[metadata]`, `target`,
+    /// `[mappings.vowels]` and so on). The section names of older tooling are
+    /// read too: `vowel_marks` and `special_marks` for `vowel_signs` and
+    /// `marks`, `punctuation` and `ligatures` tables, and mappings written
+    /// directly under `[mappings]`.
+    pub fn load_schema_from_toml_string(
+        &mut self,
+        toml_content: &str,
+        schema_name: &str,
+    ) -> Result<(), RegistryError> {
+        let schema = Self::parse_toml_schema(toml_content, schema_name)?;
+        let name = schema.name.clone();
+        self.register_schema(name, schema)
+    }
+
+    /// Replace the schema registered under `schema.name`, returning its new revision
+    ///
+    /// Unlike `register_schema`, the name must already be registered. An
+    /// invalid schema leaves the old one in place.
+    pub fn replace_schema(&mut self, schema: Schema) -> Result<u64, RegistryError> {
+        if !self.schemas.contains_key(&schema.name) {
+            return Err(RegistryError::SchemaNotFound(schema.name));
+        }
+        let name = schema.name.clone();
+        self.register_schema(name.clone(), schema)?;
+        Ok(self.revisions[&name])
+    }
+
+    /// How many times the schema named `script_name` has been registered, counting from 1
+    pub fn schema_revision(&self, script_name: &str) -> Option<u64> {
+        let schema = self.get_schema(script_name)?;
+        self.revisions.get(&schema.name).copied()
     }
 
     /// Check if registry is empty (only built-in schemas)
@@ -368,7 +911,10 @@ impl SchemaRegistryTrait for SchemaRegistry {
     fn register_schema(&mut self, name: String, schema: Schema) -> Result<(), RegistryError> {
         // Validate the schema before registration
         self.validate_schema(&schema)?;
+        let matcher = SchemaMatcher::new(&schema)?;
 
+        *self.revisions.entry(name.clone()).or_default() += 1;
+        self.matchers.insert(name.clone(), Arc::new(matcher));
         self.schemas.insert(name, schema);
         Ok(())
     }
@@ -379,18 +925,7 @@ impl SchemaRegistryTrait for SchemaRegistry {
     }
 
     fn load_schema(&mut self, schema_path: &str) -> Result<(), RegistryError> {
-        let path = Path::new(schema_path);
-
-        if !path.exists() {
-            return Err(RegistryError::LoadFailed(format!(
-                "Schema file not found: {schema_path}"
-            )));
-        }
-
-        let schema = self.load_schema_from_file(path)?;
-        let name = schema.name.clone();
-
-        self.register_schema(name, schema)
+        self.load_schema_file(Path::new(schema_path)).map(drop)
     }
 
     fn list_schemas(&self) -> Vec<&str> {
@@ -420,6 +955,22 @@ impl SchemaRegistryTrait for SchemaRegistry {
             ));
         }
 
+        // A token may be written as nothing, but a spelling only read must not be
+        // empty: it would match without consuming any input
+        let mut empty: Vec<&str> = schema
+            .alternates
+            .iter()
+            .filter(|(_, values)| values.iter().any(String::is_empty))
+            .map(|(token, _)| token.as_str())
+            .collect();
+        if !empty.is_empty() {
+            empty.sort();
+            return Err(RegistryError::InvalidSchema(format!(
+                "Empty spelling read for token(s): {}; only the written (first) spelling may be empty",
+                empty.join(", ")
+            )));
+        }
+
         Ok(())
     }
 
@@ -428,17 +979,7 @@ impl SchemaRegistryTrait for SchemaRegistry {
         yaml_content: &str,
         schema_name: &str,
     ) -> Result<(), RegistryError> {
-        // Parse YAML content
-        let schema_file: SchemaFile = serde_yaml::from_str(yaml_content)
-            .map_err(|e| RegistryError::ParseError(format!("Failed to parse YAML: {e}")))?;
-
-        // Create schema from parsed content
-        let mut schema = Schema::from_schema_file(schema_file)?;
-
-        // Override name if provided
-        if !schema_name.is_empty() {
-            schema.name = schema_name.to_string();
-        }
+        let schema = Self::parse_schema(yaml_content, schema_name)?;
 
         // Register the schema
         let name = schema.name.clone();
@@ -452,12 +993,16 @@ impl SchemaRegistryTrait for SchemaRegistry {
     }
 
     fn remove_schema(&mut self, script_name: &str) -> bool {
+        self.revisions.remove(script_name);
+        self.matchers.remove(script_name);
         self.schemas.remove(script_name).is_some()
     }
 
     fn clear(&mut self) {
         self.schemas.clear();
         self.schema_cache.clear();
+        self.revisions.clear();
+        self.matchers.clear();
     }
 
     fn schema_count(&self) -> usize {
@@ -543,13 +1088,20 @@ mod tests {
             script_type: "roman".to_string(),
             target: "iso15919".to_string(),
             mappings: FxHashMap::default(),
+            alternates: FxHashMap::default(),
             metadata: SchemaMetadata {
                 name: "test".to_string(),
                 script_type: "roman".to_string(),
                 has_implicit_a: false,
                 description: None,
                 aliases: None,
+                direction: TextDirection::Ltr,
+                rendering_notes: None,
+                version: None,
+                author: None,
             },
+            rules: SchemaRules::default(),
+            tests: Vec::new(),
         };
 
         assert!(registry
@@ -568,7 +1120,10 @@ mod tests {
             script_type: "roman".to_string(),
             target: "iso15919".to_string(),
             mappings: FxHashMap::default(),
+            alternates: FxHashMap::default(),
             metadata: SchemaMetadata::default(),
+            rules: SchemaRules::default(),
+            tests: Vec::new(),
         };
 
         assert!(registry.validate_schema(&invalid_schema).is_err());
@@ -579,7 +1134,10 @@ mod tests {
             script_type: "invalid".to_string(),
             target: "iso15919".to_string(),
             mappings: FxHashMap::default(),
+            alternates: FxHashMap::default(),
             metadata: SchemaMetadata::default(),
+            rules: SchemaRules::default(),
+            tests: Vec::new(),
         };
 
         assert!(registry
@@ -604,7 +1162,9 @@ mod tests {
             let schema = schema.unwrap();
             assert_eq!(schema.name, "sample");
             assert_eq!(schema.script_type, "roman");
-            assert_eq!(schema.target, "devanagari");
+            // Legacy character mappings are translated to alphabet token mappings
+            assert_eq!(schema.target, "alphabet_tokens");
+            assert_eq!(schema.mappings.get("VowelAa"), Some(&"A".to_string()));
             assert!(!schema.metadata.has_implicit_a);
         }
     }
@@ -643,8 +1203,8 @@ mod tests {
             let result = registry.load_schemas_from_directory(test_dir);
             assert!(result.is_ok());
 
-            let count = result.unwrap();
-            assert!(count > 0); // Should have loaded at least one schema
+            let report = result.unwrap();
+            assert!(report.loaded > 0); // Should have loaded at least one schema
 
             // Verify the sample schema was loaded
             assert!(registry.get_schema("sample").is_some());
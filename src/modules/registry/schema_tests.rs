@@ -0,0 +1,170 @@
+//! Test cases shipped in a schema's `tests` section
+//!
+//! A schema can list inputs in its own script with what they read as in
+//! Devanagari, ISO 15919 or both:
+//!
+//! ```yaml
+//! tests:
+//!   - input: "kṛṣṇa"
+//!     expected_devanagari: "कृष्ण"
+//!   - input: "రామ"
+//!     expected_iso: "rāma"
+//! ```
+//!
+//! Conversion ignores the section. [`SchemaRegistry::run_schema_tests`] and
+//! `shlesha schema test <file>` run the cases through the normal pipeline.
+
+use super::{RegistryError, Schema, SchemaRegistry, SchemaRegistryTrait};
+use crate::modules::compare::{compare, DiffEdit};
+use crate::Shlesha;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One input in a schema's script and what it must convert to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaTestCase {
+    pub input: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_devanagari: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_iso: Option<String>,
+}
+
+impl SchemaTestCase {
+    /// The scripts the case converts to, with the output expected in each
+    pub fn expectations(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [
+            ("devanagari", &self.expected_devanagari),
+            ("iso15919", &self.expected_iso),
+        ]
+        .into_iter()
+        .filter_map(|(script, expected)| Some((script, expected.as_deref()?)))
+    }
+}
+
+/// A case whose output was not the one expected
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaTestFailure {
+    pub schema: String,
+    /// Index of the case in the schema's `tests`
+    pub case_index: usize,
+    /// The script the case converted to
+    pub to: String,
+    pub input: String,
+    pub expected: String,
+    pub actual: String,
+    pub reason: String,
+    /// Where `actual` reads as other tokens than `expected`
+    pub differences: Vec<DiffEdit>,
+}
+
+impl fmt::Display for SchemaTestFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} case {} ({} → {}): {}",
+            self.schema, self.case_index, self.schema, self.to, self.reason
+        )?;
+        writeln!(f, "  input:    {}", self.input)?;
+        writeln!(f, "  expected: {}", self.expected)?;
+        write!(f, "  actual:   {}", self.actual)?;
+        for difference in &self.differences {
+            write!(f, "\n  {difference}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of running a schema's test cases
+#[derive(Debug, Clone, Default)]
+pub struct SchemaTestReport {
+    pub schema: String,
+    /// Conversions checked; a case with both expectations counts twice
+    pub checks: usize,
+    pub failures: Vec<SchemaTestFailure>,
+}
+
+impl SchemaTestReport {
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// One-line summary, e.g. "iast: 6 checks, 0 failures"
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: {} checks, {} failures",
+            self.schema,
+            self.checks,
+            self.failures.len()
+        )
+    }
+}
+
+/// What the schema under test is registered as, so that no compiled-in script
+/// of the same name converts in its place
+const UNDER_TEST_SUFFIX: &str = " (under test)";
+
+impl SchemaRegistry {
+    /// Run the test cases of the schema named `script_name`
+    ///
+    /// The schema is loaded alone into a fresh transliterator, under a name of
+    /// its own, so its mappings are tested even when a compiled-in converter
+    /// has its name. Each case is converted from the schema to Devanagari or
+    /// ISO 15919, as its expectations say.
+    pub fn run_schema_tests(&self, script_name: &str) -> Result<SchemaTestReport, RegistryError> {
+        let schema = self
+            .get_schema(script_name)
+            .ok_or_else(|| RegistryError::SchemaNotFound(script_name.to_string()))?;
+
+        let under_test = format!("{}{UNDER_TEST_SUFFIX}", schema.name);
+        let mut isolated = Schema {
+            name: under_test.clone(),
+            ..schema.clone()
+        };
+        isolated.metadata.name = under_test.clone();
+        isolated.metadata.aliases = None;
+        let mut shlesha = Shlesha::new();
+        shlesha
+            .schemas_mut()
+            .register_schema(under_test.clone(), isolated)?;
+
+        let mut report = SchemaTestReport {
+            schema: schema.name.clone(),
+            ..Default::default()
+        };
+        for (index, case) in schema.tests.iter().enumerate() {
+            for (to, expected) in case.expectations() {
+                report.checks += 1;
+                let failure = |actual: String, reason: String, differences| SchemaTestFailure {
+                    schema: schema.name.clone(),
+                    case_index: index,
+                    to: to.to_string(),
+                    input: case.input.clone(),
+                    expected: expected.to_string(),
+                    actual,
+                    reason,
+                    differences,
+                };
+                match shlesha.transliterate(&case.input, &under_test, to) {
+                    Ok(output) if output == expected => {}
+                    Ok(output) => {
+                        let differences = compare(expected, &output, to)
+                            .map(|diff| diff.differences().cloned().collect())
+                            .unwrap_or_default();
+                        report.failures.push(failure(
+                            output,
+                            "output mismatch".to_string(),
+                            differences,
+                        ));
+                    }
+                    Err(e) => report.failures.push(failure(
+                        String::new(),
+                        format!("conversion failed: {e}"),
+                        Vec::new(),
+                    )),
+                }
+            }
+        }
+        Ok(report)
+    }
+}
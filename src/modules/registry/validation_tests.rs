@@ -40,6 +40,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_schema_validation_empty_spellings() {
+        let registry = SchemaRegistry::new();
+
+        // Written as nothing
+        let mut schema = Schema::new("test_silent".to_string(), "roman".to_string());
+        schema
+            .mappings
+            .insert("MarkVirama".to_string(), String::new());
+        assert!(registry.validate_schema(&schema).is_ok());
+
+        // Read from nothing
+        schema
+            .alternates
+            .insert("MarkVirama".to_string(), vec![String::new()]);
+        let error = registry.validate_schema(&schema).unwrap_err();
+        assert!(error.to_string().contains("MarkVirama"), "{error}");
+    }
+
     #[test]
     fn test_schema_validation_empty_name() {
         let registry = SchemaRegistry::new();
@@ -104,8 +123,8 @@ mappings:
             Ok(_) => {
                 // If it succeeds, check that one of the values was used
                 let schema = registry.get_schema("conflicting_test").unwrap();
-                assert!(schema.mappings.contains_key("a"));
-                assert!(schema.mappings.contains_key("k"));
+                assert!(schema.mappings.values().any(|source| source == "a"));
+                assert!(schema.mappings.values().any(|source| source == "k"));
             }
             Err(_) => {
                 // Failing on conflicting mappings is also acceptable
@@ -141,9 +160,10 @@ mappings:
         // Should load successfully - circular mappings aren't necessarily invalid
         assert!(result.is_ok(), "Circular mappings should be allowed");
 
+        // Read as the legacy format: source characters for ISO-15919 letters
         let schema = registry.get_schema("circular_test").unwrap();
-        assert_eq!(schema.mappings.get("a"), Some(&"b".to_string()));
-        assert_eq!(schema.mappings.get("k"), Some(&"g".to_string()));
+        assert_eq!(schema.mappings.get("ConsonantB"), Some(&"a".to_string()));
+        assert_eq!(schema.mappings.get("ConsonantK"), Some(&"g".to_string()));
     }
 
     #[test]
@@ -249,7 +269,7 @@ mappings:
     "1": "१"
     "2": "२"
   vowels:
-    "123": "invalid_vowel"  # Numeric key in wrong section
+    "123": "अ"  # Numeric key in wrong section
 "#;
 
         let file_path = create_temp_file(&temp_dir, "numeric.yaml", numeric_schema);
@@ -261,9 +281,9 @@ mappings:
         assert!(result.is_ok(), "Numeric keys should be handled");
 
         let schema = registry.get_schema("numeric_test").unwrap();
-        assert!(schema.mappings.contains_key("0"));
-        assert!(schema.mappings.contains_key("1"));
-        assert!(schema.mappings.contains_key("123"));
+        assert_eq!(schema.mappings.get("Digit0"), Some(&"0".to_string()));
+        assert_eq!(schema.mappings.get("Digit1"), Some(&"1".to_string()));
+        assert_eq!(schema.mappings.get("VowelA"), Some(&"123".to_string()));
     }
 
     #[test]
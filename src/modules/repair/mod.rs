@@ -0,0 +1,202 @@
+//! Repair of malformed vowel sequences in Indic input
+//!
+//! OCR of Indic text often yields sequences no script allows: a vowel sign
+//! after an independent vowel (अा for आ) or two vowel signs in a row (कोे).
+//! Converted as written they come out wrong without a warning. Before an Indic
+//! source is read, an independent vowel and sign the Unicode Standard lists as
+//! the "do not use" spelling of a vowel letter are read as that letter, and any
+//! other vowel sign with no consonant to attach to is dropped, keeping the vowel
+//! or sign before it. Each repair is reported as a `RepairedSequence` span.
+//!
+//! Only the scripts sharing the ISCII layout (Devanagari to Malayalam) are
+//! repaired. Two signs that compose canonically, like Bengali ে + া, are a
+//! decomposed vowel sign and left as written.
+
+use crate::modules::core::unknown_handler::{
+    NormalizationKind, NormalizedSpan, TransliterationMetadata, UnknownStage,
+};
+
+/// Independent vowel, vowel sign, and the vowel letter the pair is a spelling of
+const VOWEL_LETTER_SPELLINGS: [(char, char, char); 29] = [
+    // Devanagari
+    ('\u{0905}', '\u{093E}', '\u{0906}'),
+    ('\u{0905}', '\u{0945}', '\u{0972}'),
+    ('\u{0905}', '\u{0946}', '\u{0904}'),
+    ('\u{0905}', '\u{0949}', '\u{0911}'),
+    ('\u{0905}', '\u{094A}', '\u{0912}'),
+    ('\u{0905}', '\u{094B}', '\u{0913}'),
+    ('\u{0905}', '\u{094C}', '\u{0914}'),
+    ('\u{0906}', '\u{0947}', '\u{0913}'),
+    ('\u{0906}', '\u{0948}', '\u{0914}'),
+    ('\u{090F}', '\u{0945}', '\u{090D}'),
+    ('\u{090F}', '\u{0946}', '\u{090E}'),
+    ('\u{090F}', '\u{0947}', '\u{0910}'),
+    // Bengali
+    ('\u{0985}', '\u{09BE}', '\u{0986}'),
+    ('\u{098B}', '\u{09C3}', '\u{09E0}'),
+    ('\u{098C}', '\u{09E2}', '\u{09E1}'),
+    // Gurmukhi
+    ('\u{0A05}', '\u{0A3E}', '\u{0A06}'),
+    ('\u{0A05}', '\u{0A48}', '\u{0A10}'),
+    ('\u{0A05}', '\u{0A4C}', '\u{0A14}'),
+    ('\u{0A72}', '\u{0A3F}', '\u{0A07}'),
+    ('\u{0A72}', '\u{0A40}', '\u{0A08}'),
+    ('\u{0A72}', '\u{0A47}', '\u{0A0F}'),
+    ('\u{0A73}', '\u{0A41}', '\u{0A09}'),
+    ('\u{0A73}', '\u{0A42}', '\u{0A0A}'),
+    ('\u{0A73}', '\u{0A4B}', '\u{0A13}'),
+    // Gujarati and Oriya
+    ('\u{0A85}', '\u{0ABE}', '\u{0A86}'),
+    ('\u{0B05}', '\u{0B3E}', '\u{0B06}'),
+    // Malayalam
+    ('\u{0D05}', '\u{0D3E}', '\u{0D06}'),
+    ('\u{0D0E}', '\u{0D46}', '\u{0D10}'),
+    ('\u{0D12}', '\u{0D3E}', '\u{0D13}'),
+];
+
+/// Text with its malformed vowel sequences repaired, and the repairs made
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repaired {
+    pub text: String,
+    pub spans: Vec<NormalizedSpan>,
+}
+
+impl Repaired {
+    /// Byte offset in the original text of `position` in the repaired text
+    ///
+    /// A position within a repaired span maps to the start of the span.
+    pub fn original_position(&self, position: usize) -> usize {
+        let mut shift = 0isize;
+        for span in &self.spans {
+            let start = span.position.saturating_add_signed(shift);
+            if position < start {
+                break;
+            }
+            if position < start + span.read_as.len() {
+                return span.position;
+            }
+            shift += span.read_as.len() as isize - span.input.len() as isize;
+        }
+        position.saturating_add_signed(-shift)
+    }
+
+    /// Move the input positions of `metadata`, collected on the repaired text, to the original
+    ///
+    /// The repairs are added to its `normalized_spans`, in input order.
+    pub fn restore_positions(self, metadata: &mut TransliterationMetadata) {
+        for token in &mut metadata.unknown_tokens {
+            if token.stage == UnknownStage::SourceParsing {
+                token.position = self.original_position(token.position);
+            }
+        }
+        for span in &mut metadata.fixed_spans {
+            span.position = self.original_position(span.position);
+        }
+        for span in &mut metadata.normalized_spans {
+            span.position = self.original_position(span.position);
+        }
+        for span in &mut metadata.converted_spans {
+            *span = self.original_position(span.start)..self.original_position(span.end);
+        }
+        metadata.normalized_spans.extend(self.spans);
+        metadata.normalized_spans.sort_by_key(|span| span.position);
+    }
+}
+
+/// What a character of an ISCII-layout block is, for repair
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    IndependentVowel,
+    VowelSign,
+    Other,
+}
+
+fn classify(c: char) -> Class {
+    if !('\u{0900}'..='\u{0D7F}').contains(&c) || !c.is_alphabetic() {
+        return Class::Other;
+    }
+    match c as u32 & 0x7F {
+        0x04..=0x14 | 0x60 | 0x61 => Class::IndependentVowel,
+        0x3E..=0x4C | 0x62 | 0x63 => Class::VowelSign,
+        // Gurmukhi ੲ and ੳ, which carry vowel signs to write vowels
+        0x72 | 0x73 if ('\u{0A00}'..'\u{0A80}').contains(&c) => Class::IndependentVowel,
+        _ => Class::Other,
+    }
+}
+
+fn same_block(a: char, b: char) -> bool {
+    a as u32 >> 7 == b as u32 >> 7
+}
+
+/// Repair the malformed vowel sequences of Indic `text`, `None` if it has none
+pub fn repair_vowel_sequences(text: &str) -> Option<Repaired> {
+    let mut repaired: Option<Repaired> = None;
+    // The character before, as repaired, its class and its offset in `text`
+    let mut prev: Option<(char, Class, usize)> = None;
+
+    for (position, c) in text.char_indices() {
+        let class = classify(c);
+        let repair = match prev {
+            Some((before, prev_class, start))
+                if class == Class::VowelSign && same_block(before, c) =>
+            {
+                match prev_class {
+                    Class::IndependentVowel => Some((
+                        start,
+                        VOWEL_LETTER_SPELLINGS
+                            .iter()
+                            .find(|&&(vowel, sign, _)| vowel == before && sign == c)
+                            .map_or(before, |&(_, _, letter)| letter),
+                    )),
+                    Class::VowelSign
+                        if unicode_normalization::char::compose(before, c).is_none() =>
+                    {
+                        Some((start, before))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        let Some((start, read_as)) = repair else {
+            if let Some(repaired) = &mut repaired {
+                repaired.text.push(c);
+            }
+            prev = Some((c, class, position));
+            continue;
+        };
+
+        // The character before is replaced; until the first repair it was not copied
+        if let Some(repaired) = &mut repaired {
+            repaired.text.pop();
+        }
+        let repaired = repaired.get_or_insert_with(|| {
+            let mut copied = String::with_capacity(text.len());
+            copied.push_str(&text[..start]);
+            Repaired {
+                text: copied,
+                spans: Vec::new(),
+            }
+        });
+        repaired.text.push(read_as);
+
+        // A repair of the character a span ended with extends the span
+        let end = position + c.len_utf8();
+        match repaired.spans.last_mut() {
+            Some(span) if span.position + span.input.len() == position => {
+                span.input = text[span.position..end].to_string();
+                span.read_as = read_as.to_string();
+            }
+            _ => repaired.spans.push(NormalizedSpan {
+                kind: NormalizationKind::RepairedSequence,
+                input: text[start..end].to_string(),
+                read_as: read_as.to_string(),
+                position: start,
+            }),
+        }
+        prev = Some((read_as, classify(read_as), start));
+    }
+
+    repaired
+}
@@ -0,0 +1,271 @@
+//! Memoized results of whole-string conversions
+//!
+//! Workloads such as UI labels convert the same short strings over and over.
+//! With `Shlesha::enable_result_cache`, the output of each conversion of a
+//! string up to `ResultCacheConfig::max_input_bytes` is kept, keyed by the
+//! source and target names and the text, and returned the next time without
+//! running the pipeline.
+//!
+//! The entries are split across shards by the hash of their key, each shard an
+//! LRU list behind its own mutex, so threads converting different strings
+//! rarely wait on each other. A shard evicts its least recently used entry when
+//! full, which approximates LRU over the whole cache.
+//!
+//! Every change to how text converts (loading, reloading or removing a schema,
+//! a word override, a hook, or a setter such as `set_vedic_mode`) clears the
+//! cache. A conversion that started before the clear does not store its result.
+
+use rustc_hash::FxHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+/// Longest input, in bytes, cached by default
+pub const DEFAULT_MAX_INPUT_BYTES: usize = 128;
+
+/// Shards the entries are split across, fewer for a small capacity
+const SHARDS: usize = 16;
+
+/// Fewest entries a shard is made for, so a small cache keeps LRU order
+const MIN_SHARD_CAPACITY: usize = 32;
+
+/// No slot, at either end of a shard's list
+const NIL: usize = usize::MAX;
+
+/// Size of the result cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResultCacheConfig {
+    /// Most conversions kept
+    pub capacity: usize,
+    /// Longest input, in bytes, whose conversion is kept
+    pub max_input_bytes: usize,
+}
+
+impl Default for ResultCacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            max_input_bytes: DEFAULT_MAX_INPUT_BYTES,
+        }
+    }
+}
+
+/// The cache behind `Shlesha::enable_result_cache`
+pub(crate) struct ResultCache {
+    shards: Box<[Mutex<Shard>]>,
+    max_input_bytes: usize,
+    /// Bumped by every clear, so results of conversions started before it are not kept
+    generation: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResultCache {
+    pub(crate) fn new(config: ResultCacheConfig) -> Self {
+        let shards = (config.capacity / MIN_SHARD_CAPACITY).clamp(1, SHARDS);
+        let capacity = config.capacity.div_ceil(shards);
+        Self {
+            shards: (0..shards)
+                .map(|_| Mutex::new(Shard::new(capacity)))
+                .collect(),
+            max_input_bytes: config.max_input_bytes,
+            generation: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether conversions of `text` are cached
+    pub(crate) fn admits(&self, text: &str) -> bool {
+        text.len() <= self.max_input_bytes
+    }
+
+    /// The generation to pass to `insert` for a conversion starting now
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// The cached output of converting `text` from `from` to `to`, counting a hit or miss
+    pub(crate) fn get(&self, from: &str, to: &str, text: &str) -> Option<String> {
+        let hash = key_hash(from, to, text);
+        let output = self.shard(hash).get(hash, from, to, text);
+        let counter = if output.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        output
+    }
+
+    /// Keep `output`, unless the cache was cleared since `generation`
+    pub(crate) fn insert(&self, generation: u64, from: &str, to: &str, text: &str, output: &str) {
+        let hash = key_hash(from, to, text);
+        let mut shard = self.shard(hash);
+        // Checked under the shard lock, which `clear` takes after bumping the generation
+        if self.generation() == generation {
+            shard.insert(hash, from, to, text, output);
+        }
+    }
+
+    /// Drop every entry
+    pub(crate) fn clear(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        for shard in self.shards.iter() {
+            shard.lock().unwrap_or_else(PoisonError::into_inner).clear();
+        }
+    }
+
+    /// Lookups that found an entry, and those that did not
+    pub(crate) fn counts(&self) -> (u64, u64) {
+        (
+            self.hits.load(Ordering::Relaxed),
+            self.misses.load(Ordering::Relaxed),
+        )
+    }
+
+    pub(crate) fn reset_counts(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    fn shard(&self, hash: u64) -> std::sync::MutexGuard<'_, Shard> {
+        // FxHash mixes into the high bits best
+        let index = (hash >> 32) as usize % self.shards.len();
+        self.shards[index]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn key_hash(from: &str, to: &str, text: &str) -> u64 {
+    let mut hasher = FxHasher::default();
+    (from, to, text).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A conversion kept in a shard, in its list from most to least recently used
+struct Slot {
+    hash: u64,
+    from: String,
+    to: String,
+    text: String,
+    output: String,
+    previous: usize,
+    next: usize,
+}
+
+/// Entries of one shard, by the hash of their key
+///
+/// Keys whose hashes collide share a slot: the one stored last replaces the other.
+struct Shard {
+    capacity: usize,
+    index: rustc_hash::FxHashMap<u64, usize>,
+    slots: Vec<Slot>,
+    /// The most recently used slot
+    head: usize,
+    /// The least recently used slot, evicted first
+    tail: usize,
+}
+
+impl Shard {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            index: rustc_hash::FxHashMap::default(),
+            slots: Vec::new(),
+            head: NIL,
+            tail: NIL,
+        }
+    }
+
+    fn get(&mut self, hash: u64, from: &str, to: &str, text: &str) -> Option<String> {
+        let slot = *self.index.get(&hash)?;
+        let entry = &self.slots[slot];
+        if entry.from != from || entry.to != to || entry.text != text {
+            return None;
+        }
+        let output = entry.output.clone();
+        self.move_to_front(slot);
+        Some(output)
+    }
+
+    fn insert(&mut self, hash: u64, from: &str, to: &str, text: &str, output: &str) {
+        if self.capacity == 0 {
+            return;
+        }
+        let slot = match self.index.get(&hash) {
+            Some(&slot) => slot,
+            None if self.slots.len() < self.capacity => {
+                self.slots.push(Slot {
+                    hash,
+                    from: String::new(),
+                    to: String::new(),
+                    text: String::new(),
+                    output: String::new(),
+                    previous: NIL,
+                    next: NIL,
+                });
+                let slot = self.slots.len() - 1;
+                self.link_front(slot);
+                slot
+            }
+            None => {
+                let slot = self.tail;
+                self.index.remove(&self.slots[slot].hash);
+                slot
+            }
+        };
+
+        let entry = &mut self.slots[slot];
+        entry.hash = hash;
+        for (field, value) in [
+            (&mut entry.from, from),
+            (&mut entry.to, to),
+            (&mut entry.text, text),
+            (&mut entry.output, output),
+        ] {
+            field.clear();
+            field.push_str(value);
+        }
+        self.index.insert(hash, slot);
+        self.move_to_front(slot);
+    }
+
+    fn clear(&mut self) {
+        self.index.clear();
+        self.slots.clear();
+        self.head = NIL;
+        self.tail = NIL;
+    }
+
+    fn move_to_front(&mut self, slot: usize) {
+        if self.head == slot {
+            return;
+        }
+        self.unlink(slot);
+        self.link_front(slot);
+    }
+
+    fn unlink(&mut self, slot: usize) {
+        let (previous, next) = (self.slots[slot].previous, self.slots[slot].next);
+        match previous {
+            NIL => self.head = next,
+            previous => self.slots[previous].next = next,
+        }
+        match next {
+            NIL => self.tail = previous,
+            next => self.slots[next].previous = previous,
+        }
+    }
+
+    fn link_front(&mut self, slot: usize) {
+        self.slots[slot].previous = NIL;
+        self.slots[slot].next = self.head;
+        match self.head {
+            NIL => self.tail = slot,
+            head => self.slots[head].previous = slot,
+        }
+        self.head = slot;
+    }
+}
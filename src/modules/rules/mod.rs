@@ -0,0 +1,356 @@
+//! Context-sensitive spelling rules from a schema's `rules` section
+//!
+//! Some spellings depend on their neighbours rather than on one token:
+//! Malayalam writes a dead consonant at the end of a word as a chillu letter
+//! (ൻ, not ന്), and Gurmukhi writes anusvara as tippi ੰ after a short vowel and
+//! as bindi ਂ elsewhere. A rule names a run of hub tokens, the text spelling
+//! them, and optionally the tokens that must come before and after:
+//!
+//! ```yaml
+//! rules:
+//!   write:
+//!     - tokens: [ConsonantNn, MarkVirama]
+//!       after: boundary
+//!       text: "ൻ"
+//!   read:
+//!     - text: "ൻ"
+//!       tokens: [ConsonantNn, MarkVirama]
+//! ```
+//!
+//! Write rules apply as hub tokens are written, before the tokens' own
+//! mappings; read rules apply as text is read, before the mappings are matched.
+//! Rules are tried in order and the first that matches wins. Neither direction
+//! is derived from the other, so a written spelling that should read back needs
+//! a read rule too.
+//!
+//! A context is a token class, or a list of classes any of which will do:
+//!
+//! | Class | Matches |
+//! |-------|---------|
+//! | `boundary` | the start or end of the text, or a token outside words: a space, punctuation, a digit |
+//! | `consonant` | a consonant |
+//! | `vowel` | an independent vowel |
+//! | `vowel_sign` | a dependent vowel sign |
+//! | `virama` | the virama |
+//! | `mark` | anusvara, visarga, candrabindu or avagraha |
+//! | a token name | that token, e.g. `VowelSignI` |
+//!
+//! In a read rule, `before` is the token read before the text and `after` the
+//! token the mappings read from the text after it. The language stops there: no
+//! patterns over text, no repetition and no negation, so every rule consumes
+//! what it matches and conversion stays linear in the input.
+//!
+//! A schema's `conjuncts` map text to the token sequence it stands for, written
+//! and read alike; `read_conjuncts` are only read. Each becomes a rule without
+//! context, tried after the schema's own rules, longest sequence first:
+//!
+//! ```yaml
+//! conjuncts:
+//!   "x": [ConsonantK, MarkVirama, ConsonantSs]
+//! ```
+
+use crate::modules::hub::{AbugidaToken, AlphabetToken, HubToken};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+    #[error("{0:?} is not a token name")]
+    UnknownToken(String),
+    #[error("{0:?} is neither a token class nor a token name")]
+    UnknownClass(String),
+    #[error("Rule {0:?} must spell one or more tokens with non-empty text")]
+    Empty(String),
+    #[error("Conjunct {0:?} must stand for two or more tokens")]
+    Conjunct(String),
+}
+
+/// A schema's `rules` section as written
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleDefinitions {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub write: Vec<RuleDefinition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub read: Vec<RuleDefinition>,
+}
+
+impl RuleDefinitions {
+    /// These rules followed by those of a schema's `conjuncts`, which are written
+    /// and read, and `read_conjuncts`, which are only read
+    ///
+    /// Conjuncts come longest sequence first, so a sequence is matched before
+    /// any conjunct it starts with.
+    pub fn with_conjuncts<'a>(
+        mut self,
+        conjuncts: impl IntoIterator<Item = (&'a String, &'a Vec<String>)>,
+        read_conjuncts: impl IntoIterator<Item = (&'a String, &'a Vec<String>)>,
+    ) -> Result<Self, RuleError> {
+        let written = conjuncts.into_iter().map(|pair| (pair, true));
+        let read_only = read_conjuncts.into_iter().map(|pair| (pair, false));
+        let mut conjuncts: Vec<_> = written.chain(read_only).collect();
+        conjuncts.sort_by(|((a, a_tokens), _), ((b, b_tokens), _)| {
+            b_tokens.len().cmp(&a_tokens.len()).then(a.cmp(b))
+        });
+        for ((text, tokens), written) in conjuncts {
+            if tokens.len() < 2 {
+                return Err(RuleError::Conjunct(text.clone()));
+            }
+            let rule = RuleDefinition {
+                tokens: tokens.clone(),
+                text: text.clone(),
+                before: None,
+                after: None,
+            };
+            if written {
+                self.write.push(rule.clone());
+            }
+            self.read.push(rule);
+        }
+        Ok(self)
+    }
+}
+
+/// One rule as written: token names, their text, and the names of context classes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleDefinition {
+    pub tokens: Vec<String>,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<ContextDefinition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<ContextDefinition>,
+}
+
+/// A context as written: one class, or a list of them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContextDefinition {
+    One(String),
+    Any(Vec<String>),
+}
+
+impl ContextDefinition {
+    fn names(&self) -> &[String] {
+        match self {
+            ContextDefinition::One(name) => std::slice::from_ref(name),
+            ContextDefinition::Any(names) => names,
+        }
+    }
+}
+
+/// A class of neighbouring token a rule can require
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenClass {
+    Boundary,
+    Consonant,
+    Vowel,
+    VowelSign,
+    Virama,
+    Mark,
+    Token(HubToken),
+}
+
+impl TokenClass {
+    /// The class named `name`, reading token names as alphabet or abugida tokens
+    pub fn parse(name: &str, is_alphabet: bool) -> Result<Self, RuleError> {
+        Ok(match name {
+            "boundary" => TokenClass::Boundary,
+            "consonant" => TokenClass::Consonant,
+            "vowel" => TokenClass::Vowel,
+            "vowel_sign" => TokenClass::VowelSign,
+            "virama" => TokenClass::Virama,
+            "mark" => TokenClass::Mark,
+            _ => TokenClass::Token(
+                parse_token(name, is_alphabet)
+                    .map_err(|_| RuleError::UnknownClass(name.to_string()))?,
+            ),
+        })
+    }
+
+    /// The name the class is written with in a schema
+    pub fn name(&self) -> String {
+        match self {
+            TokenClass::Boundary => "boundary".to_string(),
+            TokenClass::Consonant => "consonant".to_string(),
+            TokenClass::Vowel => "vowel".to_string(),
+            TokenClass::VowelSign => "vowel_sign".to_string(),
+            TokenClass::Virama => "virama".to_string(),
+            TokenClass::Mark => "mark".to_string(),
+            TokenClass::Token(token) => crate::modules::script_converter::token_name(token),
+        }
+    }
+
+    /// Whether `token` is of this class, `None` standing for the start or end of the text
+    pub fn matches(&self, token: Option<&HubToken>) -> bool {
+        match (self, token) {
+            (TokenClass::Boundary, token) => token.is_none_or(|token| !in_word(token)),
+            (_, None) => false,
+            (TokenClass::Consonant, Some(token)) => token.is_consonant(),
+            (TokenClass::Vowel, Some(token)) => token.is_vowel(),
+            (TokenClass::VowelSign, Some(token)) => token.is_vowel_sign(),
+            (TokenClass::Virama, Some(token)) => token.is_virama(),
+            (TokenClass::Mark, Some(token)) => token.is_yogavaha(),
+            (TokenClass::Token(expected), Some(token)) => token == expected,
+        }
+    }
+}
+
+/// Whether `token` belongs to a word: a letter, sign or mark, or a joiner shaping one
+fn in_word(token: &HubToken) -> bool {
+    token.is_consonant()
+        || token.is_vowel()
+        || token.is_vowel_sign()
+        || token.is_virama()
+        || token.is_yogavaha()
+        || token.is_vedic_accent()
+        || matches!(token, HubToken::Abugida(AbugidaToken::MarkNukta))
+        || matches!(token.as_unknown_string(), Some("\u{200C}" | "\u{200D}"))
+}
+
+fn parse_token(name: &str, is_alphabet: bool) -> Result<HubToken, RuleError> {
+    let token = if is_alphabet {
+        name.parse::<AlphabetToken>().ok().map(HubToken::Alphabet)
+    } else {
+        name.parse::<AbugidaToken>().ok().map(HubToken::Abugida)
+    };
+    match token {
+        Some(token) if !token.is_unknown() => Ok(token),
+        _ => Err(RuleError::UnknownToken(name.to_string())),
+    }
+}
+
+/// A run of tokens spelled as `text` where the tokens around it are of the
+/// `before` and `after` classes; an empty context allows anything
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpellingRule {
+    pub tokens: Vec<HubToken>,
+    pub text: String,
+    pub before: Vec<TokenClass>,
+    pub after: Vec<TokenClass>,
+}
+
+impl SpellingRule {
+    pub fn parse(definition: &RuleDefinition, is_alphabet: bool) -> Result<Self, RuleError> {
+        if definition.tokens.is_empty() || definition.text.is_empty() {
+            return Err(RuleError::Empty(definition.text.clone()));
+        }
+        let context = |context: &Option<ContextDefinition>| {
+            context
+                .iter()
+                .flat_map(ContextDefinition::names)
+                .map(|name| TokenClass::parse(name, is_alphabet))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            tokens: definition
+                .tokens
+                .iter()
+                .map(|name| parse_token(name, is_alphabet))
+                .collect::<Result<_, _>>()?,
+            text: definition.text.clone(),
+            before: context(&definition.before)?,
+            after: context(&definition.after)?,
+        })
+    }
+
+    /// The rule as a schema writes it
+    pub fn to_definition(&self) -> RuleDefinition {
+        let context = |classes: &[TokenClass]| match classes {
+            [] => None,
+            [class] => Some(ContextDefinition::One(class.name())),
+            classes => Some(ContextDefinition::Any(
+                classes.iter().map(TokenClass::name).collect(),
+            )),
+        };
+        RuleDefinition {
+            tokens: self
+                .tokens
+                .iter()
+                .map(crate::modules::script_converter::token_name)
+                .collect(),
+            text: self.text.clone(),
+            before: context(&self.before),
+            after: context(&self.after),
+        }
+    }
+
+    fn in_context(&self, before: Option<&HubToken>, after: Option<&HubToken>) -> bool {
+        let holds = |classes: &[TokenClass], token| {
+            classes.is_empty() || classes.iter().any(|class| class.matches(token))
+        };
+        holds(&self.before, before) && holds(&self.after, after)
+    }
+}
+
+/// A schema's write and read rules, in the order they are tried
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaRules {
+    pub write: Vec<SpellingRule>,
+    pub read: Vec<SpellingRule>,
+}
+
+impl SchemaRules {
+    pub fn parse(definitions: &RuleDefinitions, is_alphabet: bool) -> Result<Self, RuleError> {
+        let parse = |rules: &[RuleDefinition]| {
+            rules
+                .iter()
+                .map(|rule| SpellingRule::parse(rule, is_alphabet))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            write: parse(&definitions.write)?,
+            read: parse(&definitions.read)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.write.is_empty() && self.read.is_empty()
+    }
+
+    /// The rules as a schema's `rules` section writes them
+    pub fn to_definitions(&self) -> RuleDefinitions {
+        RuleDefinitions {
+            write: self.write.iter().map(SpellingRule::to_definition).collect(),
+            read: self.read.iter().map(SpellingRule::to_definition).collect(),
+        }
+    }
+
+    /// The text of the first write rule matching `tokens` at `i`, and how many tokens it spells
+    pub fn write_at(&self, tokens: &[HubToken], i: usize) -> Option<(&str, usize)> {
+        self.write
+            .iter()
+            .find(|rule| {
+                tokens[i..].starts_with(&rule.tokens)
+                    && rule.in_context(
+                        i.checked_sub(1).map(|before| &tokens[before]),
+                        tokens.get(i + rule.tokens.len()),
+                    )
+            })
+            .map(|rule| (rule.text.as_str(), rule.tokens.len()))
+    }
+
+    /// The tokens of the first read rule whose text starts `input[pos..]`, and its length
+    ///
+    /// `previous` is the token read before `pos`, and `read_at` reads the token at
+    /// a byte offset of `input`, `None` at its end.
+    pub fn read_at(
+        &self,
+        input: &str,
+        pos: usize,
+        previous: Option<&HubToken>,
+        read_at: impl Fn(usize) -> Option<HubToken>,
+    ) -> Option<(&[HubToken], usize)> {
+        self.read
+            .iter()
+            .find(|rule| {
+                input[pos..].starts_with(&rule.text)
+                    && rule.in_context(previous, read_at(pos + rule.text.len()).as_ref())
+            })
+            .map(|rule| (rule.tokens.as_slice(), rule.text.len()))
+    }
+
+    /// Whether `input` contains text a read rule spells
+    pub fn reads(&self, input: &str) -> bool {
+        self.read.iter().any(|rule| input.contains(&rule.text))
+    }
+}
@@ -4,7 +4,7 @@
 
 use blake3::Hasher;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
@@ -38,6 +38,8 @@ struct CacheIndex {
 #[derive(Debug, Serialize, Deserialize)]
 struct CacheEntry {
     schema_hash: String,
+    #[serde(default)]
+    schema_name: String,
     dylib_path: PathBuf,
     source_path: PathBuf,
     metadata_path: PathBuf,
@@ -51,8 +53,14 @@ pub struct CacheManager {
 }
 
 impl CacheManager {
+    /// Open the cache in `$SHLESHA_CACHE_DIR`, or in `shlesha` under the user cache directory
     pub fn new() -> Result<Self, CacheError> {
-        let cache_dir = Self::get_cache_directory()?;
+        Self::with_cache_dir(Self::get_cache_directory()?)
+    }
+
+    /// Open (creating if needed) the cache stored in `cache_dir`
+    pub fn with_cache_dir(cache_dir: impl Into<PathBuf>) -> Result<Self, CacheError> {
+        let cache_dir = cache_dir.into();
         fs::create_dir_all(&cache_dir)?;
 
         // Create subdirectories
@@ -64,7 +72,15 @@ impl CacheManager {
         Ok(Self { cache_dir, index })
     }
 
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
     fn get_cache_directory() -> Result<PathBuf, CacheError> {
+        if let Ok(cache_dir) = std::env::var("SHLESHA_CACHE_DIR") {
+            return Ok(PathBuf::from(cache_dir));
+        }
+
         let cache_base = if let Ok(xdg_cache) = std::env::var("XDG_CACHE_HOME") {
             PathBuf::from(xdg_cache)
         } else if let Ok(home) = std::env::var("HOME") {
@@ -109,17 +125,22 @@ impl CacheManager {
     pub fn generate_cache_key(&self, schema: &Schema) -> String {
         let mut hasher = Hasher::new();
 
-        // Hash schema content for deterministic cache key
-        let schema_json = serde_json::to_string(schema).unwrap_or_default();
+        // Hash schema content, not its name, in an order that does not depend on
+        // HashMap iteration so the key is the same in every process
+        let mappings: BTreeMap<_, BTreeMap<_, _>> = schema
+            .mappings
+            .iter()
+            .map(|(category, entries)| (category, entries.iter().collect()))
+            .collect();
+        let schema_json = serde_json::to_string(&(&schema.metadata, &schema.target, mappings))
+            .unwrap_or_default();
         hasher.update(schema_json.as_bytes());
 
         // Include Shlesha version to invalidate cache on updates
         hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
 
-        // Include template file hash if it exists
-        if let Ok(template_content) = fs::read_to_string("templates/token_based_converter.hbs") {
-            hasher.update(template_content.as_bytes());
-        }
+        // Include the template the code is generated from
+        hasher.update(include_str!("../../../templates/token_based_converter.hbs").as_bytes());
 
         hex::encode(hasher.finalize().as_bytes())
     }
@@ -129,10 +150,7 @@ impl CacheManager {
             // Check if files still exist
             if entry.dylib_path.exists() && entry.source_path.exists() {
                 // Update last accessed time
-                entry.last_accessed = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs();
+                entry.last_accessed = now_secs();
 
                 // Load compilation cache
                 let metadata_content = fs::read_to_string(&entry.metadata_path)?;
@@ -155,15 +173,53 @@ impl CacheManager {
         Ok(None)
     }
 
+    /// Remove entries for `schema_name` whose hash is not `cache_key`
+    ///
+    /// A schema edited since it was cached gets a new key, so without this its old
+    /// artifacts would stay on disk forever. Returns the number of entries removed.
+    pub fn invalidate_stale(
+        &mut self,
+        schema_name: &str,
+        cache_key: &str,
+    ) -> Result<usize, CacheError> {
+        let stale: Vec<String> = self
+            .index
+            .entries
+            .iter()
+            .filter(|(key, entry)| entry.schema_name == schema_name && key.as_str() != cache_key)
+            .map(|(key, _)| key.clone())
+            .collect();
+        if stale.is_empty() {
+            return Ok(0);
+        }
+
+        for key in &stale {
+            if let Some(entry) = self.index.entries.remove(key) {
+                let _ = fs::remove_file(&entry.dylib_path);
+                let _ = fs::remove_file(&entry.source_path);
+                let _ = fs::remove_file(&entry.metadata_path);
+            }
+        }
+
+        self.save_index()?;
+        Ok(stale.len())
+    }
+
+    /// Number of compiled schemas in the cache
+    pub fn len(&self) -> usize {
+        self.index.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.entries.is_empty()
+    }
+
     pub fn store_cache(
         &mut self,
         cache_key: &str,
         cache: &CompilationCache,
     ) -> Result<(), CacheError> {
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let timestamp = now_secs();
 
         // Generate file paths
         let dylib_dest = self
@@ -192,6 +248,7 @@ impl CacheManager {
         // Update index
         let entry = CacheEntry {
             schema_hash: cache_key.to_string(),
+            schema_name: cache.metadata.name.clone(),
             dylib_path: dylib_dest,
             source_path: source_dest,
             metadata_path: metadata_dest,
@@ -213,11 +270,7 @@ impl CacheManager {
     }
 
     pub fn cleanup_old_entries(&mut self, max_age_days: u64) -> Result<(), CacheError> {
-        let cutoff_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
-            - (max_age_days * 24 * 60 * 60);
+        let cutoff_time = now_secs().saturating_sub(max_age_days * 24 * 60 * 60);
 
         let mut to_remove = Vec::new();
 
@@ -262,3 +315,11 @@ impl Drop for CacheManager {
         let _ = self.save_index();
     }
 }
+
+/// Seconds since the Unix epoch; a clock set before it reads as the epoch
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
@@ -2,9 +2,9 @@
 // This module requires filesystem access, process spawning (cargo), and dynamic library loading
 #![cfg(not(target_arch = "wasm32"))]
 
-use handlebars::Handlebars;
+use handlebars::{handlebars_helper, Handlebars};
+use once_cell::sync::OnceCell;
 use serde_json::Value;
-use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -30,46 +30,104 @@ pub enum RuntimeCompilerError {
     LibraryLoadingError(String),
 }
 
+handlebars_helper!(uppercase: |s: str| s.to_uppercase());
+handlebars_helper!(escape: |s: str| s.replace('\\', "\\\\").replace('"', "\\\""));
+
+/// The converter template with its helpers
+fn template_engine() -> Result<Handlebars<'static>, RuntimeCompilerError> {
+    let mut template_engine = Handlebars::new();
+
+    // Use the same template and helpers as build.rs; the template is embedded so
+    // it does not depend on the working directory and matches the cache key
+    template_engine.register_template_string(
+        "token_based_converter",
+        include_str!("../../../templates/token_based_converter.hbs"),
+    )?;
+    template_engine.register_helper("uppercase", Box::new(uppercase));
+    template_engine.register_helper("escape", Box::new(escape));
+    Ok(template_engine)
+}
+
+/// How often compilation was served from the cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompilationStats {
+    /// Schemas whose compiled artifacts were found in the cache
+    pub cache_hits: usize,
+    /// Schemas that had to be compiled
+    pub compilations: usize,
+}
+
 pub struct RuntimeCompiler {
-    template_engine: Handlebars<'static>,
+    /// Parsed on the first compilation, as most compilers never render it
+    template_engine: OnceCell<Handlebars<'static>>,
     cache_manager: CacheManager,
     temp_dir: Option<TempDir>,
+    cargo: PathBuf,
+    stats: CompilationStats,
 }
 
 impl RuntimeCompiler {
+    /// Create a compiler using the default cache directory (see [`CacheManager::new`])
     pub fn new() -> Result<Self, RuntimeCompilerError> {
-        let mut template_engine = Handlebars::new();
-
-        // Load the same templates used by build.rs
-        let template_path = "templates/token_based_converter.hbs";
-        if Path::new(template_path).exists() {
-            template_engine.register_template_file("token_based_converter", template_path)?;
-        }
+        Self::with_cache_manager(CacheManager::new()?)
+    }
 
-        let cache_manager = CacheManager::new()?;
+    /// Create a compiler that keeps compiled schemas in `cache_dir`
+    pub fn with_cache_dir(cache_dir: impl Into<PathBuf>) -> Result<Self, RuntimeCompilerError> {
+        Self::with_cache_manager(CacheManager::with_cache_dir(cache_dir)?)
+    }
 
+    fn with_cache_manager(cache_manager: CacheManager) -> Result<Self, RuntimeCompilerError> {
         Ok(Self {
-            template_engine,
+            template_engine: OnceCell::new(),
             cache_manager,
             temp_dir: None,
+            cargo: PathBuf::from("cargo"),
+            stats: CompilationStats::default(),
         })
     }
 
+    /// Build generated crates with `cargo` instead of the `cargo` found on `PATH`
+    pub fn with_cargo(mut self, cargo: impl Into<PathBuf>) -> Self {
+        self.cargo = cargo.into();
+        self
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        self.cache_manager.cache_dir()
+    }
+
+    /// Cache hits and compilations since this compiler was created
+    pub fn stats(&self) -> CompilationStats {
+        self.stats
+    }
+
+    /// Remove every compiled schema from the cache
+    pub fn clear_cache(&mut self) -> Result<(), RuntimeCompilerError> {
+        self.cache_manager.clear_cache()?;
+        Ok(())
+    }
+
     pub fn compile_schema(
         &mut self,
         schema: &Schema,
     ) -> Result<CompiledProcessor, RuntimeCompilerError> {
-        // Check cache first
+        // Check cache first, dropping artifacts of earlier versions of this schema
         let cache_key = self.cache_manager.generate_cache_key(schema);
+        self.cache_manager
+            .invalidate_stale(&schema.metadata.name, &cache_key)?;
 
         if let Some(cached) = self.cache_manager.get_cached(&cache_key)? {
-            return Ok(CompiledProcessor::from_cache(cached));
+            self.stats.cache_hits += 1;
+            return Ok(CompiledProcessor::new(cached.dylib_path, schema.clone()));
         }
+        self.stats.compilations += 1;
 
         // Generate code using the same template as build.rs
         let template_data = self.prepare_template_data(schema)?;
         let generated_code = self
             .template_engine
+            .get_or_try_init(template_engine)?
             .render("token_based_converter", &template_data)?;
 
         // Create temporary crate
@@ -242,7 +300,7 @@ pub extern "C" fn convert_tokens_to_string(tokens: *const std::os::raw::c_char)
 
     fn compile_to_dylib(&self, crate_dir: &Path) -> Result<PathBuf, RuntimeCompilerError> {
         // Run cargo build --release
-        let output = Command::new("cargo")
+        let output = Command::new(&self.cargo)
             .args(["build", "--release"])
             .current_dir(crate_dir)
             .output()?;
@@ -301,17 +359,6 @@ impl CompiledProcessor {
         Self { dylib_path, schema }
     }
 
-    fn from_cache(cache: CompilationCache) -> Self {
-        Self {
-            dylib_path: cache.dylib_path,
-            schema: Schema {
-                metadata: cache.metadata,
-                target: "unknown".to_string(), // Will be populated from cache metadata
-                mappings: HashMap::new(),      // Will be populated from dylib
-            },
-        }
-    }
-
     pub fn get_dylib_path(&self) -> &Path {
         &self.dylib_path
     }
@@ -320,9 +367,3 @@ impl CompiledProcessor {
         &self.schema
     }
 }
-
-impl Default for RuntimeCompiler {
-    fn default() -> Self {
-        Self::new().expect("Failed to create RuntimeCompiler")
-    }
-}
@@ -8,4 +8,4 @@ pub mod compiler;
 #[cfg(not(target_arch = "wasm32"))]
 pub use cache::{CacheManager, CompilationCache};
 #[cfg(not(target_arch = "wasm32"))]
-pub use compiler::RuntimeCompiler;
+pub use compiler::{CompilationStats, RuntimeCompiler};
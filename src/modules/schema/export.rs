@@ -0,0 +1,190 @@
+//! Schema YAML for the mappings a script converts with
+//!
+//! [`builtin_schema`] gathers the spellings and rules a generated converter
+//! was built with into a runtime [`Schema`], and [`schema_yaml`] writes a
+//! runtime schema out in the schema file format, so what a script uses can be
+//! diffed against its source file or loaded again with
+//! `Shlesha::load_schema_from_string`.
+
+use super::scaffold::quote;
+use crate::modules::hub::tokens::{HubFormat, TokenCategory};
+use crate::modules::registry::{Schema, SchemaMetadata, TextDirection};
+use crate::modules::script_converter::{token_name, TokenConverter};
+use rustc_hash::FxHashMap;
+
+/// The runtime schema a generated converter's spellings and rules make up
+///
+/// `None` for converters that do not list their spellings. The built-in
+/// schemas' other sections, such as lossy mappings, fallbacks, conjuncts and
+/// hiatus forms, have no place in a runtime schema and are left out.
+pub fn builtin_schema(
+    converter: &dyn TokenConverter,
+    aliases: Vec<String>,
+    (direction, rendering_notes): (TextDirection, Option<&str>),
+) -> Option<Schema> {
+    let mut mappings = FxHashMap::default();
+    let mut alternates = FxHashMap::default();
+    for (token, spellings) in converter.spellings()? {
+        let Some((written, others)) = spellings.split_first() else {
+            continue;
+        };
+        let name = token_name(&token);
+        if !others.is_empty() {
+            alternates.insert(
+                name.clone(),
+                others.iter().map(|spelling| spelling.to_string()).collect(),
+            );
+        }
+        mappings.insert(name, written.to_string());
+    }
+
+    let name = converter.script_name().to_string();
+    let script_type = if converter.is_alphabet() {
+        "roman"
+    } else {
+        "brahmic"
+    };
+    Some(Schema {
+        target: if converter.is_alphabet() {
+            "alphabet_tokens"
+        } else {
+            "abugida_tokens"
+        }
+        .to_string(),
+        mappings,
+        alternates,
+        metadata: SchemaMetadata {
+            name: name.clone(),
+            script_type: script_type.to_string(),
+            // As every shipped schema declares it
+            has_implicit_a: !converter.is_alphabet(),
+            description: None,
+            aliases: (!aliases.is_empty()).then_some(aliases),
+            direction,
+            rendering_notes: rendering_notes.map(str::to_string),
+            version: None,
+            author: None,
+        },
+        rules: converter.spelling_rules().cloned().unwrap_or_default(),
+        tests: Vec::new(),
+        name,
+        script_type: script_type.to_string(),
+    })
+}
+
+/// `schema` in the schema file format
+///
+/// Tokens are listed under their categories in the order of the hub
+/// inventory. A token read with several spellings is mapped to a list of
+/// them, the written one first.
+pub fn schema_yaml(schema: &Schema) -> String {
+    let metadata = &schema.metadata;
+    let mut yaml = format!(
+        "# Exported by shlesha {}; of a list of spellings, the first is written\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    yaml.push_str("metadata:\n");
+    yaml.push_str(&format!("  name: {}\n", quote(&metadata.name)));
+    yaml.push_str(&format!(
+        "  script_type: {}\n",
+        quote(&metadata.script_type)
+    ));
+    yaml.push_str(&format!("  has_implicit_a: {}\n", metadata.has_implicit_a));
+    if let Some(description) = &metadata.description {
+        yaml.push_str(&format!("  description: {}\n", quote(description)));
+    }
+    if let Some(aliases) = metadata
+        .aliases
+        .as_ref()
+        .filter(|aliases| !aliases.is_empty())
+    {
+        yaml.push_str(&format!("  aliases: {}\n", quote_list(aliases)));
+    }
+    if metadata.direction != TextDirection::Ltr {
+        yaml.push_str(&format!("  direction: \"{}\"\n", metadata.direction));
+    }
+    if let Some(notes) = &metadata.rendering_notes {
+        yaml.push_str(&format!("  rendering_notes: {}\n", quote(notes)));
+    }
+    if let Some(version) = &metadata.version {
+        yaml.push_str(&format!("  version: {}\n", quote(version)));
+    }
+    if let Some(author) = &metadata.author {
+        yaml.push_str(&format!("  author: {}\n", quote(author)));
+    }
+    yaml.push_str(&format!(
+        "\ntarget: {}\n\nmappings:\n",
+        quote(&schema.target)
+    ));
+
+    let format = if schema.is_alphabet() {
+        HubFormat::Alphabet
+    } else {
+        HubFormat::Abugida
+    };
+    let mut tokens: Vec<(&String, &String)> = schema.mappings.iter().collect();
+    tokens.sort_by_cached_key(|(token, _)| inventory_position(format, token));
+    let mut category = None;
+    for (token, written) in tokens {
+        let (token_category, _, _) = inventory_position(format, token);
+        if category != Some(token_category) {
+            category = Some(token_category);
+            yaml.push_str(&format!("  {}:\n", token_category.key()));
+        }
+        let value = match schema.alternates.get(token) {
+            Some(others) => quote_list(std::iter::once(written).chain(others)),
+            None => quote(written),
+        };
+        yaml.push_str(&format!("    {token}: {value}\n"));
+    }
+
+    if !schema.rules.is_empty() {
+        // Rule definitions are plain strings and lists, which always serialize
+        #[allow(clippy::expect_used)]
+        let rules = serde_yaml::to_string(&schema.rules.to_definitions())
+            .expect("rule definitions serialize");
+        yaml.push_str("\nrules:\n");
+        for line in rules.lines() {
+            yaml.push_str(&format!("  {line}\n"));
+        }
+    }
+
+    if !schema.tests.is_empty() {
+        yaml.push_str("\ntests:\n");
+        for case in &schema.tests {
+            yaml.push_str(&format!("  - input: {}\n", quote(&case.input)));
+            for (key, expected) in [
+                ("expected_devanagari", &case.expected_devanagari),
+                ("expected_iso", &case.expected_iso),
+            ] {
+                if let Some(expected) = expected {
+                    yaml.push_str(&format!("    {key}: {}\n", quote(expected)));
+                }
+            }
+        }
+    }
+    yaml
+}
+
+/// YAML flow sequence of quoted `items`
+fn quote_list<'a>(items: impl IntoIterator<Item = &'a String>) -> String {
+    let quoted: Vec<String> = items.into_iter().map(|item| quote(item)).collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+/// The category a token is listed under and its place there
+///
+/// Names the inventory of `format` does not know, such as those of runtime
+/// schemas mapping text to text, are listed at the end of `special`.
+fn inventory_position(format: HubFormat, token: &str) -> (TokenCategory, usize, String) {
+    format
+        .inventory()
+        .iter()
+        .find_map(|(category, tokens)| {
+            tokens
+                .iter()
+                .position(|known| *known == token)
+                .map(|index| (*category, index, token.to_string()))
+        })
+        .unwrap_or((TokenCategory::Special, usize::MAX, token.to_string()))
+}
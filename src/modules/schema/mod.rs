@@ -1,3 +1,6 @@
+pub mod export;
+pub mod scaffold;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -6,6 +9,10 @@ use std::collections::HashMap;
 pub struct SchemaMetadata {
     pub name: String,
     pub script_type: String,
+    /// Whether a consonant without a vowel sign is read with an implicit a;
+    /// when not given, as the target's token type implies
+    #[serde(default)]
+    pub has_implicit_a: Option<bool>,
     pub description: Option<String>,
     pub version: Option<String>,
     pub author: Option<String>,
@@ -16,6 +23,12 @@ pub struct Schema {
     pub metadata: SchemaMetadata,
     pub target: String,
     pub mappings: HashMap<String, HashMap<String, Value>>,
+    /// Text written and read for a whole token sequence: "x" -> [ConsonantK, MarkVirama, ConsonantSs]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub conjuncts: HashMap<String, Vec<String>>,
+    /// Text read as a token sequence but never written
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub read_conjuncts: HashMap<String, Vec<String>>,
 }
 
 impl Schema {
@@ -68,11 +81,40 @@ impl Schema {
     }
 }
 
+/// Builds a [`Schema`] in code, for `Shlesha::add_runtime_schema`
+///
+/// Mappings are keyed by hub token names ("VowelAa", "ConsonantK"); of several
+/// strings for a token, the first is written and all are read. Unless set with
+/// [`Self::target`], the target follows [`Self::has_implicit_a`], or without it
+/// the script type: alphabet tokens for `roman` schemes and abugida tokens for
+/// `brahmic` scripts.
+///
+/// ```
+/// use shlesha::modules::schema::SchemaBuilder;
+/// use shlesha::Shlesha;
+///
+/// let mut transliterator = Shlesha::new();
+/// transliterator.disable_runtime_compiler();
+/// let schema = SchemaBuilder::new("doubled_vowels")
+///     .script_type("roman")
+///     .add_vowel_mapping("VowelA", &["a"])
+///     .add_vowel_mapping("VowelAa", &["aa"])
+///     .add_consonant_mapping("ConsonantM", &["m"])
+///     .build();
+/// transliterator.add_runtime_schema(schema).unwrap();
+///
+/// let devanagari = transliterator
+///     .transliterate("maamaa", "doubled_vowels", "devanagari")
+///     .unwrap();
+/// assert_eq!(devanagari, "मामा");
+/// ```
 #[derive(Debug, Clone)]
 pub struct SchemaBuilder {
     metadata: SchemaMetadata,
-    target: String,
+    target: Option<String>,
     mappings: HashMap<String, HashMap<String, Value>>,
+    conjuncts: HashMap<String, Vec<String>>,
+    read_conjuncts: HashMap<String, Vec<String>>,
 }
 
 impl SchemaBuilder {
@@ -81,12 +123,15 @@ impl SchemaBuilder {
             metadata: SchemaMetadata {
                 name: name.to_string(),
                 script_type: "unknown".to_string(),
+                has_implicit_a: None,
                 description: None,
                 version: None,
                 author: None,
             },
-            target: "alphabet_tokens".to_string(),
+            target: None,
             mappings: HashMap::new(),
+            conjuncts: HashMap::new(),
+            read_conjuncts: HashMap::new(),
         }
     }
 
@@ -95,6 +140,13 @@ impl SchemaBuilder {
         self
     }
 
+    /// Declare whether consonants carry an implicit a, which also decides the
+    /// target unless one is set
+    pub fn has_implicit_a(mut self, has_implicit_a: bool) -> Self {
+        self.metadata.has_implicit_a = Some(has_implicit_a);
+        self
+    }
+
     pub fn description(mut self, description: &str) -> Self {
         self.metadata.description = Some(description.to_string());
         self
@@ -111,7 +163,7 @@ impl SchemaBuilder {
     }
 
     pub fn target(mut self, target: &str) -> Self {
-        self.target = target.to_string();
+        self.target = Some(target.to_string());
         self
     }
 
@@ -149,11 +201,39 @@ impl SchemaBuilder {
         self
     }
 
+    /// Write the token sequence `tokens` as `text`, and read `text` as it
+    pub fn add_conjunct(mut self, text: &str, tokens: &[&str]) -> Self {
+        let tokens = tokens.iter().map(|token| token.to_string()).collect();
+        self.conjuncts.insert(text.to_string(), tokens);
+        self
+    }
+
+    /// Read `text` as the token sequence `tokens`, which is written otherwise
+    pub fn add_read_conjunct(mut self, text: &str, tokens: &[&str]) -> Self {
+        let tokens = tokens.iter().map(|token| token.to_string()).collect();
+        self.read_conjuncts.insert(text.to_string(), tokens);
+        self
+    }
+
     pub fn build(self) -> Schema {
+        let target = self.target.unwrap_or_else(|| {
+            let has_implicit_a = self
+                .metadata
+                .has_implicit_a
+                .unwrap_or(self.metadata.script_type == "brahmic");
+            if has_implicit_a {
+                "abugida_tokens"
+            } else {
+                "alphabet_tokens"
+            }
+            .to_string()
+        });
         Schema {
             metadata: self.metadata,
-            target: self.target,
+            target,
             mappings: self.mappings,
+            conjuncts: self.conjuncts,
+            read_conjuncts: self.read_conjuncts,
         }
     }
 }
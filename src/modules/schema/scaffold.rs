@@ -0,0 +1,168 @@
+//! Authoring aids for new script schemas
+//!
+//! [`scaffold_schema`] writes a skeleton schema listing every hub token the
+//! build knows, to be filled in with the new script's text, and [`diff_schemas`]
+//! lists the tokens one schema maps and another does not.
+
+use super::Schema;
+use crate::modules::hub::tokens::{AbugidaToken, AlphabetToken, HubFormat, TokenCategory};
+use crate::modules::hub::HubToken;
+use crate::modules::script_converter::ScriptConverterRegistry;
+use rustc_hash::FxHashSet;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ScaffoldError {
+    #[error("Unknown script type '{0}', expected 'brahmic' or 'roman'")]
+    UnknownScriptType(String),
+    #[error("Unknown script '{0}'")]
+    UnknownScript(String),
+    #[error("'{like}' is not a {script_type} script")]
+    ScriptTypeMismatch { like: String, script_type: String },
+}
+
+/// Skeleton YAML schema for a new `brahmic` or `roman` script
+///
+/// Every hub token of the script type is listed under its category with an
+/// empty value. With `like`, tokens that script reads are filled in with its
+/// rendering, so a related script can be started from an existing one.
+pub fn scaffold_schema(
+    name: &str,
+    script_type: &str,
+    like: Option<&str>,
+) -> Result<String, ScaffoldError> {
+    let format = match script_type {
+        "brahmic" => HubFormat::Abugida,
+        "roman" => HubFormat::Alphabet,
+        _ => return Err(ScaffoldError::UnknownScriptType(script_type.to_string())),
+    };
+
+    let registry = ScriptConverterRegistry::default();
+    let like = match like {
+        Some(script) => {
+            let converter = registry
+                .token_converter(script)
+                .ok_or_else(|| ScaffoldError::UnknownScript(script.to_string()))?;
+            if converter.is_alphabet() != (format == HubFormat::Alphabet) {
+                return Err(ScaffoldError::ScriptTypeMismatch {
+                    like: script.to_string(),
+                    script_type: script_type.to_string(),
+                });
+            }
+            Some(converter)
+        }
+        None => None,
+    };
+    let like_tokens: FxHashSet<HubToken> = like
+        .and_then(|converter| converter.mapped_tokens())
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+
+    let (target, processor_type) = match format {
+        HubFormat::Abugida => ("abugida_tokens", "indic_token_based"),
+        HubFormat::Alphabet => ("alphabet_tokens", "roman_token_based"),
+    };
+    let mut yaml = String::new();
+    yaml.push_str("# Fill in the script's text for each token and remove the tokens it lacks\n");
+    yaml.push_str("metadata:\n");
+    yaml.push_str(&format!("  name: {}\n", quote(name)));
+    yaml.push_str(&format!("  script_type: \"{script_type}\"\n"));
+    yaml.push_str(&format!(
+        "  has_implicit_a: {}\n",
+        format == HubFormat::Abugida
+    ));
+    yaml.push_str("  description: \"\"\n");
+    yaml.push_str(&format!("\ntarget: \"{target}\"\n\nmappings:\n"));
+
+    for (category, tokens) in format.inventory() {
+        if tokens.is_empty() {
+            continue;
+        }
+        yaml.push_str(&format!("  {}:\n", category.key()));
+        for &token in tokens.iter() {
+            let hub_token = match format {
+                HubFormat::Abugida => token.parse::<AbugidaToken>().map(HubToken::Abugida),
+                HubFormat::Alphabet => token.parse::<AlphabetToken>().map(HubToken::Alphabet),
+            };
+            let value = match (like, hub_token) {
+                (Some(converter), Ok(hub_token)) if like_tokens.contains(&hub_token) => {
+                    converter.tokens_to_string(&vec![hub_token])
+                }
+                _ => String::new(),
+            };
+            yaml.push_str(&format!("    {token}: {}\n", quote(&value)));
+        }
+        yaml.push('\n');
+    }
+
+    yaml.push_str(&format!(
+        "codegen:\n  processor_type: \"{processor_type}\"\n"
+    ));
+    Ok(yaml)
+}
+
+/// YAML double-quoted scalar for `text`
+pub(super) fn quote(text: &str) -> String {
+    // A JSON string is a valid YAML double-quoted scalar
+    serde_json::Value::String(text.to_string()).to_string()
+}
+
+/// Tokens mapped by one schema and not the other, as (category key, token name)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDiff {
+    pub only_in_first: Vec<(String, String)>,
+    pub only_in_second: Vec<(String, String)>,
+}
+
+impl SchemaDiff {
+    /// Whether both schemas map the same tokens
+    pub fn is_empty(&self) -> bool {
+        self.only_in_first.is_empty() && self.only_in_second.is_empty()
+    }
+}
+
+/// Compare the tokens two schemas map, category by category
+///
+/// Entries follow the category order of schema files and the token order of
+/// the hub inventory; names the inventory does not know come last.
+pub fn diff_schemas(first: &Schema, second: &Schema) -> SchemaDiff {
+    let missing_from = |schema: &Schema, other: &Schema| {
+        let mut missing: Vec<(String, String)> = schema
+            .mappings
+            .iter()
+            .flat_map(|(category, tokens)| {
+                tokens
+                    .keys()
+                    .filter(|token| {
+                        !other
+                            .mappings
+                            .get(category)
+                            .is_some_and(|other_tokens| other_tokens.contains_key(*token))
+                    })
+                    .map(move |token| (category.clone(), token.clone()))
+            })
+            .collect();
+        missing.sort_by_cached_key(|(category, token)| inventory_position(category, token));
+        missing
+    };
+
+    SchemaDiff {
+        only_in_first: missing_from(first, second),
+        only_in_second: missing_from(second, first),
+    }
+}
+
+/// Sort key placing a token in schema file order
+fn inventory_position(category: &str, token: &str) -> (usize, usize, String) {
+    let Some(category) = TokenCategory::from_key(category) else {
+        return (usize::MAX, usize::MAX, format!("{category}.{token}"));
+    };
+    let token_index = [HubFormat::Abugida, HubFormat::Alphabet]
+        .into_iter()
+        .flat_map(|format| format.inventory())
+        .filter(|(c, _)| *c == category)
+        .find_map(|(_, tokens)| tokens.iter().position(|t| *t == token))
+        .unwrap_or(usize::MAX);
+    (category as usize, token_index, token.to_string())
+}
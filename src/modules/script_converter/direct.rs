@@ -0,0 +1,146 @@
+//! Direct converters for common script pairs
+//!
+//! A direct converter turns text of one script straight into another, without
+//! the alias resolution and registry dispatch of the hub path. Pairs of the same
+//! token type (IAST → SLP1) are converted in a single replacement pass; the others
+//! (Devanagari → IAST) still run the hub's token conversion, called directly,
+//! and from an abugida to an alphabet stream their tokens through it one at a
+//! time rather than collecting them.
+//!
+//! Every table is taken from the generated token converters of the two scripts,
+//! so a direct converter produces exactly what the hub path produces.
+
+use super::ConverterError;
+#[cfg(feature = "scripts-core")]
+use crate::modules::hub::tokens::AlphabetToken;
+#[cfg(feature = "scripts-core")]
+use crate::modules::hub::trait_based_converter::{
+    AbugidaToAlphabetStream, NuktaLetters, Streamed, TraitBasedConverter,
+};
+#[cfg(feature = "scripts-core")]
+use aho_corasick::AhoCorasick;
+#[cfg(feature = "scripts-core")]
+use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
+
+/// Converts text of one script straight into another
+pub trait DirectConverter: Send + Sync {
+    /// Convert `input`, producing the same output as the hub path
+    fn convert(&self, input: &str) -> Result<String, ConverterError>;
+
+    /// Convert `input` onto the end of `output`
+    ///
+    /// Generated converters from an abugida to an alphabet stream their tokens
+    /// through the hub conversion, so they allocate nothing beyond the growth
+    /// of `output`; others write the output of [`Self::convert`].
+    fn convert_into(&self, input: &str, output: &mut String) -> Result<(), ConverterError> {
+        output.push_str(&self.convert(input)?);
+        Ok(())
+    }
+
+    /// Script the converter reads
+    #[allow(clippy::wrong_self_convention)]
+    fn from_script(&self) -> &'static str;
+
+    /// Script the converter writes
+    fn to_script(&self) -> &'static str;
+}
+
+// Every generated pair reads or writes a core script, so the helpers of the
+// generated converters are only built with `scripts-core`
+
+/// Output for one source pattern of a same-token-type pair
+#[cfg(feature = "scripts-core")]
+struct Replacement {
+    /// The target's rendering of the pattern's token
+    output: String,
+    /// Rendering directly after a, where the plain one would read as a diphthong
+    hiatus: Option<&'static str>,
+    /// Whether the pattern is the vowel a
+    is_a: bool,
+}
+
+/// Replace every source pattern in one pass, copying unmatched text through
+///
+/// `matcher` is the source script's leftmost-longest matcher, so matches fall
+/// where the tokenizer would put its tokens.
+#[cfg(feature = "scripts-core")]
+fn replace_patterns(input: &str, matcher: &AhoCorasick, replacements: &[Replacement]) -> String {
+    let mut output = String::with_capacity(input.len() + input.len() / 2);
+    let mut last = 0;
+    let mut after_a = false;
+
+    for mat in matcher.find_iter(input) {
+        if mat.start() > last {
+            // Unmatched characters are unknown tokens, which pass through
+            output.push_str(&input[last..mat.start()]);
+            after_a = false;
+        }
+        let replacement = &replacements[mat.pattern().as_usize()];
+        match replacement.hiatus.filter(|_| after_a) {
+            Some(form) => output.push_str(form),
+            None => output.push_str(&replacement.output),
+        }
+        after_a = replacement.is_a;
+        last = mat.end();
+    }
+    output.push_str(&input[last..]);
+
+    output
+}
+
+/// Registry of direct converters by source, then target script name
+#[derive(Default)]
+pub struct DirectConverterRegistry {
+    converters: FxHashMap<&'static str, FxHashMap<&'static str, Box<dyn DirectConverter>>>,
+}
+
+impl DirectConverterRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry holding every generated direct converter
+    pub fn with_generated_converters() -> Self {
+        let mut registry = Self::new();
+        for converter in generated_direct_converters() {
+            registry.register(converter);
+        }
+        registry
+    }
+
+    /// Register a converter under its source and target script names
+    pub fn register(&mut self, converter: Box<dyn DirectConverter>) {
+        self.converters
+            .entry(converter.from_script())
+            .or_default()
+            .insert(converter.to_script(), converter);
+    }
+
+    /// The direct converter for a pair of primary script names, if there is one
+    pub fn get_converter(&self, from: &str, to: &str) -> Option<&dyn DirectConverter> {
+        self.converters.get(from)?.get(to).map(|c| c.as_ref())
+    }
+
+    /// Drop every converter reading or writing `script`
+    pub fn remove_script(&mut self, script: &str) {
+        self.converters.remove(script);
+        for targets in self.converters.values_mut() {
+            targets.remove(script);
+        }
+    }
+
+    /// The (source, target) pairs with a direct converter, sorted
+    pub fn pairs(&self) -> Vec<(&'static str, &'static str)> {
+        let mut pairs: Vec<_> = self
+            .converters
+            .iter()
+            .flat_map(|(&from, targets)| targets.keys().map(move |&to| (from, to)))
+            .collect();
+        pairs.sort_unstable();
+        pairs
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/direct_converters_generated.rs"));
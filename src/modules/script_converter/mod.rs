@@ -1,6 +1,9 @@
-use crate::modules::core::unknown_handler::{TransliterationMetadata, TransliterationResult};
-use crate::modules::hub::{HubError, HubInput};
-use crate::modules::registry::SchemaRegistryTrait;
+use crate::modules::core::unknown_handler::{
+    FixedSpan, LossyMapping, SpanKind, TransliterationMetadata, TransliterationResult, UnknownToken,
+};
+use crate::modules::hub::{is_attached_mark, HubError, HubInput, AVAGRAHA_APOSTROPHES};
+use crate::modules::registry::{Schema, SchemaMatcher};
+use crate::modules::trace::stage;
 use rustc_hash::FxHashMap;
 use thiserror::Error;
 
@@ -138,6 +141,31 @@ pub trait TokenConverter: Send + Sync {
     /// Convert string to tokens
     fn string_to_tokens(&self, input: &str) -> HubTokenSequence;
 
+    /// Convert string to tokens, with the byte offset in `input` each token was read from
+    ///
+    /// Generated converters track positions exactly. The default finds unknown
+    /// tokens by their text and puts every other token where the last one found ended.
+    fn string_to_tokens_with_positions(&self, input: &str) -> (HubTokenSequence, Vec<usize>) {
+        let tokens = self.string_to_tokens(input);
+        let mut positions = Vec::with_capacity(tokens.len());
+        let mut cursor = 0;
+        for token in &tokens {
+            let unknown = match token {
+                HubToken::Abugida(AbugidaToken::Unknown(text))
+                | HubToken::Alphabet(AlphabetToken::Unknown(text)) => Some(text),
+                _ => None,
+            };
+            match unknown.and_then(|text| Some((input[cursor..].find(text.as_str())?, text))) {
+                Some((offset, text)) => {
+                    positions.push(cursor + offset);
+                    cursor += offset + text.len();
+                }
+                None => positions.push(cursor),
+            }
+        }
+        (tokens, positions)
+    }
+
     /// Convert tokens to string
     fn tokens_to_string(&self, tokens: &HubTokenSequence) -> String;
 
@@ -146,6 +174,52 @@ pub trait TokenConverter: Send + Sync {
 
     /// Get whether this converter handles alphabet tokens (Roman) or abugida tokens (Indic)
     fn is_alphabet(&self) -> bool;
+
+    /// Whether this script has an output for `token`
+    fn maps_token(&self, _token: &HubToken) -> bool {
+        true
+    }
+
+    /// The token written in place of `token` when this script cannot represent it
+    fn lossy_substitute(&self, _token: &HubToken) -> Option<HubToken> {
+        None
+    }
+
+    /// The tokens written in place of `token` when this script has no letter for it
+    fn fallback_tokens(&self, _token: &HubToken) -> Option<Vec<HubToken>> {
+        None
+    }
+
+    /// Every token this script reads from text, or `None` when that is not known
+    fn mapped_tokens(&self) -> Option<Vec<HubToken>> {
+        None
+    }
+
+    /// Every spelling of each token this script maps, the written one first,
+    /// or `None` when that is not known
+    fn spellings(&self) -> Option<Vec<(HubToken, &'static [&'static str])>> {
+        None
+    }
+
+    /// The script's spellings that depend on the neighbouring tokens, if any
+    fn spelling_rules(&self) -> Option<&'static crate::modules::rules::SchemaRules> {
+        None
+    }
+
+    /// Whether this script writes avagraha as an apostrophe, read as such only in context
+    ///
+    /// See `ApostropheAvagraha` for the context.
+    fn reads_apostrophe_avagraha(&self) -> bool {
+        false
+    }
+
+    /// Whether reading `input` may find anything but unknown characters
+    ///
+    /// Unknown characters pass through every conversion, so text this returns
+    /// `false` for is written unchanged in any script. The default assumes it may.
+    fn would_modify(&self, _input: &str) -> bool {
+        true
+    }
 }
 
 /// Registry for token-based converters
@@ -232,16 +306,268 @@ impl TokenConverterRegistry {
         self.script_to_converter.contains_key(script)
     }
 
+    /// Stop routing a script name to any token converter
+    pub fn remove_script(&mut self, script: &str) -> bool {
+        self.script_to_converter.remove(script).is_some()
+    }
+
     pub fn list_supported_scripts(&self) -> Vec<String> {
         self.script_to_converter.keys().cloned().collect()
     }
 
+    /// Lossy substitutions the script's converter applies to a token sequence
+    pub fn find_lossy_mappings(
+        &self,
+        script: &str,
+        tokens: &HubTokenSequence,
+    ) -> Vec<LossyMapping> {
+        let Some(&converter_index) = self.script_to_converter.get(script) else {
+            return Vec::new();
+        };
+        let converter = &self.converters[converter_index];
+
+        tokens
+            .iter()
+            .enumerate()
+            .filter_map(|(position, token)| {
+                let substitute = match converter.lossy_substitute(token) {
+                    Some(substitute) => token_name(&substitute),
+                    None => fallback_name(&converter.fallback_tokens(token)?),
+                };
+                Some(LossyMapping::new(
+                    script,
+                    &token_name(token),
+                    &substitute,
+                    position,
+                ))
+            })
+            .collect()
+    }
+
+    /// Tokens in the sequence that the script's converter has no output for
+    pub fn find_unmapped_tokens<'a>(
+        &'a self,
+        script: &'a str,
+        tokens: &'a HubTokenSequence,
+    ) -> impl Iterator<Item = UnknownToken> + 'a {
+        let converter = self
+            .script_to_converter
+            .get(script)
+            .map(|&converter_index| &self.converters[converter_index]);
+
+        tokens
+            .iter()
+            .enumerate()
+            .filter(move |(_, token)| {
+                converter.is_some_and(|converter| !converter.maps_token(token))
+            })
+            .map(move |(position, token)| {
+                UnknownToken::unmapped(script, &token_name(token), position)
+            })
+    }
+
     pub fn is_alphabet_script(&self, script: &str) -> bool {
         self.script_to_converter
             .get(script)
             .map(|&idx| self.converters[idx].is_alphabet())
             .unwrap_or(false)
     }
+
+    /// The converter a script name (or alias) routes to
+    pub fn get(&self, script: &str) -> Option<&dyn TokenConverter> {
+        self.script_to_converter
+            .get(script)
+            .map(|&idx| self.converters[idx].as_ref())
+    }
+
+    /// Primary names of the scripts still routed to a converter, without aliases
+    pub fn primary_scripts(&self) -> Vec<&'static str> {
+        let mut scripts: Vec<_> = self
+            .script_to_converter
+            .values()
+            .map(|&idx| self.converters[idx].script_name())
+            .filter(|name| self.supports_script(name))
+            .collect();
+        scripts.sort_unstable();
+        scripts.dedup();
+        scripts
+    }
+
+    /// The other names routed to the converter registered as `script`
+    pub fn aliases(&self, script: &str) -> Vec<String> {
+        let Some(&converter_index) = self.script_to_converter.get(script) else {
+            return Vec::new();
+        };
+        let mut aliases: Vec<String> = self
+            .script_to_converter
+            .iter()
+            .filter(|&(name, &idx)| idx == converter_index && name != script)
+            .map(|(name, _)| name.clone())
+            .collect();
+        aliases.sort();
+        aliases
+    }
+}
+
+pub(crate) fn token_name(token: &HubToken) -> String {
+    match token {
+        HubToken::Abugida(token) => token.to_string(),
+        HubToken::Alphabet(token) => token.to_string(),
+    }
+}
+
+/// The names of a fallback's tokens, joined as "ConsonantR + VowelSignI"
+pub(crate) fn fallback_name(tokens: &[HubToken]) -> String {
+    tokens
+        .iter()
+        .map(token_name)
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// Metadata reporting the characters `script` could not read from its input
+///
+/// Whitespace, ASCII punctuation and control characters pass through every
+/// script and are not reported. Format characters such as bidi controls also
+/// pass through, and are recorded as `SpanKind::FormatChar` fixed spans, as are
+/// unmapped marks of the Vedic blocks as `SpanKind::AttachedMark` spans.
+fn source_unknowns(
+    script: &str,
+    tokens: &HubTokenSequence,
+    positions: &[usize],
+    unknown_limit: Option<usize>,
+) -> TransliterationMetadata {
+    let mut metadata =
+        TransliterationMetadata::new(script, script).with_unknown_limit(unknown_limit);
+    for (token, &position) in tokens.iter().zip(positions) {
+        let (HubToken::Abugida(AbugidaToken::Unknown(text))
+        | HubToken::Alphabet(AlphabetToken::Unknown(text))) = token
+        else {
+            continue;
+        };
+        let Some(ch) = text.chars().next() else {
+            continue;
+        };
+        let kind = if is_format_char(ch) {
+            Some(SpanKind::FormatChar)
+        } else if is_attached_mark(ch) {
+            Some(SpanKind::AttachedMark)
+        } else {
+            None
+        };
+        if let Some(kind) = kind {
+            metadata.fixed_spans.push(FixedSpan {
+                kind,
+                input: text.clone(),
+                output: text.clone(),
+                position,
+            });
+        } else if !ch.is_whitespace()
+            && !ch.is_ascii_punctuation()
+            && !ch.is_control()
+            // Apostrophes not read as avagraha are punctuation too
+            && !AVAGRAHA_APOSTROPHES.contains(&text.as_str())
+        {
+            metadata.add_unknown(UnknownToken::new(script, ch, position, false));
+        }
+    }
+    metadata
+}
+
+/// The byte ranges of an input of `len` bytes that `tokens` were read from,
+/// leaving out unknown tokens, in order and with touching ranges joined
+///
+/// A token covers the input from its position to the next position any token
+/// was read at, so the tokens of one rule or conjunct share their text.
+pub(crate) fn converted_spans(
+    tokens: &[HubToken],
+    positions: &[usize],
+    len: usize,
+) -> Vec<std::ops::Range<usize>> {
+    let mut starts = positions.to_vec();
+    starts.sort_unstable();
+    starts.dedup();
+    let mut spans: Vec<_> = tokens
+        .iter()
+        .zip(positions)
+        .filter(|(token, _)| !token.is_unknown())
+        .map(|(_, &start)| {
+            let next = starts.partition_point(|&other| other <= start);
+            start..starts.get(next).copied().unwrap_or(len)
+        })
+        .collect();
+    spans.sort_unstable_by_key(|span| span.start);
+
+    let mut joined: Vec<std::ops::Range<usize>> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match joined.last_mut() {
+            Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+            _ => joined.push(span),
+        }
+    }
+    joined
+}
+
+/// Whether `ch` is a Unicode format character (general category Cf)
+///
+/// ZWNJ and ZWJ are left out: they shape conjuncts and are handled by the
+/// scripts' schemas.
+fn is_format_char(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{00AD}'
+            | '\u{0600}'..='\u{0605}'
+            | '\u{061C}'
+            | '\u{06DD}'
+            | '\u{070F}'
+            | '\u{0890}'..='\u{0891}'
+            | '\u{08E2}'
+            | '\u{180E}'
+            | '\u{200B}'
+            | '\u{200E}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{206F}'
+            | '\u{FEFF}'
+            | '\u{FFF9}'..='\u{FFFB}'
+            | '\u{110BD}'
+            | '\u{110CD}'
+            | '\u{13430}'..='\u{1343F}'
+            | '\u{1BCA0}'..='\u{1BCA3}'
+            | '\u{1D173}'..='\u{1D17A}'
+            | '\u{E0001}'
+            | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+/// Whether `script` names Devanagari, in any letter case
+fn is_devanagari_name(script: &str) -> bool {
+    script.eq_ignore_ascii_case("devanagari") || script.eq_ignore_ascii_case("deva")
+}
+
+/// `script`, or the name of the shipped schema declaring it as an alias
+fn builtin_script_name(script: &str) -> &str {
+    SCRIPT_ALIASES
+        .binary_search_by_key(&script, |&(alias, _)| alias)
+        .map_or(script, |index| SCRIPT_ALIASES[index].1)
+}
+
+/// Direction and rendering notes of the shipped schema of `script`
+///
+/// Scripts not in the shipped schemas are written left to right.
+pub(crate) fn builtin_layout(
+    script: &str,
+) -> (
+    crate::modules::registry::TextDirection,
+    Option<&'static str>,
+) {
+    let script = builtin_script_name(script);
+    SCRIPT_LAYOUTS
+        .binary_search_by_key(&script, |&(name, ..)| name)
+        .map_or(Default::default(), |index| {
+            let (_, direction, notes) = SCRIPT_LAYOUTS[index];
+            (direction, notes)
+        })
 }
 
 /// Registry for script converters
@@ -251,6 +577,8 @@ pub struct ScriptConverterRegistry {
     script_to_converter: FxHashMap<String, usize>,
     /// Token-based converter registry
     token_converters: TokenConverterRegistry,
+    /// Converters for common pairs that skip the hub dispatch
+    direct_converters: DirectConverterRegistry,
 }
 
 impl ScriptConverterRegistry {
@@ -259,15 +587,20 @@ impl ScriptConverterRegistry {
             converters: Vec::new(),
             script_to_converter: FxHashMap::default(),
             token_converters: TokenConverterRegistry::new(),
+            direct_converters: DirectConverterRegistry::new(),
         }
     }
 
     /// Register a script converter
+    ///
+    /// The most recent registration wins: any token converter previously handling
+    /// one of the converter's scripts stops being used for it.
     pub fn register_converter(&mut self, converter: Box<dyn ScriptConverter>) {
         let converter_index = self.converters.len();
 
         // Cache script mappings for fast lookup
         for script in converter.supported_scripts() {
+            self.token_converters.remove_script(script);
             self.script_to_converter
                 .insert(script.to_string(), converter_index);
         }
@@ -275,6 +608,147 @@ impl ScriptConverterRegistry {
         self.converters.push(converter);
     }
 
+    /// Register a token-based converter under its script name and the given aliases
+    ///
+    /// The most recent registration wins: any script converter previously handling
+    /// the script name or one of the aliases stops being used for it.
+    pub fn register_token_converter(
+        &mut self,
+        converter: Box<dyn TokenConverter>,
+        aliases: &[&str],
+    ) {
+        self.script_to_converter.remove(converter.script_name());
+        for alias in aliases {
+            self.script_to_converter.remove(*alias);
+        }
+        // Generated direct converters would bypass the replacement
+        self.direct_converters
+            .remove_script(converter.script_name());
+
+        self.token_converters
+            .register_converter_with_aliases(converter, aliases);
+    }
+
+    /// The direct converter for a pair, if both names route to generated converters
+    ///
+    /// Names are resolved the way the hub path resolves them, through schema
+    /// aliases and then token converter aliases, so `deva` → `iast` finds the
+    /// devanagari → iast converter.
+    pub fn direct_converter(
+        &self,
+        from: &str,
+        to: &str,
+        schema_registry: Option<&crate::modules::registry::SchemaRegistry>,
+    ) -> Option<&dyn DirectConverter> {
+        let primary_name = |script: &str| {
+            self.token_converters
+                .get(self.primary_name(script, schema_registry))
+                .map(|converter| converter.script_name())
+        };
+        self.direct_converters
+            .get_converter(primary_name(from)?, primary_name(to)?)
+    }
+
+    /// The primary name of the script a name or alias routes to
+    ///
+    /// Every lookup by script name resolves the name here, trying in turn
+    /// Devanagari's names, a token converter's name or alias, a hand-written
+    /// converter's name, a runtime schema's name or alias and an alias from the
+    /// shipped schemas. A primary name resolves to itself, so a name resolved
+    /// once can be passed on and resolved again without changing. Names nothing
+    /// answers to are returned as they are.
+    pub fn primary_name<'a>(
+        &'a self,
+        script: &'a str,
+        schema_registry: Option<&'a crate::modules::registry::SchemaRegistry>,
+    ) -> &'a str {
+        let stage = stage!("resolve_script", script, resolved = tracing::field::Empty);
+        let resolved = self.resolve_name(script, schema_registry);
+        stage.record("resolved", resolved);
+        resolved
+    }
+
+    fn resolve_name<'a>(
+        &'a self,
+        script: &'a str,
+        schema_registry: Option<&'a crate::modules::registry::SchemaRegistry>,
+    ) -> &'a str {
+        if is_devanagari_name(script) {
+            return "devanagari";
+        }
+        if let Some(converter) = self.token_converters.get(script) {
+            return converter.script_name();
+        }
+        if self.script_to_converter.contains_key(script) {
+            return script;
+        }
+        let runtime_name = |script: &'a str| schema_registry?.resolve_name(script);
+        if let Some(name) = runtime_name(script) {
+            // A runtime schema named after an alias of a token converter adds nothing
+            return self
+                .token_converters
+                .get(name)
+                .map_or(name, |converter| converter.script_name());
+        }
+        let builtin = builtin_script_name(script);
+        runtime_name(builtin).unwrap_or(builtin)
+    }
+
+    /// Whether converting `input` between two scripts copies it unchanged
+    ///
+    /// Known only for token converters: true when the source reads nothing from
+    /// `input`, whose characters then pass through as unknown tokens.
+    pub fn passes_through(&self, from: &str, to: &str, input: &str) -> bool {
+        self.token_converters.get(to).is_some()
+            && self
+                .token_converters
+                .get(from)
+                .is_some_and(|converter| !converter.would_modify(input))
+    }
+
+    /// The (source, target) pairs with a direct converter
+    pub fn direct_pairs(&self) -> Vec<(&'static str, &'static str)> {
+        self.direct_converters.pairs()
+    }
+
+    /// The token converter a script name (or alias) routes to, if it is token-based
+    pub fn token_converter(&self, script: &str) -> Option<&dyn TokenConverter> {
+        self.token_converters.get(script)
+    }
+
+    /// Scripts by their primary names, leaving out token converter aliases
+    pub fn primary_scripts(&self) -> Vec<String> {
+        let mut scripts: Vec<String> = self
+            .token_converters
+            .primary_scripts()
+            .into_iter()
+            .map(str::to_string)
+            .chain(self.script_to_converter.keys().cloned())
+            .collect();
+        scripts.sort();
+        scripts.dedup();
+        scripts
+    }
+
+    /// Aliases of a script registered under its primary name, in name order
+    pub fn aliases(&self, script: &str) -> Vec<String> {
+        self.token_converters.aliases(script)
+    }
+
+    /// Check whether a registered converter works with alphabet (Roman) hub tokens
+    ///
+    /// Returns `None` when no converter is registered under this exact name, so callers
+    /// can fall back to the schema-derived script classification.
+    pub fn is_alphabet_script(&self, script: &str) -> Option<bool> {
+        if self.token_converters.supports_script(script) {
+            return Some(self.token_converters.is_alphabet_script(script));
+        }
+
+        self.script_to_converter
+            .get(script)
+            .map(|&idx| !self.converters[idx].script_has_implicit_a(script))
+    }
+
     /// Convert text from any supported script to hub format
     pub fn to_hub(&self, script: &str, input: &str) -> Result<HubInput, ConverterError> {
         self.to_hub_with_schema_registry(script, input, None)
@@ -287,25 +761,14 @@ impl ScriptConverterRegistry {
         input: &str,
         schema_registry: Option<&crate::modules::registry::SchemaRegistry>,
     ) -> Result<HubInput, ConverterError> {
-        // Resolve script aliases using schema registry
-        let resolved_script = if let Some(registry) = schema_registry {
-            if let Some(schema) = registry.find_schema_by_alias(script) {
-                &schema.name
-            } else {
-                script
-            }
-        } else {
-            script
-        };
+        let script = self.primary_name(script, schema_registry);
 
         // Try token-based converters first
-        if self.token_converters.supports_script(resolved_script) {
-            let tokens = self
-                .token_converters
-                .convert_to_tokens(resolved_script, input)?;
+        if self.token_converters.supports_script(script) {
+            let tokens = self.token_converters.convert_to_tokens(script, input)?;
 
             // Convert tokens to appropriate hub format
-            let hub_format = if self.token_converters.is_alphabet_script(resolved_script) {
+            let hub_format = if self.token_converters.is_alphabet_script(script) {
                 HubFormat::AlphabetTokens(tokens)
             } else {
                 HubFormat::AbugidaTokens(tokens)
@@ -314,28 +777,14 @@ impl ScriptConverterRegistry {
             return Ok(hub_format);
         }
 
-        // Resolve aliases first (including schema registry aliases)
-        let canonical_script = self.resolve_script_alias_with_registry(script, schema_registry);
-
         // Fast lookup using HashMap cache instead of linear search
-        if let Some(&converter_index) = self.script_to_converter.get(&canonical_script) {
-            return self.converters[converter_index].to_hub(&canonical_script, input);
+        if let Some(&converter_index) = self.script_to_converter.get(script) {
+            return self.converters[converter_index].to_hub(script, input);
         }
 
         // Fallback: use runtime schema from registry as source
-        if let Some(registry) = schema_registry {
-            let lookup_name = if canonical_script != script {
-                canonical_script.as_str()
-            } else {
-                resolved_script
-            };
-            if let Some(schema) = registry.get_schema(lookup_name) {
-                return self.to_hub_from_runtime_schema(input, schema);
-            }
-            // Also try the original script name
-            if let Some(schema) = registry.get_schema(script) {
-                return self.to_hub_from_runtime_schema(input, schema);
-            }
+        if let Some(runtime) = self.runtime_schema(script, schema_registry) {
+            return Ok(self.to_hub_from_runtime_schema(input, runtime, None));
         }
 
         Err(ConverterError::ConversionFailed {
@@ -346,91 +795,150 @@ impl ScriptConverterRegistry {
 
     /// Convert input text to hub tokens using a runtime-loaded schema as the source.
     ///
-    /// The schema's `mappings` field maps token names (e.g. "VowelA") to script
-    /// characters (e.g. "a").  We invert this to build a char→token_name table,
-    /// then use longest-match parsing and `FromStr` on the generated token enums
-    /// to produce a proper `HubInput`.
+    /// The schema's matcher, built when the schema was registered, reads the
+    /// longest of its spellings at each position; characters it does not map
+    /// read as unknown tokens. The schema's read rules are tried before its
+    /// mappings. With `positions`, the byte offset each token was read from is
+    /// recorded.
     fn to_hub_from_runtime_schema(
         &self,
         input: &str,
-        schema: &crate::modules::registry::Schema,
-    ) -> Result<HubInput, ConverterError> {
-        use std::str::FromStr;
-
-        // Build reverse mapping: script_char → token_name
-        // Mappings in the registry are already flattened (one preferred value per token).
-        // Schemas can also carry multiple alternatives via their original YAML lists, but
-        // after flattening only the first (preferred) value is stored.  That is sufficient
-        // for source parsing because users writing in a particular scheme use the canonical
-        // form.  For aliases we accept alternate representations via the schema cache.
-        let mut reverse: rustc_hash::FxHashMap<&str, &str> = rustc_hash::FxHashMap::default();
-        for (token_name, char_value) in &schema.mappings {
-            // Insert; last-write-wins for duplicate char values (rare but harmless)
-            reverse.insert(char_value.as_str(), token_name.as_str());
-        }
-
-        // Sort candidate keys by descending byte length so we always try the
-        // longest match first (greedy / maximal munch).
-        let mut candidates: Vec<(&str, &str)> = reverse.iter().map(|(&k, &v)| (k, v)).collect();
-        candidates.sort_by_key(|b| std::cmp::Reverse(b.0.len()));
-
-        let is_alphabet = schema.metadata.script_type == "roman"
-            || schema.target == "alphabet_tokens"
-            || schema.target == "iso15919";
+        (schema, matcher): (&Schema, &SchemaMatcher),
+        mut positions: Option<&mut Vec<usize>>,
+    ) -> HubInput {
+        let is_alphabet = schema.is_alphabet();
+        let unknown = |ch: char| {
+            if is_alphabet {
+                HubToken::Alphabet(AlphabetToken::Unknown(ch.to_string()))
+            } else {
+                HubToken::Abugida(AbugidaToken::Unknown(ch.to_string()))
+            }
+        };
+        // The token the mappings read at a byte offset, for the context of read rules
+        let token_at = |at: usize| {
+            let rest = input.get(at..).filter(|rest| !rest.is_empty())?;
+            Some(match matcher.read_at(input, at) {
+                Some((token, _)) => token.clone(),
+                None => unknown(rest.chars().next()?),
+            })
+        };
 
         let mut tokens: HubTokenSequence = Vec::new();
-        let bytes = input.as_bytes();
         let len = input.len();
         let mut pos = 0usize;
 
         while pos < len {
-            let mut matched = false;
-            for &(pat, token_name) in &candidates {
-                let pat_len = pat.len();
-                if pos + pat_len <= len && &bytes[pos..pos + pat_len] == pat.as_bytes() {
-                    // Parse the token name into the appropriate enum
-                    let hub_token = if is_alphabet {
-                        match AlphabetToken::from_str(token_name) {
-                            Ok(t) => HubToken::Alphabet(t),
-                            Err(_) => {
-                                // Unknown token name – treat the matched chars as unknown
-                                HubToken::Alphabet(AlphabetToken::Unknown(pat.to_string()))
-                            }
-                        }
-                    } else {
-                        match AbugidaToken::from_str(token_name) {
-                            Ok(t) => HubToken::Abugida(t),
-                            Err(_) => HubToken::Abugida(AbugidaToken::Unknown(pat.to_string())),
-                        }
-                    };
-                    tokens.push(hub_token);
-                    pos += pat_len;
-                    matched = true;
-                    break;
+            if let Some((rule_tokens, rule_len)) =
+                schema.rules.read_at(input, pos, tokens.last(), token_at)
+            {
+                for token in rule_tokens {
+                    tokens.push(token.clone());
+                    if let Some(positions) = positions.as_deref_mut() {
+                        positions.push(pos);
+                    }
                 }
+                pos += rule_len;
+                continue;
             }
-
-            if !matched {
-                // Consume one Unicode scalar and emit an Unknown token
-                let rest = &input[pos..];
-                let ch = rest.chars().next().unwrap();
-                let unknown_str = ch.to_string();
-                if is_alphabet {
-                    tokens.push(HubToken::Alphabet(AlphabetToken::Unknown(unknown_str)));
-                } else {
-                    tokens.push(HubToken::Abugida(AbugidaToken::Unknown(unknown_str)));
+            if let Some(positions) = positions.as_deref_mut() {
+                positions.push(pos);
+            }
+            match matcher.read_at(input, pos) {
+                Some((token, matched)) => {
+                    tokens.push(token.clone());
+                    pos += matched;
+                }
+                None => {
+                    // Consume one Unicode scalar and emit an Unknown token
+                    let Some(ch) = input[pos..].chars().next() else {
+                        break;
+                    };
+                    tokens.push(unknown(ch));
+                    pos += ch.len_utf8();
                 }
-                pos += ch.len_utf8();
             }
         }
 
         if is_alphabet {
-            Ok(HubFormat::AlphabetTokens(tokens))
+            HubFormat::AlphabetTokens(tokens)
         } else {
-            Ok(HubFormat::AbugidaTokens(tokens))
+            crate::modules::hub::attach_unknowns(&mut tokens, positions.as_deref_mut());
+            crate::modules::hub::order_vowel_signs(&mut tokens, positions);
+            HubFormat::AbugidaTokens(tokens)
         }
     }
 
+    /// Render hub tokens with a runtime-loaded schema as the target
+    ///
+    /// Each token is written with the schema's write rules where one matches, and
+    /// otherwise with the schema's mapping for it. Tokens the schema does not map
+    /// are written as `[Token]`, as generated converters write them, and added to
+    /// `unmapped` as `TargetRendering` unknowns.
+    fn render_runtime_schema(
+        &self,
+        hub_input: &HubInput,
+        (schema, matcher): (&Schema, &SchemaMatcher),
+        unmapped: &mut TransliterationMetadata,
+    ) -> String {
+        let tokens = match hub_input {
+            HubFormat::AlphabetTokens(tokens) => tokens,
+            HubFormat::AbugidaTokens(tokens) => tokens,
+        };
+
+        let mut output = String::new();
+        let mut position = 0;
+        while position < tokens.len() {
+            if let Some((text, spelled)) = schema.rules.write_at(tokens, position) {
+                output.push_str(text);
+                position += spelled;
+                continue;
+            }
+            let (at, token) = (position, &tokens[position]);
+            position += 1;
+            if let HubToken::Abugida(AbugidaToken::Unknown(text))
+            | HubToken::Alphabet(AlphabetToken::Unknown(text)) = token
+            {
+                output.push_str(text);
+                continue;
+            }
+            match matcher.written(token) {
+                Some(text) => output.push_str(text),
+                None => {
+                    let name = token_name(token);
+                    output.push_str(&format!("[{name}]"));
+                    unmapped.add_unknown(UnknownToken::unmapped(&schema.name, &name, at));
+                }
+            }
+        }
+        output
+    }
+
+    /// The runtime-loaded schema a script name (or alias) refers to, with its matcher
+    /// The runtime schema that converts `script`, when no compiled-in
+    /// converter answers for the name first
+    pub fn runtime_schema_for<'r>(
+        &self,
+        script: &str,
+        schema_registry: Option<&'r crate::modules::registry::SchemaRegistry>,
+    ) -> Option<&'r Schema> {
+        let script = self.primary_name(script, schema_registry);
+        if self.token_converters.supports_script(script)
+            || self.script_to_converter.contains_key(script)
+        {
+            return None;
+        }
+        self.runtime_schema(script, schema_registry)
+            .map(|(schema, _)| schema)
+    }
+
+    fn runtime_schema<'r>(
+        &self,
+        script: &str,
+        schema_registry: Option<&'r crate::modules::registry::SchemaRegistry>,
+    ) -> Option<(&'r Schema, &'r SchemaMatcher)> {
+        schema_registry?.get_schema_with_matcher(self.primary_name(script, schema_registry))
+    }
+
     /// Convert text from hub format to any supported script (reverse conversion)
     pub fn from_hub(&self, script: &str, hub_input: &HubInput) -> Result<String, ConverterError> {
         self.from_hub_with_schema_registry(script, hub_input, None)
@@ -443,19 +951,10 @@ impl ScriptConverterRegistry {
         hub_input: &HubInput,
         schema_registry: Option<&crate::modules::registry::SchemaRegistry>,
     ) -> Result<String, ConverterError> {
-        // Resolve script aliases using schema registry
-        let resolved_script = if let Some(registry) = schema_registry {
-            if let Some(schema) = registry.find_schema_by_alias(script) {
-                &schema.name
-            } else {
-                script
-            }
-        } else {
-            script
-        };
+        let script = self.primary_name(script, schema_registry);
 
         // Try token-based converters first
-        if self.token_converters.supports_script(resolved_script) {
+        if self.token_converters.supports_script(script) {
             // Extract tokens from hub format
             let tokens = match hub_input {
                 HubFormat::AlphabetTokens(tokens) => tokens,
@@ -463,18 +962,21 @@ impl ScriptConverterRegistry {
             };
 
             // Convert tokens to string
-            let result = self
-                .token_converters
-                .convert_from_tokens(resolved_script, tokens)?;
+            let result = self.token_converters.convert_from_tokens(script, tokens)?;
             return Ok(result);
         }
 
-        // Resolve aliases first (including schema registry aliases)
-        let canonical_script = self.resolve_script_alias_with_registry(script, schema_registry);
-
         // Fast lookup using HashMap cache instead of linear search
-        if let Some(&converter_index) = self.script_to_converter.get(&canonical_script) {
-            return self.converters[converter_index].from_hub(&canonical_script, hub_input);
+        if let Some(&converter_index) = self.script_to_converter.get(script) {
+            return self.converters[converter_index].from_hub(script, hub_input);
+        }
+
+        // Fallback: use runtime schema from registry as target
+        if let Some(runtime @ (schema, _)) = self.runtime_schema(script, schema_registry) {
+            // Unmapped tokens are only written, not collected
+            let mut unmapped = TransliterationMetadata::new(&schema.name, &schema.name)
+                .with_unknown_limit(Some(0));
+            return Ok(self.render_runtime_schema(hub_input, runtime, &mut unmapped));
         }
 
         Err(ConverterError::ConversionFailed {
@@ -489,30 +991,88 @@ impl ScriptConverterRegistry {
         script: &str,
         input: &str,
     ) -> Result<(HubInput, TransliterationMetadata), ConverterError> {
+        self.to_hub_with_metadata_with_schema_registry(script, input, None)
+    }
+
+    /// Convert text to hub format with metadata collection and optional schema registry
+    pub fn to_hub_with_metadata_with_schema_registry(
+        &self,
+        script: &str,
+        input: &str,
+        schema_registry: Option<&crate::modules::registry::SchemaRegistry>,
+    ) -> Result<(HubInput, TransliterationMetadata), ConverterError> {
+        self.to_hub_with_positions_with_schema_registry(script, input, schema_registry, None)
+            .map(|(hub_input, _, metadata)| (hub_input, metadata))
+    }
+
+    /// Convert text to hub format with metadata, and the byte offset each hub token was read from
+    ///
+    /// Characters the script cannot read are reported as `SourceParsing` unknowns,
+    /// except whitespace, ASCII punctuation and control characters, which every
+    /// script passes through. Format characters are reported as fixed spans.
+    /// Positions are only known for token-based scripts and runtime schemas.
+    pub fn to_hub_with_positions(
+        &self,
+        script: &str,
+        input: &str,
+    ) -> Result<(HubInput, Option<Vec<usize>>, TransliterationMetadata), ConverterError> {
+        self.to_hub_with_positions_with_schema_registry(script, input, None, None)
+    }
+
+    /// [`Self::to_hub_with_positions`] with optional schema registry
+    ///
+    /// Script names are resolved as [`Self::to_hub_with_schema_registry`] resolves them.
+    /// The metadata keeps at most `unknown_limit` unknown tokens, counting the rest.
+    pub fn to_hub_with_positions_with_schema_registry(
+        &self,
+        script: &str,
+        input: &str,
+        schema_registry: Option<&crate::modules::registry::SchemaRegistry>,
+        unknown_limit: Option<usize>,
+    ) -> Result<(HubInput, Option<Vec<usize>>, TransliterationMetadata), ConverterError> {
+        let script = self.primary_name(script, schema_registry);
+
         // Try token-based converters first
-        if self.token_converters.supports_script(script) {
-            let tokens = self.token_converters.convert_to_tokens(script, input)?;
+        if let Some(converter) = self.token_converters.get(script) {
+            let (tokens, positions) = converter.string_to_tokens_with_positions(input);
+            let mut metadata = source_unknowns(script, &tokens, &positions, unknown_limit);
+            match converter.script_name() {
+                "devanagari" => {
+                    metadata.normalized_spans =
+                        crate::modules::orthography::prishthamatra_spans(input);
+                }
+                "telugu" => {
+                    metadata.normalized_spans =
+                        crate::modules::orthography::nakara_pollu_spans(input);
+                }
+                _ => {}
+            }
 
             // Convert tokens to appropriate hub format
-            let hub_format = if self.token_converters.is_alphabet_script(script) {
+            let hub_format = if converter.is_alphabet() {
                 HubFormat::AlphabetTokens(tokens)
             } else {
                 HubFormat::AbugidaTokens(tokens)
             };
 
-            // Create basic metadata for script → hub conversion
-            let metadata = TransliterationMetadata::new(script, script);
-
-            return Ok((hub_format, metadata));
+            return Ok((hub_format, Some(positions), metadata));
         }
 
         // Fast lookup using HashMap cache instead of linear search
         if let Some(&converter_index) = self.script_to_converter.get(script) {
-            return self.converters[converter_index].to_hub_with_metadata(script, input);
+            let (hub_input, metadata) =
+                self.converters[converter_index].to_hub_with_metadata(script, input)?;
+            return Ok((hub_input, None, metadata.with_unknown_limit(unknown_limit)));
         }
 
-        // The metadata methods would also need schema registry support
-        // For now, keeping original error until we add schema support here too
+        // Fallback: use runtime schema from registry as source
+        if let Some(runtime @ (schema, _)) = self.runtime_schema(script, schema_registry) {
+            let mut positions = Vec::new();
+            let hub_input = self.to_hub_from_runtime_schema(input, runtime, Some(&mut positions));
+            let metadata =
+                source_unknowns(&schema.name, hub_input.tokens(), &positions, unknown_limit);
+            return Ok((hub_input, Some(positions), metadata));
+        }
 
         Err(ConverterError::ConversionFailed {
             script: script.to_string(),
@@ -526,6 +1086,23 @@ impl ScriptConverterRegistry {
         script: &str,
         hub_input: &HubInput,
     ) -> Result<TransliterationResult, ConverterError> {
+        self.from_hub_with_metadata_with_schema_registry(script, hub_input, None, None)
+    }
+
+    /// Convert from hub format with metadata collection and optional schema registry
+    ///
+    /// Script names are resolved as [`Self::from_hub_with_schema_registry`] resolves
+    /// them, so both produce the same output. The metadata keeps at most
+    /// `unknown_limit` unknown tokens, counting the rest.
+    pub fn from_hub_with_metadata_with_schema_registry(
+        &self,
+        script: &str,
+        hub_input: &HubInput,
+        schema_registry: Option<&crate::modules::registry::SchemaRegistry>,
+        unknown_limit: Option<usize>,
+    ) -> Result<TransliterationResult, ConverterError> {
+        let script = self.primary_name(script, schema_registry);
+
         // Try token-based converters first
         if self.token_converters.supports_script(script) {
             // Extract tokens from hub format
@@ -538,7 +1115,14 @@ impl ScriptConverterRegistry {
             let result = self.token_converters.convert_from_tokens(script, tokens)?;
 
             // Create basic metadata for hub → script conversion
-            let metadata = TransliterationMetadata::new(script, script);
+            let mut metadata =
+                TransliterationMetadata::new(script, script).with_unknown_limit(unknown_limit);
+            for token in self.token_converters.find_unmapped_tokens(script, tokens) {
+                metadata.add_unknown(token);
+            }
+            for mapping in self.token_converters.find_lossy_mappings(script, tokens) {
+                metadata.add_lossy(mapping);
+            }
 
             return Ok(TransliterationResult {
                 output: result,
@@ -546,17 +1130,26 @@ impl ScriptConverterRegistry {
             });
         }
 
-        // Resolve aliases first (hardcoded only, no schema registry available here)
-        let canonical_script = self.resolve_script_alias(script);
-
         // Fast lookup using HashMap cache instead of linear search
-        if let Some(&converter_index) = self.script_to_converter.get(canonical_script) {
-            return self.converters[converter_index]
-                .from_hub_with_metadata(canonical_script, hub_input);
+        if let Some(&converter_index) = self.script_to_converter.get(script) {
+            let mut result =
+                self.converters[converter_index].from_hub_with_metadata(script, hub_input)?;
+            result.metadata = result
+                .metadata
+                .map(|metadata| metadata.with_unknown_limit(unknown_limit));
+            return Ok(result);
         }
 
-        // The metadata methods would also need schema registry support
-        // For now, keeping original error until we add schema support here too
+        // Fallback: use runtime schema from registry as target
+        if let Some(runtime @ (schema, _)) = self.runtime_schema(script, schema_registry) {
+            let mut metadata = TransliterationMetadata::new(&schema.name, &schema.name)
+                .with_unknown_limit(unknown_limit);
+            let output = self.render_runtime_schema(hub_input, runtime, &mut metadata);
+            return Ok(TransliterationResult {
+                output,
+                metadata: Some(metadata),
+            });
+        }
 
         Err(ConverterError::ConversionFailed {
             script: script.to_string(),
@@ -569,86 +1162,17 @@ impl ScriptConverterRegistry {
         self.supports_script_with_registry(script, None)
     }
 
-    /// Check if a script is supported by any converter (with schema registry for alias resolution)
+    /// Check if a script is supported by any converter or by a runtime schema
+    /// of `schema_registry`, resolving names as [`Self::primary_name`] does
     pub fn supports_script_with_registry(
         &self,
         script: &str,
         schema_registry: Option<&crate::modules::registry::SchemaRegistry>,
     ) -> bool {
-        // Special case: Devanagari is always supported (hub format)
-        if script.to_lowercase() == "devanagari" || script.to_lowercase() == "deva" {
-            return true;
-        }
-
-        // Check direct script name first
-        if self.script_to_converter.contains_key(script)
-            || self.token_converters.supports_script(script)
-        {
-            return true;
-        }
-
-        // Resolve script aliases using schema registry
-        let resolved_script = if let Some(registry) = schema_registry {
-            if let Some(schema) = registry.find_schema_by_alias(script) {
-                &schema.name
-            } else {
-                // Fallback to hardcoded aliases for built-in converters
-                self.resolve_script_alias(script)
-            }
-        } else {
-            // No schema registry available, use hardcoded aliases only
-            self.resolve_script_alias(script)
-        };
-
-        // Check resolved script name
-        if resolved_script != script {
-            self.script_to_converter.contains_key(resolved_script)
-                || self.token_converters.supports_script(resolved_script)
-        } else {
-            false
-        }
-    }
-
-    /// Resolve script aliases to canonical script names
-    fn resolve_script_alias<'a>(&self, script: &'a str) -> &'a str {
-        // Check hardcoded aliases first for built-in converters
-        match script {
-            "hk" => "harvard_kyoto",
-            "bn" => "bengali",
-            "ta" => "tamil",
-            "te" => "telugu",
-            "gu" => "gujarati",
-            "kn" => "kannada",
-            "ml" => "malayalam",
-            "or" => "odia",
-            "pa" => "gurmukhi",
-            "si" => "sinhala",
-            "deva" => "devanagari",
-            "iso" => "iso15919",
-            _ => script,
-        }
-    }
-
-    /// Resolve script aliases using schema registry
-    fn resolve_script_alias_with_registry(
-        &self,
-        script: &str,
-        schema_registry: Option<&crate::modules::registry::SchemaRegistry>,
-    ) -> String {
-        // First try hardcoded aliases
-        let resolved = self.resolve_script_alias(script);
-        if resolved != script {
-            return resolved.to_string();
-        }
-
-        // If no hardcoded alias found and we have a schema registry, check for schema aliases
-        if let Some(registry) = schema_registry {
-            if let Some(schema) = registry.find_schema_by_alias(script) {
-                return schema.name.clone();
-            }
-        }
-
-        script.to_string()
+        let script = self.primary_name(script, schema_registry);
+        self.token_converters.supports_script(script)
+            || self.script_to_converter.contains_key(script)
+            || self.runtime_schema(script, schema_registry).is_some()
     }
 
     /// Get all supported scripts across all converters
@@ -667,12 +1191,11 @@ impl ScriptConverterRegistry {
     /// Check if a converter supports bidirectional conversion for a specific script
     pub fn supports_reverse_conversion(&self, script: &str) -> bool {
         // Special case: Devanagari always supports reverse conversion (hub format)
-        if script.to_lowercase() == "devanagari" || script.to_lowercase() == "deva" {
+        if is_devanagari_name(script) {
             return true;
         }
 
-        // Resolve aliases first (hardcoded only, no schema registry available here)
-        let canonical_script = self.resolve_script_alias(script);
+        let canonical_script = self.primary_name(script, None);
 
         // Fast lookup using HashMap cache
         if let Some(&converter_index) = self.script_to_converter.get(canonical_script) {
@@ -684,21 +1207,25 @@ impl ScriptConverterRegistry {
 
     /// Check if a script has implicit 'a' vowel in consonants
     pub fn script_has_implicit_a(&self, script: &str) -> bool {
-        // Special case: Devanagari always has implicit 'a' vowels
-        if script.to_lowercase() == "devanagari" || script.to_lowercase() == "deva" {
-            return true;
-        }
-
-        // Resolve aliases first (hardcoded only, no schema registry available here)
-        let canonical_script = self.resolve_script_alias(script);
+        self.script_has_implicit_a_with_registry(script, None)
+    }
 
-        // Fast lookup using HashMap cache
-        if let Some(&converter_index) = self.script_to_converter.get(canonical_script) {
-            return self.converters[converter_index].script_has_implicit_a(canonical_script);
+    /// Check if a script, or a runtime schema of `schema_registry`, has implicit 'a'
+    ///
+    /// Token converters have it when they read abugida tokens, other converters
+    /// say so themselves, and runtime schemas as their `has_implicit_a` or token
+    /// target declares. Unknown scripts do not.
+    pub fn script_has_implicit_a_with_registry(
+        &self,
+        script: &str,
+        schema_registry: Option<&crate::modules::registry::SchemaRegistry>,
+    ) -> bool {
+        let script = self.primary_name(script, schema_registry);
+        if let Some(is_alphabet) = self.is_alphabet_script(script) {
+            return !is_alphabet;
         }
-
-        // Default to false for unknown scripts
-        false
+        self.runtime_schema(script, schema_registry)
+            .is_some_and(|(schema, _)| !schema.is_alphabet())
     }
 
     /// Get converter statistics and capabilities
@@ -741,17 +1268,19 @@ impl ScriptConverterRegistry {
         register_schema_generated_converters(&mut registry);
 
         // Register token-based converters with their aliases from schemas
-        for (converter, aliases) in register_token_converters_with_aliases() {
-            if aliases.is_empty() {
-                registry.token_converters.register_converter(converter);
-            } else {
-                let alias_refs: Vec<&str> = aliases.iter().map(|s| s.as_str()).collect();
-                registry
-                    .token_converters
-                    .register_converter_with_aliases(converter, &alias_refs);
-            }
+        for converter in register_token_converters() {
+            let aliases: Vec<&str> = SCRIPT_ALIASES
+                .iter()
+                .filter(|&&(_, name)| name == converter.script_name())
+                .map(|&(alias, _)| alias)
+                .collect();
+            registry
+                .token_converters
+                .register_converter_with_aliases(converter, &aliases);
         }
 
+        registry.direct_converters = DirectConverterRegistry::with_generated_converters();
+
         registry
     }
 
@@ -764,29 +1293,30 @@ impl ScriptConverterRegistry {
     /// Get information about whether a script has implicit vowels
     pub fn script_has_implicit_vowels(&self, script: &str) -> Result<bool, ConverterError> {
         // Special case: Devanagari (hub format) always has implicit 'a' vowels
-        if script.to_lowercase() == "devanagari" || script.to_lowercase() == "deva" {
+        if is_devanagari_name(script) {
             return Ok(true);
         }
 
-        // Resolve aliases first (hardcoded only, no schema registry available here)
-        let canonical_script = self.resolve_script_alias(script);
+        let canonical_script = self.primary_name(script, None);
 
-        // Fast lookup using HashMap cache instead of linear search
-        if let Some(&converter_index) = self.script_to_converter.get(canonical_script) {
-            return Ok(self.converters[converter_index].script_has_implicit_a(canonical_script));
+        match self.is_alphabet_script(canonical_script) {
+            Some(is_alphabet) => Ok(!is_alphabet),
+            None => Err(ConverterError::ConversionFailed {
+                script: script.to_string(),
+                reason: "Script not supported".to_string(),
+            }),
         }
-
-        Err(ConverterError::ConversionFailed {
-            script: script.to_string(),
-            reason: "Script not supported".to_string(),
-        })
     }
 }
 
 // Submodules for specific script converters
+// Direct converters for common pairs
+pub mod direct;
 // Shared processing logic
 pub mod processors;
 
+pub use direct::{DirectConverter, DirectConverterRegistry};
+
 // Include generated schema-based converters
 include!(concat!(env!("OUT_DIR"), "/schema_generated.rs"));
 
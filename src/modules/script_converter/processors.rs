@@ -123,7 +123,7 @@ impl RomanScriptProcessor {
                         &remaining[..end_pos]
                     } else {
                         // Single character case - take first character
-                        &remaining[..remaining.chars().next().unwrap().len_utf8()]
+                        &remaining[..ch.len_utf8()]
                     };
 
                     if let Some(&mapped_str) = mapping.get(seq) {
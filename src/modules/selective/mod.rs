@@ -0,0 +1,129 @@
+//! Picking out the Sanskrit spans of mixed-language Roman text
+//!
+//! Text such as "the word dharmaḥ means…" is split into words: runs of
+//! letters, digits and combining marks, with an apostrophe allowed between two
+//! letters (so'ham). A word is converted if it is on the policy's word list or if
+//! the caller recognises it as written in the source scheme. Qualifying words
+//! separated only by whitespace form one span; everything else is left as is.
+
+use rustc_hash::FxHashSet;
+use std::ops::Range;
+
+/// Which words of mixed-language text to transliterate
+///
+/// Words carrying characters of the source scheme (ā, ṛ, ś in IAST) always
+/// qualify. ASCII-only words such as "dharma" are indistinguishable from
+/// English, so they qualify only when listed. Matching is case-sensitive, as
+/// Roman schemes like SLP1 and ITRANS are.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectivePolicy {
+    words: FxHashSet<String>,
+}
+
+impl SelectivePolicy {
+    /// A policy that converts only words carrying scheme-specific characters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also convert each of `words` wherever it appears as a whole word
+    pub fn with_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.words.extend(words.into_iter().map(Into::into));
+        self
+    }
+
+    /// Parse a word list: one word per line, blank lines and `#` comments ignored
+    pub fn from_word_list(list: &str) -> Self {
+        Self::new().with_words(
+            list.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#')),
+        )
+    }
+
+    /// Add a single word to the list
+    pub fn add_word(&mut self, word: impl Into<String>) {
+        self.words.insert(word.into());
+    }
+
+    /// Whether `word` is on the list
+    pub fn contains_word(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+
+    /// Byte ranges of `text` to transliterate
+    ///
+    /// `in_scheme` is asked about each word not on the list. The ranges are in
+    /// order, do not overlap, and start and end on words.
+    pub fn spans(&self, text: &str, mut in_scheme: impl FnMut(&str) -> bool) -> Vec<Range<usize>> {
+        let mut spans: Vec<Range<usize>> = Vec::new();
+
+        for word in words(text) {
+            let word_text = &text[word.clone()];
+            if !self.contains_word(word_text) && !in_scheme(word_text) {
+                continue;
+            }
+            match spans.last_mut() {
+                Some(span) if text[span.end..word.start].chars().all(char::is_whitespace) => {
+                    span.end = word.end;
+                }
+                _ => spans.push(word),
+            }
+        }
+        spans
+    }
+}
+
+/// Byte ranges of the words in `text`
+pub fn words(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let in_word = if is_word_char(c) {
+            true
+        } else if is_apostrophe(c) && start.is_some() {
+            chars.peek().is_some_and(|&(_, next)| is_word_char(next))
+        } else {
+            false
+        };
+
+        match (in_word, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                words.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push(s..text.len());
+    }
+    words
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || is_combining_mark(c)
+}
+
+fn is_apostrophe(c: char) -> bool {
+    matches!(c, '\'' | '\u{2019}')
+}
+
+/// Combining marks used for Roman diacritics and Vedic accents
+fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
@@ -0,0 +1,151 @@
+//! Conversion counters kept by every `Shlesha` instance
+//!
+//! Unlike the profiler, the counters are always on: a conversion adds to a few
+//! atomics, with no lock for pairs of built-in scripts. They are compiled out
+//! when the `stats` feature is disabled, and `Shlesha::stats` then reports zeros.
+
+use std::collections::BTreeMap;
+
+/// Counts of the conversions an instance has carried out
+///
+/// Conversions that failed, such as those naming an unknown script, are only
+/// counted in `errors`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConversionStats {
+    /// Successful conversions
+    pub conversions: u64,
+    /// Bytes of input converted
+    pub input_bytes: u64,
+    /// Bytes of output written
+    pub output_bytes: u64,
+    /// Conversions that returned an error
+    pub errors: u64,
+    /// Successful conversions by (source, target), under the scripts' primary names
+    pub pairs: BTreeMap<(String, String), u64>,
+    /// Conversions answered from the result cache, if it is enabled
+    pub cache_hits: u64,
+    /// Conversions of strings the result cache would keep that it did not have
+    pub cache_misses: u64,
+    /// Optimization tables loaded now, which `reset_stats` leaves as they are
+    pub optimization_tables: usize,
+    /// Sequence and word mappings across those tables
+    pub optimization_entries: usize,
+}
+
+#[cfg(feature = "stats")]
+pub(crate) use counters::StatsCounters;
+
+#[cfg(feature = "stats")]
+mod counters {
+    use super::ConversionStats;
+    use rustc_hash::FxHashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Mutex;
+
+    /// The counters behind `ConversionStats`
+    pub(crate) struct StatsCounters {
+        conversions: AtomicU64,
+        input_bytes: AtomicU64,
+        output_bytes: AtomicU64,
+        errors: AtomicU64,
+        /// Index of each script known when the counters were created
+        scripts: FxHashMap<String, usize>,
+        /// Conversions by pair of known scripts, `from * scripts.len() + to`
+        pair_counts: Box<[AtomicU64]>,
+        /// Conversions by pairs involving scripts added later, such as runtime schemas
+        other_pairs: Mutex<FxHashMap<(String, String), u64>>,
+    }
+
+    impl StatsCounters {
+        /// Counters with a lock-free slot for each pair of `scripts`
+        pub(crate) fn new(scripts: Vec<String>) -> Self {
+            let pair_count = scripts.len() * scripts.len();
+            Self {
+                conversions: AtomicU64::new(0),
+                input_bytes: AtomicU64::new(0),
+                output_bytes: AtomicU64::new(0),
+                errors: AtomicU64::new(0),
+                scripts: scripts
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, script)| (script, index))
+                    .collect(),
+                pair_counts: (0..pair_count).map(|_| AtomicU64::new(0)).collect(),
+                other_pairs: Mutex::new(FxHashMap::default()),
+            }
+        }
+
+        /// Count a successful conversion between two scripts, by primary name
+        pub(crate) fn record(&self, from: &str, to: &str, input_bytes: usize, output_bytes: usize) {
+            self.conversions.fetch_add(1, Ordering::Relaxed);
+            self.input_bytes
+                .fetch_add(input_bytes as u64, Ordering::Relaxed);
+            self.output_bytes
+                .fetch_add(output_bytes as u64, Ordering::Relaxed);
+
+            match (self.scripts.get(from), self.scripts.get(to)) {
+                (Some(&from), Some(&to)) => {
+                    self.pair_counts[from * self.scripts.len() + to]
+                        .fetch_add(1, Ordering::Relaxed);
+                }
+                _ => {
+                    let mut other_pairs =
+                        self.other_pairs.lock().unwrap_or_else(|e| e.into_inner());
+                    *other_pairs
+                        .entry((from.to_string(), to.to_string()))
+                        .or_default() += 1;
+                }
+            }
+        }
+
+        /// Count a conversion that returned an error
+        pub(crate) fn record_error(&self) {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+
+        pub(crate) fn snapshot(&self) -> ConversionStats {
+            let mut pairs = std::collections::BTreeMap::new();
+            let width = self.scripts.len();
+            for (from, &from_index) in &self.scripts {
+                for (to, &to_index) in &self.scripts {
+                    let count =
+                        self.pair_counts[from_index * width + to_index].load(Ordering::Relaxed);
+                    if count > 0 {
+                        pairs.insert((from.clone(), to.clone()), count);
+                    }
+                }
+            }
+            let other_pairs = self.other_pairs.lock().unwrap_or_else(|e| e.into_inner());
+            for (pair, &count) in other_pairs.iter() {
+                pairs.insert(pair.clone(), count);
+            }
+
+            ConversionStats {
+                conversions: self.conversions.load(Ordering::Relaxed),
+                input_bytes: self.input_bytes.load(Ordering::Relaxed),
+                output_bytes: self.output_bytes.load(Ordering::Relaxed),
+                errors: self.errors.load(Ordering::Relaxed),
+                pairs,
+                ..ConversionStats::default()
+            }
+        }
+
+        pub(crate) fn reset(&self) {
+            for counter in [
+                &self.conversions,
+                &self.input_bytes,
+                &self.output_bytes,
+                &self.errors,
+            ] {
+                counter.store(0, Ordering::Relaxed);
+            }
+            for counter in self.pair_counts.iter() {
+                counter.store(0, Ordering::Relaxed);
+            }
+            self.other_pairs
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clear();
+        }
+    }
+}
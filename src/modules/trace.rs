@@ -0,0 +1,118 @@
+//! Tracing instrumentation of the conversion stages
+//!
+//! With the `tracing` feature each stage (script resolution, to-hub conversion,
+//! hub conversion, from-hub rendering, optimization cache lookup) runs inside a
+//! debug-level span carrying the script names, sizes and the stage's duration in
+//! microseconds, and failed conversions and schemas `Shlesha::new` could not
+//! load are reported with `tracing::warn!`.
+//! Without the feature [`stage!`] expands to a zero-sized guard and nothing is
+//! recorded.
+
+/// Longest input sample included in failure warnings, in characters
+#[cfg(feature = "tracing")]
+const SAMPLE_CHARS: usize = 32;
+
+/// An entered stage span, recording its duration when dropped
+pub(crate) struct Stage {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    // std::time::Instant is not available in WASM
+    #[cfg(all(feature = "tracing", not(target_arch = "wasm32")))]
+    start: std::time::Instant,
+}
+
+impl Stage {
+    /// Enter `span`, which must declare a `duration_us` field
+    #[cfg(feature = "tracing")]
+    pub(crate) fn enter(span: tracing::Span) -> Self {
+        Self {
+            span: span.entered(),
+            #[cfg(not(target_arch = "wasm32"))]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// A guard that records nothing
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn disabled() -> Self {
+        Self {}
+    }
+
+    /// Leave the stage before the end of the enclosing scope
+    pub(crate) fn finish(self) {}
+
+    /// Record a value for a field declared (as `tracing::field::Empty`) on the span
+    #[cfg(feature = "tracing")]
+    pub(crate) fn record(&self, field: &'static str, value: impl tracing::Value) {
+        self.span.record(field, value);
+    }
+
+    /// Record a value for a field declared (as `tracing::field::Empty`) on the span
+    #[cfg(not(feature = "tracing"))]
+    #[inline(always)]
+    pub(crate) fn record<T>(&self, _field: &'static str, _value: T) {}
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for Stage {
+    fn drop(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.span
+            .record("duration_us", self.start.elapsed().as_micros() as u64);
+    }
+}
+
+/// Enter a span for a conversion stage, e.g. `stage!("to_hub", script = from)`
+///
+/// Fields are only evaluated with the `tracing` feature; fields to be filled in
+/// later with [`Stage::record`] are declared as `tracing::field::Empty`.
+macro_rules! stage {
+    ($name:literal $(, $($fields:tt)*)?) => {{
+        #[cfg(feature = "tracing")]
+        let stage = $crate::modules::trace::Stage::enter(tracing::debug_span!(
+            $name,
+            $($($fields)*,)?
+            duration_us = tracing::field::Empty
+        ));
+        #[cfg(not(feature = "tracing"))]
+        let stage = $crate::modules::trace::Stage::disabled();
+        stage
+    }};
+}
+pub(crate) use stage;
+
+/// Warn that converting `text` from `from` to `to` failed
+///
+/// The warning carries the scripts, the input length and the first characters
+/// of the input, or `<redacted>` in their place when `redact_input` is set.
+#[cfg(feature = "tracing")]
+pub(crate) fn conversion_failed(
+    from: &str,
+    to: &str,
+    text: &str,
+    redact_input: bool,
+    error: &dyn std::fmt::Display,
+) {
+    let sample = if redact_input {
+        "<redacted>".to_string()
+    } else {
+        match text.char_indices().nth(SAMPLE_CHARS) {
+            Some((end, _)) => format!("{}…", &text[..end]),
+            None => text.to_string(),
+        }
+    };
+    tracing::warn!(
+        from,
+        to,
+        input_len = text.len(),
+        input_sample = %sample,
+        %error,
+        "transliteration failed"
+    );
+}
+
+/// Warn that a schema `Shlesha::new` loads could not be loaded, and was left out
+#[cfg(feature = "tracing")]
+pub(crate) fn initialization_failed(error: &dyn std::fmt::Display) {
+    tracing::warn!(%error, "schema left out of the transliterator");
+}
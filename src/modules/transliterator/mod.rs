@@ -0,0 +1,78 @@
+//! Conversion behind a trait object, for applications that inject it
+//!
+//! Code that only converts text can hold an `Arc<dyn Transliterator>` rather
+//! than a `Shlesha`, and be given a test double, or a client of a remote
+//! service, in its place. The trait is the two calls such code needs, and
+//! reports failures as `ShleshaError` so that every implementation fails the
+//! same way.
+//!
+//! ```
+//! use shlesha::{Shlesha, ShleshaError, Transliterator};
+//! use std::sync::Arc;
+//!
+//! struct Glossary {
+//!     transliterator: Arc<dyn Transliterator>,
+//! }
+//!
+//! impl Glossary {
+//!     fn entry(&self, word: &str) -> Result<String, ShleshaError> {
+//!         let roman = self.transliterator.transliterate(word, "devanagari", "iast")?;
+//!         Ok(format!("{word} ({roman})"))
+//!     }
+//! }
+//!
+//! let glossary = Glossary {
+//!     transliterator: Arc::new(Shlesha::new()),
+//! };
+//! assert_eq!(glossary.entry("धर्म")?, "धर्म (dharma)");
+//! # Ok::<(), ShleshaError>(())
+//! ```
+
+use crate::modules::builder::ShleshaError;
+use crate::Shlesha;
+
+/// Converts text between scripts
+pub trait Transliterator: Send + Sync {
+    /// `text` in script `from` written in script `to`
+    fn transliterate(&self, text: &str, from: &str, to: &str) -> Result<String, ShleshaError>;
+
+    /// Whether `script` can be converted from and to
+    fn supports(&self, script: &str) -> bool;
+}
+
+impl Transliterator for Shlesha {
+    fn transliterate(&self, text: &str, from: &str, to: &str) -> Result<String, ShleshaError> {
+        Shlesha::transliterate(self, text, from, to).map_err(|error| {
+            match [from, to]
+                .into_iter()
+                .find(|script| !self.supports_script(script))
+            {
+                Some(script) => ShleshaError::UnsupportedScript(script.to_string()),
+                None => ShleshaError::Conversion(error.to_string()),
+            }
+        })
+    }
+
+    fn supports(&self, script: &str) -> bool {
+        self.supports_script(script)
+    }
+}
+
+/// A `Transliterator` that returns its input unchanged and supports every script
+///
+/// For tests of code that takes a `dyn Transliterator` and should not depend
+/// on what a conversion writes. Built with the `testing` feature.
+#[cfg(feature = "testing")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoopTransliterator;
+
+#[cfg(feature = "testing")]
+impl Transliterator for NoopTransliterator {
+    fn transliterate(&self, text: &str, _from: &str, _to: &str) -> Result<String, ShleshaError> {
+        Ok(text.to_string())
+    }
+
+    fn supports(&self, _script: &str) -> bool {
+        true
+    }
+}
@@ -5,24 +5,30 @@
 //! - Metadata collection for unknown tokens
 //! - Script discovery and validation
 //! - Runtime schema loading
+//!
+//! Text to convert may hold lone surrogates, as strings decoded with
+//! `surrogateescape` do; each is read as U+FFFD rather than raising, and
+//! reported in metadata as an unknown token of kind "invalid_encoding".
 
 use once_cell::sync::Lazy;
 use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
 use std::collections::HashMap;
 
+use crate::modules::hub::HubToken;
 use crate::Shlesha;
 
 // Global transliterator instance for convenience function
 static GLOBAL_TRANSLITERATOR: Lazy<Shlesha> = Lazy::new(Shlesha::new);
 
 /// Python wrapper for the Shlesha transliterator
-#[pyclass(unsendable)]
+#[pyclass(name = "Shlesha", unsendable)]
 pub struct PyShlesha {
     inner: Shlesha,
 }
 
 /// Python wrapper for transliteration metadata
-#[pyclass]
+#[pyclass(name = "TransliterationMetadata")]
 #[derive(Clone)]
 pub struct PyTransliterationMetadata {
     #[pyo3(get)]
@@ -33,10 +39,16 @@ pub struct PyTransliterationMetadata {
     used_extensions: String,
     #[pyo3(get)]
     unknown_tokens: Vec<PyUnknownToken>,
+    /// Unknown tokens found, including those past the collection limit
+    #[pyo3(get)]
+    total_unknown_count: usize,
+    /// Whether unknown tokens were found past the collection limit and not listed
+    #[pyo3(get)]
+    truncated: bool,
 }
 
 /// Python wrapper for unknown token information
-#[pyclass]
+#[pyclass(name = "UnknownToken")]
 #[derive(Clone)]
 pub struct PyUnknownToken {
     #[pyo3(get)]
@@ -49,10 +61,17 @@ pub struct PyUnknownToken {
     unicode: String,
     #[pyo3(get)]
     is_extension: bool,
+    /// Conversion stage that reported the token: "source_parsing",
+    /// "hub_conversion" or "target_rendering"
+    #[pyo3(get)]
+    stage: String,
+    /// "invalid_encoding" for a lone surrogate in the input, else "unmapped"
+    #[pyo3(get)]
+    kind: String,
 }
 
 /// Python wrapper for transliteration result with metadata
-#[pyclass]
+#[pyclass(name = "TransliterationResult")]
 pub struct PyTransliterationResult {
     #[pyo3(get)]
     output: String,
@@ -87,9 +106,58 @@ impl PyShlesha {
     ///     >>> transliterator = Shlesha()
     ///     >>> result = transliterator.transliterate("धर्म", "devanagari", "iast")
     ///     >>> print(result)  # "dharma"
-    fn transliterate(&self, text: &str, from_script: &str, to_script: &str) -> PyResult<String> {
+    ///
+    /// Performance:
+    ///     No metadata is collected and none is built; use this rather than
+    ///     `transliterate_with_metadata` when only the string is wanted.
+    fn transliterate(
+        &self,
+        #[pyo3(from_py_with = lossy_text)] text: String,
+        from_script: &str,
+        to_script: &str,
+    ) -> PyResult<String> {
+        self.inner
+            .transliterate(&text, from_script, to_script)
+            .map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                    "Transliteration failed: {e}"
+                ))
+            })
+    }
+
+    /// Transliterate text by the shortest path, for latency-critical callers
+    ///
+    /// Args:
+    ///     text (str): Text to transliterate
+    ///     from_script (str): Source script name
+    ///     to_script (str): Target script name
+    ///
+    /// Returns:
+    ///     str: Transliterated text
+    ///
+    /// Raises:
+    ///     RuntimeError: If transliteration fails
+    ///
+    /// Example:
+    ///     >>> transliterator = Shlesha()
+    ///     >>> transliterator.transliterate_fast("धर्म", "devanagari", "iast")
+    ///     'dharma'
+    ///
+    /// Performance:
+    ///     Skips the profiler, the optimization cache, word overrides and the
+    ///     conversion statistics that `transliterate` goes through, saving a
+    ///     little per call on short strings. The output is the same as
+    ///     `transliterate` unless the instance has word overrides, options or
+    ///     hooks configured, which are not applied; `stats()` does not count
+    ///     these conversions.
+    fn transliterate_fast(
+        &self,
+        #[pyo3(from_py_with = lossy_text)] text: String,
+        from_script: &str,
+        to_script: &str,
+    ) -> PyResult<String> {
         self.inner
-            .transliterate(text, from_script, to_script)
+            .transliterate_fast(&text, from_script, to_script)
             .map_err(|e| {
                 PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
                     "Transliteration failed: {e}"
@@ -105,7 +173,7 @@ impl PyShlesha {
     ///     to_script (str): Target script name
     ///
     /// Returns:
-    ///     PyTransliterationResult: Result with output and metadata
+    ///     TransliterationResult: Result with output and metadata
     ///
     /// Raises:
     ///     RuntimeError: If transliteration fails
@@ -115,15 +183,22 @@ impl PyShlesha {
     ///     >>> result = transliterator.transliterate_with_metadata("धर्मkr", "devanagari", "iast")
     ///     >>> print(result.output)  # "dharmakr"
     ///     >>> print(len(result.metadata.unknown_tokens))  # 2 (for 'k' and 'r')
+    ///
+    /// The first 1000 unknown tokens are listed; `metadata.total_unknown_count`
+    /// counts all of them and `metadata.truncated` tells when some were left out.
+    ///
+    /// Performance:
+    ///     Collecting unknown tokens and building the result objects costs more
+    ///     than `transliterate`; call this only when the metadata is used.
     fn transliterate_with_metadata(
         &self,
-        text: &str,
+        #[pyo3(from_py_with = lossy_text)] text: String,
         from_script: &str,
         to_script: &str,
     ) -> PyResult<PyTransliterationResult> {
         let result = self
             .inner
-            .transliterate_with_metadata(text, from_script, to_script)
+            .transliterate_with_metadata(&text, from_script, to_script)
             .map_err(|e| {
                 PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
                     "Transliteration failed: {e}"
@@ -140,6 +215,8 @@ impl PyShlesha {
                     position: token.position,
                     unicode: token.unicode,
                     is_extension: token.is_extension,
+                    stage: token.stage.as_str().to_string(),
+                    kind: token.kind.as_str().to_string(),
                 })
                 .collect();
 
@@ -148,6 +225,8 @@ impl PyShlesha {
                 target_script: metadata.target_script,
                 used_extensions: metadata.used_extensions.to_string(),
                 unknown_tokens,
+                total_unknown_count: metadata.total_unknown_count,
+                truncated: metadata.truncated,
             }
         });
 
@@ -190,6 +269,33 @@ impl PyShlesha {
         self.inner.supports_script(script)
     }
 
+    /// Read text as hub tokens, giving the stable ID of each
+    ///
+    /// Args:
+    ///     text (str): Text to read
+    ///     script (str): Script the text is in
+    ///
+    /// Returns:
+    ///     List[int]: Stable token IDs, 0 for unknown text
+    ///
+    /// Raises:
+    ///     RuntimeError: If the script is not supported
+    ///
+    /// Example:
+    ///     >>> transliterator = Shlesha()
+    ///     >>> ids = transliterator.tokenize_ids("धर्म", "devanagari")
+    ///     >>> names = get_token_ids()
+    ///     >>> print([names[i][1] for i in ids])  # ['ConsonantDdh', 'ConsonantR', ...]
+    fn tokenize_ids(
+        &self,
+        #[pyo3(from_py_with = lossy_text)] text: String,
+        script: &str,
+    ) -> PyResult<Vec<u32>> {
+        self.inner.tokenize_ids(&text, script).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Tokenization failed: {e}"))
+        })
+    }
+
     /// Load a schema from a file path for runtime script support
     ///
     /// Args:
@@ -249,16 +355,58 @@ impl PyShlesha {
     ///     >>> info = transliterator.get_schema_info("custom")
     ///     >>> print(info["description"])
     fn get_schema_info(&self, py: Python<'_>, script_name: &str) -> PyResult<Option<PyObject>> {
-        Ok(self.inner.get_schema_info(script_name).map(|info| {
-            let dict = pyo3::types::PyDict::new(py);
-            dict.set_item("name", info.name).unwrap();
-            dict.set_item("description", info.description).unwrap();
-            dict.set_item("script_type", info.script_type).unwrap();
-            dict.set_item("is_runtime_loaded", info.is_runtime_loaded)
-                .unwrap();
-            dict.set_item("mapping_count", info.mapping_count).unwrap();
-            dict.into()
-        }))
+        self.inner
+            .get_schema_info(script_name)
+            .map(|info| schema_info_dict(py, info))
+            .transpose()
+    }
+
+    /// Replace a loaded runtime schema with new YAML, atomically
+    ///
+    /// Conversions running meanwhile use either the old schema or the new one.
+    /// If the YAML is invalid, the old schema stays in use.
+    ///
+    /// Args:
+    ///     name (str): Name of the loaded schema
+    ///     yaml_content (str): The new schema
+    ///
+    /// Returns:
+    ///     Dict[str, Any]: Information about the new schema, with its "revision"
+    ///
+    /// Raises:
+    ///     RuntimeError: If the schema is not loaded or the YAML is invalid
+    fn reload_schema(&self, py: Python<'_>, name: &str, yaml_content: &str) -> PyResult<PyObject> {
+        let info = self.inner.reload_schema(name, yaml_content).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                "Schema reloading failed: {e}"
+            ))
+        })?;
+        schema_info_dict(py, info)
+    }
+
+    /// Counts of the conversions carried out since creation or the last reset
+    ///
+    /// Returns:
+    ///     Dict[str, Any]: "conversions", "input_bytes", "output_bytes" and
+    ///     "errors" counts, and "pairs", mapping (source, target) to its count
+    ///
+    /// Example:
+    ///     >>> transliterator.transliterate("धर्म", "devanagari", "iast")
+    ///     >>> print(transliterator.stats()["pairs"][("devanagari", "iast")])  # 1
+    fn stats(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let stats = self.inner.stats();
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("conversions", stats.conversions)?;
+        dict.set_item("input_bytes", stats.input_bytes)?;
+        dict.set_item("output_bytes", stats.output_bytes)?;
+        dict.set_item("errors", stats.errors)?;
+        dict.set_item("pairs", stats.pairs.into_iter().collect::<HashMap<_, _>>())?;
+        Ok(dict.into())
+    }
+
+    /// Set the conversion counters back to zero
+    fn reset_stats(&self) {
+        self.inner.reset_stats()
     }
 
     /// Remove a runtime loaded schema
@@ -432,20 +580,54 @@ impl PyUnknownToken {
     /// Python representation
     fn __repr__(&self) -> String {
         format!(
-            "UnknownToken(script='{}', token='{}', position={})",
-            self.script, self.token, self.position
+            "UnknownToken(script='{}', token='{}', position={}, stage='{}')",
+            self.script, self.token, self.position, self.stage
         )
     }
 }
 
+/// A `str` argument as UTF-8, with each lone surrogate read as U+FFFD
+///
+/// UTF-8 cannot hold a surrogate, so PyO3's own conversion raises
+/// UnicodeEncodeError for one. Such strings are rare, and only they pay for
+/// the round trip through UTF-16.
+fn lossy_text(text: &Bound<'_, PyAny>) -> PyResult<String> {
+    let text = text.downcast::<PyString>()?;
+    if let Ok(text) = text.to_str() {
+        return Ok(text.to_string());
+    }
+    let encoded = text.call_method1("encode", ("utf-16-le", "surrogatepass"))?;
+    let units: Vec<u16> = encoded
+        .downcast::<PyBytes>()?
+        .as_bytes()
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    Ok(String::from_utf16_lossy(&units))
+}
+
+/// A `SchemaInfo` as a Python dict
+fn schema_info_dict(py: Python<'_>, info: crate::SchemaInfo) -> PyResult<PyObject> {
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("name", info.name)?;
+    dict.set_item("description", info.description)?;
+    dict.set_item("script_type", info.script_type)?;
+    dict.set_item("is_runtime_loaded", info.is_runtime_loaded)?;
+    dict.set_item("mapping_count", info.mapping_count)?;
+    dict.set_item("revision", info.revision)?;
+    dict.set_item("direction", info.direction.as_str())?;
+    dict.set_item("rendering_notes", info.rendering_notes)?;
+    Ok(dict.into())
+}
+
 /// Convenience function to create a new Shlesha instance
 ///
 /// Returns:
-///     PyShlesha: New transliterator instance
+///     Shlesha: New transliterator instance
 ///
 /// Example:
-///     >>> from shlesha import Shlesha
-///     >>> transliterator = Shlesha()
+///     >>> from shlesha import create_transliterator
+///     >>> transliterator = create_transliterator()
 #[pyfunction]
 fn create_transliterator() -> PyShlesha {
     PyShlesha::new()
@@ -466,9 +648,13 @@ fn create_transliterator() -> PyShlesha {
 ///     >>> result = transliterate("धर्म", "devanagari", "iast")
 ///     >>> print(result)  # "dharma"
 #[pyfunction]
-fn transliterate(text: &str, from_script: &str, to_script: &str) -> PyResult<String> {
+fn transliterate(
+    #[pyo3(from_py_with = lossy_text)] text: String,
+    from_script: &str,
+    to_script: &str,
+) -> PyResult<String> {
     GLOBAL_TRANSLITERATOR
-        .transliterate(text, from_script, to_script)
+        .transliterate(&text, from_script, to_script)
         .map_err(|e| {
             PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
                 "Transliteration failed: {e}"
@@ -495,6 +681,25 @@ fn get_supported_scripts() -> Vec<String> {
         .collect()
 }
 
+/// Get the stable ID of every hub token
+///
+/// Returns:
+///     Dict[int, Tuple[str, str]]: Each ID's format ("abugida" or "alphabet")
+///     and token name. IDs do not change between releases; 0, for unknown
+///     text, is not listed.
+///
+/// Example:
+///     >>> from shlesha import get_token_ids
+///     >>> ids = get_token_ids()
+///     >>> print(ids[Shlesha().tokenize_ids("क", "devanagari")[0]])  # ('abugida', 'ConsonantK')
+#[pyfunction]
+fn get_token_ids() -> HashMap<u32, (&'static str, &'static str)> {
+    HubToken::STABLE_IDS
+        .iter()
+        .map(|&(id, format, name)| (id, (format.key(), name)))
+        .collect()
+}
+
 /// Configure the Python module with all classes and functions
 pub fn configure_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Add classes
@@ -502,14 +707,18 @@ pub fn configure_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyTransliterationResult>()?;
     m.add_class::<PyTransliterationMetadata>()?;
     m.add_class::<PyUnknownToken>()?;
+    // The name the class was exported under before it was called Shlesha
+    m.add("PyShlesha", m.getattr("Shlesha")?)?;
 
     // Add convenience functions
     m.add_function(wrap_pyfunction!(create_transliterator, m)?)?;
     m.add_function(wrap_pyfunction!(transliterate, m)?)?;
     m.add_function(wrap_pyfunction!(get_supported_scripts, m)?)?;
+    m.add_function(wrap_pyfunction!(get_token_ids, m)?)?;
 
     // Add module metadata
     m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add("__output_version__", Shlesha::output_version())?;
     m.add("__author__", "Shlesha Contributors")?;
     m.add(
         "__description__",
@@ -529,7 +738,7 @@ mod tests {
     fn test_python_basic_transliteration() {
         let transliterator = PyShlesha::new();
         let result = transliterator
-            .transliterate("अ", "devanagari", "iast")
+            .transliterate("अ".into(), "devanagari", "iast")
             .unwrap();
         assert_eq!(result, "a");
     }
@@ -538,7 +747,7 @@ mod tests {
     fn test_python_metadata_collection() {
         let transliterator = PyShlesha::new();
         let result = transliterator
-            .transliterate_with_metadata("धर्मkr", "devanagari", "iast")
+            .transliterate_with_metadata("धर्मkr".into(), "devanagari", "iast")
             .unwrap();
         assert!(result.output.contains("dharma"));
         assert!(result.metadata.is_some());
@@ -564,7 +773,7 @@ mod tests {
 
     #[test]
     fn test_convenience_functions() {
-        let result = transliterate("अ", "devanagari", "iast").unwrap();
+        let result = transliterate("अ".into(), "devanagari", "iast").unwrap();
         assert_eq!(result, "a");
 
         let scripts = get_supported_scripts();
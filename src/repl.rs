@@ -0,0 +1,187 @@
+//! `shlesha repl`: convert lines as they are typed
+//!
+//! On a terminal lines are read with rustyline, with history and line editing;
+//! Ctrl-C drops the line being typed and Ctrl-D quits. Piped input is read line
+//! by line without a prompt, so a session can be scripted.
+
+use rustyline::error::ReadlineError;
+use shlesha::modules::hub::HubToken;
+use shlesha::{Shlesha, TransliterationMetadata};
+use std::io::{BufRead, IsTerminal};
+
+const HELP: &str = "\
+Lines are converted from the source script to the target script.
+Commands:
+  :from SCRIPT     convert from SCRIPT
+  :to SCRIPT       convert to SCRIPT
+  :load FILE       load a schema file (YAML or TOML)
+  :trace on|off    show the hub tokens each line is read as
+  :meta on|off     show unknown tokens and lossy mappings
+  :help            show this help
+  :quit            leave (or Ctrl-D)";
+
+/// A REPL session: one instance, so loaded schemas last for the session
+pub struct Repl {
+    shlesha: Shlesha,
+    from: String,
+    to: String,
+    trace: bool,
+    meta: bool,
+}
+
+impl Repl {
+    pub fn new(shlesha: Shlesha, from: String, to: String) -> Self {
+        Self {
+            shlesha,
+            from,
+            to,
+            trace: false,
+            meta: false,
+        }
+    }
+
+    /// Read lines until Ctrl-D, the end of piped input or `:quit`
+    pub fn run(mut self) -> Result<(), ReadlineError> {
+        if !std::io::stdin().is_terminal() {
+            for line in std::io::stdin().lock().lines() {
+                if !self.handle(&line?) {
+                    break;
+                }
+            }
+            return Ok(());
+        }
+
+        let mut editor = rustyline::DefaultEditor::new()?;
+        println!(
+            "Converting {} → {}; :help lists the commands",
+            self.from, self.to
+        );
+        loop {
+            match editor.readline(&format!("{}→{}> ", self.from, self.to)) {
+                Ok(line) => {
+                    if !line.trim().is_empty() {
+                        editor.add_history_entry(line.as_str())?;
+                    }
+                    if !self.handle(&line) {
+                        break;
+                    }
+                }
+                // Ctrl-C abandons the line, as in a shell
+                Err(ReadlineError::Interrupted) => continue,
+                Err(ReadlineError::Eof) => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a command or convert a line; false when the line asks to quit
+    fn handle(&mut self, line: &str) -> bool {
+        let Some(command) = line.trim().strip_prefix(':') else {
+            self.convert(line);
+            return true;
+        };
+        let (name, argument) = command
+            .split_once(char::is_whitespace)
+            .map_or((command, ""), |(name, argument)| (name, argument.trim()));
+        match (name, argument) {
+            ("q" | "quit" | "exit", _) => return false,
+            ("help", _) => println!("{HELP}"),
+            ("from" | "to", "") => eprintln!("Error: :{name} needs a script name"),
+            ("from" | "to", script) if !self.shlesha.supports_script(script) => {
+                eprintln!("Error: unsupported script: {script}")
+            }
+            ("from", script) => {
+                self.from = script.to_string();
+                println!("Converting {} → {}", self.from, self.to);
+            }
+            ("to", script) => {
+                self.to = script.to_string();
+                println!("Converting {} → {}", self.from, self.to);
+            }
+            ("load", "") => eprintln!("Error: :load needs a schema file"),
+            ("load", path) => match self.shlesha.load_schema_from_file(path) {
+                Ok(()) => println!("Loaded {path}"),
+                Err(e) => eprintln!("Error: cannot load {path}: {e}"),
+            },
+            ("trace" | "meta", "on" | "off") => {
+                let enabled = argument == "on";
+                match name {
+                    "trace" => self.trace = enabled,
+                    _ => self.meta = enabled,
+                }
+                println!("{name} {argument}");
+            }
+            ("trace" | "meta", _) => eprintln!("Error: :{name} takes on or off"),
+            _ => eprintln!("Error: unknown command :{name}; :help lists the commands"),
+        }
+        true
+    }
+
+    /// Print the conversion of `line`, with its tokens and metadata if asked for
+    fn convert(&self, line: &str) {
+        if self.trace {
+            match self.shlesha.read_tokens(line, &self.from) {
+                Ok(tokens) => println!("  read: {}", token_names(&tokens)),
+                Err(e) => eprintln!("Error: {e}"),
+            }
+        }
+        if !self.meta {
+            match self.shlesha.transliterate(line, &self.from, &self.to) {
+                Ok(output) => println!("{output}"),
+                Err(e) => eprintln!("Error: {e}"),
+            }
+            return;
+        }
+        match self
+            .shlesha
+            .transliterate_with_metadata(line, &self.from, &self.to)
+        {
+            Ok(result) => {
+                println!("{}", result.output);
+                if let Some(metadata) = &result.metadata {
+                    print_metadata(metadata);
+                }
+            }
+            Err(e) => eprintln!("Error: {e}"),
+        }
+    }
+}
+
+/// Token names separated by spaces, whitespace shown as ␣
+fn token_names(tokens: &[HubToken]) -> String {
+    tokens
+        .iter()
+        .map(|token| match token.as_unknown_string() {
+            Some(text) if text.trim().is_empty() => "␣".to_string(),
+            Some(text) => format!("'{text}'"),
+            None => match token {
+                HubToken::Abugida(token) => token.to_string(),
+                HubToken::Alphabet(token) => token.to_string(),
+            },
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The unknown tokens and lossy mappings of a conversion, one per line
+fn print_metadata(metadata: &TransliterationMetadata) {
+    for token in &metadata.unknown_tokens {
+        match &token.hub_token {
+            Some(hub_token) => println!(
+                "  unknown: {hub_token} at token {} ({})",
+                token.position, token.stage
+            ),
+            None => println!(
+                "  unknown: '{}' ({}) at byte {} ({})",
+                token.token, token.unicode, token.position, token.stage
+            ),
+        }
+    }
+    for mapping in &metadata.lossy_mappings {
+        println!(
+            "  lossy: {} written as {} at token {}",
+            mapping.token, mapping.substitute, mapping.position
+        );
+    }
+}
@@ -6,6 +6,14 @@
 //! - Script discovery and validation
 //! - Runtime schema loading
 //!
+//! ## Lone surrogates
+//!
+//! A JavaScript string can hold a lone surrogate, for instance when a longer
+//! string was cut between the two halves of a pair. wasm-bindgen converts
+//! string arguments to UTF-8 with each lone surrogate replaced by U+FFFD, so
+//! such input never fails or panics; metadata reports the U+FFFD as an
+//! unknown token of kind `"invalid_encoding"`.
+//!
 //! ## Performance and Benchmarking
 //!
 //! WASM builds disable criterion's default features (specifically rayon) for benchmarking because:
@@ -19,8 +27,12 @@
 
 use crate::Shlesha;
 use js_sys::{Array, Object, Reflect};
+use once_cell::sync::Lazy;
 use wasm_bindgen::prelude::*;
 
+// Global transliterator instance for the convenience function
+static GLOBAL_TRANSLITERATOR: Lazy<Shlesha> = Lazy::new(Shlesha::new);
+
 // Import console.log for debugging
 #[wasm_bindgen]
 extern "C" {
@@ -48,6 +60,8 @@ pub struct WasmUnknownToken {
     position: usize,
     unicode: String,
     is_extension: bool,
+    stage: String,
+    kind: String,
 }
 
 /// WASM wrapper for transliteration metadata
@@ -56,6 +70,8 @@ pub struct WasmTransliterationMetadata {
     source_script: String,
     target_script: String,
     unknown_tokens: Vec<WasmUnknownToken>,
+    total_unknown_count: usize,
+    truncated: bool,
 }
 
 /// WASM wrapper for transliteration result with metadata
@@ -97,6 +113,9 @@ impl WasmShlesha {
     /// const result = transliterator.transliterate("धर्म", "devanagari", "iast");
     /// console.log(result); // "dharma"
     /// ```
+    ///
+    /// No metadata is collected; use this rather than `transliterateWithMetadata`
+    /// when only the string is wanted.
     #[wasm_bindgen]
     pub fn transliterate(
         &self,
@@ -109,6 +128,36 @@ impl WasmShlesha {
             .map_err(|e| JsValue::from_str(&format!("Transliteration failed: {e}")))
     }
 
+    /// Transliterate text by the shortest path, for latency-critical callers
+    ///
+    /// @param {string} text - Text to transliterate
+    /// @param {string} fromScript - Source script name
+    /// @param {string} toScript - Target script name
+    /// @returns {string} Transliterated text
+    /// @throws {Error} If transliteration fails
+    ///
+    /// @example
+    /// ```javascript
+    /// const transliterator = new WasmShlesha();
+    /// const result = transliterator.transliterateFast("धर्म", "devanagari", "iast");
+    /// console.log(result); // "dharma"
+    /// ```
+    ///
+    /// Skips the word overrides and conversion statistics `transliterate` goes
+    /// through. The output is the same unless the instance has word overrides,
+    /// options or hooks configured, which are not applied.
+    #[wasm_bindgen(js_name = transliterateFast)]
+    pub fn transliterate_fast(
+        &self,
+        text: &str,
+        from_script: &str,
+        to_script: &str,
+    ) -> Result<String, JsValue> {
+        self.inner
+            .transliterate_fast(text, from_script, to_script)
+            .map_err(|e| JsValue::from_str(&format!("Transliteration failed: {e}")))
+    }
+
     /// Transliterate text with metadata collection for unknown tokens
     ///
     /// @param {string} text - Text to transliterate
@@ -146,6 +195,8 @@ impl WasmShlesha {
                     position: token.position,
                     unicode: token.unicode,
                     is_extension: token.is_extension,
+                    stage: token.stage.as_str().to_string(),
+                    kind: token.kind.as_str().to_string(),
                 })
                 .collect();
 
@@ -153,6 +204,8 @@ impl WasmShlesha {
                 source_script: metadata.source_script,
                 target_script: metadata.target_script,
                 unknown_tokens,
+                total_unknown_count: metadata.total_unknown_count,
+                truncated: metadata.truncated,
             }
         });
 
@@ -198,6 +251,26 @@ impl WasmShlesha {
         self.inner.supports_script(script)
     }
 
+    /// Read text as hub tokens, giving the stable ID of each
+    ///
+    /// @param {string} text - Text to read
+    /// @param {string} script - Script the text is in
+    /// @returns {Uint32Array} Stable token IDs, 0 for unknown text
+    /// @throws {Error} If the script is not supported
+    ///
+    /// @example
+    /// ```javascript
+    /// const ids = transliterator.tokenizeIds("धर्म", "devanagari");
+    /// const names = getTokenIds();
+    /// console.log(names[ids[0]].name); // "ConsonantDdh"
+    /// ```
+    #[wasm_bindgen(js_name = tokenizeIds)]
+    pub fn tokenize_ids(&self, text: &str, script: &str) -> Result<Vec<u32>, JsValue> {
+        self.inner
+            .tokenize_ids(text, script)
+            .map_err(|e| JsValue::from_str(&format!("Tokenization failed: {e}")))
+    }
+
     /// Load a new script schema at runtime
     ///
     /// @param {string} schemaPath - Path to YAML schema file
@@ -331,34 +404,30 @@ impl WasmShlesha {
     /// ```
     #[wasm_bindgen(js_name = getSchemaInfo)]
     pub fn get_schema_info(&self, script_name: &str) -> Option<Object> {
-        self.inner.get_schema_info(script_name).map(|info| {
-            let obj = Object::new();
-
-            // Use Reflect to set properties
-            let _ = Reflect::set(&obj, &"name".into(), &JsValue::from_str(&info.name));
-            let _ = Reflect::set(
-                &obj,
-                &"description".into(),
-                &JsValue::from_str(&info.description),
-            );
-            let _ = Reflect::set(
-                &obj,
-                &"script_type".into(),
-                &JsValue::from_str(&info.script_type),
-            );
-            let _ = Reflect::set(
-                &obj,
-                &"is_runtime_loaded".into(),
-                &JsValue::from_bool(info.is_runtime_loaded),
-            );
-            let _ = Reflect::set(
-                &obj,
-                &"mapping_count".into(),
-                &JsValue::from_f64(info.mapping_count as f64),
-            );
+        self.inner
+            .get_schema_info(script_name)
+            .map(schema_info_object)
+    }
 
-            obj
-        })
+    /// Replace a loaded runtime schema with new YAML, atomically
+    ///
+    /// If the YAML is invalid, the old schema stays in use.
+    ///
+    /// @param {string} name - Name of the loaded schema
+    /// @param {string} yamlContent - The new schema
+    /// @returns {Object} Information about the new schema, with its revision
+    ///
+    /// @example
+    /// ```javascript
+    /// const info = transliterator.reloadSchema("custom", yamlContent);
+    /// console.log(info.revision); // 2 after the first reload
+    /// ```
+    #[wasm_bindgen(js_name = reloadSchema)]
+    pub fn reload_schema(&self, name: &str, yaml_content: &str) -> Result<Object, JsValue> {
+        self.inner
+            .reload_schema(name, yaml_content)
+            .map(schema_info_object)
+            .map_err(|e| JsValue::from_str(&format!("Schema reloading failed: {e}")))
     }
 
     /// Remove a runtime loaded schema
@@ -422,7 +491,9 @@ impl WasmTransliterationResult {
         self.metadata.as_ref().map(|m| m.target_script.clone())
     }
 
-    /// Get the number of unknown tokens
+    /// Get the number of unknown tokens listed
+    ///
+    /// At most 1000 are listed; see `getTotalUnknownCount`.
     ///
     /// @returns {number} Number of unknown tokens (0 if no metadata)
     #[wasm_bindgen(js_name = getUnknownTokenCount)]
@@ -433,6 +504,25 @@ impl WasmTransliterationResult {
             .unwrap_or(0)
     }
 
+    /// Get the number of unknown tokens found, including those not listed
+    ///
+    /// @returns {number} Number of unknown tokens found (0 if no metadata)
+    #[wasm_bindgen(js_name = getTotalUnknownCount)]
+    pub fn get_total_unknown_count(&self) -> usize {
+        self.metadata
+            .as_ref()
+            .map(|m| m.total_unknown_count)
+            .unwrap_or(0)
+    }
+
+    /// Check whether unknown tokens were found past the limit and not listed
+    ///
+    /// @returns {boolean} True if the unknown token list is incomplete
+    #[wasm_bindgen(js_name = isTruncated)]
+    pub fn is_truncated(&self) -> bool {
+        self.metadata.as_ref().is_some_and(|m| m.truncated)
+    }
+
     /// Get unknown tokens as JavaScript Array
     ///
     /// @returns {Array<Object>} Array of unknown token objects
@@ -468,6 +558,16 @@ impl WasmTransliterationResult {
                     &JsValue::from_str("isExtension"),
                     &JsValue::from_bool(token.is_extension),
                 )?;
+                Reflect::set(
+                    &obj,
+                    &JsValue::from_str("stage"),
+                    &JsValue::from_str(&token.stage),
+                )?;
+                Reflect::set(
+                    &obj,
+                    &JsValue::from_str("kind"),
+                    &JsValue::from_str(&token.kind),
+                )?;
                 array.push(&obj);
             }
         }
@@ -506,8 +606,7 @@ pub fn create_transliterator() -> WasmShlesha {
 /// ```
 #[wasm_bindgen]
 pub fn transliterate(text: &str, from_script: &str, to_script: &str) -> Result<String, JsValue> {
-    let transliterator = Shlesha::new();
-    transliterator
+    GLOBAL_TRANSLITERATOR
         .transliterate(text, from_script, to_script)
         .map_err(|e| JsValue::from_str(&format!("Transliteration failed: {e}")))
 }
@@ -533,6 +632,30 @@ pub fn get_supported_scripts() -> Array {
     array
 }
 
+/// Get the stable ID of every hub token
+///
+/// IDs do not change between releases; 0, for unknown text, is not listed.
+///
+/// @returns {Object} Each ID's `format` ("abugida" or "alphabet") and token `name`, by ID
+///
+/// @example
+/// ```javascript
+/// import { getTokenIds } from 'shlesha';
+/// const ids = getTokenIds();
+/// console.log(ids[transliterator.tokenizeIds("क", "devanagari")[0]].name); // "ConsonantK"
+/// ```
+#[wasm_bindgen(js_name = getTokenIds)]
+pub fn get_token_ids() -> Object {
+    let table = Object::new();
+    for &(id, format, name) in crate::modules::hub::HubToken::STABLE_IDS {
+        let entry = Object::new();
+        let _ = Reflect::set(&entry, &"format".into(), &JsValue::from_str(format.key()));
+        let _ = Reflect::set(&entry, &"name".into(), &JsValue::from_str(name));
+        let _ = Reflect::set(&table, &JsValue::from(id), &entry);
+    }
+    table
+}
+
 /// Get the library version
 ///
 /// @returns {string} Version string
@@ -541,6 +664,61 @@ pub fn get_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
+/// Get the version of the built-in conversion outputs
+///
+/// Changes whenever an upgrade may change the output of a conversion.
+///
+/// @returns {string} Output version
+#[wasm_bindgen(js_name = getOutputVersion)]
+pub fn get_output_version() -> String {
+    Shlesha::output_version().to_string()
+}
+
+/// A `SchemaInfo` as a JavaScript object
+fn schema_info_object(info: crate::SchemaInfo) -> Object {
+    let obj = Object::new();
+
+    // Use Reflect to set properties
+    let _ = Reflect::set(&obj, &"name".into(), &JsValue::from_str(&info.name));
+    let _ = Reflect::set(
+        &obj,
+        &"description".into(),
+        &JsValue::from_str(&info.description),
+    );
+    let _ = Reflect::set(
+        &obj,
+        &"script_type".into(),
+        &JsValue::from_str(&info.script_type),
+    );
+    let _ = Reflect::set(
+        &obj,
+        &"is_runtime_loaded".into(),
+        &JsValue::from_bool(info.is_runtime_loaded),
+    );
+    let _ = Reflect::set(
+        &obj,
+        &"mapping_count".into(),
+        &JsValue::from_f64(info.mapping_count as f64),
+    );
+    let _ = Reflect::set(
+        &obj,
+        &"revision".into(),
+        &JsValue::from_f64(info.revision as f64),
+    );
+    let _ = Reflect::set(
+        &obj,
+        &"direction".into(),
+        &JsValue::from_str(info.direction.as_str()),
+    );
+    let rendering_notes = info
+        .rendering_notes
+        .as_deref()
+        .map_or(JsValue::NULL, JsValue::from_str);
+    let _ = Reflect::set(&obj, &"rendering_notes".into(), &rendering_notes);
+
+    obj
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -570,6 +748,25 @@ mod tests {
         let _count = result.get_unknown_token_count();
     }
 
+    #[wasm_bindgen_test]
+    fn test_wasm_lone_surrogate() {
+        // "क", a lone high surrogate, "म"
+        let text = js_sys::JsString::from_char_code(&[0x0915, 0xD800, 0x092E]);
+        // The conversion wasm-bindgen applies to string arguments
+        let text = text.as_string().unwrap();
+        assert_eq!(text, "क\u{FFFD}म");
+
+        let transliterator = WasmShlesha::new();
+        let result = transliterator
+            .transliterate_with_metadata(&text, "devanagari", "iast")
+            .unwrap();
+        assert_eq!(result.get_output(), "ka\u{FFFD}ma");
+        let tokens = result.get_unknown_tokens().unwrap();
+        assert_eq!(tokens.length(), 1);
+        let kind = Reflect::get(&tokens.get(0), &JsValue::from_str("kind")).unwrap();
+        assert_eq!(kind.as_string().as_deref(), Some("invalid_encoding"));
+    }
+
     #[wasm_bindgen_test]
     fn test_wasm_script_support() {
         let transliterator = WasmShlesha::new();
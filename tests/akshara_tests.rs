@@ -0,0 +1,106 @@
+use shlesha::modules::hub::AbugidaToken;
+use shlesha::{Akshara, Shlesha};
+
+fn texts(aksharas: &[Akshara]) -> Vec<&str> {
+    aksharas
+        .iter()
+        .map(|akshara| akshara.text.as_str())
+        .collect()
+}
+
+#[test]
+fn test_syllabify_devanagari() {
+    let shlesha = Shlesha::new();
+
+    let aksharas = shlesha.syllabify("संस्कृतम्", "devanagari").unwrap();
+    assert_eq!(texts(&aksharas), ["सं", "स्कृ", "त", "म्"]);
+
+    // Anusvara attaches to the akshara before it
+    assert_eq!(aksharas[0].vowel, Some(AbugidaToken::VowelA));
+    assert_eq!(aksharas[0].final_marks, [AbugidaToken::MarkAnusvara]);
+    assert!(!aksharas[0].has_conjunct);
+
+    assert_eq!(
+        aksharas[1].tokens,
+        [
+            AbugidaToken::ConsonantS,
+            AbugidaToken::MarkVirama,
+            AbugidaToken::ConsonantK,
+            AbugidaToken::VowelSignR,
+        ]
+    );
+    assert!(aksharas[1].has_conjunct);
+    assert_eq!(aksharas[1].vowel, Some(AbugidaToken::VowelSignR));
+
+    assert!(aksharas[3].is_halanta());
+    assert_eq!(aksharas[3].vowel, None);
+    assert_eq!(aksharas[3].position, "संस्कृत".len());
+}
+
+#[test]
+fn test_syllabify_initial_vowels_and_word_boundaries() {
+    let shlesha = Shlesha::new();
+
+    let aksharas = shlesha
+        .syllabify("अग्निमीळे पुरोहितं। वाक् ऋषिः", "devanagari")
+        .unwrap();
+    assert_eq!(
+        texts(&aksharas),
+        [
+            "अ",
+            "ग्नि",
+            "मी",
+            "ळे",
+            "पु",
+            "रो",
+            "हि",
+            "तं",
+            "वा",
+            "क्",
+            "ऋ",
+            "षिः"
+        ]
+    );
+    assert_eq!(aksharas[0].vowel, Some(AbugidaToken::VowelA));
+    // A word-final consonant is a halanta akshara, not joined to the next word
+    assert!(aksharas[9].is_halanta());
+    assert_eq!(
+        aksharas[9].tokens,
+        [AbugidaToken::ConsonantK, AbugidaToken::MarkVirama]
+    );
+    assert_eq!(aksharas[10].vowel, Some(AbugidaToken::VowelR));
+    assert_eq!(aksharas[11].final_marks, [AbugidaToken::MarkVisarga]);
+}
+
+#[test]
+fn test_syllabify_roman_matches_devanagari() {
+    let shlesha = Shlesha::new();
+
+    for (iast, devanagari) in [
+        ("saṃskṛtam", "संस्कृतम्"),
+        ("dharmakṣetre kurukṣetre", "धर्मक्षेत्रे कुरुक्षेत्रे"),
+        ("tat savitur vareṇyaṃ", "तत् सवितुर् वरेण्यं"),
+    ] {
+        let roman = shlesha.syllabify(iast, "iast").unwrap();
+        let abugida = shlesha.syllabify(devanagari, "devanagari").unwrap();
+
+        let tokens = |aksharas: &[Akshara]| -> Vec<Vec<AbugidaToken>> {
+            aksharas
+                .iter()
+                .map(|akshara| akshara.tokens.clone())
+                .collect()
+        };
+        assert_eq!(tokens(&roman), tokens(&abugida), "{iast}");
+        // Each akshara's text is its own span of the Roman input
+        for akshara in &roman {
+            assert_eq!(
+                &iast[akshara.position..akshara.position + akshara.text.len()],
+                akshara.text
+            );
+        }
+    }
+
+    let aksharas = shlesha.syllabify("saṃskṛtam", "iast").unwrap();
+    assert_eq!(texts(&aksharas), ["saṃ", "skṛ", "ta", "m"]);
+    assert!(aksharas[3].is_halanta());
+}
@@ -0,0 +1,142 @@
+//! Allocations the conversion layers add around the converters
+//!
+//! Short-string workloads (one word per call) are dominated by per-call
+//! overhead, so resolving the script names must not allocate. A counting global
+//! allocator compares `Shlesha::transliterate` with calling the converters it
+//! routes to directly; the difference is what the layers in between cost.
+
+use shlesha::modules::script_converter::ScriptConverterRegistry;
+use shlesha::Shlesha;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    // Per thread, so tests running in parallel do not count each other
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Allocations made by `f`, after a first call to warm up lazy tables
+fn allocations<T>(mut f: impl FnMut() -> T) -> usize {
+    drop(f());
+    let before = ALLOCATIONS.with(Cell::get);
+    drop(f());
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn test_direct_pair_adds_no_allocations() {
+    let shlesha = Shlesha::new();
+    let registry = ScriptConverterRegistry::default();
+    let direct = registry
+        .direct_converter("iast", "devanagari", None)
+        .unwrap();
+
+    for word in ["dharma", "kṣetra", "saṃskṛtam"] {
+        let converter = allocations(|| direct.convert(word).unwrap());
+        // Aliases are resolved without allocating too
+        for (from, to) in [("iast", "devanagari"), ("iast", "deva")] {
+            let transliterate = allocations(|| shlesha.transliterate(word, from, to).unwrap());
+            assert_eq!(transliterate, converter, "{word}: {from} → {to}");
+        }
+    }
+}
+
+#[test]
+fn test_hub_path_adds_no_allocations() {
+    let mut shlesha = Shlesha::new();
+    shlesha.set_force_hub(true);
+    let registry = ScriptConverterRegistry::default();
+
+    for word in ["ధర్మ", "సంస్కృతం"] {
+        let converters = allocations(|| {
+            let hub = registry.to_hub("telugu", word).unwrap();
+            registry.from_hub("kannada", &hub).unwrap()
+        });
+        for (from, to) in [("telugu", "kannada"), ("te", "kn")] {
+            let transliterate = allocations(|| shlesha.transliterate(word, from, to).unwrap());
+            assert_eq!(transliterate, converters, "{word}: {from} → {to}");
+        }
+    }
+}
+
+#[test]
+fn test_passthrough_lines_are_not_copied() {
+    let shlesha = Shlesha::new();
+    // Half the lines have nothing Devanagari reads
+    let corpus = [
+        "धर्मक्षेत्रे कुरुक्षेत्रे",
+        "1.1 (p. 42)",
+        "समवेता युयुत्सवः",
+        "-- 2024-10-16 --",
+    ];
+
+    let cow = allocations(|| {
+        for line in corpus {
+            drop(
+                shlesha
+                    .transliterate_cow(line, "devanagari", "iast")
+                    .unwrap(),
+            );
+        }
+    });
+    let devanagari_lines = allocations(|| {
+        for line in [corpus[0], corpus[2]] {
+            drop(shlesha.transliterate(line, "devanagari", "iast").unwrap());
+        }
+    });
+    assert_eq!(cow, devanagari_lines);
+
+    let identity = allocations(|| {
+        shlesha
+            .transliterate_cow(corpus[0], "devanagari", "devanagari")
+            .unwrap()
+    });
+    assert_eq!(identity, 0);
+}
+
+#[test]
+fn test_transliterate_into_streams_without_allocating() {
+    let shlesha = Shlesha::new();
+    let mut output = String::from("cleared first");
+    output.reserve(256);
+
+    for text in [
+        "धर्मक्षेत्रे कुरुक्षेत्रे",
+        "समवेता युयुत्सवः ।",
+        "क़लम ॐ १२३ x",
+        "अग्निमी॑ळे पु॒रोहि॑तं",
+    ] {
+        let allocated = allocations(|| {
+            shlesha
+                .transliterate_into(text, "devanagari", "iast", &mut output)
+                .unwrap()
+        });
+        assert_eq!(allocated, 0, "{text}");
+        assert_eq!(
+            output,
+            shlesha.transliterate(text, "devanagari", "iast").unwrap()
+        );
+    }
+
+    // Pairs that collect their tokens write the same output into the buffer
+    shlesha
+        .transliterate_into("dharmakṣetre", "iast", "devanagari", &mut output)
+        .unwrap();
+    assert_eq!(output, "धर्मक्षेत्रे");
+}
@@ -0,0 +1,94 @@
+use shlesha::{ApostropheAvagraha, Shlesha};
+
+#[test]
+fn test_apostrophe_between_vowel_and_syllable_is_avagraha() {
+    let shlesha = Shlesha::new();
+    for (from, text) in [
+        ("iast", "so'ham"),
+        ("iso15919", "sō'ham"),
+        ("harvard_kyoto", "so'ham"),
+    ] {
+        assert_eq!(
+            shlesha.transliterate(text, from, "devanagari").unwrap(),
+            "सोऽहम्",
+            "{from}"
+        );
+    }
+    assert_eq!(
+        shlesha
+            .transliterate("tvayā'dya", "iast", "devanagari")
+            .unwrap(),
+        "त्वयाऽद्य"
+    );
+    // The typographic and modifier letter apostrophes read the same
+    for text in ["so\u{2019}ham", "so\u{02BC}ham"] {
+        assert_eq!(
+            shlesha.transliterate(text, "iast", "devanagari").unwrap(),
+            "सोऽहम्"
+        );
+        assert_eq!(
+            shlesha.transliterate(text, "iast", "slp1").unwrap(),
+            "so`ham"
+        );
+    }
+}
+
+#[test]
+fn test_english_apostrophes_pass_through() {
+    let shlesha = Shlesha::new();
+    let text = "Arjuna's bow, kṛṣṇa' iti and 'rāma' so'ham";
+    assert_eq!(
+        shlesha.transliterate(text, "iast", "slp1").unwrap(),
+        "Arjuna's bow, kfzRa' iti and 'rAma' so`ham"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("arjuna’s dhanuḥ", "iast", "devanagari")
+            .unwrap(),
+        "अर्जुन’स् धनुः"
+    );
+
+    // Punctuation is not reported as unknown
+    let result = shlesha
+        .transliterate_with_metadata("arjuna’s", "iast", "devanagari")
+        .unwrap();
+    assert!(result.metadata.unwrap().unknown_tokens.is_empty());
+}
+
+#[test]
+fn test_forced_apostrophe_readings() {
+    let mut shlesha = Shlesha::new();
+    assert_eq!(
+        shlesha.apostrophe_avagraha(),
+        ApostropheAvagraha::Contextual
+    );
+    let text = "arjuna's so’ham";
+
+    shlesha.set_apostrophe_avagraha(ApostropheAvagraha::Always);
+    assert_eq!(
+        shlesha.transliterate(text, "iast", "devanagari").unwrap(),
+        "अर्जुनऽस् सोऽहम्"
+    );
+    assert_eq!(
+        shlesha.transliterate(text, "iast", "slp1").unwrap(),
+        "arjuna`s so`ham"
+    );
+
+    shlesha.set_apostrophe_avagraha(ApostropheAvagraha::Never);
+    assert_eq!(
+        shlesha.transliterate(text, "iast", "devanagari").unwrap(),
+        "अर्जुन'स् सो’हम्"
+    );
+    let result = shlesha
+        .transliterate_with_metadata(text, "iast", "devanagari")
+        .unwrap();
+    assert_eq!(result.output, "अर्जुन'स् सो’हम्");
+
+    // Schemes that write avagraha otherwise are not affected
+    assert_eq!(
+        shlesha
+            .transliterate("so`ham", "slp1", "devanagari")
+            .unwrap(),
+        "सोऽहम्"
+    );
+}
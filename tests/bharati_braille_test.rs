@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod bharati_braille_tests {
+    use shlesha::Shlesha;
+
+    #[test]
+    fn test_bharati_braille_from_devanagari() {
+        let transliterator = Shlesha::new();
+
+        // Vowel signs are written with the independent vowel's cell, after the
+        // consonant; the inherent a is not written
+        let result = transliterator
+            .transliterate(
+                "भारत एक विशाल देश है। यहाँ अनेक भाषाएँ बोली जाती हैं।",
+                "devanagari",
+                "bharati_braille",
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            "⠘⠜⠗⠞ ⠑⠅ ⠧⠊⠩⠜⠇ ⠙⠑⠩ ⠓⠌। ⠽⠓⠜⠄ ⠁⠝⠑⠅ ⠘⠜⠯⠜⠑⠄ ⠃⠕⠇⠔ ⠚⠜⠞⠔ ⠓⠌⠰।"
+        );
+
+        // The virama joins clusters, and kṣa and jña have cells of their own
+        assert_eq!(
+            transliterator
+                .transliterate("नमस्ते कृष्ण क्षत्रिय ज्ञान", "devanagari", "braille")
+                .unwrap(),
+            "⠝⠍⠎⠈⠞⠑ ⠅⠐⠗⠯⠈⠼ ⠟⠞⠈⠗⠊⠽ ⠱⠜⠝"
+        );
+    }
+
+    #[test]
+    fn test_bharati_braille_numeric_indicator() {
+        let transliterator = Shlesha::new();
+
+        // Each run of digits starts with ⠼, and the digits are the cells of a to j
+        let result = transliterator
+            .transliterate("सन् १९४७ में भारत स्वतंत्र हुआ।", "devanagari", "braille")
+            .unwrap();
+        assert_eq!(result, "⠎⠝⠈ ⠼⠁⠊⠙⠛ ⠍⠑⠰ ⠘⠜⠗⠞ ⠎⠈⠧⠞⠰⠞⠈⠗ ⠓⠥⠜।");
+
+        assert_eq!(
+            transliterator
+                .transliterate("adhyāyaḥ 18, ślokaḥ 66", "iast", "braille")
+                .unwrap(),
+            "⠁⠮⠈⠽⠜⠽⠠ ⠼⠁⠓, ⠩⠈⠇⠕⠅⠠ ⠼⠋⠋"
+        );
+    }
+
+    #[test]
+    fn test_bharati_braille_to_devanagari() {
+        let transliterator = Shlesha::new();
+
+        for text in [
+            "भारत एक विशाल देश है। यहाँ अनेक भाषाएँ बोली जाती हैं।",
+            "सन् १९४७ में भारत स्वतंत्र हुआ।",
+            "नमस्ते कृष्ण क्षत्रिय ज्ञान संस्कृतम्",
+        ] {
+            let braille = transliterator
+                .transliterate(text, "devanagari", "braille")
+                .unwrap();
+            let back = transliterator
+                .transliterate(&braille, "braille", "devanagari")
+                .unwrap();
+            assert_eq!(back, text);
+        }
+
+        // ⠼ is ṇa within a word, and the numeric indicator only at its start
+        assert_eq!(
+            transliterator
+                .transliterate("⠛⠼⠑⠩ ⠍⠼⠊ ⠼⠁⠚⠓", "braille", "devanagari")
+                .unwrap(),
+            "गणेश मणि १०८"
+        );
+    }
+
+    #[test]
+    fn test_bharati_braille_merges_reported_as_lossy() {
+        let transliterator = Shlesha::new();
+
+        // Precomposed qa
+        let result = transliterator
+            .transliterate_with_metadata("\u{0958}लम ऱ", "devanagari", "braille")
+            .unwrap();
+        assert_eq!(result.output, "⠅⠇⠍ ⠗");
+
+        let metadata = result.metadata.unwrap();
+        let merges: Vec<_> = metadata
+            .lossy_mappings
+            .iter()
+            .map(|m| (m.token.as_str(), m.substitute.as_str()))
+            .collect();
+        assert_eq!(
+            merges,
+            [("ConsonantQa", "ConsonantK"), ("ConsonantRr", "ConsonantR")]
+        );
+    }
+}
@@ -46,18 +46,22 @@ fn test_bijective_roundtrip_multiple_scripts() {
             // Convert to target script
             let converted = shlesha
                 .transliterate(original_char, source_script, target_script)
-                .expect(&format!(
-                    "Failed to convert {} from {} to {}",
-                    original_char, source_script, target_script
-                ));
+                .unwrap_or_else(|_| {
+                    panic!(
+                        "Failed to convert {} from {} to {}",
+                        original_char, source_script, target_script
+                    )
+                });
 
             // Convert back to source script
             let roundtrip = shlesha
                 .transliterate(&converted, target_script, source_script)
-                .expect(&format!(
-                    "Failed to convert back from {} to {}",
-                    target_script, source_script
-                ));
+                .unwrap_or_else(|_| {
+                    panic!(
+                        "Failed to convert back from {} to {}",
+                        target_script, source_script
+                    )
+                });
 
             assert_eq!(
                 original_char,
@@ -0,0 +1,82 @@
+mod common;
+
+#[cfg(test)]
+mod brahmi_tests {
+    use crate::common::syllabary;
+    use shlesha::Shlesha;
+
+    const SIGNS: &[&str] = &[
+        "", "ा", "ि", "ी", "ु", "ू", "ृ", "ॄ", "ॢ", "ॣ", "े", "ै", "ो", "ौ", "्", "ं", "ः", "ँ",
+    ];
+
+    const CONSONANTS: &[&str] = &[
+        "क", "ख", "ग", "घ", "ङ", "च", "छ", "ज", "झ", "ञ", "ट", "ठ", "ड", "ढ", "ण", "त", "थ", "द",
+        "ध", "न", "प", "फ", "ब", "भ", "म", "य", "र", "ल", "व", "श", "ष", "स", "ह", "ळ",
+    ];
+
+    const VOWELS: &[&str] = &[
+        "अ", "आ", "इ", "ई", "उ", "ऊ", "ऋ", "ॠ", "ऌ", "ॡ", "ए", "ऐ", "ओ", "औ",
+    ];
+
+    #[test]
+    fn test_brahmi_from_devanagari() {
+        let transliterator = Shlesha::new();
+
+        let result = transliterator
+            .transliterate("धर्मक्षेत्रे कुरुक्षेत्रे", "devanagari", "brahmi")
+            .unwrap();
+        assert_eq!(result, "𑀥𑀭𑁆𑀫𑀓𑁆𑀱𑁂𑀢𑁆𑀭𑁂 𑀓𑀼𑀭𑀼𑀓𑁆𑀱𑁂𑀢𑁆𑀭𑁂");
+        assert_eq!(
+            transliterator
+                .transliterate(&result, "brah", "iast")
+                .unwrap(),
+            "dharmakṣetre kurukṣetre"
+        );
+    }
+
+    #[test]
+    fn test_brahmi_syllabary_roundtrip() {
+        let transliterator = Shlesha::new();
+
+        for line in syllabary(CONSONANTS, SIGNS, VOWELS) {
+            let result = transliterator
+                .transliterate_with_metadata(&line, "devanagari", "brahmi")
+                .unwrap();
+            assert!(
+                result.metadata.unwrap().unknown_tokens.is_empty(),
+                "{line} has unmapped tokens in brahmi: {}",
+                result.output
+            );
+            let back = transliterator
+                .transliterate(&result.output, "brahmi", "devanagari")
+                .unwrap();
+            assert_eq!(back, line);
+        }
+    }
+
+    #[test]
+    fn test_brahmi_numerals() {
+        let transliterator = Shlesha::new();
+
+        // Positional digits map to digit tokens
+        let result = transliterator
+            .transliterate("१०८ २०२४", "devanagari", "brahmi")
+            .unwrap();
+        assert_eq!(result, "𑁧𑁦𑁮 𑁨𑁦𑁨𑁪");
+        assert_eq!(
+            transliterator
+                .transliterate(&result, "brahmi", "devanagari")
+                .unwrap(),
+            "१०८ २०२४"
+        );
+
+        // The additive numerals (here 100 and 8) have no tokens and pass through
+        let additive = "\u{11064}\u{11059}";
+        assert_eq!(
+            transliterator
+                .transliterate(additive, "brahmi", "devanagari")
+                .unwrap(),
+            additive
+        );
+    }
+}
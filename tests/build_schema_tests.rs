@@ -0,0 +1,153 @@
+//! The schema checks build.rs makes before generating code
+
+#[allow(dead_code)]
+#[path = "../build/schema.rs"]
+mod schema;
+
+use std::fs;
+use std::path::Path;
+
+use schema::{
+    assign_token_ids, check_token_names, parse_token_ids, read_schemas, HubTokens, TokenId,
+};
+
+const ABUGIDA_TOKENS: &str = r#"
+metadata:
+  name: "abugida_tokens"
+  script_type: "brahmic"
+  has_implicit_a: false
+target: "devanagari"
+mappings:
+  marks:
+    MarkPluta: "[MarkPluta]"
+"#;
+
+const SCRIPT: &str = r#"
+metadata:
+  name: "broken"
+  script_type: "brahmic"
+  has_implicit_a: true
+target: "abugida_tokens"
+mappings:
+  vowels:
+    VowelA: "a"
+    VowelAa: "aa"
+  vowel_signs:
+    VowelSignAa: "A"
+  consonants:
+    ConsonantK: "k"
+"#;
+
+fn write(dir: &Path, name: &str, content: &str) {
+    fs::write(dir.join(name), content).unwrap();
+}
+
+#[test]
+fn test_shipped_schemas_pass() {
+    let schemas = read_schemas(Path::new("schemas")).unwrap();
+    assert!(schemas
+        .iter()
+        .any(|schema| schema.metadata.name == "devanagari"));
+    check_token_names(&schemas).unwrap();
+}
+
+#[test]
+fn test_parse_error_names_file_and_yaml_path() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "abugida_tokens.yaml", ABUGIDA_TOKENS);
+    write(
+        dir.path(),
+        "broken.yaml",
+        &SCRIPT.replace(r#"ConsonantK: "k""#, "ConsonantK: {k: 1}"),
+    );
+
+    let error = read_schemas(dir.path()).err().unwrap();
+    assert!(
+        error.contains("broken.yaml: at mappings.consonants.ConsonantK: "),
+        "{error}"
+    );
+    assert!(error.contains("line 14"), "{error}");
+}
+
+#[test]
+fn test_unknown_token_names_are_listed() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "abugida_tokens.yaml", ABUGIDA_TOKENS);
+    let script = format!(
+        "{SCRIPT}lossy_mappings:\n  VowelAa: VowelA\n  VowelAi: VowelAa\nfallbacks:\n  VowelR: [ConsonantR, VowelSignAa]\n"
+    );
+    write(dir.path(), "broken.yaml", &script);
+
+    let schemas = read_schemas(dir.path()).unwrap();
+    let error = check_token_names(&schemas).err().unwrap();
+    let offenders: Vec<&str> = error
+        .lines()
+        .filter(|line| line.starts_with("  "))
+        .collect();
+    let path = dir.path().join("broken.yaml");
+    let path = path.display();
+    assert_eq!(
+        offenders,
+        [
+            format!("  {path}: at lossy_mappings.VowelAi: VowelAi is not an abugida token"),
+            format!("  {path}: at fallbacks.VowelR: VowelR is not an abugida token"),
+            format!("  {path}: at fallbacks.VowelR: ConsonantR is not an abugida token"),
+        ]
+    );
+}
+
+#[test]
+fn test_token_lists_supply_tokens_no_script_maps() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "abugida_tokens.yaml", ABUGIDA_TOKENS);
+    let script = format!("{SCRIPT}lossy_mappings:\n  MarkPluta: VowelAa\n");
+    write(dir.path(), "script.yaml", &script);
+
+    let schemas = read_schemas(dir.path()).unwrap();
+    check_token_names(&schemas).unwrap();
+}
+
+#[test]
+fn test_new_tokens_do_not_shift_existing_ids() {
+    let committed = parse_token_ids(&fs::read_to_string("build/token_ids.tsv").unwrap()).unwrap();
+    let mut schemas = read_schemas(Path::new("schemas")).unwrap();
+
+    // The shipped schemas have an ID for every token
+    let mut ids = committed.clone();
+    assert!(assign_token_ids(&mut ids, &HubTokens::collect(&schemas)).is_empty());
+
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "fake.yaml",
+        &SCRIPT.replace("ConsonantK", "ConsonantAaa"),
+    );
+    schemas.extend(read_schemas(dir.path()).unwrap());
+    let added = assign_token_ids(&mut ids, &HubTokens::collect(&schemas));
+
+    let next = committed.iter().map(|entry| entry.id).max().unwrap() + 1;
+    assert_eq!(
+        added,
+        [TokenId {
+            id: next,
+            format: "abugida".to_string(),
+            name: "ConsonantAaa".to_string(),
+        }]
+    );
+    assert_eq!(ids[..committed.len()], committed[..]);
+}
+
+#[test]
+fn test_token_id_registry_errors() {
+    assert!(parse_token_ids("# header\n\n1\tabugida\tVowelA\n").is_ok());
+    for (content, error) in [
+        ("0\tabugida\tVowelA\n", "line 1: ID 0"),
+        ("1\tabugida\tVowelA\n1\talphabet\tVowelA\n", "line 2: "),
+        ("1\tabugida\tVowelA\n2\tabugida\tVowelA\n", "line 2: "),
+        ("1 abugida VowelA\n", "line 1: expected"),
+        ("x\tabugida\tVowelA\n", "line 1: ID x"),
+    ] {
+        let message = parse_token_ids(content).err().unwrap();
+        assert!(message.starts_with(error), "{message}");
+    }
+}
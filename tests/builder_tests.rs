@@ -0,0 +1,97 @@
+use shlesha::{Shlesha, ShleshaError, TransliterationOptions};
+use std::fs;
+
+const GOOD_SCHEMA: &str = r#"
+metadata:
+  name: "builder_roman"
+  script_type: "roman"
+  has_implicit_a: false
+target: "alphabet_tokens"
+mappings:
+  vowels:
+    VowelA: "a"
+  consonants:
+    ConsonantK: "q"
+"#;
+
+#[test]
+fn test_bad_schema_fails_the_build() {
+    let dir = tempfile::tempdir().unwrap();
+    let bad = dir.path().join("broken.yaml");
+    fs::write(&bad, "metadata:\n  name: [unclosed\n").unwrap();
+
+    let error = Shlesha::builder()
+        .with_schema_str(GOOD_SCHEMA, "builder_roman")
+        .with_schema_file(&bad)
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(error, ShleshaError::Schema(_)), "{error:?}");
+    assert!(error.to_string().contains("broken.yaml"), "{error}");
+
+    // Every failing file of a directory is listed
+    fs::write(dir.path().join("good.yaml"), GOOD_SCHEMA).unwrap();
+    fs::write(dir.path().join("empty.yml"), "").unwrap();
+    let error = Shlesha::builder()
+        .with_schema_dir(dir.path())
+        .build()
+        .err()
+        .unwrap();
+    let message = error.to_string();
+    assert!(message.starts_with("2 schema(s)"), "{message}");
+    assert!(message.contains("broken.yaml"), "{message}");
+    assert!(message.contains("empty.yml"), "{message}");
+
+    let missing = dir.path().join("missing.tsv");
+    let error = Shlesha::builder()
+        .with_word_overrides("iast", "devanagari", &missing)
+        .build()
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("missing.tsv"), "{error}");
+}
+
+#[test]
+fn test_good_configuration_is_ready_when_built() {
+    let dir = tempfile::tempdir().unwrap();
+    let overrides = dir.path().join("overrides.tsv");
+    fs::write(&overrides, "qa\tक़\n").unwrap();
+
+    let shlesha = Shlesha::builder()
+        .with_schema_str(GOOD_SCHEMA, "builder_roman")
+        .with_word_overrides("builder_roman", "devanagari", &overrides)
+        .build()
+        .unwrap();
+    assert!(shlesha.supports_script("builder_roman"));
+    assert_eq!(
+        shlesha
+            .transliterate("qa aqa", "builder_roman", "devanagari")
+            .unwrap(),
+        "क़ अक"
+    );
+
+    // Default options apply to conversions given none
+    let shlesha = Shlesha::builder()
+        .with_options(TransliterationOptions::itrans_legacy_caps())
+        .build()
+        .unwrap();
+    assert_eq!(
+        shlesha
+            .transliterate("DHARMAH", "itrans", "devanagari")
+            .unwrap(),
+        "धर्मः"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate_with_metadata("DHARMAH", "itrans", "devanagari")
+            .unwrap()
+            .output,
+        "धर्मः"
+    );
+    assert_ne!(
+        Shlesha::new()
+            .transliterate("DHARMAH", "itrans", "devanagari")
+            .unwrap(),
+        "धर्मः"
+    );
+}
@@ -0,0 +1,95 @@
+//! Candra vowels ऍ/ॅ and ऑ/ॉ of Hindi and Marathi loanwords
+
+use shlesha::{Shlesha, TransliterationOptions, UnicodeForm};
+
+#[test]
+fn test_loanwords_roundtrip_through_roman() {
+    let shlesha = Shlesha::new();
+
+    for (devanagari, iso, itrans) in [
+        ("डॉक्टर", "ḍôkṭara", "Do.ckTara"),
+        ("कॉलेज", "kôlēja", "ko.cleja"),
+        ("ऑपरेशन", "ôparēśana", "o.cpareshana"),
+        ("बॅट", "bêṭa", "be.cTa"),
+        ("ऍक्ट", "êkṭa", "e.ckTa"),
+        ("बैंक", "baiṁka", "baiMka"),
+    ] {
+        assert_eq!(
+            shlesha
+                .transliterate(devanagari, "devanagari", "iso15919")
+                .unwrap(),
+            iso
+        );
+        assert_eq!(
+            shlesha
+                .transliterate(devanagari, "devanagari", "itrans")
+                .unwrap(),
+            itrans
+        );
+        for scheme in ["iso15919", "iast", "itrans"] {
+            let roman = shlesha
+                .transliterate(devanagari, "devanagari", scheme)
+                .unwrap();
+            assert_eq!(
+                shlesha.transliterate(&roman, scheme, "devanagari").unwrap(),
+                devanagari,
+                "{devanagari} via {scheme}"
+            );
+        }
+    }
+
+    // IAST borrows ISO-15919's letters
+    assert_eq!(
+        shlesha.transliterate("कॉफ़ी", "devanagari", "iast").unwrap(),
+        "kôfī"
+    );
+
+    // ITRANS 5.3 writes फ़ as f; Devanagari output precomposes it unless asked for NFC
+    let itrans = shlesha
+        .transliterate("कॉफ़ी", "devanagari", "itrans")
+        .unwrap();
+    assert_eq!(itrans, "ko.cfii");
+    assert_eq!(
+        shlesha
+            .transliterate(&itrans, "itrans", "devanagari")
+            .unwrap(),
+        "कॉ\u{095E}ी"
+    );
+    let nfc = TransliterationOptions {
+        unicode_form: UnicodeForm::Nfc,
+        ..Default::default()
+    };
+    assert_eq!(
+        shlesha
+            .transliterate_with_options(&itrans, "itrans", "devanagari", &nfc)
+            .unwrap(),
+        "कॉफ़ी"
+    );
+}
+
+#[test]
+fn test_alternate_spellings_of_candra_vowels() {
+    let shlesha = Shlesha::new();
+
+    // A combining circumflex reads as the precomposed letter
+    assert_eq!(
+        shlesha
+            .transliterate("ḍo\u{0302}kṭara", "iast", "devanagari")
+            .unwrap(),
+        "डॉक्टर"
+    );
+    // Marathi candra a ॲ is candra e
+    assert_eq!(
+        shlesha
+            .transliterate("ॲक्ट", "devanagari", "iso15919")
+            .unwrap(),
+        "êkṭa"
+    );
+    // अ + candra e sign, as OCR spells it
+    assert_eq!(
+        shlesha
+            .transliterate("अॅक्ट", "devanagari", "iso15919")
+            .unwrap(),
+        "êkṭa"
+    );
+}
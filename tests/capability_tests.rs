@@ -0,0 +1,126 @@
+use shlesha::modules::hub::tokens::HubTokenSequence;
+use shlesha::modules::script_converter::{IastConverter, TokenConverter};
+use shlesha::Shlesha;
+
+#[test]
+fn test_devanagari_to_iso15919_is_lossless() {
+    let capability = Shlesha::new().pair_capability("devanagari", "iso15919");
+    assert!(capability.supported);
+    assert!(!capability.lossy, "{:?}", capability.notes);
+    assert!(capability.notes.is_empty());
+}
+
+#[test]
+fn test_devanagari_to_iast_merges_the_visarga_variants() {
+    let capability = Shlesha::new().pair_capability("devanagari", "iast");
+    assert!(capability.supported);
+    assert_eq!(
+        capability.notes,
+        [
+            "MarkJihvamuliya is written as MarkVisarga",
+            "MarkUpadhmaniya is written as MarkVisarga"
+        ]
+    );
+}
+
+#[test]
+fn test_devanagari_to_tamil_is_lossy() {
+    let capability = Shlesha::new().pair_capability("devanagari", "tamil");
+    assert!(capability.supported);
+    assert!(capability.lossy);
+    for note in [
+        "ConsonantQa is not written in tamil",
+        "MarkAvagraha is not written in tamil",
+        "SpecialOm is not written in tamil",
+    ] {
+        assert!(capability.notes.contains(&note.to_string()), "{note}");
+    }
+}
+
+#[test]
+fn test_declared_lossy_mappings_make_a_pair_lossy() {
+    let capability = Shlesha::new().pair_capability("telugu", "devanagari");
+    assert!(capability.lossy);
+    assert!(capability
+        .notes
+        .contains(&"VowelSignE is written as VowelSignEe".to_string()));
+
+    // Roman targets keep the short/long distinction
+    let capability = Shlesha::new().pair_capability("telugu", "iso15919");
+    assert!(!capability.lossy, "{:?}", capability.notes);
+}
+
+#[test]
+fn test_unknown_script_is_unsupported() {
+    let shlesha = Shlesha::new();
+    for (from, to) in [("devanagari", "klingon"), ("klingon", "iast")] {
+        let capability = shlesha.pair_capability(from, to);
+        assert!(!capability.supported);
+        assert!(!capability.lossy);
+        assert_eq!(
+            capability.notes,
+            vec!["unknown script 'klingon'".to_string()]
+        );
+    }
+}
+
+#[test]
+fn test_aliases_resolve_to_the_same_capability() {
+    let shlesha = Shlesha::new();
+    let by_alias = shlesha.pair_capability("deva", "iso");
+    let by_name = shlesha.pair_capability("devanagari", "iso15919");
+    assert_eq!(by_alias.lossy, by_name.lossy);
+    assert_eq!(by_alias.notes, by_name.notes);
+}
+
+#[test]
+fn test_capability_matrix_lists_primary_pairs() {
+    let matrix = Shlesha::new().capability_matrix();
+
+    let pair = |from: &str, to: &str| matrix.iter().find(|p| p.from == from && p.to == to);
+    assert!(pair("devanagari", "iso15919").is_some_and(|p| p.supported && !p.lossy));
+    assert!(pair("devanagari", "tamil").is_some_and(|p| p.supported && p.lossy));
+
+    // Aliases and identity conversions are left out
+    assert!(matrix.iter().all(|p| p.from != p.to));
+    assert!(matrix.iter().all(|p| p.from != "deva" && p.to != "iso"));
+    assert!(matrix.iter().all(|p| p.supported));
+}
+
+#[test]
+fn test_converter_without_token_list_has_unknown_coverage() {
+    struct PlainIast;
+
+    impl TokenConverter for PlainIast {
+        fn string_to_tokens(&self, input: &str) -> HubTokenSequence {
+            IastConverter::new().string_to_tokens(input)
+        }
+
+        fn tokens_to_string(&self, tokens: &HubTokenSequence) -> String {
+            IastConverter::new().tokens_to_string(tokens)
+        }
+
+        fn script_name(&self) -> &'static str {
+            "plain_iast"
+        }
+
+        fn is_alphabet(&self) -> bool {
+            true
+        }
+    }
+
+    let mut shlesha = Shlesha::new();
+    shlesha.register_token_converter(Box::new(PlainIast), &[]);
+
+    let capability = shlesha.pair_capability("plain_iast", "devanagari");
+    assert!(capability.supported);
+    assert!(!capability.lossy);
+    assert_eq!(
+        capability.notes,
+        vec!["tokens read by plain_iast are not known".to_string()]
+    );
+    assert!(shlesha
+        .capability_matrix()
+        .iter()
+        .any(|p| p.from == "plain_iast" && p.to == "devanagari"));
+}
@@ -0,0 +1,145 @@
+//! Capitals in Roman output with the `capitalize` and `proper_nouns` options
+
+// proper_nouns is a list of byte ranges, often of one
+#![allow(clippy::single_range_in_vec_init)]
+
+use std::collections::BTreeSet;
+
+use shlesha::modules::capitalize::{capitalize, words_in_spans};
+use shlesha::{Capitalization, Shlesha, TransliterationOptions};
+
+const VERSE: &str = "धर्मक्षेत्रे कुरुक्षेत्रे समवेता युयुत्सवः । मामकाः पाण्डवाश्चैव किमकुर्वत सञ्जय ॥ ओम्";
+
+fn capitalized(text: &str, to: &str, options: &TransliterationOptions) -> String {
+    Shlesha::new()
+        .transliterate_with_options(text, "devanagari", to, options)
+        .unwrap()
+}
+
+fn sentences() -> TransliterationOptions {
+    TransliterationOptions {
+        capitalize: Capitalization::Sentences,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_sentences_start_after_dandas() {
+    assert_eq!(
+        capitalized(VERSE, "iast", &sentences()),
+        "Dharmakṣetre kurukṣetre samavetā yuyutsavaḥ । Māmakāḥ pāṇḍavāścaiva kimakurvata sañjaya ॥ Om"
+    );
+    // A danda written as a bar, and sentences ending in a full stop or question mark
+    assert_eq!(
+        capitalize(
+            "ṛṣir uvāca | ādau. īśaḥ? (ūrdhvam) 2 ṛtam",
+            Capitalization::Sentences,
+            &BTreeSet::new()
+        ),
+        "Ṛṣir uvāca | Ādau. Īśaḥ? (Ūrdhvam) 2 ṛtam"
+    );
+}
+
+#[test]
+fn test_words_keep_their_diacritics() {
+    let words = TransliterationOptions {
+        capitalize: Capitalization::Words,
+        ..Default::default()
+    };
+    assert_eq!(
+        capitalized("आदिपर्व ऋग्वेद शिव षडानन ऐरावत", "iast", &words),
+        "Ādiparva Ṛgveda Śiva Ṣaḍānana Airāvata"
+    );
+    assert_eq!(capitalized("ऋग्वेद ओम्", "iso15919", &words), "R̥gvēda Ōm");
+    // A letter followed by a combining mark is uppercased with the mark in place
+    assert_eq!(
+        capitalize(
+            "a\u{0304}di r\u{0325}ṣi",
+            Capitalization::Words,
+            &BTreeSet::new()
+        ),
+        "A\u{0304}di R\u{0325}ṣi"
+    );
+}
+
+#[test]
+fn test_proper_nouns_are_marked_in_the_input() {
+    let text = "श्रीमद्भगवद्गीता अर्जुन उवाच । कृष्णः";
+    let arjuna = text.find("अर्जुन").unwrap();
+    let krishna = text.find("कृष्णः").unwrap();
+    let options = TransliterationOptions {
+        proper_nouns: vec![0..3, arjuna..arjuna + 3, krishna..text.len()],
+        ..Default::default()
+    };
+    let expected = "Śrīmadbhagavadgītā Arjuna uvāca । Kṛṣṇaḥ";
+    assert_eq!(capitalized(text, "iast", &options), expected);
+
+    let result = Shlesha::new()
+        .transliterate_with_options_and_metadata(text, "devanagari", "iast", &options)
+        .unwrap();
+    assert_eq!(result.output, expected);
+
+    // Proper nouns are capitalized together with sentence starts
+    let text = "वदति अर्जुन उवाच । किम्";
+    let arjuna = text.find("अर्जुन").unwrap();
+    let options = TransliterationOptions {
+        capitalize: Capitalization::Sentences,
+        proper_nouns: vec![arjuna..arjuna],
+        ..Default::default()
+    };
+    assert_eq!(
+        capitalized(text, "iast", &options),
+        "Vadati Arjuna uvāca । Kim"
+    );
+}
+
+#[test]
+fn test_words_in_spans() {
+    let text = "  śrī rāma\tkṛṣṇa ";
+    assert_eq!(
+        words_in_spans(text, &[5..6, 14..14]).unwrap(),
+        BTreeSet::from([0, 2])
+    );
+    assert_eq!(
+        words_in_spans(text, &[2..text.len()]).unwrap(),
+        BTreeSet::from([0, 1, 2])
+    );
+
+    let error = Shlesha::new()
+        .transliterate_with_options(
+            "राम",
+            "devanagari",
+            "iast",
+            &TransliterationOptions {
+                proper_nouns: vec![0..100],
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert!(error.to_string().contains("0..100"), "{error}");
+}
+
+#[test]
+fn test_case_significant_schemes_are_refused() {
+    let shlesha = Shlesha::new();
+    for to in ["slp1", "itrans", "harvard_kyoto", "velthuis"] {
+        let error = shlesha
+            .transliterate_with_options(VERSE, "devanagari", to, &sentences())
+            .unwrap_err();
+        assert!(error.to_string().contains("letters of their own"), "{to}");
+    }
+}
+
+#[test]
+fn test_scripts_without_case_are_unchanged() {
+    let shlesha = Shlesha::new();
+    let plain = shlesha
+        .transliterate(VERSE, "devanagari", "telugu")
+        .unwrap();
+    assert_eq!(capitalized(VERSE, "telugu", &sentences()), plain);
+    // The default writes Roman output as the scheme does
+    assert_eq!(
+        capitalized(VERSE, "iast", &TransliterationOptions::default()),
+        shlesha.transliterate(VERSE, "devanagari", "iast").unwrap()
+    );
+}
@@ -0,0 +1,88 @@
+//! `shlesha bench` on a small fixture
+#![cfg(feature = "bench")]
+
+use assert_cmd::Command;
+use serde_json::Value;
+
+fn bench(from: &str, to: &str, text: &str, extra: &[&str]) -> assert_cmd::assert::Assert {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("corpus.txt");
+    std::fs::write(&path, text).unwrap();
+    Command::cargo_bin("shlesha")
+        .unwrap()
+        .args(["bench", "--from", from, "--to", to, "--input"])
+        .arg(&path)
+        .args(["--iterations", "3", "--warmup", "1"])
+        .args(extra)
+        .assert()
+}
+
+fn bench_json(from: &str, to: &str, text: &str) -> Value {
+    let assert = bench(from, to, text, &["--format", "json"]).success();
+    serde_json::from_slice(&assert.get_output().stdout).unwrap()
+}
+
+#[test]
+fn test_json_timings_are_positive() {
+    let text = "dharmakṣetre kurukṣetre samavetā yuyutsavaḥ\n".repeat(20);
+    let json = bench_json("iast", "devanagari", &text);
+
+    assert_eq!(json["from"], "iast");
+    assert_eq!(json["to"], "devanagari");
+    assert_eq!(json["input_bytes"], text.len());
+    assert_eq!(json["input_chars"], text.chars().count());
+    assert_eq!(json["iterations"], 3);
+    assert_eq!(json["warmup"], 1);
+
+    // iast → devanagari has a direct converter, timed against the hub
+    let paths = json["paths"].as_array().unwrap();
+    let names: Vec<_> = paths.iter().map(|path| path["path"].clone()).collect();
+    assert_eq!(names, ["direct", "hub"]);
+    for path in paths {
+        let seconds = |statistic: &str| path[statistic]["seconds"].as_f64().unwrap();
+        assert!(seconds("min") > 0.0, "{path}");
+        assert!(seconds("min") <= seconds("median"), "{path}");
+        assert!(seconds("median") <= seconds("p95"), "{path}");
+        for statistic in ["min", "median", "p95"] {
+            assert!(path[statistic]["mb_per_s"].as_f64().unwrap() > 0.0);
+            assert!(path[statistic]["chars_per_s"].as_f64().unwrap() > 0.0);
+        }
+        if cfg!(feature = "count-allocations") {
+            assert!(path["allocations"].as_u64().unwrap() > 0, "{path}");
+        } else {
+            assert!(path["allocations"].is_null(), "{path}");
+        }
+    }
+}
+
+#[test]
+fn test_pair_without_direct_converter_times_the_hub() {
+    let json = bench_json("telugu", "tamil", "ధర్మక్షేత్రే కురుక్షేత్రే\n");
+    let paths = json["paths"].as_array().unwrap();
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0]["path"], "hub");
+}
+
+#[test]
+fn test_text_report() {
+    let assert = bench("iast", "slp1", "rāmaḥ\n", &[]).success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(
+        stdout.starts_with("iast → slp1: 9 bytes (6 chars), 3 iterations after 1 warmup\n"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("\ndirect:\n  min "), "{stdout}");
+    assert!(stdout.contains("\nhub:\n  min "), "{stdout}");
+    assert!(stdout.contains("direct is "), "{stdout}");
+    assert!(stdout.contains(" MB/s "), "{stdout}");
+}
+
+#[test]
+fn test_bench_errors() {
+    let assert = bench("klingon", "iast", "", &["--format", "json"]).failure();
+    let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+    let error: Value = serde_json::from_str(stderr.trim()).unwrap();
+    assert_eq!(error["error"], "unsupported script: klingon");
+
+    bench("iast", "slp1", "a", &["--iterations", "0"]).failure();
+}
@@ -130,6 +130,35 @@ mod cli_tests {
         assert_eq!(stdout.trim(), "a");
     }
 
+    #[test]
+    fn test_cli_stdin_raw_keeps_whitespace() {
+        let convert = |raw: bool| {
+            let mut command = Command::new(get_cli_binary());
+            command.args(["transliterate", "--from", "devanagari", "--to", "iast"]);
+            if raw {
+                command.arg("--raw");
+            }
+            let mut child = command
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::piped())
+                .spawn()
+                .expect("Failed to spawn CLI");
+            child
+                .stdin
+                .take()
+                .expect("Failed to get stdin")
+                .write_all("  अ  आ\t\nइ\n\n".as_bytes())
+                .expect("Failed to write to stdin");
+            let output = child.wait_with_output().expect("Failed to wait for CLI");
+            assert!(output.status.success());
+            String::from_utf8(output.stdout).unwrap()
+        };
+
+        assert_eq!(convert(true), "  a  ā\t\ni\n\n");
+        // Without --raw the input is trimmed and a newline follows the output
+        assert_eq!(convert(false), "a  ā\t\ni\n");
+    }
+
     #[test]
     fn test_cli_error_handling_invalid_script() {
         let output = Command::new(get_cli_binary())
@@ -232,4 +261,257 @@ mod cli_tests {
         assert!(stdout.contains("dharma"));
         assert!(stdout.contains("hello"));
     }
+
+    #[test]
+    fn test_cli_test_data_subcommand() {
+        let output = Command::new(get_cli_binary())
+            .arg("test-data")
+            .arg("tests/data")
+            .output()
+            .expect("Failed to execute CLI");
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains(", 0 failures"));
+    }
+
+    #[test]
+    fn test_cli_test_data_reports_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("wrong.yaml"),
+            "from: devanagari\nto: iast\ncases:\n  - input: \"धर्म\"\n    expected: \"dharmā\"\n",
+        )
+        .unwrap();
+
+        let output = Command::new(get_cli_binary())
+            .arg("test-data")
+            .arg(dir.path())
+            .output()
+            .expect("Failed to execute CLI");
+
+        assert!(!output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("wrong.yaml case 0 (devanagari → iast)"));
+        assert!(stdout.contains("1 files, 1 cases, 1 failures"));
+    }
+
+    #[test]
+    fn test_cli_pairs_command() {
+        let output = Command::new(get_cli_binary())
+            .arg("pairs")
+            .output()
+            .expect("Failed to execute CLI");
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("devanagari → iso15919: lossless"));
+        assert!(stdout.contains("devanagari → tamil: lossy"));
+
+        let output = Command::new(get_cli_binary())
+            .args(["pairs", "--lossy-only"])
+            .output()
+            .expect("Failed to execute CLI");
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(!stdout.contains("lossless"));
+        assert!(stdout.contains("devanagari → tamil: lossy\n  ConsonantFa is not written in tamil"));
+    }
+
+    #[test]
+    fn test_cli_schema_subcommands() {
+        let output = Command::new(get_cli_binary())
+            .args([
+                "schema", "new", "tulu", "--type", "brahmic", "--like", "kannada",
+            ])
+            .output()
+            .expect("Failed to execute CLI");
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("  name: \"tulu\"\n"));
+        assert!(stdout.contains("    ConsonantK: \"ಕ\"\n"));
+
+        let output = Command::new(get_cli_binary())
+            .args([
+                "schema",
+                "diff",
+                "schemas/devanagari.yaml",
+                "schemas/sharada.yaml",
+            ])
+            .output()
+            .expect("Failed to execute CLI");
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains(
+            "Only in schemas/devanagari.yaml: 21\n  vowels:\n    VowelCandraE\n    VowelCandraO\n    VowelE\n"
+        ));
+        assert!(stdout.contains("Only in schemas/sharada.yaml: 11\n"));
+    }
+
+    #[test]
+    fn test_cli_schema_validate() {
+        let dir = tempfile::tempdir().unwrap();
+        let broken = dir.path().join("broken.yaml");
+        std::fs::write(
+            &broken,
+            "metadata:\n  name: \"broken\"\n  script_type: \"roman\"\n  has_implicit_a: false\nmappings: [\n",
+        )
+        .unwrap();
+
+        let output = Command::new(get_cli_binary())
+            .args(["schema", "validate", "schemas/slp1.yaml"])
+            .arg(&broken)
+            .output()
+            .expect("Failed to execute CLI");
+
+        assert!(!output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains(&format!("Parse error in {} at line ", broken.display())));
+        assert!(stdout.ends_with("1 valid, 1 invalid\n"));
+    }
+
+    #[test]
+    fn test_cli_schema_test() {
+        let output = Command::new(get_cli_binary())
+            .args(["schema", "test", "schemas/telugu.yaml"])
+            .output()
+            .expect("Failed to execute CLI");
+        assert!(output.status.success());
+        assert!(String::from_utf8(output.stdout)
+            .unwrap()
+            .ends_with(" checks, 0 failures\n"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let wrong = dir.path().join("wrong.yaml");
+        std::fs::write(
+            &wrong,
+            "metadata:\n  name: \"wrong\"\n  script_type: \"roman\"\n  has_implicit_a: false\n\
+             target: \"alphabet_tokens\"\nmappings:\n  vowels:\n    VowelA: \"a\"\n    VowelI: \"i\"\n\
+             \x20 consonants:\n    ConsonantK: \"k\"\n\
+             tests:\n  - input: \"ki\"\n    expected_devanagari: \"का\"\n",
+        )
+        .unwrap();
+        let output = Command::new(get_cli_binary())
+            .args(["schema", "test"])
+            .arg(&wrong)
+            .output()
+            .expect("Failed to execute CLI");
+
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "wrong case 0 (wrong → devanagari): output mismatch\n\
+             \x20 input:    ki\n\
+             \x20 expected: का\n\
+             \x20 actual:   कि\n\
+             \x20 position 3: expected VowelSignAa, got VowelSignI\n\
+             \n\
+             wrong: 1 checks, 1 failures\n"
+        );
+    }
+
+    #[test]
+    fn test_cli_compare() {
+        let output = Command::new(get_cli_binary())
+            .args(["compare", "--script", "devanagari", "सीता राम", "सिता राम"])
+            .output()
+            .expect("Failed to execute CLI");
+
+        // Differing texts exit like diff, and the report is uncolored off a terminal
+        assert_eq!(output.status.code(), Some(1));
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(
+            stdout,
+            "position 3: expected VowelSignIi, got VowelSignI\n\
+             \n\
+             expected: स[ी]ता राम\n\
+             actual:   स[ि]ता राम\n\
+             \n\
+             1 difference\n"
+        );
+
+        let output = Command::new(get_cli_binary())
+            .args([
+                "compare",
+                "-s",
+                "bengali",
+                "\u{09AC}\u{09CB}",
+                "\u{09AC}\u{09C7}\u{09BE}",
+            ])
+            .output()
+            .expect("Failed to execute CLI");
+        assert!(output.status.success());
+
+        let output = Command::new(get_cli_binary())
+            .args(["compare", "-s", "klingon", "a", "a"])
+            .output()
+            .expect("Failed to execute CLI");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("Unknown script: klingon"));
+    }
+
+    /// The bash completion script, checked against tests/snapshots/shlesha.bash
+    ///
+    /// After a change to the CLI, regenerate the snapshot with
+    /// `SHLESHA_UPDATE_GOLDEN=1 cargo test --test cli_integration_tests` and review the diff.
+    /// The snapshot is of the default build, without the opt-in `repl` and `bench` subcommands.
+    #[cfg(not(any(feature = "repl", feature = "bench")))]
+    #[test]
+    fn test_cli_bash_completions_snapshot() {
+        let output = Command::new(get_cli_binary())
+            .args(["completions", "bash"])
+            .output()
+            .expect("Failed to execute CLI");
+
+        assert!(output.status.success());
+        let script = String::from_utf8(output.stdout).unwrap();
+        // Script names come from the hook as bash completes
+        assert!(script.contains(
+            "COMPREPLY=($(compgen -W \"$(shlesha __complete-scripts 2>/dev/null)\" -- \"${cur}\"))"
+        ));
+
+        let snapshot =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/shlesha.bash");
+        if std::env::var_os("SHLESHA_UPDATE_GOLDEN").is_some() {
+            std::fs::write(&snapshot, &script).unwrap();
+        }
+        assert_eq!(
+            script,
+            std::fs::read_to_string(&snapshot).unwrap(),
+            "bash completions changed; see this test's doc comment"
+        );
+    }
+
+    #[test]
+    fn test_cli_complete_scripts_hook() {
+        let output = Command::new(get_cli_binary())
+            .arg("__complete-scripts")
+            .output()
+            .expect("Failed to execute CLI");
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let scripts: Vec<&str> = stdout.lines().collect();
+        assert!(scripts.contains(&"devanagari"), "{stdout}");
+        assert!(scripts.contains(&"iast"), "{stdout}");
+    }
+
+    #[test]
+    fn test_cli_markdown_help() {
+        let output = Command::new(get_cli_binary())
+            .arg("--markdown-help")
+            .output()
+            .expect("Failed to execute CLI");
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.starts_with("# Command-Line Help for `shlesha`"));
+        assert!(stdout.contains("## `shlesha transliterate`"));
+        assert!(!stdout.contains("__complete-scripts"));
+    }
 }
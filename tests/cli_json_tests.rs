@@ -0,0 +1,113 @@
+//! `--format json` output of the CLI, parsed as other programs would
+#![cfg(feature = "cli")]
+
+use assert_cmd::Command;
+use serde_json::Value;
+
+fn shlesha() -> Command {
+    Command::cargo_bin("shlesha").unwrap()
+}
+
+fn stdout_json(command: &mut Command) -> Value {
+    let output = command.assert().success().get_output().stdout.clone();
+    serde_json::from_slice(&output).unwrap()
+}
+
+#[test]
+fn test_transliterate_json() {
+    let json = stdout_json(shlesha().args([
+        "transliterate",
+        "--from",
+        "devanagari",
+        "--to",
+        "iast",
+        "--format",
+        "json",
+        "धर्मk",
+    ]));
+
+    assert_eq!(json["output"], "dharmak");
+    assert_eq!(json["from"], "devanagari");
+    assert_eq!(json["to"], "iast");
+    assert_eq!(
+        json["unknown_tokens"],
+        serde_json::json!([{
+            "token": "k",
+            "position": 12,
+            "unicode": "U+006B",
+            "stage": "source_parsing",
+            "script": "devanagari",
+            "hub_token": null,
+        }])
+    );
+    assert_eq!(json["stats"]["input_bytes"], 13);
+    assert_eq!(json["stats"]["output_bytes"], 7);
+    assert_eq!(json["stats"]["unknown_count"], 1);
+}
+
+#[test]
+fn test_transliterate_json_reads_stdin_and_reports_lossy_mappings() {
+    let json = stdout_json(
+        shlesha()
+            .args([
+                "transliterate",
+                "-f",
+                "iast",
+                "-t",
+                "gurmukhi",
+                "--format",
+                "json",
+            ])
+            .write_stdin("kṛpā\n"),
+    );
+
+    assert_eq!(json["output"], "ਕ੍ਰਿਪਾ");
+    assert_eq!(json["unknown_tokens"], serde_json::json!([]));
+    assert_eq!(json["lossy_mappings"][0]["token"], "VowelSignR");
+    assert_eq!(json["stats"]["lossy_count"], 1);
+}
+
+#[test]
+fn test_json_errors_go_to_stderr() {
+    let output = shlesha()
+        .args([
+            "transliterate",
+            "--from",
+            "no_such_script",
+            "--to",
+            "iast",
+            "--format",
+            "json",
+            "a",
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .get_output()
+        .clone();
+
+    assert!(output.stdout.is_empty());
+    let error: Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert!(error["error"].as_str().unwrap().contains("no_such_script"));
+}
+
+#[test]
+fn test_scripts_json() {
+    let json = stdout_json(shlesha().args(["scripts", "--format", "json"]));
+    let scripts = json.as_array().unwrap();
+
+    let devanagari = scripts
+        .iter()
+        .find(|script| script["name"] == "devanagari")
+        .unwrap();
+    assert_eq!(devanagari["aliases"], serde_json::json!(["deva"]));
+    assert_eq!(devanagari["description"], "Devanagari script (देवनागरी)");
+
+    // Every script is listed once, aliases included
+    let names: Vec<_> = scripts
+        .iter()
+        .map(|script| script["name"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"iast"));
+    assert!(!names.contains(&"deva"));
+}
@@ -0,0 +1,112 @@
+//! `shlesha repl` driven through piped stdin
+#![cfg(feature = "repl")]
+
+use assert_cmd::Command;
+
+fn repl(from: &str, to: &str, input: &str) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("shlesha")
+        .unwrap()
+        .args(["repl", "--from", from, "--to", to])
+        .write_stdin(input)
+        .assert()
+}
+
+fn stdout_of(assert: assert_cmd::assert::Assert) -> String {
+    String::from_utf8(assert.success().get_output().stdout.clone()).unwrap()
+}
+
+fn stderr_of(assert: assert_cmd::assert::Assert) -> String {
+    String::from_utf8(assert.get_output().stderr.clone()).unwrap()
+}
+
+#[test]
+fn test_lines_are_converted() {
+    let stdout = stdout_of(repl("iast", "devanagari", "dharma\n\nrāma\n"));
+    assert_eq!(stdout, "धर्म\n\nराम\n");
+}
+
+#[test]
+fn test_scripts_change_within_the_session() {
+    let stdout = stdout_of(repl(
+        "iast",
+        "devanagari",
+        "dharma\n:to telugu\ndharma\n:from slp1\nrAma\n",
+    ));
+    assert_eq!(
+        stdout,
+        "धर्म\nConverting iast → telugu\nధర్మ\nConverting slp1 → telugu\nరామ\n"
+    );
+}
+
+#[test]
+fn test_errors_do_not_end_the_session() {
+    let assert = repl(
+        "iast",
+        "devanagari",
+        ":to klingon\n:bogus\n:trace maybe\nka\n",
+    )
+    .success()
+    .stdout("क\n");
+    let stderr = stderr_of(assert);
+    assert!(stderr.contains("unsupported script: klingon"), "{stderr}");
+    assert!(stderr.contains("unknown command :bogus"), "{stderr}");
+    assert!(stderr.contains(":trace takes on or off"), "{stderr}");
+}
+
+#[test]
+fn test_quit_stops_reading() {
+    let stdout = stdout_of(repl("iast", "devanagari", "ka\n:quit\nkha\n"));
+    assert_eq!(stdout, "क\n");
+}
+
+#[test]
+fn test_trace_and_meta() {
+    let stdout = stdout_of(repl(
+        "iast",
+        "devanagari",
+        ":trace on\nka\n:trace off\n:meta on\nka☺\n",
+    ));
+    assert!(
+        stdout.contains("  read: ConsonantK VowelA\nक\n"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("क☺\n  unknown: '☺' (U+263A) at byte 2 (source parsing)\n"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_load_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("mini.yaml");
+    std::fs::write(
+        &path,
+        r#"
+metadata:
+  name: "mini"
+  script_type: "roman"
+  has_implicit_a: false
+target: "alphabet_tokens"
+mappings:
+  vowels:
+    VowelA: "a"
+  consonants:
+    ConsonantK: "q"
+"#,
+    )
+    .unwrap();
+
+    let input = format!(":load {}\n:from mini\nqa\n", path.display());
+    let stdout = stdout_of(repl("iast", "devanagari", &input));
+    assert!(
+        stdout.ends_with("Converting mini → devanagari\nक\n"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_unsupported_starting_script_fails() {
+    let stderr = stderr_of(repl("klingon", "devanagari", "").failure());
+    assert!(stderr.contains("unsupported script: klingon"), "{stderr}");
+}
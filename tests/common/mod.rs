@@ -0,0 +1,241 @@
+//! Helpers shared by the integration tests
+
+use std::collections::HashMap;
+
+/// Generate complete character sets for different script types
+#[allow(dead_code)]
+pub fn generate_script_character_sets() -> HashMap<String, Vec<String>> {
+    let mut character_sets = HashMap::new();
+
+    // IAST character set
+    character_sets.insert(
+        "iast".to_string(),
+        vec![
+            // Vowels
+            "a", "ā", "i", "ī", "u", "ū", "ṛ", "ṝ", "ḷ", "ḹ", "e", "ai", "o", "au",
+            // Consonants
+            "k", "kh", "g", "gh", "ṅ", "c", "ch", "j", "jh", "ñ", "ṭ", "ṭh", "ḍ", "ḍh", "ṇ", "t",
+            "th", "d", "dh", "n", "p", "ph", "b", "bh", "m", "y", "r", "l", "v", "ś", "ṣ", "s",
+            "h", // Marks
+            "ṃ", "ḥ", "m̐", // Special combinations
+            "kṣ", "jñ", // Digits
+            "0", "1", "2", "3", "4", "5", "6", "7", "8", "9",
+        ]
+        .into_iter()
+        .map(|s| s.to_string())
+        .collect(),
+    );
+
+    // SLP1 character set
+    character_sets.insert(
+        "slp1".to_string(),
+        vec![
+            // Vowels
+            "a", "A", "i", "I", "u", "U", "f", "F", "x", "X", "e", "E", "o", "O",
+            // Consonants
+            "k", "K", "g", "G", "N", "c", "C", "j", "J", "Y", "w", "W", "q", "Q", "R", "t", "T",
+            "d", "D", "n", "p", "P", "b", "B", "m", "y", "r", "l", "v", "S", "z", "s", "h",
+            // Marks
+            "M", "H", // Special combinations
+            "kz", "jY", // Digits
+            "0", "1", "2", "3", "4", "5", "6", "7", "8", "9",
+        ]
+        .into_iter()
+        .map(|s| s.to_string())
+        .collect(),
+    );
+
+    // Harvard-Kyoto character set
+    character_sets.insert(
+        "harvard_kyoto".to_string(),
+        vec![
+            // Vowels
+            "a", "A", "i", "I", "u", "U", "R", "RR", "lR", "lRR", "e", "ai", "o", "au",
+            // Consonants
+            "k", "kh", "g", "gh", "G", "c", "ch", "j", "jh", "J", "T", "Th", "D", "Dh", "N", "t",
+            "th", "d", "dh", "n", "p", "ph", "b", "bh", "m", "y", "r", "l", "v", "z", "S", "s",
+            "h", // Marks
+            "M", "H", // Special combinations
+            "kS", "jJ", // Digits
+            "0", "1", "2", "3", "4", "5", "6", "7", "8", "9",
+        ]
+        .into_iter()
+        .map(|s| s.to_string())
+        .collect(),
+    );
+
+    // Devanagari character set
+    character_sets.insert(
+        "devanagari".to_string(),
+        vec![
+            // Vowels
+            "अ",
+            "आ",
+            "इ",
+            "ई",
+            "उ",
+            "ऊ",
+            "ऋ",
+            "ॠ",
+            "ऌ",
+            "ॡ",
+            "ए",
+            "ऐ",
+            "ओ",
+            "औ",
+            // Consonants
+            "क",
+            "ख",
+            "ग",
+            "घ",
+            "ङ",
+            "च",
+            "छ",
+            "ज",
+            "झ",
+            "ञ",
+            "ट",
+            "ठ",
+            "ड",
+            "ढ",
+            "ण",
+            "त",
+            "थ",
+            "द",
+            "ध",
+            "न",
+            "प",
+            "फ",
+            "ब",
+            "भ",
+            "म",
+            "य",
+            "र",
+            "ल",
+            "व",
+            "श",
+            "ष",
+            "स",
+            "ह",
+            // Vowel signs
+            "ा",
+            "ि",
+            "ी",
+            "ु",
+            "ू",
+            "ृ",
+            "ॄ",
+            "ॢ",
+            "ॣ",
+            "े",
+            "ै",
+            "ो",
+            "ौ",
+            // Marks
+            "ं",
+            "ः",
+            "ँ",
+            "्",
+            // Special combinations
+            "क्ष",
+            "ज्ञ",
+            // Digits
+            "०",
+            "१",
+            "२",
+            "३",
+            "४",
+            "५",
+            "६",
+            "७",
+            "८",
+            "९",
+        ]
+        .into_iter()
+        .map(|s| s.to_string())
+        .collect(),
+    );
+
+    // Default Roman charset for other scripts
+    let default_roman_charset: Vec<String> = vec![
+        "a", "i", "u", "e", "o", "k", "g", "c", "j", "t", "d", "p", "b", "m", "n", "r", "l", "v",
+        "s", "h",
+    ]
+    .into_iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    // Add other Roman scripts with default charset
+    for script in ["iso15919", "itrans", "velthuis", "wx", "kolkata"] {
+        character_sets.insert(script.to_string(), default_roman_charset.clone());
+    }
+
+    // Default Indic charset for other scripts
+    let default_indic_charset: Vec<String> = vec![
+        "क", "ग", "च", "ज", "त", "द", "प", "ब", "म", "न", "र", "ल", "व", "स", "ह",
+    ]
+    .into_iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    // Add other Indic scripts with default charset
+    for script in [
+        "bengali",
+        "gujarati",
+        "gurmukhi",
+        "kannada",
+        "malayalam",
+        "odia",
+        "tamil",
+        "telugu",
+        "grantha",
+        "sinhala",
+    ] {
+        character_sets.insert(script.to_string(), default_indic_charset.clone());
+    }
+
+    character_sets
+}
+
+/// Every consonant with every vowel sign, one line per consonant, plus a line
+/// of the independent vowels
+#[allow(dead_code)]
+pub fn syllabary(consonants: &[&str], signs: &[&str], vowels: &[&str]) -> Vec<String> {
+    let mut words: Vec<String> = consonants
+        .iter()
+        .map(|c| {
+            signs
+                .iter()
+                .map(|s| format!("{c}{s}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    words.push(vowels.join(" "));
+    words
+}
+
+/// The runtime ascii_roman schema, aliased `ar`, writing ā as `long_a`
+#[allow(dead_code)]
+pub fn ascii_roman_schema(long_a: &str) -> String {
+    format!(
+        r#"
+metadata:
+  name: "ascii_roman"
+  script_type: "roman"
+  has_implicit_a: false
+  aliases: ["ar"]
+target: "alphabet_tokens"
+mappings:
+  vowels:
+    VowelA: "a"
+    VowelAa: "{long_a}"
+    VowelI: "i"
+  consonants:
+    ConsonantK: "k"
+    ConsonantDdh: "dh"
+    ConsonantR: "r"
+    ConsonantM: "m"
+"#
+    )
+}
@@ -0,0 +1,80 @@
+//! Token-level comparison of two texts in the same script
+
+use shlesha::modules::hub::{AbugidaToken, HubToken};
+use shlesha::{compare, CompareError, DiffOp};
+
+#[test]
+fn test_differences_are_aligned_by_token() {
+    // A single matra
+    let diff = compare("सीता राम", "सिता राम", "devanagari").unwrap();
+    assert!(!diff.is_equal());
+    let differences: Vec<_> = diff.differences().collect();
+    assert_eq!(differences.len(), 1);
+    let edit = differences[0];
+    assert_eq!(edit.op, DiffOp::Replace);
+    assert_eq!(
+        edit.expected,
+        [HubToken::Abugida(AbugidaToken::VowelSignIi)]
+    );
+    assert_eq!(edit.actual, [HubToken::Abugida(AbugidaToken::VowelSignI)]);
+    assert_eq!(
+        (edit.expected_span.clone(), edit.actual_span.clone()),
+        (3..6, 3..6)
+    );
+    assert_eq!(
+        edit.to_string(),
+        "position 3: expected VowelSignIi, got VowelSignI"
+    );
+    assert_eq!(
+        diff.edits.iter().map(|edit| edit.op).collect::<Vec<_>>(),
+        [DiffOp::Equal, DiffOp::Replace, DiffOp::Equal]
+    );
+
+    // A missing virama
+    let diff = compare("धर्म", "धरम", "deva").unwrap();
+    let differences: Vec<_> = diff.differences().collect();
+    assert_eq!(differences.len(), 1);
+    assert_eq!(differences[0].op, DiffOp::Delete);
+    assert_eq!(differences[0].expected_span, 6..9);
+    assert_eq!(differences[0].actual_span, 6..6);
+    assert_eq!(
+        differences[0].to_string(),
+        "position 6: expected MarkVirama, got nothing"
+    );
+
+    // An extra letter, and text the script does not read
+    let diff = compare("dharma", "dharmma!", "iast").unwrap();
+    let differences: Vec<_> = diff.differences().map(ToString::to_string).collect();
+    assert_eq!(
+        differences,
+        [
+            "position 5: expected nothing, got ConsonantM",
+            "position 6: expected nothing, got \"!\""
+        ]
+    );
+}
+
+#[test]
+fn test_spellings_read_alike_compare_equal() {
+    // Precomposed and decomposed nukta letters
+    let diff = compare("\u{0958}िला", "\u{0915}\u{093C}िला", "devanagari").unwrap();
+    assert!(diff.is_equal(), "{diff:?}");
+    assert_eq!(diff.edits.len(), 1);
+    assert_eq!(diff.edits[0].expected_span, 0.."\u{0958}िला".len());
+    assert_eq!(diff.edits[0].actual_span, 0.."\u{0915}\u{093C}िला".len());
+
+    // Precomposed and decomposed two-part vowel signs
+    assert!(compare(
+        "\u{09AC}\u{09CB}\u{09A8}",
+        "\u{09AC}\u{09C7}\u{09BE}\u{09A8}",
+        "bengali"
+    )
+    .unwrap()
+    .is_equal());
+
+    assert!(compare("", "", "iast").unwrap().is_equal());
+    assert_eq!(
+        compare("a", "a", "klingon"),
+        Err(CompareError::UnknownScript("klingon".to_string()))
+    );
+}
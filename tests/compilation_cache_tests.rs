@@ -0,0 +1,146 @@
+//! The runtime compilation cache, driven through a stand-in for cargo that
+//! records each build it is asked to run
+#![cfg(unix)]
+
+use shlesha::modules::runtime::{CacheManager, CompilationStats, RuntimeCompiler};
+use shlesha::modules::schema::{Schema, SchemaBuilder};
+use shlesha::Shlesha;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Write a fake cargo that logs its invocation and produces an empty library
+fn fake_cargo(dir: &Path) -> PathBuf {
+    let cargo = dir.join("fake-cargo");
+    let log = dir.join("builds.log");
+    fs::write(
+        &cargo,
+        format!(
+            "#!/bin/sh\necho build >> '{}'\nmkdir -p target/release\ntouch target/release/libfake.so target/release/libfake.dylib\n",
+            log.display()
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&cargo, fs::Permissions::from_mode(0o755)).unwrap();
+    cargo
+}
+
+fn builds(dir: &Path) -> usize {
+    fs::read_to_string(dir.join("builds.log"))
+        .map(|log| log.lines().count())
+        .unwrap_or(0)
+}
+
+fn shlesha_with_cache(dir: &Path) -> Shlesha {
+    let compiler = RuntimeCompiler::with_cache_dir(dir.join("cache"))
+        .unwrap()
+        .with_cargo(fake_cargo(dir));
+    let mut shlesha = Shlesha::new();
+    shlesha.set_runtime_compiler(compiler);
+    shlesha
+}
+
+fn sample_schema(ka: &str) -> Schema {
+    SchemaBuilder::new("cached_script")
+        .script_type("roman")
+        .add_vowel_mapping("a", &["a"])
+        .add_consonant_mapping("ka", &[ka])
+        .build()
+}
+
+#[test]
+fn test_second_instance_hits_the_cache() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut first = shlesha_with_cache(dir.path());
+    first.add_runtime_schema(sample_schema("k")).unwrap();
+    assert_eq!(
+        first.compilation_stats(),
+        Some(CompilationStats {
+            cache_hits: 0,
+            compilations: 1
+        })
+    );
+    drop(first);
+
+    let mut second = shlesha_with_cache(dir.path());
+    second.add_runtime_schema(sample_schema("k")).unwrap();
+    assert_eq!(
+        second.compilation_stats(),
+        Some(CompilationStats {
+            cache_hits: 1,
+            compilations: 0
+        })
+    );
+    assert_eq!(builds(dir.path()), 1);
+    assert!(second.supports_script("cached_script"));
+}
+
+#[test]
+fn test_changed_schema_replaces_its_cache_entry() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut shlesha = shlesha_with_cache(dir.path());
+    shlesha.add_runtime_schema(sample_schema("k")).unwrap();
+    shlesha.add_runtime_schema(sample_schema("q")).unwrap();
+    assert_eq!(shlesha.compilation_stats().unwrap().compilations, 2);
+    drop(shlesha);
+
+    // The edited schema left only its own entry behind
+    let mut shlesha = shlesha_with_cache(dir.path());
+    shlesha.add_runtime_schema(sample_schema("q")).unwrap();
+    shlesha.add_runtime_schema(sample_schema("k")).unwrap();
+    assert_eq!(
+        shlesha.compilation_stats(),
+        Some(CompilationStats {
+            cache_hits: 1,
+            compilations: 1
+        })
+    );
+}
+
+#[test]
+fn test_cache_key_ignores_mapping_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = CacheManager::with_cache_dir(dir.path()).unwrap();
+
+    let forward = SchemaBuilder::new("ordered")
+        .add_consonant_mapping("ka", &["k"])
+        .add_consonant_mapping("ga", &["g"])
+        .add_vowel_mapping("a", &["a"])
+        .build();
+    let backward = SchemaBuilder::new("ordered")
+        .add_vowel_mapping("a", &["a"])
+        .add_consonant_mapping("ga", &["g"])
+        .add_consonant_mapping("ka", &["k"])
+        .build();
+    assert_eq!(
+        cache.generate_cache_key(&forward),
+        cache.generate_cache_key(&backward)
+    );
+    assert_ne!(
+        cache.generate_cache_key(&forward),
+        cache.generate_cache_key(&sample_schema("k"))
+    );
+}
+
+#[test]
+fn test_precompile_and_clear() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut warmup = shlesha_with_cache(dir.path());
+    warmup.precompile_schemas(&[sample_schema("k")]).unwrap();
+    assert!(!warmup.supports_script("cached_script"));
+    drop(warmup);
+
+    let mut shlesha = shlesha_with_cache(dir.path());
+    shlesha.add_runtime_schema(sample_schema("k")).unwrap();
+    assert_eq!(shlesha.compilation_stats().unwrap().cache_hits, 1);
+    shlesha.clear_compilation_cache().unwrap();
+    drop(shlesha);
+
+    let mut shlesha = shlesha_with_cache(dir.path());
+    shlesha.add_runtime_schema(sample_schema("k")).unwrap();
+    assert_eq!(shlesha.compilation_stats().unwrap().cache_hits, 0);
+    assert_eq!(builds(dir.path()), 2);
+}
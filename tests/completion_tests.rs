@@ -0,0 +1,92 @@
+//! Prefixes and next characters for editors and spell-checkers
+
+mod common;
+
+use common::ascii_roman_schema;
+use shlesha::Shlesha;
+
+fn has(continuations: &[String], next: &str) -> bool {
+    continuations.iter().any(|c| c == next)
+}
+
+#[test]
+fn test_iast_continuations() {
+    let shlesha = Shlesha::new();
+
+    // kh and the conjunct kṣ extend k; digits, the apostrophe and other
+    // letters would start patterns of their own
+    let next = shlesha.next_valid_continuations("k", "iast");
+    assert!(has(&next, "h") && has(&next, "ṣ"), "{next:?}");
+    assert_eq!(next, ["h", "ṣ"]);
+    assert_eq!(shlesha.next_valid_continuations("j", "iast"), ["h", "ñ"]);
+    assert!(shlesha.is_valid_prefix("k", "iast"));
+    assert!(shlesha.is_valid_prefix("kṣetra", "iast"));
+
+    // Only "ai" and "au" continue an "a" with i or u as one letter
+    assert_eq!(shlesha.next_valid_continuations("a", "iast"), ["i", "u"]);
+
+    // Every letter starts a word, distinct and in order
+    let next = shlesha.next_valid_continuations("", "iast");
+    assert!(has(&next, "k") && has(&next, "ṣ"), "{next:?}");
+    let mut sorted = next.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(next, sorted);
+}
+
+#[test]
+fn test_slp1_continuations() {
+    let shlesha = Shlesha::new();
+
+    // Each SLP1 letter is one character, so only the conjunct kz extends k
+    let next = shlesha.next_valid_continuations("k", "slp1");
+    assert!(has(&next, "z"), "{next:?}");
+    assert_eq!(next, ["z"]);
+    assert!(shlesha.is_valid_prefix("kz", "slp1"));
+}
+
+#[test]
+fn test_invalid_prefixes() {
+    let shlesha = Shlesha::new();
+
+    for (prefix, script) in [("kx", "iast"), ("k ", "iast"), ("kṣ", "slp1")] {
+        assert!(
+            !shlesha.is_valid_prefix(prefix, script),
+            "{prefix} {script}"
+        );
+        assert!(
+            shlesha.next_valid_continuations(prefix, script).is_empty(),
+            "{prefix} {script}"
+        );
+    }
+    assert!(!shlesha.is_valid_prefix("k", "no_such_script"));
+    assert!(shlesha
+        .next_valid_continuations("k", "no_such_script")
+        .is_empty());
+}
+
+#[test]
+fn test_partial_multi_character_pattern() {
+    let shlesha = Shlesha::new();
+
+    // A dot alone is no Velthuis letter, but starts .r, .s and the others
+    assert!(shlesha.is_valid_prefix(".", "velthuis"));
+    let next = shlesha.next_valid_continuations(".", "velthuis");
+    assert!(has(&next, "r") && has(&next, "s"), "{next:?}");
+}
+
+#[test]
+fn test_runtime_schema_patterns() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(&ascii_roman_schema("aa"), "ascii_roman")
+        .unwrap();
+
+    assert_eq!(
+        shlesha.pattern_inventory("ascii_roman").unwrap(),
+        ["a", "aa", "dh", "i", "k", "m", "r"]
+    );
+    assert_eq!(shlesha.next_valid_continuations("d", "ascii_roman"), ["h"]);
+    assert_eq!(shlesha.next_valid_continuations("ka", "ascii_roman"), ["a"]);
+    assert!(!shlesha.is_valid_prefix("x", "ascii_roman"));
+}
@@ -1,4 +1,5 @@
 use quickcheck::{quickcheck, TestResult};
+use shlesha::modules::hub::is_attached_mark;
 use shlesha::Shlesha;
 
 /// Comprehensive test string covering all major Devanagari features
@@ -247,7 +248,9 @@ fn normalize_for_comparison(text: &str) -> String {
 
 /// Property-based test: any valid Devanagari text should roundtrip
 fn prop_devanagari_roundtrip(text: String) -> TestResult {
-    let shlesha = Shlesha::new();
+    let mut shlesha = Shlesha::new();
+    // Random text has stray vowel signs that input repair would drop
+    shlesha.set_repair_input(false);
     let (indic_scripts, _) = categorize_scripts();
 
     // Filter to valid Devanagari range
@@ -256,8 +259,16 @@ fn prop_devanagari_roundtrip(text: String) -> TestResult {
         .filter(|c| {
             let code = *c as u32;
             // Devanagari block (U+0900-U+097F) + Extended (U+A8E0-U+A8FF)
-            (code >= 0x0900 && code <= 0x097F) || (code >= 0xA8E0 && code <= 0xA8FF)
+            (0x0900..=0x097F).contains(&code) || (0xA8E0..=0xA8FF).contains(&code)
         })
+        // Short e/o (ऎ ऒ ॆ ॊ) are read but written back as ए ओ े ो - a documented lossy mapping
+        .filter(|c| !['\u{090E}', '\u{0912}', '\u{0946}', '\u{094A}'].contains(c))
+        // The prishthamatra ॎ is read as part of e, ai, o or au and written in the modern form
+        .filter(|&c| c != '\u{094E}')
+        // Candra a ॲ is read as candra e and written back as ऍ
+        .filter(|&c| c != '\u{0972}')
+        // Cantillation marks are moved after the vowel sign, virama and marks of their akshara
+        .filter(|&c| !is_attached_mark(c))
         .take(50) // Limit length for test performance
         .collect();
 
@@ -279,7 +290,11 @@ fn prop_devanagari_roundtrip(text: String) -> TestResult {
                 Err(_) => return TestResult::failed(),
             };
 
-            let original_normalized = normalize_for_comparison(&devanagari_text);
+            let mut original_normalized = normalize_for_comparison(&devanagari_text);
+            // Bengali writes va as ba - a documented lossy mapping
+            if *target_script == "bengali" {
+                original_normalized = original_normalized.replace('\u{0935}', "\u{092C}");
+            }
             let roundtrip_normalized = normalize_for_comparison(&roundtrip);
 
             if original_normalized != roundtrip_normalized {
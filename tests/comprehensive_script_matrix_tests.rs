@@ -54,6 +54,7 @@ const INDIC_SCRIPTS: &[&str] = &[
 struct TestCase {
     text: &'static str,
     script: &'static str,
+    #[allow(dead_code)]
     description: &'static str,
 }
 
@@ -302,7 +303,7 @@ fn test_roman_to_roman_conversions() {
 
     // Print conversion matrix
     println!("\n📊 Roman-to-Roman Conversion Matrix:");
-    println!("{:<15} {}", "Source→Target", "Success/Total");
+    println!("{:<15} Success/Total", "Source→Target");
     println!("{}", "-".repeat(40));
 
     for &source in &available_roman {
@@ -413,7 +414,7 @@ fn test_indic_to_indic_conversions() {
 
     // Print conversion matrix
     println!("\n📊 Indic-to-Indic Conversion Matrix:");
-    println!("{:<20} {}", "Source→Target", "Success/Total");
+    println!("{:<20} Success/Total", "Source→Target");
     println!("{}", "-".repeat(45));
 
     for &source in &available_indic {
@@ -521,7 +522,7 @@ fn test_roman_to_indic_conversions() {
 
     // Print conversion matrix
     println!("\n📊 Roman-to-Indic Conversion Matrix:");
-    println!("{:<20} {}", "Source→Target", "Success/Total");
+    println!("{:<20} Success/Total", "Source→Target");
     println!("{}", "-".repeat(45));
 
     for &roman in &available_roman {
@@ -627,7 +628,7 @@ fn test_indic_to_roman_conversions() {
 
     // Print conversion matrix
     println!("\n📊 Indic-to-Roman Conversion Matrix:");
-    println!("{:<20} {}", "Source→Target", "Success/Total");
+    println!("{:<20} Success/Total", "Source→Target");
     println!("{}", "-".repeat(45));
 
     for &indic in &available_indic {
@@ -0,0 +1,111 @@
+use shlesha::modules::conformance::{load_case_file, run_directory};
+use shlesha::Shlesha;
+use std::fs;
+use std::path::Path;
+
+/// Every case file in tests/data must pass
+#[test]
+fn test_conformance_data() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+    let report = run_directory(&Shlesha::new(), &dir).unwrap();
+
+    assert!(report.files > 0, "no case files found in {}", dir.display());
+    if !report.is_success() {
+        let failures: Vec<String> = report.failures.iter().map(|f| f.to_string()).collect();
+        panic!("{}\n\n{}", report.summary(), failures.join("\n\n"));
+    }
+}
+
+#[test]
+fn test_unknown_script_is_a_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("typo.yaml"),
+        "from: devanagri\nto: iast\ncases:\n  - input: \"अ\"\n    expected: \"a\"\n",
+    )
+    .unwrap();
+
+    let report = run_directory(&Shlesha::new(), dir.path()).unwrap();
+    assert_eq!(report.failures.len(), 1);
+    let failure = &report.failures[0];
+    assert_eq!(failure.case_index, None);
+    assert!(failure.reason.contains("unknown script 'devanagri'"));
+}
+
+#[test]
+fn test_failures_report_file_case_and_difference() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wrong.yaml");
+    fs::write(
+        &path,
+        "from: devanagari\nto: iso15919\ncases:\n  - input: \"धर्म\"\n    expected: \"dharma\"\n  - input: \"कर्म\"\n    expected: \"kārma\"\n",
+    )
+    .unwrap();
+
+    let report = run_directory(&Shlesha::new(), dir.path()).unwrap();
+    assert_eq!(report.cases, 2);
+    assert_eq!(report.failures.len(), 1);
+
+    let failure = &report.failures[0];
+    assert_eq!(failure.case_index, Some(1));
+    assert_eq!(failure.actual, "karma");
+
+    let message = failure.to_string();
+    assert!(message.contains("wrong.yaml case 1 (devanagari → iso15919)"));
+    assert!(message.contains("first difference at char 1: expected 'ā' (U+0101), got 'a' (U+0061)"));
+}
+
+#[test]
+fn test_roundtrip_and_lossy_options() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("options.yaml"),
+        r#"from: telugu
+to: devanagari
+cases:
+  - input: "కెరళ"
+    expected: "केरळ"
+    options:
+      roundtrip: true
+  - input: "ఏకమ్"
+    expected: "एकम्"
+    options:
+      lossy: true
+"#,
+    )
+    .unwrap();
+
+    let report = run_directory(&Shlesha::new(), dir.path()).unwrap();
+    assert_eq!(report.failures.len(), 2);
+    assert!(report.failures[0].reason.contains("roundtrip"));
+    assert_eq!(report.failures[0].actual, "కేరళ");
+    assert!(report.failures[1].reason.contains("expected lossy = true"));
+}
+
+#[test]
+fn test_toml_case_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cases.toml");
+    fs::write(
+        &path,
+        "from = \"iast\"\nto = \"devanagari\"\n\n[[cases]]\ninput = \"dharma\"\nexpected = \"धर्म\"\noptions = { roundtrip = true }\n",
+    )
+    .unwrap();
+
+    let file = load_case_file(&path).unwrap();
+    assert_eq!(file.from, "iast");
+    assert_eq!(file.cases.len(), 1);
+    assert!(file.cases[0].options.roundtrip);
+
+    let report = run_directory(&Shlesha::new(), dir.path()).unwrap();
+    assert!(report.is_success(), "{:?}", report.failures);
+}
+
+#[test]
+fn test_malformed_case_file_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("broken.yaml"), "from: iast\ncases: 3\n").unwrap();
+
+    let err = run_directory(&Shlesha::new(), dir.path()).unwrap_err();
+    assert!(err.to_string().contains("broken.yaml"));
+}
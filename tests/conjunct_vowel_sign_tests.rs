@@ -0,0 +1,73 @@
+//! Conjuncts with ya and ra as their last member, followed by a vowel sign
+//!
+//! The vowel sign comes after the whole conjunct, so a ya-phala or ra-phala
+//! ends up between the consonant and the sign: খ্যা is খ + ্ + য + া.
+
+use shlesha::Shlesha;
+
+/// IAST words and their Devanagari, Bengali, Odia and Gujarati spellings
+const WORDS: &[(&str, [&str; 4])] = &[
+    ("vyā", ["व्या", "ব্যা", "ଵ୍ଯା", "વ્યા"]),
+    ("khyā", ["ख्या", "খ্যা", "ଖ୍ଯା", "ખ્યા"]),
+    ("krā", ["क्रा", "ক্রা", "କ୍ରା", "ક્રા"]),
+    ("ṣṭrī", ["ष्ट्री", "ষ্ট্রী", "ଷ୍ଟ୍ରୀ", "ષ્ટ્રી"]),
+    ("krama", ["क्रम", "ক্রম", "କ୍ରମ", "ક્રમ"]),
+    ("vyākhyā", ["व्याख्या", "ব্যাখ্যা", "ଵ୍ଯାଖ୍ଯା", "વ્યાખ્યા"]),
+];
+
+const SCRIPTS: [&str; 4] = ["devanagari", "bengali", "odia", "gujarati"];
+
+#[test]
+fn test_vowel_signs_follow_the_conjunct() {
+    let shlesha = Shlesha::new();
+    for (iast, spellings) in WORDS {
+        for (script, spelling) in SCRIPTS.iter().zip(spellings) {
+            assert_eq!(
+                shlesha.transliterate(iast, "iast", script).unwrap(),
+                *spelling,
+                "{iast} in {script}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_conjuncts_round_trip_through_devanagari() {
+    let shlesha = Shlesha::new();
+    for (_, spellings) in WORDS {
+        let devanagari = spellings[0];
+        for script in &SCRIPTS[1..] {
+            let written = shlesha
+                .transliterate(devanagari, "devanagari", script)
+                .unwrap();
+            let read = shlesha
+                .transliterate(&written, script, "devanagari")
+                .unwrap();
+            // Bengali writes va as ba, and reads it back as ba
+            let expected = if *script == "bengali" {
+                devanagari.replace('व', "ब")
+            } else {
+                devanagari.to_string()
+            };
+            assert_eq!(read, expected, "{devanagari} through {script}");
+        }
+    }
+}
+
+#[test]
+fn test_bengali_va_is_a_reported_merge_with_ba() {
+    let shlesha = Shlesha::new();
+    let result = shlesha
+        .transliterate_with_metadata("vyākhyā", "iast", "bengali")
+        .unwrap();
+    assert_eq!(result.output, "ব্যাখ্যা");
+
+    let metadata = result.metadata.unwrap();
+    assert!(metadata.unknown_tokens.is_empty());
+    let lossy: Vec<_> = metadata
+        .lossy_mappings
+        .iter()
+        .map(|mapping| (mapping.token.as_str(), mapping.substitute.as_str()))
+        .collect();
+    assert_eq!(lossy, [("ConsonantV", "ConsonantB")]);
+}
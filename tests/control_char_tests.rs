@@ -0,0 +1,139 @@
+//! Control and format characters pass through every conversion in place
+
+use shlesha::{Shlesha, SpanKind};
+
+/// `text` with `inserted` placed before the char at `index` (or at the end)
+fn insert_at(text: &str, index: usize, inserted: char) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    chars.insert(index.min(chars.len()), inserted);
+    chars.into_iter().collect()
+}
+
+#[test]
+fn test_controls_pass_through_in_place() {
+    let shlesha = Shlesha::new();
+    let samples = [
+        ("devanagari", "धर्म क्षेत्रे", "iast", "dharma kṣetre"),
+        ("devanagari", "धर्म क्षेत्रे", "telugu", "ధర్మ క్షేత్రే"),
+        ("iast", "dharma kṣetre", "devanagari", "धर्म क्षेत्रे"),
+        ("iast", "dharma kṣetre", "slp1", "Darma kzetre"),
+    ];
+    let controls = [
+        '\u{0}', '\u{7}', '\u{1B}', '\u{9F}', '\u{202E}', '\u{2066}', '\u{FEFF}',
+    ];
+
+    for (from, input, to, expected) in samples {
+        // Start, the space between the words, and end
+        let space = input.chars().position(|c| c == ' ').unwrap();
+        let expected_space = expected.chars().position(|c| c == ' ').unwrap();
+        for control in controls {
+            for (at, expected_at) in [(0, 0), (space, expected_space), (usize::MAX, usize::MAX)] {
+                let text = insert_at(input, at, control);
+                let output = shlesha.transliterate(&text, from, to).unwrap();
+                assert_eq!(
+                    output,
+                    insert_at(expected, expected_at, control),
+                    "{from} → {to} with {control:?} at {at}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_controls_inside_words_are_kept() {
+    let shlesha = Shlesha::new();
+
+    // A control between letters keeps its place; it separates them as a space would
+    assert_eq!(
+        shlesha
+            .transliterate("धर्\u{0}म", "devanagari", "iast")
+            .unwrap(),
+        "dhar\u{0}ma"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("dha\u{7}rma", "iast", "devanagari")
+            .unwrap(),
+        "ध\u{7}र्म"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("क\u{202E}ष", "devanagari", "telugu")
+            .unwrap(),
+        "క\u{202E}ష"
+    );
+}
+
+#[test]
+fn test_format_chars_are_recorded_in_metadata() {
+    let shlesha = Shlesha::new();
+    let text = "\u{202E}धर्म\u{7}\u{2060}क्षेत्र\u{0}";
+    let result = shlesha
+        .transliterate_with_metadata(text, "devanagari", "iast")
+        .unwrap();
+    assert_eq!(result.output, "\u{202E}dharma\u{7}\u{2060}kṣetra\u{0}");
+
+    let metadata = result.metadata.unwrap();
+    // C0 controls pass through like whitespace, and are not unknown
+    assert!(
+        metadata.unknown_tokens.is_empty(),
+        "{:?}",
+        metadata.unknown_tokens
+    );
+
+    let spans: Vec<_> = metadata
+        .fixed_spans
+        .iter()
+        .map(|span| {
+            (
+                span.kind,
+                span.input.as_str(),
+                span.output.as_str(),
+                span.position,
+            )
+        })
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            (SpanKind::FormatChar, "\u{202E}", "\u{202E}", 0),
+            (SpanKind::FormatChar, "\u{2060}", "\u{2060}", 16),
+        ]
+    );
+
+    // Same from IAST, through a Roman source
+    let result = shlesha
+        .transliterate_with_metadata("dharma\u{AD}kṣetra\u{2069}", "iast", "devanagari")
+        .unwrap();
+    assert_eq!(result.output, "धर्म\u{AD}क्षेत्र\u{2069}");
+    let metadata = result.metadata.unwrap();
+    let positions: Vec<_> = metadata.fixed_spans.iter().map(|s| s.position).collect();
+    assert_eq!(positions, [6, 16]);
+}
+
+#[test]
+fn test_controls_never_panic() {
+    let shlesha = Shlesha::new();
+    let noise: String = [
+        '\u{0}', '\u{8}', '\u{7F}', '\u{85}', '\u{202E}', '\u{200B}', '\u{FEFF}',
+    ]
+    .into_iter()
+    .collect();
+
+    for from in shlesha.list_supported_scripts() {
+        let Ok(sample) = shlesha.transliterate("नमोऽस्तु", "devanagari", &from)
+        else {
+            continue;
+        };
+        let text = format!("{noise}{sample}{noise}");
+        for to in ["devanagari", "iast", "telugu", "slp1"] {
+            let output = shlesha.transliterate(&text, &from, to).unwrap();
+            assert!(output.starts_with(&noise), "{from} → {to}: {output:?}");
+            assert!(output.ends_with(&noise), "{from} → {to}: {output:?}");
+            shlesha
+                .transliterate_with_metadata(&text, &from, to)
+                .unwrap();
+        }
+    }
+}
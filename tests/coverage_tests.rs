@@ -0,0 +1,97 @@
+use shlesha::Shlesha;
+
+fn converted_spans(shlesha: &Shlesha, text: &str, from: &str) -> Vec<std::ops::Range<usize>> {
+    shlesha
+        .transliterate_with_metadata(text, from, "devanagari")
+        .unwrap()
+        .metadata
+        .unwrap()
+        .converted_spans
+}
+
+#[test]
+fn test_valid_text_is_fully_covered() {
+    let shlesha = Shlesha::new();
+    for (text, script) in [
+        ("dharmakṣetre kurukṣetre samavetā yuyutsavaḥ", "iast"),
+        ("dharmakSetre kurukSetre", "slp1"),
+        ("धर्मक्षेत्रे कुरुक्षेत्रे", "devanagari"),
+        ("rāmaḥ, sītā ca", "iast"),
+    ] {
+        assert_eq!(shlesha.coverage(text, script).unwrap(), 1.0, "{text}");
+    }
+    // Nothing to read is nothing unread
+    assert_eq!(shlesha.coverage("", "iast").unwrap(), 1.0);
+    assert_eq!(shlesha.coverage(" \n", "iast").unwrap(), 1.0);
+}
+
+#[test]
+fn test_half_garbage_is_half_covered() {
+    let shlesha = Shlesha::new();
+    assert_eq!(shlesha.coverage("dharma ΩψχΦδε", "iast").unwrap(), 0.5);
+    // Characters are counted, not bytes: three-byte Devanagari weighs as one
+    assert_eq!(shlesha.coverage("धर्म ΩψχΦ", "devanagari").unwrap(), 0.5);
+
+    let coverage = shlesha.coverage("the quick brown fox", "iast").unwrap();
+    assert!(coverage < 0.9, "{coverage}");
+    assert!(shlesha.coverage("धर्म", "iast").unwrap() < 0.1);
+}
+
+#[test]
+fn test_converted_spans_tile_the_read_text() {
+    let shlesha = Shlesha::new();
+    for (text, script) in [
+        ("dharma ΩψχΦδε kṣetre", "iast"),
+        ("धर्मक्षेत्रे, kr ☺ कुरु", "devanagari"),
+    ] {
+        let result = shlesha
+            .transliterate_with_metadata(text, script, "telugu")
+            .unwrap();
+        let metadata = result.metadata.unwrap();
+        let spans = &metadata.converted_spans;
+        for pair in spans.windows(2) {
+            assert!(pair[0].end < pair[1].start, "{text}: {spans:?}");
+        }
+
+        // Each character is read, reported unknown or passed through, and only one
+        let unknown: Vec<usize> = metadata
+            .unknown_tokens
+            .iter()
+            .map(|token| token.position)
+            .collect();
+        for (at, ch) in text.char_indices() {
+            let in_span = spans.iter().any(|span| span.contains(&at));
+            let passed = ch.is_whitespace() || ch == ',' || unknown.contains(&at);
+            assert_ne!(in_span, passed, "{text}: {ch:?} at {at}, spans {spans:?}");
+        }
+    }
+
+    assert_eq!(
+        converted_spans(&shlesha, "dharma ΩψχΦδε kṣetre", "iast"),
+        [0..6, 20..28]
+    );
+    // An apostrophe read as avagraha is read, a possessive's is not, and
+    // IAST has no capital letters
+    assert_eq!(
+        converted_spans(&shlesha, "so'ham Arjuna's", "iast"),
+        [0..6, 8..13, 14..15]
+    );
+}
+
+#[test]
+fn test_converted_spans_are_positions_in_the_text_as_written() {
+    let shlesha = Shlesha::new();
+    // NFD ā is read as ā, and its span covers both characters
+    let spans = converted_spans(&shlesha, "ra\u{304}ma x", "iast");
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0], 0..6);
+    // An override counts as converted
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .add_word_override("iast", "devanagari", "kṛṣṇa", "कृष्ण")
+        .unwrap();
+    assert_eq!(
+        converted_spans(&shlesha, "kṛṣṇa rāma", "iast"),
+        [0..11, 12..17]
+    );
+}
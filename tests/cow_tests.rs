@@ -0,0 +1,105 @@
+//! Borrowed output from `Shlesha::transliterate_cow` for text a conversion leaves unchanged
+
+use shlesha::Shlesha;
+use std::borrow::Cow;
+
+/// Text without anything an Indic script reads
+const PASSTHROUGH: &[&str] = &["", "123, 456!", "(p. 42) -- see § 3", "\t\n"];
+
+#[test]
+fn test_identity_is_borrowed() {
+    let shlesha = Shlesha::new();
+
+    for (text, script) in [("राम", "devanagari"), ("rāma", "iast"), ("abc", "klingon")] {
+        let output = shlesha.transliterate_cow(text, script, script).unwrap();
+        assert!(matches!(output, Cow::Borrowed(_)), "{script}");
+        assert_eq!(output, text);
+    }
+}
+
+#[test]
+fn test_text_the_source_does_not_read_is_borrowed() {
+    let shlesha = Shlesha::new();
+
+    for text in PASSTHROUGH {
+        for (from, to) in [
+            ("devanagari", "iast"),
+            ("devanagari", "telugu"),
+            ("telugu", "kannada"),
+            ("deva", "slp1"),
+        ] {
+            let output = shlesha.transliterate_cow(text, from, to).unwrap();
+            assert!(
+                matches!(output, Cow::Borrowed(_)),
+                "{text:?}: {from} → {to}"
+            );
+            assert_eq!(output, *text);
+        }
+    }
+}
+
+#[test]
+fn test_text_the_source_reads_is_owned() {
+    let shlesha = Shlesha::new();
+
+    for (text, from, to, expected) in [
+        ("राम 123", "devanagari", "iast", "rāma 123"),
+        // ASCII digits are digits in Roman schemes
+        ("123", "iast", "devanagari", "१२३"),
+        ("[VowelAa]", "devanagari", "iast", "ā"),
+    ] {
+        let output = shlesha.transliterate_cow(text, from, to).unwrap();
+        assert!(matches!(output, Cow::Owned(_)), "{text:?}: {from} → {to}");
+        assert_eq!(output, expected);
+    }
+}
+
+#[test]
+fn test_word_overrides_are_not_passed_through() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .add_word_override("devanagari", "iast", "OK", "okay")
+        .unwrap();
+
+    let output = shlesha
+        .transliterate_cow("OK, 42", "devanagari", "iast")
+        .unwrap();
+    assert!(matches!(output, Cow::Owned(_)));
+    assert_eq!(output, "okay, 42");
+}
+
+#[test]
+fn test_borrowed_output_matches_conversion_for_every_pair() {
+    let shlesha = Shlesha::new();
+
+    for pair in shlesha.capability_matrix() {
+        if !pair.supported {
+            continue;
+        }
+        for text in PASSTHROUGH.iter().chain(&["dharma", "धर्म 12", "|| . ' ~"]) {
+            let output = shlesha
+                .transliterate_cow(text, &pair.from, &pair.to)
+                .unwrap();
+            assert_eq!(
+                output,
+                shlesha.transliterate(text, &pair.from, &pair.to).unwrap(),
+                "{text:?}: {} → {}",
+                pair.from,
+                pair.to
+            );
+            if let Cow::Borrowed(output) = output {
+                assert_eq!(output, *text, "{} → {}", pair.from, pair.to);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_unsupported_script_is_an_error() {
+    let shlesha = Shlesha::new();
+
+    assert!(shlesha.transliterate_cow("123", "klingon", "iast").is_err());
+    assert!(shlesha
+        .transliterate_cow("123", "devanagari", "klingon")
+        .is_err());
+}
@@ -0,0 +1,210 @@
+//! Custom converters built on the generated IAST and Devanagari converters
+#![cfg(all(feature = "scripts-core", feature = "scripts-south"))]
+
+use shlesha::modules::hub::tokens::{AbugidaToken, HubToken, HubTokenSequence};
+use shlesha::modules::hub::{HubFormat, HubInput};
+use shlesha::modules::script_converter::{
+    ConverterError, DevanagariConverter, IastConverter, ScriptConverter, TokenConverter,
+};
+use shlesha::Shlesha;
+
+/// IAST written in capitals, handled through the full `ScriptConverter` trait
+struct UppercaseIastConverter;
+
+impl ScriptConverter for UppercaseIastConverter {
+    fn to_hub(&self, _script: &str, input: &str) -> Result<HubInput, ConverterError> {
+        let tokens = IastConverter::new().string_to_tokens(&input.to_lowercase());
+        Ok(HubFormat::AlphabetTokens(tokens))
+    }
+
+    fn from_hub(&self, script: &str, hub_input: &HubInput) -> Result<String, ConverterError> {
+        match hub_input {
+            HubFormat::AlphabetTokens(tokens) => {
+                Ok(IastConverter::new().tokens_to_string(tokens).to_uppercase())
+            }
+            HubFormat::AbugidaTokens(_) => Err(ConverterError::ConversionFailed {
+                script: script.to_string(),
+                reason: "Expected alphabet tokens".to_string(),
+            }),
+        }
+    }
+
+    fn supported_scripts(&self) -> Vec<&'static str> {
+        vec!["upper_iast"]
+    }
+
+    fn script_has_implicit_a(&self, _script: &str) -> bool {
+        false
+    }
+}
+
+/// Devanagari that always writes anusvara as a homorganic nasal + virama before stops
+struct NasalDevanagariConverter {
+    devanagari: DevanagariConverter,
+}
+
+impl TokenConverter for NasalDevanagariConverter {
+    fn string_to_tokens(&self, input: &str) -> HubTokenSequence {
+        self.devanagari.string_to_tokens(input)
+    }
+
+    fn tokens_to_string(&self, tokens: &HubTokenSequence) -> String {
+        let mut rewritten = Vec::with_capacity(tokens.len());
+        for (i, token) in tokens.iter().enumerate() {
+            let next = tokens.get(i + 1);
+            let nasal = match (token, next) {
+                (
+                    HubToken::Abugida(AbugidaToken::MarkAnusvara),
+                    Some(HubToken::Abugida(AbugidaToken::ConsonantK | AbugidaToken::ConsonantG)),
+                ) => Some(AbugidaToken::ConsonantNg),
+                (
+                    HubToken::Abugida(AbugidaToken::MarkAnusvara),
+                    Some(HubToken::Abugida(AbugidaToken::ConsonantP | AbugidaToken::ConsonantB)),
+                ) => Some(AbugidaToken::ConsonantM),
+                _ => None,
+            };
+            match nasal {
+                Some(consonant) => {
+                    rewritten.push(HubToken::Abugida(consonant));
+                    rewritten.push(HubToken::Abugida(AbugidaToken::MarkVirama));
+                }
+                None => rewritten.push(token.clone()),
+            }
+        }
+        self.devanagari.tokens_to_string(&rewritten)
+    }
+
+    fn script_name(&self) -> &'static str {
+        "nasal_devanagari"
+    }
+
+    fn is_alphabet(&self) -> bool {
+        false
+    }
+}
+
+fn nasal_devanagari() -> Box<dyn TokenConverter> {
+    Box::new(NasalDevanagariConverter {
+        devanagari: DevanagariConverter::new(),
+    })
+}
+
+#[test]
+fn test_registered_script_converter_end_to_end() {
+    let mut transliterator = Shlesha::new();
+    assert!(!transliterator.supports_script("upper_iast"));
+
+    transliterator.register_converter(Box::new(UppercaseIastConverter));
+    assert!(transliterator.supports_script("upper_iast"));
+    assert!(transliterator
+        .list_supported_scripts()
+        .contains(&"upper_iast".to_string()));
+
+    let result = transliterator
+        .transliterate("DHARMA", "upper_iast", "devanagari")
+        .unwrap();
+    assert_eq!(result, "धर्म");
+
+    let result = transliterator
+        .transliterate("धर्म", "devanagari", "upper_iast")
+        .unwrap();
+    assert_eq!(result, "DHARMA");
+
+    let result = transliterator
+        .transliterate("RĀMA", "upper_iast", "telugu")
+        .unwrap();
+    assert_eq!(result, "రామ");
+}
+
+#[test]
+fn test_registered_token_converter_end_to_end() {
+    let mut transliterator = Shlesha::new();
+    transliterator.register_token_converter(nasal_devanagari(), &["nasal_deva"]);
+
+    // Abugida target reached from a Roman source goes through the hub
+    let result = transliterator
+        .transliterate("saṁkalpa", "iast", "nasal_devanagari")
+        .unwrap();
+    assert_eq!(result, "सङ्कल्प");
+
+    // Abugida → abugida
+    let result = transliterator
+        .transliterate("సంబల", "telugu", "nasal_devanagari")
+        .unwrap();
+    assert_eq!(result, "सम्बल");
+
+    // Registered as a source too
+    let result = transliterator
+        .transliterate("सङ्कल्प", "nasal_devanagari", "iast")
+        .unwrap();
+    assert_eq!(result, "saṅkalpa");
+}
+
+#[test]
+fn test_registered_token_converter_aliases() {
+    let mut transliterator = Shlesha::new();
+    transliterator.register_token_converter(nasal_devanagari(), &["nasal_deva", "ndeva"]);
+
+    for name in ["nasal_devanagari", "nasal_deva", "ndeva"] {
+        assert!(transliterator.supports_script(name), "{name} not supported");
+    }
+    // Listed once, by its own name, with the aliases alongside
+    let scripts = transliterator.list_supported_scripts();
+    assert!(scripts.contains(&"nasal_devanagari".to_string()));
+    assert!(!scripts.contains(&"ndeva".to_string()));
+    let listing = transliterator
+        .list_scripts_detailed()
+        .into_iter()
+        .find(|listing| listing.name == "nasal_devanagari")
+        .unwrap();
+    assert_eq!(listing.aliases, ["nasal_deva", "ndeva"]);
+
+    let via_alias = transliterator
+        .transliterate("saṁkalpa", "iast", "ndeva")
+        .unwrap();
+    let via_name = transliterator
+        .transliterate("saṁkalpa", "iast", "nasal_devanagari")
+        .unwrap();
+    assert_eq!(via_alias, via_name);
+}
+
+#[test]
+fn test_registration_is_per_instance() {
+    let mut transliterator = Shlesha::new();
+    transliterator.register_converter(Box::new(UppercaseIastConverter));
+
+    let other = Shlesha::new();
+    assert!(!other.supports_script("upper_iast"));
+    assert!(other.transliterate("DHARMA", "upper_iast", "iast").is_err());
+}
+
+#[test]
+fn test_registered_converter_replaces_builtin() {
+    struct ShoutingIast;
+
+    impl TokenConverter for ShoutingIast {
+        fn string_to_tokens(&self, input: &str) -> HubTokenSequence {
+            IastConverter::new().string_to_tokens(&input.to_lowercase())
+        }
+
+        fn tokens_to_string(&self, tokens: &HubTokenSequence) -> String {
+            IastConverter::new().tokens_to_string(tokens).to_uppercase()
+        }
+
+        fn script_name(&self) -> &'static str {
+            "iast"
+        }
+
+        fn is_alphabet(&self) -> bool {
+            true
+        }
+    }
+
+    let mut transliterator = Shlesha::new();
+    transliterator.register_token_converter(Box::new(ShoutingIast), &[]);
+
+    let result = transliterator
+        .transliterate("धर्म", "devanagari", "iast")
+        .unwrap();
+    assert_eq!(result, "DHARMA");
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use common::generate_script_character_sets;
+use shlesha::modules::normalize::compose;
+use shlesha::modules::script_converter::ScriptConverterRegistry;
+use shlesha::Shlesha;
+
+fn hub_only() -> Shlesha {
+    let mut shlesha = Shlesha::new();
+    shlesha.set_force_hub(true);
+    // Compared with the direct converters alone, which read input as written
+    shlesha.set_repair_input(false);
+    shlesha
+}
+
+/// Input the tokenizers treat specially, added to every character set
+const EDGE_CASES: &[&str] = &[
+    " ",
+    "\n",
+    "?!",
+    "x",
+    "[VowelA]",
+    "[NotAToken]",
+    "'",
+    "\u{200c}",
+    "\u{200d}",
+    "ï",
+    "i\u{0308}",
+    "ü",
+    "u\u{0308}",
+    "।",
+    "॥",
+    "ॐ",
+    "़",
+];
+
+/// Characters of `script`: its coverage set, or the Devanagari set converted into it
+fn script_characters(script: &str, hub: &Shlesha) -> Vec<String> {
+    let character_sets = generate_script_character_sets();
+    let mut characters = match character_sets.get(script) {
+        Some(characters) => characters.clone(),
+        None => character_sets["devanagari"]
+            .iter()
+            .map(|c| hub.transliterate(c, "devanagari", script).unwrap())
+            .collect(),
+    };
+    characters.extend(EDGE_CASES.iter().map(|c| c.to_string()));
+    characters
+}
+
+#[test]
+fn test_direct_converters_match_hub_path() {
+    let registry = ScriptConverterRegistry::default();
+    let hub = hub_only();
+    let pairs = registry.direct_pairs();
+    assert!(pairs.contains(&("iast", "slp1")));
+    assert!(pairs.contains(&("devanagari", "iast")));
+
+    for (from, to) in pairs {
+        let direct = registry.direct_converter(from, to, None).unwrap();
+        let characters = script_characters(from, &hub);
+
+        // Every character alone and followed by every other, so context rules are covered
+        let mut inputs = characters.clone();
+        for first in &characters {
+            for second in &characters {
+                inputs.push(format!("{first}{second}"));
+            }
+        }
+
+        // Roman input is composed to NFC before any converter reads it
+        let roman = registry.is_alphabet_script(from) == Some(true);
+        for input in inputs {
+            let expected = hub.transliterate(&input, from, to).unwrap();
+            let composed = compose(&input).filter(|_| roman);
+            let actual = direct
+                .convert(composed.as_ref().map_or(&input, |composed| &composed.text))
+                .unwrap();
+            assert_eq!(actual, expected, "{from} → {to} for {input:?}");
+        }
+    }
+}
+
+#[test]
+fn test_transliterate_uses_direct_converter_output() {
+    let shlesha = Shlesha::new();
+    let hub = hub_only();
+    assert!(!shlesha.force_hub());
+
+    let samples = [
+        ("praüga rāmaḥ kṛṣṇa", "iast", "slp1"),
+        ("a'i a\u{200c}u [VowelA]", "iast", "slp1"),
+        ("praUga aiSvarya", "slp1", "iast"),
+        ("धर्मक्षेत्रे कुरुक्षेत्रे ॥ १२ ॥", "devanagari", "iast"),
+        ("धर्मक्षेत्रे कुरुक्षेत्रे", "deva", "slp1"),
+        ("dharmakṣetre kurukṣetre", "iast", "devanagari"),
+    ];
+    for (text, from, to) in samples {
+        assert_eq!(
+            shlesha.transliterate(text, from, to).unwrap(),
+            hub.transliterate(text, from, to).unwrap(),
+            "{from} → {to} for {text:?}"
+        );
+    }
+}
+
+#[test]
+fn test_direct_converter_lookup_resolves_aliases() {
+    let registry = ScriptConverterRegistry::default();
+    let by_alias = registry.direct_converter("deva", "iast", None).unwrap();
+    assert_eq!(by_alias.from_script(), "devanagari");
+    assert_eq!(by_alias.to_script(), "iast");
+
+    // Pairs without a generated converter go through the hub
+    assert!(registry.direct_converter("wx", "velthuis", None).is_none());
+    assert!(ScriptConverterRegistry::new().direct_pairs().is_empty());
+}
+
+#[test]
+fn test_vedic_mode_keeps_hub_path() {
+    let mut shlesha = Shlesha::new();
+    shlesha.set_vedic_mode(true);
+    assert_eq!(
+        shlesha
+            .transliterate("अग्ना३इ", "devanagari", "iast")
+            .unwrap(),
+        "agnā3i"
+    );
+    assert_eq!(
+        shlesha.transliterate("agnA3i", "slp1", "iast").unwrap(),
+        "agnā3i"
+    );
+}
@@ -0,0 +1,91 @@
+//! Smoke tests for the programs in examples/, which are not run otherwise
+
+#[path = "../examples/benchmark_pairs.rs"]
+#[allow(dead_code)]
+mod benchmark_pairs;
+#[path = "../examples/custom_schema.rs"]
+#[allow(dead_code)]
+mod custom_schema;
+#[path = "../examples/metadata_report.rs"]
+#[allow(dead_code)]
+mod metadata_report;
+
+use shlesha::Shlesha;
+
+#[test]
+fn test_custom_schema_converts_both_ways() {
+    let mut shlesha = Shlesha::new();
+    shlesha.disable_runtime_compiler();
+    shlesha
+        .add_runtime_schema(custom_schema::doubled_vowel_schema())
+        .unwrap();
+
+    for (word, devanagari) in [("raama", "राम"), ("bhaarata", "भारत"), ("viira", "वीर")]
+    {
+        assert_eq!(
+            shlesha
+                .transliterate(word, "doubled_vowels", "devanagari")
+                .unwrap(),
+            devanagari
+        );
+        assert_eq!(
+            shlesha
+                .transliterate(devanagari, "devanagari", "doubled_vowels")
+                .unwrap(),
+            word
+        );
+    }
+    assert_eq!(
+        shlesha
+            .transliterate("deva", "doubled_vowels", "iast")
+            .unwrap(),
+        "deva"
+    );
+}
+
+#[test]
+fn test_runtime_schema_without_script_type_is_rejected() {
+    let mut shlesha = Shlesha::new();
+    shlesha.disable_runtime_compiler();
+
+    let schema = shlesha
+        .create_schema("untyped")
+        .add_vowel_mapping("VowelA", &["a"])
+        .build();
+    assert!(shlesha.add_runtime_schema(schema).is_err());
+    assert!(!shlesha.supports_script("untyped"));
+}
+
+#[test]
+fn test_metadata_report_lists_each_stage() {
+    let report = metadata_report::report("राम§", "devanagari", "iast").unwrap();
+    assert_eq!(
+        report,
+        "rāma§\n\nToken(s) not read from the source:\n  '§' (U+00A7): 1 time(s), first at 9\n"
+    );
+
+    // Tamil has no nukta to write
+    let report = metadata_report::report("ज़मीन", "devanagari", "tamil").unwrap();
+    assert!(report.contains("Token(s) not written in the target:\n  MarkNukta: 1 time(s)"));
+
+    let clean = metadata_report::report("राम", "devanagari", "iast").unwrap();
+    assert_eq!(
+        clean,
+        "rāma\n\nNo unknown tokens found in devanagari → iast conversion\n"
+    );
+}
+
+#[test]
+fn test_benchmark_pairs_times_every_supported_pair() {
+    let shlesha = Shlesha::new();
+    let timings = benchmark_pairs::benchmark(&shlesha, "नमः", 1).unwrap();
+
+    let supported = shlesha
+        .capability_matrix()
+        .iter()
+        .filter(|pair| pair.supported)
+        .count();
+    assert_eq!(timings.len(), supported);
+    assert!(timings.iter().any(|t| t.from == "iast" && t.to == "telugu"));
+    assert!(timings.iter().all(|t| t.bytes > 0));
+}
@@ -0,0 +1,77 @@
+//! Inputs found by the fuzz targets in `fuzz/`, kept so they stay fixed
+
+use shlesha::Shlesha;
+
+/// A schema cut off after a mapping key, as in a half-written file
+const TRUNCATED_SCHEMA: &str = r#"
+metadata:
+  name: "truncated"
+  script_type: "brahmic"
+  has_implicit_a: true
+target: "devanagari"
+mappings:
+  vowels:
+    "അ": "अ"
+  consonants:
+    "ക": "क"
+  marks:
+    "ം":
+"#;
+
+#[test]
+fn test_null_mapping_is_rejected() {
+    // This used to load, then loop forever matching the empty pattern
+    let mut shlesha = Shlesha::new();
+    let result = shlesha.load_schema_from_string(TRUNCATED_SCHEMA, "fuzzed");
+    assert!(result.is_err());
+    assert!(!shlesha
+        .list_supported_scripts()
+        .contains(&"fuzzed".to_string()));
+}
+
+#[test]
+fn test_empty_spelling_read_is_rejected() {
+    // A token may be written as nothing, but not read from nothing
+    let yaml = r#"
+metadata:
+  name: "empty"
+  script_type: "roman"
+  has_implicit_a: false
+target: "alphabet_tokens"
+mappings:
+  vowels:
+    VowelA: "a"
+    VowelAa: ["aa", ""]
+    VowelI: ""
+"#;
+    let mut shlesha = Shlesha::new();
+    let error = shlesha.load_schema_from_string(yaml, "").unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Empty spelling read for token(s): VowelAa;"),
+        "{error}"
+    );
+}
+
+#[test]
+fn test_lone_combining_marks_across_scripts() {
+    let shlesha = Shlesha::new();
+    let scripts = shlesha.list_supported_scripts();
+    let inputs = [
+        "\u{0301}",
+        "\u{094D}a",
+        "ं\u{0951}",
+        "\u{0300}[",
+        "a\u{0331}ृ",
+        "[\u{0952}]",
+    ];
+
+    for from in &scripts {
+        for to in &scripts {
+            for input in inputs {
+                let _ = shlesha.transliterate(input, from, to);
+            }
+        }
+    }
+}
@@ -0,0 +1,331 @@
+//! Frozen outputs of every built-in script, against `Shlesha::output_version`
+//!
+//! tests/golden holds one case file per script pair (Devanagari to each script
+//! and back), and OUTPUT_VERSION, the output version the files were written at.
+//! tests/golden/runtime holds the same for runtime-loaded copies of the schemas.
+//! When outputs change on purpose, regenerate both with
+//!
+//! ```bash
+//! SHLESHA_UPDATE_GOLDEN=1 cargo test --test golden_tests
+//! ```
+//!
+//! and review the diff. Regenerating refuses to change outputs without a change
+//! of output version; see "Output Compatibility" in the README.
+
+use serde::Serialize;
+use shlesha::modules::conformance::load_case_file;
+use shlesha::modules::script_converter::ScriptConverterRegistry;
+use shlesha::Shlesha;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Samples converted in every fixture, as Devanagari
+const SAMPLES: &[&str] = &[
+    "धर्मक्षेत्रे कुरुक्षेत्रे समवेता युयुत्सवः",
+    "अ आ इ ई उ ऊ ऋ ॠ ऌ ए ऐ ओ औ",
+    "कं कः कँ का कि की कु कू कृ कॄ के कै को कौ क्",
+    "कृष्ण ज्ञान श्री ह्रीं वाङ्मय सोऽहम्",
+    "संस्कृतम् १२३४५६७८९०",
+];
+
+const UPDATE_VAR: &str = "SHLESHA_UPDATE_GOLDEN";
+
+#[derive(Serialize)]
+struct GoldenFile {
+    from: String,
+    to: String,
+    cases: Vec<GoldenCase>,
+}
+
+#[derive(Serialize)]
+struct GoldenCase {
+    input: String,
+    expected: String,
+}
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+fn recorded_version() -> String {
+    fs::read_to_string(golden_dir().join("OUTPUT_VERSION"))
+        .unwrap()
+        .trim()
+        .to_string()
+}
+
+/// The case files, in name order
+fn golden_files() -> Vec<PathBuf> {
+    let mut paths: Vec<_> = fs::read_dir(golden_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no golden files");
+    paths
+}
+
+/// Outputs that differ from the case files, as readable lines
+fn drift(shlesha: &Shlesha) -> Vec<String> {
+    let mut drift = Vec::new();
+    for path in golden_files() {
+        let file = load_case_file(&path).unwrap();
+        // Builds with a subset of the scripts-* features check what they have
+        if !shlesha.supports_script(&file.from) || !shlesha.supports_script(&file.to) {
+            continue;
+        }
+        for case in &file.cases {
+            let actual = shlesha
+                .transliterate(&case.input, &file.from, &file.to)
+                .unwrap_or_else(|e| format!("<error: {e}>"));
+            if actual != case.expected {
+                drift.push(format!(
+                    "{} → {}: {:?}\n  expected {:?}\n  actual   {:?}",
+                    file.from, file.to, case.input, case.expected, actual
+                ));
+            }
+        }
+    }
+    drift
+}
+
+/// Write the case files and OUTPUT_VERSION from the current outputs
+fn regenerate(shlesha: &Shlesha) {
+    let dir = golden_dir();
+    for script in ScriptConverterRegistry::default().primary_scripts() {
+        let script = script.as_str();
+        if script == "devanagari" {
+            continue;
+        }
+        let outputs: Vec<String> = SAMPLES
+            .iter()
+            .map(|sample| shlesha.transliterate(sample, "devanagari", script).unwrap())
+            .collect();
+        let pairs = [
+            (
+                "devanagari",
+                script,
+                SAMPLES.iter().map(|s| s.to_string()).collect(),
+            ),
+            (script, "devanagari", outputs),
+        ];
+        for (from, to, inputs) in pairs {
+            let cases = inputs
+                .into_iter()
+                .map(|input: String| GoldenCase {
+                    expected: shlesha.transliterate(&input, from, to).unwrap(),
+                    input,
+                })
+                .collect();
+            let file = GoldenFile {
+                from: from.to_string(),
+                to: to.to_string(),
+                cases,
+            };
+            let yaml = format!(
+                "# Generated by {UPDATE_VAR}=1 cargo test --test golden_tests\n{}",
+                serde_yaml::to_string(&file).unwrap()
+            );
+            fs::write(dir.join(format!("{from}-{to}.yaml")), yaml).unwrap();
+        }
+    }
+    fs::write(
+        dir.join("OUTPUT_VERSION"),
+        format!("{}\n", Shlesha::output_version()),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_golden_outputs() {
+    let shlesha = Shlesha::new();
+    let drift = drift(&shlesha);
+
+    if std::env::var_os(UPDATE_VAR).is_some() {
+        assert!(
+            drift.is_empty() || recorded_version() != Shlesha::output_version(),
+            "Outputs changed but the output version did not; bump OUTPUT_EPOCH in build.rs\n\n{}",
+            drift.join("\n")
+        );
+        regenerate(&shlesha);
+        return;
+    }
+
+    assert!(
+        drift.is_empty(),
+        "{} outputs differ from tests/golden. If the change is intended, run \
+         {UPDATE_VAR}=1 cargo test --test golden_tests and review the diff.\n\n{}",
+        drift.len(),
+        drift.join("\n")
+    );
+}
+
+#[test]
+fn test_output_version_is_recorded() {
+    if std::env::var_os(UPDATE_VAR).is_some() {
+        return;
+    }
+    assert_eq!(
+        Shlesha::output_version(),
+        recorded_version(),
+        "Schemas or hub rules changed since tests/golden was written. Run \
+         {UPDATE_VAR}=1 cargo test --test golden_tests to record the new output version."
+    );
+}
+
+#[test]
+fn test_fast_path_matches_transliterate() {
+    let shlesha = Shlesha::new();
+    for path in golden_files() {
+        let file = load_case_file(&path).unwrap();
+        if !shlesha.supports_script(&file.from) || !shlesha.supports_script(&file.to) {
+            continue;
+        }
+        for case in &file.cases {
+            assert_eq!(
+                shlesha
+                    .transliterate_fast(&case.input, &file.from, &file.to)
+                    .unwrap(),
+                shlesha
+                    .transliterate(&case.input, &file.from, &file.to)
+                    .unwrap(),
+                "{} → {}: {:?}",
+                file.from,
+                file.to,
+                case.input
+            );
+        }
+    }
+}
+
+/// The scripts with a schema file, each loaded again at runtime as `runtime_<script>`
+///
+/// Aliases are dropped so the copies do not shadow the built-in scripts, and
+/// each token keeps only its first spelling.
+fn runtime_copies() -> (Shlesha, Vec<(String, serde_yaml::Value)>) {
+    let mut shlesha = Shlesha::new();
+    let mut copies = Vec::new();
+    for script in ScriptConverterRegistry::default().primary_scripts() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("schemas")
+            .join(format!("{script}.yaml"));
+        if script == "devanagari" || !path.exists() {
+            continue;
+        }
+        let mut schema: serde_yaml::Value =
+            serde_yaml::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        if let Some(metadata) = schema
+            .get_mut("metadata")
+            .and_then(|metadata| metadata.as_mapping_mut())
+        {
+            metadata.remove("aliases");
+        }
+        for (_, category) in schema["mappings"].as_mapping_mut().into_iter().flatten() {
+            for (_, spelling) in category.as_mapping_mut().into_iter().flatten() {
+                if let Some(first) = spelling.as_sequence().and_then(|forms| forms.first()) {
+                    *spelling = first.clone();
+                }
+            }
+        }
+        let name = format!("runtime_{script}");
+        shlesha
+            .load_schema_from_string(&serde_yaml::to_string(&schema).unwrap(), &name)
+            .unwrap();
+        copies.push((name, schema));
+    }
+    (shlesha, copies)
+}
+
+/// Every spelling in a schema's mappings, separated by spaces
+fn mapped_spellings(schema: &serde_yaml::Value) -> String {
+    let mut spellings = Vec::new();
+    for (_, category) in schema["mappings"].as_mapping().into_iter().flatten() {
+        for (_, spelling) in category.as_mapping().into_iter().flatten() {
+            spellings.extend(spelling.as_str());
+        }
+    }
+    spellings.join(" ")
+}
+
+/// The case files of the runtime copies: the samples written in each copy, and
+/// read back from it together with every spelling it maps
+fn runtime_case_files(
+    shlesha: &Shlesha,
+    copies: &[(String, serde_yaml::Value)],
+) -> Vec<GoldenFile> {
+    let mut files = Vec::new();
+    for (name, schema) in copies {
+        let outputs: Vec<String> = SAMPLES
+            .iter()
+            .map(|sample| shlesha.transliterate(sample, "devanagari", name).unwrap())
+            .collect();
+        let mut inputs = outputs;
+        inputs.push(mapped_spellings(schema));
+        for (from, to, inputs) in [
+            (
+                "devanagari",
+                name.as_str(),
+                SAMPLES.iter().map(|s| s.to_string()).collect(),
+            ),
+            (name.as_str(), "devanagari", inputs),
+        ] {
+            let cases = inputs
+                .into_iter()
+                .map(|input: String| GoldenCase {
+                    expected: shlesha.transliterate(&input, from, to).unwrap(),
+                    input,
+                })
+                .collect();
+            files.push(GoldenFile {
+                from: from.to_string(),
+                to: to.to_string(),
+                cases,
+            });
+        }
+    }
+    files
+}
+
+/// Runtime-loaded copies of the schemas convert as they did when
+/// tests/golden/runtime was written, whatever matcher reads and writes them
+#[test]
+fn test_runtime_schema_outputs() {
+    let (shlesha, copies) = runtime_copies();
+    let dir = golden_dir().join("runtime");
+    let files = runtime_case_files(&shlesha, &copies);
+
+    if std::env::var_os(UPDATE_VAR).is_some() {
+        fs::create_dir_all(&dir).unwrap();
+        for file in &files {
+            let yaml = format!(
+                "# Generated by {UPDATE_VAR}=1 cargo test --test golden_tests\n{}",
+                serde_yaml::to_string(file).unwrap()
+            );
+            fs::write(dir.join(format!("{}-{}.yaml", file.from, file.to)), yaml).unwrap();
+        }
+        return;
+    }
+
+    let mut drift = Vec::new();
+    for file in &files {
+        let recorded =
+            load_case_file(&dir.join(format!("{}-{}.yaml", file.from, file.to))).unwrap();
+        for (case, actual) in recorded.cases.iter().zip(&file.cases) {
+            assert_eq!(case.input, actual.input, "{} → {}", file.from, file.to);
+            if case.expected != actual.expected {
+                drift.push(format!(
+                    "{} → {}: {:?}\n  expected {:?}\n  actual   {:?}",
+                    file.from, file.to, case.input, case.expected, actual.expected
+                ));
+            }
+        }
+    }
+    assert!(
+        drift.is_empty(),
+        "{} runtime schema outputs differ from tests/golden/runtime. If the change is \
+         intended, run {UPDATE_VAR}=1 cargo test --test golden_tests and review the diff.\n\n{}",
+        drift.len(),
+        drift.join("\n")
+    );
+}
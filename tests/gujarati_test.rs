@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod gujarati_tests {
+    use shlesha::Shlesha;
+
+    /// Devanagari ↔ Gujarati pairs for the characters completed in the Gujarati schema
+    const EXTENDED_PAIRS: &[(&str, &str)] = &[
+        // Avagraha and candrabindu
+        ("ऽ", "ઽ"),
+        ("कँ", "કઁ"),
+        // Om sign
+        ("ॐ", "ૐ"),
+        // Nukta consonants (precomposed in Devanagari)
+        ("\u{0958}", "ક઼"),
+        ("\u{0959}", "ખ઼"),
+        ("\u{095A}", "ગ઼"),
+        ("\u{095B}", "જ઼"),
+        ("\u{095E}", "ફ઼"),
+        ("\u{095C}", "ડ઼"),
+        ("\u{095D}", "ઢ઼"),
+        // Vocalic r/l vowels and vowel signs
+        ("ऋ", "ઋ"),
+        ("ॠ", "ૠ"),
+        ("ऌ", "ઌ"),
+        ("ॡ", "ૡ"),
+        ("कृ", "કૃ"),
+        ("कॄ", "કૄ"),
+        ("कॢ", "કૢ"),
+        ("कॣ", "કૣ"),
+        // e/o vowels and signs
+        ("ए", "એ"),
+        ("ओ", "ઓ"),
+        ("के", "કે"),
+        ("को", "કો"),
+        // Digits
+        ("०१२३४५६७८९", "૦૧૨૩૪૫૬૭૮૯"),
+    ];
+
+    #[test]
+    fn test_gujarati_extended_characters_from_devanagari() {
+        let transliterator = Shlesha::new();
+
+        for (devanagari, gujarati) in EXTENDED_PAIRS {
+            let result = transliterator
+                .transliterate(devanagari, "devanagari", "gujarati")
+                .unwrap();
+            assert_eq!(&result, gujarati, "Devanagari {devanagari} → Gujarati");
+        }
+    }
+
+    #[test]
+    fn test_gujarati_extended_characters_to_devanagari() {
+        let transliterator = Shlesha::new();
+
+        for (devanagari, gujarati) in EXTENDED_PAIRS {
+            let result = transliterator
+                .transliterate(gujarati, "gujarati", "devanagari")
+                .unwrap();
+            // Nukta consonants come back in their precomposed Devanagari form
+            assert_eq!(&result, devanagari, "Gujarati {gujarati} → Devanagari");
+        }
+    }
+
+    #[test]
+    fn test_gujarati_roundtrip() {
+        let transliterator = Shlesha::new();
+
+        for (devanagari, _) in EXTENDED_PAIRS {
+            let gujarati = transliterator
+                .transliterate(devanagari, "devanagari", "gujarati")
+                .unwrap();
+            let back = transliterator
+                .transliterate(&gujarati, "gujarati", "devanagari")
+                .unwrap();
+            assert_eq!(&back, devanagari, "Round-trip via Gujarati {gujarati}");
+        }
+    }
+
+    #[test]
+    fn test_gujarati_real_words() {
+        let transliterator = Shlesha::new();
+
+        let words = [
+            ("संस्कृत", "સંસ્કૃત"),
+            ("सोऽहम्", "સોઽહમ્"),
+            ("चाँद", "ચાઁદ"),
+            ("\u{095B}मीन", "જ઼મીન"),
+            ("ॐ नमः शिवाय", "ૐ નમઃ શિવાય"),
+        ];
+
+        for (devanagari, gujarati) in words {
+            let result = transliterator
+                .transliterate(devanagari, "devanagari", "gujarati")
+                .unwrap();
+            assert_eq!(result, gujarati);
+
+            let back = transliterator
+                .transliterate(gujarati, "gujarati", "devanagari")
+                .unwrap();
+            assert_eq!(back, devanagari);
+        }
+
+        // Mixed Gujarati and Latin text keeps the Latin untouched
+        let result = transliterator
+            .transliterate("સંસ્કૃત text ૧૨", "gujarati", "devanagari")
+            .unwrap();
+        assert_eq!(result, "संस्कृत text १२");
+    }
+
+    #[test]
+    fn test_gujarati_om_to_roman() {
+        let transliterator = Shlesha::new();
+
+        let result = transliterator
+            .transliterate("ૐ", "gujarati", "iso15919")
+            .unwrap();
+        assert_eq!(result, "ōṁ");
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod gurmukhi_tests {
+    use shlesha::Shlesha;
+
+    #[test]
+    fn test_gurmukhi_letters() {
+        let transliterator = Shlesha::new();
+
+        assert_eq!(
+            transliterator
+                .transliterate(
+                    "dharmakṣetre kurukṣetre samavetā yuyutsavaḥ",
+                    "iast",
+                    "gurmukhi"
+                )
+                .unwrap(),
+            "ਧਰ੍ਮਕ੍\u{0A36}ੇਤ੍ਰੇ ਕੁਰੁਕ੍\u{0A36}ੇਤ੍ਰੇ ਸਮਵੇਤਾ ਯੁਯੁਤ੍ਸਵਃ"
+        );
+
+        // Tippi reads as bindi, and base + nukta as the nukta letter
+        assert_eq!(
+            transliterator
+                .transliterate(
+                    "ਸੰਤ ਸਂਤ ਖ\u{0A3C}ੁ\u{0A36}ੀ \u{0A59}ੁ\u{0A36}ੀ",
+                    "pa",
+                    "devanagari"
+                )
+                .unwrap(),
+            "संत संत \u{0959}ुशी \u{0959}ुशी"
+        );
+    }
+
+    #[test]
+    fn test_gurmukhi_vocalic_r_fallback() {
+        let transliterator = Shlesha::new();
+
+        assert_eq!(
+            transliterator
+                .transliterate("kṛpā ṛṣi pitṝn kḷpta", "iast", "gurmukhi")
+                .unwrap(),
+            "ਕ੍ਰਿਪਾ ਰਿ\u{0A36}ਿ ਪਿਤ੍ਰੀਨ੍ ਕ੍ਲਿਪ੍ਤ"
+        );
+        // From Devanagari too, which has the letters Gurmukhi lacks
+        assert_eq!(
+            transliterator
+                .transliterate("कृपा", "devanagari", "gurmukhi")
+                .unwrap(),
+            "ਕ੍ਰਿਪਾ"
+        );
+
+        let result = transliterator
+            .transliterate_with_metadata("kṛpā", "iast", "gurmukhi")
+            .unwrap();
+        assert_eq!(result.output, "ਕ੍ਰਿਪਾ");
+        let metadata = result.metadata.unwrap();
+        assert!(metadata.is_lossy());
+        let substituted: Vec<(&str, &str)> = metadata
+            .lossy_mappings
+            .iter()
+            .map(|m| (m.token.as_str(), m.substitute.as_str()))
+            .collect();
+        assert_eq!(
+            substituted,
+            [("VowelSignR", "MarkVirama + ConsonantR + VowelSignI")]
+        );
+    }
+}
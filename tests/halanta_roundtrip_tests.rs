@@ -0,0 +1,71 @@
+use shlesha::Shlesha;
+
+/// Words ending in a halanta consonant or cluster, alone and in running text
+const HALANTA_WORDS: &[&str] = &[
+    "संस्कृतम्",
+    "वाक्",
+    "मरुत्",
+    "सम्राट्",
+    "षट्",
+    "भगवन्",
+    "कार्त्स्न्य्",
+    "अहम् अस्मि",
+    "वाक्। इति",
+    "वाक्, मनः",
+    "त्वम्\nअहम्",
+    "(वाक्)",
+];
+
+const ROMAN_SCHEMES: &[&str] = &["iast", "iso15919", "slp1", "harvard_kyoto"];
+
+#[test]
+fn test_halanta_words_roundtrip_through_roman() {
+    // Both the direct converters and the hub path
+    for force_hub in [false, true] {
+        let mut shlesha = Shlesha::new();
+        shlesha.set_force_hub(force_hub);
+
+        for scheme in ROMAN_SCHEMES {
+            for word in HALANTA_WORDS {
+                let roman = shlesha.transliterate(word, "devanagari", scheme).unwrap();
+                let back = shlesha.transliterate(&roman, scheme, "devanagari").unwrap();
+                assert_eq!(
+                    &back, word,
+                    "{word} → {scheme} {roman:?} → devanagari (force_hub: {force_hub})"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_final_consonant_without_vowel_is_halanta() {
+    let shlesha = Shlesha::new();
+    let cases = [
+        ("saṁskṛtam", "iast", "संस्कृतम्"),
+        ("saṁskr̥tam", "iso15919", "संस्कृतम्"),
+        ("vāk", "iast", "वाक्"),
+        ("vAk", "slp1", "वाक्"),
+        ("vAk", "harvard_kyoto", "वाक्"),
+        ("marut", "iso15919", "मरुत्"),
+        // A written a is the inherent vowel, not a halanta
+        ("vāka", "iast", "वाक"),
+    ];
+    for (roman, scheme, devanagari) in cases {
+        assert_eq!(
+            shlesha.transliterate(roman, scheme, "devanagari").unwrap(),
+            devanagari,
+            "{scheme} {roman}"
+        );
+    }
+}
+
+#[test]
+fn test_halanta_survives_metadata_path() {
+    let shlesha = Shlesha::new();
+    let result = shlesha
+        .transliterate_with_metadata("saṁskṛtam vāk", "iast", "devanagari")
+        .unwrap();
+    assert_eq!(result.output, "संस्कृतम् वाक्");
+    assert!(result.metadata.unwrap().unknown_tokens.is_empty());
+}
@@ -0,0 +1,99 @@
+use shlesha::Shlesha;
+
+#[test]
+fn test_diaeresis_separates_vowels() {
+    let shlesha = Shlesha::new();
+
+    for script in ["iast", "iso15919"] {
+        // Precomposed and combining diaeresis read the same way
+        for input in ["praüga", "prau\u{0308}ga"] {
+            let result = shlesha.transliterate(input, script, "devanagari").unwrap();
+            assert_eq!(result, "प्रउग", "{script}: {input}");
+        }
+        let result = shlesha
+            .transliterate("aïndra", script, "devanagari")
+            .unwrap();
+        assert_eq!(result, "अइन्द्र", "{script}");
+        let result = shlesha.transliterate("gaü", script, "telugu").unwrap();
+        assert_eq!(result, "గఉ", "{script}");
+    }
+}
+
+#[test]
+fn test_separators_between_a_and_i_or_u() {
+    let shlesha = Shlesha::new();
+
+    for input in ["pra'uga", "pra\u{200C}uga"] {
+        let result = shlesha.transliterate(input, "iast", "devanagari").unwrap();
+        assert_eq!(result, "प्रउग", "{input:?}");
+    }
+    let result = shlesha
+        .transliterate("a\u{200C}indra", "iso15919", "devanagari")
+        .unwrap();
+    assert_eq!(result, "अइन्द्र");
+
+    // Elsewhere the apostrophe is still avagraha
+    let result = shlesha
+        .transliterate("so'ham", "iast", "devanagari")
+        .unwrap();
+    assert_eq!(result, "सोऽहम्");
+}
+
+#[test]
+fn test_hub_to_roman_writes_diaeresis_after_a() {
+    let shlesha = Shlesha::new();
+
+    let cases = [
+        ("प्रउग", "praüga"),
+        ("अइन्द्र", "aïndra"),
+        ("गउ", "gaü"),
+        // Only directly after a
+        ("किउ", "kiu"),
+        ("आइ", "āi"),
+    ];
+    for (devanagari, iast) in cases {
+        let result = shlesha
+            .transliterate(devanagari, "devanagari", "iast")
+            .unwrap();
+        assert_eq!(result, iast);
+        let result = shlesha
+            .transliterate(devanagari, "devanagari", "iso15919")
+            .unwrap();
+        assert_eq!(result, iast);
+    }
+}
+
+#[test]
+fn test_diphthongs_unaffected() {
+    let shlesha = Shlesha::new();
+
+    let result = shlesha
+        .transliterate("kaumudī", "iast", "devanagari")
+        .unwrap();
+    assert_eq!(result, "कौमुदी");
+    let result = shlesha
+        .transliterate("कौमुदी", "devanagari", "iast")
+        .unwrap();
+    assert_eq!(result, "kaumudī");
+    let result = shlesha
+        .transliterate("aiśvarya", "iast", "devanagari")
+        .unwrap();
+    assert_eq!(result, "ऐश्वर्य");
+    let result = shlesha
+        .transliterate("prauga", "iast", "devanagari")
+        .unwrap();
+    assert_eq!(result, "प्रौग");
+}
+
+#[test]
+fn test_hiatus_roundtrip_through_roman() {
+    let shlesha = Shlesha::new();
+
+    for text in ["प्रउग", "अइन्द्र", "तितउ", "कौमुदी"] {
+        for roman in ["iast", "iso15919"] {
+            let there = shlesha.transliterate(text, "devanagari", roman).unwrap();
+            let back = shlesha.transliterate(&there, roman, "devanagari").unwrap();
+            assert_eq!(back, text, "{text} via {roman} ({there})");
+        }
+    }
+}
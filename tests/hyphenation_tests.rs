@@ -0,0 +1,144 @@
+//! Hyphens between the members of compounds with the `hyphenation` option
+
+use shlesha::modules::hyphenation::join_members;
+use shlesha::{Hyphenation, MarkerOutput, SelectivePolicy, Shlesha, TransliterationOptions};
+
+const VERSE: &str = "धर्म-क्षेत्रे कुरु-क्षेत्रे समवेता युयुत्सवः । मामकाः पाण्डवाश्-चैव किम्-अकुर्वत सञ्जय ॥";
+
+fn stripped() -> TransliterationOptions {
+    TransliterationOptions {
+        hyphenation: Some(Hyphenation {
+            output: MarkerOutput::Strip,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+fn convert(text: &str, from: &str, to: &str, options: &TransliterationOptions) -> String {
+    Shlesha::new()
+        .transliterate_with_options(text, from, to, options)
+        .unwrap()
+}
+
+#[test]
+fn test_compound_members_keep_their_hyphen() {
+    let citation = TransliterationOptions::citation();
+    assert_eq!(
+        convert("धर्म-क्षेत्र", "devanagari", "iast", &citation),
+        "dharma-kṣetra"
+    );
+    assert_eq!(
+        convert("धर्म‐क्षेत्र", "devanagari", "iast", &citation),
+        "dharma‐kṣetra"
+    );
+    assert_eq!(
+        convert(VERSE, "devanagari", "iast", &citation),
+        "dharma-kṣetre kuru-kṣetre samavetā yuyutsavaḥ । māmakāḥ pāṇḍavāś-caiva kim-akurvata sañjaya ॥"
+    );
+}
+
+#[test]
+fn test_compound_members_are_stripped() {
+    assert_eq!(
+        convert("धर्म-क्षेत्र", "devanagari", "iast", &stripped()),
+        "dharmakṣetra"
+    );
+    assert_eq!(
+        convert(VERSE, "devanagari", "iast", &stripped()),
+        "dharmakṣetre kurukṣetre samavetā yuyutsavaḥ । māmakāḥ pāṇḍavāścaiva kimakurvata sañjaya ॥"
+    );
+}
+
+#[test]
+fn test_hyphens_do_not_end_the_word() {
+    // Without the option, a hyphen ends the word: a virama before it and an
+    // independent vowel after it
+    let shlesha = Shlesha::new();
+    assert_eq!(
+        shlesha
+            .transliterate("tat-tvam-asi", "iast", "devanagari")
+            .unwrap(),
+        "तत्-त्वम्-असि"
+    );
+    assert_eq!(
+        convert("tat-tvam-asi", "iast", "devanagari", &stripped()),
+        "तत्त्वमसि"
+    );
+    // Members sharing an akshara keep the marker inside it
+    assert_eq!(
+        convert(
+            "tat-tvam-asi",
+            "iast",
+            "devanagari",
+            &TransliterationOptions::citation()
+        ),
+        "तत्-त्वम-सि"
+    );
+}
+
+#[test]
+fn test_hyphens_outside_compounds_are_converted() {
+    let text = "रामः - सीता राम- -राम क--ख";
+    for options in [TransliterationOptions::citation(), stripped()] {
+        assert_eq!(
+            convert(text, "devanagari", "iast", &options),
+            "rāmaḥ - sītā rāma- -rāma ka--kha"
+        );
+    }
+    // A hyphen between the a and i of IAST ai marks a hiatus, and stays
+    assert_eq!(
+        convert("rāma-iti", "iast", "devanagari", &stripped()),
+        "राम-इति"
+    );
+}
+
+#[test]
+fn test_markers_are_configurable() {
+    let options = TransliterationOptions {
+        hyphenation: Some(Hyphenation {
+            markers: vec!['·'],
+            output: MarkerOutput::Keep,
+        }),
+        ..Default::default()
+    };
+    assert_eq!(
+        convert("tat·tvam-asi", "iast", "devanagari", &options),
+        "तत्·त्वम्-असि"
+    );
+
+    let joined = join_members("a-b‐c", &['-', '\u{2010}']);
+    assert_eq!(joined.text, "abc");
+    assert_eq!(joined.original_position(1), 2);
+    assert_eq!(joined.original_position(2), 6);
+}
+
+#[test]
+fn test_metadata_positions_refer_to_the_hyphenated_text() {
+    let text = "धर्म-क्षेत्रk";
+    let result = Shlesha::new()
+        .transliterate_with_options_and_metadata(
+            text,
+            "devanagari",
+            "iast",
+            &TransliterationOptions::citation(),
+        )
+        .unwrap();
+    assert_eq!(result.output, "dharma-kṣetrak");
+    let metadata = result.metadata.unwrap();
+    let [unknown] = metadata.unknown_tokens.as_slice() else {
+        panic!("{:?}", metadata.unknown_tokens);
+    };
+    assert_eq!(unknown.position, text.find('k').unwrap());
+}
+
+#[test]
+fn test_selective_conversion_is_refused() {
+    let options = TransliterationOptions {
+        hyphenation: Some(Hyphenation::default()),
+        ..TransliterationOptions::selective(SelectivePolicy::default())
+    };
+    assert!(Shlesha::new()
+        .transliterate_with_options("dharma-kṣetra", "iast", "devanagari", &options)
+        .is_err());
+}
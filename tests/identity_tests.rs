@@ -0,0 +1,41 @@
+//! Conversion between two names of the same script copies the input
+
+mod common;
+
+use common::ascii_roman_schema;
+use shlesha::Shlesha;
+
+#[test]
+fn test_aliases_of_the_same_script_copy_the_input() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(&ascii_roman_schema("aa"), "ascii_roman")
+        .unwrap();
+
+    // Each input would come out changed if it were read and written back:
+    // repaired vowel signs, a decomposed nukta letter, unknown characters
+    for (input, from, to) in [
+        ("कोे क\u{093C} Q", "deva", "devanagari"),
+        ("devanagari text कोे", "devanagari", "deva"),
+        ("saṃskṛtam Q", "iso", "iso15919"),
+        ("kaam xyz", "ar", "ascii_roman"),
+        ("kaam xyz", "ascii_roman", "ar"),
+    ] {
+        assert_eq!(
+            shlesha.transliterate(input, from, to).unwrap(),
+            input,
+            "{from} -> {to}"
+        );
+
+        let result = shlesha
+            .transliterate_with_metadata(input, from, to)
+            .unwrap();
+        assert_eq!(result.output, input, "{from} -> {to}");
+        let metadata = result.metadata.unwrap();
+        assert!(metadata.unknown_tokens.is_empty(), "{from} -> {to}");
+        assert!(metadata.normalized_spans.is_empty(), "{from} -> {to}");
+    }
+
+    // Normalization still reads and writes the text back
+    assert_eq!(shlesha.normalize("क\u{093C}", "deva").unwrap(), "\u{0958}");
+}
@@ -0,0 +1,79 @@
+//! The Devanagari schema `Shlesha::new` loads from the working directory
+//!
+//! The working directory is shared by the whole process, so the tests that
+//! change it hold `CWD` while they run.
+
+use shlesha::{Shlesha, ShleshaError};
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+static CWD: Mutex<()> = Mutex::new(());
+
+/// Run `test` with a fresh temporary directory as the working directory
+fn in_temp_dir(test: impl FnOnce(&Path)) {
+    let _guard = CWD.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let previous = env::current_dir().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    env::set_current_dir(dir.path()).unwrap();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| test(dir.path())));
+    env::set_current_dir(previous).unwrap();
+    if let Err(panic) = result {
+        std::panic::resume_unwind(panic);
+    }
+}
+
+fn write_corrupt_schema(dir: &Path) {
+    fs::create_dir(dir.join("schemas")).unwrap();
+    fs::write(
+        dir.join("schemas/devanagari.yaml"),
+        "metadata:\n  name: [unclosed\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_new_keeps_the_error_of_a_corrupt_schema() {
+    in_temp_dir(|dir| {
+        write_corrupt_schema(dir);
+        let shlesha = Shlesha::new();
+        let [warning] = shlesha.initialization_warnings() else {
+            panic!("{:?}", shlesha.initialization_warnings());
+        };
+        assert!(matches!(warning, ShleshaError::Schema(_)), "{warning:?}");
+        assert!(warning.to_string().contains("devanagari.yaml"), "{warning}");
+        // The compiled-in converters still cover Devanagari
+        assert_eq!(
+            shlesha.transliterate("धर्म", "devanagari", "iast").unwrap(),
+            "dharma"
+        );
+    });
+}
+
+#[test]
+fn test_builder_fails_on_a_corrupt_schema() {
+    in_temp_dir(|dir| {
+        write_corrupt_schema(dir);
+        let error = Shlesha::builder().build().err().unwrap();
+        assert!(matches!(error, ShleshaError::Schema(_)), "{error:?}");
+        assert!(error.to_string().contains("devanagari.yaml"), "{error}");
+    });
+}
+
+#[test]
+fn test_missing_schema_is_not_a_warning() {
+    in_temp_dir(|_| {
+        assert!(Shlesha::new().initialization_warnings().is_empty());
+        assert!(Shlesha::builder().build().is_ok());
+    });
+}
+
+#[test]
+fn test_bundled_schema_loads_without_warnings() {
+    let _guard = CWD.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    env::set_current_dir(env!("CARGO_MANIFEST_DIR")).unwrap();
+    assert!(Path::new("schemas/devanagari.yaml").exists());
+    assert!(Shlesha::new().initialization_warnings().is_empty());
+    assert!(Shlesha::builder().build().is_ok());
+}
@@ -0,0 +1,115 @@
+//! Malformed vowel sequences in Indic input, as OCR produces them
+
+use shlesha::{NormalizationKind, Shlesha, UnknownStage};
+
+#[test]
+fn test_ocr_vowel_sequences_are_repaired() {
+    let shlesha = Shlesha::new();
+
+    for (input, expected) in [
+        // Independent vowel + sign, the spelling of a vowel letter
+        ("अाम", "āma"),
+        ("अोम्", "om"),
+        ("अौषध", "auṣadha"),
+        ("आेम", "oma"),
+        ("एेरावत", "airāvata"),
+        // Independent vowel + sign spelling nothing: the sign is dropped
+        ("इीश", "iśa"),
+        // Doubled signs: the first is kept
+        ("कोे", "ko"),
+        ("रामाा", "rāmā"),
+        ("कििताब", "kitāba"),
+        ("कोेे", "ko"),
+    ] {
+        assert_eq!(
+            shlesha.transliterate(input, "devanagari", "iast").unwrap(),
+            expected,
+            "{input}"
+        );
+    }
+
+    // Other ISCII-layout scripts, and between Indic scripts
+    assert_eq!(
+        shlesha.transliterate("অাম", "bengali", "iast").unwrap(),
+        "āma"
+    );
+    assert_eq!(
+        shlesha.transliterate("ਅਾਪ", "gurmukhi", "iast").unwrap(),
+        "āpa"
+    );
+    assert_eq!(
+        shlesha.transliterate("कोे", "devanagari", "telugu").unwrap(),
+        "కో"
+    );
+
+    // A decomposed vowel sign is not a doubled sign
+    let decomposed = "\u{0995}\u{09C7}\u{09BE}";
+    let metadata = shlesha
+        .transliterate_with_metadata(decomposed, "bengali", "iast")
+        .unwrap()
+        .metadata
+        .unwrap();
+    assert!(metadata.normalized_spans.is_empty());
+}
+
+#[test]
+fn test_repairs_are_reported_with_the_original_bytes() {
+    let shlesha = Shlesha::new();
+
+    let result = shlesha
+        .transliterate_with_metadata("कोेQ अाम", "devanagari", "iast")
+        .unwrap();
+    assert_eq!(result.output, "koQ āma");
+    assert_eq!(
+        result.output,
+        shlesha
+            .transliterate("कोेQ अाम", "devanagari", "iast")
+            .unwrap()
+    );
+
+    let metadata = result.metadata.unwrap();
+    let spans: Vec<_> = metadata
+        .normalized_spans
+        .iter()
+        .map(|span| {
+            (
+                span.kind,
+                span.input.as_str(),
+                span.read_as.as_str(),
+                span.position,
+            )
+        })
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            (NormalizationKind::RepairedSequence, "ोे", "ो", "क".len()),
+            (NormalizationKind::RepairedSequence, "अा", "आ", "कोेQ ".len()),
+        ]
+    );
+
+    // Unknown tokens keep their position in the input as given
+    let unknowns: Vec<_> = metadata
+        .unknowns_in_stage(UnknownStage::SourceParsing)
+        .map(|token| (token.token, token.position))
+        .collect();
+    assert_eq!(unknowns, [('Q', "कोे".len())]);
+}
+
+#[test]
+fn test_repair_can_be_turned_off() {
+    let mut shlesha = Shlesha::new();
+    assert!(shlesha.repair_input());
+    shlesha.set_repair_input(false);
+
+    assert_eq!(
+        shlesha.transliterate("अा", "devanagari", "iast").unwrap(),
+        "aā"
+    );
+    let metadata = shlesha
+        .transliterate_with_metadata("कोे", "devanagari", "iast")
+        .unwrap()
+        .metadata
+        .unwrap();
+    assert!(metadata.normalized_spans.is_empty());
+}
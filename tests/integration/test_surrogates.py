@@ -0,0 +1,41 @@
+#!/usr/bin/env python3
+"""
+Text with lone surrogates, as surrogateescape decoding produces for bytes that
+are not UTF-8. Needs shlesha installed (maturin develop --features python).
+"""
+
+import shlesha
+
+# "क" and "म" around the byte 0xFF, which surrogateescape reads as U+DCFF
+ESCAPED = b"\xe0\xa4\x95\xff\xe0\xa4\xae".decode("utf-8", "surrogateescape")
+
+
+def test_lone_surrogate_is_read_as_replacement_character():
+    assert ESCAPED == "क\udcffम"
+    transliterator = shlesha.Shlesha()
+    assert transliterator.transliterate(ESCAPED, "devanagari", "iast") == "ka�ma"
+    assert transliterator.transliterate_fast(ESCAPED, "devanagari", "iast") == "ka�ma"
+    assert shlesha.transliterate(ESCAPED, "devanagari", "iast") == "ka�ma"
+
+
+def test_lone_surrogate_is_reported_as_invalid_encoding():
+    result = shlesha.Shlesha().transliterate_with_metadata(ESCAPED, "devanagari", "iast")
+    assert result.output == "ka�ma"
+    [token] = result.metadata.unknown_tokens
+    assert token.token == "�"
+    assert token.kind == "invalid_encoding"
+    assert token.stage == "source_parsing"
+    # A byte offset into the converted text, where "क" takes three bytes
+    assert token.position == 3
+
+
+def test_unmapped_characters_keep_their_kind():
+    result = shlesha.Shlesha().transliterate_with_metadata("धर्मk", "devanagari", "iast")
+    assert [token.kind for token in result.metadata.unknown_tokens] == ["unmapped"]
+
+
+def test_each_escaped_byte_is_one_replacement_character():
+    # An encoded surrogate, which UTF-8 forbids, escapes as three bytes
+    escaped = b"\xed\xa0\x80".decode("utf-8", "surrogateescape")
+    output = shlesha.transliterate(escaped, "iast", "slp1")
+    assert output == "�" * 3
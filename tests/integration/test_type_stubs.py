@@ -0,0 +1,97 @@
+#!/usr/bin/env python3
+"""
+Checks shlesha.pyi against the built module, and type-checks a typed example
+against it with mypy. Needs shlesha installed (maturin develop --features python).
+"""
+
+import ast
+import inspect
+import shutil
+from pathlib import Path
+
+import pytest
+
+import shlesha
+
+PROJECT_ROOT = Path(__file__).parent.parent.parent
+STUB = PROJECT_ROOT / "shlesha.pyi"
+EXAMPLE = Path(__file__).parent / "typed_example.py"
+
+
+def stub_definitions():
+    """The stub's module-level definitions, by name
+
+    TypedDicts are left out: they describe dicts and exist only in the stub.
+    """
+    definitions = {}
+    for node in ast.parse(STUB.read_text(encoding="utf-8")).body:
+        if isinstance(node, ast.ClassDef):
+            if not any(getattr(base, "id", None) == "TypedDict" for base in node.bases):
+                definitions[node.name] = node
+        elif isinstance(node, ast.FunctionDef):
+            definitions[node.name] = node
+        elif isinstance(node, ast.AnnAssign):
+            definitions[node.target.id] = node
+        elif isinstance(node, ast.Assign):
+            definitions.update((target.id, node) for target in node.targets)
+    return definitions
+
+
+def public(names):
+    return {name for name in names if not name.startswith("_")}
+
+
+def check_signature(stub, runtime, where):
+    parameters = [argument.arg for argument in stub.args.args]
+    assert list(inspect.signature(runtime).parameters) == parameters, where
+
+
+def test_stub_lists_the_module():
+    definitions = stub_definitions()
+    # The extension is a submodule of the package maturin builds
+    assert public(definitions) == public(dir(shlesha)) - {"shlesha"}
+    for name in definitions.keys() - public(definitions):
+        assert hasattr(shlesha, name), name
+
+
+def test_stub_lists_each_class():
+    for name, node in stub_definitions().items():
+        if not isinstance(node, ast.ClassDef):
+            continue
+        runtime = getattr(shlesha, name)
+        members = {member.name: member for member in node.body if isinstance(member, ast.FunctionDef)}
+        assert public(members) == public(vars(runtime)), name
+
+        for member_name in public(members):
+            member = members[member_name]
+            if not member.decorator_list:
+                check_signature(member, getattr(runtime, member_name), (name, member_name))
+
+
+def test_stub_function_signatures():
+    for name, node in stub_definitions().items():
+        if isinstance(node, ast.FunctionDef):
+            check_signature(node, getattr(shlesha, name), name)
+
+
+def test_typed_example_passes_mypy(tmp_path, monkeypatch):
+    api = pytest.importorskip("mypy.api")
+
+    # Check this checkout's stub rather than whatever is installed
+    package = tmp_path / "shlesha"
+    package.mkdir()
+    shutil.copy(STUB, package / "__init__.pyi")
+    (package / "py.typed").touch()
+    monkeypatch.setenv("MYPYPATH", str(tmp_path))
+
+    def mypy(*args):
+        return api.run(
+            ["--strict", "--no-incremental", "--python-version", "3.9", "--config-file", "", *args]
+        )
+
+    stdout, stderr, status = mypy(str(EXAMPLE))
+    assert status == 0, stdout + stderr
+
+    # The stub is not Any all the way down
+    stdout, _, status = mypy("-c", "import shlesha\nshlesha.transliterate(1, 'iast', 'slp1')")
+    assert status == 1 and "incompatible type" in stdout, stdout
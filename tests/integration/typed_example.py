@@ -0,0 +1,41 @@
+"""Typed use of shlesha, type-checked with mypy --strict by test_type_stubs.py"""
+
+from typing import TYPE_CHECKING, Optional
+
+import shlesha
+
+if TYPE_CHECKING:
+    from shlesha import SchemaInfo, Stats
+
+
+def romanize(text: str) -> str:
+    return shlesha.transliterate(text, "devanagari", "iast")
+
+
+def unknown_positions(transliterator: shlesha.Shlesha, text: str) -> list[int]:
+    result = transliterator.transliterate_with_metadata(text, "devanagari", "iast")
+    if result.metadata is None:
+        return []
+    return [token.position for token in result.metadata.unknown_tokens]
+
+
+def mapping_count(transliterator: shlesha.Shlesha, name: str) -> int:
+    info: Optional[SchemaInfo] = transliterator.get_schema_info(name)
+    return 0 if info is None else info["mapping_count"]
+
+
+def conversions(transliterator: shlesha.Shlesha) -> int:
+    stats: Stats = transliterator.stats()
+    return sum(stats["pairs"].values())
+
+
+def main() -> None:
+    transliterator = shlesha.create_transliterator()
+    print(romanize("धर्म"), transliterator.transliterate_fast("धर्म", "devanagari", "slp1"))
+    print(unknown_positions(transliterator, "धर्मkr"))
+    print(mapping_count(transliterator, "devanagari"), conversions(transliterator))
+    print(sorted(shlesha.get_supported_scripts()), shlesha.__output_version__)
+
+
+if __name__ == "__main__":
+    main()
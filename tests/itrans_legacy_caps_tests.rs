@@ -0,0 +1,114 @@
+//! ALL CAPS legacy ITRANS read with the `itrans_legacy_caps` option
+
+use shlesha::{Shlesha, TransliterationOptions, UnknownStage};
+
+const CAPS_VERSE: &str = "YADAA YADAA HI DHARMASYA GLAANIRBHAVATI BHAARATA |
+ABHYUTTHAANAMADHARMASYA TADAATMAANAM SRJAAMYAHAM ||";
+
+const CASED_VERSE: &str = "yadA yadA hi dharmasya glAnirbhavati bhArata |
+abhyutthAnamadharmasya tadAtmAnam sRjAmyaham ||";
+
+fn legacy_caps(text: &str) -> String {
+    Shlesha::new()
+        .transliterate_with_options(
+            text,
+            "itrans",
+            "devanagari",
+            &TransliterationOptions::itrans_legacy_caps(),
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_caps_verse_matches_cased_verse() {
+    let shlesha = Shlesha::new();
+    let expected = shlesha
+        .transliterate(CASED_VERSE, "itrans", "devanagari")
+        .unwrap();
+    assert_eq!(legacy_caps(CAPS_VERSE), expected);
+    assert!(expected.starts_with("यदा यदा हि धर्मस्य"));
+
+    // Strict reading is the default: "A" is ā and "DH" is not dh
+    assert_ne!(
+        shlesha
+            .transliterate(CAPS_VERSE, "itrans", "devanagari")
+            .unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_capitals_recovered_from_context() {
+    // Final visarga, vocalic r between consonants, anusvara before a sibilant
+    assert_eq!(legacy_caps("DHARMAH"), "धर्मः");
+    assert_eq!(legacy_caps("AHAM PITR"), "अहम् पितृ");
+    assert_eq!(legacy_caps("SAMSKRTAM"), "संस्कृतम्");
+    // kṣ and jñ, since kś and jn do not occur
+    assert_eq!(legacy_caps("KSHETRE JNAANAM"), "क्षेत्रे ज्ञानम्");
+}
+
+#[test]
+fn test_only_caps_words_are_recased() {
+    let shlesha = Shlesha::new();
+    let options = TransliterationOptions::itrans_legacy_caps();
+
+    // In mixed-case text a lone capital is properly-cased ITRANS
+    let result = shlesha
+        .transliterate_with_options_and_metadata(
+            "rAmaH A DHARMAH",
+            "itrans",
+            "devanagari",
+            &options,
+        )
+        .unwrap();
+    assert_eq!(result.output, "रामः आ धर्मः");
+
+    let metadata = result.metadata.unwrap();
+    assert_eq!(metadata.recased_words.len(), 1);
+    let word = &metadata.recased_words[0];
+    assert_eq!(word.word, "DHARMAH");
+    assert_eq!(word.read_as, "dharmaH");
+    assert_eq!(word.position, 8);
+
+    // Nothing to re-case, nothing recorded
+    let result = shlesha
+        .transliterate_with_options_and_metadata("dharmaH", "i-trans", "devanagari", &options)
+        .unwrap();
+    assert!(result.metadata.unwrap().recased_words.is_empty());
+}
+
+#[test]
+fn test_unknown_positions_refer_to_original_text() {
+    // JN is read as the longer j~n
+    let result = Shlesha::new()
+        .transliterate_with_options_and_metadata(
+            "JNAANAM ☺",
+            "itrans",
+            "devanagari",
+            &TransliterationOptions::itrans_legacy_caps(),
+        )
+        .unwrap();
+    assert_eq!(result.output, "ज्ञानम् ☺");
+
+    let metadata = result.metadata.unwrap();
+    let unknown: Vec<_> = metadata
+        .unknowns_in_stage(UnknownStage::SourceParsing)
+        .collect();
+    assert_eq!(unknown.len(), 1);
+    assert_eq!(unknown[0].token, '☺');
+    assert_eq!(unknown[0].position, 8);
+}
+
+#[test]
+fn test_option_applies_only_to_itrans() {
+    let shlesha = Shlesha::new();
+    let options = TransliterationOptions::itrans_legacy_caps();
+    for (text, from) in [("RAAMA", "harvard_kyoto"), ("DHARMA", "slp1")] {
+        assert_eq!(
+            shlesha
+                .transliterate_with_options(text, from, "devanagari", &options)
+                .unwrap(),
+            shlesha.transliterate(text, from, "devanagari").unwrap()
+        );
+    }
+}
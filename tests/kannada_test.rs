@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod kannada_tests {
+    use shlesha::Shlesha;
+
+    #[test]
+    fn test_tamil_zh_and_rr_roundtrip_through_kannada() {
+        let transliterator = Shlesha::new();
+
+        for (tamil, kannada) in [("தமிழ்", "ತಮಿೞ್"), ("ஆறு", "ಆಱು"), ("கள்", "ಕಳ್")]
+        {
+            let result = transliterator
+                .transliterate(tamil, "tamil", "kannada")
+                .unwrap();
+            assert_eq!(result, kannada, "Tamil {tamil} → Kannada");
+
+            let back = transliterator
+                .transliterate(&result, "kannada", "tamil")
+                .unwrap();
+            assert_eq!(back, tamil, "Kannada {kannada} → Tamil");
+        }
+    }
+
+    #[test]
+    fn test_old_kannada_to_roman() {
+        let transliterator = Shlesha::new();
+        let old_kannada = "ಪೞಗನ್ನಡ ಕಱು ಬಾಳ";
+
+        let iso = transliterator
+            .transliterate(old_kannada, "kannada", "iso15919")
+            .unwrap();
+        assert_eq!(iso, "paḻagannaḍa kaṟu bāḷa");
+        let back = transliterator
+            .transliterate(&iso, "iso15919", "kannada")
+            .unwrap();
+        assert_eq!(back, old_kannada);
+
+        // ḻ is ḷa in IAST, so ḻa is written with a dot above as ṙ is for ड़
+        let iast = transliterator
+            .transliterate(old_kannada, "kannada", "iast")
+            .unwrap();
+        assert_eq!(iast, "pal\u{0307}agannaḍa kaṟu bāḻa");
+        let back = transliterator
+            .transliterate(&iast, "iast", "kannada")
+            .unwrap();
+        assert_eq!(back, old_kannada);
+    }
+
+    #[test]
+    fn test_archaic_letters_to_devanagari() {
+        let transliterator = Shlesha::new();
+        let result = transliterator
+            .transliterate("ೞ ಱ", "kannada", "devanagari")
+            .unwrap();
+        assert_eq!(result, "ऴ ऱ");
+    }
+}
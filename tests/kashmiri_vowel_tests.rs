@@ -0,0 +1,59 @@
+//! Kashmiri vowels ॳ ॴ ॶ ॷ and their signs, in Devanagari, Sharada and Roman
+
+use shlesha::Shlesha;
+
+/// Kashmiri spellings in Devanagari, Sharada and ISO-15919
+const WORDS: &[(&str, &str, &str)] = &[
+    ("ॳस", "𑆃𑇋𑆱", "ọsa"),
+    ("ॴठ", "𑆄𑇋𑆜", "ọ̄ṭha"),
+    ("ॶन", "𑆇𑇋𑆤", "ụna"),
+    ("ॷर", "𑆈𑇋𑆫", "ụ̄ra"),
+    ("कऺर", "𑆑𑇋𑆫", "kọra"),
+    ("गऻर", "𑆓𑆳𑇋𑆫", "gọ̄ra"),
+    ("दॖ", "𑆢𑆶𑇋", "dụ"),
+    ("त्रॗ", "𑆠𑇀𑆫𑆷𑇋", "trụ̄"),
+];
+
+#[test]
+fn test_kashmiri_words_roundtrip() {
+    let shlesha = Shlesha::new();
+    for (devanagari, sharada, iso) in WORDS {
+        for (text, script) in [
+            (*devanagari, "devanagari"),
+            (*sharada, "sharada"),
+            (*iso, "iso15919"),
+        ] {
+            for (expected, to) in [
+                (*devanagari, "devanagari"),
+                (*sharada, "sharada"),
+                (*iso, "iso15919"),
+            ] {
+                if to == script {
+                    continue;
+                }
+                assert_eq!(
+                    shlesha.transliterate(text, script, to).unwrap(),
+                    expected,
+                    "{text} from {script} to {to}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_iast_and_decomposed_input() {
+    let shlesha = Shlesha::new();
+
+    assert_eq!(
+        shlesha.transliterate("ॴठ कॖ", "devanagari", "iast").unwrap(),
+        "ọ̄ṭha kụ"
+    );
+    // The dot below and macron typed as combining marks read the same
+    assert_eq!(
+        shlesha
+            .transliterate("o\u{323}\u{304}ṭha ku\u{323}", "iso15919", "devanagari")
+            .unwrap(),
+        "ॴठ कॖ"
+    );
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod kharoshthi_tests {
+    use shlesha::modules::core::unknown_handler::UnknownStage;
+    use shlesha::Shlesha;
+
+    #[test]
+    fn test_kharoshthi_from_iast() {
+        let transliterator = Shlesha::new();
+
+        let result = transliterator
+            .transliterate("dharmakṣetre kurukṣetre", "iast", "kharoshthi")
+            .unwrap();
+        assert_eq!(result, "𐨢𐨪𐨿𐨨𐨐𐨿𐨮𐨅𐨟𐨿𐨪𐨅 𐨐𐨂𐨪𐨂𐨐𐨿𐨮𐨅𐨟𐨿𐨪𐨅");
+
+        // Independent vowels are A with a vowel sign, long ones add the length mark
+        assert_eq!(
+            transliterator
+                .transliterate("a ā i ī u ū ṛ ṝ e o", "iast", "khar")
+                .unwrap(),
+            "𐨀 𐨀𐨌 𐨀𐨁 𐨀𐨁𐨌 𐨀𐨂 𐨀𐨂𐨌 𐨀𐨃 𐨀𐨃𐨌 𐨀𐨅 𐨀𐨆"
+        );
+        assert_eq!(
+            transliterator
+                .transliterate("kā kī kū śrīḥ saṁgha", "iast", "kharoshthi")
+                .unwrap(),
+            "𐨐𐨌 𐨐𐨁𐨌 𐨐𐨂𐨌 𐨭𐨿𐨪𐨁𐨌𐨏 𐨯𐨎𐨓"
+        );
+    }
+
+    #[test]
+    fn test_kharoshthi_stays_in_logical_order() {
+        let transliterator = Shlesha::new();
+
+        // Letters are written in reading order with no bidi controls added, even
+        // next to left-to-right digits
+        let result = transliterator
+            .transliterate("dharma (5)", "iast", "kharoshthi")
+            .unwrap();
+        assert_eq!(result, "𐨢𐨪𐨿𐨨 (5)");
+        assert!(!result.chars().any(|c| matches!(
+            c,
+            '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+        )));
+    }
+
+    #[test]
+    fn test_kharoshthi_merges_reported_as_lossy() {
+        let transliterator = Shlesha::new();
+
+        let result = transliterator
+            .transliterate_with_metadata("kai gaṅgā", "iast", "kharoshthi")
+            .unwrap();
+        assert_eq!(result.output, "𐨐𐨅 𐨒𐨣𐨿𐨒𐨌");
+        let metadata = result.metadata.unwrap();
+        let merged: Vec<(&str, &str)> = metadata
+            .lossy_mappings
+            .iter()
+            .map(|m| (m.token.as_str(), m.substitute.as_str()))
+            .collect();
+        assert_eq!(
+            merged,
+            [
+                ("VowelSignAi", "VowelSignEe"),
+                ("ConsonantNg", "ConsonantNn")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_kharoshthi_has_no_positional_digits() {
+        let transliterator = Shlesha::new();
+
+        // Digits pass through as ASCII rather than as placeholders
+        let result = transliterator
+            .transliterate_with_metadata("१०८", "devanagari", "kharoshthi")
+            .unwrap();
+        assert_eq!(result.output, "108");
+        let metadata = result.metadata.unwrap();
+        assert_eq!(metadata.unknown_tokens.len(), 3);
+        assert!(metadata
+            .unknown_tokens
+            .iter()
+            .all(|token| token.stage == UnknownStage::TargetRendering));
+
+        // The additive numerals (4 + 1) have no tokens and pass through unchanged
+        assert_eq!(
+            transliterator
+                .transliterate("𐩃𐩀", "kharoshthi", "iast")
+                .unwrap(),
+            "𐩃𐩀"
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Schemas in the older format, mapping characters to Devanagari or ISO-15919
+//! text, are translated to token mappings when loaded
+
+use shlesha::Shlesha;
+
+#[test]
+fn test_legacy_roman_schema_converts() {
+    let yaml = r#"
+metadata:
+  name: "legacy_roman"
+  script_type: "roman"
+  has_implicit_a: false
+target: "iso15919"
+mappings:
+  vowels:
+    "a": "a"
+    "aa": "ā"
+    "i": "i"
+    "RRi": "r̥"
+  consonants:
+    "k": "k"
+    "dh": "dh"
+    "r": "r"
+    "m": "m"
+    "sh": "ś"
+  marks:
+    "M": "ṁ"
+"#;
+    let mut shlesha = Shlesha::new();
+    shlesha.load_schema_from_string(yaml, "").unwrap();
+
+    assert_eq!(
+        shlesha
+            .transliterate("dharma shaakaM kRRi", "legacy_roman", "devanagari")
+            .unwrap(),
+        "धर्म शाकं कृ"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("dharma", "legacy_roman", "iast")
+            .unwrap(),
+        "dharma"
+    );
+}
+
+#[test]
+fn test_legacy_brahmic_schema_converts() {
+    // Target defaults to Devanagari for Brahmic scripts
+    let yaml = r#"
+metadata:
+  name: "legacy_tamil"
+  script_type: "brahmic"
+  has_implicit_a: true
+mappings:
+  vowels:
+    "அ": "अ"
+  consonants:
+    "க": "क"
+    "ம": "म"
+    "ர": "र"
+  vowel_signs:
+    "ா": "ा"
+  marks:
+    "்": "्"
+"#;
+    let mut shlesha = Shlesha::new();
+    shlesha.load_schema_from_string(yaml, "").unwrap();
+
+    assert_eq!(
+        shlesha
+            .transliterate("கர்ம காம", "legacy_tamil", "iast")
+            .unwrap(),
+        "karma kāma"
+    );
+}
+
+#[test]
+fn test_token_schema_with_reference_target_is_unchanged() {
+    // Token names as keys are read as the current format whatever the target says
+    let yaml = r#"
+metadata:
+  name: "tokens_with_old_target"
+  script_type: "roman"
+  has_implicit_a: false
+target: "iso15919"
+mappings:
+  vowels:
+    VowelA: "a"
+    VowelAa: "A"
+  consonants:
+    ConsonantK: "k"
+"#;
+    let mut shlesha = Shlesha::new();
+    shlesha.load_schema_from_string(yaml, "").unwrap();
+    assert_eq!(
+        shlesha
+            .transliterate("kAka", "tokens_with_old_target", "devanagari")
+            .unwrap(),
+        "काक"
+    );
+}
+
+#[test]
+fn test_legacy_mapping_to_several_characters_is_rejected() {
+    let yaml = r#"
+metadata:
+  name: "legacy_conjunct"
+  script_type: "brahmic"
+  has_implicit_a: true
+target: "devanagari"
+mappings:
+  consonants:
+    "க": "क"
+    "க்ஷ": "क्ष"
+"#;
+    let mut shlesha = Shlesha::new();
+    let error = shlesha
+        .load_schema_from_string(yaml, "")
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains(
+            "Legacy mapping \"க்ஷ\": \"क्ष\" is not a single Devanagari or ISO-15919 character"
+        ),
+        "{error}"
+    );
+    assert!(error.contains("target: abugida_tokens"), "{error}");
+}
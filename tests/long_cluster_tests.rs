@@ -0,0 +1,143 @@
+//! Long chains of consonants joined by viramas
+//!
+//! Real words have clusters of up to five consonants (kārtsnya), but input can
+//! have any number: tongue-twisters, synthetic test data, or an adversarial
+//! र्र्र्… Conversion time must grow linearly with the length of the chain.
+
+use shlesha::Shlesha;
+use std::time::{Duration, Instant};
+
+/// Conversion of a 10,000-virama chain; debug builds are much slower
+const MAX_CHAIN_MILLIS: u128 = if cfg!(debug_assertions) { 2000 } else { 200 };
+
+/// How much longer a chain ten times as long may take
+const MAX_SCALING: f64 = 12.0;
+
+/// IAST words with long clusters and their Devanagari spellings
+const WORDS: &[(&str, &str)] = &[
+    ("kārtsnya", "कार्त्स्न्य"),
+    ("kārtsnyena", "कार्त्स्न्येन"),
+    ("ārtsnyam", "आर्त्स्न्यम्"),
+    ("mātsnya", "मात्स्न्य"),
+];
+
+/// Inputs whose chains are `n` consonants long, with their source scripts
+fn chains(n: usize) -> Vec<(String, &'static str, &'static str)> {
+    vec![
+        ("र्".repeat(n) + "र", "devanagari", "iast"),
+        ("r".repeat(n) + "a", "iast", "devanagari"),
+        ("rtsny".repeat(n / 5) + "a", "iast", "telugu"),
+        ("ক্ষ্".repeat(n / 2) + "ক", "bengali", "malayalam"),
+    ]
+}
+
+#[test]
+fn test_long_clusters_convert_correctly() {
+    let shlesha = Shlesha::new();
+    for (iast, devanagari) in WORDS {
+        assert_eq!(
+            shlesha.transliterate(iast, "iast", "devanagari").unwrap(),
+            *devanagari
+        );
+        assert_eq!(
+            shlesha
+                .transliterate(devanagari, "devanagari", "iast")
+                .unwrap(),
+            *iast
+        );
+        for script in ["bengali", "telugu", "grantha", "sinhala", "slp1"] {
+            let written = shlesha
+                .transliterate(devanagari, "devanagari", script)
+                .unwrap();
+            assert_eq!(
+                shlesha
+                    .transliterate(&written, script, "devanagari")
+                    .unwrap(),
+                *devanagari,
+                "{iast} through {script}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_virama_chain_converts_within_budget() {
+    let shlesha = Shlesha::new();
+    let chain = "र्".repeat(10_000) + "र";
+
+    let start = Instant::now();
+    let output = shlesha.transliterate(&chain, "devanagari", "iast").unwrap();
+    let elapsed = start.elapsed();
+    assert_eq!(output, "r".repeat(10_000) + "ra");
+    assert_eq!(
+        shlesha
+            .transliterate(&output, "iast", "devanagari")
+            .unwrap(),
+        chain
+    );
+
+    #[cfg(not(tarpaulin))]
+    assert!(
+        elapsed.as_millis() < MAX_CHAIN_MILLIS,
+        "a 10,000-virama chain took {}ms, expected < {}ms",
+        elapsed.as_millis(),
+        MAX_CHAIN_MILLIS
+    );
+}
+
+/// How many times longer `convert` takes on the 10,000-consonant input than the 1,000
+///
+/// Each takes the quickest of several runs, alternating between the two so
+/// that tests running alongside slow both down alike. A linear conversion
+/// measures near 10x but other tests can still push one trial past
+/// [`MAX_SCALING`], so this is the lowest of three trials: a quadratic one
+/// measures near 100x in every trial.
+fn scaling(short: &str, long: &str, convert: impl Fn(&str)) -> f64 {
+    let time = |text: &str| {
+        let start = Instant::now();
+        convert(text);
+        start.elapsed()
+    };
+    let trial = || {
+        let (mut short_time, mut long_time) = (Duration::MAX, Duration::MAX);
+        for _ in 0..5 {
+            short_time = short_time.min(time(short));
+            long_time = long_time.min(time(long));
+        }
+        long_time.as_secs_f64() / short_time.as_secs_f64()
+    };
+    (0..3).map(|_| trial()).fold(f64::INFINITY, f64::min)
+}
+
+#[cfg(not(tarpaulin))]
+#[test]
+fn test_conversion_time_grows_linearly_with_chain_length() {
+    let shlesha = Shlesha::new();
+    for ((short, from, to), (long, ..)) in chains(1_000).into_iter().zip(chains(10_000)) {
+        let plain = scaling(&short, &long, |text| {
+            shlesha.transliterate(text, from, to).unwrap();
+        });
+        let with_metadata = scaling(&short, &long, |text| {
+            shlesha.transliterate_with_metadata(text, from, to).unwrap();
+        });
+        for (conversion, scaling) in [("plain", plain), ("with metadata", with_metadata)] {
+            assert!(
+                scaling <= MAX_SCALING,
+                "{from} to {to} ({conversion}): 10x the chain took {scaling:.1}x as long"
+            );
+        }
+    }
+
+    // Normalizing composes each consonant and separate nukta into one letter
+    let scaling = scaling(
+        &"क\u{93C}्".repeat(1_000),
+        &"क\u{93C}्".repeat(10_000),
+        |text| {
+            shlesha.normalize(text, "devanagari").unwrap();
+        },
+    );
+    assert!(
+        scaling <= MAX_SCALING,
+        "normalizing 10x the chain took {scaling:.1}x as long"
+    );
+}
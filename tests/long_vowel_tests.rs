@@ -57,11 +57,11 @@ mod long_vowel_tests {
     fn test_iso_to_deva_long_vowels() {
         let transliterator = Shlesha::new();
 
-        // Devanagari has been updated to distinguish short/long e/o
+        // Devanagari merges short e/o into the long forms
         let result = transliterator
             .transliterate("e", "iso15919", "devanagari")
             .unwrap();
-        assert_eq!(result, "ऎ"); // Short e
+        assert_eq!(result, "ए"); // Short e
 
         let result = transliterator
             .transliterate("ē", "iso15919", "devanagari")
@@ -71,7 +71,7 @@ mod long_vowel_tests {
         let result = transliterator
             .transliterate("o", "iso15919", "devanagari")
             .unwrap();
-        assert_eq!(result, "ऒ"); // Short o
+        assert_eq!(result, "ओ"); // Short o
 
         let result = transliterator
             .transliterate("ō", "iso15919", "devanagari")
@@ -108,6 +108,80 @@ mod long_vowel_tests {
         assert_eq!(back_to_iso, iso_text);
     }
 
+    #[test]
+    fn test_telugu_to_iast_short_long_distinction() {
+        let transliterator = Shlesha::new();
+
+        let result = transliterator
+            .transliterate("ఎ ఏ కె కే ఒ ఓ కొ కో", "telugu", "iast")
+            .unwrap();
+        assert_eq!(result, "ĕ e kĕ ke ŏ o kŏ ko");
+
+        let back = transliterator
+            .transliterate(&result, "iast", "telugu")
+            .unwrap();
+        assert_eq!(back, "ఎ ఏ కె కే ఒ ఓ కొ కో");
+    }
+
+    #[test]
+    fn test_telugu_to_devanagari_merges_short_e_o() {
+        let transliterator = Shlesha::new();
+
+        let result = transliterator
+            .transliterate_with_metadata("ఎ ఏ కె కే ఒ ఓ కొ కో", "telugu", "devanagari")
+            .unwrap();
+        assert_eq!(result.output, "ए ए के के ओ ओ को को");
+
+        // Every short vowel written as its long counterpart is recorded
+        let metadata = result.metadata.unwrap();
+        assert!(metadata.is_lossy());
+        let merged: Vec<(&str, &str)> = metadata
+            .lossy_mappings
+            .iter()
+            .map(|m| (m.token.as_str(), m.substitute.as_str()))
+            .collect();
+        assert_eq!(
+            merged,
+            vec![
+                ("VowelE", "VowelEe"),
+                ("VowelSignE", "VowelSignEe"),
+                ("VowelO", "VowelOo"),
+                ("VowelSignO", "VowelSignOo"),
+            ]
+        );
+        assert!(metadata
+            .lossy_mappings
+            .iter()
+            .all(|m| m.script == "devanagari"));
+
+        // Long vowels alone are not lossy
+        let result = transliterator
+            .transliterate_with_metadata("ఏకమ్", "telugu", "devanagari")
+            .unwrap();
+        assert!(!result.metadata.unwrap().is_lossy());
+    }
+
+    #[test]
+    fn test_telugu_devanagari_round_trip_is_lossy() {
+        let transliterator = Shlesha::new();
+
+        // Short vowels cannot survive a trip through Devanagari: they come back long
+        let devanagari = transliterator
+            .transliterate("కెరళ", "telugu", "devanagari")
+            .unwrap();
+        assert_eq!(devanagari, "केरळ");
+        let back = transliterator
+            .transliterate(&devanagari, "devanagari", "telugu")
+            .unwrap();
+        assert_eq!(back, "కేరళ");
+
+        // Devanagari input that does use ऎ/ॆ still reads as short e
+        let result = transliterator
+            .transliterate("कॆ", "devanagari", "telugu")
+            .unwrap();
+        assert_eq!(result, "కె");
+    }
+
     #[test]
     fn test_dependent_vowel_signs() {
         let transliterator = Shlesha::new();
@@ -136,4 +210,24 @@ mod long_vowel_tests {
             .unwrap();
         assert_eq!(result, "kō");
     }
+
+    #[test]
+    fn test_vocalic_l_signs_after_consonants() {
+        let mut transliterator = Shlesha::new();
+        for force_hub in [false, true] {
+            transliterator.set_force_hub(force_hub);
+            assert_eq!(
+                transliterator
+                    .transliterate("kḷ kḹ kḷpta", "iast", "devanagari")
+                    .unwrap(),
+                "कॢ कॣ कॢप्त"
+            );
+            assert_eq!(
+                transliterator
+                    .transliterate("kx kX", "slp1", "devanagari")
+                    .unwrap(),
+                "कॢ कॣ"
+            );
+        }
+    }
 }
@@ -0,0 +1,102 @@
+use shlesha::Shlesha;
+
+const TARGETS: &[&str] = &["bengali", "telugu", "gujarati", "kannada", "malayalam"];
+
+/// Devanagari words with an anusvara, visarga or candrabindu, and how each
+/// target writes them: the vowel sign, if any, comes before the mark
+const WORDS: &[(&str, [&str; 5])] = &[
+    (
+        "दुःख",
+        [
+            "\u{09A6}\u{09C1}\u{0983}\u{0996}",
+            "\u{0C26}\u{0C41}\u{0C03}\u{0C16}",
+            "\u{0AA6}\u{0AC1}\u{0A83}\u{0A96}",
+            "\u{0CA6}\u{0CC1}\u{0C83}\u{0C96}",
+            "\u{0D26}\u{0D41}\u{0D03}\u{0D16}",
+        ],
+    ),
+    (
+        "कों",
+        [
+            "\u{0995}\u{09CB}\u{0982}",
+            "\u{0C15}\u{0C4B}\u{0C02}",
+            "\u{0A95}\u{0ACB}\u{0A82}",
+            "\u{0C95}\u{0CCB}\u{0C82}",
+            "\u{0D15}\u{0D4B}\u{0D02}",
+        ],
+    ),
+    (
+        "अँश",
+        [
+            "\u{0985}\u{0981}\u{09B6}",
+            "\u{0C05}\u{0C01}\u{0C36}",
+            "\u{0A85}\u{0A81}\u{0AB6}",
+            "\u{0C85}\u{0C81}\u{0CB6}",
+            "\u{0D05}\u{0D01}\u{0D36}",
+        ],
+    ),
+];
+
+/// The words above with the mark typed before the vowel sign
+const MISORDERED: &[(&str, &str)] = &[("दःुख", "दुःख"), ("कंो", "कों")];
+
+#[test]
+fn test_vowel_signs_come_before_marks() {
+    let shlesha = Shlesha::new();
+    for (word, expected) in WORDS {
+        for (target, expected) in TARGETS.iter().zip(expected) {
+            let output = shlesha.transliterate(word, "devanagari", target).unwrap();
+            assert_eq!(&output, expected, "{word} → {target}");
+            let back = shlesha
+                .transliterate(&output, target, "devanagari")
+                .unwrap();
+            assert_eq!(&back, word, "{word} → {target} → devanagari");
+        }
+    }
+}
+
+#[test]
+fn test_marks_typed_before_the_vowel_sign_are_reordered() {
+    let shlesha = Shlesha::new();
+    for (misordered, word) in MISORDERED {
+        let expected = WORDS.iter().find(|(w, _)| w == word).unwrap().1;
+        for (target, expected) in TARGETS.iter().zip(expected) {
+            let output = shlesha
+                .transliterate(misordered, "devanagari", target)
+                .unwrap();
+            assert_eq!(output, expected, "{misordered} → {target}");
+            // Read back, the output keeps the canonical order
+            let back = shlesha
+                .transliterate(&output, target, "devanagari")
+                .unwrap();
+            assert_eq!(&back, word, "{misordered} → {target} → devanagari");
+            let again = shlesha.transliterate(&back, "devanagari", target).unwrap();
+            assert_eq!(again, output, "{misordered} → {target}, twice");
+        }
+    }
+}
+
+#[test]
+fn test_reordering_to_roman_on_every_path() {
+    let expected = [("दःुख", "duḥkha"), ("कंो", "koṁ"), ("कँा", "kām̐")];
+    for force_hub in [false, true] {
+        let mut shlesha = Shlesha::new();
+        shlesha.set_force_hub(force_hub);
+        for (misordered, roman) in expected {
+            let output = shlesha
+                .transliterate(misordered, "devanagari", "iast")
+                .unwrap();
+            assert_eq!(output, roman, "{misordered} (force_hub: {force_hub})");
+
+            // The streaming direct converter starts over on the token sequence
+            let mut buffer = String::from("stale");
+            shlesha
+                .transliterate_into(misordered, "devanagari", "iast", &mut buffer)
+                .unwrap();
+            assert_eq!(
+                buffer, roman,
+                "{misordered} into a buffer (force_hub: {force_hub})"
+            );
+        }
+    }
+}
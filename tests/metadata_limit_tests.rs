@@ -0,0 +1,110 @@
+use shlesha::{Shlesha, TransliterationOptions, UnknownStage, DEFAULT_MAX_UNKNOWN_TOKENS};
+
+fn with_limit(limit: Option<usize>) -> TransliterationOptions {
+    TransliterationOptions {
+        max_unknown_tokens: limit,
+        ..TransliterationOptions::default()
+    }
+}
+
+#[test]
+fn test_garbage_input_keeps_bounded_metadata() {
+    let shlesha = Shlesha::new();
+    let input = "☺".repeat(5 * DEFAULT_MAX_UNKNOWN_TOKENS);
+
+    let result = shlesha
+        .transliterate_with_metadata(&input, "devanagari", "iast")
+        .unwrap();
+    assert_eq!(result.output, input);
+    let metadata = result.metadata.unwrap();
+
+    assert_eq!(metadata.unknown_tokens.len(), DEFAULT_MAX_UNKNOWN_TOKENS);
+    assert_eq!(metadata.total_unknown_count, 5 * DEFAULT_MAX_UNKNOWN_TOKENS);
+    assert!(metadata.truncated);
+
+    // The tokens kept are the first ones
+    let last = metadata.unknown_tokens.last().unwrap();
+    assert_eq!(
+        last.position,
+        (DEFAULT_MAX_UNKNOWN_TOKENS - 1) * '☺'.len_utf8()
+    );
+}
+
+#[test]
+fn test_limit_applies_across_stages() {
+    let shlesha = Shlesha::new();
+    // One unknown from each stage per word: Grantha nukta ka has no SLP1
+    // spelling, ☺ is not Grantha and the Grantha Om has no alphabet token
+    let words = 40;
+    let input = vec!["𑌕𑌼 ☺ 𑍐"; words].join(" ");
+
+    let options = with_limit(Some(10));
+    let result = shlesha
+        .transliterate_with_options_and_metadata(&input, "grantha", "slp1", &options)
+        .unwrap();
+    assert_eq!(
+        result.output,
+        shlesha.transliterate(&input, "grantha", "slp1").unwrap()
+    );
+    let metadata = result.metadata.unwrap();
+
+    assert_eq!(metadata.unknown_tokens.len(), 10);
+    assert_eq!(metadata.total_unknown_count, 3 * words);
+    assert!(metadata.truncated);
+
+    // The same first ten as an unlimited conversion keeps
+    let unlimited = shlesha
+        .transliterate_with_options_and_metadata(&input, "grantha", "slp1", &with_limit(None))
+        .unwrap()
+        .metadata
+        .unwrap();
+    assert_eq!(unlimited.unknown_tokens.len(), 3 * words);
+    assert!(!unlimited.truncated);
+    let kept = |tokens: &[shlesha::UnknownToken]| -> Vec<(UnknownStage, usize)> {
+        tokens.iter().map(|t| (t.stage, t.position)).collect()
+    };
+    assert_eq!(
+        kept(&metadata.unknown_tokens),
+        kept(&unlimited.unknown_tokens[..10])
+    );
+}
+
+#[test]
+fn test_input_under_the_limit_is_not_truncated() {
+    let shlesha = Shlesha::new();
+
+    let metadata = shlesha
+        .transliterate_with_metadata("धर्म☺", "devanagari", "iast")
+        .unwrap()
+        .metadata
+        .unwrap();
+    assert_eq!(metadata.unknown_tokens.len(), 1);
+    assert_eq!(metadata.total_unknown_count, 1);
+    assert!(!metadata.truncated);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_limit_holds_for_parallel_chunks() {
+    let mut shlesha = Shlesha::new();
+    shlesha.set_parallel_threshold(64);
+    let input = vec!["☺☺☺☺"; 500].join(" ");
+
+    let metadata = shlesha
+        .transliterate_with_options_and_metadata(
+            &input,
+            "devanagari",
+            "iast",
+            &with_limit(Some(100)),
+        )
+        .unwrap()
+        .metadata
+        .unwrap();
+    assert_eq!(metadata.unknown_tokens.len(), 100);
+    assert_eq!(metadata.total_unknown_count, 2000);
+    assert!(metadata.truncated);
+    assert!(metadata
+        .unknown_tokens
+        .windows(2)
+        .all(|pair| pair[0].position < pair[1].position));
+}
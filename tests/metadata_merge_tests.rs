@@ -0,0 +1,77 @@
+use shlesha::modules::core::unknown_handler::TransliterationMetadata;
+use shlesha::{Shlesha, TransliterationOptions};
+
+/// Unknowns of every stage: nukta ka has no SLP1 spelling, ☺ is not Grantha
+/// and the Grantha Om has no alphabet token
+const TEXT: &str = "𑌕𑌼 ☺ 𑍐 𑌧𑌰𑍍𑌮 𑌕𑌼 ☺ 𑍐";
+
+fn metadata_of(shlesha: &Shlesha, text: &str) -> TransliterationMetadata {
+    limited_metadata_of(shlesha, text, None)
+}
+
+fn limited_metadata_of(
+    shlesha: &Shlesha,
+    text: &str,
+    limit: Option<usize>,
+) -> TransliterationMetadata {
+    let options = TransliterationOptions {
+        max_unknown_tokens: limit,
+        ..Default::default()
+    };
+    shlesha
+        .transliterate_with_options_and_metadata(text, "grantha", "slp1", &options)
+        .unwrap()
+        .metadata
+        .unwrap()
+}
+
+#[test]
+fn test_merged_pieces_match_the_whole() {
+    let shlesha = Shlesha::new();
+    let whole = metadata_of(&shlesha, TEXT);
+    assert_eq!(whole.unknown_tokens.len(), 6);
+
+    let spaces: Vec<usize> = TEXT.match_indices(' ').map(|(at, _)| at).collect();
+    for cuts in [vec![spaces[0]], vec![spaces[1], spaces[3]], spaces.clone()] {
+        let bounds: Vec<usize> = std::iter::once(0)
+            .chain(cuts)
+            .chain(std::iter::once(TEXT.len()))
+            .collect();
+        let parts: Vec<(usize, TransliterationMetadata)> = bounds
+            .windows(2)
+            .map(|range| (range[0], metadata_of(&shlesha, &TEXT[range[0]..range[1]])))
+            .collect();
+        let merged = TransliterationMetadata::merge(parts);
+
+        assert_eq!(merged.unknown_tokens, whole.unknown_tokens, "{bounds:?}");
+        assert_eq!(merged.total_unknown_count, whole.total_unknown_count);
+        assert_eq!(merged.source_token_count, whole.source_token_count);
+        assert_eq!(merged.rendered_token_count, whole.rendered_token_count);
+        assert_eq!(merged.lossy_mappings, whole.lossy_mappings);
+        assert_eq!(merged.fixed_spans, whole.fixed_spans);
+        assert_eq!(merged.converted_spans, whole.converted_spans);
+        assert_eq!(merged.source_script, "grantha");
+        assert_eq!(merged.target_script, "slp1");
+    }
+}
+
+#[test]
+fn test_merge_keeps_the_first_parts_limit() {
+    let shlesha = Shlesha::new();
+    let (first, second) = TEXT.split_at(TEXT.find(" 𑌧").unwrap());
+
+    let merged = TransliterationMetadata::merge(vec![
+        (0, limited_metadata_of(&shlesha, first, Some(2))),
+        (first.len(), limited_metadata_of(&shlesha, second, Some(2))),
+    ]);
+    assert_eq!(merged.unknown_tokens.len(), 2);
+    assert_eq!(merged.total_unknown_count, 6);
+    assert!(merged.truncated);
+}
+
+#[test]
+fn test_merge_of_nothing_is_empty() {
+    let merged = TransliterationMetadata::merge(Vec::new());
+    assert!(merged.unknown_tokens.is_empty());
+    assert_eq!(merged.total_unknown_count, 0);
+}
@@ -0,0 +1,59 @@
+use shlesha::{Shlesha, UnknownStage};
+
+#[test]
+fn test_unknown_tokens_report_their_stage() {
+    let shlesha = Shlesha::new();
+
+    // ḷa has no WX spelling, ☺ is not Grantha and the Grantha Om has no
+    // alphabet token
+    let result = shlesha
+        .transliterate_with_metadata("𑌳 ☺ 𑍐", "grantha", "wx")
+        .unwrap();
+    let metadata = result.metadata.unwrap();
+
+    for stage in UnknownStage::ALL {
+        assert_eq!(metadata.unknowns_in_stage(stage).count(), 1, "{stage}");
+    }
+
+    let stages: Vec<_> = metadata.unknown_tokens.iter().map(|t| t.stage).collect();
+    assert_eq!(
+        stages,
+        [
+            UnknownStage::TargetRendering,
+            UnknownStage::SourceParsing,
+            UnknownStage::HubConversion,
+        ],
+        "ordered by input position"
+    );
+
+    let [rendering, parsing, conversion] = &metadata.unknown_tokens[..] else {
+        panic!("expected three unknown tokens");
+    };
+    assert_eq!(rendering.hub_token.as_deref(), Some("ConsonantLl"));
+    assert_eq!(rendering.script, "wx");
+    assert_eq!(parsing.token, '☺');
+    assert_eq!(parsing.script, "grantha");
+    assert_eq!(conversion.hub_token.as_deref(), Some("OmSymbol"));
+    assert_eq!(conversion.script, "grantha");
+}
+
+#[test]
+fn test_same_token_type_has_no_hub_conversion_stage() {
+    let shlesha = Shlesha::new();
+    let result = shlesha
+        .transliterate_with_metadata("rAmaH ☺", "slp1", "iast")
+        .unwrap();
+    let metadata = result.metadata.unwrap();
+
+    assert_eq!(
+        metadata
+            .unknowns_in_stage(UnknownStage::HubConversion)
+            .count(),
+        0
+    );
+    let parsing: Vec<_> = metadata
+        .unknowns_in_stage(UnknownStage::SourceParsing)
+        .map(|t| (t.token, t.position))
+        .collect();
+    assert!(parsing.contains(&('☺', 6)), "{parsing:?}");
+}
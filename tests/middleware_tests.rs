@@ -0,0 +1,133 @@
+//! Preprocessors and postprocessors run around conversion
+
+use shlesha::modules::middleware::protect_braced;
+use shlesha::{PreprocessOutput, Shlesha, SpanKind};
+
+#[test]
+fn test_braced_spans_survive_expansion_and_contraction() {
+    let mut shlesha = Shlesha::new();
+    shlesha.add_preprocessor(Box::new(protect_braced));
+
+    // Devanagari takes more bytes than the Roman it is converted from
+    assert_eq!(
+        shlesha
+            .transliterate("{Note:} dharma {kṣetra} kṣetre{}", "iast", "devanagari")
+            .unwrap(),
+        "{Note:} धर्म {kṣetra} क्षेत्रे{}"
+    );
+    // and fewer bytes in the other direction
+    assert_eq!(
+        shlesha
+            .transliterate("धर्मक्षेत्रे{कुरु}क्षेत्रे {unclosed", "devanagari", "iast")
+            .unwrap(),
+        "dharmakṣetre{कुरु}kṣetre {unclosed"
+    );
+}
+
+#[test]
+fn test_hooks_compose_in_registration_order() {
+    let spell_om = || Box::new(|text: &str| PreprocessOutput::new(text.replace("OM", "{oṃ}")));
+
+    // Braces written by the first preprocessor are protected by the second
+    let mut shlesha = Shlesha::new();
+    shlesha.add_preprocessor(spell_om());
+    shlesha.add_preprocessor(Box::new(protect_braced));
+    assert_eq!(
+        shlesha
+            .transliterate("OM namaḥ", "iast", "devanagari")
+            .unwrap(),
+        "{oṃ} नमः"
+    );
+
+    // A span already protected is never seen by a later preprocessor
+    let mut shlesha = Shlesha::new();
+    shlesha.add_preprocessor(Box::new(protect_braced));
+    shlesha.add_preprocessor(spell_om());
+    assert_eq!(
+        shlesha
+            .transliterate("{OM} OM", "iast", "devanagari")
+            .unwrap(),
+        "{OM} {ओं}"
+    );
+
+    shlesha.add_postprocessor(Box::new(|output: &str| format!("{output} ।")));
+    shlesha.add_postprocessor(Box::new(|output: &str| output.replace('।', "॥")));
+    assert_eq!(
+        shlesha
+            .transliterate("namaḥ", "iast", "devanagari")
+            .unwrap(),
+        "नमः ॥"
+    );
+
+    shlesha.clear_processors();
+    assert_eq!(
+        shlesha.transliterate("{oṃ}", "iast", "devanagari").unwrap(),
+        "{ओं}"
+    );
+}
+
+#[test]
+fn test_metadata_variant_runs_hooks() {
+    let mut shlesha = Shlesha::new();
+    shlesha.add_preprocessor(Box::new(protect_braced));
+    shlesha.add_postprocessor(Box::new(|output: &str| output.trim_end().to_string()));
+    shlesha
+        .add_word_override("iast", "devanagari", "bengaluru", "बेंगलूरु")
+        .unwrap();
+
+    let text = "{kṣetra} bengaluru dharmaQ {x} ";
+    let result = shlesha
+        .transliterate_with_metadata(text, "iast", "devanagari")
+        .unwrap();
+    assert_eq!(result.output, "{kṣetra} बेंगलूरु धर्मQ {x}");
+    assert_eq!(
+        result.output,
+        shlesha.transliterate(text, "iast", "devanagari").unwrap()
+    );
+
+    let metadata = result.metadata.unwrap();
+    let spans: Vec<_> = metadata
+        .fixed_spans
+        .iter()
+        .map(|span| (span.kind, span.input.as_str(), span.position))
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            (SpanKind::Protected, "{kṣetra}", 0),
+            (SpanKind::Override, "bengaluru", "{kṣetra} ".len()),
+            (
+                SpanKind::Protected,
+                "{x}",
+                "{kṣetra} bengaluru dharmaQ ".len()
+            ),
+        ]
+    );
+    // Unknown tokens keep their position in the whole input
+    assert_eq!(metadata.unknown_tokens.len(), 1);
+    assert_eq!(metadata.unknown_tokens[0].token, 'Q');
+    assert_eq!(
+        metadata.unknown_tokens[0].position,
+        "{kṣetra} bengaluru dharma".len()
+    );
+}
+
+#[test]
+fn test_invalid_protected_spans_are_errors() {
+    let mut shlesha = Shlesha::new();
+    shlesha.add_preprocessor(Box::new(|text: &str| {
+        PreprocessOutput::new(text).protect(0..text.len() + 1)
+    }));
+    let error = shlesha
+        .transliterate("dharma", "iast", "devanagari")
+        .unwrap_err();
+    assert!(error.to_string().contains("0..7"), "{error}");
+
+    let mut shlesha = Shlesha::new();
+    shlesha.add_preprocessor(Box::new(|text: &str| {
+        PreprocessOutput::new(text).protect(2..4).protect(1..3)
+    }));
+    assert!(shlesha
+        .transliterate_with_metadata("dharma", "iast", "devanagari")
+        .is_err());
+}
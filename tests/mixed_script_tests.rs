@@ -0,0 +1,118 @@
+//! Indic input with text of a second Indic script in it
+//!
+//! Text declared Devanagari that is half Bengali is reported in the
+//! conversion's metadata, and with `MixedScriptPolicy::convert` the Bengali is
+//! converted as Bengali instead of passed through.
+
+use shlesha::{ConversionWarning, MixedScriptPolicy, Shlesha, TransliterationOptions};
+
+fn converting() -> TransliterationOptions {
+    TransliterationOptions {
+        mixed_script: MixedScriptPolicy {
+            convert: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn detected(warnings: &[ConversionWarning]) -> Vec<(&str, f64)> {
+    warnings
+        .iter()
+        .map(|warning| match warning {
+            ConversionWarning::MixedScriptInput { detected, fraction } => {
+                (detected.as_str(), *fraction)
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_half_bengali_input_is_reported() {
+    let shlesha = Shlesha::new();
+    let result = shlesha
+        .transliterate_with_metadata("धर्म ধর্ম", "devanagari", "iast")
+        .unwrap();
+    // Without conversion the Bengali passes through
+    assert_eq!(result.output, "dharma ধর্ম");
+    let metadata = result.metadata.unwrap();
+    let warnings = detected(&metadata.warnings);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].0, "bengali");
+    assert!((warnings[0].1 - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_runs_of_the_detected_script_are_converted() {
+    let shlesha = Shlesha::new();
+    let options = converting();
+    assert_eq!(
+        shlesha
+            .transliterate_with_options("धर्म ধর্ম", "devanagari", "iast", &options)
+            .unwrap(),
+        "dharma dharma"
+    );
+
+    let result = shlesha
+        .transliterate_with_options_and_metadata("धर्म ধর্ম", "devanagari", "iast", &options)
+        .unwrap();
+    assert_eq!(result.output, "dharma dharma");
+    let metadata = result.metadata.unwrap();
+    assert_eq!(detected(&metadata.warnings)[0].0, "bengali");
+    assert!(metadata.unknown_tokens.is_empty());
+}
+
+#[test]
+fn test_positions_point_into_the_original_input() {
+    let shlesha = Shlesha::new();
+    // The Bengali run is respelled before the unknown character is found
+    let input = "ধর্ম धर्म ধর্ম ☃";
+    let result = shlesha
+        .transliterate_with_options_and_metadata(input, "devanagari", "iast", &converting())
+        .unwrap();
+    assert_eq!(result.output, "dharma dharma dharma ☃");
+    let metadata = result.metadata.unwrap();
+    assert!(!metadata.unknown_tokens.is_empty());
+    for token in &metadata.unknown_tokens {
+        assert_eq!(&input[token.position..], "☃");
+    }
+}
+
+#[test]
+fn test_a_few_foreign_characters_are_not_reported() {
+    let shlesha = Shlesha::new();
+    let result = shlesha
+        .transliterate_with_metadata("धर्मक्षेत्रे कुरुक्षेत्रे ক", "devanagari", "iast")
+        .unwrap();
+    assert!(result.metadata.unwrap().warnings.is_empty());
+
+    let options = TransliterationOptions {
+        mixed_script: MixedScriptPolicy {
+            threshold: 0.01,
+            convert: false,
+        },
+        ..Default::default()
+    };
+    let result = shlesha
+        .transliterate_with_options_and_metadata("धर्मक्षेत्रे कुरुक्षेत्रे ক", "devanagari", "iast", &options)
+        .unwrap();
+    assert_eq!(detected(&result.metadata.unwrap().warnings)[0].0, "bengali");
+}
+
+#[test]
+fn test_dandas_are_not_another_script() {
+    let shlesha = Shlesha::new();
+    let result = shlesha
+        .transliterate_with_metadata("ধর্ম।", "bengali", "iast")
+        .unwrap();
+    assert!(result.metadata.unwrap().warnings.is_empty());
+}
+
+#[test]
+fn test_roman_sources_are_not_checked() {
+    let shlesha = Shlesha::new();
+    let result = shlesha
+        .transliterate_with_metadata("dharma ধর্ম", "iast", "devanagari")
+        .unwrap();
+    assert!(result.metadata.unwrap().warnings.is_empty());
+}
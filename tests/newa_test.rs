@@ -0,0 +1,66 @@
+mod common;
+
+#[cfg(test)]
+mod newa_tests {
+    use crate::common::syllabary;
+    use shlesha::Shlesha;
+
+    const SIGNS: &[&str] = &[
+        "", "ा", "ि", "ी", "ु", "ू", "ृ", "ॄ", "ॢ", "ॣ", "े", "ै", "ो", "ौ", "्", "ं", "ः",
+    ];
+
+    const CONSONANTS: &[&str] = &[
+        "क", "ख", "ग", "घ", "ङ", "च", "छ", "ज", "झ", "ञ", "ट", "ठ", "ड", "ढ", "ण", "त", "थ", "द",
+        "ध", "न", "प", "फ", "ब", "भ", "म", "य", "र", "ल", "व", "श", "ष", "स", "ह",
+    ];
+
+    const VOWELS: &[&str] = &[
+        "अ", "आ", "इ", "ई", "उ", "ऊ", "ऋ", "ॠ", "ऌ", "ॡ", "ए", "ऐ", "ओ", "औ",
+    ];
+
+    #[test]
+    fn test_newa_vowel_signs_from_iast() {
+        let transliterator = Shlesha::new();
+
+        // Long ā and i signs and the dental/retroflex series
+        let result = transliterator
+            .transliterate("siddhaṁ śāradā kṛṣṇa", "iast", "newa")
+            .unwrap();
+        assert_eq!(result, "𑐳𑐶𑐡𑑂𑐢𑑄 𑐱𑐵𑐬𑐡𑐵 𑐎𑐺𑐲𑑂𑐞");
+
+        let back = transliterator
+            .transliterate(&result, "newa", "iast")
+            .unwrap();
+        assert_eq!(back, "siddhaṁ śāradā kṛṣṇa");
+    }
+
+    #[test]
+    fn test_newa_syllabary_roundtrip() {
+        let transliterator = Shlesha::new();
+
+        for line in syllabary(CONSONANTS, SIGNS, VOWELS) {
+            let result = transliterator
+                .transliterate_with_metadata(&line, "devanagari", "newa")
+                .unwrap();
+            assert!(
+                result.metadata.unwrap().unknown_tokens.is_empty(),
+                "{line} has unmapped tokens in newa: {}",
+                result.output
+            );
+            let back = transliterator
+                .transliterate(&result.output, "newa", "devanagari")
+                .unwrap();
+            assert_eq!(back, line, "Round-trip via newa {}", result.output);
+        }
+    }
+
+    #[test]
+    fn test_newa_marks() {
+        let transliterator = Shlesha::new();
+
+        let result = transliterator
+            .transliterate("कं कः कँ क्", "devanagari", "newa")
+            .unwrap();
+        assert_eq!(result, "𑐎𑑄 𑐎𑑅 𑐎𑑃 𑐎𑑂");
+    }
+}
@@ -0,0 +1,156 @@
+use shlesha::Shlesha;
+
+#[test]
+fn test_decomposed_nukta_becomes_precomposed() {
+    let shlesha = Shlesha::new();
+    let cases = [
+        ("\u{0915}\u{093C}", "\u{0958}"),                 // क़
+        ("\u{091C}\u{093C}\u{093E}", "\u{095B}\u{093E}"), // ज़ा
+        ("\u{0921}\u{093C}", "\u{095C}"),                 // ड़
+        ("\u{0928}\u{093C}", "\u{0929}"),                 // ऩ, which has no hub token
+        // Already precomposed stays so
+        ("\u{0958}\u{094D}\u{0937}", "\u{0958}\u{094D}\u{0937}"),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(shlesha.normalize(input, "devanagari").unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_joiners_are_normalized() {
+    let shlesha = Shlesha::new();
+    let cases = [
+        // After a virama a joiner selects the half form or visible virama
+        ("क्\u{200D}ष", "क्\u{200D}ष"),
+        ("क्\u{200C}ष", "क्\u{200C}ष"),
+        // Old spelling of the half form
+        ("र\u{200D}्य", "र्\u{200D}य"),
+        // No effect anywhere else
+        ("क\u{200C}ष", "कष"),
+        ("राम\u{200D} \u{200C}", "राम "),
+        ("क्\u{200C}\u{200D}\u{200C}ष", "क्\u{200C}ष"),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(
+            shlesha.normalize(input, "devanagari").unwrap(),
+            expected,
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+fn test_discouraged_sequences_and_danda_spacing() {
+    let shlesha = Shlesha::new();
+    let cases = [
+        ("\u{0905}\u{0945}", "\u{090D}"), // ॲ, read as candra e and written ऍ
+        ("\u{0905}\u{0949}", "\u{0911}"), // ऑ
+        ("\u{0905}\u{093E}", "\u{0906}"), // आ
+        ("रामः।", "रामः ।"),
+        ("रामः   ।।", "रामः ॥"),
+        ("रामः ।सीता", "रामः । सीता"),
+        ("युयुत्सवः ॥१॥\nमामकाः", "युयुत्सवः ॥१॥\nमामकाः"),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(
+            shlesha.normalize(input, "devanagari").unwrap(),
+            expected,
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+fn test_normalize_is_idempotent() {
+    let shlesha = Shlesha::new();
+    let inputs = [
+        ("धर्मक्षेत्रे कुरुक्षेत्रे  समवेता युयुत्सवः।।१।।", "devanagari"),
+        ("ज\u{093C}\u{200D}रा क\u{200D}्ष अ\u{0945} ।।। ॥ १ ॥", "deva"),
+        ("ధర్మ\u{200C}క్షేత్రే ।", "telugu"),
+        ("dharmaṃ ṛṣi", "iast"),
+    ];
+    for (input, script) in inputs {
+        let once = shlesha.normalize(input, script).unwrap();
+        let twice = shlesha.normalize(&once, script).unwrap();
+        assert_eq!(once, twice, "{input:?}");
+    }
+
+    // Roman schemes are rewritten in their preferred spelling
+    assert_eq!(shlesha.normalize("dharmaṃ", "iast").unwrap(), "dharmaṁ");
+}
+
+/// Every non-ASCII spelling in a Roman schema's mappings, as written in the schema file
+fn non_ascii_spellings(schema: &str) -> Vec<String> {
+    fn collect(value: &serde_yaml::Value, spellings: &mut Vec<String>) {
+        match value {
+            serde_yaml::Value::String(text) if !text.is_ascii() => spellings.push(text.clone()),
+            serde_yaml::Value::Sequence(values) => {
+                values.iter().for_each(|value| collect(value, spellings))
+            }
+            serde_yaml::Value::Mapping(mapping) => {
+                mapping.values().for_each(|value| collect(value, spellings))
+            }
+            _ => {}
+        }
+    }
+    let path = format!("{}/schemas/{schema}.yaml", env!("CARGO_MANIFEST_DIR"));
+    let file: serde_yaml::Value =
+        serde_yaml::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let mut spellings = Vec::new();
+    collect(&file["mappings"], &mut spellings);
+    spellings
+}
+
+#[test]
+fn test_decomposed_roman_input_reads_as_composed() {
+    use unicode_normalization::UnicodeNormalization;
+
+    let shlesha = Shlesha::new();
+    for schema in ["iast", "iso15919"] {
+        let spellings = non_ascii_spellings(schema);
+        assert!(spellings.len() > 10, "{schema}: {spellings:?}");
+        for spelling in spellings {
+            // Between consonants, as a vowel or mark would stand
+            let composed: String = format!("ka{spelling}ta").nfc().collect();
+            let decomposed: String = composed.nfd().collect();
+            for target in ["devanagari", "telugu", "slp1"] {
+                assert_eq!(
+                    shlesha.transliterate(&decomposed, schema, target).unwrap(),
+                    shlesha.transliterate(&composed, schema, target).unwrap(),
+                    "{schema} → {target}: {decomposed:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_decomposed_roman_input_keeps_original_positions() {
+    let shlesha = Shlesha::new();
+    // dharmakṣetre samavetā with ṣ and ā decomposed, then an unknown character
+    let input = "dharmaks\u{0323}etre samaveta\u{0304}\u{00A7}";
+    let result = shlesha
+        .transliterate_with_metadata(input, "iast", "devanagari")
+        .unwrap();
+    assert_eq!(
+        result.output,
+        shlesha
+            .transliterate("dharmakṣetre samavetā\u{00A7}", "iast", "devanagari")
+            .unwrap()
+    );
+    let metadata = result.metadata.unwrap();
+    assert_eq!(metadata.unknown_tokens.len(), 1);
+    // A character NFC replaces on its own passes through as written
+    assert_eq!(
+        shlesha
+            .transliterate("ka\u{0304}\u{F900}", "iast", "slp1")
+            .unwrap(),
+        "kA\u{F900}"
+    );
+    // The position of § in the input as given, not in its composed form
+    assert_eq!(
+        metadata.unknown_tokens[0].position,
+        input.find('\u{00A7}').unwrap()
+    );
+    assert_eq!(metadata.unknown_tokens[0].token, '\u{00A7}');
+}
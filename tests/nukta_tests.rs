@@ -0,0 +1,58 @@
+//! Consonants written with a separate nukta (फ + ़) in Roman output
+
+use shlesha::Shlesha;
+
+#[test]
+fn test_decomposed_nukta_to_roman_schemes() {
+    let shlesha = Shlesha::new();
+    let text = "\u{092B}\u{093C} \u{091C}\u{093C} \u{0915}\u{093C} \u{0921}\u{093C}";
+
+    // Schemes with the nukta letters write them; the others drop the nukta,
+    // which has no Roman letter, and write the base consonant
+    for (scheme, expected) in [
+        ("baraha", "Pa ja ka Da"),
+        ("harvard_kyoto", "pha ja ka Da"),
+        ("iast", "fa za qa ṙa"),
+        ("iso15919", "fa za qa ṛa"),
+        ("itrans", "fa za qa .Da"),
+        ("kolkata", "pha ja ka ḍa"),
+        ("slp1", "Pa ja ka qa"),
+        ("velthuis", "pha ja ka .da"),
+        ("wx", "Pa ja ka xa"),
+    ] {
+        let output = shlesha.transliterate(text, "devanagari", scheme).unwrap();
+        assert_eq!(output, expected, "devanagari → {scheme}");
+        assert!(!output.contains('['), "placeholder in {scheme}: {output}");
+    }
+
+    // A vowel sign after the nukta replaces the inherent a, whether the nukta is read or dropped
+    for (scheme, expected) in [
+        ("harvard_kyoto", "kilA"),
+        ("iast", "qilā"),
+        ("itrans", "qilaa"),
+        ("slp1", "kilA"),
+        ("velthuis", "kilaa"),
+        ("wx", "kilA"),
+    ] {
+        let output = shlesha
+            .transliterate("\u{0915}\u{093C}िला", "devanagari", scheme)
+            .unwrap();
+        assert_eq!(output, expected, "devanagari → {scheme}");
+    }
+}
+
+#[test]
+fn test_decomposed_nukta_matches_on_hub_and_direct_paths() {
+    let direct = Shlesha::new();
+    let mut hub = Shlesha::new();
+    hub.set_force_hub(true);
+
+    for scheme in ["iast", "itrans", "slp1", "velthuis"] {
+        let text = "\u{0915}\u{0949}\u{092B}\u{093C}\u{0940} \u{0921}\u{093C}";
+        assert_eq!(
+            hub.transliterate(text, "devanagari", scheme).unwrap(),
+            direct.transliterate(text, "devanagari", scheme).unwrap(),
+            "devanagari → {scheme}"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! Numbers in the digits of a script, with Indian and Western grouping
+
+use shlesha::numerals::{
+    convert_digits, convert_digits_grouped, format_number, parse_number, Grouping, NumeralError,
+};
+
+#[test]
+fn test_numbers_are_formatted_and_parsed_in_each_script() {
+    for (script, indian, western) in [
+        ("devanagari", "१,२३,४५,६७८", "१२,३४५,६७८"),
+        ("bengali", "১,২৩,৪৫,৬৭৮", "১২,৩৪৫,৬৭৮"),
+        ("telugu", "౧,౨౩,౪౫,౬౭౮", "౧౨,౩౪౫,౬౭౮"),
+        ("tamil", "௧,௨௩,௪௫,௬௭௮", "௧௨,௩௪௫,௬௭௮"),
+        ("iast", "1,23,45,678", "12,345,678"),
+    ] {
+        assert_eq!(
+            format_number(12345678, script, Grouping::Indian).unwrap(),
+            indian
+        );
+        assert_eq!(
+            format_number(12345678, script, Grouping::Western).unwrap(),
+            western
+        );
+        assert_eq!(parse_number(indian, script), Some(12345678), "{script}");
+        assert_eq!(parse_number(western, script), Some(12345678), "{script}");
+    }
+
+    assert_eq!(
+        format_number(999, "devanagari", Grouping::Indian).unwrap(),
+        "९९९"
+    );
+    assert_eq!(
+        format_number(1000, "devanagari", Grouping::Indian).unwrap(),
+        "१,०००"
+    );
+    assert_eq!(
+        format_number(100000, "devanagari", Grouping::Ungrouped).unwrap(),
+        "१०००००"
+    );
+    assert_eq!(format_number(0, "tamil", Grouping::Western).unwrap(), "௦");
+
+    // Aliases name the same digits
+    assert_eq!(
+        format_number(42, "bn", Grouping::Indian).unwrap(),
+        format_number(42, "bengali", Grouping::Indian).unwrap()
+    );
+
+    for malformed in ["", "१,", ",१", "१,,२", "१२a", "12"] {
+        assert_eq!(parse_number(malformed, "devanagari"), None, "{malformed}");
+    }
+    assert_eq!(parse_number("99999999999999999999", "iast"), None);
+    assert_eq!(parse_number("1", "klingon"), None);
+    assert_eq!(
+        format_number(1, "klingon", Grouping::Indian),
+        Err(NumeralError::UnknownScript("klingon".to_string()))
+    );
+}
+
+#[test]
+fn test_only_digits_of_the_source_script_are_converted() {
+    let text = "अध्याय १२, श्लोक १,२३,४५६ (p. 7)";
+    assert_eq!(
+        convert_digits(text, "devanagari", "telugu").unwrap(),
+        "अध्याय ౧౨, श्लोक ౧,౨౩,౪౫౬ (p. 7)"
+    );
+    assert_eq!(
+        convert_digits_grouped(text, "devanagari", "bengali", Grouping::Western).unwrap(),
+        "अध्याय ১২, श्लोक ১২৩,৪৫৬ (p. 7)"
+    );
+    assert_eq!(
+        convert_digits_grouped(text, "devanagari", "tamil", Grouping::Ungrouped).unwrap(),
+        "अध्याय ௧௨, श्लोक ௧௨௩௪௫௬ (p. 7)"
+    );
+
+    // Fractions are converted but not grouped
+    assert_eq!(
+        convert_digits_grouped("₹ 1234567.8901", "iast", "devanagari", Grouping::Indian).unwrap(),
+        "₹ १२,३४,५६७.८९०१"
+    );
+    assert_eq!(
+        convert_digits_grouped("১২৩৪৫ টাকা", "bengali", "iast", Grouping::Indian).unwrap(),
+        "12,345 টাকা"
+    );
+
+    assert!(matches!(
+        convert_digits("1", "iast", "nonexistent"),
+        Err(NumeralError::UnknownScript(_))
+    ));
+}
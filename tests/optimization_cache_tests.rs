@@ -0,0 +1,99 @@
+//! Optimization tables loaded into a transliterator
+
+mod common;
+
+use common::ascii_roman_schema;
+use shlesha::modules::profiler::{
+    OptimizationCacheConfig, OptimizationMetadata, OptimizedLookupTable, ProfileStats,
+};
+use shlesha::Shlesha;
+use std::time::SystemTime;
+
+/// A table writing काम as KAAM, so its use shows in the output
+fn table(to: &str) -> OptimizedLookupTable {
+    OptimizedLookupTable {
+        from_script: "devanagari".to_string(),
+        to_script: to.to_string(),
+        sequence_mappings: [("काम".to_string(), "KAAM".to_string())]
+            .into_iter()
+            .collect(),
+        word_mappings: Default::default(),
+        metadata: OptimizationMetadata {
+            generated_at: SystemTime::now(),
+            sequence_count: 1,
+            min_frequency: 1,
+            profile_stats: ProfileStats {
+                total_sequences_profiled: 1,
+                unique_sequences: 1,
+                top_sequences: vec![],
+            },
+        },
+    }
+}
+
+#[test]
+fn test_schema_reload_invalidates_its_tables() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(&ascii_roman_schema("aa"), "ascii_roman")
+        .unwrap();
+    shlesha.load_optimization(table("ascii_roman"));
+    shlesha.load_optimization(table("iast"));
+    let convert = |shlesha: &Shlesha, to| shlesha.transliterate("काम", "devanagari", to).unwrap();
+    assert_eq!(convert(&shlesha, "ascii_roman"), "KAAM");
+    assert_eq!(shlesha.stats().optimization_tables, 2);
+
+    // The table was generated for the old schema and is dropped on its next use
+    shlesha
+        .reload_schema("ascii_roman", &ascii_roman_schema("A"))
+        .unwrap();
+    assert_eq!(convert(&shlesha, "ascii_roman"), "kAma");
+    assert_eq!(shlesha.stats().optimization_tables, 1);
+
+    // Other pairs keep theirs
+    assert_eq!(convert(&shlesha, "iast"), "KAAM");
+
+    // A table loaded after the reload is used
+    shlesha.load_optimization(table("ascii_roman"));
+    assert_eq!(convert(&shlesha, "ascii_roman"), "KAAM");
+}
+
+#[test]
+fn test_bounds_and_clear() {
+    let mut shlesha = Shlesha::new();
+    shlesha.set_optimization_cache_config(OptimizationCacheConfig {
+        max_tables: 2,
+        max_entries: 100,
+    });
+    for to in ["iast", "slp1", "itrans"] {
+        shlesha.load_optimization(table(to));
+    }
+    let stats = shlesha.stats();
+    assert_eq!(
+        (stats.optimization_tables, stats.optimization_entries),
+        (2, 2)
+    );
+    assert_eq!(
+        shlesha.transliterate("काम", "devanagari", "iast").unwrap(),
+        "kāma"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("काम", "devanagari", "itrans")
+            .unwrap(),
+        "KAAM"
+    );
+
+    shlesha.clear_optimizations();
+    let stats = shlesha.stats();
+    assert_eq!(
+        (stats.optimization_tables, stats.optimization_entries),
+        (0, 0)
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("काम", "devanagari", "itrans")
+            .unwrap(),
+        "kaama"
+    );
+}
@@ -0,0 +1,229 @@
+//! Chakma, Tai Tham, Balinese, Thai and Khmer, the Pali scripts of South and
+//! Southeast Asia
+
+use shlesha::Shlesha;
+
+const SCRIPTS: [&str; 5] = ["chakma", "tai_tham", "balinese", "thai", "khmer"];
+
+/// The Pali alphabet in IAST, whose ḷa is written ḻ, and the niggahita on each letter
+fn pali_alphabet() -> Vec<String> {
+    let consonants = [
+        "k", "kh", "g", "gh", "ṅ", "c", "ch", "j", "jh", "ñ", "ṭ", "ṭh", "ḍ", "ḍh", "ṇ", "t", "th",
+        "d", "dh", "n", "p", "ph", "b", "bh", "m", "y", "r", "l", "v", "s", "h", "ḻ",
+    ];
+    let vowels = ["a", "ā", "i", "ī", "u", "ū", "e", "o"];
+    let mut lines: Vec<String> = consonants
+        .iter()
+        .map(|c| {
+            vowels
+                .iter()
+                .map(|v| format!("{c}{v} {c}{v}ṁ"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    lines.push(vowels.join(" "));
+    lines
+}
+
+const PALI_TEXT: &str = "namo tassa bhagavato arahato sammāsambuddhassa \
+     buddhaṁ saraṇaṁ gacchāmi dhammaṁ saraṇaṁ gacchāmi saṅghaṁ saraṇaṁ gacchāmi \
+     brahmā indriya vuḍḍhi virūḻhi 108";
+
+#[test]
+fn test_pali_alphabet_roundtrips_through_iast() {
+    let shlesha = Shlesha::new();
+
+    for script in SCRIPTS {
+        for line in pali_alphabet()
+            .iter()
+            .map(String::as_str)
+            .chain([PALI_TEXT])
+        {
+            let result = shlesha
+                .transliterate_with_metadata(line, "iast", script)
+                .unwrap();
+            assert!(
+                result.metadata.unwrap().unknown_tokens.is_empty(),
+                "{line} has unmapped tokens in {script}: {}",
+                result.output
+            );
+            assert!(!result.output.is_ascii());
+            assert_eq!(
+                shlesha
+                    .transliterate(&result.output, script, "iast")
+                    .unwrap(),
+                line,
+                "{script}: {}",
+                result.output
+            );
+        }
+    }
+}
+
+#[test]
+fn test_clusters_are_subjoined() {
+    let shlesha = Shlesha::new();
+
+    // Tai Tham stacks with the sakot, and writes medial ra and the great sa as letters
+    for (iast, tai_tham) in [
+        ("buddhaṁ", "ᨻᩩᨴ᩠ᨵᩴ"),
+        ("dhammo", "ᨵᨾ᩠ᨾᩮᩣ"),
+        ("tassa", "ᨲᩔ"),
+        ("brahmā", "ᨻᩕᩉ᩠ᨾᩣ"),
+    ] {
+        assert_eq!(
+            shlesha.transliterate(iast, "iast", "tai_tham").unwrap(),
+            tai_tham
+        );
+        assert_eq!(
+            shlesha.transliterate(tai_tham, "lana", "iast").unwrap(),
+            iast
+        );
+    }
+    // Stacked spellings of the medial ra and great sa read the same
+    assert_eq!(
+        shlesha
+            .transliterate("ᨻ᩠ᩁᩉ᩠ᨾᩣ ᨲᩈ᩠ᩈ", "tai_tham", "iast")
+            .unwrap(),
+        "brahmā tassa"
+    );
+
+    // A consonant ending a word takes the visible killer; a cluster the stacker
+    assert_eq!(
+        shlesha
+            .transliterate("वाक् धम्म", "devanagari", "tai_tham")
+            .unwrap(),
+        "ᩅᩣᨠ᩺ ᨵᨾ᩠ᨾ"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("वाक् धम्म", "devanagari", "chakma")
+            .unwrap(),
+        "\u{11147}\u{11145}\u{11107}\u{11134} \u{11119}\u{1111F}\u{11133}\u{1111F}"
+    );
+    for script in ["tai_tham", "chakma"] {
+        let written = shlesha
+            .transliterate("वाक् धम्म", "devanagari", script)
+            .unwrap();
+        assert_eq!(
+            shlesha
+                .transliterate(&written, script, "devanagari")
+                .unwrap(),
+            "वाक् धम्म"
+        );
+    }
+
+    // Balinese gantungan are encoded as the adeg-adeg before the consonant
+    assert_eq!(
+        shlesha.transliterate("buddhaṁ", "iast", "bali").unwrap(),
+        "ᬩᬸᬤ᭄ᬥᬂ"
+    );
+    assert_eq!(
+        shlesha.transliterate("ᬤᬵᬦᬂ ᬤᬵᬦᬂ", "balinese", "iast").unwrap(),
+        "dānaṁ dānaṁ"
+    );
+}
+
+#[test]
+fn test_thai_pali_orthography() {
+    let shlesha = Shlesha::new();
+
+    // The pinthu kills a consonant, and e, o and ai go before their consonant
+    for (iast, thai) in [
+        ("dhamma", "ธมฺม"),
+        ("saṅgha", "สงฺฆ"),
+        ("nibbāna", "นิพฺพาน"),
+        ("namo", "นโม"),
+        ("mettā", "เมตฺตา"),
+        ("buddhaṁ", "พุทฺธํ"),
+        ("kaumudī", "เกามุที"),
+        ("kṛṣṇa", "กฤษฺณ"),
+    ] {
+        assert_eq!(shlesha.transliterate(iast, "iast", "thai").unwrap(), thai);
+        assert_eq!(shlesha.transliterate(thai, "th", "iast").unwrap(), iast);
+    }
+    // The yamakkan of older books and the thanthakhat read as the pinthu
+    assert_eq!(
+        shlesha.transliterate("ธม๎ม ธม์ม", "thai", "iast").unwrap(),
+        "dhamma dhamma"
+    );
+
+    // Thai has one e and one o, so Telugu short e is written as the long one
+    assert_eq!(
+        shlesha.transliterate("దెవ", "telugu", "thai").unwrap(),
+        "เทว"
+    );
+    assert!(shlesha.pair_capability("telugu", "thai").lossy);
+}
+
+#[test]
+fn test_khmer_clusters_take_the_coeng() {
+    let shlesha = Shlesha::new();
+
+    for (iast, khmer) in [
+        ("dhamma", "ធម្ម"),
+        ("saṅgha", "សង្ឃ"),
+        ("nibbāna", "និព្ពាន"),
+        ("namo", "នមោ"),
+        ("buddhaṁ", "ពុទ្ធំ"),
+        ("kṛṣṇa", "ក្ឫឞ្ណ"),
+    ] {
+        assert_eq!(shlesha.transliterate(iast, "iast", "khmer").unwrap(), khmer);
+        assert_eq!(shlesha.transliterate(khmer, "km", "iast").unwrap(), iast);
+    }
+    // A consonant ending a word takes the viriam
+    assert_eq!(
+        shlesha
+            .transliterate("वाक् धम्म", "devanagari", "khmer")
+            .unwrap(),
+        "វាក៑ ធម្ម"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("វាក៑ ធម្ម", "khmer", "devanagari")
+            .unwrap(),
+        "वाक् धम्म"
+    );
+}
+
+#[test]
+fn test_chakma_beyond_the_bmp() {
+    let shlesha = Shlesha::new();
+
+    // Every Chakma letter is a four-byte character
+    let chakma = shlesha
+        .transliterate("namo tassa 108", "iast", "cakm")
+        .unwrap();
+    assert_eq!(
+        chakma,
+        "\u{1111A}\u{1111F}\u{1112E} \u{11116}\u{11125}\u{11133}\u{11125} \u{11137}\u{11136}\u{1113E}"
+    );
+    assert!(chakma
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|c| c.len_utf8() == 4));
+
+    // The decomposed o sign reads as the precomposed one
+    assert_eq!(
+        shlesha
+            .transliterate("\u{1111A}\u{1111F}\u{11131}\u{11127}", "chakma", "iast")
+            .unwrap(),
+        "namo"
+    );
+
+    // Unknown characters among Chakma letters are reported at their byte offsets
+    let input = "\u{1111F}Q\u{11107}";
+    let result = shlesha
+        .transliterate_with_metadata(input, "chakma", "devanagari")
+        .unwrap();
+    assert_eq!(result.output, "मQक");
+    let unknowns: Vec<_> = result
+        .metadata
+        .unwrap()
+        .unknown_tokens
+        .iter()
+        .map(|token| (token.token, token.position))
+        .collect();
+    assert_eq!(unknowns, [('Q', 4)]);
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "parallel")]
+
+use shlesha::modules::parallel::split_at_safe_boundaries;
+use shlesha::Shlesha;
+
+const DEVANAGARI: &str = "धर्मक्षेत्रे कुरुक्षेत्रे समवेता युयुत्सवः ।\nमामकाः पाण्डवाश्चैव किमकुर्वत सञ्जय ॥१॥\t";
+const IAST: &str = "dharmakṣetre kurukṣetre samavetā yuyutsavaḥ |\nmāmakāḥ pāṇḍavāś caiva kim akurvata sañjaya ||1||  ";
+const TELUGU: &str = "కెరళ ఏకమ్ రామః\nఒకటి  ";
+
+/// Roughly `bytes` of text built from `unit`, large enough to split into many chunks
+fn corpus(unit: &str, bytes: usize) -> String {
+    unit.repeat(bytes / unit.len() + 1)
+}
+
+fn serial() -> Shlesha {
+    let mut transliterator = Shlesha::new();
+    transliterator.set_parallel_threshold(usize::MAX);
+    transliterator
+}
+
+fn parallel() -> Shlesha {
+    let mut transliterator = Shlesha::new();
+    transliterator.set_parallel_threshold(0);
+    transliterator
+}
+
+#[test]
+fn test_parallel_output_is_byte_identical_to_serial() {
+    let (serial, parallel) = (serial(), parallel());
+    let cases = [
+        (DEVANAGARI, "devanagari", "iast"),
+        (DEVANAGARI, "devanagari", "telugu"),
+        (IAST, "iast", "devanagari"),
+        (IAST, "iast", "slp1"),
+        (TELUGU, "telugu", "iso15919"),
+        (TELUGU, "telugu", "devanagari"),
+    ];
+
+    for (unit, from, to) in cases {
+        let text = corpus(unit, 512 * 1024);
+        assert!(split_at_safe_boundaries(&text, 16 * 1024).len() > 1);
+
+        let expected = serial.transliterate(&text, from, to).unwrap();
+        let actual = parallel.transliterate(&text, from, to).unwrap();
+        assert!(
+            expected == actual,
+            "{from} → {to} differs from serial output"
+        );
+    }
+}
+
+#[test]
+fn test_parallel_metadata_matches_serial() {
+    let (serial, parallel) = (serial(), parallel());
+    let text = corpus(TELUGU, 256 * 1024);
+
+    let expected = serial
+        .transliterate_with_metadata(&text, "telugu", "devanagari")
+        .unwrap();
+    let actual = parallel
+        .transliterate_with_metadata(&text, "telugu", "devanagari")
+        .unwrap();
+    assert_eq!(expected.output, actual.output);
+
+    let (expected, actual) = (expected.metadata.unwrap(), actual.metadata.unwrap());
+    assert!(!expected.lossy_mappings.is_empty());
+    assert_eq!(expected.lossy_mappings, actual.lossy_mappings);
+    assert_eq!(expected.unknown_tokens, actual.unknown_tokens);
+}
+
+#[test]
+fn test_parallel_unmapped_token_positions_are_shifted() {
+    let (serial, parallel) = (serial(), parallel());
+    // Siddham has no vowel sign vocalic l
+    let text = corpus("कॢप्त धर्म ", 128 * 1024);
+
+    let expected = serial
+        .transliterate_with_metadata(&text, "devanagari", "siddham")
+        .unwrap()
+        .metadata
+        .unwrap();
+    let actual = parallel
+        .transliterate_with_metadata(&text, "devanagari", "siddham")
+        .unwrap()
+        .metadata
+        .unwrap();
+    assert!(expected.unknown_tokens.len() > 1);
+    assert_eq!(expected.unknown_tokens, actual.unknown_tokens);
+}
+
+#[test]
+fn test_parallel_errors_are_reported() {
+    let text = corpus(IAST, 64 * 1024);
+    assert!(parallel()
+        .transliterate(&text, "iast", "no_such_script")
+        .is_err());
+}
+
+#[test]
+fn test_split_at_safe_boundaries() {
+    let text = corpus(DEVANAGARI, 64 * 1024);
+    let chunks = split_at_safe_boundaries(&text, 1000);
+    assert!(chunks.len() > 10);
+
+    let mut rebuilt = String::new();
+    for &(offset, chunk) in &chunks {
+        assert_eq!(offset, rebuilt.len());
+        rebuilt.push_str(chunk);
+    }
+    assert_eq!(rebuilt, text);
+
+    // Every chunk after the first starts right after whitespace or a danda run
+    for pair in chunks.windows(2) {
+        let last = pair[0].1.chars().last().unwrap();
+        let first = pair[1].1.chars().next().unwrap();
+        assert!(last.is_whitespace() || last == '।' || last == '॥');
+        assert!(!first.is_whitespace() && first != '।' && first != '॥');
+    }
+}
+
+#[test]
+fn test_split_without_boundaries_keeps_text_whole() {
+    let text = "क".repeat(10_000);
+    assert_eq!(
+        split_at_safe_boundaries(&text, 100),
+        vec![(0, text.as_str())]
+    );
+    assert_eq!(split_at_safe_boundaries("", 100), vec![(0, "")]);
+}
@@ -0,0 +1,96 @@
+use shlesha::Shlesha;
+
+/// Prishthamatra spellings from the Unicode Standard's description of U+094E,
+/// with their modern equivalents
+const OLD_ORTHOGRAPHY: &[(&str, &str)] = &[
+    ("क\u{094E}", "के"),
+    ("क\u{094E}\u{0947}", "कै"),
+    ("क\u{094E}\u{093E}", "को"),
+    ("क\u{094E}\u{094B}", "कौ"),
+];
+
+#[test]
+fn test_prishthamatra_reads_as_modern_vowel_signs() {
+    let shlesha = Shlesha::new();
+
+    for &(old, modern) in OLD_ORTHOGRAPHY {
+        for to in ["iast", "telugu", "slp1"] {
+            assert_eq!(
+                shlesha.transliterate(old, "devanagari", to).unwrap(),
+                shlesha.transliterate(modern, "devanagari", to).unwrap(),
+                "{old} → {to}"
+            );
+        }
+        assert_eq!(shlesha.normalize(old, "devanagari").unwrap(), modern);
+    }
+
+    assert_eq!(
+        shlesha
+            .transliterate("म\u{094E}\u{093E}क्ष", "devanagari", "iast")
+            .unwrap(),
+        "mokṣa"
+    );
+}
+
+#[test]
+fn test_prishthamatra_is_noted_in_metadata() {
+    let shlesha = Shlesha::new();
+
+    let text = "के क\u{094E}\u{093E} क\u{094E}";
+    let metadata = shlesha
+        .transliterate_with_metadata(text, "devanagari", "iast")
+        .unwrap()
+        .metadata
+        .unwrap();
+    assert!(metadata.unknown_tokens.is_empty());
+
+    let spans: Vec<_> = metadata
+        .normalized_spans
+        .iter()
+        .map(|span| (span.input.as_str(), span.read_as.as_str(), span.position))
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            ("\u{094E}\u{093E}", "ो", "के क".len()),
+            ("\u{094E}", "े", "के कॎा क".len()),
+        ]
+    );
+}
+
+#[test]
+fn test_prishthamatra_output_for_facsimiles() {
+    let mut shlesha = Shlesha::new();
+    assert_eq!(
+        shlesha
+            .transliterate("ke kai ko kau", "iast", "devanagari")
+            .unwrap(),
+        "के कै को कौ"
+    );
+
+    shlesha.set_prishthamatra_output(true);
+    let old: Vec<_> = OLD_ORTHOGRAPHY.iter().map(|&(old, _)| old).collect();
+    assert_eq!(
+        shlesha
+            .transliterate("ke kai ko kau", "iast", "devanagari")
+            .unwrap(),
+        old.join(" ")
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("కే కై కో కౌ", "telugu", "devanagari")
+            .unwrap(),
+        old.join(" ")
+    );
+
+    // Old orthography reads back to the same text
+    let facsimile = shlesha
+        .transliterate("dharmakṣetre kurukṣetre", "iast", "devanagari")
+        .unwrap();
+    assert_eq!(
+        shlesha
+            .transliterate(&facsimile, "devanagari", "iast")
+            .unwrap(),
+        "dharmakṣetre kurukṣetre"
+    );
+}
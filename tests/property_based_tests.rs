@@ -1,5 +1,8 @@
 use quickcheck::{Arbitrary, Gen};
 use quickcheck_macros::quickcheck;
+use shlesha::modules::hub::tokens::{AlphabetToken, HubToken, TokenCategory};
+use shlesha::modules::hub::trait_based_converter::TraitBasedConverter;
+use shlesha::modules::script_converter::ScriptConverterRegistry;
 use shlesha::Shlesha;
 
 /// Generate valid Sanskrit text for property-based testing
@@ -274,7 +277,8 @@ fn prop_ascii_preservation(ascii_chars: String, script1: String, script2: String
     // Filter to only simple ASCII letters and spaces that won't form Sanskrit patterns
     let ascii_chars: String = ascii_chars
         .chars()
-        .filter(|c| "bcdfgjklmnpqstvwxyz ".contains(*c)) // Exclude vowels and Sanskrit consonants
+        // Exclude vowels and Sanskrit consonants, including the nukta consonants q, z and f
+        .filter(|c| "bcdgjklmnpstvwxy ".contains(*c))
         .take(10) // Keep it short to avoid accidental patterns
         .collect();
 
@@ -292,7 +296,7 @@ fn prop_ascii_preservation(ascii_chars: String, script1: String, script2: String
         }
     }
 
-    let roman_scripts = vec!["iast", "slp1", "iso", "harvard_kyoto"];
+    let roman_scripts = ["iast", "slp1", "iso", "harvard_kyoto"];
     let script1 = if roman_scripts.contains(&script1.as_str()) {
         script1
     } else {
@@ -309,14 +313,12 @@ fn prop_ascii_preservation(ascii_chars: String, script1: String, script2: String
     if let Ok(result) = shlesha.transliterate(&ascii_chars, &script1, &script2) {
         // Basic ASCII characters should be preserved (excluding Sanskrit patterns)
         for ch in ascii_chars.chars() {
-            if ch.is_ascii_alphanumeric() || ch == ' ' {
-                if !result.contains(ch) {
-                    eprintln!(
-                        "ASCII char '{}' not preserved: '{}' → '{}'",
-                        ch, ascii_chars, result
-                    );
-                    return false;
-                }
+            if (ch.is_ascii_alphanumeric() || ch == ' ') && !result.contains(ch) {
+                eprintln!(
+                    "ASCII char '{}' not preserved: '{}' → '{}'",
+                    ch, ascii_chars, result
+                );
+                return false;
             }
         }
     }
@@ -393,10 +395,10 @@ fn prop_character_mapping_consistency(_ch: char) -> bool {
         ("z", "slp1", "iast", "ṣ"),
         ("kz", "slp1", "iast", "kṣ"),
         ("e", "slp1", "iast", "e"), // SLP1 'e' is long e (VowelEe) which exists in IAST
-        ("e1", "slp1", "iast", "[VowelE]"), // SLP1 'e1' is short e (VowelE) which IAST lacks
+        ("e1", "slp1", "iast", "ĕ"), // SLP1 'e1' is short e (VowelE), written with a breve in IAST
         ("E", "slp1", "iast", "ai"), // SLP1 'E' is diphthong ai
         ("o", "slp1", "iast", "o"), // SLP1 'o' is long o (VowelOo) which exists in IAST
-        ("o1", "slp1", "iast", "[VowelO]"), // SLP1 'o1' is short o (VowelO) which IAST lacks
+        ("o1", "slp1", "iast", "ŏ"), // SLP1 'o1' is short o (VowelO), written with a breve in IAST
         ("O", "slp1", "iast", "au"), // SLP1 'O' is diphthong au
     ];
 
@@ -489,6 +491,181 @@ fn prop_error_handling_consistent(text: String, _source: String, _target: String
     true
 }
 
+/// A sequence of alphabet vowels and consonants that have abugida counterparts
+#[derive(Debug, Clone)]
+pub struct AlphabetSyllables(pub Vec<HubToken>);
+
+impl Arbitrary for AlphabetSyllables {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let tokens: Vec<AlphabetToken> = AlphabetToken::INVENTORY
+            .iter()
+            .filter(|(category, _)| {
+                matches!(category, TokenCategory::Vowels | TokenCategory::Consonants)
+            })
+            .flat_map(|(_, names)| names.iter())
+            .map(|name| name.parse().unwrap())
+            .filter(|token: &AlphabetToken| token.to_abugida().is_some())
+            .collect();
+
+        let len = usize::arbitrary(g) % 12;
+        AlphabetSyllables(
+            (0..len)
+                .map(|_| HubToken::Alphabet(g.choose(&tokens).unwrap().clone()))
+                .collect(),
+        )
+    }
+}
+
+#[quickcheck]
+fn prop_hub_conversion_roundtrips(input: AlphabetSyllables) -> bool {
+    // Consonants get their vowel as a sign or a virama, and lose them again
+    let abugida = TraitBasedConverter::alphabet_to_abugida(&input.0).unwrap();
+    TraitBasedConverter::abugida_to_alphabet(&abugida).unwrap() == input.0
+}
+
+/// Roman letters mixed with digits, punctuation and characters no Roman scheme reads
+#[derive(Debug, Clone)]
+pub struct MixedRomanText {
+    pub text: String,
+    pub script: &'static str,
+}
+
+impl Arbitrary for MixedRomanText {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let scripts = [
+            "iast",
+            "iso15919",
+            "slp1",
+            "harvard_kyoto",
+            "itrans",
+            "velthuis",
+            "wx",
+            "kolkata",
+            "baraha",
+        ];
+        let pieces = [
+            "a",
+            "A",
+            "aa",
+            "i",
+            "u",
+            "e",
+            "o",
+            "ai",
+            "au",
+            "k",
+            "kh",
+            "g",
+            "c",
+            "S",
+            "s",
+            "sh",
+            "z",
+            "R",
+            "RR",
+            "f",
+            "x",
+            "L",
+            "M",
+            "H",
+            "m",
+            ".",
+            "~",
+            "^",
+            "_",
+            "/",
+            "'",
+            "\"",
+            "-",
+            " ",
+            "\n",
+            "0",
+            "1",
+            "2",
+            "9",
+            "[",
+            "]",
+            "[ConsonantK]",
+            "ā",
+            "ṃ",
+            "ṣ",
+            "ṛ",
+            "ḷ",
+            "ï",
+            "\u{0308}",
+            "\u{200C}",
+            "é",
+            "क",
+            "ß",
+            "😀",
+        ];
+
+        let len = usize::arbitrary(g) % 16;
+        MixedRomanText {
+            script: g.choose(&scripts).unwrap(),
+            text: (0..len).map(|_| *g.choose(&pieces).unwrap()).collect(),
+        }
+    }
+}
+
+#[quickcheck]
+fn prop_tokens_segment_the_input(input: MixedRomanText) -> bool {
+    // Each token's text runs from its position to the next token's, so the
+    // segments must tile the input, and an unknown token's text must be exactly
+    // its segment: no match may begin inside an unknown span or overlap it
+    let registry = ScriptConverterRegistry::default();
+    let (hub, positions, _) = registry
+        .to_hub_with_positions(input.script, &input.text)
+        .unwrap();
+    let positions = positions.unwrap();
+    let text = input.text.as_str();
+
+    if positions.len() != hub.tokens().len()
+        || positions.first().is_some_and(|&first| first != 0)
+        || positions.windows(2).any(|pair| pair[0] > pair[1])
+        || !positions.iter().all(|&at| text.is_char_boundary(at))
+    {
+        return false;
+    }
+
+    let mut segments = String::new();
+    for (i, token) in hub.tokens().iter().enumerate() {
+        let end = positions[i + 1..]
+            .iter()
+            .copied()
+            .find(|&at| at > positions[i])
+            .unwrap_or(text.len());
+        let segment = &text[positions[i]..end];
+        if let HubToken::Alphabet(AlphabetToken::Unknown(unknown)) = token {
+            if unknown != segment {
+                return false;
+            }
+        }
+        if positions.get(i + 1) != Some(&positions[i]) {
+            segments.push_str(segment);
+        }
+    }
+    segments == text
+}
+
+#[test]
+fn test_digits_and_hyphens_between_roman_letters() {
+    let shlesha = Shlesha::new();
+    for (input, from, to, expected) in [
+        ("aSoka-stambha2", "slp1", "harvard_kyoto", "azoka-stambha2"),
+        ("aSoka-stambha2", "slp1", "iast", "aśoka-stambha2"),
+        ("1971-ko", "slp1", "itrans", "1971-ko"),
+        ("1971-ko", "harvard_kyoto", "devanagari", "१९७१-को"),
+        ("azoka-stambha2", "harvard_kyoto", "slp1", "aSoka-stamBa2"),
+    ] {
+        assert_eq!(
+            shlesha.transliterate(input, from, to).unwrap(),
+            expected,
+            "{from} → {to} for {input:?}"
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,103 @@
+//! Recording in the metadata what produced a conversion
+
+use shlesha::modules::schema::SchemaBuilder;
+use shlesha::{Shlesha, TransliterationOptions};
+
+const VERSIONED: &str = r#"
+metadata:
+  name: "versioned_roman"
+  script_type: "roman"
+  has_implicit_a: false
+  version: "2.1.0"
+  author: "A. Scholar"
+target: "alphabet_tokens"
+mappings:
+  vowels:
+    VowelA: "a"
+  consonants:
+    ConsonantK: "k"
+"#;
+
+fn with_provenance() -> TransliterationOptions {
+    TransliterationOptions {
+        record_provenance: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_provenance_names_runtime_schema_versions() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(VERSIONED, "versioned_roman")
+        .unwrap();
+
+    let result = shlesha
+        .transliterate_with_options_and_metadata(
+            "ka",
+            "versioned_roman",
+            "devanagari",
+            &with_provenance(),
+        )
+        .unwrap();
+    assert_eq!(result.output, "क");
+    let provenance = result.metadata.unwrap().provenance.unwrap();
+    assert_eq!(provenance.crate_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(provenance.output_version, Shlesha::output_version());
+    assert_eq!(provenance.runtime_schemas.len(), 1);
+    assert_eq!(provenance.runtime_schemas[0].name, "versioned_roman");
+    assert_eq!(
+        provenance.runtime_schemas[0].version.as_deref(),
+        Some("2.1.0")
+    );
+}
+
+#[test]
+fn test_built_schemas_keep_their_version() {
+    let mut shlesha = Shlesha::new();
+    let schema = SchemaBuilder::new("built_roman")
+        .script_type("roman")
+        .target("alphabet_tokens")
+        .version("0.3")
+        .author("A. Scholar")
+        .add_vowel_mapping("VowelA", &["a"])
+        .add_consonant_mapping("ConsonantK", &["k"])
+        .build();
+    shlesha.add_runtime_schema(schema).unwrap();
+
+    let metadata = shlesha
+        .transliterate_with_options_and_metadata(
+            "क",
+            "devanagari",
+            "built_roman",
+            &with_provenance(),
+        )
+        .unwrap()
+        .metadata
+        .unwrap();
+    let schemas = metadata.provenance.unwrap().runtime_schemas;
+    assert_eq!(schemas.len(), 1);
+    assert_eq!(schemas[0].name, "built_roman");
+    assert_eq!(schemas[0].version.as_deref(), Some("0.3"));
+}
+
+#[test]
+fn test_provenance_is_only_recorded_when_asked_for() {
+    let shlesha = Shlesha::new();
+    let metadata = shlesha
+        .transliterate_with_metadata("धर्म", "devanagari", "iast")
+        .unwrap()
+        .metadata
+        .unwrap();
+    assert_eq!(metadata.provenance, None);
+
+    // Compiled-in scripts name no runtime schema
+    let provenance = shlesha
+        .transliterate_with_options_and_metadata("धर्म", "devanagari", "iast", &with_provenance())
+        .unwrap()
+        .metadata
+        .unwrap()
+        .provenance
+        .unwrap();
+    assert!(provenance.runtime_schemas.is_empty());
+}
@@ -0,0 +1,197 @@
+//! shlesha.pyi against the Python bindings it describes
+//!
+//! Reads both files, so it runs without Python or the `python` feature;
+//! tests/integration/test_type_stubs.py checks the stub against the built
+//! module instead.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+const BINDINGS: &str = include_str!("../src/python_bindings.rs");
+const STUB: &str = include_str!("../shlesha.pyi");
+
+/// Python names, and the parameters of the callable ones
+type Names = BTreeMap<String, Vec<String>>;
+
+/// What is inside the parentheses `text` starts with
+fn parenthesized(text: &str) -> &str {
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 1 => return &text[1..i],
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    panic!("unclosed parenthesis in {text}")
+}
+
+/// The names of parameters in a parameter list, without `self`, `py` or
+/// attributes such as `#[pyo3(from_py_with = ...)]`
+fn parameters(list: &str) -> Vec<String> {
+    let mut depth = 0;
+    let mut names = Vec::new();
+    let mut start = 0;
+    for (i, c) in list.char_indices().chain([(list.len(), ',')]) {
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            ')' | ']' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                let parameter = list[start..i].trim();
+                start = i + 1;
+                let name = parameter.split(':').next().unwrap();
+                let name = name.rsplit(']').next().unwrap().trim();
+                if !matches!(name, "" | "&self" | "&mut self" | "self" | "py") {
+                    names.push(name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// What the bindings export: module-level names and each class's members
+fn bindings() -> (Names, BTreeMap<String, Names>) {
+    let mut rust_to_python = BTreeMap::new();
+    let mut classes: BTreeMap<String, Names> = BTreeMap::new();
+    let lines: Vec<&str> = BINDINGS.lines().collect();
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(attribute) = line.strip_prefix("#[pyclass(name = \"") {
+            let python = attribute.split('"').next().unwrap();
+            let rust = lines[i + 1..]
+                .iter()
+                .find_map(|line| line.strip_prefix("pub struct "))
+                .unwrap()
+                .trim_end_matches(" {");
+            rust_to_python.insert(rust.to_string(), python.to_string());
+            let fields = classes.entry(python.to_string()).or_default();
+            for (j, field) in lines.iter().enumerate().skip(i + 1) {
+                if *field == "}" {
+                    break;
+                }
+                if field.trim() == "#[pyo3(get)]" {
+                    let name = lines[j + 1].trim().split(':').next().unwrap();
+                    fields.insert(name.to_string(), Vec::new());
+                }
+            }
+        }
+    }
+
+    let mut module = Names::new();
+    let mut class = None;
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with("impl ") && lines[i - 1] == "#[pymethods]" {
+            let rust = line["impl ".len()..].trim_end_matches(" {");
+            class = Some(rust_to_python[rust].clone());
+        } else if *line == "}" {
+            class = None;
+        }
+        let signature = |definition: &str| {
+            let name = definition.split('(').next().unwrap().to_string();
+            let rest = lines[i..].join("\n");
+            let rest = &rest[rest.find(&format!("fn {name}(")).unwrap() + 3 + name.len()..];
+            (name, parameters(parenthesized(rest)))
+        };
+        if let (Some(class), Some(definition)) = (&class, line.strip_prefix("    fn ")) {
+            let (name, parameters) = signature(definition);
+            let name = if lines[i - 1].trim() == "#[new]" {
+                "__init__".to_string()
+            } else {
+                name
+            };
+            classes.get_mut(class).unwrap().insert(name, parameters);
+        } else if let Some(definition) = line.strip_prefix("fn ") {
+            if lines[i - 1] == "#[pyfunction]" {
+                let (name, parameters) = signature(definition);
+                module.insert(name, parameters);
+            }
+        }
+    }
+    for added in BINDINGS.split("m.add(").skip(1) {
+        let name = added.trim_start().strip_prefix('"').unwrap();
+        module.insert(name.split('"').next().unwrap().to_string(), Vec::new());
+    }
+    for class in classes.keys() {
+        module.insert(class.clone(), Vec::new());
+    }
+    (module, classes)
+}
+
+/// What the stub declares, leaving out the TypedDicts that exist only in it
+fn stub() -> (Names, BTreeMap<String, Names>) {
+    let mut module = Names::new();
+    let mut classes: BTreeMap<String, Names> = BTreeMap::new();
+    let mut class = None;
+    // Past the module docstring
+    let body = STUB.splitn(3, "\"\"\"").nth(2).unwrap();
+    let lines: Vec<&str> = body.lines().collect();
+    for (i, line) in lines.iter().enumerate() {
+        let signature = || {
+            let rest = lines[i..].join("\n");
+            let definition = &rest[rest.find("def ").unwrap() + 4..];
+            let name = definition.split('(').next().unwrap().to_string();
+            let parameters = parameters(parenthesized(&definition[name.len()..]));
+            (name, parameters)
+        };
+        if let Some(definition) = line.strip_prefix("class ") {
+            let name = definition.split([':', '(']).next().unwrap();
+            class = (!definition.contains("TypedDict")).then(|| name.to_string());
+            if let Some(class) = &class {
+                module.insert(class.clone(), Vec::new());
+                classes.insert(class.clone(), Names::new());
+            }
+        } else if line.starts_with("def ") {
+            class = None;
+            let (name, parameters) = signature();
+            module.insert(name, parameters);
+        } else if let (Some(class), true) = (&class, line.starts_with("    def ")) {
+            let (name, parameters) = signature();
+            let property = lines[i - 1].trim() == "@property";
+            let parameters = if property { Vec::new() } else { parameters };
+            classes.get_mut(class).unwrap().insert(name, parameters);
+        } else if !line.starts_with([' ', '#', '"', ')', '@']) && !line.is_empty() {
+            if let Some(name) = line.split([':', ' ']).next() {
+                if name != "from" && name != "import" {
+                    module.insert(name.to_string(), Vec::new());
+                }
+            }
+        }
+    }
+    (module, classes)
+}
+
+#[test]
+fn test_stub_matches_the_bindings() {
+    let (module, classes) = bindings();
+    let (stub_module, stub_classes) = stub();
+
+    let names = |names: &Names| names.keys().cloned().collect::<BTreeSet<_>>();
+    assert_eq!(names(&stub_module), names(&module));
+    assert_eq!(
+        stub_classes.keys().collect::<Vec<_>>(),
+        classes.keys().collect::<Vec<_>>()
+    );
+    // Special methods besides __init__ come from the base object type
+    let declared = |members: &Names| -> Names {
+        members
+            .iter()
+            .filter(|(name, _)| !name.starts_with("__") || *name == "__init__")
+            .map(|(name, parameters)| (name.clone(), parameters.clone()))
+            .collect()
+    };
+    for (class, members) in &classes {
+        assert_eq!(declared(&stub_classes[class]), declared(members), "{class}");
+    }
+    for (function, parameters) in &module {
+        assert_eq!(&stub_module[function], parameters, "{function}");
+    }
+}
+
+#[test]
+fn test_stub_is_read() {
+    let (module, classes) = stub();
+    assert!(module.contains_key("transliterate"));
+    assert!(classes["Shlesha"].contains_key("transliterate_with_metadata"));
+    assert_eq!(classes["TransliterationResult"].len(), 2);
+}
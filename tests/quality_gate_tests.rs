@@ -0,0 +1,106 @@
+//! Rejecting conversions below a fidelity threshold
+
+use shlesha::{QualityError, Shlesha};
+
+/// Eighteen tokens, one of them a character Devanagari cannot read
+const ONE_UNKNOWN: &str = "धर्मक्षेत्रे कुरु☺";
+
+#[test]
+fn test_clean_input_passes_at_full_fidelity() {
+    let shlesha = Shlesha::new();
+    let output = shlesha
+        .transliterate_strict("धर्मक्षेत्रे कुरुक्षेत्रे", "devanagari", "iast", 1.0)
+        .unwrap();
+    assert_eq!(output, "dharmakṣetre kurukṣetre");
+    assert_eq!(
+        shlesha
+            .transliterate_strict("", "devanagari", "iast", 1.0)
+            .unwrap(),
+        ""
+    );
+}
+
+#[test]
+fn test_one_unknown_character_fails_only_a_strict_threshold() {
+    let shlesha = Shlesha::new();
+    assert_eq!(
+        shlesha
+            .transliterate_strict(ONE_UNKNOWN, "devanagari", "iast", 0.9)
+            .unwrap(),
+        "dharmakṣetre kuru☺"
+    );
+
+    let error = shlesha
+        .transliterate_strict(ONE_UNKNOWN, "devanagari", "iast", 1.0)
+        .unwrap_err();
+    let QualityError::BelowThreshold {
+        fidelity,
+        min_fidelity,
+        unknown_tokens,
+        unknown_count,
+        lossy_count,
+        output,
+        ..
+    } = &error
+    else {
+        panic!("expected a fidelity error, got {error}");
+    };
+    assert!((fidelity - 17.0 / 18.0).abs() < 1e-9, "{fidelity}");
+    assert_eq!(*min_fidelity, 1.0);
+    assert_eq!(*unknown_count, 1);
+    assert_eq!(*lossy_count, 0);
+    assert_eq!(unknown_tokens.len(), 1);
+    assert_eq!(unknown_tokens[0].token, '☺');
+    assert_eq!(unknown_tokens[0].position, "धर्मक्षेत्रे कुरु".len());
+    assert_eq!(output, "dharmakṣetre kuru☺");
+    assert!(error.to_string().starts_with("fidelity 0.9444 is below 1"));
+}
+
+#[test]
+fn test_lossy_mappings_lower_the_fidelity() {
+    let shlesha = Shlesha::new();
+    let error = shlesha
+        .transliterate_strict("तत ᳵ", "devanagari", "iast", 1.0)
+        .unwrap_err();
+    let QualityError::BelowThreshold { lossy_mappings, .. } = error else {
+        panic!("expected a fidelity error, got {error}");
+    };
+    let lossy: Vec<_> = lossy_mappings
+        .iter()
+        .map(|mapping| (mapping.token.as_str(), mapping.substitute.as_str()))
+        .collect();
+    assert_eq!(lossy, [("MarkJihvamuliya", "MarkVisarga")]);
+}
+
+#[test]
+fn test_conversion_errors_are_reported_as_such() {
+    let shlesha = Shlesha::new();
+    let error = shlesha
+        .transliterate_strict("धर्म", "devanagari", "no_such_script", 0.0)
+        .unwrap_err();
+    assert!(matches!(error, QualityError::Conversion(_)), "{error}");
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_sets_aside_lines_below_min_fidelity() {
+    let dir = tempfile::tempdir().unwrap();
+    let errors = dir.path().join("errors.tsv");
+    let output = assert_cmd::Command::cargo_bin("shlesha")
+        .unwrap()
+        .args(["transliterate", "--from", "devanagari", "--to", "iast"])
+        .args(["--min-fidelity", "0.98", "--errors"])
+        .arg(&errors)
+        .write_stdin(format!("धर्म\n\n{ONE_UNKNOWN}\nकुरु\n"))
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    assert_eq!(String::from_utf8(output).unwrap(), "dharma\n\nkuru\n");
+    assert_eq!(
+        std::fs::read_to_string(&errors).unwrap(),
+        format!("3\t0.9444\t{ONE_UNKNOWN}\n")
+    );
+}
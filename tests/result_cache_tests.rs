@@ -0,0 +1,165 @@
+//! Memoized conversions with `Shlesha::enable_result_cache`
+
+mod common;
+
+use common::ascii_roman_schema;
+use shlesha::modules::result_cache::ResultCacheConfig;
+use shlesha::Shlesha;
+use std::sync::Arc;
+use std::thread;
+
+fn cached(capacity: usize) -> Shlesha {
+    let mut shlesha = Shlesha::new();
+    shlesha.enable_result_cache(capacity);
+    shlesha
+}
+
+fn counts(shlesha: &Shlesha) -> (u64, u64) {
+    let stats = shlesha.stats();
+    (stats.cache_hits, stats.cache_misses)
+}
+
+#[test]
+fn test_repeated_conversions_are_hits() {
+    let shlesha = cached(16);
+    for _ in 0..3 {
+        assert_eq!(
+            shlesha.transliterate("धर्म", "devanagari", "iast").unwrap(),
+            "dharma"
+        );
+    }
+    // The pair and the text make the key
+    assert_eq!(
+        shlesha.transliterate("धर्म", "devanagari", "slp1").unwrap(),
+        "Darma"
+    );
+    assert_eq!(counts(&shlesha), (2, 2));
+    #[cfg(feature = "stats")]
+    assert_eq!(shlesha.stats().conversions, 4);
+
+    shlesha.reset_stats();
+    assert_eq!(counts(&shlesha), (0, 0));
+    // Without the cache nothing is counted
+    assert_eq!(counts(&Shlesha::new()), (0, 0));
+}
+
+#[test]
+fn test_least_recently_used_entry_is_evicted() {
+    // One shard of two entries
+    let shlesha = cached(2);
+    let convert = |text: &str| shlesha.transliterate(text, "iast", "devanagari").unwrap();
+    convert("rāma");
+    convert("kṛṣṇa");
+    convert("rāma");
+    convert("sītā");
+    shlesha.reset_stats();
+
+    // kṛṣṇa was the least recently used of the three
+    convert("rāma");
+    convert("sītā");
+    assert_eq!(counts(&shlesha), (2, 0));
+    convert("kṛṣṇa");
+    assert_eq!(counts(&shlesha), (2, 1));
+}
+
+#[test]
+fn test_long_inputs_and_metadata_bypass_the_cache() {
+    let mut shlesha = Shlesha::new();
+    shlesha.enable_result_cache_with_config(ResultCacheConfig {
+        capacity: 16,
+        max_input_bytes: 8,
+    });
+    for _ in 0..2 {
+        shlesha
+            .transliterate("dharmakṣetre", "iast", "devanagari")
+            .unwrap();
+        shlesha
+            .transliterate_with_metadata("rāma", "iast", "devanagari")
+            .unwrap();
+    }
+    assert_eq!(counts(&shlesha), (0, 0));
+}
+
+#[test]
+fn test_schema_reload_invalidates() {
+    let mut shlesha = cached(16);
+    shlesha
+        .load_schema_from_string(&ascii_roman_schema("aa"), "ascii_roman")
+        .unwrap();
+    let convert = |shlesha: &Shlesha| {
+        shlesha
+            .transliterate("कामि", "devanagari", "ascii_roman")
+            .unwrap()
+    };
+    assert_eq!(convert(&shlesha), "kaami");
+    assert_eq!(convert(&shlesha), "kaami");
+
+    shlesha
+        .reload_schema("ascii_roman", &ascii_roman_schema("A"))
+        .unwrap();
+    assert_eq!(convert(&shlesha), "kAmi");
+    assert_eq!(counts(&shlesha), (1, 2));
+
+    // Removing the schema and loading it again also starts afresh
+    assert!(shlesha.remove_schema("ascii_roman"));
+    assert!(shlesha
+        .transliterate("कामि", "devanagari", "ascii_roman")
+        .is_err());
+    shlesha
+        .load_schema_from_string(&ascii_roman_schema("aa"), "ascii_roman")
+        .unwrap();
+    assert_eq!(convert(&shlesha), "kaami");
+}
+
+#[test]
+fn test_configuration_changes_invalidate() {
+    let mut shlesha = cached(16);
+    let convert = |shlesha: &Shlesha| shlesha.transliterate("अा", "devanagari", "iast").unwrap();
+    let repaired = convert(&shlesha);
+    assert_eq!(repaired, "ā");
+    shlesha.set_repair_input(false);
+    assert_ne!(convert(&shlesha), repaired);
+
+    shlesha
+        .add_word_override("devanagari", "iast", "अा", "aa")
+        .unwrap();
+    assert_eq!(convert(&shlesha), "aa");
+    assert_eq!(counts(&shlesha).0, 0);
+}
+
+#[test]
+fn test_concurrent_conversions_share_the_cache() {
+    let shlesha = Arc::new(cached(64));
+    let words = ["rāma", "sītā", "kṛṣṇa", "arjuna", "dharma"];
+    let expected: Vec<String> = words
+        .iter()
+        .map(|word| {
+            Shlesha::new()
+                .transliterate(word, "iast", "telugu")
+                .unwrap()
+        })
+        .collect();
+
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let shlesha = Arc::clone(&shlesha);
+            let expected = expected.clone();
+            thread::spawn(move || {
+                for round in 0..50 {
+                    let index = round % words.len();
+                    let output = shlesha
+                        .transliterate(words[index], "iast", "telugu")
+                        .unwrap();
+                    assert_eq!(output, expected[index]);
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    let (hits, misses) = counts(&shlesha);
+    assert_eq!(hits + misses, 400);
+    assert!(misses >= words.len() as u64 && hits > 0, "{hits} {misses}");
+}
@@ -0,0 +1,333 @@
+//! Runtime-loaded schemas through the plain and metadata conversion paths
+
+mod common;
+
+use common::ascii_roman_schema;
+use shlesha::modules::core::unknown_handler::UnknownStage;
+use shlesha::Shlesha;
+
+fn shlesha() -> Shlesha {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(&ascii_roman_schema("aa"), "ascii_roman")
+        .unwrap();
+    shlesha
+}
+
+#[test]
+fn test_metadata_path_converts_to_runtime_schema_alias() {
+    let shlesha = shlesha();
+
+    let plain = shlesha
+        .transliterate("धर्म काकि", "devanagari", "ar")
+        .unwrap();
+    assert_eq!(plain, "dharma kaaki");
+    assert_eq!(
+        shlesha
+            .transliterate("धर्म काकि", "devanagari", "ascii_roman")
+            .unwrap(),
+        plain
+    );
+
+    let result = shlesha
+        .transliterate_with_metadata("धर्म काकि", "devanagari", "ar")
+        .unwrap();
+    assert_eq!(result.output, plain);
+    let metadata = result.metadata.unwrap();
+    assert_eq!(metadata.source_script, "devanagari");
+    assert_eq!(metadata.target_script, "ar");
+    assert!(metadata.unknown_tokens.is_empty());
+}
+
+#[test]
+fn test_metadata_path_converts_from_runtime_schema_alias() {
+    let shlesha = shlesha();
+
+    let plain = shlesha.transliterate("dharma kaaki", "ar", "iast").unwrap();
+    assert_eq!(plain, "dharma kāki");
+    let result = shlesha
+        .transliterate_with_metadata("dharma kaaki", "ar", "iast")
+        .unwrap();
+    assert_eq!(result.output, plain);
+
+    // Characters the schema cannot read are reported where they were found
+    let result = shlesha
+        .transliterate_with_metadata("kaa ✓", "ar", "devanagari")
+        .unwrap();
+    assert_eq!(result.output, "का ✓");
+    let unknowns = result.metadata.unwrap().unknown_tokens;
+    assert_eq!(unknowns.len(), 1);
+    assert_eq!(unknowns[0].token, '✓');
+    assert_eq!(unknowns[0].position, 4);
+    assert_eq!(unknowns[0].stage, UnknownStage::SourceParsing);
+}
+
+#[test]
+fn test_tokens_missing_from_runtime_schema_are_reported() {
+    let shlesha = shlesha();
+
+    // The schema has no u
+    let result = shlesha
+        .transliterate_with_metadata("कु", "devanagari", "ar")
+        .unwrap();
+    assert_eq!(result.output, "k[VowelU]");
+    assert_eq!(
+        shlesha.transliterate("कु", "devanagari", "ar").unwrap(),
+        result.output
+    );
+    let unknowns = result.metadata.unwrap().unknown_tokens;
+    assert_eq!(unknowns.len(), 1);
+    assert_eq!(unknowns[0].hub_token.as_deref(), Some("VowelU"));
+    assert_eq!(unknowns[0].stage, UnknownStage::TargetRendering);
+}
+
+#[test]
+fn test_token_written_as_nothing() {
+    // Some romanizations and scripts leave the virama unwritten
+    let schema = r#"
+metadata:
+  name: "silent_virama"
+  script_type: "brahmic"
+  has_implicit_a: true
+target: "abugida_tokens"
+mappings:
+  consonants:
+    ConsonantDdh: "ध"
+    ConsonantR: "र"
+    ConsonantM: "म"
+  marks:
+    MarkVirama: ""
+"#;
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(schema, "silent_virama")
+        .unwrap();
+
+    let result = shlesha
+        .transliterate_with_metadata("dharma", "iast", "silent_virama")
+        .unwrap();
+    assert_eq!(result.output, "धरम");
+    assert!(result.metadata.unwrap().unknown_tokens.is_empty());
+
+    // Nothing is read as the virama
+    assert_eq!(
+        shlesha
+            .transliterate("धरम", "silent_virama", "iast")
+            .unwrap(),
+        "dharama"
+    );
+}
+
+#[test]
+fn test_implicit_a_follows_the_schema() {
+    // A syllabic ASCII scheme: k is ka, as in the Brahmic scripts
+    let syllabic = r#"
+metadata:
+  name: "syllabic"
+  script_type: "roman"
+  has_implicit_a: true
+target: "abugida_tokens"
+mappings:
+  vowel_signs:
+    VowelSignAa: "A"
+  consonants:
+    ConsonantK: "k"
+    ConsonantM: "m"
+  marks:
+    MarkVirama: "_"
+"#;
+    let mut shlesha = shlesha();
+    shlesha
+        .load_schema_from_string(syllabic, "syllabic")
+        .unwrap();
+    assert!(shlesha.script_has_implicit_a("syllabic"));
+    assert_eq!(
+        shlesha
+            .transliterate("km kAm_", "syllabic", "devanagari")
+            .unwrap(),
+        "कम काम्"
+    );
+    assert_eq!(
+        shlesha.transliterate("kama", "iast", "syllabic").unwrap(),
+        "km"
+    );
+
+    // The alphabet schema writes every vowel
+    assert!(!shlesha.script_has_implicit_a("ascii_roman"));
+    assert_eq!(
+        shlesha.transliterate("km", "ar", "devanagari").unwrap(),
+        "क्म्"
+    );
+    assert_eq!(
+        shlesha.transliterate("कम", "devanagari", "ar").unwrap(),
+        "kama"
+    );
+}
+
+#[test]
+fn test_builder_schema_declares_implicit_a() {
+    use shlesha::modules::schema::SchemaBuilder;
+
+    let mut shlesha = Shlesha::new();
+    shlesha.disable_runtime_compiler();
+    let schema = SchemaBuilder::new("syllabic")
+        .script_type("roman")
+        .has_implicit_a(true)
+        .add_consonant_mapping("ConsonantK", &["k"])
+        .add_consonant_mapping("ConsonantM", &["m"])
+        .build();
+    assert_eq!(schema.target, "abugida_tokens");
+    shlesha.add_runtime_schema(schema).unwrap();
+
+    assert!(shlesha.script_has_implicit_a("syllabic"));
+    assert_eq!(
+        shlesha.transliterate("km", "syllabic", "iast").unwrap(),
+        "kama"
+    );
+    assert!(shlesha.script_has_implicit_a("gurmukhi"));
+    assert!(!shlesha.script_has_implicit_a("iast"));
+    assert!(!shlesha.script_has_implicit_a("klingon"));
+}
+
+#[test]
+fn test_conjunct_stands_for_a_token_sequence() {
+    // x is kṣa, written as one letter in a syllabic scheme
+    let syllabic = r#"
+metadata:
+  name: "syllabic_x"
+  script_type: "roman"
+  has_implicit_a: true
+target: "abugida_tokens"
+mappings:
+  vowel_signs:
+    VowelSignI: "i"
+  consonants:
+    ConsonantK: "k"
+    ConsonantSs: "S"
+    ConsonantM: "m"
+  marks:
+    MarkVirama: "_"
+conjuncts:
+  "x": [ConsonantK, MarkVirama, ConsonantSs]
+read_conjuncts:
+  "kS": [ConsonantK, MarkVirama, ConsonantSs]
+"#;
+    let mut shlesha = shlesha();
+    shlesha
+        .load_schema_from_string(syllabic, "syllabic_x")
+        .unwrap();
+
+    for (text, devanagari) in [("xi", "क्षि"), ("mx", "मक्ष"), ("kSm", "क्षम")]
+    {
+        assert_eq!(
+            shlesha
+                .transliterate(text, "syllabic_x", "devanagari")
+                .unwrap(),
+            devanagari
+        );
+    }
+    for (devanagari, text) in [("क्षि", "xi"), ("मक्ष", "mx"), ("क्षम", "xm"), ("क्", "k_")]
+    {
+        assert_eq!(
+            shlesha
+                .transliterate(devanagari, "devanagari", "syllabic_x")
+                .unwrap(),
+            text
+        );
+    }
+
+    // One letter is read as the three tokens
+    use shlesha::modules::hub::{AbugidaToken, HubToken};
+    assert_eq!(
+        shlesha.read_tokens("x", "syllabic_x").unwrap(),
+        [
+            AbugidaToken::ConsonantK,
+            AbugidaToken::MarkVirama,
+            AbugidaToken::ConsonantSs
+        ]
+        .map(HubToken::Abugida)
+    );
+}
+
+#[test]
+fn test_builder_schema_conjuncts() {
+    use shlesha::modules::schema::SchemaBuilder;
+
+    let mut shlesha = Shlesha::new();
+    shlesha.disable_runtime_compiler();
+    let schema = SchemaBuilder::new("x_roman")
+        .script_type("roman")
+        .add_vowel_mapping("VowelA", &["a"])
+        .add_consonant_mapping("ConsonantK", &["k"])
+        .add_consonant_mapping("ConsonantSs", &["sh"])
+        .add_conjunct("x", &["ConsonantK", "ConsonantSs"])
+        .add_read_conjunct("ksh", &["ConsonantK", "ConsonantSs"])
+        .build();
+    shlesha.add_runtime_schema(schema).unwrap();
+
+    for text in ["xa", "ksha"] {
+        assert_eq!(
+            shlesha
+                .transliterate(text, "x_roman", "devanagari")
+                .unwrap(),
+            "क्ष"
+        );
+    }
+    assert_eq!(
+        shlesha
+            .transliterate("क्ष", "devanagari", "x_roman")
+            .unwrap(),
+        "xa"
+    );
+}
+
+#[test]
+fn test_conjunct_validation() {
+    let schema = |conjunct: &str| {
+        format!(
+            r#"
+metadata:
+  name: "bad_conjunct"
+  script_type: "roman"
+  has_implicit_a: false
+target: "alphabet_tokens"
+mappings:
+  consonants:
+    ConsonantK: "k"
+conjuncts:
+  "x": {conjunct}
+"#
+        )
+    };
+    let mut shlesha = Shlesha::new();
+    let error = shlesha
+        .load_schema_from_string(&schema("[ConsonantK, ConsonantSss]"), "bad_conjunct")
+        .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("\"ConsonantSss\" is not a token name"),
+        "{error}"
+    );
+
+    let error = shlesha
+        .load_schema_from_string(&schema("[ConsonantK]"), "bad_conjunct")
+        .unwrap_err();
+    assert!(error.to_string().contains("two or more tokens"), "{error}");
+
+    // Abugida tokens are not tokens of an alphabet schema
+    let error = shlesha
+        .load_schema_from_string(&schema("[ConsonantK, VowelSignI]"), "bad_conjunct")
+        .unwrap_err();
+    assert!(error.to_string().contains("VowelSignI"), "{error}");
+
+    use shlesha::modules::schema::SchemaBuilder;
+    shlesha.disable_runtime_compiler();
+    let schema = SchemaBuilder::new("bad_conjunct")
+        .script_type("roman")
+        .add_consonant_mapping("ConsonantK", &["k"])
+        .add_conjunct("x", &["ConsonantK", "ConsonantSss"])
+        .build();
+    assert!(shlesha.add_runtime_schema(schema).is_err());
+    assert!(!shlesha.supports_script("bad_conjunct"));
+}
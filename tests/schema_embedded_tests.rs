@@ -0,0 +1,116 @@
+//! Test cases schemas carry in their `tests` section
+
+use shlesha::modules::registry::{RegistryError, SchemaRegistry, SchemaRegistryTrait};
+use shlesha::Shlesha;
+
+/// A Roman scheme writing ā as "aa", with one case right and one wrong
+const DOUBLED_VOWELS: &str = r#"
+metadata:
+  name: "iast"
+  script_type: "roman"
+  has_implicit_a: false
+target: "alphabet_tokens"
+mappings:
+  vowels:
+    VowelA: "a"
+    VowelAa: "aa"
+  consonants:
+    ConsonantR: "r"
+    ConsonantM: "m"
+tests:
+  - input: "raama"
+    expected_devanagari: "राम"
+    expected_iso: "rāma"
+  - input: "ramaa"
+    expected_devanagari: "रम"
+"#;
+
+#[test]
+fn test_shipped_schema_tests_pass() {
+    let mut registry = SchemaRegistry::new();
+    let mut tested = Vec::new();
+    let mut paths: Vec<_> = std::fs::read_dir("schemas")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let name = registry.load_schema_file(&path).unwrap();
+        if registry.get_schema(&name).unwrap().tests.is_empty() {
+            continue;
+        }
+        let report = registry.run_schema_tests(&name).unwrap();
+        let failures: Vec<String> = report.failures.iter().map(|f| f.to_string()).collect();
+        assert!(report.is_success(), "{}", failures.join("\n\n"));
+        tested.push(name);
+    }
+
+    for seeded in ["iast", "telugu", "urdu"] {
+        assert!(tested.iter().any(|name| name == seeded), "{tested:?}");
+    }
+}
+
+#[test]
+fn test_cases_run_against_the_schema_not_the_compiled_in_script() {
+    let mut registry = SchemaRegistry::new();
+    registry
+        .load_schema_from_string(DOUBLED_VOWELS, "")
+        .unwrap();
+
+    let report = registry.run_schema_tests("iast").unwrap();
+    assert_eq!(report.checks, 3);
+    assert_eq!(report.summary(), "iast: 3 checks, 1 failures");
+
+    let failure = &report.failures[0];
+    assert_eq!(failure.case_index, 1);
+    assert_eq!(failure.to, "devanagari");
+    assert_eq!(failure.input, "ramaa");
+    assert_eq!(failure.expected, "रम");
+    assert_eq!(failure.actual, "रमा");
+    assert_eq!(failure.differences.len(), 1);
+    assert!(failure.to_string().contains("got VowelSignAa"), "{failure}");
+}
+
+#[test]
+fn test_conversion_ignores_the_tests_section() {
+    let mut shlesha = Shlesha::new();
+    let schema = DOUBLED_VOWELS.replace("\"iast\"", "\"doubled\"");
+    shlesha.load_schema_from_string(&schema, "doubled").unwrap();
+    assert_eq!(
+        shlesha
+            .transliterate("raama", "doubled", "devanagari")
+            .unwrap(),
+        "राम"
+    );
+}
+
+#[test]
+fn test_cases_need_an_expected_output() {
+    let schema = DOUBLED_VOWELS.replace("    expected_devanagari: \"रम\"\n", "");
+    let error = SchemaRegistry::parse_schema(&schema, "").unwrap_err();
+    assert!(
+        matches!(&error, RegistryError::InvalidSchema(message)
+            if message.contains("\"ramaa\" has neither expected_devanagari nor expected_iso")),
+        "{error}"
+    );
+
+    let error = SchemaRegistry::new()
+        .run_schema_tests("no_such_schema")
+        .unwrap_err();
+    assert!(matches!(error, RegistryError::SchemaNotFound(_)));
+}
+
+#[test]
+fn test_export_keeps_the_tests_section() {
+    let mut shlesha = Shlesha::new();
+    let schema = DOUBLED_VOWELS.replace("\"iast\"", "\"doubled\"");
+    shlesha.load_schema_from_string(&schema, "doubled").unwrap();
+
+    let exported =
+        SchemaRegistry::parse_schema(&shlesha.export_schema("doubled").unwrap(), "").unwrap();
+    let original = SchemaRegistry::parse_schema(&schema, "").unwrap();
+    assert_eq!(exported.tests, original.tests);
+    assert_eq!(exported.tests.len(), 2);
+}
@@ -0,0 +1,141 @@
+use shlesha::modules::conformance::load_case_file;
+use shlesha::Shlesha;
+use std::path::Path;
+
+/// The golden fixtures of `script`, both ways, as (from, to, inputs)
+fn golden_inputs(script: &str) -> Vec<(String, String, Vec<String>)> {
+    [("devanagari", script), (script, "devanagari")]
+        .into_iter()
+        .map(|(from, to)| {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/golden")
+                .join(format!("{from}-{to}.yaml"));
+            let file = load_case_file(&path).unwrap();
+            let inputs = file.cases.into_iter().map(|case| case.input).collect();
+            (from.to_string(), to.to_string(), inputs)
+        })
+        .collect()
+}
+
+/// Spellings read only as an alternate (IAST's "ṃ"), and rules (Malayalam's
+/// chillus), come through the export as well as plain mappings do
+#[test]
+fn test_exported_builtins_convert_as_the_originals() {
+    let builtin = Shlesha::new();
+    let mut exported = Shlesha::new();
+
+    for script in ["iast", "telugu", "malayalam"] {
+        let yaml = builtin.export_schema(script).unwrap();
+        let name = format!("exported_{script}");
+        exported.load_schema_from_string(&yaml, &name).unwrap();
+
+        for (from, to, inputs) in golden_inputs(script) {
+            let rename = |side: &str| {
+                if side == script {
+                    name.clone()
+                } else {
+                    side.to_string()
+                }
+            };
+            for input in inputs {
+                assert_eq!(
+                    exported
+                        .transliterate(&input, &rename(&from), &rename(&to))
+                        .unwrap(),
+                    builtin.transliterate(&input, &from, &to).unwrap(),
+                    "{from} → {to}: {input}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_export_lists_every_spelling() {
+    let shlesha = Shlesha::new();
+    let yaml = shlesha.export_schema("itrans").unwrap();
+    assert!(yaml.contains("  name: \"itrans\"\n"), "{yaml}");
+    assert!(yaml.contains("  aliases: [\"i-trans\"]\n"), "{yaml}");
+    assert!(yaml.contains("\ntarget: \"alphabet_tokens\"\n"), "{yaml}");
+    assert!(yaml.contains("  vowels:\n    VowelA: \"a\"\n    VowelAa: [\"aa\", \"A\"]\n"));
+    assert!(yaml.contains("    MarkAnusvara: [\"M\", \".N\", \".n\"]\n"));
+
+    let yaml = shlesha.export_schema("ml").unwrap();
+    assert!(yaml.contains("  script_type: \"brahmic\"\n  has_implicit_a: true\n"));
+    assert!(yaml.contains("\nrules:\n  write:\n"), "{yaml}");
+
+    assert!(shlesha.export_schema("klingon").is_err());
+}
+
+#[test]
+fn test_every_builtin_export_loads() {
+    let shlesha = Shlesha::new();
+    let mut loader = Shlesha::new();
+    for script in shlesha.list_supported_scripts() {
+        let Ok(yaml) = shlesha.export_schema(&script) else {
+            continue;
+        };
+        loader
+            .load_schema_from_string(&yaml, &format!("exported_{script}"))
+            .unwrap_or_else(|e| panic!("{script}: {e}\n{yaml}"));
+    }
+}
+
+#[test]
+fn test_runtime_schema_export_round_trips() {
+    let schema = r#"
+metadata:
+  name: "doubled"
+  script_type: "roman"
+  has_implicit_a: false
+  description: "Long vowels doubled"
+target: "alphabet_tokens"
+mappings:
+  vowels:
+    VowelA: "a"
+    VowelAa: ["aa", "A"]
+  consonants:
+    ConsonantK: "k"
+    ConsonantM: "m"
+rules:
+  write:
+    - tokens: [ConsonantM]
+      after: boundary
+      text: "M"
+"#;
+    let mut shlesha = Shlesha::new();
+    shlesha.load_schema_from_string(schema, "doubled").unwrap();
+    // Every spelling is read, the first written
+    assert_eq!(
+        shlesha
+            .transliterate("kAm", "doubled", "devanagari")
+            .unwrap(),
+        "काम्"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("काम्", "devanagari", "doubled")
+            .unwrap(),
+        "kaaM"
+    );
+
+    let yaml = shlesha.export_schema("doubled").unwrap();
+    assert!(
+        yaml.contains("  description: \"Long vowels doubled\"\n"),
+        "{yaml}"
+    );
+    assert!(yaml.contains("    VowelAa: [\"aa\", \"A\"]\n"), "{yaml}");
+    shlesha
+        .load_schema_from_string(&yaml, "doubled_again")
+        .unwrap();
+    // Exporting is a fixed point
+    assert_eq!(shlesha.export_schema("doubled_again").unwrap(), yaml);
+    for text in ["kAm", "kaam"] {
+        assert_eq!(
+            shlesha
+                .transliterate(text, "doubled_again", "iast")
+                .unwrap(),
+            shlesha.transliterate(text, "doubled", "iast").unwrap()
+        );
+    }
+}
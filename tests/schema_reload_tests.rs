@@ -0,0 +1,109 @@
+//! Replacing a runtime schema while conversions run
+
+mod common;
+
+use common::ascii_roman_schema;
+use shlesha::Shlesha;
+
+fn shlesha() -> Shlesha {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(&ascii_roman_schema("aa"), "ascii_roman")
+        .unwrap();
+    shlesha
+}
+
+#[test]
+fn test_reload_swaps_schema_and_bumps_revision() {
+    let shlesha = shlesha();
+    assert_eq!(shlesha.get_schema_info("ascii_roman").unwrap().revision, 1);
+
+    let info = shlesha
+        .reload_schema("ascii_roman", &ascii_roman_schema("A"))
+        .unwrap();
+    assert_eq!(info.revision, 2);
+    assert_eq!(info.mapping_count, 7);
+    assert_eq!(
+        shlesha
+            .transliterate("काकि", "devanagari", "ascii_roman")
+            .unwrap(),
+        "kAki"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("kAki", "ascii_roman", "devanagari")
+            .unwrap(),
+        "काकि"
+    );
+
+    // A mapping more
+    let extended =
+        ascii_roman_schema("aa").replace("    VowelI:", "    VowelU: \"u\"\n    VowelI:");
+    let info = shlesha.reload_schema("ascii_roman", &extended).unwrap();
+    assert_eq!(info.revision, 3);
+    assert_eq!(info.mapping_count, 8);
+}
+
+#[test]
+fn test_failed_reload_keeps_old_schema() {
+    let shlesha = shlesha();
+
+    // Unparseable, and parseable but invalid
+    assert!(shlesha.reload_schema("ascii_roman", "metadata: [").is_err());
+    let invalid = ascii_roman_schema("A").replace("\"roman\"", "\"klingon\"");
+    assert!(shlesha.reload_schema("ascii_roman", &invalid).is_err());
+
+    assert_eq!(shlesha.get_schema_info("ascii_roman").unwrap().revision, 1);
+    assert_eq!(
+        shlesha
+            .transliterate("काकि", "devanagari", "ascii_roman")
+            .unwrap(),
+        "kaaki"
+    );
+
+    // Only loaded runtime schemas can be reloaded
+    assert!(shlesha
+        .reload_schema("not_loaded", &ascii_roman_schema("A"))
+        .is_err());
+    assert!(shlesha
+        .reload_schema("iast", &ascii_roman_schema("A"))
+        .is_err());
+}
+
+#[test]
+fn test_conversions_see_one_version_during_reloads() {
+    let shlesha = shlesha();
+    let versions = ["dharma kaaki", "dharma kAki"];
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..200 {
+                    let output = shlesha
+                        .transliterate("धर्म काकि", "devanagari", "ascii_roman")
+                        .unwrap();
+                    assert!(versions.contains(&output.as_str()), "{output}");
+
+                    let result = shlesha
+                        .transliterate_with_metadata("धर्म काकि", "devanagari", "ascii_roman")
+                        .unwrap();
+                    assert!(versions.contains(&result.output.as_str()));
+                    assert!(result.metadata.unwrap().unknown_tokens.is_empty());
+                }
+            });
+        }
+        scope.spawn(|| {
+            for i in 0..100 {
+                let long_a = if i % 2 == 0 { "A" } else { "aa" };
+                shlesha
+                    .reload_schema("ascii_roman", &ascii_roman_schema(long_a))
+                    .unwrap();
+            }
+        });
+    });
+
+    assert_eq!(
+        shlesha.get_schema_info("ascii_roman").unwrap().revision,
+        101
+    );
+}
@@ -0,0 +1,160 @@
+//! Context-sensitive spellings from the `rules` section of schemas
+
+use shlesha::Shlesha;
+
+#[test]
+fn test_malayalam_chillus() {
+    let shlesha = Shlesha::new();
+
+    // A dead consonant ending a word is a chillu; inside a word it keeps the candrakkala
+    assert_eq!(
+        shlesha
+            .transliterate("अवन् कमल् बाल् कर्णः पर्वतः वाक्", "devanagari", "malayalam")
+            .unwrap(),
+        "അവൻ കമൽ ബാൽ കര്ണഃ പര്വതഃ വാക്"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("kaṇ avan avar kamal kaḻ", "iast", "ml")
+            .unwrap(),
+        "കൺ അവൻ അവർ കമൽ കൾ"
+    );
+
+    // Chillus, and their older spellings with ZWJ, read as the dead consonant
+    for malayalam in [
+        "കൺ അവൻ അവർ കമൽ കൾ വാൿ",
+        "ക\u{0D23}\u{0D4D}\u{200D} അവ\u{0D28}\u{0D4D}\u{200D} അവ\u{0D30}\u{0D4D}\u{200D} \
+         കമ\u{0D32}\u{0D4D}\u{200D} ക\u{0D33}\u{0D4D}\u{200D} വാ\u{0D15}\u{0D4D}\u{200D}",
+    ] {
+        assert_eq!(
+            shlesha
+                .transliterate(malayalam, "malayalam", "iast")
+                .unwrap(),
+            "kaṇ avan avar kamal kaḻ vāk"
+        );
+    }
+    // A chillu before a consonant is still a dead consonant
+    assert_eq!(
+        shlesha
+            .transliterate("അവൻമാർ", "malayalam", "devanagari")
+            .unwrap(),
+        "अवन्मार्"
+    );
+}
+
+#[test]
+fn test_gurmukhi_tippi_and_bindi() {
+    let shlesha = Shlesha::new();
+
+    // Tippi after a short vowel and dulainkar, bindi after the other vowels
+    assert_eq!(
+        shlesha
+            .transliterate(
+                "saṃta kiṃ kuṃ kūṃ kṛṃ aṃ kāṃ kīṃ keṃ kaiṃ koṃ kauṃ āṃ",
+                "iast",
+                "gurmukhi"
+            )
+            .unwrap(),
+        "ਸੰਤ ਕਿੰ ਕੁੰ ਕੂੰ ਕ੍ਰਿੰ ਅੰ ਕਾਂ ਕੀਂ ਕੇਂ ਕੈਂ ਕੋਂ ਕੌਂ ਆਂ"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("संस्कृतं ह्रीं", "devanagari", "guru")
+            .unwrap(),
+        "ਸੰਸ੍ਕ੍ਰਿਤੰ ਹ੍ਰੀਂ"
+    );
+
+    // Either mark reads as anusvara, wherever it stands
+    assert_eq!(
+        shlesha
+            .transliterate("ਸੰਤ ਸਂਤ ਕਾਂ ਕਾੰ", "gurmukhi", "iast")
+            .unwrap(),
+        "saṁta saṁta kāṁ kāṁ"
+    );
+}
+
+/// A Roman scheme writing anusvara as n before a velar, and reading it back there only
+const RULED_SCHEMA: &str = r#"
+metadata:
+  name: "ruled_roman"
+  script_type: "roman"
+  has_implicit_a: false
+target: "alphabet_tokens"
+mappings:
+  vowels:
+    VowelA: "a"
+    VowelAa: "aa"
+  consonants:
+    ConsonantK: "k"
+    ConsonantG: "g"
+    ConsonantS: "s"
+    ConsonantM: "m"
+  marks:
+    MarkAnusvara: "M"
+rules:
+  write:
+    - tokens: [MarkAnusvara]
+      after: [ConsonantK, ConsonantG]
+      text: "n"
+  read:
+    - tokens: [MarkAnusvara]
+      before: vowel
+      after: [ConsonantK, ConsonantG]
+      text: "n"
+"#;
+
+#[test]
+fn test_runtime_schema_rules() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(RULED_SCHEMA, "ruled_roman")
+        .unwrap();
+
+    assert_eq!(
+        shlesha
+            .transliterate("saṃgam saṃsa", "iast", "ruled_roman")
+            .unwrap(),
+        "sangam saMsa"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("sangam saMsa", "ruled_roman", "iast")
+            .unwrap(),
+        "saṁgam saṁsa"
+    );
+    // Out of context, n is not anusvara, and this scheme has no other reading of it
+    let result = shlesha
+        .transliterate_with_metadata("nasa", "ruled_roman", "iast")
+        .unwrap();
+    assert_eq!(result.output, "nasa");
+    assert_eq!(result.metadata.unwrap().unknown_tokens.len(), 1);
+}
+
+#[test]
+fn test_invalid_rules_are_rejected() {
+    let mut shlesha = Shlesha::new();
+
+    for (rule, problem) in [
+        (
+            "    - tokens: [MarkAnusvaraa]\n      text: \"n\"",
+            "\"MarkAnusvaraa\" is not a token name",
+        ),
+        (
+            "    - tokens: [MarkAnusvara]\n      after: velar\n      text: \"n\"",
+            "\"velar\" is neither a token class nor a token name",
+        ),
+        (
+            "    - tokens: [MarkAnusvara]\n      text: \"\"",
+            "must spell one or more tokens",
+        ),
+    ] {
+        let schema = RULED_SCHEMA.split("rules:").next().unwrap().to_string()
+            + "rules:\n  write:\n"
+            + rule
+            + "\n";
+        let error = shlesha
+            .load_schema_from_string(&schema, "ruled_roman")
+            .unwrap_err();
+        assert!(error.to_string().contains(problem), "{error}");
+    }
+}
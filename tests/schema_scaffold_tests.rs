@@ -0,0 +1,166 @@
+//! Schema skeletons from the hub token inventory, and schema diffs
+
+use shlesha::modules::hub::tokens::{AbugidaToken, AlphabetToken, HubFormat, TokenCategory};
+use shlesha::modules::schema::scaffold::{diff_schemas, scaffold_schema, ScaffoldError};
+use shlesha::modules::schema::Schema;
+use shlesha::Shlesha;
+
+/// Replace every empty mapping with a private-use character of its own
+fn fill_placeholders(yaml: &str) -> String {
+    let mut next = 0xE000;
+    yaml.lines()
+        .map(|line| {
+            if line.starts_with("    ") && line.ends_with(": \"\"") {
+                let dummy = char::from_u32(next).unwrap();
+                next += 1;
+                format!("{}\"{dummy}\"\n", line.trim_end_matches("\"\""))
+            } else {
+                format!("{line}\n")
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_inventory_lists_every_token_by_category() {
+    for (category, tokens) in AbugidaToken::INVENTORY {
+        for token in tokens.iter() {
+            let parsed: AbugidaToken = token.parse().unwrap();
+            assert_eq!(
+                parsed.is_vowel_sign(),
+                *category == TokenCategory::VowelSigns
+            );
+            assert_eq!(
+                parsed.is_consonant(),
+                *category == TokenCategory::Consonants
+            );
+        }
+    }
+    for (_, tokens) in AlphabetToken::INVENTORY {
+        for token in tokens.iter() {
+            assert!(token.parse::<AlphabetToken>().is_ok(), "{token}");
+        }
+    }
+
+    // Alphabet tokens have no vowel signs
+    let categories: Vec<_> = HubFormat::Alphabet
+        .inventory()
+        .iter()
+        .map(|(category, _)| *category)
+        .collect();
+    assert!(!categories.contains(&TokenCategory::VowelSigns));
+
+    for category in TokenCategory::ALL {
+        assert_eq!(TokenCategory::from_key(category.key()), Some(category));
+    }
+    assert_eq!(TokenCategory::from_key("punctuation"), None);
+}
+
+#[test]
+fn test_scaffold_lists_every_token() {
+    let yaml = scaffold_schema("tulu", "brahmic", None).unwrap();
+    let schema = Schema::from_yaml_str(&yaml).unwrap();
+    assert_eq!(schema.target, "abugida_tokens");
+
+    for (category, tokens) in AbugidaToken::INVENTORY {
+        let mappings = &schema.mappings[category.key()];
+        assert_eq!(mappings.len(), tokens.len(), "{}", category.key());
+        assert!(mappings.values().all(|value| value == ""));
+    }
+}
+
+#[test]
+fn test_filled_scaffold_loads() {
+    for script_type in ["brahmic", "roman"] {
+        let name = format!("scaffold_{script_type}");
+        let yaml = fill_placeholders(&scaffold_schema(&name, script_type, None).unwrap());
+
+        let mut shlesha = Shlesha::new();
+        shlesha.load_schema_from_string(&yaml, &name).unwrap();
+        assert!(shlesha.list_supported_scripts().contains(&name));
+    }
+}
+
+#[test]
+fn test_scaffold_like_copies_existing_script() {
+    let yaml = scaffold_schema("tulu", "brahmic", Some("telugu")).unwrap();
+    assert!(yaml.contains("    ConsonantK: \"క\"\n"));
+    assert!(yaml.contains("    VowelSignAa: \"ా\"\n"));
+    // Telugu has no candra o sign, which is left to be filled in
+    assert!(yaml.contains("    VowelSignCandraO: \"\"\n"));
+
+    let roman = scaffold_schema("new_roman", "roman", Some("iast")).unwrap();
+    assert!(roman.contains("    VowelAa: \"ā\"\n"));
+    assert!(roman.contains("target: \"alphabet_tokens\""));
+
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(&fill_placeholders(&yaml), "tulu")
+        .unwrap();
+    shlesha
+        .load_schema_from_string(&fill_placeholders(&roman), "new_roman")
+        .unwrap();
+    assert_eq!(
+        shlesha.transliterate("ధర్మ", "tulu", "devanagari").unwrap(),
+        "धर्म"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("dharma", "new_roman", "devanagari")
+            .unwrap(),
+        "धर्म"
+    );
+}
+
+#[test]
+fn test_scaffold_errors() {
+    assert_eq!(
+        scaffold_schema("x", "syllabary", None),
+        Err(ScaffoldError::UnknownScriptType("syllabary".to_string()))
+    );
+    assert_eq!(
+        scaffold_schema("x", "brahmic", Some("klingon")),
+        Err(ScaffoldError::UnknownScript("klingon".to_string()))
+    );
+    assert_eq!(
+        scaffold_schema("x", "roman", Some("telugu")),
+        Err(ScaffoldError::ScriptTypeMismatch {
+            like: "telugu".to_string(),
+            script_type: "roman".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_diff_lists_missing_tokens_in_schema_order() {
+    let devanagari = Schema::from_yaml_str(include_str!("../schemas/devanagari.yaml")).unwrap();
+    let sharada = Schema::from_yaml_str(include_str!("../schemas/sharada.yaml")).unwrap();
+
+    let diff = diff_schemas(&devanagari, &sharada);
+    let only_devanagari: Vec<_> = diff
+        .only_in_first
+        .iter()
+        .map(|(category, token)| format!("{category}.{token}"))
+        .collect();
+    // Sharada has no candra vowels, and writes only the long e and o
+    assert_eq!(
+        only_devanagari[..8],
+        [
+            "vowels.VowelCandraE",
+            "vowels.VowelCandraO",
+            "vowels.VowelE",
+            "vowels.VowelO",
+            "vowel_signs.VowelSignCandraE",
+            "vowel_signs.VowelSignCandraO",
+            "vowel_signs.VowelSignE",
+            "vowel_signs.VowelSignO"
+        ]
+    );
+    // Categories the hub does not know come last
+    assert_eq!(
+        diff.only_in_second.last().unwrap(),
+        &("punctuation".to_string(), "PuncDoubleDanda".to_string())
+    );
+
+    assert!(diff_schemas(&sharada, &sharada).is_empty());
+}
@@ -0,0 +1,161 @@
+//! Every name `supports_script` accepts converts, by every entry point
+
+use shlesha::Shlesha;
+
+/// A runtime schema reading alphabet tokens, answering to `aliases`
+fn roman_schema(name: &str, aliases: &str) -> String {
+    format!(
+        r#"
+metadata:
+  name: "{name}"
+  script_type: "roman"
+  has_implicit_a: false
+  aliases: [{aliases}]
+target: "alphabet_tokens"
+mappings:
+  vowels:
+    VowelA: "a"
+    VowelI: "i"
+  consonants:
+    ConsonantK: "k"
+    ConsonantM: "m"
+"#
+    )
+}
+
+/// A runtime schema reading abugida tokens, answering to `aliases`
+fn abugida_schema(name: &str, aliases: &str) -> String {
+    format!(
+        r#"
+metadata:
+  name: "{name}"
+  script_type: "brahmic"
+  has_implicit_a: true
+  aliases: [{aliases}]
+target: "abugida_tokens"
+mappings:
+  vowels:
+    VowelA: ""
+  consonants:
+    ConsonantK: ""
+"#
+    )
+}
+
+/// An instance with runtime schemas whose aliases are their own, shared with
+/// each other, or also aliases of built-in scripts
+fn with_runtime_aliases() -> Shlesha {
+    let mut shlesha = Shlesha::new();
+    for (yaml, name) in [
+        (
+            roman_schema("plain_roman", r#""pr", "shared""#),
+            "plain_roman",
+        ),
+        (
+            abugida_schema("private_abugida", r#""pa", "shared""#),
+            "private_abugida",
+        ),
+        (
+            roman_schema("shadowing", r#""hk", "bangla", "deva""#),
+            "shadowing",
+        ),
+        (roman_schema("hk", r#""my_hk""#), "hk"),
+    ] {
+        shlesha.load_schema_from_string(&yaml, name).unwrap();
+    }
+    shlesha
+}
+
+/// Every name, alias and case variant listed for the instance's scripts
+fn names(shlesha: &Shlesha) -> Vec<String> {
+    let mut names: Vec<String> = shlesha
+        .list_scripts_detailed()
+        .into_iter()
+        .flat_map(|listing| std::iter::once(listing.name).chain(listing.aliases))
+        .collect();
+    names.extend(
+        [
+            "Devanagari",
+            "DEVA",
+            "pr",
+            "pa",
+            "shared",
+            "my_hk",
+            "hk",
+            "bangla",
+        ]
+        .map(str::to_string),
+    );
+    names
+}
+
+fn assert_every_supported_name_converts(shlesha: &Shlesha) {
+    let mut checked = 0;
+    for name in names(shlesha) {
+        if !shlesha.supports_script(&name) {
+            continue;
+        }
+        checked += 1;
+        let sample = shlesha
+            .transliterate("अ", "devanagari", &name)
+            .unwrap_or_else(|e| panic!("devanagari → {name}: {e}"));
+        shlesha
+            .transliterate(&sample, &name, "devanagari")
+            .unwrap_or_else(|e| panic!("{name} → devanagari: {e}"));
+
+        // The metadata path resolves the name as the plain path does
+        let to = shlesha
+            .transliterate_with_metadata("अ", "devanagari", &name)
+            .unwrap_or_else(|e| panic!("devanagari → {name} with metadata: {e}"));
+        assert_eq!(to.output, sample, "devanagari → {name}");
+        let from = shlesha
+            .transliterate_with_metadata(&sample, &name, "devanagari")
+            .unwrap_or_else(|e| panic!("{name} → devanagari with metadata: {e}"));
+        assert_eq!(
+            from.output,
+            shlesha.transliterate(&sample, &name, "devanagari").unwrap(),
+            "{name} → devanagari"
+        );
+        shlesha
+            .read_tokens(&sample, &name)
+            .unwrap_or_else(|e| panic!("reading {name}: {e}"));
+    }
+    assert!(checked > 20, "only {checked} names checked");
+}
+
+#[test]
+fn test_builtin_names_and_aliases_convert() {
+    assert_every_supported_name_converts(&Shlesha::new());
+}
+
+#[test]
+fn test_runtime_aliases_convert() {
+    assert_every_supported_name_converts(&with_runtime_aliases());
+}
+
+#[test]
+fn test_names_resolve_in_one_order() {
+    let shlesha = with_runtime_aliases();
+    let convert = |from: &str| shlesha.transliterate("ka", from, "devanagari").unwrap();
+
+    // A built-in script's aliases come before a runtime schema declaring them
+    assert_eq!(convert("deva"), "ka");
+    assert_eq!(
+        convert("bangla"),
+        shlesha
+            .transliterate("ka", "bengali", "devanagari")
+            .unwrap()
+    );
+    assert_eq!(convert("hk"), convert("harvard_kyoto"));
+    // A runtime schema named after a built-in alias answers for that script
+    assert_eq!(convert("my_hk"), convert("harvard_kyoto"));
+    // An alias two runtime schemas declare is the first one's in name order
+    assert_eq!(convert("shared"), convert("plain_roman"));
+    assert_eq!(
+        shlesha
+            .transliterate_with_metadata("ka", "shared", "devanagari")
+            .unwrap()
+            .output,
+        convert("plain_roman")
+    );
+}
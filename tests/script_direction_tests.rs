@@ -0,0 +1,115 @@
+use shlesha::{Shlesha, ShleshaBuilder, TextDirection, TransliterationOptions};
+
+const RTL_SCHEMA: &str = r#"
+metadata:
+  name: "mirror_roman"
+  script_type: "roman"
+  has_implicit_a: false
+  direction: "rtl"
+  rendering_notes: "Lines run right to left"
+target: "alphabet_tokens"
+mappings:
+  vowels:
+    VowelA: "a"
+  consonants:
+    ConsonantK: "k"
+    ConsonantM: "m"
+"#;
+
+fn wrapping() -> TransliterationOptions {
+    TransliterationOptions {
+        wrap_directional_isolates: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_runtime_schema_direction_surfaces() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(RTL_SCHEMA, "mirror_roman")
+        .unwrap();
+
+    let info = shlesha.get_schema_info("mirror_roman").unwrap();
+    assert_eq!(info.direction, TextDirection::Rtl);
+    assert_eq!(
+        info.rendering_notes.as_deref(),
+        Some("Lines run right to left")
+    );
+    assert_eq!(shlesha.script_direction("mirror_roman"), TextDirection::Rtl);
+
+    let listing = shlesha
+        .list_scripts_detailed()
+        .into_iter()
+        .find(|listing| listing.name == "mirror_roman")
+        .unwrap();
+    assert_eq!(listing.direction, TextDirection::Rtl);
+}
+
+#[test]
+fn test_builtin_directions() {
+    let shlesha = Shlesha::new();
+    assert_eq!(shlesha.script_direction("kharoshthi"), TextDirection::Rtl);
+    assert_eq!(shlesha.script_direction("khar"), TextDirection::Rtl);
+    assert_eq!(shlesha.script_direction("urdu"), TextDirection::Rtl);
+    assert_eq!(shlesha.script_direction("ur"), TextDirection::Rtl);
+    assert_eq!(shlesha.script_direction("devanagari"), TextDirection::Ltr);
+    assert_eq!(
+        shlesha.script_direction("no_such_script"),
+        TextDirection::Ltr
+    );
+
+    for listing in shlesha.list_scripts_detailed() {
+        let expected = if matches!(listing.name.as_str(), "kharoshthi" | "urdu") {
+            TextDirection::Rtl
+        } else {
+            TextDirection::Ltr
+        };
+        assert_eq!(listing.direction, expected, "{}", listing.name);
+    }
+}
+
+#[test]
+fn test_rtl_output_is_wrapped_in_isolates() {
+    let shlesha = Shlesha::new();
+
+    let output = shlesha
+        .transliterate_with_options("dharma", "iast", "kharoshthi", &wrapping())
+        .unwrap();
+    assert_eq!(output, "\u{2068}𐨢𐨪𐨿𐨨\u{2069}");
+
+    let result = shlesha
+        .transliterate_with_options_and_metadata("dharma", "iast", "khar", &wrapping())
+        .unwrap();
+    assert_eq!(result.output, output);
+
+    // Left-to-right output, and right-to-left output without the option, is left alone
+    assert_eq!(
+        shlesha
+            .transliterate_with_options("dharma", "iast", "devanagari", &wrapping())
+            .unwrap(),
+        "धर्म"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("dharma", "iast", "kharoshthi")
+            .unwrap(),
+        "𐨢𐨪𐨿𐨨"
+    );
+}
+
+#[test]
+fn test_default_options_wrap_transliterate() {
+    let shlesha = ShleshaBuilder::new()
+        .with_schema_str(RTL_SCHEMA, "mirror_roman")
+        .with_options(wrapping())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        shlesha
+            .transliterate("कम", "devanagari", "mirror_roman")
+            .unwrap(),
+        "\u{2068}kama\u{2069}"
+    );
+}
@@ -0,0 +1,161 @@
+use shlesha::Shlesha;
+
+/// Canonical names of the scripts a default build supports
+const DEFAULT_SCRIPTS: &[&str] = &[
+    "balinese",
+    "baraha",
+    "bengali",
+    "bhaiksuki",
+    "bharati_braille",
+    "brahmi",
+    "chakma",
+    "devanagari",
+    "dogra",
+    "grantha",
+    "gujarati",
+    "gurmukhi",
+    "harvard_kyoto",
+    "iast",
+    "iso15919",
+    "itrans",
+    "kaithi",
+    "kannada",
+    "kharoshthi",
+    "khmer",
+    "kolkata",
+    "malayalam",
+    "modi",
+    "nandinagari",
+    "newa",
+    "odia",
+    "sharada",
+    "siddham",
+    "sinhala",
+    "slp1",
+    "tai_tham",
+    "takri",
+    "tamil",
+    "telugu",
+    "thai",
+    "tibetan",
+    "urdu",
+    "velthuis",
+    "wx",
+];
+
+#[test]
+fn test_default_build_lists_canonical_names() {
+    let shlesha = Shlesha::new();
+    assert_eq!(shlesha.list_supported_scripts(), DEFAULT_SCRIPTS);
+}
+
+#[test]
+fn test_detailed_listing_carries_aliases() {
+    let shlesha = Shlesha::new();
+    let listings = shlesha.list_scripts_detailed();
+
+    let names: Vec<_> = listings.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(names, DEFAULT_SCRIPTS);
+
+    let aliases = |name: &str| {
+        listings
+            .iter()
+            .find(|listing| listing.name == name)
+            .map(|listing| listing.aliases.clone())
+            .unwrap()
+    };
+    assert_eq!(aliases("iso15919"), ["iso"]);
+    assert_eq!(aliases("devanagari"), ["deva"]);
+    assert_eq!(aliases("tibetan"), ["bo", "tibt"]);
+    assert!(aliases("iast").is_empty());
+
+    // Every alias still converts
+    for listing in &listings {
+        for alias in &listing.aliases {
+            assert!(shlesha.supports_script(alias), "{alias}");
+        }
+    }
+}
+
+#[test]
+fn test_every_schema_alias_converts_as_its_script() {
+    let shlesha = Shlesha::new();
+    let sample = "धर्मक्षेत्रे कुरुक्षेत्रे १२";
+
+    let mut checked = 0;
+    for entry in std::fs::read_dir("schemas").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|ext| ext != "yaml") {
+            continue;
+        }
+        let yaml: serde_yaml::Value =
+            serde_yaml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let name = yaml["metadata"]["name"].as_str().unwrap();
+        let Some(aliases) = yaml["metadata"]["aliases"].as_sequence() else {
+            continue;
+        };
+        // Scripts whose feature group is left out of the build are skipped
+        if !shlesha.supports_script(name) {
+            continue;
+        }
+
+        let written = shlesha.transliterate(sample, "devanagari", name).unwrap();
+        let read = shlesha.transliterate(&written, name, "iast").unwrap();
+        for alias in aliases {
+            let alias = alias.as_str().unwrap();
+            assert!(shlesha.supports_script(alias), "{alias}");
+            assert_eq!(
+                shlesha.transliterate(sample, "devanagari", alias).unwrap(),
+                written,
+                "{alias}"
+            );
+            assert_eq!(
+                shlesha.transliterate(&written, alias, "iast").unwrap(),
+                read,
+                "{alias}"
+            );
+            checked += 1;
+        }
+    }
+    assert!(checked > 30, "{checked}");
+}
+
+#[test]
+fn test_runtime_schemas_join_the_listing_once() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(
+            r#"
+metadata:
+  name: "doubled"
+  script_type: "roman"
+  has_implicit_a: false
+  aliases: ["dbl"]
+target: "iso15919"
+mappings:
+  vowels:
+    "a": "a"
+    "aa": "ā"
+"#,
+            "doubled",
+        )
+        .unwrap();
+    // The token schemas of the schemas directory are not scripts
+    for internal in ["abugida_tokens", "alphabet_tokens"] {
+        shlesha
+            .load_schema_from_file(&format!("schemas/{internal}.yaml"))
+            .unwrap();
+    }
+
+    let scripts = shlesha.list_supported_scripts();
+    assert_eq!(scripts.len(), DEFAULT_SCRIPTS.len() + 1);
+    assert!(scripts.contains(&"doubled".to_string()));
+    assert!(!scripts.iter().any(|script| script.ends_with("_tokens")));
+
+    let doubled = shlesha
+        .list_scripts_detailed()
+        .into_iter()
+        .find(|listing| listing.name == "doubled")
+        .unwrap();
+    assert_eq!(doubled.aliases, ["dbl"]);
+}
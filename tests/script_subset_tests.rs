@@ -0,0 +1,55 @@
+//! A build with only the core scripts compiled in:
+//!
+//! cargo test --no-default-features --features scripts-core --test script_subset_tests
+#![cfg(all(
+    feature = "scripts-core",
+    not(feature = "scripts-north"),
+    not(feature = "scripts-south"),
+    not(feature = "scripts-historic")
+))]
+
+use shlesha::Shlesha;
+
+#[test]
+fn test_core_build_lists_exactly_core_scripts() {
+    let scripts = Shlesha::new().list_supported_scripts();
+    assert_eq!(
+        scripts,
+        [
+            "baraha",
+            "devanagari",
+            "harvard_kyoto",
+            "iast",
+            "iso15919",
+            "itrans",
+            "kolkata",
+            "slp1",
+            "velthuis",
+            "wx",
+        ]
+    );
+}
+
+#[test]
+fn test_core_build_converts_core_scripts_only() {
+    let shlesha = Shlesha::new();
+    assert_eq!(
+        shlesha
+            .transliterate("dharma", "iast", "devanagari")
+            .unwrap(),
+        "धर्म"
+    );
+    assert_eq!(
+        shlesha.transliterate("धर्म", "devanagari", "slp1").unwrap(),
+        "Darma"
+    );
+
+    for script in ["telugu", "bengali", "grantha"] {
+        assert!(!shlesha.supports_script(script), "{script}");
+        let error = shlesha
+            .transliterate("dharma", "iast", script)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("No converter found"), "{error}");
+    }
+}
@@ -0,0 +1,123 @@
+use shlesha::{Shlesha, UnknownStage};
+
+/// A document whose parts were tagged by a language identifier upstream
+const DOCUMENT: &[(&str, Option<&str>)] = &[
+    ("<p>The verse reads ", None),
+    ("धर्मक्षेत्रे कुरुक्षेत्रे", Some("devanagari")),
+    (", or in IAST ", None),
+    ("dharmakṣetre kurukṣetre", Some("iast")),
+    (", and in Telugu ", None),
+    ("ధర్మక్షేత్రే", Some("telugu")),
+    ("</p>", None),
+];
+
+#[test]
+fn test_segments_match_per_segment_conversion() {
+    let shlesha = Shlesha::new();
+
+    let output = shlesha
+        .transliterate_segments(DOCUMENT.iter().copied(), "slp1")
+        .unwrap();
+
+    let expected: String = DOCUMENT
+        .iter()
+        .map(|&(text, script)| match script {
+            Some(from) => shlesha.transliterate(text, from, "slp1").unwrap(),
+            None => text.to_string(),
+        })
+        .collect();
+    assert_eq!(output, expected);
+    assert_eq!(
+        output,
+        "<p>The verse reads Darmakzetre kurukzetre, or in IAST Darmakzetre kurukzetre, \
+         and in Telugu Darmakzetre</p>"
+    );
+}
+
+#[test]
+fn test_segments_resolve_aliases() {
+    let shlesha = Shlesha::new();
+
+    let segments = [
+        ("नमः", Some("deva")),
+        (" ", None),
+        ("namaH", Some("itrans")),
+    ];
+    assert_eq!(
+        shlesha.transliterate_segments(segments, "iast").unwrap(),
+        "namaḥ namaḥ"
+    );
+}
+
+#[test]
+fn test_segment_metadata_carries_segment_index() {
+    let shlesha = Shlesha::new();
+
+    let segments = [
+        ("See ", None),
+        ("राम", Some("devanagari")),
+        (" and ", None),
+        ("rāma§", Some("iast")),
+    ];
+    let result = shlesha
+        .transliterate_segments_with_metadata(segments, "devanagari")
+        .unwrap();
+
+    assert_eq!(
+        result.output,
+        shlesha
+            .transliterate_segments(segments, "devanagari")
+            .unwrap()
+    );
+
+    // Only tagged segments have metadata, with their place in the output
+    let placed: Vec<_> = result
+        .segments
+        .iter()
+        .map(|segment| (segment.index, segment.output_offset))
+        .collect();
+    assert_eq!(placed, [(1, 4), (3, "See राम and ".len())]);
+
+    let unknowns: Vec<_> = result
+        .unknown_tokens()
+        .filter(|(_, token)| token.stage == UnknownStage::SourceParsing)
+        .map(|(index, token)| (index, token.token, token.position))
+        .collect();
+    assert_eq!(unknowns, [(3, '§', "rāma".len())]);
+}
+
+#[test]
+fn test_segments_reject_unknown_script() {
+    let shlesha = Shlesha::new();
+
+    let error = shlesha
+        .transliterate_segments([("a", None), ("b", Some("klingon"))], "devanagari")
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Segment 1: Unsupported script 'klingon'");
+
+    assert!(shlesha
+        .transliterate_segments([("a", None)], "klingon")
+        .is_err());
+}
+
+#[test]
+fn test_segments_apply_word_overrides_and_count_each_segment() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .add_word_override("iast", "devanagari", "kṛṣṇa", "कृष्ण")
+        .unwrap();
+
+    let output = shlesha
+        .transliterate_segments(
+            [
+                ("kṛṣṇa rāma", Some("iast")),
+                (" | ", None),
+                ("rāma", Some("iast")),
+            ],
+            "devanagari",
+        )
+        .unwrap();
+    assert_eq!(output, "कृष्ण राम | राम");
+    #[cfg(feature = "stats")]
+    assert_eq!(shlesha.stats().conversions, 2);
+}
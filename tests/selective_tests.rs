@@ -0,0 +1,107 @@
+use shlesha::modules::selective::words;
+use shlesha::{SelectivePolicy, Shlesha, TransliterationOptions};
+
+fn selective(text: &str, from: &str, to: &str, policy: SelectivePolicy) -> String {
+    Shlesha::new()
+        .transliterate_with_options(text, from, to, &TransliterationOptions::selective(policy))
+        .unwrap()
+}
+
+#[test]
+fn test_english_survives_and_diacritic_words_convert() {
+    assert_eq!(
+        selective(
+            "The dharmaḥ of a kṣatriya is to fight, says Kṛṣṇa.",
+            "iast",
+            "devanagari",
+            SelectivePolicy::new()
+        ),
+        // Capitalized Kṛṣṇa is not IAST as written, so it is left alone
+        "The धर्मः of a क्षत्रिय is to fight, says Kṛṣṇa."
+    );
+    assert_eq!(
+        selective(
+            "In the Gītā: dharmakṣetre kurukṣetre samavetā yuyutsavaḥ, etc.",
+            "iast",
+            "devanagari",
+            SelectivePolicy::new()
+        ),
+        "In the Gītā: धर्मक्षेत्रे कुरुक्षेत्रे समवेता युयुत्सवः, etc."
+    );
+}
+
+#[test]
+fn test_word_list_converts_ascii_words() {
+    let policy = SelectivePolicy::new().with_words(["dharma", "karma"]);
+    assert_eq!(
+        selective(
+            "the word dharma means duty, karma means action, and karmas are many",
+            "iast",
+            "devanagari",
+            policy
+        ),
+        "the word धर्म means duty, कर्म means action, and karmas are many"
+    );
+
+    let policy = SelectivePolicy::from_word_list("# SLP1 words\nyoga\n\nfzi\n");
+    assert!(policy.contains_word("fzi"));
+    assert_eq!(
+        selective("a fzi teaches yoga daily", "slp1", "iast", policy),
+        "a ṛṣi teaches yoga daily"
+    );
+}
+
+#[test]
+fn test_spans_adjacent_to_punctuation() {
+    let policy = SelectivePolicy::new();
+    let cases = [
+        ("(dharmaḥ)", "(धर्मः)"),
+        ("\"ātmā\"", "\"आत्मा\""),
+        ("end: śivaḥ.", "end: शिवः."),
+        ("ṛṣi,muni", "ऋषि,muni"),
+        ("Bhagavad-gītā", "Bhagavad-गीता"),
+        ("…śāntiḥ…", "…शान्तिः…"),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(
+            selective(input, "iast", "devanagari", policy.clone()),
+            expected,
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn test_word_spans_merge_across_whitespace_only() {
+    let policy = SelectivePolicy::new().with_words(["so'ham"]);
+    let text = "so'ham  ātmā, brahma śivaḥ";
+    let spans = policy.spans(text, |word| !word.is_ascii());
+    let spans: Vec<&str> = spans.into_iter().map(|span| &text[span]).collect();
+    assert_eq!(spans, ["so'ham  ātmā", "śivaḥ"]);
+
+    let text = "it's ṛṣi' 'x";
+    let found: Vec<&str> = words(text).into_iter().map(|word| &text[word]).collect();
+    assert_eq!(found, ["it's", "ṛṣi", "x"]);
+}
+
+#[test]
+fn test_selective_needs_roman_source() {
+    let options = TransliterationOptions::selective(SelectivePolicy::new());
+    let shlesha = Shlesha::new();
+    assert!(shlesha
+        .transliterate_with_options("धर्म", "devanagari", "iast", &options)
+        .is_err());
+
+    // Without a policy every word converts
+    assert_eq!(
+        shlesha
+            .transliterate_with_options(
+                "dharma",
+                "iast",
+                "devanagari",
+                &TransliterationOptions::default()
+            )
+            .unwrap(),
+        "धर्म"
+    );
+}
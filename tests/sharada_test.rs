@@ -1,7 +1,23 @@
+mod common;
+
 #[cfg(test)]
 mod sharada_tests {
+    use crate::common::syllabary;
     use shlesha::Shlesha;
 
+    const SIGNS: &[&str] = &[
+        "", "ा", "ि", "ी", "ु", "ू", "ृ", "ॄ", "ॢ", "ॣ", "े", "ै", "ो", "ौ", "्", "ं", "ः",
+    ];
+
+    const CONSONANTS: &[&str] = &[
+        "क", "ख", "ग", "घ", "ङ", "च", "छ", "ज", "झ", "ञ", "ट", "ठ", "ड", "ढ", "ण", "त", "थ", "द",
+        "ध", "न", "प", "फ", "ब", "भ", "म", "य", "र", "ल", "व", "श", "ष", "स", "ह",
+    ];
+
+    const VOWELS: &[&str] = &[
+        "अ", "आ", "इ", "ई", "उ", "ऊ", "ऋ", "ॠ", "ऌ", "ॡ", "ए", "ऐ", "ओ", "औ",
+    ];
+
     #[test]
     fn test_sharada_basic() {
         let transliterator = Shlesha::new();
@@ -38,11 +54,7 @@ mod sharada_tests {
         let result = transliterator
             .transliterate("namaḥ", "iso15919", "sharada")
             .unwrap();
-        // Direct ISO to Sharada conversion currently has issues
-        // The result should be "𑆤𑆩𑆂" but direct conversion produces "?????"
-        // This is likely due to the hub-and-spoke architecture limitations
-        // For now, we'll just check that we get some output
-        assert!(!result.is_empty());
+        assert_eq!(result, "𑆤𑆩𑆂");
 
         // Test with accent via Devanagari hub
         let deva = transliterator
@@ -68,4 +80,40 @@ mod sharada_tests {
             .unwrap();
         assert_eq!(back, test_text);
     }
+
+    #[test]
+    fn test_sharada_syllabary_roundtrip() {
+        let transliterator = Shlesha::new();
+
+        for line in syllabary(CONSONANTS, SIGNS, VOWELS) {
+            let result = transliterator
+                .transliterate_with_metadata(&line, "devanagari", "sharada")
+                .unwrap();
+            assert!(
+                result.metadata.unwrap().unknown_tokens.is_empty(),
+                "{line} has unmapped tokens in sharada: {}",
+                result.output
+            );
+            let back = transliterator
+                .transliterate(&result.output, "sharada", "devanagari")
+                .unwrap();
+            assert_eq!(back, line, "Round-trip via sharada {}", result.output);
+        }
+    }
+
+    #[test]
+    fn test_sharada_vowel_signs_from_iast() {
+        let transliterator = Shlesha::new();
+
+        // Long ā and i signs and the dental/retroflex series
+        let result = transliterator
+            .transliterate("siddhaṁ śāradā kṛṣṇa", "iast", "sharada")
+            .unwrap();
+        assert_eq!(result, "𑆱𑆴𑆢𑇀𑆣𑆁 𑆯𑆳𑆫𑆢𑆳 𑆑𑆸𑆰𑇀𑆟");
+
+        let result = transliterator
+            .transliterate("kḷ kḹ", "iast", "sharada")
+            .unwrap();
+        assert_eq!(result, "𑆑𑆺 𑆑𑆻");
+    }
 }
@@ -0,0 +1,83 @@
+mod common;
+
+#[cfg(test)]
+mod siddham_tests {
+    use crate::common::syllabary;
+    use shlesha::Shlesha;
+
+    const SIGNS: &[&str] = &[
+        "", "ा", "ि", "ी", "ु", "ू", "ृ", "ॄ", "े", "ै", "ो", "ौ", "्", "ं", "ः",
+    ];
+
+    const CONSONANTS: &[&str] = &[
+        "क", "ख", "ग", "घ", "ङ", "च", "छ", "ज", "झ", "ञ", "ट", "ठ", "ड", "ढ", "ण", "त", "थ", "द",
+        "ध", "न", "प", "फ", "ब", "भ", "म", "य", "र", "ल", "व", "श", "ष", "स", "ह",
+    ];
+
+    const VOWELS: &[&str] = &[
+        "अ", "आ", "इ", "ई", "उ", "ऊ", "ऋ", "ॠ", "ऌ", "ॡ", "ए", "ऐ", "ओ", "औ",
+    ];
+
+    #[test]
+    fn test_siddham_vowel_signs_from_iast() {
+        let transliterator = Shlesha::new();
+
+        // Long ā and i signs and the dental/retroflex series
+        let result = transliterator
+            .transliterate("siddhaṁ śāradā kṛṣṇa", "iast", "siddham")
+            .unwrap();
+        assert_eq!(result, "𑖭𑖰𑖟𑖿𑖠𑖽 𑖫𑖯𑖨𑖟𑖯 𑖎𑖴𑖬𑖿𑖜");
+
+        let back = transliterator
+            .transliterate(&result, "siddham", "iast")
+            .unwrap();
+        assert_eq!(back, "siddhaṁ śāradā kṛṣṇa");
+    }
+
+    #[test]
+    fn test_siddham_syllabary_roundtrip() {
+        let transliterator = Shlesha::new();
+
+        for line in syllabary(CONSONANTS, SIGNS, VOWELS) {
+            let result = transliterator
+                .transliterate_with_metadata(&line, "devanagari", "siddham")
+                .unwrap();
+            assert!(
+                result.metadata.unwrap().unknown_tokens.is_empty(),
+                "{line} has unmapped tokens in siddham: {}",
+                result.output
+            );
+            let back = transliterator
+                .transliterate(&result.output, "siddham", "devanagari")
+                .unwrap();
+            assert_eq!(back, line, "Round-trip via siddham {}", result.output);
+        }
+    }
+
+    #[test]
+    fn test_siddham_vocalic_l_sign_is_reported() {
+        let transliterator = Shlesha::new();
+
+        // Siddham has no encoded vowel sign for vocalic l - the token is kept visibly
+        // and reported instead of being written as an unassigned code point
+        let result = transliterator
+            .transliterate_with_metadata("कॢ", "devanagari", "siddham")
+            .unwrap();
+        assert_eq!(result.output, "𑖎[VowelSignL]");
+
+        let metadata = result.metadata.unwrap();
+        assert_eq!(metadata.unknown_tokens.len(), 1);
+        let unknown = &metadata.unknown_tokens[0];
+        assert_eq!(unknown.hub_token.as_deref(), Some("VowelSignL"));
+        assert_eq!(unknown.script, "siddham");
+        assert_eq!(unknown.position, 1);
+        assert_eq!(unknown.token, char::REPLACEMENT_CHARACTER);
+        assert_eq!(unknown.format(), "[siddham:VowelSignL]");
+
+        // The independent vowel letters do exist
+        let result = transliterator
+            .transliterate("ऌ ॡ", "devanagari", "siddham")
+            .unwrap();
+        assert_eq!(result, "𑖈 𑖉");
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod sinhala_tests {
+    use shlesha::{Shlesha, SinhalaConjuncts};
+
+    const VERSE: &str = "dharmakṣetre kurukṣetre samavetā yuyutsavaḥ";
+
+    #[test]
+    fn test_sinhala_sanskrit_letters() {
+        let transliterator = Shlesha::new();
+
+        assert_eq!(
+            transliterator
+                .transliterate("a ā i ī u ū ṛ ṝ ḷ ḹ e ai o au", "iast", "sinhala")
+                .unwrap(),
+            "අ ආ ඉ ඊ උ ඌ ඍ ඎ ඏ ඐ ඒ ඓ ඕ ඖ"
+        );
+
+        // Vocalic vowel signs, visarga, anusvara and candrabindu
+        assert_eq!(
+            transliterator
+                .transliterate("ṛṣi kṛṣṇa pitṝn kḷpta duḥkha haṃsa ham̐sa", "iast", "si")
+                .unwrap(),
+            "ඍෂි කෘෂ්ණ පිතෲන් කෟප්ත දුඃඛ හංස හඁස"
+        );
+    }
+
+    #[test]
+    fn test_sinhala_vocalic_r_roundtrip() {
+        let transliterator = Shlesha::new();
+
+        for text in ["r̥ṣi kr̥ṣṇa pitr̥̄n kl̥pta r̥̄", "saṁskr̥tam mr̥tyu"] {
+            let sinhala = transliterator
+                .transliterate(text, "iso15919", "sinhala")
+                .unwrap();
+            assert!(!sinhala.is_ascii(), "{sinhala}");
+            assert_eq!(
+                transliterator
+                    .transliterate(&sinhala, "sinhala", "iso15919")
+                    .unwrap(),
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_sinhala_conjunct_styles() {
+        let mut transliterator = Shlesha::new();
+        assert_eq!(
+            transliterator.sinhala_conjuncts(),
+            SinhalaConjuncts::Explicit
+        );
+
+        // A visible al-lakuna, but rakaransaya for tra
+        let explicit = transliterator
+            .transliterate(VERSE, "iast", "sinhala")
+            .unwrap();
+        assert_eq!(explicit, "ධර්මක්ෂේත්\u{200D}රේ කුරුක්ෂේත්\u{200D}රේ සමවේතා යුයුත්සවඃ");
+
+        transliterator.set_sinhala_conjuncts(SinhalaConjuncts::Touching);
+        let touching = transliterator
+            .transliterate(VERSE, "iast", "sinhala")
+            .unwrap();
+        assert_eq!(
+            touching,
+            "ධර\u{200D}්මක\u{200D}්ෂේත්\u{200D}රේ කුරුක\u{200D}්ෂේත්\u{200D}රේ සමවේතා යුයුත\u{200D}්සවඃ"
+        );
+
+        // Either style reads back the same
+        for sinhala in [&explicit, &touching] {
+            assert_eq!(
+                transliterator
+                    .transliterate(sinhala, "sinhala", "iast")
+                    .unwrap(),
+                VERSE
+            );
+            assert_eq!(
+                transliterator
+                    .transliterate(sinhala, "sinhala", "devanagari")
+                    .unwrap(),
+                "धर्मक्षेत्रे कुरुक्षेत्रे समवेता युयुत्सवः"
+            );
+        }
+
+        // The style also applies with metadata
+        let result = transliterator
+            .transliterate_with_metadata(VERSE, "iast", "sinhala")
+            .unwrap();
+        assert_eq!(result.output, touching);
+    }
+}
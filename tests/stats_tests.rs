@@ -0,0 +1,117 @@
+#![cfg(feature = "stats")]
+
+use shlesha::{SelectivePolicy, Shlesha, TransliterationOptions};
+
+fn pair(from: &str, to: &str) -> (String, String) {
+    (from.to_string(), to.to_string())
+}
+
+#[test]
+fn test_counters_match_conversions() {
+    let shlesha = Shlesha::new();
+
+    let output = shlesha.transliterate("धर्म", "devanagari", "iast").unwrap();
+    // Aliases are counted under the primary name
+    shlesha.transliterate("dharma", "iast", "deva").unwrap();
+    let metadata_output = shlesha
+        .transliterate_with_metadata("dharma", "iast", "devanagari")
+        .unwrap()
+        .output;
+    assert!(shlesha.transliterate("a", "klingon", "iast").is_err());
+    assert!(shlesha
+        .transliterate_with_metadata("a", "iast", "klingon")
+        .is_err());
+
+    let stats = shlesha.stats();
+    assert_eq!(stats.conversions, 3);
+    assert_eq!(stats.errors, 2);
+    assert_eq!(stats.input_bytes, ("धर्म".len() + "dharma".len() * 2) as u64);
+    assert_eq!(
+        stats.output_bytes,
+        (output.len() + metadata_output.len() * 2) as u64
+    );
+    assert_eq!(
+        stats.pairs.into_iter().collect::<Vec<_>>(),
+        [
+            (pair("devanagari", "iast"), 1),
+            (pair("iast", "devanagari"), 2),
+        ]
+    );
+
+    shlesha.reset_stats();
+    assert_eq!(shlesha.stats(), Default::default());
+}
+
+#[test]
+fn test_selective_conversion_counts_once() {
+    let shlesha = Shlesha::new();
+    let options = TransliterationOptions::selective(SelectivePolicy::default());
+
+    shlesha
+        .transliterate_with_options("The word dharmaḥ and karma", "iast", "devanagari", &options)
+        .unwrap();
+    assert_eq!(shlesha.stats().conversions, 1);
+}
+
+#[test]
+fn test_runtime_schema_pairs_are_counted() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(
+            r#"
+metadata:
+  name: "doubled"
+  script_type: "roman"
+  has_implicit_a: false
+target: "iso15919"
+mappings:
+  vowels:
+    "a": "a"
+    "aa": "ā"
+"#,
+            "doubled",
+        )
+        .unwrap();
+
+    shlesha.transliterate("aa", "doubled", "iast").unwrap();
+    assert_eq!(shlesha.stats().pairs[&pair("doubled", "iast")], 1);
+}
+
+#[test]
+fn test_counters_under_concurrent_access() {
+    let shlesha = Shlesha::new();
+    let (threads, rounds) = (8, 250);
+
+    std::thread::scope(|scope| {
+        for thread in 0..threads {
+            let shlesha = &shlesha;
+            scope.spawn(move || {
+                for _ in 0..rounds {
+                    let (from, to) = if thread % 2 == 0 {
+                        ("devanagari", "iast")
+                    } else {
+                        ("iast", "telugu")
+                    };
+                    let text = if from == "iast" {
+                        "dharma"
+                    } else {
+                        "धर्म"
+                    };
+                    shlesha.transliterate(text, from, to).unwrap();
+                    assert!(shlesha.transliterate(text, from, "klingon").is_err());
+                }
+            });
+        }
+    });
+
+    let stats = shlesha.stats();
+    let total = (threads * rounds) as u64;
+    assert_eq!(stats.conversions, total);
+    assert_eq!(stats.errors, total);
+    assert_eq!(stats.pairs[&pair("devanagari", "iast")], total / 2);
+    assert_eq!(stats.pairs[&pair("iast", "telugu")], total / 2);
+    assert_eq!(
+        stats.input_bytes,
+        total / 2 * ("धर्म".len() + "dharma".len()) as u64
+    );
+}
@@ -0,0 +1,72 @@
+use shlesha::Shlesha;
+
+#[test]
+fn test_telugu_signs_round_trip_with_devanagari() {
+    let shlesha = Shlesha::new();
+
+    for (devanagari, telugu) in [
+        ("सोऽहम्", "సోఽహమ్"),
+        ("चाँद", "చాఁద"),
+        ("कॢप्त", "కౢప్త"),
+        ("कॣ", "కౣ"),
+        ("ऱ", "ఱ"),
+        ("यꣳ सꣴ", "యꣳ సꣴ"),
+        (
+            "अ॒ग्निमी॑ळे पु॒रोहि॑तं य॒ज्ञस्य॑ दे॒वमृ॒त्विज॑म् ।",
+            "అ॒గ్నిమీ॑ళే పు॒రోహి॑తం య॒జ్ఞస్య॑ దే॒వమృ॒త్విజ॑మ్ ।",
+        ),
+    ] {
+        assert_eq!(
+            shlesha
+                .transliterate(devanagari, "devanagari", "telugu")
+                .unwrap(),
+            telugu
+        );
+        assert_eq!(
+            shlesha
+                .transliterate(telugu, "telugu", "devanagari")
+                .unwrap(),
+            devanagari
+        );
+    }
+}
+
+#[test]
+fn test_nakara_pollu() {
+    let mut shlesha = Shlesha::new();
+
+    // Read as న్ wherever it is written
+    assert_eq!(
+        shlesha.transliterate("వాటిౝ", "telugu", "iast").unwrap(),
+        "vāṭin"
+    );
+    let metadata = shlesha
+        .transliterate_with_metadata("వాటిౝ", "telugu", "devanagari")
+        .unwrap()
+        .metadata
+        .unwrap();
+    assert_eq!(metadata.normalized_spans.len(), 1);
+    assert_eq!(metadata.normalized_spans[0].read_as, "న్");
+    assert_eq!(metadata.normalized_spans[0].position, "వాటి".len());
+
+    // Written only when asked, and only at the end of a word
+    assert_eq!(
+        shlesha
+            .transliterate("vāṭin. santi", "iast", "telugu")
+            .unwrap(),
+        "వాటిన్. సన్తి"
+    );
+    shlesha.set_nakara_pollu_output(true);
+    assert_eq!(
+        shlesha
+            .transliterate("vāṭin. santi", "iast", "telugu")
+            .unwrap(),
+        "వాటిౝ. సన్తి"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("वाटिन्", "devanagari", "telugu")
+            .unwrap(),
+        "వాటిౝ"
+    );
+}
@@ -41,10 +41,12 @@ fn test_avagraha_across_scripts() {
         "SLP1 avagraha to IAST should convert to apostrophe"
     );
 
-    // Test IAST to SLP1 with apostrophe
-    let result = shlesha.transliterate("'", "iast", "slp1").unwrap();
+    // Test IAST to SLP1 with apostrophe, which is avagraha only between a vowel and a letter
+    let result = shlesha.transliterate("so'ham", "iast", "slp1").unwrap();
     assert_eq!(
-        result, "`",
+        result, "so`ham",
         "IAST apostrophe should convert to SLP1 backtick"
     );
+    let result = shlesha.transliterate("'", "iast", "slp1").unwrap();
+    assert_eq!(result, "'", "A lone IAST apostrophe is punctuation");
 }
@@ -0,0 +1,96 @@
+//! Stable token IDs, as recorded in build/token_ids.tsv
+
+#[allow(dead_code)]
+#[path = "../build/schema.rs"]
+mod schema;
+
+use shlesha::modules::hub::tokens::{AbugidaToken, AlphabetToken, HubFormat};
+use shlesha::modules::hub::{HubToken, UNKNOWN_STABLE_ID};
+use shlesha::Shlesha;
+
+use schema::parse_token_ids;
+
+const TOKEN_IDS: &str = include_str!("../build/token_ids.tsv");
+
+/// Every variant of the token enums, with its format
+fn every_token() -> Vec<(HubFormat, HubToken)> {
+    let abugida = AbugidaToken::INVENTORY
+        .iter()
+        .flat_map(|(_, names)| names.iter())
+        .map(|name| (HubFormat::Abugida, HubToken::Abugida(name.parse().unwrap())));
+    let alphabet = AlphabetToken::INVENTORY
+        .iter()
+        .flat_map(|(_, names)| names.iter())
+        .map(|name| {
+            (
+                HubFormat::Alphabet,
+                HubToken::Alphabet(name.parse().unwrap()),
+            )
+        });
+    abugida.chain(alphabet).collect()
+}
+
+fn token_name(token: &HubToken) -> String {
+    match token {
+        HubToken::Abugida(token) => token.to_string(),
+        HubToken::Alphabet(token) => token.to_string(),
+    }
+}
+
+#[test]
+fn test_committed_ids_match_the_enums() {
+    let ids = parse_token_ids(TOKEN_IDS).unwrap();
+    let tokens = every_token();
+    for (format, token) in &tokens {
+        let name = token_name(token);
+        let entry = ids
+            .iter()
+            .find(|entry| entry.format == format.key() && entry.name == name)
+            .unwrap_or_else(|| panic!("{name} has no ID in build/token_ids.tsv"));
+        assert_eq!(token.stable_id(), entry.id, "{name}");
+        assert_eq!(HubToken::from_stable_id(entry.id).as_ref(), Some(token));
+    }
+
+    assert_eq!(HubToken::STABLE_IDS.len(), tokens.len());
+    for &(id, format, name) in HubToken::STABLE_IDS {
+        assert!(
+            ids.iter().any(
+                |entry| (entry.id, entry.format.as_str(), entry.name.as_str())
+                    == (id, format.key(), name)
+            ),
+            "{id} {name}"
+        );
+    }
+}
+
+#[test]
+fn test_unknown_text_has_the_reserved_id() {
+    let unknown = HubToken::Abugida(AbugidaToken::Unknown("☺".to_string()));
+    assert_eq!(unknown.stable_id(), UNKNOWN_STABLE_ID);
+    assert_eq!(HubToken::from_stable_id(UNKNOWN_STABLE_ID), None);
+    assert_eq!(HubToken::from_stable_id(u32::MAX), None);
+}
+
+#[test]
+fn test_tokenize_ids() {
+    let shlesha = Shlesha::new();
+    let ids = shlesha.tokenize_ids("धर्म☺", "devanagari").unwrap();
+    let tokens = shlesha.read_tokens("धर्म☺", "devanagari").unwrap();
+    assert_eq!(
+        ids,
+        tokens.iter().map(HubToken::stable_id).collect::<Vec<_>>()
+    );
+    assert_eq!(ids.last(), Some(&UNKNOWN_STABLE_ID));
+    assert_eq!(
+        HubToken::from_stable_id(ids[0]),
+        Some(HubToken::Abugida(AbugidaToken::ConsonantDdh))
+    );
+
+    // The same letters read from a Roman script have the alphabet's IDs
+    let roman = shlesha.tokenize_ids("dharma", "iast").unwrap();
+    assert_eq!(
+        HubToken::from_stable_id(roman[0]),
+        Some(HubToken::Alphabet(AlphabetToken::ConsonantDdh))
+    );
+    assert!(shlesha.tokenize_ids("dharma", "no_such_script").is_err());
+}
@@ -0,0 +1,169 @@
+//! Schemas read from TOML, as older tooling writes them
+
+use shlesha::modules::registry::{RegistryError, SchemaRegistry, SchemaRegistryTrait};
+use shlesha::Shlesha;
+use std::fs;
+
+const YAML_SCHEMA: &str = r#"
+metadata:
+  name: "rounded"
+  script_type: "brahmic"
+  has_implicit_a: true
+target: "abugida_tokens"
+mappings:
+  vowels:
+    VowelA: "अ"
+    VowelAa: "आ"
+    VowelI: "इ"
+  vowel_signs:
+    VowelSignAa: "ा"
+    VowelSignI: "ि"
+  consonants:
+    ConsonantK: "क"
+    ConsonantR: "र"
+    ConsonantM: "म"
+    ConsonantSs: "ष"
+  marks:
+    MarkVirama: "्"
+    MarkAnusvara: "ं"
+  special:
+    MarkAvagraha: "ऽ"
+  digits:
+    Digit1: "१"
+    Digit2: "२"
+"#;
+
+/// YAML_SCHEMA in the layout of older tooling
+const TOML_SCHEMA: &str = r#"
+target = "abugida_tokens"
+
+[metadata]
+name = "rounded"
+script_type = "brahmic"
+has_implicit_a = true
+
+[mappings]
+ConsonantSs = "ष"
+
+[mappings.vowels]
+VowelA = "अ"
+VowelAa = "आ"
+VowelI = "इ"
+
+[mappings.vowel_marks]
+VowelSignAa = "ा"
+VowelSignI = "ि"
+
+[mappings.consonants]
+ConsonantK = "क"
+ConsonantR = "र"
+ConsonantM = "म"
+
+[mappings.punctuation]
+MarkAvagraha = "ऽ"
+
+[mappings.special_marks]
+MarkVirama = "्"
+MarkAnusvara = "ं"
+
+[mappings.digits]
+Digit1 = "१"
+Digit2 = "२"
+"#;
+
+#[test]
+fn test_toml_and_yaml_schemas_convert_alike() {
+    let dir = tempfile::tempdir().unwrap();
+    let yaml_path = dir.path().join("rounded.yaml");
+    let toml_path = dir.path().join("rounded.toml");
+    fs::write(&yaml_path, YAML_SCHEMA).unwrap();
+    fs::write(&toml_path, TOML_SCHEMA).unwrap();
+
+    let mut from_yaml = Shlesha::new();
+    from_yaml
+        .load_schema_from_file(yaml_path.to_str().unwrap())
+        .unwrap();
+    let mut from_toml = Shlesha::new();
+    from_toml
+        .load_schema_from_file(toml_path.to_str().unwrap())
+        .unwrap();
+    let mut from_toml_string = Shlesha::new();
+    from_toml_string
+        .load_schema_from_toml_string(TOML_SCHEMA, "rounded")
+        .unwrap();
+
+    for (input, from, to) in [
+        ("कर्म किं रामऽ १२", "rounded", "iast"),
+        ("karma kiṃ rāma'ṣi 12", "iast", "rounded"),
+        ("कर्षा", "rounded", "devanagari"),
+    ] {
+        let expected = from_yaml.transliterate(input, from, to).unwrap();
+        assert_eq!(from_toml.transliterate(input, from, to).unwrap(), expected);
+        assert_eq!(
+            from_toml_string.transliterate(input, from, to).unwrap(),
+            expected
+        );
+    }
+    assert_eq!(
+        from_toml
+            .transliterate("कर्म किं", "rounded", "iast")
+            .unwrap(),
+        "karma kiṁ"
+    );
+
+    // Directories pick up TOML schemas alongside YAML ones
+    fs::remove_file(&yaml_path).unwrap();
+    let mut registry = SchemaRegistry::new();
+    let report = registry
+        .load_schemas_from_directory(dir.path().to_str().unwrap())
+        .unwrap();
+    assert_eq!(report.loaded, 1);
+    assert!(report.failures.is_empty());
+    assert_eq!(
+        registry.get_schema("rounded").unwrap().mappings,
+        SchemaRegistry::parse_schema(YAML_SCHEMA, "rounded")
+            .unwrap()
+            .mappings
+    );
+}
+
+#[test]
+fn test_malformed_toml_names_file_and_line() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("broken.toml");
+    fs::write(
+        &path,
+        "[metadata]\nname = \"broken\"\nscript_type = roman\nhas_implicit_a = false\n",
+    )
+    .unwrap();
+
+    let mut registry = SchemaRegistry::new();
+    let error = registry.load_schema(path.to_str().unwrap()).unwrap_err();
+    match &error {
+        RegistryError::ParseError {
+            origin,
+            line,
+            snippet,
+            ..
+        } => {
+            assert_eq!(origin, &path.display().to_string());
+            assert_eq!(*line, Some(3));
+            assert_eq!(snippet.as_deref(), Some("script_type = roman"));
+        }
+        other => panic!("Expected ParseError, got {other:?}"),
+    }
+    let message = error.to_string();
+    assert!(message.contains(&format!("{} at line 3, column 15", path.display())));
+
+    // A table of the wrong type is reported where it is
+    let error = registry
+        .load_schema_from_toml_string(
+            "[metadata]\nname = \"x\"\nscript_type = \"roman\"\nhas_implicit_a = false\n\n[mappings]\nvowels = 3\n",
+            "x",
+        )
+        .unwrap_err();
+    assert!(
+        matches!(error, RegistryError::ParseError { ref origin, line: Some(7), .. } if origin == "x"),
+        "{error:?}"
+    );
+}
@@ -0,0 +1,191 @@
+//! Spans and warnings emitted with the `tracing` feature
+//!
+//! Run with `cargo test --features tracing --test tracing_tests`.
+#![cfg(feature = "tracing")]
+
+use shlesha::Shlesha;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// A span or event with its fields, formatted with `Debug`
+#[derive(Debug, Clone, Default)]
+struct Recorded {
+    name: String,
+    fields: HashMap<String, String>,
+}
+
+impl Visit for Recorded {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.fields
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields
+            .insert(field.name().to_string(), value.to_string());
+    }
+}
+
+/// Layer keeping every span, in the order they were opened, and every warning
+#[derive(Clone, Default)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<(Id, Recorded)>>>,
+    warnings: Arc<Mutex<Vec<Recorded>>>,
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Recorder {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        let mut span = Recorded {
+            name: attrs.metadata().name().to_string(),
+            ..Default::default()
+        };
+        attrs.record(&mut span);
+        self.spans.lock().unwrap().push((id.clone(), span));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        let mut spans = self.spans.lock().unwrap();
+        if let Some((_, span)) = spans.iter_mut().find(|(span_id, _)| span_id == id) {
+            values.record(span);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if *event.metadata().level() == Level::WARN {
+            let mut warning = Recorded::default();
+            event.record(&mut warning);
+            self.warnings.lock().unwrap().push(warning);
+        }
+    }
+}
+
+impl Recorder {
+    fn spans(&self) -> Vec<Recorded> {
+        let spans = self.spans.lock().unwrap();
+        spans.iter().map(|(_, span)| span.clone()).collect()
+    }
+
+    fn span(&self, name: &str) -> Recorded {
+        self.spans()
+            .into_iter()
+            .find(|span| span.name == name)
+            .unwrap_or_else(|| panic!("no {name} span"))
+    }
+}
+
+/// Run `f` with a recorder as the thread's subscriber
+fn record<T>(f: impl FnOnce() -> T) -> (Recorder, T) {
+    let recorder = Recorder::default();
+    let subscriber = tracing_subscriber::registry().with(recorder.clone());
+    let result = tracing::subscriber::with_default(subscriber, f);
+    (recorder, result)
+}
+
+#[test]
+fn test_conversion_stages_emit_spans() {
+    let shlesha = Shlesha::new();
+    let (recorder, output) = record(|| shlesha.transliterate("धर्म", "deva", "telugu").unwrap());
+    assert_eq!(output, "ధర్మ");
+
+    // Telugu is an abugida like Devanagari, so there is no hub conversion
+    let names: Vec<_> = recorder.spans().into_iter().map(|s| s.name).collect();
+    for stage in [
+        "transliterate",
+        "optimization_cache",
+        "resolve_script",
+        "to_hub",
+        "from_hub",
+    ] {
+        assert!(names.contains(&stage.to_string()), "{stage} in {names:?}");
+    }
+    assert!(!names.contains(&"hub_conversion".to_string()));
+
+    let top = recorder.span("transliterate");
+    assert_eq!(top.fields["from"], "deva");
+    assert_eq!(top.fields["to"], "telugu");
+    assert_eq!(top.fields["input_len"], "12");
+    assert_eq!(top.fields["output_len"], output.len().to_string());
+
+    assert_eq!(recorder.span("optimization_cache").fields["hit"], "false");
+    let resolve = recorder.span("resolve_script");
+    assert_eq!(resolve.fields["script"], "deva");
+    assert_eq!(resolve.fields["resolved"], "devanagari");
+
+    let to_hub = recorder.span("to_hub");
+    assert_eq!(to_hub.fields["script"], "deva");
+    assert_eq!(to_hub.fields["token_count"], "4");
+
+    // Every stage records how long it took
+    for span in recorder.spans() {
+        assert!(
+            span.fields.contains_key("duration_us"),
+            "{} has no duration",
+            span.name
+        );
+    }
+}
+
+#[test]
+fn test_hub_conversion_span_counts_tokens() {
+    let mut shlesha = Shlesha::new();
+    shlesha.set_force_hub(true);
+    let (recorder, _) = record(|| shlesha.transliterate("धर्म", "devanagari", "iast").unwrap());
+
+    let hub = recorder.span("hub_conversion");
+    assert_eq!(hub.fields["direction"], "abugida_to_alphabet");
+    assert_eq!(hub.fields["token_count"], "4");
+    // dh a r m a: the implicit vowels are written out
+    assert_eq!(hub.fields["output_token_count"], "5");
+
+    let (recorder, _) = record(|| {
+        shlesha
+            .transliterate_with_metadata("dharma", "iast", "devanagari")
+            .unwrap()
+    });
+    let names: Vec<_> = recorder.spans().into_iter().map(|s| s.name).collect();
+    assert_eq!(
+        names
+            .iter()
+            .filter(|name| !name.starts_with("resolve"))
+            .collect::<Vec<_>>(),
+        [
+            "transliterate_with_metadata",
+            "to_hub",
+            "hub_conversion",
+            "from_hub"
+        ]
+    );
+}
+
+#[test]
+fn test_failures_warn_with_input_sample() {
+    let mut shlesha = Shlesha::new();
+    let long_input = "क".repeat(40);
+
+    let (recorder, result) = record(|| shlesha.transliterate(&long_input, "devanagari", "klingon"));
+    assert!(result.is_err());
+    let warnings = recorder.warnings.lock().unwrap().clone();
+    assert_eq!(warnings.len(), 1);
+    let warning = &warnings[0];
+    assert_eq!(warning.fields["from"], "devanagari");
+    assert_eq!(warning.fields["to"], "klingon");
+    assert_eq!(warning.fields["input_len"], long_input.len().to_string());
+    assert_eq!(
+        warning.fields["input_sample"],
+        format!("{}…", "क".repeat(32))
+    );
+    assert!(warning.fields.contains_key("error"));
+
+    shlesha.set_redact_trace_input(true);
+    let (recorder, _) =
+        record(|| shlesha.transliterate_with_metadata(&long_input, "devanagari", "klingon"));
+    let warnings = recorder.warnings.lock().unwrap().clone();
+    assert_eq!(warnings[0].fields["input_sample"], "<redacted>");
+}
@@ -0,0 +1,78 @@
+//! `Shlesha` behind the `Transliterator` trait object
+
+use shlesha::{Shlesha, ShleshaError, Transliterator};
+use std::sync::Arc;
+
+fn injected() -> Arc<dyn Transliterator> {
+    Arc::new(Shlesha::new())
+}
+
+#[test]
+fn test_trait_object_converts_as_shlesha_does() {
+    let transliterator = injected();
+    let shlesha = Shlesha::new();
+    for (text, from, to) in [
+        ("धर्मक्षेत्रे", "devanagari", "iast"),
+        ("dharmakṣetre", "iast", "telugu"),
+        ("kRSNa", "harvard_kyoto", "devanagari"),
+    ] {
+        assert_eq!(
+            transliterator.transliterate(text, from, to).unwrap(),
+            shlesha.transliterate(text, from, to).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_supports_matches_supports_script() {
+    let transliterator = injected();
+    assert!(transliterator.supports("devanagari"));
+    assert!(transliterator.supports("iast"));
+    assert!(!transliterator.supports("klingon"));
+}
+
+#[test]
+fn test_unsupported_script_names_the_script() {
+    let transliterator = injected();
+    match transliterator.transliterate("dharma", "iast", "klingon") {
+        Err(ShleshaError::UnsupportedScript(script)) => assert_eq!(script, "klingon"),
+        other => panic!("expected an unsupported script error, got {other:?}"),
+    }
+    match transliterator.transliterate("dharma", "klingon", "iast") {
+        Err(ShleshaError::UnsupportedScript(script)) => assert_eq!(script, "klingon"),
+        other => panic!("expected an unsupported script error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_trait_object_is_shared_between_threads() {
+    let transliterator = injected();
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let transliterator = Arc::clone(&transliterator);
+            std::thread::spawn(move || {
+                transliterator
+                    .transliterate("धर्म", "devanagari", "iast")
+                    .unwrap()
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), "dharma");
+    }
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_noop_transliterator_returns_its_input() {
+    use shlesha::NoopTransliterator;
+
+    let transliterator: Arc<dyn Transliterator> = Arc::new(NoopTransliterator);
+    assert_eq!(
+        transliterator
+            .transliterate("धर्म", "devanagari", "iast")
+            .unwrap(),
+        "धर्म"
+    );
+    assert!(transliterator.supports("klingon"));
+}
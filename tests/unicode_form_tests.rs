@@ -0,0 +1,118 @@
+use shlesha::{Shlesha, TransliterationOptions, UnicodeForm};
+
+/// Devanagari letters for "zarā", with ज़ written as ज + ़
+const DECOMPOSED_SCHEMA: &str = r#"
+metadata:
+  name: "nagari_plain"
+  script_type: "brahmic"
+  has_implicit_a: true
+target: "abugida_tokens"
+mappings:
+  vowels:
+    VowelA: "अ"
+  vowel_signs:
+    VowelSignAa: "ा"
+  consonants:
+    ConsonantJ: "ज"
+    ConsonantR: "र"
+    ConsonantZa: "ज़"
+  marks:
+    MarkVirama: "्"
+"#;
+
+fn with_form(unicode_form: UnicodeForm) -> TransliterationOptions {
+    TransliterationOptions {
+        unicode_form,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_nukta_letters_are_precomposed_on_every_path() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(DECOMPOSED_SCHEMA, "nagari_plain")
+        .unwrap();
+
+    let expected = "\u{095B}\u{0930}\u{093E}";
+    assert_eq!(
+        shlesha.transliterate("zarā", "iast", "devanagari").unwrap(),
+        expected
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("zarā", "iast", "nagari_plain")
+            .unwrap(),
+        expected
+    );
+    assert_eq!(
+        shlesha
+            .transliterate_with_metadata("zarā", "iast", "nagari_plain")
+            .unwrap()
+            .output,
+        expected
+    );
+
+    // Devanagari input in either form comes out the same
+    for input in ["\u{095B}रा", "\u{091C}\u{093C}रा"] {
+        assert_eq!(
+            shlesha
+                .transliterate(input, "devanagari", "gurmukhi")
+                .unwrap(),
+            "\u{0A5B}ਰਾ"
+        );
+    }
+}
+
+#[test]
+fn test_unicode_form_option() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .load_schema_from_string(DECOMPOSED_SCHEMA, "nagari_plain")
+        .unwrap();
+
+    // Unicode excludes the nukta letters from composition, so NFC and NFD agree
+    for form in [UnicodeForm::Nfc, UnicodeForm::Nfd] {
+        for target in ["devanagari", "nagari_plain"] {
+            let options = with_form(form);
+            assert_eq!(
+                shlesha
+                    .transliterate_with_options("zarā", "iast", target, &options)
+                    .unwrap(),
+                "\u{091C}\u{093C}\u{0930}\u{093E}",
+                "{form:?} {target}"
+            );
+            assert_eq!(
+                shlesha
+                    .transliterate_with_options_and_metadata("zarā", "iast", target, &options)
+                    .unwrap()
+                    .output,
+                "\u{091C}\u{093C}\u{0930}\u{093E}",
+                "{form:?} {target}"
+            );
+        }
+    }
+
+    // NFD also splits vowel signs with a decomposition; NFC keeps them whole
+    let bengali = "\u{0995}\u{09CC}"; // কৌ
+    assert_eq!(
+        shlesha
+            .transliterate_with_options("kau", "iast", "bengali", &with_form(UnicodeForm::Nfc))
+            .unwrap(),
+        bengali
+    );
+    assert_eq!(
+        shlesha
+            .transliterate_with_options("kau", "iast", "bengali", &with_form(UnicodeForm::Nfd))
+            .unwrap(),
+        "\u{0995}\u{09C7}\u{09D7}"
+    );
+
+    // Roman output is left as written
+    assert_eq!(
+        shlesha
+            .transliterate_with_options("कृपा", "devanagari", "iast", &with_form(UnicodeForm::Nfd))
+            .unwrap(),
+        "kṛpā"
+    );
+}
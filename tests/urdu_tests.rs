@@ -0,0 +1,123 @@
+//! Hindustani between Devanagari and Urdu
+//!
+//! Urdu leaves out the short vowels that Devanagari writes, so it is written
+//! without harakat by default and read with the inherent a where no vowel
+//! letter follows a consonant.
+
+use shlesha::{Shlesha, TextDirection, TransliterationOptions};
+
+/// Hindi words with their Urdu spellings, which read back as written; ड़ is
+/// the precomposed U+095C
+const WORDS: &[(&str, &str)] = &[
+    ("पानी", "پانی"),
+    ("ल\u{95C}का", "لڑکا"),
+    ("है", "ہے"),
+    ("गया", "گیا"),
+    ("सवाल", "سوال"),
+    ("गए", "گئے"),
+];
+
+#[test]
+fn test_words_convert_both_ways() {
+    let shlesha = Shlesha::new();
+    for (devanagari, urdu) in WORDS {
+        assert_eq!(
+            shlesha
+                .transliterate(devanagari, "devanagari", "urdu")
+                .unwrap(),
+            *urdu
+        );
+        assert_eq!(
+            shlesha.transliterate(urdu, "urdu", "devanagari").unwrap(),
+            *devanagari
+        );
+    }
+    // ड़ typed as ड with a separate nukta
+    assert_eq!(
+        shlesha
+            .transliterate("लड\u{93C}का", "devanagari", "ur")
+            .unwrap(),
+        "لڑکا"
+    );
+}
+
+#[test]
+fn test_short_vowels_are_not_recovered() {
+    let shlesha = Shlesha::new();
+
+    // Without harakat, کتاب has no i to read and अरदो no u
+    assert_eq!(
+        shlesha.transliterate("उर्दू", "devanagari", "urdu").unwrap(),
+        "اردو"
+    );
+    assert_eq!(
+        shlesha.transliterate("کتاب", "urdu", "devanagari").unwrap(),
+        "कताब"
+    );
+}
+
+#[test]
+fn test_harakat_output() {
+    let mut shlesha = Shlesha::new();
+    assert!(!shlesha.urdu_harakat_output());
+    assert_eq!(
+        shlesha.transliterate("पानी", "devanagari", "urdu").unwrap(),
+        "پانی"
+    );
+
+    shlesha.set_urdu_harakat_output(true);
+    assert!(shlesha.urdu_harakat_output());
+    for (devanagari, urdu) in [
+        ("पानी", "پانِی"),
+        ("है", "ہَے"),
+        ("उर्दू", "اُرْدُو"),
+        ("क्या", "کْیا"),
+    ] {
+        assert_eq!(
+            shlesha
+                .transliterate(devanagari, "devanagari", "urdu")
+                .unwrap(),
+            urdu
+        );
+    }
+    // Input with harakat reads as it does without them
+    assert_eq!(
+        shlesha
+            .transliterate("پانِی ہَے", "urdu", "devanagari")
+            .unwrap(),
+        "पानी है"
+    );
+}
+
+#[test]
+fn test_merges_make_the_pair_lossy() {
+    let shlesha = Shlesha::new();
+
+    let result = shlesha
+        .transliterate_with_metadata("गंगा", "devanagari", "urdu")
+        .unwrap();
+    assert_eq!(result.output, "گنگا");
+    let capability = shlesha.pair_capability("devanagari", "urdu");
+    assert!(capability.supported);
+    assert!(capability.lossy);
+    assert!(capability
+        .notes
+        .contains(&"ConsonantSs is written as ConsonantSh".to_string()));
+}
+
+#[test]
+fn test_urdu_is_right_to_left() {
+    let shlesha = Shlesha::new();
+    assert_eq!(shlesha.script_direction("urdu"), TextDirection::Rtl);
+
+    let options = TransliterationOptions {
+        wrap_directional_isolates: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        shlesha
+            .transliterate_with_options("पानी", "devanagari", "urdu", &options)
+            .unwrap(),
+        "\u{2068}پانی\u{2069}"
+    );
+}
@@ -0,0 +1,298 @@
+use shlesha::modules::core::unknown_handler::SpanKind;
+use shlesha::modules::hub::{AbugidaToken, AlphabetToken, HubFormat, HubToken};
+use shlesha::Shlesha;
+
+fn vedic() -> Shlesha {
+    let mut shlesha = Shlesha::new();
+    shlesha.set_vedic_mode(true);
+    shlesha
+}
+
+#[test]
+fn test_vedic_samples_roundtrip() {
+    let shlesha = vedic();
+    let samples = [
+        ("अग्ना३इ", "agnā3i", "agnA3i"),
+        ("भो३ अ॒ग्ना३इ", "bho3 a̱gnā3i", "Bo3 a\\\\gnA3i"),
+        ("सꣳहिता", "sagͫhitā", "sa[MarkCandrabinduVirama]hitA"),
+        ("यज्ञꣴ", "yajñaggͫ", "yajYa[MarkDoubleCandrabinduVirama]"),
+    ];
+
+    for (devanagari, iast, slp1) in samples {
+        for (script, roman) in [("iast", iast), ("slp1", slp1)] {
+            let forward = shlesha
+                .transliterate(devanagari, "devanagari", script)
+                .unwrap();
+            assert_eq!(forward, roman, "devanagari → {script}");
+
+            let back = shlesha.transliterate(roman, script, "devanagari").unwrap();
+            assert_eq!(back, devanagari, "{script} → devanagari");
+        }
+    }
+}
+
+/// Jihvāmūlīya before k, upadhmānīya before p, and a plain visarga
+const FRICATIVES: &str = "ततᳵ किम् पुनᳶ पुनः";
+
+#[test]
+fn test_jihvamuliya_and_upadhmaniya_roundtrip() {
+    let shlesha = Shlesha::new();
+    let forms = [
+        ("iso15919", "tataẖ kim punaḫ punaḥ"),
+        ("slp1", "tataZ kim punaV punaH"),
+        ("kannada", "ತತೱ ಕಿಮ್ ಪುನೲ ಪುನಃ"),
+        ("telugu", "తతᳵ కిమ్ పునᳶ పునః"),
+    ];
+
+    for (script, expected) in forms {
+        let forward = shlesha
+            .transliterate(FRICATIVES, "devanagari", script)
+            .unwrap();
+        assert_eq!(forward, expected, "devanagari → {script}");
+        let back = shlesha
+            .transliterate(expected, script, "devanagari")
+            .unwrap();
+        assert_eq!(back, FRICATIVES, "{script} → devanagari");
+    }
+
+    let kannada = shlesha
+        .transliterate("tataẖ kim punaḫ punaḥ", "iso15919", "kannada")
+        .unwrap();
+    assert_eq!(kannada, "ತತೱ ಕಿಮ್ ಪುನೲ ಪುನಃ");
+    assert_eq!(
+        shlesha
+            .transliterate(&kannada, "kannada", "iso15919")
+            .unwrap(),
+        "tataẖ kim punaḫ punaḥ"
+    );
+    // Kannada also reads the Vedic Extensions signs
+    assert_eq!(
+        shlesha
+            .transliterate("ತತᳵ ಕಿಮ್ ಪುನᳶ", "kannada", "iso15919")
+            .unwrap(),
+        "tataẖ kim punaḫ"
+    );
+}
+
+#[test]
+fn test_iast_writes_jihvamuliya_and_upadhmaniya_as_visarga() {
+    let shlesha = Shlesha::new();
+
+    for (text, script) in [(FRICATIVES, "devanagari"), ("ತತೱ ಕಿಮ್ ಪುನೲ ಪುನಃ", "kannada")]
+    {
+        let result = shlesha
+            .transliterate_with_metadata(text, script, "iast")
+            .unwrap();
+        assert_eq!(result.output, "tataḥ kim punaḥ punaḥ", "{script}");
+
+        let metadata = result.metadata.unwrap();
+        assert!(metadata.unknown_tokens.is_empty());
+        let lossy: Vec<_> = metadata
+            .lossy_mappings
+            .iter()
+            .map(|mapping| (mapping.token.as_str(), mapping.substitute.as_str()))
+            .collect();
+        assert_eq!(
+            lossy,
+            [
+                ("MarkJihvamuliya", "MarkVisarga"),
+                ("MarkUpadhmaniya", "MarkVisarga")
+            ],
+            "{script}"
+        );
+    }
+}
+
+#[test]
+fn test_pluta_is_read_only_after_a_vowel() {
+    let shlesha = vedic();
+
+    // Pluta is written with the target script's digit 3
+    assert_eq!(
+        shlesha.transliterate("आ३", "devanagari", "telugu").unwrap(),
+        "ఆ౩"
+    );
+    // Plain numbers are untouched
+    assert_eq!(
+        shlesha.transliterate("१३ ३", "devanagari", "iast").unwrap(),
+        "13 3"
+    );
+
+    let result = shlesha
+        .transliterate_with_metadata("अग्ना३इ", "devanagari", "iso15919")
+        .unwrap();
+    assert_eq!(result.output, "agnā3i");
+    assert!(result.metadata.unwrap().unknown_tokens.is_empty());
+}
+
+#[test]
+fn test_mark_pluta_tokens() {
+    let mut abugida = HubFormat::AbugidaTokens(vec![
+        HubToken::Abugida(AbugidaToken::ConsonantN),
+        HubToken::Abugida(AbugidaToken::VowelSignAa),
+        HubToken::Abugida(AbugidaToken::Digit3),
+        HubToken::Abugida(AbugidaToken::ConsonantK),
+        HubToken::Abugida(AbugidaToken::Digit3),
+        HubToken::Abugida(AbugidaToken::Digit3),
+    ]);
+    abugida.mark_pluta();
+    assert_eq!(
+        abugida,
+        HubFormat::AbugidaTokens(vec![
+            HubToken::Abugida(AbugidaToken::ConsonantN),
+            HubToken::Abugida(AbugidaToken::VowelSignAa),
+            HubToken::Abugida(AbugidaToken::MarkPluta),
+            HubToken::Abugida(AbugidaToken::ConsonantK),
+            // Followed by another digit, so a number
+            HubToken::Abugida(AbugidaToken::Digit3),
+            HubToken::Abugida(AbugidaToken::Digit3),
+        ])
+    );
+
+    // In Roman text only a vowel letter carries the pluta
+    let mut alphabet = HubFormat::AlphabetTokens(vec![
+        HubToken::Alphabet(AlphabetToken::VowelO),
+        HubToken::Alphabet(AlphabetToken::MarkVerticalLineAbove),
+        HubToken::Alphabet(AlphabetToken::Digit3),
+        HubToken::Alphabet(AlphabetToken::ConsonantK),
+        HubToken::Alphabet(AlphabetToken::Digit3),
+    ]);
+    alphabet.mark_pluta();
+    assert_eq!(
+        alphabet,
+        HubFormat::AlphabetTokens(vec![
+            HubToken::Alphabet(AlphabetToken::VowelO),
+            HubToken::Alphabet(AlphabetToken::MarkVerticalLineAbove),
+            HubToken::Alphabet(AlphabetToken::MarkPluta),
+            HubToken::Alphabet(AlphabetToken::ConsonantK),
+            HubToken::Alphabet(AlphabetToken::Digit3),
+        ])
+    );
+}
+
+#[test]
+fn test_vedic_mode_is_off_by_default() {
+    let shlesha = Shlesha::new();
+    assert!(!shlesha.vedic_mode());
+    // The signs themselves are read either way
+    assert_eq!(
+        shlesha
+            .transliterate("ततᳵ किम्", "devanagari", "iso15919")
+            .unwrap(),
+        "tataẖ kim"
+    );
+    assert_eq!(
+        shlesha.transliterate("आ३", "devanagari", "iast").unwrap(),
+        "ā3"
+    );
+}
+
+/// Samavedic svara numerals (U+A8E1 …) and a Vedic Extensions tone mark, one of
+/// them typed before its vowel sign
+const SAMAVEDA: &str = "अ\u{A8E1}ग्न\u{A8E2} आ\u{A8E1}या\u{A8E2}हि र\u{A8E3}ामः कृ\u{1CD6}ष्णं";
+
+#[test]
+fn test_samavedic_marks_stay_with_their_akshara() {
+    let shlesha = Shlesha::new();
+
+    for (script, expected) in [
+        (
+            "telugu",
+            "అ\u{A8E1}గ్న\u{A8E2} ఆ\u{A8E1}యా\u{A8E2}హి రా\u{A8E3}మః కృ\u{1CD6}ష్ణం",
+        ),
+        (
+            "bengali",
+            "অ\u{A8E1}গ্ন\u{A8E2} আ\u{A8E1}যা\u{A8E2}হি রা\u{A8E3}মঃ কৃ\u{1CD6}ষ্ণং",
+        ),
+        (
+            "iast",
+            "a\u{A8E1}gna\u{A8E2} ā\u{A8E1}yā\u{A8E2}hi rā\u{A8E3}maḥ kṛ\u{1CD6}ṣṇaṁ",
+        ),
+        (
+            "slp1",
+            "a\u{A8E1}gna\u{A8E2} A\u{A8E1}yA\u{A8E2}hi rA\u{A8E3}maH kf\u{1CD6}zRaM",
+        ),
+    ] {
+        assert_eq!(
+            shlesha
+                .transliterate(SAMAVEDA, "devanagari", script)
+                .unwrap(),
+            expected,
+            "devanagari → {script}"
+        );
+    }
+
+    // The marks read back in place, each after its whole akshara
+    assert_eq!(
+        shlesha
+            .transliterate("రా\u{A8E3}మః కం\u{1CD6}", "telugu", "devanagari")
+            .unwrap(),
+        "रा\u{A8E3}मः कं\u{1CD6}"
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("ग\u{A8E1}्नि क\u{1CD6}ं", "devanagari", "iast")
+            .unwrap(),
+        "g\u{A8E1}ni kaṁ\u{1CD6}"
+    );
+}
+
+#[test]
+fn test_samavedic_marks_are_noted_not_unknown() {
+    let shlesha = Shlesha::new();
+
+    let result = shlesha
+        .transliterate_with_metadata(SAMAVEDA, "devanagari", "iast")
+        .unwrap();
+    let metadata = result.metadata.unwrap();
+    assert!(metadata.unknown_tokens.is_empty());
+    let marks: Vec<(&str, usize)> = metadata
+        .fixed_spans
+        .iter()
+        .filter(|span| span.kind == SpanKind::AttachedMark)
+        .map(|span| (span.input.as_str(), span.position))
+        .collect();
+    // Positions are where each mark is in the input, including the one read after ा
+    let expected: Vec<(&str, usize)> = [
+        "\u{A8E1}", "\u{A8E2}", "\u{A8E1}", "\u{A8E2}", "\u{A8E3}", "\u{1CD6}",
+    ]
+    .into_iter()
+    .scan(0, |from, mark| {
+        let position = *from + SAMAVEDA[*from..].find(mark).unwrap();
+        *from = position + mark.len();
+        Some((mark, position))
+    })
+    .collect();
+    assert_eq!(marks, expected);
+}
+
+#[test]
+fn test_syllabify_keeps_samavedic_marks() {
+    let shlesha = Shlesha::new();
+
+    let aksharas = shlesha.syllabify(SAMAVEDA, "devanagari").unwrap();
+    let texts: Vec<&str> = aksharas
+        .iter()
+        .map(|akshara| akshara.text.as_str())
+        .collect();
+    assert_eq!(
+        texts,
+        [
+            "अ\u{A8E1}",
+            "ग्न\u{A8E2}",
+            "आ\u{A8E1}",
+            "या\u{A8E2}",
+            "हि",
+            "र\u{A8E3}ा",
+            "मः",
+            "कृ\u{1CD6}",
+            "ष्णं"
+        ]
+    );
+    // The mark typed before ा is still one of the akshara's final marks
+    assert_eq!(aksharas[5].vowel, Some(AbugidaToken::VowelSignAa));
+    assert_eq!(
+        aksharas[5].final_marks,
+        [AbugidaToken::Unknown("\u{A8E3}".to_string())]
+    );
+}
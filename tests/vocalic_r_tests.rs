@@ -0,0 +1,95 @@
+//! ṛ in IAST and ISO-15919
+//!
+//! IAST writes vocalic r with a dot below (ṛ), ISO-15919 with a ring below
+//! (r̥), keeping ṛ for the flap ड़. Converting between the two must translate
+//! the letters, not pass them through.
+
+use shlesha::Shlesha;
+
+#[test]
+fn test_vocalic_r_in_each_convention() {
+    let shlesha = Shlesha::new();
+
+    for (iast, iso, devanagari) in [
+        ("kṛṣṇa", "kr̥ṣṇa", "कृष्ण"),
+        ("ṛṣi", "r̥ṣi", "ऋषि"),
+        ("pitṝn", "pitr̥̄n", "पितॄन्"),
+        ("kḷpta", "kl̥pta", "कॢप्त"),
+    ] {
+        assert_eq!(
+            shlesha.transliterate(iast, "iast", "devanagari").unwrap(),
+            devanagari
+        );
+        assert_eq!(
+            shlesha
+                .transliterate(iso, "iso15919", "devanagari")
+                .unwrap(),
+            devanagari
+        );
+        assert_eq!(
+            shlesha.transliterate(iast, "iast", "iso15919").unwrap(),
+            iso
+        );
+        assert_eq!(
+            shlesha.transliterate(iso, "iso15919", "iast").unwrap(),
+            iast
+        );
+    }
+}
+
+#[test]
+fn test_iso_dot_below_is_the_flap() {
+    let shlesha = Shlesha::new();
+
+    // ड़ and ढ़ come out as the precomposed U+095C and U+095D
+
+    for (iso, devanagari) in [
+        ("laṛkā", "ल\u{95C}का"),
+        ("vaṛā", "व\u{95C}ा"),
+        ("paṛhnā", "प\u{95D}ना"),
+    ] {
+        assert_eq!(
+            shlesha
+                .transliterate(iso, "iso15919", "devanagari")
+                .unwrap(),
+            devanagari
+        );
+    }
+    // Also when the dot below is a separate combining mark
+    assert_eq!(
+        shlesha
+            .transliterate("lar\u{323}kā", "iso15919", "devanagari")
+            .unwrap(),
+        "ल\u{95C}का"
+    );
+    // IAST has no flap of its own and borrows ṙ
+    assert_eq!(
+        shlesha.transliterate("laṛkā", "iso15919", "iast").unwrap(),
+        "laṙkā"
+    );
+    assert_eq!(
+        shlesha.transliterate("laṙkā", "iast", "iso15919").unwrap(),
+        "laṛkā"
+    );
+}
+
+#[test]
+fn test_iast_reads_the_iso_ring() {
+    let shlesha = Shlesha::new();
+
+    // Text that mixes the conventions reads as vocalic r either way
+    assert_eq!(
+        shlesha
+            .transliterate("kr̥ṣṇa kṛṣṇa pitr̥̄n kl̥pta", "iast", "devanagari")
+            .unwrap(),
+        "कृष्ण कृष्ण पितॄन् कॢप्त"
+    );
+    assert_eq!(
+        shlesha.transliterate("kr̥ṣṇa", "iast", "iso15919").unwrap(),
+        "kr̥ṣṇa"
+    );
+    assert_eq!(
+        shlesha.transliterate("kr̥ṣṇa", "iast", "slp1").unwrap(),
+        "kfzRa"
+    );
+}
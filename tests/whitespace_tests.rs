@@ -0,0 +1,82 @@
+//! Whitespace passes through conversion byte for byte
+
+use shlesha::Shlesha;
+
+/// Pairs covering the direct converters, the hub and both token types
+const PAIRS: &[(&str, &str)] = &[
+    ("devanagari", "iast"),
+    ("iast", "telugu"),
+    ("slp1", "bengali"),
+];
+
+/// Inputs as Devanagari, written in each source script before converting
+const SAMPLES: &[&str] = &[
+    "अ  ब",
+    "अ\t\nब",
+    "राम\n\n",
+    "  धर्म  क्षेत्रे\r\n\tकुरु \u{00A0}क्षेत्रे  ",
+];
+
+/// The whitespace between, before and after the words of `text`, byte for byte
+///
+/// Words are compared as runs only, since a word's letters may change in number.
+fn whitespace_runs(text: &str) -> Vec<String> {
+    let mut runs = Vec::new();
+    let mut run = String::new();
+    let mut in_word = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            run.push(c);
+            in_word = false;
+        } else if !in_word {
+            runs.push(std::mem::take(&mut run));
+            in_word = true;
+        }
+    }
+    runs.push(run);
+    runs
+}
+
+#[test]
+fn test_whitespace_is_preserved() {
+    let shlesha = Shlesha::new();
+    for &(from, to) in PAIRS {
+        for sample in SAMPLES {
+            let input = shlesha.transliterate(sample, "devanagari", from).unwrap();
+            assert_eq!(whitespace_runs(&input), whitespace_runs(sample));
+
+            let outputs = [
+                shlesha.transliterate(&input, from, to).unwrap(),
+                shlesha.transliterate_fast(&input, from, to).unwrap(),
+                shlesha
+                    .transliterate_with_metadata(&input, from, to)
+                    .unwrap()
+                    .output,
+            ];
+            for output in outputs {
+                assert_eq!(
+                    whitespace_runs(&output),
+                    whitespace_runs(&input),
+                    "{from} → {to}: {input:?} gave {output:?}"
+                );
+            }
+        }
+    }
+    // Byte for byte where the letters are known
+    assert_eq!(
+        shlesha
+            .transliterate("a  b\t\nka\n\n", "iast", "devanagari")
+            .unwrap(),
+        "अ  ब्\t\nक\n\n"
+    );
+}
+
+#[test]
+fn test_whitespace_only_input() {
+    let shlesha = Shlesha::new();
+    for &(from, to) in PAIRS {
+        for text in [" ", "  \t\n", "\n\n"] {
+            assert_eq!(shlesha.transliterate(text, from, to).unwrap(), text);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Words written a fixed way before conversion
+
+use shlesha::{Shlesha, SpanKind};
+
+#[test]
+fn test_override_replaces_rule_output() {
+    let rules = Shlesha::new();
+    let mut shlesha = Shlesha::new();
+    // The rules write the long ē and a cluster with virama
+    assert_eq!(
+        rules.transliterate("bengaluru", "iast", "kannada").unwrap(),
+        "ಬೇನ್ಗಲುರು"
+    );
+
+    shlesha
+        .add_word_override("iast", "kannada", "bengaluru", "ಬೆಂಗಳೂರು")
+        .unwrap();
+    assert_eq!(
+        shlesha
+            .transliterate("bengaluru nagara", "iast", "kannada")
+            .unwrap(),
+        "ಬೆಂಗಳೂರು ನಗರ"
+    );
+    // Aliases name the same pair
+    assert_eq!(
+        shlesha.transliterate("bengaluru", "iast", "kn").unwrap(),
+        "ಬೆಂಗಳೂರು"
+    );
+    // Other pairs are not affected
+    assert_eq!(
+        shlesha
+            .transliterate("bengaluru", "iast", "telugu")
+            .unwrap(),
+        rules.transliterate("bengaluru", "iast", "telugu").unwrap()
+    );
+
+    let result = shlesha
+        .transliterate_with_metadata("nagara bengaluru.", "iast", "kannada")
+        .unwrap();
+    assert_eq!(result.output, "ನಗರ ಬೆಂಗಳೂರು.");
+    let spans = result.metadata.unwrap().fixed_spans;
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].kind, SpanKind::Override);
+    assert_eq!(spans[0].input, "bengaluru");
+    assert_eq!(spans[0].output, "ಬೆಂಗಳೂರು");
+    assert_eq!(spans[0].position, 7);
+}
+
+#[test]
+fn test_longest_overlapping_override_wins() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .add_word_override("iast", "devanagari", "rāma", "RĀMA")
+        .unwrap();
+    shlesha
+        .add_word_override("iast", "devanagari", "rāma kṛṣṇa", "RĀMAKṚṢṆA")
+        .unwrap();
+
+    assert_eq!(
+        shlesha
+            .transliterate("rāma kṛṣṇa rāma kṛṣṇaḥ", "iast", "devanagari")
+            .unwrap(),
+        "RĀMAKṚṢṆA RĀMA कृष्णः"
+    );
+
+    // Matching is case-sensitive
+    assert_eq!(
+        shlesha.transliterate("Rāma", "iast", "devanagari").unwrap(),
+        Shlesha::new()
+            .transliterate("Rāma", "iast", "devanagari")
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_words_inside_larger_words_are_not_overridden() {
+    let mut shlesha = Shlesha::new();
+    shlesha
+        .add_word_override("iast", "devanagari", "rāma", "RĀMA")
+        .unwrap();
+
+    assert_eq!(
+        shlesha
+            .transliterate("rāmaḥ parāma rāmāyaṇa rāma-rāma", "iast", "devanagari")
+            .unwrap(),
+        "रामः पराम रामायण RĀMA-RĀMA"
+    );
+    let result = shlesha
+        .transliterate_with_metadata("rāmāyaṇa", "iast", "devanagari")
+        .unwrap();
+    assert!(result.metadata.unwrap().fixed_spans.is_empty());
+}
+
+#[test]
+fn test_overrides_load_from_tsv() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("overrides.tsv");
+    std::fs::write(&path, "# Place names\nbengaluru\tಬೆಂಗಳೂರು\n\nmaisūru\tಮೈಸೂರು\n").unwrap();
+
+    let rules = Shlesha::new();
+    let mut shlesha = Shlesha::new();
+    assert_eq!(
+        shlesha
+            .load_word_overrides("iast", "kannada", &path)
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        shlesha
+            .transliterate("maisūru bengaluru", "iast", "kannada")
+            .unwrap(),
+        "ಮೈಸೂರು ಬೆಂಗಳೂರು"
+    );
+
+    std::fs::write(&path, "bengaluru\tಬೆಂಗಳೂರು\nmaisūru\n").unwrap();
+    let error = shlesha
+        .load_word_overrides("iast", "telugu", &path)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Line 2: expected the word and its output separated by a tab"
+    );
+    // Nothing is added from a malformed file
+    assert_eq!(
+        shlesha
+            .transliterate("bengaluru", "iast", "telugu")
+            .unwrap(),
+        rules.transliterate("bengaluru", "iast", "telugu").unwrap()
+    );
+
+    shlesha.clear_word_overrides();
+    assert_eq!(
+        shlesha
+            .transliterate("bengaluru", "iast", "kannada")
+            .unwrap(),
+        rules.transliterate("bengaluru", "iast", "kannada").unwrap()
+    );
+}